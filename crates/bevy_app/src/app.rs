@@ -9,6 +9,7 @@ use bevy_ecs::{
     prelude::*,
     schedule::{ScheduleBuildSettings, ScheduleLabel},
     system::SystemId,
+    world::ResourceDependencies,
 };
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
@@ -225,6 +226,7 @@ impl App {
     /// Runs [`Plugin::finish`] for each plugin. This is usually called by the event loop once all
     /// plugins are ready, but can be useful for situations where you want to use [`App::update`].
     pub fn finish(&mut self) {
+        self.main_mut().initialize_resources();
         // plugins installed to main should see all sub-apps
         let plugins = std::mem::take(&mut self.main_mut().plugins);
         for plugin in &plugins.registry {
@@ -384,6 +386,39 @@ impl App {
         self
     }
 
+    /// Initializes `T` event handling like [`App::add_event`], but without updating the
+    /// [`Events::<T>`] resource automatically.
+    ///
+    /// Events of this type are not dropped after two updates. They are kept until
+    /// [`Events::update`] or [`Events::clear`] is called, for example by the system that reads them.
+    /// This makes sure that systems which do not run every frame do not miss any events.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Event)]
+    /// # struct SaveRequested;
+    /// # let mut app = App::new();
+    /// #
+    /// app.add_manually_cleared_event::<SaveRequested>();
+    ///
+    /// fn save(mut events: ResMut<Events<SaveRequested>>) {
+    ///     for _ in events.drain() {
+    ///         // ...
+    ///     }
+    /// }
+    /// ```
+    pub fn add_manually_cleared_event<T>(&mut self) -> &mut Self
+    where
+        T: Event,
+    {
+        self.main_mut().add_manually_cleared_event::<T>();
+        self
+    }
+
     /// Inserts the [`Resource`] into the app, overwriting any existing resource of the same type.
     ///
     /// There is also an [`init_resource`](Self::init_resource) for resources that have
@@ -442,6 +477,54 @@ impl App {
         self
     }
 
+    /// Inserts the [`Resource`] like [`init_resource`](Self::init_resource), but only once every
+    /// plugin has been built, and after the resources in `D` that were added the same way.
+    ///
+    /// This lets the [`FromWorld`] implementation of `R` read other resources no matter in which
+    /// order the plugins adding them were registered. The resources are initialized at the start of
+    /// [`App::finish`], before [`Plugin::finish`] runs.
+    ///
+    /// # Panics
+    ///
+    /// [`App::finish`] panics if the dependencies of these resources form a cycle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Resource)]
+    /// struct Scale(f32);
+    ///
+    /// impl Default for Scale {
+    ///     fn default() -> Self {
+    ///         Scale(2.0)
+    ///     }
+    /// }
+    ///
+    /// #[derive(Resource)]
+    /// struct Width(f32);
+    ///
+    /// impl FromWorld for Width {
+    ///     fn from_world(world: &mut World) -> Self {
+    ///         Width(100.0 * world.resource::<Scale>().0)
+    ///     }
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.init_resource_after::<Width, (Scale,)>()
+    ///     .init_resource_after::<Scale, ()>();
+    /// app.finish();
+    /// assert_eq!(app.world().resource::<Width>().0, 200.0);
+    /// ```
+    pub fn init_resource_after<R: Resource + FromWorld, D: ResourceDependencies>(
+        &mut self,
+    ) -> &mut Self {
+        self.main_mut().init_resource_after::<R, D>();
+        self
+    }
+
     /// Inserts the [`!Send`](Send) resource into the app, overwriting any existing resource
     /// of the same type.
     ///
@@ -731,6 +814,21 @@ impl App {
         self.main_mut().get_schedule_mut(label)
     }
 
+    /// Writes the [`Schedule`] with the provided `label` as a DOT graph, see [`Schedule::graph_to_dot`].
+    ///
+    /// Returns `None` if the schedule does not exist. Ambiguities are only included once the
+    /// schedule has been initialized, for example after the first [`App::update`].
+    pub fn schedule_graph_to_dot(&self, label: impl ScheduleLabel) -> Option<String> {
+        self.main().schedule_graph_to_dot(label)
+    }
+
+    /// Writes the [`Schedule`] with the provided `label` as JSON, see [`Schedule::graph_to_json`].
+    ///
+    /// Returns `None` if the schedule does not exist.
+    pub fn schedule_graph_to_json(&self, label: impl ScheduleLabel) -> Option<String> {
+        self.main().schedule_graph_to_json(label)
+    }
+
     /// Runs function `f` with the [`Schedule`] associated with `label`.
     ///
     /// **Note:** This will create the schedule if it does not already exist.
@@ -1108,4 +1206,41 @@ mod tests {
             .add_systems(PreUpdate, my_system)
             .run();
     }
+
+    #[test]
+    fn reserved_bundles_are_applied_every_frame() {
+        use crate::ReservedBundlesPlugin;
+        use bevy_ecs::{component::Component, world::ReservedBundles};
+
+        #[derive(Component)]
+        struct A;
+
+        let mut app = App::new();
+        app.add_plugins(ReservedBundlesPlugin);
+        let entity = app.world().entities().reserve_entity();
+        app.world()
+            .resource::<ReservedBundles>()
+            .clone()
+            .insert(entity, A);
+
+        app.update();
+        assert!(app.world().get::<A>(entity).is_some());
+    }
+
+    #[test]
+    #[should_panic = "form a cycle"]
+    fn resource_dependency_cycles_panic_on_finish() {
+        use bevy_ecs::system::Resource;
+
+        #[derive(Resource, Default)]
+        struct A;
+
+        #[derive(Resource, Default)]
+        struct B;
+
+        let mut app = App::new();
+        app.init_resource_after::<A, (B,)>()
+            .init_resource_after::<B, (A,)>();
+        app.finish();
+    }
 }
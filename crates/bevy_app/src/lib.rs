@@ -12,6 +12,7 @@ mod main_schedule;
 mod panic_handler;
 mod plugin;
 mod plugin_group;
+mod reserved_bundles;
 mod schedule_runner;
 mod sub_app;
 
@@ -21,6 +22,7 @@ pub use main_schedule::*;
 pub use panic_handler::*;
 pub use plugin::*;
 pub use plugin_group::*;
+pub use reserved_bundles::*;
 pub use schedule_runner::*;
 pub use sub_app::*;
 
@@ -0,0 +1,18 @@
+use bevy_ecs::world::{apply_reserved_bundles, ReservedBundles};
+
+use crate::{App, First, Plugin};
+
+/// Adds the [`ReservedBundles`] resource and applies it at the start of every frame,
+/// in the [`First`] schedule.
+///
+/// [`apply_reserved_bundles`] is an exclusive system, so this plugin is only worth adding
+/// to apps that insert bundles on reserved entities.
+#[derive(Default)]
+pub struct ReservedBundlesPlugin;
+
+impl Plugin for ReservedBundlesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReservedBundles>()
+            .add_systems(First, apply_reserved_bundles);
+    }
+}
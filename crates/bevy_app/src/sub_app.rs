@@ -7,6 +7,7 @@ use bevy_ecs::{
         InternedScheduleLabel, ScheduleBuildSettings, ScheduleLabel,
     },
     system::SystemId,
+    world::{ResourceDependencies, ResourceInitializer},
 };
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
@@ -77,6 +78,8 @@ pub struct SubApp {
     /// A function that gives mutable access to two app worlds. This is primarily
     /// intended for copying data from the main world to secondary worlds.
     extract: Option<ExtractFn>,
+    /// Resources added with [`init_resource_after`](Self::init_resource_after), initialized by [`finish`](Self::finish).
+    resource_initializer: ResourceInitializer,
 }
 
 impl Debug for SubApp {
@@ -96,6 +99,7 @@ impl Default for SubApp {
             plugins_state: PluginsState::Adding,
             update_schedule: None,
             extract: None,
+            resource_initializer: default(),
         }
     }
 }
@@ -173,6 +177,28 @@ impl SubApp {
         self
     }
 
+    /// See [`App::init_resource_after`].
+    pub fn init_resource_after<R: Resource + FromWorld, D: ResourceDependencies>(
+        &mut self,
+    ) -> &mut Self {
+        self.resource_initializer.add_after::<R, D>();
+        self
+    }
+
+    /// Initializes the resources added with [`init_resource_after`](Self::init_resource_after),
+    /// each one after its dependencies.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dependencies of these resources form a cycle.
+    pub(crate) fn initialize_resources(&mut self) {
+        if let Err(error) =
+            std::mem::take(&mut self.resource_initializer).initialize(&mut self.world)
+        {
+            panic!("{error}");
+        }
+    }
+
     /// See [`App::add_systems`].
     pub fn add_systems<M>(
         &mut self,
@@ -242,6 +268,18 @@ impl SubApp {
         schedules.get(label)
     }
 
+    /// See [`App::schedule_graph_to_dot`].
+    pub fn schedule_graph_to_dot(&self, label: impl ScheduleLabel) -> Option<String> {
+        let schedule = self.get_schedule(label)?;
+        Some(schedule.graph_to_dot(self.world.components()))
+    }
+
+    /// See [`App::schedule_graph_to_json`].
+    pub fn schedule_graph_to_json(&self, label: impl ScheduleLabel) -> Option<String> {
+        let schedule = self.get_schedule(label)?;
+        Some(schedule.graph_to_json(self.world.components()))
+    }
+
     /// See [`App::get_schedule_mut`].
     pub fn get_schedule_mut(&mut self, label: impl ScheduleLabel) -> Option<&mut Schedule> {
         let schedules = self.world.get_resource_mut::<Schedules>()?;
@@ -369,6 +407,16 @@ impl SubApp {
         self
     }
 
+    /// See [`App::add_manually_cleared_event`].
+    pub fn add_manually_cleared_event<T>(&mut self) -> &mut Self
+    where
+        T: Event,
+    {
+        self.add_event::<T>();
+        EventRegistry::deregister_events::<T>(self.world_mut());
+        self
+    }
+
     /// See [`App::add_plugins`].
     pub fn add_plugins<M>(&mut self, plugins: impl Plugins<M>) -> &mut Self {
         self.run_as_app(|app| plugins.add_to_app(app));
@@ -427,6 +475,7 @@ impl SubApp {
 
     /// Runs [`Plugin::finish`] for each plugin.
     pub fn finish(&mut self) {
+        self.initialize_resources();
         let plugins = std::mem::take(&mut self.plugins);
         self.run_as_app(|app| {
             for plugin in &plugins.registry {
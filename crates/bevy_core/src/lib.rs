@@ -8,12 +8,14 @@
 //! This crate provides core functionality for Bevy Engine.
 
 mod name;
+mod persistent_id;
 #[cfg(feature = "serialize")]
 mod serde;
 mod task_pool_options;
 
 use bevy_ecs::system::Resource;
 pub use name::*;
+pub use persistent_id::*;
 pub use task_pool_options::*;
 
 pub mod prelude {
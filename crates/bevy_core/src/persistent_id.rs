@@ -0,0 +1,232 @@
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::{Component, ComponentHooks, ComponentId, StorageType},
+    entity::{Entity, EntityHashMap},
+    reflect::ReflectComponent,
+    system::Resource,
+    world::{DeferredWorld, World},
+};
+use bevy_reflect::std_traits::ReflectDefault;
+use bevy_reflect::Reflect;
+use bevy_utils::{tracing::warn, HashMap};
+use uuid::Uuid;
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// A unique identifier for an entity that, unlike [`Entity`], stays the same when the entity
+/// is despawned and spawned again, including in another [`World`] or another run of the application.
+///
+/// This is meant for save games and network replication, which need to refer to the same entity
+/// after a reload. The entity with a given id can be found with [`PersistentIds`], which is kept
+/// up to date when this component is inserted or removed, once [`PersistentIdPlugin`] has been added.
+///
+/// Ids can be given explicitly, when loading an entity, or generated on spawn with [`Persistent`].
+/// Replace the id of an entity by inserting a new one: mutating it in place is not tracked.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[reflect(Component, Default, Debug, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(Serialize, Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PersistentId(Uuid);
+
+impl PersistentId {
+    /// Creates a new, random id.
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// Creates an id from an existing [`Uuid`], for example one read from a save file.
+    pub const fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    /// Returns the [`Uuid`] of this id.
+    pub const fn uuid(&self) -> Uuid {
+        self.0
+    }
+}
+
+impl Default for PersistentId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for PersistentId {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks
+            .on_insert(persistent_id_on_insert)
+            .on_remove(persistent_id_on_remove);
+    }
+}
+
+fn persistent_id_on_insert(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    let Some(&id) = world.get::<PersistentId>(entity) else {
+        return;
+    };
+    if let Some(mut ids) = world.get_resource_mut::<PersistentIds>() {
+        ids.insert(entity, id);
+    }
+}
+
+fn persistent_id_on_remove(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    if let Some(mut ids) = world.get_resource_mut::<PersistentIds>() {
+        ids.remove(entity);
+    }
+}
+
+/// A marker component that gives its entity a new [`PersistentId`] when it is spawned,
+/// unless it already has one.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_core::{Persistent, PersistentId, PersistentIds};
+/// let mut world = World::new();
+/// world.init_resource::<PersistentIds>();
+/// let entity = world.spawn(Persistent).flush();
+///
+/// let id = *world.get::<PersistentId>(entity).unwrap();
+/// assert_eq!(world.resource::<PersistentIds>().entity(id), Some(entity));
+/// ```
+#[derive(Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component, Default, Debug)]
+pub struct Persistent;
+
+impl Component for Persistent {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_add(persistent_on_add);
+    }
+}
+
+fn persistent_on_add(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    world.commands().add(move |world: &mut World| {
+        if let Some(mut entity) = world.get_entity_mut(entity) {
+            if !entity.contains::<PersistentId>() {
+                entity.insert(PersistentId::new());
+            }
+        }
+    });
+}
+
+/// Maps each [`PersistentId`] to the entity that has it, and back.
+///
+/// Added by [`PersistentIdPlugin`]. Only ids inserted while this resource exists are tracked.
+#[derive(Resource, Debug, Default)]
+pub struct PersistentIds {
+    entities: HashMap<PersistentId, Entity>,
+    ids: EntityHashMap<PersistentId>,
+}
+
+impl PersistentIds {
+    /// Returns the entity with the given id, if it exists.
+    pub fn entity(&self, id: PersistentId) -> Option<Entity> {
+        self.entities.get(&id).copied()
+    }
+
+    /// Returns the id of `entity`, if it has one.
+    pub fn id(&self, entity: Entity) -> Option<PersistentId> {
+        self.ids.get(&entity).copied()
+    }
+
+    /// Returns the number of entities with a [`PersistentId`].
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns `true` if no entity has a [`PersistentId`].
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Returns an iterator over every id and the entity that has it.
+    pub fn iter(&self) -> impl Iterator<Item = (PersistentId, Entity)> + '_ {
+        self.entities.iter().map(|(&id, &entity)| (id, entity))
+    }
+
+    fn insert(&mut self, entity: Entity, id: PersistentId) {
+        self.remove(entity);
+        if let Some(previous) = self.entities.insert(id, entity) {
+            warn!("{id:?} was given to {entity:?}, but {previous:?} already has it. It now refers to {entity:?}.");
+            self.ids.remove(&previous);
+        }
+        self.ids.insert(entity, id);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(id) = self.ids.remove(&entity) {
+            self.entities.remove(&id);
+        }
+    }
+}
+
+/// Adds the [`PersistentIds`] resource, to look up entities by their [`PersistentId`],
+/// and registers [`PersistentId`] and [`Persistent`] for reflection.
+///
+/// This plugin should be added before any [`PersistentId`] is inserted, as earlier ids are not tracked.
+#[derive(Default)]
+pub struct PersistentIdPlugin;
+
+impl Plugin for PersistentIdPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PersistentId>()
+            .register_type::<Persistent>()
+            .init_resource::<PersistentIds>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_tracked() {
+        let mut world = World::new();
+        world.init_resource::<PersistentIds>();
+
+        let id = PersistentId::new();
+        let entity = world.spawn(id).id();
+        assert_eq!(world.resource::<PersistentIds>().entity(id), Some(entity));
+        assert_eq!(world.resource::<PersistentIds>().id(entity), Some(id));
+
+        // Replacing the id forgets the old one.
+        let new_id = PersistentId::new();
+        world.entity_mut(entity).insert(new_id);
+        let ids = world.resource::<PersistentIds>();
+        assert_eq!(ids.entity(id), None);
+        assert_eq!(ids.entity(new_id), Some(entity));
+
+        // The id survives a despawn and respawn, as after loading a save.
+        world.despawn(entity);
+        assert!(world.resource::<PersistentIds>().is_empty());
+        let respawned = world.spawn((new_id, Persistent)).flush();
+        assert_eq!(world.get::<PersistentId>(respawned), Some(&new_id));
+        assert_eq!(
+            world.resource::<PersistentIds>().entity(new_id),
+            Some(respawned)
+        );
+        assert_eq!(world.resource::<PersistentIds>().len(), 1);
+    }
+
+    #[test]
+    fn persistent_entities_get_an_id() {
+        let mut world = World::new();
+        world.init_resource::<PersistentIds>();
+        let a = world.spawn(Persistent).id();
+        let b = world.spawn(Persistent).id();
+        world.flush_commands();
+
+        let ids = world.resource::<PersistentIds>();
+        let a_id = ids.id(a).unwrap();
+        assert_ne!(Some(a_id), ids.id(b));
+        assert_eq!(ids.entity(a_id), Some(a));
+    }
+}
@@ -0,0 +1,132 @@
+// Builds a luminance histogram of the view, then turns it into an exposure compensation that
+// adapts over time.
+
+#import bevy_render::globals::Globals
+
+struct AutoExposure {
+    min_log_lum: f32,
+    inv_log_lum_range: f32,
+    log_lum_range: f32,
+    low_percent: f32,
+    high_percent: f32,
+    speed_brighten: f32,
+    speed_darken: f32,
+    middle_grey: f32,
+    min_compensation: f32,
+    max_compensation: f32,
+    metering_mode: u32,
+    spot_radius: f32,
+}
+
+const HISTOGRAM_BIN_COUNT: u32 = 64u;
+
+const METERING_AVERAGE: u32 = 0u;
+const METERING_CENTER_WEIGHTED: u32 = 1u;
+const METERING_SPOT: u32 = 2u;
+
+// Weights are stored as integers so they can be accumulated atomically.
+const WEIGHT_SCALE: f32 = 16.0;
+
+@group(0) @binding(0) var<uniform> globals: Globals;
+@group(0) @binding(1) var<uniform> settings: AutoExposure;
+@group(0) @binding(2) var tex_color: texture_2d<f32>;
+@group(0) @binding(3) var<storage, read_write> histogram: array<atomic<u32>, 64>;
+@group(0) @binding(4) var<storage, read_write> compensation: f32;
+
+var<workgroup> histogram_shared: array<atomic<u32>, 64>;
+
+fn metering_weight(position: vec2<f32>, size: vec2<f32>) -> f32 {
+    // Distance to the center, relative to the smallest dimension of the image
+    let offset = (position - size * 0.5) / min(size.x, size.y);
+    let distance = length(offset);
+
+    switch settings.metering_mode {
+        case METERING_CENTER_WEIGHTED: {
+            return clamp(1.0 - distance * 2.0, 0.0625, 1.0);
+        }
+        case METERING_SPOT: {
+            return select(0.0, 1.0, distance <= settings.spot_radius);
+        }
+        default: {
+            return 1.0;
+        }
+    }
+}
+
+fn luminance_to_bin(luminance: f32) -> u32 {
+    if luminance < 1e-8 {
+        return 0u;
+    }
+    let normalized = saturate((log2(luminance) - settings.min_log_lum) * settings.inv_log_lum_range);
+    return u32(normalized * f32(HISTOGRAM_BIN_COUNT - 1u));
+}
+
+@compute @workgroup_size(16, 16, 1)
+fn compute_histogram(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    if local_index < HISTOGRAM_BIN_COUNT {
+        atomicStore(&histogram_shared[local_index], 0u);
+    }
+    workgroupBarrier();
+
+    let size = textureDimensions(tex_color);
+    if global_id.x < size.x && global_id.y < size.y {
+        let color = textureLoad(tex_color, vec2<i32>(global_id.xy), 0).rgb;
+        let luminance = dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+        let weight = metering_weight(vec2<f32>(global_id.xy) + 0.5, vec2<f32>(size));
+        atomicAdd(
+            &histogram_shared[luminance_to_bin(luminance)],
+            u32(round(weight * WEIGHT_SCALE))
+        );
+    }
+    workgroupBarrier();
+
+    if local_index < HISTOGRAM_BIN_COUNT {
+        atomicAdd(&histogram[local_index], atomicLoad(&histogram_shared[local_index]));
+    }
+}
+
+@compute @workgroup_size(1, 1, 1)
+fn compute_average() {
+    var counts: array<f32, 64>;
+    var total = 0.0;
+    for (var i = 0u; i < HISTOGRAM_BIN_COUNT; i += 1u) {
+        counts[i] = f32(atomicLoad(&histogram[i]));
+        total += counts[i];
+    }
+
+    // Average the bins between the low and high percentiles, counting partial bins
+    // at both ends.
+    let low = total * settings.low_percent;
+    let high = total * settings.high_percent;
+    var accumulated = 0.0;
+    var sum = 0.0;
+    var count = 0.0;
+    for (var i = 0u; i < HISTOGRAM_BIN_COUNT; i += 1u) {
+        let start = accumulated;
+        accumulated += counts[i];
+        let included = max(min(accumulated, high) - max(start, low), 0.0);
+        let log_lum = settings.min_log_lum
+            + (f32(i) + 0.5) / f32(HISTOGRAM_BIN_COUNT) * settings.log_lum_range;
+        sum += included * log_lum;
+        count += included;
+    }
+
+    // Nothing was metered, keep the current exposure.
+    if count <= 0.0 {
+        return;
+    }
+
+    let target_compensation = clamp(
+        settings.middle_grey - sum / count,
+        settings.min_compensation,
+        settings.max_compensation
+    );
+
+    let delta = target_compensation - compensation;
+    let max_step = select(settings.speed_darken, settings.speed_brighten, delta > 0.0)
+        * globals.delta_time;
+    compensation += clamp(delta, -max_step, max_step);
+}
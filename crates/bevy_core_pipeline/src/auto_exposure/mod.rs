@@ -0,0 +1,388 @@
+//! Automatic exposure (eye adaptation) for HDR cameras.
+//!
+//! A compute pass builds a histogram of the luminance of the rendered scene, and a second pass
+//! averages the part of it selected by [`AutoExposureSettings::filter`] to find the exposure
+//! compensation that brings the scene to middle grey. The compensation moves towards that target
+//! over time, and is applied to the HDR image before bloom and tonemapping.
+
+use std::ops::RangeInclusive;
+
+use bevy_app::prelude::*;
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_ecs::{entity::EntityHashMap, prelude::*, query::QueryItem};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::Camera,
+    extract_component::{ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin},
+    globals::GlobalsUniform,
+    render_graph::{RenderGraph, RenderGraphApp, ViewNodeRunner},
+    render_resource::{
+        binding_types::{
+            sampler, storage_buffer_read_only_sized, storage_buffer_sized, texture_2d,
+            uniform_buffer,
+        },
+        *,
+    },
+    renderer::{RenderAdapter, RenderDevice},
+    view::ViewTarget,
+    Render, RenderApp, RenderSet,
+};
+
+use crate::{
+    core_3d::graph::{Core3d, Node3d},
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+};
+
+mod node;
+
+pub use node::AutoExposureNode;
+
+const AUTO_EXPOSURE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(9120468231530962474);
+const AUTO_EXPOSURE_APPLY_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(4410872519260358112);
+
+/// The number of bins of the luminance histogram. Must match `auto_exposure.wgsl`.
+const HISTOGRAM_BIN_COUNT: u64 = 64;
+
+/// The average luminance the scene is exposed for, as a log2 value.
+const MIDDLE_GREY_LOG2: f32 = -2.473_931_2; // log2(0.18)
+
+/// Adds automatic exposure to 3D cameras with an [`AutoExposureSettings`] component.
+///
+/// This requires compute shaders, and does nothing on platforms without them (e.g. WebGL 2).
+pub struct AutoExposurePlugin;
+
+impl Plugin for AutoExposurePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            AUTO_EXPOSURE_SHADER_HANDLE,
+            "auto_exposure.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            AUTO_EXPOSURE_APPLY_SHADER_HANDLE,
+            "auto_exposure_apply.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<AutoExposureSettings>()
+            .register_type::<AutoExposureMetering>()
+            .add_plugins((
+                ExtractComponentPlugin::<AutoExposureSettings>::default(),
+                UniformComponentPlugin::<AutoExposureUniform>::default(),
+            ));
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        if !render_app
+            .world()
+            .resource::<RenderAdapter>()
+            .get_downlevel_capabilities()
+            .flags
+            .contains(DownlevelFlags::COMPUTE_SHADERS)
+        {
+            return;
+        }
+
+        render_app
+            .init_resource::<AutoExposurePipeline>()
+            .init_resource::<AutoExposureBuffers>()
+            .add_systems(
+                Render,
+                prepare_auto_exposure_buffers.in_set(RenderSet::PrepareResources),
+            )
+            .add_render_graph_node::<ViewNodeRunner<AutoExposureNode>>(Core3d, Node3d::AutoExposure)
+            .add_render_graph_edges(
+                Core3d,
+                (Node3d::EndMainPass, Node3d::AutoExposure, Node3d::Bloom),
+            );
+
+        // Meter the antialiased image when TAA is enabled.
+        let has_taa = render_app
+            .world()
+            .resource::<RenderGraph>()
+            .get_sub_graph(Core3d)
+            .is_some_and(|graph| graph.get_node_state(Node3d::Taa).is_ok());
+        if has_taa {
+            render_app.add_render_graph_edge(Core3d, Node3d::Taa, Node3d::AutoExposure);
+        }
+    }
+}
+
+/// Adjusts the exposure of an HDR camera to the brightness of what it sees, like an eye
+/// adapting when going from a dark room into sunlight.
+///
+/// The compensation is applied on top of the camera's [`Exposure`](bevy_render::camera::Exposure),
+/// which should be set to a sensible starting point for the scene.
+///
+/// This only works on 3D cameras with [`Camera::hdr`] enabled.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, Default)]
+pub struct AutoExposureSettings {
+    /// The range of exposure compensation, in stops, that auto exposure may apply.
+    ///
+    /// Scenes darker or brighter than this range can handle stay under or over exposed.
+    ///
+    /// The default is `-8.0..=8.0`.
+    pub range: RangeInclusive<f32>,
+    /// The portion of the luminance histogram used for metering, as fractions of the metered
+    /// pixels sorted by brightness.
+    ///
+    /// Leaving out the darkest and brightest pixels keeps small light sources or deep shadows
+    /// from affecting the exposure of the whole scene.
+    ///
+    /// The default is `0.10..=0.90`.
+    pub filter: RangeInclusive<f32>,
+    /// How fast the exposure increases when the scene gets darker, in stops per second.
+    ///
+    /// The default is `3.0`.
+    pub speed_brighten: f32,
+    /// How fast the exposure decreases when the scene gets brighter, in stops per second.
+    ///
+    /// The default is `1.0`.
+    pub speed_darken: f32,
+    /// A constant offset, in stops, added to the metered exposure.
+    ///
+    /// Positive values make the image brighter. The default is `0.0`.
+    pub compensation: f32,
+    /// Which pixels of the image are taken into account, and how much.
+    pub metering: AutoExposureMetering,
+}
+
+impl Default for AutoExposureSettings {
+    fn default() -> Self {
+        Self {
+            range: -8.0..=8.0,
+            filter: 0.10..=0.90,
+            speed_brighten: 3.0,
+            speed_darken: 1.0,
+            compensation: 0.0,
+            metering: AutoExposureMetering::default(),
+        }
+    }
+}
+
+/// How the pixels of the image are weighted when metering for [`AutoExposureSettings`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Default)]
+pub enum AutoExposureMetering {
+    /// Every pixel counts the same.
+    #[default]
+    Average,
+    /// Pixels count less the further they are from the center of the image.
+    CenterWeighted,
+    /// Only pixels inside a circle at the center of the image count.
+    Spot {
+        /// The radius of the circle, as a fraction of the smallest dimension of the image.
+        radius: f32,
+    },
+}
+
+impl AutoExposureMetering {
+    fn mode(&self) -> u32 {
+        match self {
+            AutoExposureMetering::Average => 0,
+            AutoExposureMetering::CenterWeighted => 1,
+            AutoExposureMetering::Spot { .. } => 2,
+        }
+    }
+}
+
+/// The uniform struct extracted from [`AutoExposureSettings`] attached to a [`Camera`].
+/// Will be available for use in the auto exposure shader.
+#[doc(hidden)]
+#[derive(Component, ShaderType, Clone)]
+pub struct AutoExposureUniform {
+    min_log_lum: f32,
+    inv_log_lum_range: f32,
+    log_lum_range: f32,
+    low_percent: f32,
+    high_percent: f32,
+    speed_brighten: f32,
+    speed_darken: f32,
+    middle_grey: f32,
+    min_compensation: f32,
+    max_compensation: f32,
+    metering_mode: u32,
+    spot_radius: f32,
+}
+
+impl ExtractComponent for AutoExposureSettings {
+    type QueryData = (&'static Self, &'static Camera);
+    type QueryFilter = ();
+    type Out = AutoExposureUniform;
+
+    fn extract_component((settings, camera): QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        if !camera.is_active || !camera.hdr {
+            return None;
+        }
+
+        // A scene with an average luminance of `l` needs a compensation of `middle_grey - l`,
+        // so the histogram only has to cover the luminances reachable with `range`.
+        let middle_grey = MIDDLE_GREY_LOG2 + settings.compensation;
+        let min_log_lum = middle_grey - settings.range.end();
+        let log_lum_range = (settings.range.end() - settings.range.start()).max(f32::EPSILON);
+
+        Some(AutoExposureUniform {
+            min_log_lum,
+            inv_log_lum_range: 1.0 / log_lum_range,
+            log_lum_range,
+            low_percent: settings.filter.start().clamp(0.0, 1.0),
+            high_percent: settings.filter.end().clamp(0.0, 1.0),
+            speed_brighten: settings.speed_brighten,
+            speed_darken: settings.speed_darken,
+            middle_grey,
+            min_compensation: *settings.range.start(),
+            max_compensation: *settings.range.end(),
+            metering_mode: settings.metering.mode(),
+            spot_radius: match settings.metering {
+                AutoExposureMetering::Spot { radius } => radius,
+                _ => 0.0,
+            },
+        })
+    }
+}
+
+#[derive(Resource)]
+pub struct AutoExposurePipeline {
+    histogram_layout: BindGroupLayout,
+    apply_layout: BindGroupLayout,
+    sampler: Sampler,
+    histogram_pipeline: CachedComputePipelineId,
+    average_pipeline: CachedComputePipelineId,
+    apply_pipeline: CachedRenderPipelineId,
+}
+
+impl FromWorld for AutoExposurePipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        let render_device = render_world.resource::<RenderDevice>();
+        let pipeline_cache = render_world.resource::<PipelineCache>();
+
+        let histogram_layout = render_device.create_bind_group_layout(
+            "auto_exposure_histogram_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    uniform_buffer::<GlobalsUniform>(false),
+                    uniform_buffer::<AutoExposureUniform>(true),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    storage_buffer_sized(false, None),
+                    storage_buffer_sized(false, None),
+                ),
+            ),
+        );
+
+        let apply_layout = render_device.create_bind_group_layout(
+            "auto_exposure_apply_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    storage_buffer_read_only_sized(false, None),
+                ),
+            ),
+        );
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor::default());
+
+        let histogram_pipeline = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+            label: Some("auto_exposure_histogram_pipeline".into()),
+            layout: vec![histogram_layout.clone()],
+            push_constant_ranges: vec![],
+            shader: AUTO_EXPOSURE_SHADER_HANDLE,
+            shader_defs: vec![],
+            entry_point: "compute_histogram".into(),
+        });
+
+        let average_pipeline = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+            label: Some("auto_exposure_average_pipeline".into()),
+            layout: vec![histogram_layout.clone()],
+            push_constant_ranges: vec![],
+            shader: AUTO_EXPOSURE_SHADER_HANDLE,
+            shader_defs: vec![],
+            entry_point: "compute_average".into(),
+        });
+
+        let apply_pipeline = pipeline_cache.queue_render_pipeline(RenderPipelineDescriptor {
+            label: Some("auto_exposure_apply_pipeline".into()),
+            layout: vec![apply_layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: AUTO_EXPOSURE_APPLY_SHADER_HANDLE,
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: ViewTarget::TEXTURE_FORMAT_HDR,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: vec![],
+        });
+
+        AutoExposurePipeline {
+            histogram_layout,
+            apply_layout,
+            sampler,
+            histogram_pipeline,
+            average_pipeline,
+            apply_pipeline,
+        }
+    }
+}
+
+/// The GPU buffers of a camera using auto exposure.
+pub struct AutoExposureBuffer {
+    /// The luminance histogram, cleared before it is filled every frame.
+    histogram: Buffer,
+    /// The current exposure compensation in stops, carried over from frame to frame.
+    state: Buffer,
+}
+
+/// The [`AutoExposureBuffer`] of every camera using auto exposure.
+///
+/// Render world views are spawned again every frame, so the buffers are kept here, keyed by
+/// camera entity, for the exposure to adapt over multiple frames.
+#[derive(Resource, Default)]
+pub struct AutoExposureBuffers {
+    buffers: EntityHashMap<AutoExposureBuffer>,
+}
+
+fn prepare_auto_exposure_buffers(
+    render_device: Res<RenderDevice>,
+    mut auto_exposure_buffers: ResMut<AutoExposureBuffers>,
+    views: Query<Entity, With<AutoExposureUniform>>,
+) {
+    auto_exposure_buffers
+        .buffers
+        .retain(|entity, _| views.contains(*entity));
+
+    for entity in &views {
+        auto_exposure_buffers
+            .buffers
+            .entry(entity)
+            .or_insert_with(|| AutoExposureBuffer {
+                histogram: render_device.create_buffer(&BufferDescriptor {
+                    label: Some("auto_exposure_histogram_buffer"),
+                    size: HISTOGRAM_BIN_COUNT * std::mem::size_of::<u32>() as u64,
+                    usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                }),
+                state: render_device.create_buffer_with_data(&BufferInitDescriptor {
+                    label: Some("auto_exposure_state_buffer"),
+                    contents: &0.0f32.to_le_bytes(),
+                    usage: BufferUsages::STORAGE,
+                }),
+            });
+    }
+}
@@ -0,0 +1,141 @@
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_render::{
+    extract_component::{ComponentUniforms, DynamicUniformIndex},
+    globals::GlobalsBuffer,
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_resource::{
+        BindGroupEntries, ComputePassDescriptor, Operations, PipelineCache,
+        RenderPassColorAttachment, RenderPassDescriptor,
+    },
+    renderer::RenderContext,
+    view::ViewTarget,
+};
+
+use super::{AutoExposureBuffers, AutoExposurePipeline, AutoExposureUniform};
+
+/// Meters the luminance of the view and applies the resulting exposure compensation.
+#[derive(Default)]
+pub struct AutoExposureNode;
+
+impl ViewNode for AutoExposureNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static DynamicUniformIndex<AutoExposureUniform>,
+    );
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, uniform_index): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let auto_exposure_pipeline = world.resource::<AutoExposurePipeline>();
+        let uniforms = world.resource::<ComponentUniforms<AutoExposureUniform>>();
+        let globals_buffer = world.resource::<GlobalsBuffer>();
+
+        let (
+            Some(histogram_pipeline),
+            Some(average_pipeline),
+            Some(apply_pipeline),
+            Some(uniforms),
+            Some(globals),
+            Some(buffers),
+        ) = (
+            pipeline_cache.get_compute_pipeline(auto_exposure_pipeline.histogram_pipeline),
+            pipeline_cache.get_compute_pipeline(auto_exposure_pipeline.average_pipeline),
+            pipeline_cache.get_render_pipeline(auto_exposure_pipeline.apply_pipeline),
+            uniforms.binding(),
+            globals_buffer.buffer.binding(),
+            world
+                .resource::<AutoExposureBuffers>()
+                .buffers
+                .get(&graph.view_entity()),
+        )
+        else {
+            return Ok(());
+        };
+
+        if !view_target.is_hdr() {
+            return Ok(());
+        }
+
+        let histogram_bind_group = render_context.render_device().create_bind_group(
+            "auto_exposure_histogram_bind_group",
+            &auto_exposure_pipeline.histogram_layout,
+            &BindGroupEntries::sequential((
+                globals,
+                uniforms,
+                view_target.main_texture_view(),
+                buffers.histogram.as_entire_binding(),
+                buffers.state.as_entire_binding(),
+            )),
+        );
+
+        let size = view_target.main_texture().size();
+
+        render_context
+            .command_encoder()
+            .push_debug_group("auto_exposure");
+
+        render_context
+            .command_encoder()
+            .clear_buffer(&buffers.histogram, 0, None);
+
+        // Meter the scene and adapt the exposure compensation.
+        {
+            let mut compute_pass =
+                render_context
+                    .command_encoder()
+                    .begin_compute_pass(&ComputePassDescriptor {
+                        label: Some("auto_exposure_pass"),
+                        timestamp_writes: None,
+                    });
+
+            compute_pass.set_bind_group(0, &histogram_bind_group, &[uniform_index.index()]);
+            compute_pass.set_pipeline(histogram_pipeline);
+            compute_pass.dispatch_workgroups(size.width.div_ceil(16), size.height.div_ceil(16), 1);
+            compute_pass.set_pipeline(average_pipeline);
+            compute_pass.dispatch_workgroups(1, 1, 1);
+        }
+
+        // Apply the compensation to the rendered scene.
+        {
+            let post_process = view_target.post_process_write();
+
+            let apply_bind_group = render_context.render_device().create_bind_group(
+                "auto_exposure_apply_bind_group",
+                &auto_exposure_pipeline.apply_layout,
+                &BindGroupEntries::sequential((
+                    post_process.source,
+                    &auto_exposure_pipeline.sampler,
+                    buffers.state.as_entire_binding(),
+                )),
+            );
+
+            let mut render_pass =
+                render_context
+                    .command_encoder()
+                    .begin_render_pass(&RenderPassDescriptor {
+                        label: Some("auto_exposure_apply_pass"),
+                        color_attachments: &[Some(RenderPassColorAttachment {
+                            view: post_process.destination,
+                            resolve_target: None,
+                            ops: Operations::default(),
+                        })],
+                        depth_stencil_attachment: None,
+                        timestamp_writes: None,
+                        occlusion_query_set: None,
+                    });
+
+            render_pass.set_pipeline(apply_pipeline);
+            render_pass.set_bind_group(0, &apply_bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        render_context.command_encoder().pop_debug_group();
+
+        Ok(())
+    }
+}
@@ -3,7 +3,21 @@
 @group(0) @binding(0) var in_texture: texture_2d<f32>;
 @group(0) @binding(1) var in_sampler: sampler;
 
+#ifdef HDR_OUTPUT
+struct HdrOutput {
+    // Brings SDR white to the paper white of HDR window surfaces.
+    scale: f32,
+};
+
+@group(1) @binding(0) var<uniform> hdr_output: HdrOutput;
+#endif
+
 @fragment
 fn fs_main(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+#ifdef HDR_OUTPUT
+    let color = textureSample(in_texture, in_sampler, in.uv);
+    return vec4(color.rgb * hdr_output.scale, color.a);
+#else
     return textureSample(in_texture, in_sampler, in.uv);
+#endif
 }
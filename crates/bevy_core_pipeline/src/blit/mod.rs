@@ -3,7 +3,7 @@ use bevy_asset::{load_internal_asset, Handle};
 use bevy_ecs::prelude::*;
 use bevy_render::{
     render_resource::{
-        binding_types::{sampler, texture_2d},
+        binding_types::{sampler, texture_2d, uniform_buffer},
         *,
     },
     renderer::RenderDevice,
@@ -40,6 +40,16 @@ impl Plugin for BlitPlugin {
 pub struct BlitPipeline {
     pub texture_bind_group: BindGroupLayout,
     pub sampler: Sampler,
+    /// The layout of the [`BlitHdrOutputUniform`] bound at group 1 when
+    /// [`BlitPipelineKey::hdr_output`] is set.
+    pub hdr_output_bind_group: BindGroupLayout,
+}
+
+/// The scale a blit applies to its output so that SDR white is displayed at the paper white
+/// of an HDR window surface.
+#[derive(ShaderType, Clone, Copy)]
+pub struct BlitHdrOutputUniform {
+    pub scale: f32,
 }
 
 impl FromWorld for BlitPipeline {
@@ -59,9 +69,18 @@ impl FromWorld for BlitPipeline {
 
         let sampler = render_device.create_sampler(&SamplerDescriptor::default());
 
+        let hdr_output_bind_group = render_device.create_bind_group_layout(
+            "blit_hdr_output_bind_group_layout",
+            &BindGroupLayoutEntries::single(
+                ShaderStages::FRAGMENT,
+                uniform_buffer::<BlitHdrOutputUniform>(true),
+            ),
+        );
+
         BlitPipeline {
             texture_bind_group,
             sampler,
+            hdr_output_bind_group,
         }
     }
 }
@@ -71,19 +90,28 @@ pub struct BlitPipelineKey {
     pub texture_format: TextureFormat,
     pub blend_state: Option<BlendState>,
     pub samples: u32,
+    /// Whether the output is scaled by a [`BlitHdrOutputUniform`].
+    pub hdr_output: bool,
 }
 
 impl SpecializedRenderPipeline for BlitPipeline {
     type Key = BlitPipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut layout = vec![self.texture_bind_group.clone()];
+        let mut shader_defs = Vec::new();
+        if key.hdr_output {
+            layout.push(self.hdr_output_bind_group.clone());
+            shader_defs.push("HDR_OUTPUT".into());
+        }
+
         RenderPipelineDescriptor {
             label: Some("blit pipeline".into()),
-            layout: vec![self.texture_bind_group.clone()],
+            layout,
             vertex: fullscreen_shader_vertex_state(),
             fragment: Some(FragmentState {
                 shader: BLIT_SHADER_HANDLE,
-                shader_defs: vec![],
+                shader_defs,
                 entry_point: "fs_main".into(),
                 targets: vec![Some(ColorTargetState {
                     format: key.texture_format,
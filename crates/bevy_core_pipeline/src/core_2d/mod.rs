@@ -17,6 +17,7 @@ pub mod graph {
         MainPass,
         Bloom,
         Tonemapping,
+        PostProcessStack,
         Fxaa,
         Upscaling,
         ContrastAdaptiveSharpening,
@@ -23,11 +23,14 @@ pub mod graph {
         StartMainPass,
         MainOpaquePass,
         MainTransmissivePass,
+        MainOrderIndependentTransparentPass,
         MainTransparentPass,
         EndMainPass,
+        AutoExposure,
         Taa,
         Bloom,
         Tonemapping,
+        PostProcessStack,
         Fxaa,
         Upscaling,
         ContrastAdaptiveSharpening,
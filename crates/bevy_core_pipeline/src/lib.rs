@@ -7,6 +7,7 @@
     html_favicon_url = "https://bevyengine.org/assets/icon.png"
 )]
 
+pub mod auto_exposure;
 pub mod blit;
 pub mod bloom;
 pub mod contrast_adaptive_sharpening;
@@ -16,6 +17,8 @@ pub mod deferred;
 pub mod fullscreen_vertex_shader;
 pub mod fxaa;
 pub mod msaa_writeback;
+pub mod oit;
+pub mod post_process;
 pub mod prepass;
 mod skybox;
 mod taa;
@@ -45,6 +48,7 @@ pub mod prelude {
 }
 
 use crate::{
+    auto_exposure::AutoExposurePlugin,
     blit::BlitPlugin,
     bloom::BloomPlugin,
     contrast_adaptive_sharpening::CASPlugin,
@@ -54,6 +58,8 @@ use crate::{
     fullscreen_vertex_shader::FULLSCREEN_SHADER_HANDLE,
     fxaa::FxaaPlugin,
     msaa_writeback::MsaaWritebackPlugin,
+    oit::OrderIndependentTransparencyPlugin,
+    post_process::PostProcessStackPlugin,
     prepass::{DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass},
     tonemapping::TonemappingPlugin,
     upscaling::UpscalingPlugin,
@@ -89,6 +95,9 @@ impl Plugin for CorePipelinePlugin {
                 BloomPlugin,
                 FxaaPlugin,
                 CASPlugin,
+                PostProcessStackPlugin,
+                OrderIndependentTransparencyPlugin,
+                AutoExposurePlugin,
             ));
     }
 }
@@ -0,0 +1,459 @@
+//! Weighted blended order-independent transparency.
+//!
+//! Cameras with [`OrderIndependentTransparency`] render their alpha blended meshes in the
+//! [`OrderIndependentTransparent3d`] phase instead of [`Transparent3d`]. Instead of being
+//! blended over each other in back-to-front order, their colors are accumulated with
+//! weights that decrease with distance, along with the product of their transparencies,
+//! then composited over the main texture of the view. The result doesn't depend on the
+//! order meshes are drawn in, so it has none of the artifacts of sorting per mesh, at the
+//! cost of an approximation of the blending of overlapping surfaces.
+//!
+//! See [Weighted Blended Order-Independent Transparency](https://jcgt.org/published/0002/02/09/).
+//!
+//! [`Transparent3d`]: crate::core_3d::Transparent3d
+
+use std::ops::Range;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_color::LinearRgba;
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_math::FloatOrd;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    diagnostic::RecordDiagnostics,
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
+    render_phase::{
+        CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions, PhaseItem, SortedPhaseItem,
+        SortedRenderPhase,
+    },
+    render_resource::{binding_types::texture_2d, *},
+    renderer::{RenderContext, RenderDevice},
+    texture::{BevyDefault, ColorAttachment, TextureCache},
+    view::{ExtractedView, Msaa, ViewDepthTexture, ViewTarget},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use nonmax::NonMaxU32;
+
+use crate::{
+    core_3d::{
+        graph::{Core3d, Node3d},
+        Camera3d,
+    },
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+};
+
+const OIT_COMPOSITE_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(311455679987264376835339829102819081826);
+
+/// The format of the texture accumulating the weighted, premultiplied colors of
+/// transparent fragments, and the sum of their weighted alphas.
+pub const OIT_ACCUMULATION_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// The format of the texture accumulating the product of the transparencies of
+/// transparent fragments.
+pub const OIT_REVEALAGE_FORMAT: TextureFormat = TextureFormat::R16Float;
+
+/// Adds support for [`OrderIndependentTransparency`] to 3D cameras.
+pub struct OrderIndependentTransparencyPlugin;
+
+impl Plugin for OrderIndependentTransparencyPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            OIT_COMPOSITE_SHADER_HANDLE,
+            "oit_composite.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<OrderIndependentTransparency>()
+            .add_plugins(ExtractComponentPlugin::<OrderIndependentTransparency>::default());
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<DrawFunctions<OrderIndependentTransparent3d>>()
+            .init_resource::<SpecializedRenderPipelines<OitCompositePipeline>>()
+            .add_systems(ExtractSchedule, extract_oit_camera_phases)
+            .add_systems(
+                Render,
+                (
+                    prepare_oit_textures.in_set(RenderSet::PrepareResources),
+                    prepare_oit_composite_pipelines.in_set(RenderSet::Prepare),
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<OrderIndependentTransparencyNode>>(
+                Core3d,
+                Node3d::MainOrderIndependentTransparentPass,
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::MainTransmissivePass,
+                    Node3d::MainOrderIndependentTransparentPass,
+                    Node3d::MainTransparentPass,
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<OitCompositePipeline>();
+    }
+}
+
+/// Renders the alpha blended meshes of a 3D camera with weighted blended
+/// order-independent transparency.
+///
+/// This only applies to materials with [`AlphaMode::Blend`] or
+/// [`AlphaMode::Premultiplied`] whose shaders write the outputs of the `OIT_ENABLED`
+/// shader def, as the standard material does; other materials opt in with
+/// `Material::supports_order_independent_transparency`. Additive and multiplicative
+/// materials, and other transparent items, are still sorted and blended over the result.
+///
+/// [`AlphaMode::Blend`]: https://docs.rs/bevy/latest/bevy/pbr/enum.AlphaMode.html#variant.Blend
+/// [`AlphaMode::Premultiplied`]: https://docs.rs/bevy/latest/bevy/pbr/enum.AlphaMode.html#variant.Premultiplied
+#[derive(Component, Reflect, Clone, Copy, Default, Debug, ExtractComponent)]
+#[reflect(Component, Default)]
+#[extract_component_filter((With<Camera>, With<Camera3d>))]
+pub struct OrderIndependentTransparency;
+
+/// Transparent 3D [`SortedPhaseItem`]s rendered with [`OrderIndependentTransparency`].
+///
+/// Their order doesn't affect the result, so the phase isn't sorted.
+pub struct OrderIndependentTransparent3d {
+    pub distance: f32,
+    pub pipeline: CachedRenderPipelineId,
+    pub entity: Entity,
+    pub draw_function: DrawFunctionId,
+    pub batch_range: Range<u32>,
+    pub dynamic_offset: Option<NonMaxU32>,
+}
+
+impl PhaseItem for OrderIndependentTransparent3d {
+    #[inline]
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    #[inline]
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+
+    #[inline]
+    fn batch_range(&self) -> &Range<u32> {
+        &self.batch_range
+    }
+
+    #[inline]
+    fn batch_range_mut(&mut self) -> &mut Range<u32> {
+        &mut self.batch_range
+    }
+
+    #[inline]
+    fn dynamic_offset(&self) -> Option<NonMaxU32> {
+        self.dynamic_offset
+    }
+
+    #[inline]
+    fn dynamic_offset_mut(&mut self) -> &mut Option<NonMaxU32> {
+        &mut self.dynamic_offset
+    }
+}
+
+impl SortedPhaseItem for OrderIndependentTransparent3d {
+    type SortKey = FloatOrd;
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        FloatOrd(self.distance)
+    }
+}
+
+impl CachedRenderPipelinePhaseItem for OrderIndependentTransparent3d {
+    #[inline]
+    fn cached_pipeline(&self) -> CachedRenderPipelineId {
+        self.pipeline
+    }
+}
+
+pub fn extract_oit_camera_phases(
+    mut commands: Commands,
+    cameras_3d: Extract<
+        Query<(Entity, &Camera), (With<Camera3d>, With<OrderIndependentTransparency>)>,
+    >,
+) {
+    for (entity, camera) in &cameras_3d {
+        if camera.is_active {
+            commands
+                .get_or_spawn(entity)
+                .insert(SortedRenderPhase::<OrderIndependentTransparent3d>::default());
+        }
+    }
+}
+
+/// The textures accumulating the transparent fragments of a view with
+/// [`OrderIndependentTransparency`].
+#[derive(Component)]
+pub struct ViewOrderIndependentTransparencyTextures {
+    /// The weighted, premultiplied colors and the sum of the weighted alphas.
+    pub accumulation: ColorAttachment,
+    /// The product of the transparencies, `1 - alpha`, of the fragments.
+    pub revealage: ColorAttachment,
+}
+
+pub fn prepare_oit_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    msaa: Res<Msaa>,
+    render_device: Res<RenderDevice>,
+    views: Query<
+        (Entity, &ExtractedCamera),
+        With<SortedRenderPhase<OrderIndependentTransparent3d>>,
+    >,
+) {
+    for (entity, camera) in &views {
+        let Some(physical_target_size) = camera.physical_target_size else {
+            continue;
+        };
+
+        let size = Extent3d {
+            width: physical_target_size.x,
+            height: physical_target_size.y,
+            depth_or_array_layers: 1,
+        };
+
+        let mut create_attachment = |label, format, clear_color| {
+            let mut descriptor = TextureDescriptor {
+                label: Some(label),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            };
+            let texture = texture_cache.get(&render_device, descriptor.clone());
+
+            // The pass is multisampled like the depth texture, and resolved to the texture
+            // read by the composite pass.
+            let multisampled_texture = (msaa.samples() > 1).then(|| {
+                descriptor.sample_count = msaa.samples();
+                descriptor.usage = TextureUsages::RENDER_ATTACHMENT;
+                texture_cache.get(&render_device, descriptor)
+            });
+
+            ColorAttachment::new(texture, multisampled_texture, Some(clear_color))
+        };
+
+        let accumulation = create_attachment(
+            "oit_accumulation_texture",
+            OIT_ACCUMULATION_FORMAT,
+            LinearRgba::NONE,
+        );
+        let revealage = create_attachment(
+            "oit_revealage_texture",
+            OIT_REVEALAGE_FORMAT,
+            LinearRgba::WHITE,
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewOrderIndependentTransparencyTextures {
+                accumulation,
+                revealage,
+            });
+    }
+}
+
+/// Composites the accumulated transparent fragments over the main texture of a view.
+#[derive(Resource)]
+pub struct OitCompositePipeline {
+    layout: BindGroupLayout,
+}
+
+impl FromWorld for OitCompositePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let layout = render_device.create_bind_group_layout(
+            "oit_composite_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                ),
+            ),
+        );
+
+        Self { layout }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct OitCompositePipelineKey {
+    pub texture_format: TextureFormat,
+    pub samples: u32,
+}
+
+impl SpecializedRenderPipeline for OitCompositePipeline {
+    type Key = OitCompositePipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("oit_composite_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: OIT_COMPOSITE_SHADER_HANDLE,
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.texture_format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: key.samples,
+                ..MultisampleState::default()
+            },
+            push_constant_ranges: Vec::new(),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ViewOitCompositePipeline(pub CachedRenderPipelineId);
+
+pub fn prepare_oit_composite_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<OitCompositePipeline>>,
+    composite_pipeline: Res<OitCompositePipeline>,
+    msaa: Res<Msaa>,
+    views: Query<(Entity, &ExtractedView), With<SortedRenderPhase<OrderIndependentTransparent3d>>>,
+) {
+    for (entity, view) in &views {
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &composite_pipeline,
+            OitCompositePipelineKey {
+                texture_format: if view.hdr {
+                    ViewTarget::TEXTURE_FORMAT_HDR
+                } else {
+                    TextureFormat::bevy_default()
+                },
+                samples: msaa.samples(),
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewOitCompositePipeline(pipeline_id));
+    }
+}
+
+/// A [`bevy_render::render_graph::Node`] that runs the [`OrderIndependentTransparent3d`]
+/// [`SortedRenderPhase`], then composites the result over the main texture of the view.
+#[derive(Default)]
+pub struct OrderIndependentTransparencyNode;
+
+impl ViewNode for OrderIndependentTransparencyNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static SortedRenderPhase<OrderIndependentTransparent3d>,
+        &'static ViewTarget,
+        &'static ViewDepthTexture,
+        &'static ViewOrderIndependentTransparencyTextures,
+        &'static ViewOitCompositePipeline,
+    );
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, phase, target, depth, oit_textures, composite_pipeline_id): QueryItem<
+            Self::ViewQuery,
+        >,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        if phase.items.is_empty() {
+            return Ok(());
+        }
+
+        let Some(composite_pipeline) = world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(composite_pipeline_id.0)
+        else {
+            return Ok(());
+        };
+
+        let view_entity = graph.view_entity();
+        let diagnostics = render_context.diagnostic_recorder();
+
+        {
+            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some("main_oit_transparent_pass_3d"),
+                color_attachments: &[
+                    Some(oit_textures.accumulation.get_attachment()),
+                    Some(oit_textures.revealage.get_attachment()),
+                ],
+                // Transparent fragments are tested against the depth of opaque meshes, but
+                // don't write to it.
+                depth_stencil_attachment: Some(depth.get_attachment(StoreOp::Store)),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            let pass_span = diagnostics.pass_span(&mut render_pass, "main_oit_transparent_pass_3d");
+
+            if let Some(viewport) = camera.viewport.as_ref() {
+                render_pass.set_camera_viewport(viewport);
+            }
+
+            phase.render(&mut render_pass, world, view_entity);
+
+            pass_span.end(&mut render_pass);
+        }
+
+        let bind_group = render_context.render_device().create_bind_group(
+            "oit_composite_bind_group",
+            &world.resource::<OitCompositePipeline>().layout,
+            &BindGroupEntries::sequential((
+                &oit_textures.accumulation.texture.default_view,
+                &oit_textures.revealage.texture.default_view,
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("oit_composite_pass"),
+            color_attachments: &[Some(target.get_color_attachment())],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(viewport);
+        }
+
+        render_pass.set_render_pipeline(composite_pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+// Composites the transparent fragments accumulated by weighted blended order-independent
+// transparency over the main texture of the view.
+// McGuire and Bavoil 2013, "Weighted Blended Order-Independent Transparency"
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var accumulation_texture: texture_2d<f32>;
+@group(0) @binding(1) var revealage_texture: texture_2d<f32>;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let coordinates = vec2<i32>(in.position.xy);
+
+    // The fraction of the background still visible through all the transparent fragments
+    let revealage = textureLoad(revealage_texture, coordinates, 0).r;
+    if revealage >= 1.0 {
+        // No transparent fragments were drawn in this pixel
+        discard;
+    }
+
+    // The weighted average of the colors of the fragments, blended over the background
+    // with their combined coverage
+    let accumulation = textureLoad(accumulation_texture, coordinates, 0);
+    let average_color = accumulation.rgb / max(accumulation.a, 0.00001);
+    return vec4<f32>(average_color, 1.0 - revealage);
+}
@@ -0,0 +1,515 @@
+//! Stacks of post-processing effects, run on each camera after tonemapping,
+//! anti-aliasing and sharpening.
+//!
+//! Effects are registered with [`PostProcessStackAppExt::add_post_process_effect`], and
+//! each camera lists the effects it runs, in order, in its [`PostProcessStack`]. An effect
+//! only runs on the views its [`PostProcessEffect::ViewQuery`] matches. Each effect reads
+//! the output of the previous one and writes to the other main texture of the view, so
+//! effects don't need to modify the render graph or manage textures.
+//!
+//! Most effects are a single fullscreen fragment shader with a settings uniform, which
+//! only need to implement [`FullscreenPostProcess`] and add a [`FullscreenPostProcessPlugin`].
+
+use std::{any::TypeId, marker::PhantomData};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{AssetServer, Handle};
+use bevy_ecs::{
+    prelude::*,
+    query::{QueryItem, QueryState, ReadOnlyQueryData},
+};
+use bevy_render::{
+    extract_component::{
+        ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+        UniformComponentPlugin,
+    },
+    render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
+    render_resource::{
+        binding_types::{sampler, texture_2d, uniform_buffer},
+        encase::private::WriteInto,
+        *,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::BevyDefault,
+    view::{ExtractedView, ViewTarget},
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::TypeIdMap;
+
+use crate::{
+    core_2d::graph::{Core2d, Node2d},
+    core_3d::graph::{Core3d, Node3d},
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+};
+
+/// Adds the [`PostProcessEffects`] and the render graph nodes running the
+/// [`PostProcessStack`] of each camera, after FXAA and contrast adaptive sharpening
+/// and before the end of post-processing of the 2D and 3D graphs.
+pub struct PostProcessStackPlugin;
+
+impl Plugin for PostProcessStackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ExtractComponentPlugin::<PostProcessStack>::default());
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        if !render_app.world().contains_resource::<PostProcessEffects>() {
+            render_app.init_resource::<PostProcessEffects>();
+        }
+
+        render_app
+            .add_render_graph_node::<ViewNodeRunner<PostProcessStackNode>>(
+                Core3d,
+                Node3d::PostProcessStack,
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::ContrastAdaptiveSharpening,
+                    Node3d::PostProcessStack,
+                    Node3d::EndMainPassPostProcessing,
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<PostProcessStackNode>>(
+                Core2d,
+                Node2d::PostProcessStack,
+            )
+            .add_render_graph_edges(
+                Core2d,
+                (
+                    Node2d::ContrastAdaptiveSharpening,
+                    Node2d::PostProcessStack,
+                    Node2d::EndMainPassPostProcessing,
+                ),
+            );
+    }
+}
+
+/// A post-processing effect, run by the [`PostProcessStack`] of cameras.
+pub trait PostProcessEffect: Send + Sync + 'static {
+    /// The query on the view entity. The effect only runs on views it matches, so it
+    /// usually includes the settings component of the effect.
+    type ViewQuery: ReadOnlyQueryData;
+
+    /// Renders the effect.
+    ///
+    /// The effect calls [`ViewTarget::post_process_write`] once it is sure to render,
+    /// then reads the output of the previous effect from its `source` and entirely
+    /// overwrites its `destination`. Effects that can't render yet, for example
+    /// because their pipeline is still compiling, return without calling it.
+    fn run<'w>(
+        &self,
+        render_context: &mut RenderContext<'w>,
+        view_target: &'w ViewTarget,
+        view_query: QueryItem<'w, Self::ViewQuery>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError>;
+}
+
+/// The post-processing effects of a camera, in the order they run.
+///
+/// Effects are identified by the type they were registered with in [`PostProcessEffects`]:
+/// the effect itself for [`PostProcessStackAppExt::add_post_process_effect`], or the
+/// settings component of a [`FullscreenPostProcessPlugin`]. Effects that aren't
+/// registered are skipped.
+///
+/// ```
+/// # use bevy_core_pipeline::post_process::PostProcessStack;
+/// # struct Vignette;
+/// # struct FilmGrain;
+/// let stack = PostProcessStack::default()
+///     .with::<Vignette>()
+///     .with::<FilmGrain>();
+/// assert_eq!(stack.len(), 2);
+/// ```
+#[derive(Component, Clone, Default, Debug, ExtractComponent)]
+pub struct PostProcessStack {
+    effects: Vec<TypeId>,
+}
+
+impl PostProcessStack {
+    /// Appends the effect registered with the type `T` to the stack.
+    pub fn push<T: 'static>(&mut self) {
+        self.effects.push(TypeId::of::<T>());
+    }
+
+    /// Returns the stack with the effect registered with the type `T` appended.
+    pub fn with<T: 'static>(mut self) -> Self {
+        self.push::<T>();
+        self
+    }
+
+    /// Returns the number of effects in the stack.
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    /// Returns `true` if the stack has no effects.
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+}
+
+/// The post-processing effects that cameras can run, by the type they're registered with.
+#[derive(Resource, Default)]
+pub struct PostProcessEffects {
+    effects: TypeIdMap<Box<dyn ErasedPostProcessEffect>>,
+}
+
+impl PostProcessEffects {
+    /// Registers `effect` with the type `T`, replacing the effect registered with it
+    /// before, if any.
+    pub fn insert<T: 'static, E: PostProcessEffect>(&mut self, effect: E, world: &mut World) {
+        let effect = Box::new(PostProcessEffectRunner {
+            effect,
+            view_query: world.query::<E::ViewQuery>(),
+        });
+        self.effects.insert(TypeId::of::<T>(), effect);
+    }
+
+    /// Returns `true` if an effect is registered with the type `T`.
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.effects.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Returns the number of registered effects.
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    /// Returns `true` if no effect is registered.
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+}
+
+/// Registers effects in the [`PostProcessEffects`].
+pub trait PostProcessStackAppExt {
+    /// Registers `effect` in the [`PostProcessEffects`] of the render app, with its own
+    /// type.
+    fn add_post_process_effect<E: PostProcessEffect>(&mut self, effect: E) -> &mut Self;
+}
+
+impl PostProcessStackAppExt for App {
+    fn add_post_process_effect<E: PostProcessEffect>(&mut self, effect: E) -> &mut Self {
+        insert_post_process_effect::<E, E>(self, effect);
+        self
+    }
+}
+
+/// Registers `effect` with the type `T` in the [`PostProcessEffects`] of the render app,
+/// which is added if it's missing.
+fn insert_post_process_effect<T: 'static, E: PostProcessEffect>(app: &mut App, effect: E) {
+    let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+        return;
+    };
+
+    let world = render_app.world_mut();
+    if !world.contains_resource::<PostProcessEffects>() {
+        world.init_resource::<PostProcessEffects>();
+    }
+    world.resource_scope(|world, mut effects: Mut<PostProcessEffects>| {
+        effects.insert::<T, E>(effect, world);
+    });
+}
+
+trait ErasedPostProcessEffect: Send + Sync {
+    fn update(&mut self, world: &mut World);
+
+    fn run<'w>(
+        &self,
+        view_entity: Entity,
+        view_target: &'w ViewTarget,
+        render_context: &mut RenderContext<'w>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError>;
+}
+
+struct PostProcessEffectRunner<E: PostProcessEffect> {
+    effect: E,
+    view_query: QueryState<E::ViewQuery>,
+}
+
+impl<E: PostProcessEffect> ErasedPostProcessEffect for PostProcessEffectRunner<E> {
+    fn update(&mut self, world: &mut World) {
+        self.view_query.update_archetypes(world);
+    }
+
+    fn run<'w>(
+        &self,
+        view_entity: Entity,
+        view_target: &'w ViewTarget,
+        render_context: &mut RenderContext<'w>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let Ok(view_query) = self.view_query.get_manual(world, view_entity) else {
+            return Ok(());
+        };
+        self.effect
+            .run(render_context, view_target, view_query, world)
+    }
+}
+
+/// Runs the [`PostProcessStack`] of a view.
+#[derive(Default)]
+pub struct PostProcessStackNode;
+
+impl ViewNode for PostProcessStackNode {
+    type ViewQuery = (Entity, &'static ViewTarget, &'static PostProcessStack);
+
+    fn update(&mut self, world: &mut World) {
+        world.resource_scope(|world, mut effects: Mut<PostProcessEffects>| {
+            for effect in effects.effects.values_mut() {
+                effect.update(world);
+            }
+        });
+    }
+
+    fn run<'w>(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        (view_entity, view_target, stack): QueryItem<'w, Self::ViewQuery>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let effects = world.resource::<PostProcessEffects>();
+        for id in &stack.effects {
+            let Some(effect) = effects.effects.get(id) else {
+                continue;
+            };
+            effect.run(view_entity, view_target, render_context, world)?;
+        }
+        Ok(())
+    }
+}
+
+/// A post-processing effect made of a fullscreen fragment shader, with its settings in a
+/// component on the camera.
+///
+/// The fragment shader is given the output of the previous effect, a filtering sampler
+/// and the settings:
+///
+/// ```wgsl
+/// @group(0) @binding(0) var screen_texture: texture_2d<f32>;
+/// @group(0) @binding(1) var texture_sampler: sampler;
+/// @group(0) @binding(2) var<uniform> settings: Settings;
+/// ```
+///
+/// Its entry point is `fragment`, and it can use the `FullscreenVertexOutput` of
+/// `bevy_core_pipeline::fullscreen_vertex_shader`.
+pub trait FullscreenPostProcess:
+    ExtractComponent<Out = Self> + ShaderType + ShaderSize + WriteInto + Clone
+{
+    /// Returns the fragment shader of the effect.
+    fn fragment_shader() -> ShaderRef;
+}
+
+/// Registers a [`FullscreenPostProcess`] effect in the [`PostProcessEffects`], with the
+/// type of its settings `E`.
+///
+/// The effect is run on cameras with its component and `E` in their [`PostProcessStack`].
+pub struct FullscreenPostProcessPlugin<E: FullscreenPostProcess>(PhantomData<E>);
+
+impl<E: FullscreenPostProcess> Default for FullscreenPostProcessPlugin<E> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E: FullscreenPostProcess> Plugin for FullscreenPostProcessPlugin<E> {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            ExtractComponentPlugin::<E>::default(),
+            UniformComponentPlugin::<E>::default(),
+        ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedRenderPipelines<FullscreenPostProcessPipeline<E>>>()
+            .add_systems(
+                Render,
+                prepare_fullscreen_post_process_pipelines::<E>.in_set(RenderSet::Prepare),
+            );
+
+        insert_post_process_effect::<E, _>(app, FullscreenPostProcessEffect::<E>(PhantomData));
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<FullscreenPostProcessPipeline<E>>();
+    }
+}
+
+/// The pipeline of a [`FullscreenPostProcess`] effect.
+#[derive(Resource)]
+pub struct FullscreenPostProcessPipeline<E: FullscreenPostProcess> {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+    shader: Handle<Shader>,
+    marker: PhantomData<E>,
+}
+
+impl<E: FullscreenPostProcess> FromWorld for FullscreenPostProcessPipeline<E> {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let layout = render_device.create_bind_group_layout(
+            "fullscreen_post_process_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    uniform_buffer::<E>(true),
+                ),
+            ),
+        );
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor::default());
+
+        let shader = match E::fragment_shader() {
+            ShaderRef::Handle(handle) => handle,
+            ShaderRef::Path(path) => world.resource::<AssetServer>().load(path),
+            ShaderRef::Default => panic!(
+                "{} has no default shader; `FullscreenPostProcess::fragment_shader` must return a handle or a path",
+                std::any::type_name::<E>()
+            ),
+        };
+
+        Self {
+            layout,
+            sampler,
+            shader,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<E: FullscreenPostProcess> SpecializedRenderPipeline for FullscreenPostProcessPipeline<E> {
+    type Key = TextureFormat;
+
+    fn specialize(&self, texture_format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("fullscreen_post_process_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: texture_format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: Vec::new(),
+        }
+    }
+}
+
+/// The pipeline of a [`FullscreenPostProcess`] effect for the format of a view.
+#[derive(Component)]
+pub struct ViewFullscreenPostProcessPipeline<E: FullscreenPostProcess> {
+    pub pipeline_id: CachedRenderPipelineId,
+    marker: PhantomData<E>,
+}
+
+fn prepare_fullscreen_post_process_pipelines<E: FullscreenPostProcess>(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<FullscreenPostProcessPipeline<E>>>,
+    post_process_pipeline: Res<FullscreenPostProcessPipeline<E>>,
+    views: Query<(Entity, &ExtractedView), With<E>>,
+) {
+    for (entity, view) in &views {
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &post_process_pipeline,
+            if view.hdr {
+                ViewTarget::TEXTURE_FORMAT_HDR
+            } else {
+                TextureFormat::bevy_default()
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewFullscreenPostProcessPipeline::<E> {
+                pipeline_id,
+                marker: PhantomData,
+            });
+    }
+}
+
+struct FullscreenPostProcessEffect<E: FullscreenPostProcess>(PhantomData<fn() -> E>);
+
+impl<E: FullscreenPostProcess> PostProcessEffect for FullscreenPostProcessEffect<E> {
+    type ViewQuery = (
+        &'static ViewFullscreenPostProcessPipeline<E>,
+        &'static DynamicUniformIndex<E>,
+    );
+
+    fn run<'w>(
+        &self,
+        render_context: &mut RenderContext<'w>,
+        view_target: &'w ViewTarget,
+        (pipeline, uniform_index): QueryItem<'w, Self::ViewQuery>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let post_process_pipeline = world.resource::<FullscreenPostProcessPipeline<E>>();
+        let Some(pipeline) = world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(pipeline.pipeline_id)
+        else {
+            return Ok(());
+        };
+        let Some(settings_binding) = world
+            .resource::<ComponentUniforms<E>>()
+            .uniforms()
+            .binding()
+        else {
+            return Ok(());
+        };
+
+        let post_process = view_target.post_process_write();
+
+        let bind_group = render_context.render_device().create_bind_group(
+            "fullscreen_post_process_bind_group",
+            &post_process_pipeline.layout,
+            &BindGroupEntries::sequential((
+                post_process.source,
+                &post_process_pipeline.sampler,
+                settings_binding,
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("fullscreen_post_process_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use crate::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, Assets, Handle};
 use bevy_ecs::prelude::*;
-use bevy_reflect::Reflect;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::extract_component::{ExtractComponent, ExtractComponentPlugin};
 use bevy_render::extract_resource::{ExtractResource, ExtractResourcePlugin};
 use bevy_render::render_asset::{RenderAssetUsages, RenderAssets};
@@ -90,10 +90,12 @@ impl Plugin for TonemappingPlugin {
 
         app.register_type::<Tonemapping>();
         app.register_type::<DebandDither>();
+        app.register_type::<ColorGradingLut>();
 
         app.add_plugins((
             ExtractComponentPlugin::<Tonemapping>::default(),
             ExtractComponentPlugin::<DebandDither>::default(),
+            ExtractComponentPlugin::<ColorGradingLut>::default(),
         ));
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
@@ -183,6 +185,7 @@ impl Tonemapping {
 pub struct TonemappingPipelineKey {
     deband_dither: DebandDither,
     tonemapping: Tonemapping,
+    color_grading_lut: bool,
 }
 
 impl SpecializedRenderPipeline for TonemappingPipeline {
@@ -193,6 +196,9 @@ impl SpecializedRenderPipeline for TonemappingPipeline {
         if let DebandDither::Enabled = key.deband_dither {
             shader_defs.push("DEBAND_DITHER".into());
         }
+        if key.color_grading_lut {
+            shader_defs.push("COLOR_GRADING_LUT".into());
+        }
 
         match key.tonemapping {
             Tonemapping::None => shader_defs.push("TONEMAP_METHOD_NONE".into()),
@@ -270,6 +276,9 @@ impl FromWorld for TonemappingPipeline {
         let lut_layout_entries = get_lut_bind_group_layout_entries();
         entries =
             entries.extend_with_indices(((3, lut_layout_entries[0]), (4, lut_layout_entries[1])));
+        // The color grading LUT uses the same kind of bindings as the tonemapping LUT.
+        entries =
+            entries.extend_with_indices(((5, lut_layout_entries[0]), (6, lut_layout_entries[1])));
 
         let render_device = render_world.resource::<RenderDevice>();
         let tonemap_texture_bind_group = render_device
@@ -292,12 +301,24 @@ pub fn prepare_view_tonemapping_pipelines(
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<TonemappingPipeline>>,
     upscaling_pipeline: Res<TonemappingPipeline>,
-    view_targets: Query<(Entity, Option<&Tonemapping>, Option<&DebandDither>), With<ViewTarget>>,
+    gpu_images: Res<RenderAssets<GpuImage>>,
+    view_targets: Query<
+        (
+            Entity,
+            Option<&Tonemapping>,
+            Option<&DebandDither>,
+            Option<&ColorGradingLut>,
+        ),
+        With<ViewTarget>,
+    >,
 ) {
-    for (entity, tonemapping, dither) in view_targets.iter() {
+    for (entity, tonemapping, dither, color_grading_lut) in view_targets.iter() {
         let key = TonemappingPipelineKey {
             deband_dither: *dither.unwrap_or(&DebandDither::Disabled),
             tonemapping: *tonemapping.unwrap_or(&Tonemapping::None),
+            // The LUT is only used once it's loaded, so the image isn't graded with the fallback.
+            color_grading_lut: color_grading_lut
+                .is_some_and(|lut| gpu_images.get(&lut.0).is_some()),
         };
         let pipeline = pipelines.specialize(&pipeline_cache, &upscaling_pipeline, key);
 
@@ -318,6 +339,33 @@ pub enum DebandDither {
     Enabled,
 }
 
+/// A 3D look up table (LUT) applied to the image of a [`Camera`] entity after tonemapping and
+/// [`ColorGrading`](bevy_render::view::ColorGrading), to give it a specific look.
+///
+/// The LUT is a 3D [`Image`], usually loaded from a KTX2 or DDS file with the asset server,
+/// indexed by red, green and blue along its width, height and depth. Its texture format must
+/// be filterable. A LUT that doesn't change the image maps each texel to its own coordinates.
+///
+/// The LUT is applied in the tonemapping pass, so it has no effect on cameras tonemapping in
+/// the material shaders, which is the case when [`Camera::hdr`] is disabled.
+#[derive(Component, Debug, Clone, Reflect, Default, ExtractComponent, PartialEq, Eq)]
+#[extract_component_filter(With<Camera>)]
+#[reflect(Component, Default)]
+pub struct ColorGradingLut(pub Handle<Image>);
+
+/// Returns the texture view and sampler of the [`ColorGradingLut`] of a view,
+/// or of the fallback image if the view doesn't have a loaded LUT.
+pub fn get_color_grading_lut_bindings<'a>(
+    images: &'a RenderAssets<GpuImage>,
+    color_grading_lut: Option<&ColorGradingLut>,
+    fallback_image: &'a FallbackImage,
+) -> (&'a TextureView, &'a Sampler) {
+    let lut_image = color_grading_lut
+        .and_then(|lut| images.get(&lut.0))
+        .unwrap_or(&fallback_image.d3);
+    (&lut_image.texture_view, &lut_image.sampler)
+}
+
 pub fn get_lut_bindings<'a>(
     images: &'a RenderAssets<GpuImage>,
     tonemapping_luts: &'a TonemappingLuts,
@@ -15,11 +15,19 @@ use bevy_render::{
     view::{ViewTarget, ViewUniformOffset, ViewUniforms},
 };
 
-use super::{get_lut_bindings, Tonemapping};
+use super::{get_color_grading_lut_bindings, get_lut_bindings, ColorGradingLut, Tonemapping};
 
 #[derive(Default)]
 pub struct TonemappingNode {
-    cached_bind_group: Mutex<Option<(BufferId, TextureViewId, TextureViewId, BindGroup)>>,
+    cached_bind_group: Mutex<
+        Option<(
+            BufferId,
+            TextureViewId,
+            TextureViewId,
+            TextureViewId,
+            BindGroup,
+        )>,
+    >,
     last_tonemapping: Mutex<Option<Tonemapping>>,
 }
 
@@ -29,17 +37,23 @@ impl ViewNode for TonemappingNode {
         &'static ViewTarget,
         &'static ViewTonemappingPipeline,
         &'static Tonemapping,
+        Option<&'static ColorGradingLut>,
     );
 
     fn run(
         &self,
         _graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
-        (view_uniform_offset, target, view_tonemapping_pipeline, tonemapping): QueryItem<
-            Self::ViewQuery,
-        >,
+        view: QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
+        let (
+            view_uniform_offset,
+            target,
+            view_tonemapping_pipeline,
+            tonemapping,
+            color_grading_lut,
+        ) = view;
         let pipeline_cache = world.resource::<PipelineCache>();
         let tonemapping_pipeline = world.resource::<TonemappingPipeline>();
         let gpu_images = world.get_resource::<RenderAssets<GpuImage>>().unwrap();
@@ -71,12 +85,16 @@ impl ViewNode for TonemappingNode {
             *last_tonemapping = Some(*tonemapping);
         }
 
+        let color_grading_lut_bindings =
+            get_color_grading_lut_bindings(gpu_images, color_grading_lut, fallback_image);
+
         let mut cached_bind_group = self.cached_bind_group.lock().unwrap();
         let bind_group = match &mut *cached_bind_group {
-            Some((buffer_id, texture_id, lut_id, bind_group))
+            Some((buffer_id, texture_id, lut_id, color_grading_lut_id, bind_group))
                 if view_uniforms_id == *buffer_id
                     && source.id() == *texture_id
                     && *lut_id != fallback_image.d3.texture_view.id()
+                    && *color_grading_lut_id == color_grading_lut_bindings.0.id()
                     && !tonemapping_changed =>
             {
                 bind_group
@@ -96,13 +114,16 @@ impl ViewNode for TonemappingNode {
                         &tonemapping_pipeline.sampler,
                         lut_bindings.0,
                         lut_bindings.1,
+                        color_grading_lut_bindings.0,
+                        color_grading_lut_bindings.1,
                     )),
                 );
 
-                let (_, _, _, bind_group) = cached_bind_group.insert((
+                let (_, _, _, _, bind_group) = cached_bind_group.insert((
                     view_uniforms_id,
                     source.id(),
                     lut_bindings.0.id(),
+                    color_grading_lut_bindings.0.id(),
                     bind_group,
                 ));
                 bind_group
@@ -12,6 +12,17 @@
 @group(0) @binding(2) var hdr_sampler: sampler;
 @group(0) @binding(3) var dt_lut_texture: texture_3d<f32>;
 @group(0) @binding(4) var dt_lut_sampler: sampler;
+@group(0) @binding(5) var color_grading_lut_texture: texture_3d<f32>;
+@group(0) @binding(6) var color_grading_lut_sampler: sampler;
+
+#ifdef COLOR_GRADING_LUT
+fn sample_color_grading_lut(color: vec3<f32>) -> vec3<f32> {
+    // Sample the centers of the edge texels for inputs of 0.0 and 1.0
+    let lut_size = vec3<f32>(textureDimensions(color_grading_lut_texture));
+    let uvw = saturate(color) * ((lut_size - 1.0) / lut_size) + 0.5 / lut_size;
+    return textureSampleLevel(color_grading_lut_texture, color_grading_lut_sampler, uvw, 0.0).rgb;
+}
+#endif
 
 @fragment
 fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
@@ -19,6 +30,10 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
 
     var output_rgb = tone_mapping(hdr_color, view.color_grading).rgb;
 
+#ifdef COLOR_GRADING_LUT
+    output_rgb = sample_color_grading_lut(output_rgb);
+#endif
+
 #ifdef DEBAND_DITHER
     output_rgb = powsafe(output_rgb.rgb, 1.0 / 2.2);
     output_rgb = output_rgb + screen_space_dither(in.position.xy);
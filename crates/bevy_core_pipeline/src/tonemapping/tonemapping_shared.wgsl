@@ -154,6 +154,13 @@ fn powsafe(color: vec3<f32>, power: f32) -> vec3<f32> {
     return pow(abs(color), vec3(power)) * sign(color);
 }
 
+// Lift offsets the shadows while keeping white in place, gamma bends the midtones,
+// and gain scales the whole range.
+fn lift_gamma_gain(color: vec3<f32>, lift: vec3<f32>, gamma: vec3<f32>, gain: vec3<f32>) -> vec3<f32> {
+    let lifted = gain * (color + lift * (vec3(1.0) - color));
+    return pow(abs(lifted), vec3(1.0) / max(gamma, vec3(1e-4))) * sign(lifted);
+}
+
 /*
     Increase color saturation of the given color data.
     :param color: expected sRGB primaries input
@@ -312,6 +319,7 @@ fn tone_mapping(in: vec4<f32>, color_grading: ColorGrading) -> vec4<f32> {
 
     // Perceptual post tonemapping grading
     color = saturation(color, color_grading.post_saturation);
+    color = lift_gamma_gain(color, color_grading.lift, color_grading.post_gamma, color_grading.gain);
     
     return vec4(color, in.a);
 }
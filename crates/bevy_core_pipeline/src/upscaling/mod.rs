@@ -1,8 +1,9 @@
-use crate::blit::{BlitPipeline, BlitPipelineKey};
+use crate::blit::{BlitHdrOutputUniform, BlitPipeline, BlitPipelineKey};
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
-use bevy_render::camera::{CameraOutputMode, ExtractedCamera};
-use bevy_render::view::ViewTarget;
+use bevy_render::camera::{CameraOutputMode, ExtractedCamera, NormalizedRenderTarget};
+use bevy_render::renderer::{RenderDevice, RenderQueue};
+use bevy_render::view::{ExtractedWindows, ViewTarget};
 use bevy_render::{render_resource::*, Render, RenderApp, RenderSet};
 
 mod node;
@@ -14,10 +15,17 @@ pub struct UpscalingPlugin;
 impl Plugin for UpscalingPlugin {
     fn build(&self, app: &mut App) {
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
-            render_app.add_systems(
-                Render,
-                prepare_view_upscaling_pipelines.in_set(RenderSet::Prepare),
-            );
+            render_app
+                .init_resource::<UpscalingHdrOutputUniforms>()
+                .add_systems(
+                    Render,
+                    (
+                        prepare_view_upscaling_pipelines.in_set(RenderSet::Prepare),
+                        prepare_upscaling_hdr_output_bind_group
+                            .in_set(RenderSet::PrepareBindGroups)
+                            .after(prepare_view_upscaling_pipelines),
+                    ),
+                );
         }
     }
 }
@@ -25,13 +33,32 @@ impl Plugin for UpscalingPlugin {
 #[derive(Component)]
 pub struct ViewUpscalingPipeline(CachedRenderPipelineId);
 
+/// The offset of the [`BlitHdrOutputUniform`] of a view drawn to a window with HDR output.
+#[derive(Component)]
+pub struct ViewUpscalingHdrOutputOffset(u32);
+
+/// The HDR output scales of the views drawn to windows with HDR output, applied when the view
+/// is upscaled to the window so that it covers everything drawn to the view, including UI.
+#[derive(Resource, Default)]
+pub struct UpscalingHdrOutputUniforms {
+    uniforms: DynamicUniformBuffer<BlitHdrOutputUniform>,
+    bind_group: Option<BindGroup>,
+}
+
+#[allow(clippy::too_many_arguments)]
 fn prepare_view_upscaling_pipelines(
     mut commands: Commands,
     mut pipeline_cache: ResMut<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
     blit_pipeline: Res<BlitPipeline>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    windows: Res<ExtractedWindows>,
+    mut hdr_output_uniforms: ResMut<UpscalingHdrOutputUniforms>,
     view_targets: Query<(Entity, &ViewTarget, Option<&ExtractedCamera>)>,
 ) {
+    hdr_output_uniforms.uniforms.clear();
+
     for (entity, view_target, camera) in view_targets.iter() {
         let blend_state = if let Some(ExtractedCamera {
             output_mode: CameraOutputMode::Write { blend_state, .. },
@@ -42,18 +69,51 @@ fn prepare_view_upscaling_pipelines(
         } else {
             None
         };
+
+        let hdr_output_scale = match camera.and_then(|camera| camera.target.as_ref()) {
+            Some(NormalizedRenderTarget::Window(window_ref)) => windows
+                .get(&window_ref.entity())
+                .map_or(1.0, |window| window.hdr_output_scale()),
+            _ => 1.0,
+        };
+        let hdr_output = hdr_output_scale != 1.0;
+
         let key = BlitPipelineKey {
             texture_format: view_target.out_texture_format(),
             blend_state,
             samples: 1,
+            hdr_output,
         };
         let pipeline = pipelines.specialize(&pipeline_cache, &blit_pipeline, key);
 
         // Ensure the pipeline is loaded before continuing the frame to prevent frames without any GPU work submitted
         pipeline_cache.block_on_render_pipeline(pipeline);
 
-        commands
-            .entity(entity)
-            .insert(ViewUpscalingPipeline(pipeline));
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(ViewUpscalingPipeline(pipeline));
+        if hdr_output {
+            let offset = hdr_output_uniforms.uniforms.push(&BlitHdrOutputUniform {
+                scale: hdr_output_scale,
+            });
+            entity_commands.insert(ViewUpscalingHdrOutputOffset(offset));
+        }
     }
+
+    hdr_output_uniforms
+        .uniforms
+        .write_buffer(&render_device, &render_queue);
+}
+
+fn prepare_upscaling_hdr_output_bind_group(
+    render_device: Res<RenderDevice>,
+    blit_pipeline: Res<BlitPipeline>,
+    mut hdr_output_uniforms: ResMut<UpscalingHdrOutputUniforms>,
+) {
+    hdr_output_uniforms.bind_group = hdr_output_uniforms.uniforms.binding().map(|binding| {
+        render_device.create_bind_group(
+            "upscaling_hdr_output_bind_group",
+            &blit_pipeline.hdr_output_bind_group,
+            &BindGroupEntries::single(binding),
+        )
+    });
 }
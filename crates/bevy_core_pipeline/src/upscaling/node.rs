@@ -1,4 +1,7 @@
-use crate::{blit::BlitPipeline, upscaling::ViewUpscalingPipeline};
+use crate::{
+    blit::BlitPipeline,
+    upscaling::{UpscalingHdrOutputUniforms, ViewUpscalingHdrOutputOffset, ViewUpscalingPipeline},
+};
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_render::{
     camera::{CameraOutputMode, ExtractedCamera},
@@ -22,13 +25,14 @@ impl ViewNode for UpscalingNode {
         &'static ViewTarget,
         &'static ViewUpscalingPipeline,
         Option<&'static ExtractedCamera>,
+        Option<&'static ViewUpscalingHdrOutputOffset>,
     );
 
     fn run(
         &self,
         _graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
-        (target, upscaling_target, camera): QueryItem<Self::ViewQuery>,
+        (target, upscaling_target, camera, hdr_output_offset): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
         let pipeline_cache = world.get_resource::<PipelineCache>().unwrap();
@@ -67,6 +71,17 @@ impl ViewNode for UpscalingNode {
             return Ok(());
         };
 
+        let hdr_output = match hdr_output_offset {
+            Some(offset) => {
+                let Some(bind_group) = &world.resource::<UpscalingHdrOutputUniforms>().bind_group
+                else {
+                    return Ok(());
+                };
+                Some((bind_group, offset.0))
+            }
+            None => None,
+        };
+
         let pass_descriptor = RenderPassDescriptor {
             label: Some("upscaling_pass"),
             color_attachments: &[Some(RenderPassColorAttachment {
@@ -88,6 +103,9 @@ impl ViewNode for UpscalingNode {
 
         render_pass.set_pipeline(pipeline);
         render_pass.set_bind_group(0, bind_group, &[]);
+        if let Some((hdr_output_bind_group, offset)) = hdr_output {
+            render_pass.set_bind_group(1, hdr_output_bind_group, &[offset]);
+        }
         render_pass.draw(0..3, 0..1);
 
         Ok(())
@@ -0,0 +1,146 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, Ident, Path};
+
+use crate::bevy_ecs_path;
+
+pub fn derive_binary_serialize(input: TokenStream) -> TokenStream {
+    let mut ast = parse_macro_input!(input as DeriveInput);
+
+    let mut binary_path = bevy_ecs_path();
+    binary_path.segments.push(format_ident!("binary").into());
+    let mut trait_path = binary_path.clone();
+    trait_path
+        .segments
+        .push(format_ident!("BinarySerialize").into());
+    let mut error_path = binary_path;
+    error_path
+        .segments
+        .push(format_ident!("BinaryError").into());
+
+    for param in ast.generics.type_params_mut() {
+        param.bounds.push(parse_quote!(#trait_path));
+    }
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let struct_name = &ast.ident;
+
+    let (serialize, deserialize) = match &ast.data {
+        Data::Struct(data) => {
+            let constructor = quote!(Self);
+            let (pattern, serialize_fields, deserialize_fields) =
+                fields(&data.fields, &trait_path, &constructor);
+            (
+                quote! {
+                    let #pattern = self;
+                    #serialize_fields
+                },
+                quote! {
+                    ::core::result::Result::Ok(#deserialize_fields)
+                },
+            )
+        }
+        Data::Enum(data) => {
+            let mut serialize_arms = Vec::new();
+            let mut deserialize_arms = Vec::new();
+            for (index, variant) in data.variants.iter().enumerate() {
+                let Ok(index) = u32::try_from(index) else {
+                    return syn::Error::new_spanned(variant, "too many variants")
+                        .into_compile_error()
+                        .into();
+                };
+                let variant_name = &variant.ident;
+                let constructor = quote!(Self::#variant_name);
+                let (pattern, serialize_fields, deserialize_fields) =
+                    fields(&variant.fields, &trait_path, &constructor);
+                serialize_arms.push(quote! {
+                    #pattern => {
+                        #trait_path::serialize(&#index, __buffer);
+                        #serialize_fields
+                    }
+                });
+                deserialize_arms.push(quote! {
+                    #index => ::core::result::Result::Ok(#deserialize_fields),
+                });
+            }
+            let serialize = if serialize_arms.is_empty() {
+                quote!(match *self {})
+            } else {
+                quote! {
+                    match self {
+                        #(#serialize_arms)*
+                    }
+                }
+            };
+            (
+                serialize,
+                quote! {
+                    match <u32 as #trait_path>::deserialize(__bytes)? {
+                        #(#deserialize_arms)*
+                        _ => ::core::result::Result::Err(#error_path::InvalidValue(
+                            ::core::any::type_name::<Self>(),
+                        )),
+                    }
+                },
+            )
+        }
+        Data::Union(_) => {
+            return syn::Error::new(
+                Span::call_site(),
+                "BinarySerialize cannot be derived for unions",
+            )
+            .into_compile_error()
+            .into();
+        }
+    };
+
+    quote! {
+        impl #impl_generics #trait_path for #struct_name #ty_generics #where_clause {
+            fn serialize(&self, __buffer: &mut ::std::vec::Vec<u8>) {
+                #serialize
+            }
+
+            fn deserialize(__bytes: &mut &[u8]) -> ::core::result::Result<Self, #error_path> {
+                #deserialize
+            }
+        }
+    }
+    .into()
+}
+
+/// Returns the pattern binding every field of `fields`, the code serializing the bound fields in order,
+/// and the expression building a value from deserialized fields with `constructor`.
+fn fields(
+    fields: &Fields,
+    trait_path: &Path,
+    constructor: &TokenStream2,
+) -> (TokenStream2, TokenStream2, TokenStream2) {
+    let bindings: Vec<Ident> = (0..fields.len())
+        .map(|index| format_ident!("__field_{}", index))
+        .collect();
+    let serialize = quote! {
+        #(#trait_path::serialize(#bindings, __buffer);)*
+    };
+    match fields {
+        Fields::Named(fields) => {
+            let names: Vec<_> = fields.named.iter().map(|field| &field.ident).collect();
+            (
+                quote!(#constructor { #(#names: #bindings),* }),
+                serialize,
+                quote!(#constructor { #(#names: #trait_path::deserialize(__bytes)?),* }),
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let deserialize = fields
+                .unnamed
+                .iter()
+                .map(|_| quote!(#trait_path::deserialize(__bytes)?));
+            (
+                quote!(#constructor(#(#bindings),*)),
+                serialize,
+                quote!(#constructor(#(#deserialize),*)),
+            )
+        }
+        Fields::Unit => (constructor.clone(), serialize, constructor.clone()),
+    }
+}
@@ -1,7 +1,10 @@
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{parse_macro_input, parse_quote, DeriveInput, Ident, LitStr, Path, Result};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Ident, Index, LitBool, LitInt, LitStr,
+    Member, Path, Result,
+};
 
 pub fn derive_event(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
@@ -25,6 +28,11 @@ pub fn derive_resource(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
     let bevy_ecs_path: Path = crate::bevy_ecs_path();
 
+    let init = match parse_resource_attr(&ast) {
+        Ok(init) => init,
+        Err(e) => return e.into_compile_error().into(),
+    };
+
     ast.generics
         .make_where_clause()
         .predicates
@@ -33,12 +41,46 @@ pub fn derive_resource(input: TokenStream) -> TokenStream {
     let struct_name = &ast.ident;
     let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
 
+    let from_world = init.map(|init| {
+        quote! {
+            impl #impl_generics #bevy_ecs_path::world::FromWorld for #struct_name #type_generics #where_clause {
+                fn from_world(world: &mut #bevy_ecs_path::world::World) -> Self {
+                    #init(world)
+                }
+            }
+        }
+    });
+
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::system::Resource for #struct_name #type_generics #where_clause {
         }
+
+        #from_world
     })
 }
 
+pub const RESOURCE: &str = "resource";
+pub const INIT: &str = "init";
+
+/// Returns the function given with `#[resource(init = path)]`, if any.
+fn parse_resource_attr(ast: &DeriveInput) -> Result<Option<Path>> {
+    let mut init = None;
+    for meta in ast.attrs.iter().filter(|a| a.path().is_ident(RESOURCE)) {
+        meta.parse_nested_meta(|nested| {
+            if nested.path.is_ident(INIT) {
+                if init.is_some() {
+                    return Err(nested.error(format!("`{INIT}` is set more than once")));
+                }
+                init = Some(nested.value()?.parse::<Path>()?);
+                Ok(())
+            } else {
+                Err(nested.error("Unsupported attribute"))
+            }
+        })?;
+    }
+    Ok(init)
+}
+
 pub fn derive_component(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
     let bevy_ecs_path: Path = crate::bevy_ecs_path();
@@ -49,6 +91,44 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     };
 
     let storage = storage_path(&bevy_ecs_path, attrs.storage);
+    let capacity_hint = attrs.capacity.map(|capacity| {
+        quote! {
+            const CAPACITY_HINT: ::core::option::Option<usize> = ::core::option::Option::Some(#capacity);
+        }
+    });
+    let change_detection = attrs.change_detection.map(|change_detection| {
+        quote! {
+            const CHANGE_DETECTION: bool = #change_detection;
+        }
+    });
+    let register_hooks = [
+        (attrs.on_add, quote!(on_add)),
+        (attrs.on_insert, quote!(on_insert)),
+        (attrs.on_remove, quote!(on_remove)),
+    ]
+    .into_iter()
+    .filter_map(|(hook, register)| hook.map(|hook| quote!(hooks.#register(#hook);)))
+    .collect::<Vec<_>>();
+    let register_component_hooks = (!register_hooks.is_empty()).then(|| {
+        quote! {
+            fn register_component_hooks(hooks: &mut #bevy_ecs_path::component::ComponentHooks) {
+                #(#register_hooks)*
+            }
+        }
+    });
+
+    let tracked_fields = match parse_tracked_fields(&ast) {
+        Ok(fields) => fields,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    let check_tracked_ticks = (!tracked_fields.is_empty()).then(|| {
+        quote! {
+            const CHECK_TRACKED_TICKS: ::core::option::Option<fn(&mut Self, #bevy_ecs_path::component::Tick)> =
+                ::core::option::Option::Some(|this: &mut Self, change_tick: #bevy_ecs_path::component::Tick| {
+                    #(this.#tracked_fields.check_change_tick(change_tick);)*
+                });
+        }
+    });
 
     ast.generics
         .make_where_clause()
@@ -61,15 +141,30 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::component::Component for #struct_name #type_generics #where_clause {
             const STORAGE_TYPE: #bevy_ecs_path::component::StorageType = #storage;
+            #capacity_hint
+            #change_detection
+            #check_tracked_ticks
+            #register_component_hooks
         }
     })
 }
 
 pub const COMPONENT: &str = "component";
 pub const STORAGE: &str = "storage";
+pub const CAPACITY: &str = "capacity";
+pub const CHANGE_DETECTION: &str = "change_detection";
+pub const ON_ADD: &str = "on_add";
+pub const ON_INSERT: &str = "on_insert";
+pub const ON_REMOVE: &str = "on_remove";
+pub const TRACKED: &str = "tracked";
 
 struct Attrs {
     storage: StorageTy,
+    capacity: Option<usize>,
+    change_detection: Option<bool>,
+    on_add: Option<Path>,
+    on_insert: Option<Path>,
+    on_remove: Option<Path>,
 }
 
 #[derive(Clone, Copy)]
@@ -85,6 +180,11 @@ const SPARSE_SET: &str = "SparseSet";
 fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
     let mut attrs = Attrs {
         storage: StorageTy::Table,
+        capacity: None,
+        change_detection: None,
+        on_add: None,
+        on_insert: None,
+        on_remove: None,
     };
 
     for meta in ast.attrs.iter().filter(|a| a.path().is_ident(COMPONENT)) {
@@ -100,6 +200,28 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
                     }
                 };
                 Ok(())
+            } else if nested.path.is_ident(CAPACITY) {
+                attrs.capacity = Some(nested.value()?.parse::<LitInt>()?.base10_parse()?);
+                Ok(())
+            } else if nested.path.is_ident(CHANGE_DETECTION) {
+                if attrs.change_detection.is_some() {
+                    return Err(nested.error(format!("`{CHANGE_DETECTION}` is set more than once")));
+                }
+                attrs.change_detection = Some(nested.value()?.parse::<LitBool>()?.value);
+                Ok(())
+            } else if let Some((name, hook)) = [
+                (ON_ADD, &mut attrs.on_add),
+                (ON_INSERT, &mut attrs.on_insert),
+                (ON_REMOVE, &mut attrs.on_remove),
+            ]
+            .into_iter()
+            .find(|(name, _)| nested.path.is_ident(name))
+            {
+                if hook.is_some() {
+                    return Err(nested.error(format!("`{name}` hook is set more than once")));
+                }
+                *hook = Some(nested.value()?.parse::<Path>()?);
+                Ok(())
             } else {
                 Err(nested.error("Unsupported attribute"))
             }
@@ -109,6 +231,31 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
     Ok(attrs)
 }
 
+/// Returns the struct fields marked with `#[component(tracked)]`.
+fn parse_tracked_fields(ast: &DeriveInput) -> Result<Vec<Member>> {
+    let Data::Struct(data) = &ast.data else {
+        return Ok(Vec::new());
+    };
+
+    let mut tracked = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        for meta in field.attrs.iter().filter(|a| a.path().is_ident(COMPONENT)) {
+            meta.parse_nested_meta(|nested| {
+                if nested.path.is_ident(TRACKED) {
+                    tracked.push(match &field.ident {
+                        Some(ident) => Member::Named(ident.clone()),
+                        None => Member::Unnamed(Index::from(index)),
+                    });
+                    Ok(())
+                } else {
+                    Err(nested.error("Unsupported attribute"))
+                }
+            })?;
+        }
+    }
+    Ok(tracked)
+}
+
 fn storage_path(bevy_ecs_path: &Path, ty: StorageTy) -> TokenStream2 {
     let storage_type = match ty {
         StorageTy::Table => Ident::new("Table", Span::call_site()),
@@ -4,6 +4,7 @@
 
 extern crate proc_macro;
 
+mod binary;
 mod component;
 mod query_data;
 mod query_filter;
@@ -27,12 +28,18 @@ enum BundleFieldKind {
 
 const BUNDLE_ATTRIBUTE_NAME: &str = "bundle";
 const BUNDLE_ATTRIBUTE_IGNORE_NAME: &str = "ignore";
+const SYSTEM_PARAM_ATTRIBUTE_NAME: &str = "system_param";
+const SYSTEM_PARAM_ATTRIBUTE_BUILDER_NAME: &str = "builder";
 
 #[proc_macro_derive(Bundle, attributes(bundle))]
 pub fn derive_bundle(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     let ecs_path = bevy_ecs_path();
 
+    if let syn::Data::Enum(data) = &ast.data {
+        return derive_variant_bundle(&ast, data, &ecs_path);
+    }
+
     let named_fields = match get_struct_fields(&ast.data) {
         Ok(fields) => fields,
         Err(e) => return e.into_compile_error().into(),
@@ -41,26 +48,10 @@ pub fn derive_bundle(input: TokenStream) -> TokenStream {
     let mut field_kind = Vec::with_capacity(named_fields.len());
 
     for field in named_fields {
-        for attr in field
-            .attrs
-            .iter()
-            .filter(|a| a.path().is_ident(BUNDLE_ATTRIBUTE_NAME))
-        {
-            if let Err(error) = attr.parse_nested_meta(|meta| {
-                if meta.path.is_ident(BUNDLE_ATTRIBUTE_IGNORE_NAME) {
-                    field_kind.push(BundleFieldKind::Ignore);
-                    Ok(())
-                } else {
-                    Err(meta.error(format!(
-                        "Invalid bundle attribute. Use `{BUNDLE_ATTRIBUTE_IGNORE_NAME}`"
-                    )))
-                }
-            }) {
-                return error.into_compile_error().into();
-            }
+        match bundle_field_kind(field) {
+            Ok(kind) => field_kind.push(kind),
+            Err(error) => return error.into_compile_error().into(),
         }
-
-        field_kind.push(BundleFieldKind::Component);
     }
 
     let field = named_fields
@@ -108,11 +99,19 @@ pub fn derive_bundle(input: TokenStream) -> TokenStream {
                 }
             }
 
-            BundleFieldKind::Ignore => {
-                field_from_components.push(quote! {
-                    #field: ::std::default::Default::default(),
-                });
-            }
+            BundleFieldKind::Ignore => match field {
+                Some(field) => {
+                    field_from_components.push(quote! {
+                        #field: ::std::default::Default::default(),
+                    });
+                }
+                None => {
+                    let index = syn::Index::from(i);
+                    field_from_components.push(quote! {
+                        #index: ::std::default::Default::default(),
+                    });
+                }
+            },
         }
     }
     let generics = ast.generics;
@@ -157,6 +156,103 @@ pub fn derive_bundle(input: TokenStream) -> TokenStream {
     })
 }
 
+fn bundle_field_kind(field: &syn::Field) -> syn::Result<BundleFieldKind> {
+    let mut kind = BundleFieldKind::Component;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident(BUNDLE_ATTRIBUTE_NAME))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(BUNDLE_ATTRIBUTE_IGNORE_NAME) {
+                kind = BundleFieldKind::Ignore;
+                Ok(())
+            } else {
+                Err(meta.error(format!(
+                    "Invalid bundle attribute. Use `{BUNDLE_ATTRIBUTE_IGNORE_NAME}`"
+                )))
+            }
+        })?;
+    }
+    Ok(kind)
+}
+
+/// Implements `VariantBundle` for an enum whose variants each hold the components of one bundle.
+fn derive_variant_bundle(
+    ast: &DeriveInput,
+    data: &syn::DataEnum,
+    ecs_path: &syn::Path,
+) -> TokenStream {
+    let mut field_component_ids = Vec::new();
+    let mut variant_inserts = Vec::new();
+    for variant in &data.variants {
+        let variant_name = &variant.ident;
+        let mut bindings = Vec::new();
+        let mut components = Vec::new();
+        for (i, field) in variant.fields.iter().enumerate() {
+            let binding = format_ident!("__field_{}", i);
+            match bundle_field_kind(field) {
+                Ok(BundleFieldKind::Component) => {
+                    let field_type = &field.ty;
+                    field_component_ids.push(quote! {
+                        <#field_type as #ecs_path::bundle::Bundle>::component_ids(components, storages, &mut *ids);
+                    });
+                    components.push(binding.clone());
+                }
+                Ok(BundleFieldKind::Ignore) => {}
+                Err(error) => return error.into_compile_error().into(),
+            }
+            bindings.push(binding);
+        }
+
+        let pattern = match &variant.fields {
+            syn::Fields::Named(fields) => {
+                let names = fields.named.iter().map(|field| &field.ident);
+                quote!(Self::#variant_name { #(#names: #bindings),* })
+            }
+            syn::Fields::Unnamed(_) => quote!(Self::#variant_name(#(#bindings),*)),
+            syn::Fields::Unit => quote!(Self::#variant_name),
+        };
+        // Tuples are bundles of up to 15 bundles, so larger variants are inserted as nested tuples.
+        let chunks = components.chunks(15).map(|chunk| quote!((#(#chunk,)*)));
+        variant_inserts.push(quote! {
+            #pattern => {
+                entity.insert((#(#chunks,)*));
+            }
+        });
+    }
+
+    let generics = &ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let enum_name = &ast.ident;
+    let insert = if variant_inserts.is_empty() {
+        quote!(match self {})
+    } else {
+        quote! {
+            match self {
+                #(#variant_inserts)*
+            }
+        }
+    };
+
+    TokenStream::from(quote! {
+        impl #impl_generics #ecs_path::bundle::VariantBundle for #enum_name #ty_generics #where_clause {
+            fn component_ids(
+                components: &mut #ecs_path::component::Components,
+                storages: &mut #ecs_path::storage::Storages,
+                ids: &mut impl FnMut(#ecs_path::component::ComponentId)
+            ){
+                #(#field_component_ids)*
+            }
+
+            #[allow(unused_variables)]
+            fn insert_into(self, entity: &mut #ecs_path::world::EntityWorldMut) {
+                #insert
+            }
+        }
+    })
+}
+
 fn get_idents(fmt_string: fn(usize) -> String, count: usize) -> Vec<Ident> {
     (0..count)
         .map(|i| Ident::new(&fmt_string(i), Span::call_site()))
@@ -274,6 +370,9 @@ pub fn impl_param_set(_input: TokenStream) -> TokenStream {
 }
 
 /// Implement `SystemParam` to use a struct as a parameter in a system
+///
+/// With `#[system_param(builder)]`, this also generates a `{Name}Builder` struct
+/// implementing `SystemParamBuilder`, with a builder for each field.
 #[proc_macro_derive(SystemParam, attributes(system_param))]
 pub fn derive_system_param(input: TokenStream) -> TokenStream {
     let token_stream = input.clone();
@@ -292,6 +391,26 @@ pub fn derive_system_param(input: TokenStream) -> TokenStream {
     };
     let path = bevy_ecs_path();
 
+    let mut generate_builder = false;
+    for attr in &ast.attrs {
+        if !attr.path().is_ident(SYSTEM_PARAM_ATTRIBUTE_NAME) {
+            continue;
+        }
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(SYSTEM_PARAM_ATTRIBUTE_BUILDER_NAME) {
+                generate_builder = true;
+                Ok(())
+            } else {
+                Err(meta.error(format!(
+                    "invalid system param attribute, expected `{SYSTEM_PARAM_ATTRIBUTE_BUILDER_NAME}`"
+                )))
+            }
+        });
+        if let Err(error) = result {
+            return error.into_compile_error().into();
+        }
+    }
+
     let mut field_locals = Vec::new();
     let mut fields = Vec::new();
     let mut field_types = Vec::new();
@@ -397,7 +516,64 @@ pub fn derive_system_param(input: TokenStream) -> TokenStream {
 
     let struct_name = &ast.ident;
     let state_struct_visibility = &ast.vis;
-    let state_struct_name = ensure_no_collision(format_ident!("FetchState"), token_stream);
+    let state_struct_name = ensure_no_collision(format_ident!("FetchState"), token_stream.clone());
+
+    let (builder_struct, builder_impl) = if generate_builder {
+        let builder_name = format_ident!("{struct_name}Builder");
+        let builder_type_parameters: Vec<_> = (0..field_types.len())
+            .map(|i| ensure_no_collision(format_ident!("B{i}"), token_stream.clone()))
+            .collect();
+        let builder_doc = format!(
+            "Builds the state of [`{struct_name}`], with a `SystemParamBuilder` for each of its fields."
+        );
+        let field_visibilities = field_definitions.iter().map(|field| &field.vis);
+        let builder_struct = match &field_definitions {
+            syn::Fields::Named(_) => quote! {
+                #[doc = #builder_doc]
+                #state_struct_visibility struct #builder_name<#(#builder_type_parameters,)*> {
+                    #(#field_visibilities #fields: #builder_type_parameters,)*
+                }
+            },
+            _ => quote! {
+                #[doc = #builder_doc]
+                #state_struct_visibility struct #builder_name<#(#builder_type_parameters,)*>(
+                    #(#field_visibilities #builder_type_parameters,)*
+                );
+            },
+        };
+
+        let mut builder_generics = generics.clone();
+        for (parameter, field_type) in builder_type_parameters.iter().zip(&field_types) {
+            builder_generics
+                .params
+                .push(parse_quote!(#parameter: #path::system::SystemParamBuilder<#field_type>));
+        }
+        let (builder_impl_generics, _, builder_where_clause) = builder_generics.split_for_impl();
+        let builder_impl = quote! {
+            // SAFETY: The state of the fields is built by their builders, which register their access.
+            unsafe impl #builder_impl_generics #path::system::SystemParamBuilder<#struct_name #ty_generics>
+                for #builder_name<#(#builder_type_parameters,)*> #builder_where_clause
+            {
+                fn build(
+                    self,
+                    world: &mut #path::world::World,
+                    system_meta: &mut #path::system::SystemMeta,
+                ) -> <#struct_name #ty_generics as #path::system::SystemParam>::State {
+                    let #builder_name { #(#fields: #field_locals,)* } = self;
+                    #state_struct_name {
+                        state: #path::system::SystemParamBuilder::<(#(#tuple_types,)*)>::build(
+                            (#(#tuple_patterns,)*),
+                            world,
+                            system_meta,
+                        ),
+                    }
+                }
+            }
+        };
+        (builder_struct, builder_impl)
+    } else {
+        (quote! {}, quote! {})
+    };
 
     TokenStream::from(quote! {
         // We define the FetchState struct in an anonymous scope to avoid polluting the user namespace.
@@ -450,7 +626,11 @@ pub fn derive_system_param(input: TokenStream) -> TokenStream {
 
             // Safety: Each field is `ReadOnlySystemParam`, so this can only read from the `World`
             unsafe impl<'w, 's, #punctuated_generics> #path::system::ReadOnlySystemParam for #struct_name #ty_generics #read_only_where_clause {}
+
+            #builder_impl
         };
+
+        #builder_struct
     })
 }
 
@@ -505,7 +685,7 @@ pub fn derive_event(input: TokenStream) -> TokenStream {
     component::derive_event(input)
 }
 
-#[proc_macro_derive(Resource)]
+#[proc_macro_derive(Resource, attributes(resource))]
 pub fn derive_resource(input: TokenStream) -> TokenStream {
     component::derive_resource(input)
 }
@@ -515,7 +695,12 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     component::derive_component(input)
 }
 
-#[proc_macro_derive(States)]
+#[proc_macro_derive(BinarySerialize)]
+pub fn derive_binary_serialize(input: TokenStream) -> TokenStream {
+    binary::derive_binary_serialize(input)
+}
+
+#[proc_macro_derive(States, attributes(states))]
 pub fn derive_states(input: TokenStream) -> TokenStream {
     states::derive_states(input)
 }
@@ -7,7 +7,7 @@ use syn::{
     parse_macro_input, parse_quote,
     punctuated::Punctuated,
     token::Comma,
-    Attribute, Data, DataStruct, DeriveInput, Field, Index, Meta,
+    Attribute, Data, DataStruct, DeriveInput, Field, Index, Meta, Visibility,
 };
 
 use crate::{
@@ -24,6 +24,7 @@ struct QueryDataAttributes {
 
 static MUTABLE_ATTRIBUTE_NAME: &str = "mutable";
 static DERIVE_ATTRIBUTE_NAME: &str = "derive";
+static VISIBILITY_ATTRIBUTE_NAME: &str = "visibility";
 
 mod field_attr_keywords {
     syn::custom_keyword!(ignore);
@@ -47,45 +48,50 @@ pub fn derive_query_data_impl(input: TokenStream) -> TokenStream {
             continue;
         }
 
-        attr.parse_args_with(|input: ParseStream| {
+        let result = attr.parse_args_with(|input: ParseStream| {
             let meta = input.parse_terminated(syn::Meta::parse, Comma)?;
             for meta in meta {
-                let ident = meta.path().get_ident().unwrap_or_else(|| {
-                    panic!(
-                        "Unrecognized attribute: `{}`",
-                        meta.path().to_token_stream()
-                    )
-                });
+                let Some(ident) = meta.path().get_ident() else {
+                    return Err(syn::Error::new_spanned(
+                        meta.path(),
+                        format!("Unrecognized attribute: `{}`", meta.path().to_token_stream()),
+                    ));
+                };
                 if ident == MUTABLE_ATTRIBUTE_NAME {
                     if let Meta::Path(_) = meta {
                         attributes.is_mutable = true;
                     } else {
-                        panic!(
-                            "The `{MUTABLE_ATTRIBUTE_NAME}` attribute is expected to have no value or arguments",
-                        );
+                        return Err(syn::Error::new_spanned(
+                            &meta,
+                            format!("The `{MUTABLE_ATTRIBUTE_NAME}` attribute is expected to have no value or arguments"),
+                        ));
                     }
-                }
-                else if ident == DERIVE_ATTRIBUTE_NAME {
-                    if let Meta::List(meta_list) = meta {
+                } else if ident == DERIVE_ATTRIBUTE_NAME {
+                    if let Meta::List(meta_list) = &meta {
                         meta_list.parse_nested_meta(|meta| {
                             attributes.derive_args.push(Meta::Path(meta.path));
                             Ok(())
                         })?;
                     } else {
-                        panic!(
-                            "Expected a structured list within the `{DERIVE_ATTRIBUTE_NAME}` attribute",
-                        );
+                        return Err(syn::Error::new_spanned(
+                            &meta,
+                            format!("Expected a structured list within the `{DERIVE_ATTRIBUTE_NAME}` attribute"),
+                        ));
                     }
                 } else {
-                    panic!(
-                        "Unrecognized attribute: `{}`",
-                        meta.path().to_token_stream()
-                    );
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        format!(
+                            "Unrecognized attribute: `{ident}`, expected `{MUTABLE_ATTRIBUTE_NAME}` or `{DERIVE_ATTRIBUTE_NAME}`"
+                        ),
+                    ));
                 }
             }
             Ok(())
-        })
-        .unwrap_or_else(|_| panic!("Invalid `{QUERY_DATA_ATTRIBUTE_NAME}` attribute format"));
+        });
+        if let Err(err) = result {
+            return err.into_compile_error().into();
+        }
     }
 
     let path = bevy_ecs_path();
@@ -137,7 +143,7 @@ pub fn derive_query_data_impl(input: TokenStream) -> TokenStream {
     let Data::Struct(DataStruct { fields, .. }) = &ast.data else {
         return syn::Error::new(
             Span::call_site(),
-            "#[derive(QueryData)]` only supports structs",
+            "`#[derive(QueryData)]` only supports structs",
         )
         .into_compile_error()
         .into();
@@ -145,13 +151,17 @@ pub fn derive_query_data_impl(input: TokenStream) -> TokenStream {
 
     let mut field_attrs = Vec::new();
     let mut field_visibilities = Vec::new();
+    let mut item_field_visibilities = Vec::new();
     let mut field_idents = Vec::new();
     let mut named_field_idents = Vec::new();
     let mut field_types = Vec::new();
     let mut read_only_field_types = Vec::new();
     for (i, field) in fields.iter().enumerate() {
-        let attrs = match read_world_query_field_info(field) {
-            Ok(QueryDataFieldInfo { attrs }) => attrs,
+        let (attrs, item_visibility) = match read_world_query_field_info(field) {
+            Ok(QueryDataFieldInfo {
+                attrs,
+                item_visibility,
+            }) => (attrs, item_visibility),
             Err(e) => return e.into_compile_error().into(),
         };
 
@@ -168,6 +178,7 @@ pub fn derive_query_data_impl(input: TokenStream) -> TokenStream {
         field_idents.push(field_ident);
         named_field_idents.push(named_field_ident);
         field_attrs.push(attrs);
+        item_field_visibilities.push(item_visibility.unwrap_or_else(|| field.vis.clone()));
         field_visibilities.push(field.vis.clone());
         let field_ty = field.ty.clone();
         field_types.push(quote!(#field_ty));
@@ -188,7 +199,7 @@ pub fn derive_query_data_impl(input: TokenStream) -> TokenStream {
         &field_types,
         &user_impl_generics_with_world,
         &field_attrs,
-        &field_visibilities,
+        &item_field_visibilities,
         &field_idents,
         &user_ty_generics,
         &user_ty_generics_with_world,
@@ -225,7 +236,7 @@ pub fn derive_query_data_impl(input: TokenStream) -> TokenStream {
             &read_only_field_types,
             &user_impl_generics_with_world,
             &field_attrs,
-            &field_visibilities,
+            &item_field_visibilities,
             &field_idents,
             &user_ty_generics,
             &user_ty_generics_with_world,
@@ -316,7 +327,8 @@ pub fn derive_query_data_impl(input: TokenStream) -> TokenStream {
             // #[derive(QueryData)]
             // pub struct Foo { a: &'static mut MyComponent }
             // ```
-            #( assert_readonly::<#field_types>(); )*
+            // The assertion is named so that the error points users to the missing attribute.
+            #( mutable_query_data_fields_require_the_query_data_mutable_attribute::<#field_types>(); )*
         }
     };
 
@@ -356,6 +368,12 @@ pub fn derive_query_data_impl(input: TokenStream) -> TokenStream {
             {
             }
 
+            fn mutable_query_data_fields_require_the_query_data_mutable_attribute<T>()
+            where
+                T: #path::query::ReadOnlyQueryData,
+            {
+            }
+
             fn assert_data<T>()
             where
                 T: #path::query::QueryData,
@@ -388,23 +406,40 @@ pub fn derive_query_data_impl(input: TokenStream) -> TokenStream {
 struct QueryDataFieldInfo {
     /// All field attributes except for `query_data` ones.
     attrs: Vec<Attribute>,
+    /// The visibility of the field in the generated item structs, if set with `#[query_data(visibility = ...)]`.
+    item_visibility: Option<Visibility>,
 }
 
 fn read_world_query_field_info(field: &Field) -> syn::Result<QueryDataFieldInfo> {
     let mut attrs = Vec::new();
+    let mut item_visibility = None;
     for attr in &field.attrs {
         if attr
             .path()
             .get_ident()
             .map_or(false, |ident| ident == QUERY_DATA_ATTRIBUTE_NAME)
         {
-            return Err(syn::Error::new_spanned(
-                attr,
-                "#[derive(QueryData)] does not support field attributes.",
-            ));
+            attr.parse_nested_meta(|meta| {
+                if !meta.path.is_ident(VISIBILITY_ATTRIBUTE_NAME) {
+                    return Err(meta.error(format!(
+                        "Unrecognized field attribute, expected `{VISIBILITY_ATTRIBUTE_NAME}`"
+                    )));
+                }
+                if item_visibility.is_some() {
+                    return Err(meta.error(format!(
+                        "`{VISIBILITY_ATTRIBUTE_NAME}` is set more than once"
+                    )));
+                }
+                item_visibility = Some(meta.value()?.parse::<Visibility>()?);
+                Ok(())
+            })?;
+            continue;
         }
         attrs.push(attr.clone());
     }
 
-    Ok(QueryDataFieldInfo { attrs })
+    Ok(QueryDataFieldInfo {
+        attrs,
+        item_visibility,
+    })
 }
@@ -1,9 +1,12 @@
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, DeriveInput};
+use syn::{parse_macro_input, punctuated::Punctuated, Data, DeriveInput, Fields, Ident, Token};
 
 use crate::bevy_ecs_path;
 
+const STATES: &str = "states";
+const TO: &str = "to";
+
 pub fn derive_states(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     let generics = ast.generics;
@@ -14,8 +17,73 @@ pub fn derive_states(input: TokenStream) -> TokenStream {
     trait_path.segments.push(format_ident!("States").into());
     let struct_name = &ast.ident;
 
+    let mut variants_const = None;
+    let mut transitions_fn = None;
+    if let Data::Enum(data) = &ast.data {
+        if data
+            .variants
+            .iter()
+            .all(|variant| matches!(variant.fields, Fields::Unit))
+        {
+            let variants = data.variants.iter().map(|variant| &variant.ident);
+            variants_const = Some(quote! {
+                const VARIANTS: &'static [Self] = &[#(Self::#variants),*];
+            });
+        }
+
+        let mut arms = Vec::new();
+        for variant in &data.variants {
+            let mut targets: Option<Vec<Ident>> = None;
+            for attr in variant.attrs.iter().filter(|a| a.path().is_ident(STATES)) {
+                let result = attr.parse_nested_meta(|meta| {
+                    if !meta.path.is_ident(TO) {
+                        return Err(
+                            meta.error(format!("Unsupported attribute, expected `{TO}(...)`"))
+                        );
+                    }
+                    if targets.is_some() {
+                        return Err(meta.error(format!("`{TO}` is set more than once")));
+                    }
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let idents = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+                    targets = Some(idents.into_iter().collect());
+                    Ok(())
+                });
+                if let Err(error) = result {
+                    return error.into_compile_error().into();
+                }
+            }
+            if let Some(targets) = targets {
+                let from = &variant.ident;
+                let allowed = if targets.is_empty() {
+                    quote!(false)
+                } else {
+                    quote!(::core::matches!(to, #(Self::#targets { .. })|*))
+                };
+                arms.push(quote! {
+                    Self::#from { .. } => #allowed,
+                });
+            }
+        }
+        if !arms.is_empty() {
+            transitions_fn = Some(quote! {
+                #[allow(unreachable_patterns, unused_variables)]
+                fn is_transition_allowed(&self, to: &Self) -> bool {
+                    match self {
+                        #(#arms)*
+                        _ => true,
+                    }
+                }
+            });
+        }
+    }
+
     quote! {
-        impl #impl_generics #trait_path for #struct_name #ty_generics #where_clause {}
+        impl #impl_generics #trait_path for #struct_name #ty_generics #where_clause {
+            #variants_const
+            #transitions_fn
+        }
     }
     .into()
 }
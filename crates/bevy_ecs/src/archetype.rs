@@ -20,6 +20,7 @@
 //! [`World::archetypes`]: crate::world::World::archetypes
 
 use crate::{
+    archetype_invariants::{ArchetypeInvariant, ArchetypeInvariants},
     bundle::BundleId,
     component::{ComponentId, Components, StorageType},
     entity::{Entity, EntityLocation},
@@ -314,6 +315,7 @@ bitflags::bitflags! {
         const ON_ADD_HOOK    = (1 << 0);
         const ON_INSERT_HOOK = (1 << 1);
         const ON_REMOVE_HOOK = (1 << 2);
+        const DISABLED       = (1 << 3);
     }
 }
 
@@ -595,6 +597,12 @@ impl Archetype {
     pub(crate) fn has_on_remove(&self) -> bool {
         self.flags().contains(ArchetypeFlags::ON_REMOVE_HOOK)
     }
+
+    /// Returns true if this archetype contains the [`Disabled`](crate::entity_disabling::Disabled) marker
+    #[inline]
+    pub(crate) fn is_disabled(&self) -> bool {
+        self.flags().contains(ArchetypeFlags::DISABLED)
+    }
 }
 
 /// The next [`ArchetypeId`] in an [`Archetypes`] collection.
@@ -668,6 +676,7 @@ pub struct Archetypes {
     pub(crate) archetypes: Vec<Archetype>,
     archetype_component_count: usize,
     by_components: bevy_utils::HashMap<ArchetypeComponents, ArchetypeId>,
+    invariants: ArchetypeInvariants,
 }
 
 impl Archetypes {
@@ -676,6 +685,7 @@ impl Archetypes {
             archetypes: Vec::new(),
             by_components: Default::default(),
             archetype_component_count: 0,
+            invariants: Default::default(),
         };
         // SAFETY: Empty archetype has no components
         unsafe {
@@ -793,10 +803,21 @@ impl Archetypes {
 
         let archetypes = &mut self.archetypes;
         let archetype_component_count = &mut self.archetype_component_count;
+        let invariants = &self.invariants;
         *self
             .by_components
             .entry(archetype_identity)
             .or_insert_with(move || {
+                #[cfg(debug_assertions)]
+                invariants.validate(
+                    components,
+                    table_components
+                        .iter()
+                        .chain(sparse_set_components.iter())
+                        .copied(),
+                );
+                #[cfg(not(debug_assertions))]
+                let _ = invariants;
                 let id = ArchetypeId::new(archetypes.len());
                 let table_start = *archetype_component_count;
                 *archetype_component_count += table_components.len();
@@ -819,6 +840,29 @@ impl Archetypes {
             })
     }
 
+    /// Returns the [`ArchetypeInvariants`] that every new archetype is checked against.
+    #[inline]
+    pub fn invariants(&self) -> &ArchetypeInvariants {
+        &self.invariants
+    }
+
+    /// Registers a new [`ArchetypeInvariant`] and checks it against all existing archetypes.
+    ///
+    /// # Panics
+    ///
+    /// With `debug_assertions` enabled, panics if an existing archetype violates the invariant.
+    pub(crate) fn add_invariant(&mut self, components: &Components, invariant: ArchetypeInvariant) {
+        self.invariants.add(invariant);
+        #[cfg(debug_assertions)]
+        for archetype in &self.archetypes {
+            let archetype_components: Vec<_> = archetype.components().collect();
+            self.invariants
+                .validate(components, archetype_components.iter().copied());
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = components;
+    }
+
     /// Returns the number of components that are stored in archetypes.
     /// Note that if some component `T` is stored in more than one archetype, it will be counted once for each archetype it's present in.
     #[inline]
@@ -0,0 +1,278 @@
+//! Rules about which components may appear together on an entity.
+//!
+//! An [`ArchetypeInvariant`] is a rule of the form "if an archetype satisfies the premise,
+//! it must also satisfy the consequence". Invariants are registered with
+//! [`World::add_archetype_invariant`](crate::world::World::add_archetype_invariant)
+//! and checked every time a new [`Archetype`](crate::archetype::Archetype) is created.
+//!
+//! Checking is only performed when `debug_assertions` are enabled, so invariants have no cost in release builds.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::archetype_invariants::ArchetypeInvariant;
+//! #
+//! #[derive(Component)]
+//! struct Player;
+//!
+//! #[derive(Component)]
+//! struct Enemy;
+//!
+//! let mut world = World::new();
+//! let invariant = ArchetypeInvariant::disjoint::<(Player, Enemy)>(&mut world);
+//!
+//! // An entity cannot be both a player and an enemy.
+//! let player = world.component_id::<Player>().unwrap();
+//! let enemy = world.component_id::<Enemy>().unwrap();
+//! assert!(invariant.test(|id| id == player));
+//! assert!(!invariant.test(|id| id == player || id == enemy));
+//!
+//! world.add_archetype_invariant(invariant);
+//! world.spawn(Player);
+//! world.spawn(Enemy);
+//! // In debug builds, `world.spawn((Player, Enemy))` would now panic.
+//! ```
+
+use std::fmt;
+
+use bevy_utils::HashSet;
+
+use crate::{
+    bundle::Bundle,
+    component::{ComponentId, Components},
+    world::World,
+};
+
+/// A statement about the components of an archetype, used to build an [`ArchetypeInvariant`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArchetypeStatement {
+    /// Evaluates to `true` if and only if the archetype contains all of the components.
+    AllOf(HashSet<ComponentId>),
+    /// Evaluates to `true` if and only if the archetype contains at least one of the components.
+    AnyOf(HashSet<ComponentId>),
+    /// Evaluates to `true` if and only if the archetype contains at most one of the components.
+    AtMostOneOf(HashSet<ComponentId>),
+    /// Evaluates to `true` if and only if the archetype contains none of the components.
+    NoneOf(HashSet<ComponentId>),
+    /// Always evaluates to `true`.
+    Always,
+}
+
+impl ArchetypeStatement {
+    /// Builds an [`ArchetypeStatement::AllOf`] from the components of the bundle `B`.
+    pub fn all_of<B: Bundle>(world: &mut World) -> Self {
+        ArchetypeStatement::AllOf(bundle_component_ids::<B>(world))
+    }
+
+    /// Builds an [`ArchetypeStatement::AnyOf`] from the components of the bundle `B`.
+    pub fn any_of<B: Bundle>(world: &mut World) -> Self {
+        ArchetypeStatement::AnyOf(bundle_component_ids::<B>(world))
+    }
+
+    /// Builds an [`ArchetypeStatement::AtMostOneOf`] from the components of the bundle `B`.
+    pub fn at_most_one_of<B: Bundle>(world: &mut World) -> Self {
+        ArchetypeStatement::AtMostOneOf(bundle_component_ids::<B>(world))
+    }
+
+    /// Builds an [`ArchetypeStatement::NoneOf`] from the components of the bundle `B`.
+    pub fn none_of<B: Bundle>(world: &mut World) -> Self {
+        ArchetypeStatement::NoneOf(bundle_component_ids::<B>(world))
+    }
+
+    /// Evaluates this statement against the set of components of an archetype.
+    pub fn test(&self, contains: impl Fn(ComponentId) -> bool) -> bool {
+        match self {
+            ArchetypeStatement::AllOf(ids) => ids.iter().all(|&id| contains(id)),
+            ArchetypeStatement::AnyOf(ids) => ids.iter().any(|&id| contains(id)),
+            ArchetypeStatement::AtMostOneOf(ids) => {
+                ids.iter().filter(|&&id| contains(id)).count() <= 1
+            }
+            ArchetypeStatement::NoneOf(ids) => !ids.iter().any(|&id| contains(id)),
+            ArchetypeStatement::Always => true,
+        }
+    }
+
+    fn display<'a>(&'a self, components: &'a Components) -> impl fmt::Display + 'a {
+        StatementDisplay {
+            statement: self,
+            components,
+        }
+    }
+}
+
+/// A rule about the component composition of every [`Archetype`](crate::archetype::Archetype) in a [`World`].
+///
+/// Whenever `premise` holds for an archetype, `consequence` must hold as well.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchetypeInvariant {
+    /// Defines which archetypes this invariant applies to.
+    pub premise: ArchetypeStatement,
+    /// Must hold for every archetype that satisfies [`ArchetypeInvariant::premise`].
+    pub consequence: ArchetypeStatement,
+}
+
+impl ArchetypeInvariant {
+    /// Every component in `A` must always coexist with every component in `B`.
+    pub fn requires<A: Bundle, B: Bundle>(world: &mut World) -> Self {
+        ArchetypeInvariant {
+            premise: ArchetypeStatement::any_of::<A>(world),
+            consequence: ArchetypeStatement::all_of::<B>(world),
+        }
+    }
+
+    /// The components in `B` must either all be present or all be absent.
+    pub fn atomic<B: Bundle>(world: &mut World) -> Self {
+        ArchetypeInvariant {
+            premise: ArchetypeStatement::any_of::<B>(world),
+            consequence: ArchetypeStatement::all_of::<B>(world),
+        }
+    }
+
+    /// At most one of the components in `B` may be present at a time.
+    pub fn disjoint<B: Bundle>(world: &mut World) -> Self {
+        ArchetypeInvariant {
+            premise: ArchetypeStatement::Always,
+            consequence: ArchetypeStatement::at_most_one_of::<B>(world),
+        }
+    }
+
+    /// Returns `true` if the archetype described by `contains` upholds this invariant.
+    pub fn test(&self, contains: impl Fn(ComponentId) -> bool) -> bool {
+        !self.premise.test(&contains) || self.consequence.test(&contains)
+    }
+}
+
+/// The set of [`ArchetypeInvariant`]s registered on a [`World`].
+#[derive(Default)]
+pub struct ArchetypeInvariants {
+    invariants: Vec<ArchetypeInvariant>,
+}
+
+impl ArchetypeInvariants {
+    /// Returns an iterator over the registered invariants.
+    pub fn iter(&self) -> impl Iterator<Item = &ArchetypeInvariant> {
+        self.invariants.iter()
+    }
+
+    pub(crate) fn add(&mut self, invariant: ArchetypeInvariant) {
+        self.invariants.push(invariant);
+    }
+
+    /// Checks the archetype made of `archetype_components` against every invariant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any invariant is violated.
+    pub(crate) fn validate(
+        &self,
+        components: &Components,
+        archetype_components: impl Iterator<Item = ComponentId> + Clone,
+    ) {
+        let contains = |id: ComponentId| archetype_components.clone().any(|other| other == id);
+        for invariant in &self.invariants {
+            if !invariant.test(contains) {
+                let names = archetype_components
+                    .clone()
+                    .filter_map(|id| components.get_name(id))
+                    .collect::<Vec<_>>();
+                panic!(
+                    "Archetype {names:?} violates an archetype invariant: if {} then {}",
+                    invariant.premise.display(components),
+                    invariant.consequence.display(components),
+                );
+            }
+        }
+    }
+}
+
+fn bundle_component_ids<B: Bundle>(world: &mut World) -> HashSet<ComponentId> {
+    let mut ids = HashSet::new();
+    B::component_ids(&mut world.components, &mut world.storages, &mut |id| {
+        ids.insert(id);
+    });
+    ids
+}
+
+struct StatementDisplay<'a> {
+    statement: &'a ArchetypeStatement,
+    components: &'a Components,
+}
+
+impl fmt::Display for StatementDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (label, ids) = match self.statement {
+            ArchetypeStatement::AllOf(ids) => ("all of", ids),
+            ArchetypeStatement::AnyOf(ids) => ("any of", ids),
+            ArchetypeStatement::AtMostOneOf(ids) => ("at most one of", ids),
+            ArchetypeStatement::NoneOf(ids) => ("none of", ids),
+            ArchetypeStatement::Always => return write!(f, "always"),
+        };
+        let names = ids
+            .iter()
+            .filter_map(|&id| self.components.get_name(id))
+            .collect::<Vec<_>>();
+        write!(f, "{label} {names:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Component)]
+    struct C;
+
+    #[test]
+    fn valid_archetypes_are_accepted() {
+        let mut world = World::new();
+        let requires = ArchetypeInvariant::requires::<A, B>(&mut world);
+        world.add_archetype_invariant(requires);
+        let disjoint = ArchetypeInvariant::disjoint::<(A, C)>(&mut world);
+        world.add_archetype_invariant(disjoint);
+
+        world.spawn((A, B));
+        world.spawn(B);
+        world.spawn((B, C));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn missing_required_component_panics() {
+        let mut world = World::new();
+        let requires = ArchetypeInvariant::requires::<A, B>(&mut world);
+        world.add_archetype_invariant(requires);
+
+        world.spawn(A);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn existing_archetypes_are_checked() {
+        let mut world = World::new();
+        world.spawn((A, C));
+
+        let disjoint = ArchetypeInvariant::disjoint::<(A, C)>(&mut world);
+        world.add_archetype_invariant(disjoint);
+    }
+
+    #[test]
+    fn atomic_statement() {
+        let mut world = World::new();
+        let invariant = ArchetypeInvariant::atomic::<(A, B)>(&mut world);
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+
+        assert!(invariant.test(|id| id == a || id == b));
+        assert!(invariant.test(|_| false));
+        assert!(!invariant.test(|id| id == a));
+    }
+}
@@ -0,0 +1,634 @@
+//! Compact binary serialization of entities, without reflection or `serde`.
+//!
+//! Components implementing [`BinarySerialize`] (usually through its derive macro) are registered with
+//! [`World::register_binary_component`], after which entities can be written to a byte buffer with
+//! [`World::serialize_entities_binary`] and read back with [`World::deserialize_entities_binary`].
+//! This is meant for data that has to be sent or stored every tick, like network snapshots,
+//! where going through `Reflect` or `serde` is too slow.
+//!
+//! Components are identified by the order in which they were registered, so both sides
+//! must register the same components in the same order.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::binary::BinarySerialize;
+//! #[derive(Component, BinarySerialize, PartialEq, Debug)]
+//! struct Position {
+//!     x: f32,
+//!     y: f32,
+//! }
+//!
+//! let mut server = World::new();
+//! server.register_binary_component::<Position>();
+//! let entity = server.spawn(Position { x: 1.0, y: 2.0 }).id();
+//! let mut bytes = Vec::new();
+//! server.serialize_entities_binary([entity], &mut bytes);
+//!
+//! let mut client = World::new();
+//! client.register_binary_component::<Position>();
+//! client.deserialize_entities_binary(&bytes).unwrap();
+//! assert_eq!(client.get::<Position>(entity), Some(&Position { x: 1.0, y: 2.0 }));
+//! ```
+
+use std::any::Any;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ptr::NonNull;
+
+use bevy_ptr::{OwningPtr, Ptr};
+use bevy_utils::{all_tuples, HashMap};
+use thiserror::Error;
+
+use crate as bevy_ecs;
+use crate::{
+    component::{Component, ComponentId},
+    entity::Entity,
+    query::DebugCheckedUnwrap,
+    system::Resource,
+    world::{EntityWorldMut, World},
+};
+
+pub use bevy_ecs_macros::BinarySerialize;
+
+/// An error that occurs when deserializing a value with [`BinarySerialize`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BinaryError {
+    /// The input ended before the value was complete.
+    #[error("unexpected end of input")]
+    UnexpectedEnd,
+    /// The input does not hold a valid value of the given type.
+    #[error("invalid value for type `{0}`")]
+    InvalidValue(&'static str),
+    /// The input refers to a component that was not registered with [`World::register_binary_component`].
+    #[error("no binary component is registered with id {0}")]
+    UnknownComponent(u16),
+    /// The entity cannot be spawned because its id has been reused with a different generation.
+    #[error("entity {0:?} cannot be deserialized: its id has been reused")]
+    EntityReused(Entity),
+    /// The entity cannot be spawned because an entity with the same id already exists.
+    #[error("entity {0:?} cannot be deserialized: it already exists")]
+    EntityInUse(Entity),
+}
+
+/// A type that can be written to and read from a compact binary format.
+///
+/// Numbers are written in little-endian order with their full width, and lengths as `u32`.
+/// The format is not self-describing: a value can only be read back as the type it was written as.
+///
+/// This trait can be derived for structs and enums whose fields all implement it.
+/// Enum variants are written as their `u32` index.
+///
+/// ```
+/// # use bevy_ecs::binary::BinarySerialize;
+/// #[derive(BinarySerialize, PartialEq, Debug)]
+/// enum Action {
+///     Idle,
+///     Move { speed: f32, target: [i32; 2] },
+///     Say(String),
+/// }
+///
+/// let action = Action::Move { speed: 2.0, target: [4, -1] };
+/// let mut bytes = Vec::new();
+/// action.serialize(&mut bytes);
+/// assert_eq!(Action::deserialize(&mut bytes.as_slice()), Ok(action));
+/// ```
+pub trait BinarySerialize: Sized {
+    /// Appends the binary representation of `self` to `buffer`.
+    fn serialize(&self, buffer: &mut Vec<u8>);
+
+    /// Reads a value from the start of `bytes`, and advances `bytes` past it.
+    fn deserialize(bytes: &mut &[u8]) -> Result<Self, BinaryError>;
+}
+
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], BinaryError> {
+    if bytes.len() < len {
+        return Err(BinaryError::UnexpectedEnd);
+    }
+    let (taken, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(taken)
+}
+
+pub(crate) fn serialize_len(len: usize, buffer: &mut Vec<u8>) {
+    u32::try_from(len)
+        .expect("binary serialized collections cannot hold more than u32::MAX elements")
+        .serialize(buffer);
+}
+
+pub(crate) fn deserialize_len(bytes: &mut &[u8]) -> Result<usize, BinaryError> {
+    Ok(u32::deserialize(bytes)? as usize)
+}
+
+macro_rules! impl_binary_serialize_for_numbers {
+    ($($ty: ty),*) => {
+        $(
+            impl BinarySerialize for $ty {
+                fn serialize(&self, buffer: &mut Vec<u8>) {
+                    buffer.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn deserialize(bytes: &mut &[u8]) -> Result<Self, BinaryError> {
+                    let taken = take(bytes, std::mem::size_of::<$ty>())?;
+                    // The slice has the exact size of the number.
+                    Ok(<$ty>::from_le_bytes(taken.try_into().unwrap()))
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_serialize_for_numbers!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl BinarySerialize for usize {
+    fn serialize(&self, buffer: &mut Vec<u8>) {
+        (*self as u64).serialize(buffer);
+    }
+
+    fn deserialize(bytes: &mut &[u8]) -> Result<Self, BinaryError> {
+        usize::try_from(u64::deserialize(bytes)?)
+            .map_err(|_| BinaryError::InvalidValue(std::any::type_name::<Self>()))
+    }
+}
+
+impl BinarySerialize for isize {
+    fn serialize(&self, buffer: &mut Vec<u8>) {
+        (*self as i64).serialize(buffer);
+    }
+
+    fn deserialize(bytes: &mut &[u8]) -> Result<Self, BinaryError> {
+        isize::try_from(i64::deserialize(bytes)?)
+            .map_err(|_| BinaryError::InvalidValue(std::any::type_name::<Self>()))
+    }
+}
+
+impl BinarySerialize for bool {
+    fn serialize(&self, buffer: &mut Vec<u8>) {
+        buffer.push(u8::from(*self));
+    }
+
+    fn deserialize(bytes: &mut &[u8]) -> Result<Self, BinaryError> {
+        match u8::deserialize(bytes)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(BinaryError::InvalidValue("bool")),
+        }
+    }
+}
+
+impl BinarySerialize for char {
+    fn serialize(&self, buffer: &mut Vec<u8>) {
+        u32::from(*self).serialize(buffer);
+    }
+
+    fn deserialize(bytes: &mut &[u8]) -> Result<Self, BinaryError> {
+        char::from_u32(u32::deserialize(bytes)?).ok_or(BinaryError::InvalidValue("char"))
+    }
+}
+
+impl BinarySerialize for String {
+    fn serialize(&self, buffer: &mut Vec<u8>) {
+        serialize_len(self.len(), buffer);
+        buffer.extend_from_slice(self.as_bytes());
+    }
+
+    fn deserialize(bytes: &mut &[u8]) -> Result<Self, BinaryError> {
+        let len = deserialize_len(bytes)?;
+        let taken = take(bytes, len)?;
+        String::from_utf8(taken.to_vec()).map_err(|_| BinaryError::InvalidValue("String"))
+    }
+}
+
+impl<T: BinarySerialize> BinarySerialize for Vec<T> {
+    fn serialize(&self, buffer: &mut Vec<u8>) {
+        serialize_len(self.len(), buffer);
+        for value in self {
+            value.serialize(buffer);
+        }
+    }
+
+    fn deserialize(bytes: &mut &[u8]) -> Result<Self, BinaryError> {
+        let len = deserialize_len(bytes)?;
+        // Don't trust the length for the allocation, as it comes from the input.
+        let mut values = Vec::with_capacity(len.min(bytes.len()));
+        for _ in 0..len {
+            values.push(T::deserialize(bytes)?);
+        }
+        Ok(values)
+    }
+}
+
+impl<T: BinarySerialize, const N: usize> BinarySerialize for [T; N] {
+    fn serialize(&self, buffer: &mut Vec<u8>) {
+        for value in self {
+            value.serialize(buffer);
+        }
+    }
+
+    fn deserialize(bytes: &mut &[u8]) -> Result<Self, BinaryError> {
+        let mut values = Vec::with_capacity(N);
+        for _ in 0..N {
+            values.push(T::deserialize(bytes)?);
+        }
+        let Ok(values) = values.try_into() else {
+            unreachable!("exactly N values were deserialized");
+        };
+        Ok(values)
+    }
+}
+
+impl<T: BinarySerialize> BinarySerialize for Option<T> {
+    fn serialize(&self, buffer: &mut Vec<u8>) {
+        match self {
+            None => false.serialize(buffer),
+            Some(value) => {
+                true.serialize(buffer);
+                value.serialize(buffer);
+            }
+        }
+    }
+
+    fn deserialize(bytes: &mut &[u8]) -> Result<Self, BinaryError> {
+        if bool::deserialize(bytes)? {
+            Ok(Some(T::deserialize(bytes)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<T: ?Sized> BinarySerialize for PhantomData<T> {
+    fn serialize(&self, _buffer: &mut Vec<u8>) {}
+
+    fn deserialize(_bytes: &mut &[u8]) -> Result<Self, BinaryError> {
+        Ok(PhantomData)
+    }
+}
+
+impl BinarySerialize for Entity {
+    fn serialize(&self, buffer: &mut Vec<u8>) {
+        self.to_bits().serialize(buffer);
+    }
+
+    fn deserialize(bytes: &mut &[u8]) -> Result<Self, BinaryError> {
+        Entity::try_from_bits(u64::deserialize(bytes)?)
+            .map_err(|_| BinaryError::InvalidValue("Entity"))
+    }
+}
+
+macro_rules! impl_binary_serialize_for_tuples {
+    ($($name: ident),*) => {
+        impl<$($name: BinarySerialize),*> BinarySerialize for ($($name,)*) {
+            #[allow(unused_variables, non_snake_case)]
+            fn serialize(&self, buffer: &mut Vec<u8>) {
+                let ($($name,)*) = self;
+                $($name.serialize(buffer);)*
+            }
+
+            #[allow(unused_variables)]
+            fn deserialize(bytes: &mut &[u8]) -> Result<Self, BinaryError> {
+                Ok(($($name::deserialize(bytes)?,)*))
+            }
+        }
+    };
+}
+
+all_tuples!(impl_binary_serialize_for_tuples, 0, 15, T);
+
+/// Type-erased functions used to write and read a single registered component.
+#[derive(Clone, Copy)]
+struct BinaryComponentFns {
+    component_id: ComponentId,
+    serialize: unsafe fn(Ptr<'_>, &mut Vec<u8>),
+    deserialize: fn(&mut &[u8]) -> Result<Box<dyn Any + Send + Sync>, BinaryError>,
+}
+
+/// # Safety
+/// `ptr` must point to a valid value of type `T`.
+unsafe fn serialize_component<T: Component + BinarySerialize>(ptr: Ptr<'_>, buffer: &mut Vec<u8>) {
+    ptr.deref::<T>().serialize(buffer);
+}
+
+fn deserialize_component<T: Component + BinarySerialize>(
+    bytes: &mut &[u8],
+) -> Result<Box<dyn Any + Send + Sync>, BinaryError> {
+    Ok(Box::new(T::deserialize(bytes)?))
+}
+
+/// Inserts the deserialized `values` on `entity` as a single bundle, so the entity only moves archetype once.
+///
+/// # Safety
+/// Each value must have been deserialized by the [`BinaryComponentFns`] of the [`ComponentId`] at the same index.
+unsafe fn insert_deserialized_components(
+    entity: &mut EntityWorldMut,
+    component_ids: &[ComponentId],
+    values: Vec<Box<dyn Any + Send + Sync>>,
+) {
+    let values: Vec<_> = values.into_iter().map(Box::into_raw).collect();
+    let ptrs = values.iter().map(|&value| {
+        // SAFETY: the pointer comes from `Box::into_raw`, so it is non-null, aligned and not aliased.
+        unsafe { OwningPtr::new(NonNull::new_unchecked(value.cast::<u8>())) }
+    });
+    // SAFETY: the caller guarantees each value has the type of the component id at the same index.
+    unsafe { entity.insert_by_ids(component_ids, ptrs) };
+    for value in values {
+        // SAFETY: the values were moved into the entity, so only their allocations are freed.
+        drop(unsafe { Box::from_raw(value as *mut ManuallyDrop<dyn Any + Send + Sync>) });
+    }
+}
+
+/// Stores the components written by [`World::serialize_entities_binary`], in registration order.
+///
+/// Use [`World::register_binary_component`] to populate it.
+#[derive(Resource, Default)]
+pub struct BinaryComponentRegistry {
+    components: Vec<BinaryComponentFns>,
+    ids: HashMap<ComponentId, u16>,
+}
+
+impl BinaryComponentRegistry {
+    /// Returns the id the component with the given [`ComponentId`] is written with, if it is registered.
+    pub fn binary_id(&self, component_id: ComponentId) -> Option<u16> {
+        self.ids.get(&component_id).copied()
+    }
+}
+
+impl World {
+    /// Registers `T` to be written by [`World::serialize_entities_binary`] and read by
+    /// [`World::deserialize_entities_binary`].
+    ///
+    /// Registering a component more than once has no effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u16::MAX` components are registered.
+    pub fn register_binary_component<T: Component + BinarySerialize>(&mut self) -> ComponentId {
+        let component_id = self.init_component::<T>();
+        let mut registry = self.get_resource_or_insert_with(BinaryComponentRegistry::default);
+        if !registry.ids.contains_key(&component_id) {
+            let id = u16::try_from(registry.components.len())
+                .expect("cannot register more than u16::MAX binary components");
+            registry.components.push(BinaryComponentFns {
+                component_id,
+                serialize: serialize_component::<T>,
+                deserialize: deserialize_component::<T>,
+            });
+            registry.ids.insert(component_id, id);
+        }
+        component_id
+    }
+
+    /// Appends the given entities and their registered components to `buffer`.
+    ///
+    /// Entities that do not exist are skipped, and components that are not registered are not written.
+    pub fn serialize_entities_binary(
+        &self,
+        entities: impl IntoIterator<Item = Entity>,
+        buffer: &mut Vec<u8>,
+    ) {
+        let registry = self.get_resource::<BinaryComponentRegistry>();
+        let entities: Vec<_> = entities
+            .into_iter()
+            .filter_map(|entity| self.get_entity(entity))
+            .collect();
+
+        serialize_len(entities.len(), buffer);
+        for entity in entities {
+            entity.id().serialize(buffer);
+            let components: Vec<_> = registry
+                .iter()
+                .flat_map(|registry| {
+                    entity
+                        .archetype()
+                        .components()
+                        .filter_map(|component_id| registry.binary_id(component_id))
+                })
+                .collect();
+            serialize_len(components.len(), buffer);
+            for id in components {
+                id.serialize(buffer);
+                // The registry exists since the entity has registered components.
+                let fns = registry.unwrap().components[id as usize];
+                // SAFETY: the component exists on the entity since it is part of its archetype.
+                let ptr = unsafe { entity.get_by_id(fns.component_id).debug_checked_unwrap() };
+                // SAFETY: `ptr` points to a value of the type `fns` was registered with.
+                unsafe { (fns.serialize)(ptr, buffer) };
+            }
+        }
+    }
+
+    /// Reads entities written by [`World::serialize_entities_binary`], and returns their ids.
+    ///
+    /// Entities are spawned with their original [`Entity`] id, and the deserialized components
+    /// of an entity are inserted together. Entity ids held by the components are kept as is.
+    ///
+    /// Every entity is read before any is spawned, so the world is left untouched if an error is returned.
+    /// This includes [`BinaryError::EntityInUse`] if an entity with the same id already exists,
+    /// since merging the components into it would corrupt an unrelated entity.
+    pub fn deserialize_entities_binary(
+        &mut self,
+        mut bytes: &[u8],
+    ) -> Result<Vec<Entity>, BinaryError> {
+        let components = self
+            .get_resource::<BinaryComponentRegistry>()
+            .map(|registry| registry.components.clone())
+            .unwrap_or_default();
+
+        let bytes = &mut bytes;
+        let len = deserialize_len(bytes)?;
+        let mut entities = Vec::with_capacity(len.min(bytes.len()));
+        let mut entity_components = Vec::with_capacity(len.min(bytes.len()));
+        for _ in 0..len {
+            let entity = Entity::deserialize(bytes)?;
+            let mut component_ids = Vec::new();
+            let mut values = Vec::new();
+            for _ in 0..deserialize_len(bytes)? {
+                let id = u16::deserialize(bytes)?;
+                let fns = components
+                    .get(id as usize)
+                    .ok_or(BinaryError::UnknownComponent(id))?;
+                let value = (fns.deserialize)(bytes)?;
+                // A component written twice is replaced by its last value, as a bundle cannot hold duplicates.
+                match component_ids.iter().position(|&id| id == fns.component_id) {
+                    Some(index) => values[index] = value,
+                    None => {
+                        component_ids.push(fns.component_id);
+                        values.push(value);
+                    }
+                }
+            }
+
+            entities.push(entity);
+            entity_components.push((component_ids, values));
+        }
+
+        self.flush_entities();
+        for &entity in &entities {
+            let current = self
+                .entities
+                .resolve_from_id(entity.index())
+                .filter(|&current| self.entities.get(current).is_some());
+            match current {
+                Some(current) if current == entity => return Err(BinaryError::EntityInUse(entity)),
+                Some(_) => return Err(BinaryError::EntityReused(entity)),
+                None => {}
+            }
+        }
+
+        for (&entity, (component_ids, values)) in entities.iter().zip(entity_components) {
+            let mut entity_mut = self
+                .get_or_spawn(entity)
+                .expect("deserialized entities were checked to be free");
+            if !component_ids.is_empty() {
+                // SAFETY: each value was deserialized by the functions registered for its component id.
+                unsafe { insert_deserialized_components(&mut entity_mut, &component_ids, values) };
+            }
+        }
+        Ok(entities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+
+    #[derive(Component, BinarySerialize, Debug, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Component, BinarySerialize, Debug, PartialEq)]
+    struct Inventory {
+        items: Vec<String>,
+        owner: Option<Entity>,
+    }
+
+    #[derive(Component, BinarySerialize, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    enum Status {
+        Alive,
+        Stunned { turns: u8 },
+        Dead(bool, char),
+    }
+
+    #[derive(Component, Debug, PartialEq)]
+    struct NotRegistered;
+
+    fn round_trip<T: BinarySerialize>(value: &T) -> T {
+        let mut bytes = Vec::new();
+        value.serialize(&mut bytes);
+        let mut slice = bytes.as_slice();
+        let result = T::deserialize(&mut slice).unwrap();
+        assert!(slice.is_empty());
+        result
+    }
+
+    #[test]
+    fn values_round_trip() {
+        assert_eq!(round_trip(&-12i64), -12);
+        assert_eq!(round_trip(&1.5f32), 1.5);
+        assert_eq!(round_trip(&(1u8, true, 'x')), (1, true, 'x'));
+        assert_eq!(round_trip(&[3usize, 4]), [3, 4]);
+        assert_eq!(
+            round_trip(&Some("abc".to_string())),
+            Some("abc".to_string())
+        );
+        assert_eq!(
+            round_trip(&Status::Stunned { turns: 2 }),
+            Status::Stunned { turns: 2 }
+        );
+        assert_eq!(
+            round_trip(&Status::Dead(true, 'z')),
+            Status::Dead(true, 'z')
+        );
+    }
+
+    #[test]
+    fn invalid_input_is_rejected() {
+        assert_eq!(
+            u32::deserialize(&mut [1, 2].as_slice()),
+            Err(BinaryError::UnexpectedEnd)
+        );
+        assert_eq!(
+            bool::deserialize(&mut [2].as_slice()),
+            Err(BinaryError::InvalidValue("bool"))
+        );
+        assert!(matches!(
+            Status::deserialize(&mut [7, 0, 0, 0].as_slice()),
+            Err(BinaryError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn entities_round_trip() {
+        let mut source = World::new();
+        source.register_binary_component::<Health>();
+        source.register_binary_component::<Inventory>();
+        source.register_binary_component::<Status>();
+        let owner = source.spawn(Health(10)).id();
+        let item = source
+            .spawn((
+                Inventory {
+                    items: vec!["sword".to_string()],
+                    owner: Some(owner),
+                },
+                Status::Alive,
+                NotRegistered,
+            ))
+            .id();
+
+        let mut bytes = Vec::new();
+        source.serialize_entities_binary([owner, item], &mut bytes);
+
+        let mut target = World::new();
+        target.register_binary_component::<Health>();
+        target.register_binary_component::<Inventory>();
+        target.register_binary_component::<Status>();
+        let existing = target.spawn(Health(1)).id();
+        assert_eq!(existing, owner);
+
+        // Entities are not merged into existing ones.
+        assert_eq!(
+            target.deserialize_entities_binary(&bytes),
+            Err(BinaryError::EntityInUse(owner))
+        );
+        assert_eq!(target.get::<Health>(owner), Some(&Health(1)));
+        assert!(target.get_entity(item).is_none());
+
+        target.despawn(existing);
+        assert_eq!(
+            target.deserialize_entities_binary(&bytes),
+            Ok(vec![owner, item])
+        );
+        assert_eq!(target.get::<Health>(owner), Some(&Health(10)));
+        assert_eq!(target.get::<Inventory>(item).unwrap().owner, Some(owner));
+        assert_eq!(target.get::<Status>(item), Some(&Status::Alive));
+        assert!(!target.entity(item).contains::<NotRegistered>());
+
+        let mut other = World::new();
+        assert_eq!(
+            other.deserialize_entities_binary(&bytes),
+            Err(BinaryError::UnknownComponent(0))
+        );
+    }
+
+    #[test]
+    fn components_are_inserted_together() {
+        let mut source = World::new();
+        source.register_binary_component::<Health>();
+        source.register_binary_component::<Status>();
+        let entity = source.spawn((Health(3), Status::Alive)).id();
+        let mut bytes = Vec::new();
+        source.serialize_entities_binary([entity], &mut bytes);
+
+        let mut target = World::new();
+        target.register_binary_component::<Health>();
+        target.register_binary_component::<Status>();
+        let archetypes = target.archetypes().len();
+        target.deserialize_entities_binary(&bytes).unwrap();
+
+        // Only the final archetype is created, not one per inserted component.
+        assert_eq!(target.archetypes().len(), archetypes + 1);
+        assert_eq!(target.get::<Health>(entity), Some(&Health(3)));
+        assert_eq!(target.get::<Status>(entity), Some(&Status::Alive));
+    }
+}
@@ -15,10 +15,9 @@ use crate::{
     prelude::World,
     query::DebugCheckedUnwrap,
     storage::{SparseSetIndex, SparseSets, Storages, Table, TableRow},
-    world::unsafe_world_cell::UnsafeWorldCell,
+    world::{unsafe_world_cell::UnsafeWorldCell, EntityWorldMut},
 };
 use bevy_ptr::{ConstNonNull, OwningPtr};
-use bevy_utils::all_tuples;
 use std::any::TypeId;
 use std::ptr::NonNull;
 
@@ -73,7 +72,8 @@ use std::ptr::NonNull;
 /// Every type which implements [`Component`] also implements `Bundle`, since
 /// [`Component`] types can be added to or removed from an entity.
 ///
-/// Additionally, [Tuples](`tuple`) of bundles are also [`Bundle`] (with up to 15 bundles).
+/// Additionally, [Tuples](`tuple`) of bundles are also [`Bundle`] (with up to 15 bundles,
+/// or 32 with the `large_tuples` feature).
 /// These bundles contain the items of the 'inner' bundles.
 /// This is a convenient shorthand which is primarily used when spawning entities.
 /// For example, spawning an entity using the bundle `(SpriteBundle {...}, PlayerMarker)`
@@ -176,6 +176,56 @@ pub trait DynamicBundle {
     fn get_components(self, func: &mut impl FnMut(StorageType, OwningPtr<'_>));
 }
 
+/// An enum whose variants each hold the components of a different bundle,
+/// of which only the active variant's components are inserted.
+///
+/// This is implemented by using [`derive@Bundle`] on an enum, where every field of every variant
+/// must be a [`Bundle`] (or marked with `#[bundle(ignore)]`). The enum itself is not a [`Bundle`],
+/// as its components depend on the value: insert it with
+/// [`EntityWorldMut::insert_variant`](crate::world::EntityWorldMut::insert_variant) or
+/// [`EntityCommands::insert_variant`](crate::system::EntityCommands::insert_variant).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// struct Health(u32);
+/// #[derive(Component)]
+/// struct Speed(f32);
+/// #[derive(Component)]
+/// struct Loot(&'static str);
+///
+/// #[derive(Bundle)]
+/// enum Kind {
+///     Player { health: Health, speed: Speed },
+///     Chest(Loot),
+///     Marker,
+/// }
+///
+/// let mut world = World::new();
+/// let player = world
+///     .spawn_empty()
+///     .insert_variant(Kind::Player { health: Health(10), speed: Speed(2.0) })
+///     .id();
+/// assert!(world.entity(player).contains::<Speed>());
+/// assert!(!world.entity(player).contains::<Loot>());
+///
+/// // Removing the variants removes the components of any of them.
+/// world.entity_mut(player).remove_variants::<Kind>();
+/// assert!(!world.entity(player).contains::<Health>());
+/// ```
+pub trait VariantBundle: Send + Sync + 'static {
+    /// Gets the component ids of every variant of this enum.
+    #[doc(hidden)]
+    fn component_ids(
+        components: &mut Components,
+        storages: &mut Storages,
+        ids: &mut impl FnMut(ComponentId),
+    );
+
+    /// Inserts the components of the active variant on `entity`.
+    fn insert_into(self, entity: &mut EntityWorldMut);
+}
+
 // SAFETY:
 // - `Bundle::component_ids` calls `ids` for C's component id (and nothing else)
 // - `Bundle::get_components` is called exactly once for C and passes the component's storage type based on it's associated constant.
@@ -249,7 +299,7 @@ macro_rules! tuple_impl {
     }
 }
 
-all_tuples!(tuple_impl, 0, 15, B);
+all_ecs_tuples!(tuple_impl, 0, 15, B);
 
 /// For a specific [`World`], this stores a unique value identifying a type of a registered [`Bundle`].
 ///
@@ -517,6 +567,7 @@ pub(crate) struct BundleInserter<'w> {
     archetype: NonNull<Archetype>,
     result: InsertBundleResult,
     change_tick: Tick,
+    trigger_hooks: bool,
 }
 
 pub(crate) enum InsertBundleResult {
@@ -582,6 +633,7 @@ impl<'w> BundleInserter<'w> {
                 table: table.into(),
                 result: InsertBundleResult::SameArchetype,
                 change_tick,
+                trigger_hooks: true,
                 world: world.as_unsafe_world_cell(),
             }
         } else {
@@ -607,6 +659,7 @@ impl<'w> BundleInserter<'w> {
                         new_archetype: new_archetype.into(),
                     },
                     change_tick,
+                    trigger_hooks: true,
                     world: world.as_unsafe_world_cell(),
                 }
             } else {
@@ -621,12 +674,20 @@ impl<'w> BundleInserter<'w> {
                         new_table: new_table.into(),
                     },
                     change_tick,
+                    trigger_hooks: true,
                     world: world.as_unsafe_world_cell(),
                 }
             }
         }
     }
 
+    /// Makes [`insert`](Self::insert) skip the `on_add` and `on_insert` hooks of the inserted components.
+    #[inline]
+    pub(crate) fn without_hooks(mut self) -> Self {
+        self.trigger_hooks = false;
+        self
+    }
+
     /// # Safety
     /// `entity` must currently exist in the source archetype for this inserter. `location`
     /// must be `entity`'s location in the archetype. `T` must match this [`BundleInfo`]'s type
@@ -781,6 +842,10 @@ impl<'w> BundleInserter<'w> {
             }
         };
 
+        if !self.trigger_hooks {
+            return new_location;
+        }
+
         // SAFETY: We have no outstanding mutable references to world as they were dropped
         let mut deferred_world = unsafe { self.world.into_deferred() };
 
@@ -1100,7 +1165,9 @@ fn initialize_dynamic_bundle(
 #[cfg(test)]
 mod tests {
     use crate as bevy_ecs;
+    use crate::component::ComponentId;
     use crate::prelude::*;
+    use crate::world::DeferredWorld;
 
     #[derive(Component)]
     struct A;
@@ -1125,6 +1192,100 @@ mod tests {
         }
     }
 
+    // The marker is never read.
+    #[allow(dead_code)]
+    #[derive(Bundle)]
+    struct MarkedBundle<T: Send + Sync + 'static> {
+        a: A,
+        #[bundle(ignore)]
+        marker: std::marker::PhantomData<T>,
+        b: B,
+    }
+
+    #[derive(Bundle)]
+    struct TupleBundle(#[bundle(ignore)] usize, C, D);
+
+    #[test]
+    fn bundle_ignored_fields() {
+        let mut world = World::new();
+        let entity = world
+            .spawn((
+                MarkedBundle::<u32> {
+                    a: A,
+                    marker: std::marker::PhantomData,
+                    b: B,
+                },
+                TupleBundle(5, C, D),
+            ))
+            .id();
+        assert_eq!(world.entity(entity).archetype().components().count(), 4);
+
+        let taken = world.entity_mut(entity).take::<TupleBundle>().unwrap();
+        assert_eq!(taken.0, 0);
+        assert!(world
+            .entity_mut(entity)
+            .take::<MarkedBundle<u32>>()
+            .is_some());
+        assert_eq!(world.entity(entity).archetype().components().count(), 0);
+    }
+
+    #[derive(Bundle)]
+    enum Kind {
+        Ab(A, B),
+        Cd {
+            c: C,
+            #[bundle(ignore)]
+            _ignored: usize,
+            d: D,
+        },
+        Empty,
+    }
+
+    #[test]
+    fn variant_bundles() {
+        let mut world = World::new();
+        let ab = world.spawn_empty().insert_variant(Kind::Ab(A, B)).id();
+        let cd = world
+            .spawn(A)
+            .insert_variant(Kind::Cd {
+                c: C,
+                _ignored: 1,
+                d: D,
+            })
+            .id();
+        let empty = world.spawn_empty().insert_variant(Kind::Empty).id();
+        world.flush_commands();
+
+        assert!(world.entity(ab).contains::<A>() && world.entity(ab).contains::<B>());
+        assert!(!world.entity(ab).contains::<C>());
+        assert_eq!(world.entity(cd).archetype().components().count(), 3);
+        assert_eq!(world.entity(empty).archetype().components().count(), 0);
+
+        world.entity_mut(cd).remove_variants::<Kind>();
+        assert_eq!(world.entity(cd).archetype().components().count(), 0);
+    }
+
+    #[derive(Component)]
+    #[component(on_add = derived_on_add, on_remove = derived_on_remove)]
+    struct WithDerivedHooks;
+
+    fn derived_on_add(mut world: DeferredWorld, _: Entity, _: ComponentId) {
+        world.resource_mut::<R>().assert_order(0);
+    }
+
+    fn derived_on_remove(mut world: DeferredWorld, _: Entity, _: ComponentId) {
+        world.resource_mut::<R>().assert_order(1);
+    }
+
+    #[test]
+    fn derived_component_hooks() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        let entity = world.spawn(WithDerivedHooks).id();
+        world.despawn(entity);
+        assert_eq!(2, world.resource::<R>().0);
+    }
+
     #[test]
     fn component_hook_order_spawn_despawn() {
         let mut world = World::new();
@@ -65,6 +65,14 @@ pub trait DetectChanges {
     fn last_changed(&self) -> Tick;
 }
 
+/// Types that can read the tick recording when their data was added.
+///
+/// This is kept separate from [`DetectChanges`] so that existing implementors of that trait are not required to expose the tick.
+pub trait DetectAdded: DetectChanges {
+    /// Returns the change tick recording the time this data was added.
+    fn added(&self) -> Tick;
+}
+
 /// Types that implement reliable change detection.
 ///
 /// ## Example
@@ -247,6 +255,19 @@ pub trait DetectChangesMut: DetectChanges {
     }
 }
 
+/// Types that can overwrite the tick recording when their data was added.
+///
+/// This is kept separate from [`DetectChangesMut`] for the same reason as [`DetectAdded`].
+pub trait DetectAddedMut: DetectChangesMut + DetectAdded {
+    /// Manually sets the added tick recording the time when this data was added.
+    /// The change tick is set to the same value, since data is also marked as changed upon insertion.
+    ///
+    /// # Warning
+    /// This is a complex and error-prone operation, primarily intended for use with rollback networking strategies.
+    /// If you want to avoid triggering change detection, use [`bypass_change_detection`](DetectChangesMut::bypass_change_detection) instead.
+    fn set_last_added(&mut self, last_added: Tick);
+}
+
 macro_rules! change_detection_impl {
     ($name:ident < $( $generics:tt ),+ >, $target:ty, $($traits:ident)?) => {
         impl<$($generics),* : ?Sized $(+ $traits)?> DetectChanges for $name<$($generics),*> {
@@ -270,6 +291,25 @@ macro_rules! change_detection_impl {
             }
         }
 
+        impl<$($generics),* : ?Sized $(+ $traits)?> DetectAdded for $name<$($generics),*> {
+            #[inline]
+            fn added(&self) -> Tick {
+                *self.ticks.added
+            }
+        }
+
+        impl<$($generics),* : ?Sized $(+ $traits)?> $name<$($generics),*> {
+            /// Returns `true` if the [`Tracked`] field returned by `f` was changed after the system last ran,
+            /// or if this value was added after the system last ran.
+            #[inline]
+            pub fn is_field_changed<U>(&self, f: impl FnOnce(&$target) -> &Tracked<U>) -> bool {
+                self.is_added()
+                    || f(&*self.value)
+                        .changed
+                        .is_newer_than(self.ticks.last_run, self.ticks.this_run)
+            }
+        }
+
         impl<$($generics),*: ?Sized $(+ $traits)?> Deref for $name<$($generics),*> {
             type Target = $target;
 
@@ -295,7 +335,9 @@ macro_rules! change_detection_mut_impl {
 
             #[inline]
             fn set_changed(&mut self) {
-                *self.ticks.changed = self.ticks.this_run;
+                if self.ticks.change_detection {
+                    *self.ticks.changed = self.ticks.this_run;
+                }
             }
 
             #[inline]
@@ -309,6 +351,14 @@ macro_rules! change_detection_mut_impl {
             }
         }
 
+        impl<$($generics),* : ?Sized $(+ $traits)?> DetectAddedMut for $name<$($generics),*> {
+            #[inline]
+            fn set_last_added(&mut self, last_added: Tick) {
+                *self.ticks.added = last_added;
+                *self.ticks.changed = last_added;
+            }
+        }
+
         impl<$($generics),* : ?Sized $(+ $traits)?> DerefMut for $name<$($generics),*> {
             #[inline]
             fn deref_mut(&mut self) -> &mut Self::Target {
@@ -349,6 +399,7 @@ macro_rules! impl_methods {
                         changed: self.ticks.changed,
                         last_run: self.ticks.last_run,
                         this_run: self.ticks.this_run,
+                        change_detection: self.ticks.change_detection,
                     }
                 }
             }
@@ -382,6 +433,37 @@ macro_rules! impl_methods {
                 }
             }
 
+            /// Optionally maps to an inner value by applying a function to the contained reference, without flagging a change.
+            ///
+            /// Returns `None` if `f` does. As with [`map_unchanged`](Self::map_unchanged),
+            /// you should never modify the argument passed to the closure.
+            pub fn filter_map_unchanged<U: ?Sized>(self, f: impl FnOnce(&mut $target) -> Option<&mut U>) -> Option<Mut<'w, U>> {
+                let value = f(self.value)?;
+                Some(Mut {
+                    value,
+                    ticks: self.ticks,
+                })
+            }
+
+            /// Maps to a [`Tracked`] field, so that mutating the returned pointer only marks that field as changed.
+            ///
+            /// Such a change updates the change tick of the field rather than the one of the whole value.
+            /// It is detected by [`is_field_changed`](Self::is_field_changed), but not by
+            /// [`is_changed`](DetectChanges::is_changed) or `Changed<T>` filters.
+            pub fn map_tracked<U>(self, f: impl FnOnce(&mut $target) -> &mut Tracked<U>) -> Mut<'w, U> {
+                let tracked = f(self.value);
+                Mut {
+                    value: &mut tracked.value,
+                    ticks: TicksMut {
+                        added: self.ticks.added,
+                        changed: &mut tracked.changed,
+                        last_run: self.ticks.last_run,
+                        this_run: self.ticks.this_run,
+                        change_detection: self.ticks.change_detection,
+                    },
+                }
+            }
+
             /// Allows you access to the dereferenced value of this pointer without immediately
             /// triggering change detection.
             pub fn as_deref_mut(&mut self) -> Mut<'_, <$target as Deref>::Target>
@@ -442,6 +524,8 @@ pub(crate) struct TicksMut<'w> {
     pub(crate) changed: &'w mut Tick,
     pub(crate) last_run: Tick,
     pub(crate) this_run: Tick,
+    /// Whether [`DetectChangesMut::set_changed`] updates `changed`.
+    pub(crate) change_detection: bool,
 }
 
 impl<'w> TicksMut<'w> {
@@ -460,8 +544,17 @@ impl<'w> TicksMut<'w> {
             changed: unsafe { cells.changed.deref_mut() },
             last_run,
             this_run,
+            change_detection: true,
         }
     }
+
+    /// Stops mutations from marking the value as changed if `change_detection` is `false`,
+    /// for components that opted out with [`Component::CHANGE_DETECTION`](crate::component::Component::CHANGE_DETECTION).
+    #[inline]
+    pub(crate) fn with_change_detection(mut self, change_detection: bool) -> Self {
+        self.change_detection = change_detection;
+        self
+    }
 }
 
 impl<'w> From<TicksMut<'w>> for Ticks<'w> {
@@ -671,6 +764,14 @@ impl<'w, T: ?Sized> Ref<'w, T> {
         }
     }
 
+    /// Optionally map `Ref` to a different type using `f`, returning `None` if `f` does.
+    pub fn filter_map<U: ?Sized>(self, f: impl FnOnce(&T) -> Option<&U>) -> Option<Ref<'w, U>> {
+        Some(Ref {
+            value: f(self.value)?,
+            ticks: self.ticks,
+        })
+    }
+
     /// Create a new `Ref` using provided values.
     ///
     /// This is an advanced feature, `Ref`s are designed to be _created_ by
@@ -751,6 +852,7 @@ impl<'w, T: ?Sized> Mut<'w, T> {
                 changed: last_changed,
                 last_run,
                 this_run,
+                change_detection: true,
             },
         }
     }
@@ -795,6 +897,100 @@ change_detection_mut_impl!(Mut<'w, T>, T,);
 impl_methods!(Mut<'w, T>, T,);
 impl_debug!(Mut<'w, T>,);
 
+/// A field of a component with its own change tick.
+///
+/// Large components can wrap their fields in `Tracked` so that systems can react to changes of
+/// individual fields with [`Ref::is_field_changed`] instead of to any change of the whole component.
+/// Fields are mutated through [`Mut::map_tracked`], which only marks the field as changed.
+///
+/// Tracked fields must be marked with `#[component(tracked)]`, so that `#[derive(Component)]`
+/// keeps their change ticks from overflowing (see [`Component::CHECK_TRACKED_TICKS`](crate::component::Component::CHECK_TRACKED_TICKS)).
+/// The change ticks of resources are checked without looking at their fields, so `Tracked`
+/// should not be used in resources.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::change_detection::Tracked;
+///
+/// #[derive(Component)]
+/// struct Settings {
+///     #[component(tracked)]
+///     volume: Tracked<f32>,
+///     #[component(tracked)]
+///     resolution: Tracked<(u32, u32)>,
+/// }
+///
+/// fn set_volume(mut settings: Query<&mut Settings>) {
+///     for settings in &mut settings {
+///         *settings.map_tracked(|settings| &mut settings.volume) = 0.5;
+///     }
+/// }
+///
+/// fn resize_window(settings: Query<Ref<Settings>>) {
+///     for settings in &settings {
+///         if settings.is_field_changed(|settings| &settings.resolution) {
+///             println!("resizing to {:?}", *settings.resolution);
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(set_volume);
+/// # bevy_ecs::system::assert_is_system(resize_window);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Tracked<T> {
+    value: T,
+    changed: Tick,
+}
+
+impl<T> Tracked<T> {
+    /// Wraps `value`. Until it is mutated through [`Mut::map_tracked`], the field only counts as changed
+    /// while its owner counts as added.
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            changed: Tick::new(0),
+        }
+    }
+
+    /// Returns the change tick recording the time this field was last mutated through [`Mut::map_tracked`].
+    pub fn last_changed(&self) -> Tick {
+        self.changed
+    }
+
+    /// Clamps the change tick of this field so that it doesn't overflow, like [`World::check_change_ticks`](crate::world::World::check_change_ticks)
+    /// does for whole components.
+    ///
+    /// This is called by the code generated for `#[component(tracked)]` fields.
+    pub fn check_change_tick(&mut self, change_tick: Tick) {
+        self.changed.check_tick(change_tick);
+    }
+
+    /// Returns a mutable reference to the value without updating its change tick.
+    pub fn bypass_change_detection(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Consumes `self`, returning the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> From<T> for Tracked<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
 /// Unique mutable borrow of resources or an entity's component.
 ///
 /// Similar to [`Mut`], but not generic over the component type, instead
@@ -829,6 +1025,7 @@ impl<'w> MutUntyped<'w> {
                 changed: self.ticks.changed,
                 last_run: self.ticks.last_run,
                 this_run: self.ticks.this_run,
+                change_detection: self.ticks.change_detection,
             },
         }
     }
@@ -917,12 +1114,21 @@ impl<'w> DetectChanges for MutUntyped<'w> {
     }
 }
 
+impl<'w> DetectAdded for MutUntyped<'w> {
+    #[inline]
+    fn added(&self) -> Tick {
+        *self.ticks.added
+    }
+}
+
 impl<'w> DetectChangesMut for MutUntyped<'w> {
     type Inner = PtrMut<'w>;
 
     #[inline]
     fn set_changed(&mut self) {
-        *self.ticks.changed = self.ticks.this_run;
+        if self.ticks.change_detection {
+            *self.ticks.changed = self.ticks.this_run;
+        }
     }
 
     #[inline]
@@ -936,6 +1142,14 @@ impl<'w> DetectChangesMut for MutUntyped<'w> {
     }
 }
 
+impl<'w> DetectAddedMut for MutUntyped<'w> {
+    #[inline]
+    fn set_last_added(&mut self, last_added: Tick) {
+        *self.ticks.added = last_added;
+        *self.ticks.changed = last_added;
+    }
+}
+
 impl std::fmt::Debug for MutUntyped<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_tuple("MutUntyped")
@@ -963,14 +1177,15 @@ mod tests {
     use crate::{
         self as bevy_ecs,
         change_detection::{
-            Mut, NonSendMut, Ref, ResMut, TicksMut, CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE,
+            Mut, NonSendMut, Ref, ResMut, TicksMut, Tracked, CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE,
         },
         component::{Component, ComponentTicks, Tick},
+        query::Changed,
         system::{IntoSystem, Query, System},
         world::World,
     };
 
-    use super::{DetectChanges, DetectChangesMut, MutUntyped};
+    use super::{DetectAdded, DetectAddedMut, DetectChanges, DetectChangesMut, MutUntyped};
 
     #[derive(Component, PartialEq)]
     struct C;
@@ -1029,6 +1244,47 @@ mod tests {
         assert!(!change_expired_system.run((), &mut world));
     }
 
+    #[derive(Component)]
+    #[component(change_detection = false)]
+    struct Untracked(u32);
+
+    #[test]
+    fn change_detection_opt_out() {
+        fn mutate(mut query: Query<&mut Untracked>) {
+            for mut untracked in &mut query {
+                untracked.0 += 1;
+            }
+        }
+
+        fn count_changed(query: Query<(), Changed<Untracked>>) -> usize {
+            query.iter().count()
+        }
+
+        let mut world = World::new();
+        let entity = world.spawn(Untracked(0)).id();
+
+        let mut mutate_system = IntoSystem::into_system(mutate);
+        let mut count_changed_system = IntoSystem::into_system(count_changed);
+        mutate_system.initialize(&mut world);
+        count_changed_system.initialize(&mut world);
+
+        // Inserting the component is still detected.
+        assert_eq!(count_changed_system.run((), &mut world), 1);
+        assert_eq!(count_changed_system.run((), &mut world), 0);
+
+        mutate_system.run((), &mut world);
+        world.get_mut::<Untracked>(entity).unwrap().0 += 1;
+        let component_id = world.component_id::<Untracked>().unwrap();
+        world
+            .entity_mut(entity)
+            .get_mut_by_id(component_id)
+            .unwrap()
+            .set_changed();
+
+        assert_eq!(world.get::<Untracked>(entity).unwrap().0, 2);
+        assert_eq!(count_changed_system.run((), &mut world), 0);
+    }
+
     #[test]
     fn change_tick_wraparound() {
         let mut world = World::new();
@@ -1087,6 +1343,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run: Tick::new(3),
             this_run: Tick::new(4),
+            change_detection: true,
         };
         let mut res = R {};
         let res_mut = ResMut {
@@ -1132,6 +1389,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run: Tick::new(3),
             this_run: Tick::new(4),
+            change_detection: true,
         };
         let mut res = R {};
         let non_send_mut = NonSendMut {
@@ -1162,6 +1420,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run,
             this_run,
+            change_detection: true,
         };
 
         let mut outer = Outer(0);
@@ -1208,6 +1467,110 @@ mod tests {
         );
     }
 
+    #[test]
+    fn manual_ticks() {
+        let mut world = World::new();
+
+        world.insert_resource(R2(0));
+        let added = world.resource_ref::<R2>().added();
+        world.increment_change_tick();
+        world.clear_trackers();
+
+        let mut r = world.resource_mut::<R2>();
+        assert_eq!(r.added(), added);
+        assert_eq!(r.last_changed(), added);
+
+        r.bypass_change_detection().0 = 1;
+        assert!(!r.is_changed());
+        assert_eq!(r.last_changed(), added);
+
+        let this_run = r.ticks.this_run;
+        r.set_last_changed(this_run);
+        assert!(r.is_changed());
+        assert!(!r.is_added());
+
+        r.set_last_added(this_run);
+        assert!(r.is_added());
+        assert_eq!(r.added(), this_run);
+        assert_eq!(r.last_changed(), this_run);
+    }
+
+    #[test]
+    fn tracked_fields() {
+        #[derive(Component)]
+        struct Settings {
+            #[component(tracked)]
+            a: Tracked<u32>,
+            #[component(tracked)]
+            b: Tracked<u32>,
+        }
+
+        let mut world = World::new();
+        let entity = world
+            .spawn(Settings {
+                a: Tracked::new(0),
+                b: Tracked::new(0),
+            })
+            .id();
+
+        let settings = world.entity(entity).get_ref::<Settings>().unwrap();
+        assert!(settings.is_field_changed(|settings| &settings.a));
+
+        world.increment_change_tick();
+        world.clear_trackers();
+
+        let settings = world.get_mut::<Settings>(entity).unwrap();
+        *settings.map_tracked(|settings| &mut settings.a) = 1;
+
+        let settings = world.entity(entity).get_ref::<Settings>().unwrap();
+        assert_eq!(*settings.a, 1);
+        assert!(settings.is_field_changed(|settings| &settings.a));
+        assert!(!settings.is_field_changed(|settings| &settings.b));
+        assert!(!settings.is_changed());
+    }
+
+    #[test]
+    fn tracked_field_tick_scan() {
+        #[derive(Component)]
+        #[component(storage = "SparseSet")]
+        struct Settings(#[component(tracked)] Tracked<u32>);
+
+        let mut world = World::new();
+        let entity = world.spawn(Settings(Tracked::new(0))).id();
+        let settings = world.get_mut::<Settings>(entity).unwrap();
+        *settings.map_tracked(|settings| &mut settings.0) = 1;
+
+        *world.change_tick.get_mut() += MAX_CHANGE_AGE + CHECK_TICK_THRESHOLD;
+        let change_tick = world.change_tick();
+        let field_tick = |world: &World| world.get::<Settings>(entity).unwrap().0.last_changed();
+        assert!(change_tick.relative_to(field_tick(&world)).get() > MAX_CHANGE_AGE);
+
+        world.check_change_ticks();
+        assert_eq!(
+            change_tick.relative_to(field_tick(&world)).get(),
+            MAX_CHANGE_AGE
+        );
+    }
+
+    #[test]
+    fn filter_map_unchanged() {
+        let mut world = World::new();
+
+        world.insert_resource(R2(0));
+        world.increment_change_tick();
+        world.clear_trackers();
+
+        let r = world.resource_mut::<R2>();
+        assert!(r.filter_map_unchanged(|_| None::<&mut u8>).is_none());
+
+        let r = world.resource_mut::<R2>();
+        let mut inner = r.filter_map_unchanged(|r| Some(&mut r.0)).unwrap();
+        assert!(!inner.is_changed());
+        *inner = 2;
+        assert!(inner.is_changed());
+        assert_eq!(world.resource::<R2>().0, 2);
+    }
+
     #[test]
     fn as_deref_mut() {
         let mut world = World::new();
@@ -1247,6 +1610,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run,
             this_run,
+            change_detection: true,
         };
 
         let mut value: i32 = 5;
@@ -1281,6 +1645,7 @@ mod tests {
             changed: &mut component_ticks.changed,
             last_run: Tick::new(3),
             this_run: Tick::new(4),
+            change_detection: true,
         };
         let mut c = C {};
         let mut_typed = Mut {
@@ -5,12 +5,13 @@ use crate::{
     archetype::ArchetypeFlags,
     change_detection::MAX_CHANGE_AGE,
     entity::Entity,
+    entity_disabling::Disabled,
     storage::{SparseSetIndex, Storages},
     system::{Local, Resource, SystemParam},
     world::{DeferredWorld, FromWorld, World},
 };
 pub use bevy_ecs_macros::Component;
-use bevy_ptr::{OwningPtr, UnsafeCellDeref};
+use bevy_ptr::{OwningPtr, PtrMut, UnsafeCellDeref};
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::Reflect;
 use bevy_utils::TypeIdMap;
@@ -93,6 +94,30 @@ use std::{
 /// [`Table`]: crate::storage::Table
 /// [`SparseSet`]: crate::storage::SparseSet
 ///
+/// # Adding component hooks
+///
+/// [`ComponentHooks`] can be registered from the derive with the `on_add`, `on_insert` and `on_remove` attributes,
+/// each taking the path of a function with the signature of a [`ComponentHook`]:
+///
+/// ```
+/// # use bevy_ecs::component::{Component, ComponentId};
+/// # use bevy_ecs::entity::Entity;
+/// # use bevy_ecs::world::DeferredWorld;
+/// # use bevy_utils::tracing::info;
+/// #
+/// #[derive(Component)]
+/// #[component(on_add = log_added, on_remove = log_removed)]
+/// struct Tracked;
+///
+/// fn log_added(_world: DeferredWorld, entity: Entity, _id: ComponentId) {
+///     info!("{entity:?} is now tracked");
+/// }
+///
+/// fn log_removed(_world: DeferredWorld, entity: Entity, _id: ComponentId) {
+///     info!("{entity:?} is no longer tracked");
+/// }
+/// ```
+///
 /// # Implementing the trait for foreign types
 ///
 /// As a consequence of the [orphan rule], it is not possible to separate into two different crates the implementation of `Component` from the definition of a type.
@@ -154,6 +179,54 @@ pub trait Component: Send + Sync + 'static {
     /// A constant indicating the storage type used for this component.
     const STORAGE_TYPE: StorageType;
 
+    /// The number of entities the storage of this component initially allocates room for,
+    /// or `None` to use the default.
+    ///
+    /// Only used for components with [`StorageType::SparseSet`], whose values are stored
+    /// together regardless of archetype. Setting this to the expected number of entities with
+    /// the component avoids reallocating while it is being inserted and removed frequently.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// #[component(storage = "SparseSet", capacity = 4096)]
+    /// struct Burning;
+    /// ```
+    const CAPACITY_HINT: Option<usize> = None;
+
+    /// Clamps the change ticks of the [`Tracked`](crate::change_detection::Tracked) fields of this component
+    /// during [`World::check_change_ticks`], or `None` if it has no such fields.
+    ///
+    /// The derive generates this for the fields marked with `#[component(tracked)]`.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// use bevy_ecs::change_detection::Tracked;
+    ///
+    /// #[derive(Component)]
+    /// struct Settings {
+    ///     #[component(tracked)]
+    ///     volume: Tracked<f32>,
+    /// }
+    /// ```
+    const CHECK_TRACKED_TICKS: Option<fn(&mut Self, Tick)> = None;
+
+    /// Whether mutating this component marks it as changed, `true` by default.
+    ///
+    /// When this is `false`, writing through a [`Mut`](crate::change_detection::Mut) or
+    /// [`MutUntyped`](crate::change_detection::MutUntyped) never updates the change tick,
+    /// which avoids that cost for components that are written every frame but never
+    /// checked for changes. [`Changed`](crate::query::Changed) then only detects when the
+    /// component is inserted, while [`Added`](crate::query::Added) is unaffected.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// #[component(change_detection = false)]
+    /// struct Velocity(f32);
+    /// ```
+    const CHANGE_DETECTION: bool = true;
+
     /// Called when registering this component, allowing mutable access to it's [`ComponentHooks`].
     fn register_component_hooks(_hooks: &mut ComponentHooks) {}
 }
@@ -296,12 +369,37 @@ impl ComponentInfo {
         self.descriptor.drop
     }
 
+    /// Returns the function clamping the change ticks of the [`Tracked`](crate::change_detection::Tracked)
+    /// fields of the underlying component type.
+    ///
+    /// Returns `None` if the component has no tracked fields, as reported by [`Component::CHECK_TRACKED_TICKS`].
+    pub fn check_tracked_ticks(&self) -> Option<unsafe fn(PtrMut<'_>, Tick)> {
+        self.descriptor.check_tracked_ticks
+    }
+
     /// Returns a value indicating the storage strategy for the current component.
     #[inline]
     pub fn storage_type(&self) -> StorageType {
         self.descriptor.storage_type
     }
 
+    /// Returns `true` if mutating the current component marks it as changed.
+    ///
+    /// See [`Component::CHANGE_DETECTION`].
+    #[inline]
+    pub fn change_detection(&self) -> bool {
+        self.descriptor.change_detection
+    }
+
+    /// Returns the number of entities the storage of the current component initially allocates room for,
+    /// if it has been configured.
+    ///
+    /// See [`Component::CAPACITY_HINT`].
+    #[inline]
+    pub fn capacity_hint(&self) -> Option<usize> {
+        self.descriptor.capacity_hint
+    }
+
     /// Returns `true` if the underlying component type can be freely shared between threads.
     /// If this returns `false`, then extra care must be taken to ensure that components
     /// are not accessed from the wrong thread.
@@ -319,9 +417,13 @@ impl ComponentInfo {
         }
     }
 
-    /// Update the given flags to include any [`ComponentHook`] registered to self
+    /// Update the given flags to include any [`ComponentHook`] registered to self,
+    /// and whether self is the [`Disabled`] marker
     #[inline]
     pub(crate) fn update_archetype_flags(&self, flags: &mut ArchetypeFlags) {
+        if self.type_id() == Some(TypeId::of::<Disabled>()) {
+            flags.insert(ArchetypeFlags::DISABLED);
+        }
         if self.hooks().on_add.is_some() {
             flags.insert(ArchetypeFlags::ON_ADD_HOOK);
         }
@@ -404,6 +506,8 @@ pub struct ComponentDescriptor {
     // SAFETY: This must remain private. It must match the statically known StorageType of the
     // associated rust component type if one exists.
     storage_type: StorageType,
+    capacity_hint: Option<usize>,
+    change_detection: bool,
     // SAFETY: This must remain private. It must only be set to "true" if this component is
     // actually Send + Sync
     is_send_and_sync: bool,
@@ -413,14 +517,20 @@ pub struct ComponentDescriptor {
     // this descriptor describes.
     // None if the underlying type doesn't need to be dropped
     drop: Option<for<'a> unsafe fn(OwningPtr<'a>)>,
+    // SAFETY: this function must be safe to call with pointers pointing to items of the type
+    // this descriptor describes.
+    // None if the underlying type has no tracked fields
+    check_tracked_ticks: Option<for<'a> unsafe fn(PtrMut<'a>, Tick)>,
 }
 
-// We need to ignore the `drop` field in our `Debug` impl
+// We need to ignore the `drop` and `check_tracked_ticks` fields in our `Debug` impl
 impl std::fmt::Debug for ComponentDescriptor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ComponentDescriptor")
             .field("name", &self.name)
             .field("storage_type", &self.storage_type)
+            .field("capacity_hint", &self.capacity_hint)
+            .field("change_detection", &self.change_detection)
             .field("is_send_and_sync", &self.is_send_and_sync)
             .field("type_id", &self.type_id)
             .field("layout", &self.layout)
@@ -439,15 +549,30 @@ impl ComponentDescriptor {
         }
     }
 
+    /// # SAFETY
+    ///
+    /// `x` must point to a valid value of type `T`.
+    unsafe fn check_tracked_ticks_ptr<T: Component>(x: PtrMut<'_>, change_tick: Tick) {
+        if let Some(check_tracked_ticks) = T::CHECK_TRACKED_TICKS {
+            // SAFETY: Contract is required to be upheld by the caller.
+            check_tracked_ticks(unsafe { x.deref_mut::<T>() }, change_tick);
+        }
+    }
+
     /// Create a new `ComponentDescriptor` for the type `T`.
     pub fn new<T: Component>() -> Self {
         Self {
             name: Cow::Borrowed(std::any::type_name::<T>()),
             storage_type: T::STORAGE_TYPE,
+            capacity_hint: T::CAPACITY_HINT,
+            change_detection: T::CHANGE_DETECTION,
             is_send_and_sync: true,
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            check_tracked_ticks: T::CHECK_TRACKED_TICKS
+                .is_some()
+                .then_some(Self::check_tracked_ticks_ptr::<T> as _),
         }
     }
 
@@ -465,10 +590,13 @@ impl ComponentDescriptor {
         Self {
             name: name.into(),
             storage_type,
+            capacity_hint: None,
+            change_detection: true,
             is_send_and_sync: true,
             type_id: None,
             layout,
             drop,
+            check_tracked_ticks: None,
         }
     }
 
@@ -481,10 +609,13 @@ impl ComponentDescriptor {
             // PERF: `SparseStorage` may actually be a more
             // reasonable choice as `storage_type` for resources.
             storage_type: StorageType::Table,
+            capacity_hint: None,
+            change_detection: true,
             is_send_and_sync: true,
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            check_tracked_ticks: None,
         }
     }
 
@@ -492,10 +623,13 @@ impl ComponentDescriptor {
         Self {
             name: Cow::Borrowed(std::any::type_name::<T>()),
             storage_type,
+            capacity_hint: None,
+            change_detection: true,
             is_send_and_sync: false,
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            check_tracked_ticks: None,
         }
     }
 
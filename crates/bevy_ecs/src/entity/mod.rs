@@ -679,6 +679,30 @@ impl Entities {
         result
     }
 
+    /// Returns `true` if `entity` is alive, or if it can be allocated again with
+    /// [`alloc_at_without_replacement`](Self::alloc_at_without_replacement) without reusing
+    /// a generation that was handed out after `entity` was freed.
+    ///
+    /// `will_free` is called with the entity that currently holds the slot of `entity`, if any,
+    /// and returns whether it is going to be freed before `entity` is allocated.
+    pub(crate) fn can_respawn(&self, entity: Entity, will_free: impl Fn(Entity) -> bool) -> bool {
+        let Some(meta) = self.meta.get(entity.index() as usize) else {
+            return true;
+        };
+        let mut generation = meta.generation;
+        if meta.location.archetype_id != ArchetypeId::INVALID {
+            let current = Entity::from_raw_and_generation(entity.index(), generation);
+            if !will_free(current) {
+                return generation == entity.generation;
+            }
+            generation = IdentifierMask::inc_masked_high_by(generation, 1);
+        }
+        // Freeing an entity bumps its generation once, so a slot that is one generation
+        // ahead has not been handed out since.
+        generation == entity.generation
+            || generation == IdentifierMask::inc_masked_high_by(entity.generation, 1)
+    }
+
     /// Destroy an entity, allowing it to be reused.
     ///
     /// Must not be called while reserved entities are awaiting `flush()`.
@@ -0,0 +1,120 @@
+//! Disabled entities do not show up in queries.
+//!
+//! Entities which are disabled are not removed from the [`World`],
+//! and keep all of their components, but queries will skip over them by default.
+//!
+//! An entity is disabled by inserting the [`Disabled`] marker component and re-enabled by removing it.
+//!
+//! A query can still see disabled entities by mentioning [`Disabled`] explicitly,
+//! either in its data (`Has<Disabled>`, `Option<&Disabled>`) or in its filter
+//! (`With<Disabled>`, `Without<Disabled>`).
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! #
+//! # #[derive(Component)]
+//! # struct Health(u32);
+//! #
+//! let mut world = World::new();
+//! world.spawn(Health(10));
+//! world.spawn((Health(20), Disabled));
+//!
+//! // Normal queries skip disabled entities.
+//! let mut query = world.query::<&Health>();
+//! assert_eq!(query.iter(&world).count(), 1);
+//!
+//! // Mentioning `Disabled` opts back in.
+//! let mut query = world.query::<(&Health, Has<Disabled>)>();
+//! assert_eq!(query.iter(&world).count(), 2);
+//! ```
+
+use crate as bevy_ecs;
+use crate::{
+    component::{Component, ComponentId},
+    query::FilteredAccess,
+    world::World,
+};
+
+/// A marker component for disabled entities.
+///
+/// Every query that does not mention this component in its data or filter
+/// implicitly skips archetypes containing it, as if it had a `Without<Disabled>` filter,
+/// so disabled entities are hidden from systems without having their components removed.
+/// This implicit filter is not part of the query's reported access.
+///
+/// See the [module docs](crate::entity_disabling) for more information.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Disabled;
+
+/// Returns whether a query with the given `access` should skip disabled entities,
+/// which is the case unless the access reads, checks for, or filters on [`Disabled`].
+pub(crate) fn skips_disabled(world: &World, access: &FilteredAccess<ComponentId>) -> bool {
+    // A query that mentions `Disabled` registers it while initializing its state.
+    world
+        .component_id::<Disabled>()
+        .map_or(true, |disabled_id| !mentions_component(access, disabled_id))
+}
+
+fn mentions_component(access: &FilteredAccess<ComponentId>, id: ComponentId) -> bool {
+    // `read_all` is not treated as a mention: `EntityRef` queries should still skip disabled entities.
+    access
+        .access()
+        .reads_and_writes()
+        .any(|accessed| accessed == id)
+        || access.access().has_archetypal(id)
+        || access.with_filters().any(|with| with == id)
+        || access.without_filters().any(|without| without == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Component)]
+    struct A;
+
+    #[test]
+    fn disabled_entities_are_skipped() {
+        let mut world = World::new();
+        let enabled = world.spawn(A).id();
+        let disabled = world.spawn((A, Disabled)).id();
+
+        let mut query = world.query::<Entity>();
+        assert_eq!(vec![enabled], query.iter(&world).collect::<Vec<_>>());
+
+        let mut query = world.query::<&A>();
+        assert!(query.get(&world, disabled).is_err());
+    }
+
+    #[test]
+    fn disabling_after_query_creation() {
+        let mut world = World::new();
+        let entity = world.spawn(A).id();
+
+        let mut query = world.query::<&A>();
+        assert_eq!(1, query.iter(&world).count());
+
+        world.entity_mut(entity).insert(Disabled);
+        assert_eq!(0, query.iter(&world).count());
+
+        world.entity_mut(entity).remove::<Disabled>();
+        assert_eq!(1, query.iter(&world).count());
+    }
+
+    #[test]
+    fn mentioning_disabled_opts_in() {
+        let mut world = World::new();
+        world.spawn(A);
+        world.spawn((A, Disabled));
+
+        let mut query = world.query::<(&A, Has<Disabled>)>();
+        assert_eq!(2, query.iter(&world).count());
+
+        let mut query = world.query::<(&A, Option<&Disabled>)>();
+        assert_eq!(2, query.iter(&world).count());
+
+        let mut query = world.query_filtered::<&A, With<Disabled>>();
+        assert_eq!(1, query.iter(&world).count());
+    }
+}
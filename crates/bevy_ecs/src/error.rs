@@ -0,0 +1,217 @@
+//! Error handling for fallible systems.
+//!
+//! Systems added to a [`Schedule`](crate::schedule::Schedule) or run as one-shot systems through
+//! [`Commands`](crate::system::Commands) may return [`Result`] instead of `()`.
+//! Errors returned this way are passed to the [`ErrorHandler`] of the [`World`](crate::world::World)
+//! they run in, which panics by default and can be replaced with the [`DefaultErrorHandler`] resource.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::error::Result;
+//! # use bevy_utils::tracing::info;
+//! #
+//! # #[derive(Resource)]
+//! # struct Config(String);
+//! #
+//! fn parse_config(config: Res<Config>) -> Result {
+//!     let value: u32 = config.0.parse()?;
+//!     info!("{value}");
+//!     Ok(())
+//! }
+//!
+//! let mut world = World::new();
+//! world.insert_resource(Config("12".to_string()));
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems(parse_config);
+//! schedule.run(&mut world);
+//! ```
+
+use std::{any::Any, borrow::Cow};
+
+use bevy_utils::tracing::{error as log_error, warn as log_warn};
+
+use crate as bevy_ecs;
+use crate::{
+    system::{In, IntoSystem, PipeSystem, Res, Resource, System},
+    world::World,
+};
+
+/// The error type returned by fallible systems: any boxed error that can be sent between threads.
+pub type BevyError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// A result that defaults to `Result<(), BevyError>`, the return type of fallible systems.
+pub type Result<T = (), E = BevyError> = std::result::Result<T, E>;
+
+/// Information about where an error passed to an [`ErrorHandler`] came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// The name of the system that returned the error.
+    pub name: Cow<'static, str>,
+}
+
+/// A function that handles the errors returned by fallible systems.
+pub type ErrorHandler = fn(BevyError, ErrorContext);
+
+/// The [`ErrorHandler`] used for the errors of the fallible systems of a [`World`].
+///
+/// Worlds without this resource use [`panic`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::error::{self, DefaultErrorHandler};
+/// let mut world = World::new();
+/// world.insert_resource(DefaultErrorHandler(error::warn));
+/// ```
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct DefaultErrorHandler(pub ErrorHandler);
+
+impl Default for DefaultErrorHandler {
+    fn default() -> Self {
+        Self(panic)
+    }
+}
+
+impl World {
+    /// Returns the [`ErrorHandler`] set with the [`DefaultErrorHandler`] resource, or [`panic`] if there is none.
+    pub fn error_handler(&self) -> ErrorHandler {
+        self.get_resource::<DefaultErrorHandler>()
+            .copied()
+            .unwrap_or_default()
+            .0
+    }
+}
+
+/// Passes `output` to the [`World::error_handler`] if it is an [`Err`] of a one-shot system.
+///
+/// One-shot systems run as commands can return any type, so any other output is discarded.
+/// `name` is only called if there is an error to report.
+pub(crate) fn handle_system_output<O: 'static>(
+    world: &World,
+    output: O,
+    name: impl FnOnce() -> Cow<'static, str>,
+) {
+    let mut output = Some(output);
+    if let Some(Some(Err(error))) = (&mut output as &mut dyn Any)
+        .downcast_mut::<Option<Result>>()
+        .map(Option::take)
+    {
+        world.error_handler()(error, ErrorContext { name: name() });
+    }
+}
+
+/// Wraps a fallible system so that its errors are passed to the [`DefaultErrorHandler`]
+/// of the world it runs in.
+///
+/// Used by [`IntoSystemConfigs`](crate::schedule::IntoSystemConfigs) to add systems returning [`Result`] to schedules.
+pub(crate) fn handle_errors<S>(system: S) -> impl System<In = (), Out = ()>
+where
+    S: System<In = (), Out = Result>,
+{
+    let name = system.name();
+    let context = ErrorContext { name: name.clone() };
+    let handle_error = move |In(result): In<Result>, handler: Option<Res<DefaultErrorHandler>>| {
+        if let Err(error) = result {
+            let handler = handler.as_deref().copied().unwrap_or_default();
+            (handler.0)(error, context.clone());
+        }
+    };
+    PipeSystem::new(system, IntoSystem::into_system(handle_error), name)
+}
+
+/// An [`ErrorHandler`] that panics with the error.
+pub fn panic(error: BevyError, context: ErrorContext) {
+    panic!("Encountered an error in system `{}`: {error}", context.name);
+}
+
+/// An [`ErrorHandler`] that logs the error at the `error` level.
+pub fn error(error: BevyError, context: ErrorContext) {
+    log_error!("Encountered an error in system `{}`: {error}", context.name);
+}
+
+/// An [`ErrorHandler`] that logs the error at the `warn` level.
+pub fn warn(error: BevyError, context: ErrorContext) {
+    log_warn!("Encountered an error in system `{}`: {error}", context.name);
+}
+
+/// An [`ErrorHandler`] that discards the error.
+pub fn ignore(_: BevyError, _: ErrorContext) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+
+    #[derive(Resource, Default)]
+    struct Counter(usize);
+
+    #[test]
+    fn fallible_system_runs() {
+        fn fallible(mut counter: ResMut<Counter>) -> Result {
+            counter.0 += 1;
+            Ok(())
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(fallible);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+
+        let exclusive = |world: &mut World| -> Result {
+            world.resource_mut::<Counter>().0 += 1;
+            Ok(())
+        };
+        schedule.add_systems(exclusive);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 3);
+    }
+
+    #[test]
+    #[should_panic = "Encountered an error in system"]
+    fn default_handler_panics() {
+        fn failing() -> Result {
+            Err("failed".into())
+        }
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(failing);
+        schedule.run(&mut world);
+    }
+
+    #[test]
+    fn error_handler_is_per_world() {
+        fn failing() -> Result {
+            Err("failed".into())
+        }
+
+        let mut world = World::new();
+        world.insert_resource(DefaultErrorHandler(ignore));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(failing);
+        schedule.run(&mut world);
+
+        let id = world.register_system(failing);
+        world.commands().run_system(id);
+        world.flush_commands();
+    }
+
+    #[test]
+    #[should_panic = "Encountered an error in system"]
+    fn one_shot_system_commands_handle_errors() {
+        let mut world = World::new();
+        let id = world.register_system(|| -> Result { Err("failed".into()) });
+        world.commands().run_system(id);
+        world.flush_commands();
+    }
+
+    #[test]
+    fn one_shot_systems_return_errors() {
+        let mut world = World::new();
+        let id = world.register_system(|| -> Result { Err("failed".into()) });
+        let result = world.run_system(id).unwrap();
+        assert_eq!(result.unwrap_err().to_string(), "failed");
+    }
+}
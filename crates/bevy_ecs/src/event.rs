@@ -446,6 +446,30 @@ impl<'w, 's, E: Event> EventReader<'w, 's, E> {
         self.reader.read_with_id(&self.events)
     }
 
+    /// Returns the most recent event this [`EventReader`] has not seen yet, if any, and marks
+    /// every available event as read.
+    ///
+    /// This is useful for events where only the latest value matters, such as window resizes.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Event)]
+    /// struct Resized {
+    ///     width: f32,
+    /// }
+    ///
+    /// fn update_layout(mut events: EventReader<Resized>) {
+    ///     if let Some(resized) = events.read_latest() {
+    ///         println!("New width: {}", resized.width);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(update_layout);
+    /// ```
+    pub fn read_latest(&mut self) -> Option<&E> {
+        self.read().last()
+    }
+
     /// Determines the number of events available to be read from this [`EventReader`] without consuming any.
     pub fn len(&self) -> usize {
         self.reader.len(&self.events)
@@ -846,6 +870,22 @@ impl EventRegistry {
         });
     }
 
+    /// Stops the [`event_update_system`] from updating the [`Events<T>`] resource.
+    ///
+    /// Its events are then no longer dropped after two updates, and are kept until
+    /// [`Events::update`] or [`Events::clear`] is called manually. This is useful for events
+    /// read by systems that do not run every frame, for example because of run conditions.
+    pub fn deregister_events<T: Event>(world: &mut World) {
+        let Some(component_id) = world.components().resource_id::<Events<T>>() else {
+            return;
+        };
+        if let Some(mut registry) = world.get_resource_mut::<Self>() {
+            registry
+                .event_updates
+                .retain(|event| event.component_id != component_id);
+        }
+    }
+
     /// Updates all of the registered events in the World.
     pub fn run_updates(&mut self, world: &mut World, last_change_tick: Tick) {
         for registered_event in &mut self.event_updates {
@@ -1187,6 +1227,43 @@ mod tests {
         assert!(is_empty, "EventReader should be empty");
     }
 
+    #[test]
+    fn test_deregistered_events_are_not_updated() {
+        use crate::system::RunSystemOnce;
+
+        let mut world = World::new();
+        EventRegistry::register_event::<TestEvent>(&mut world);
+        EventRegistry::deregister_events::<TestEvent>(&mut world);
+        world.send_event(TestEvent { i: 0 });
+
+        for _ in 0..3 {
+            world.run_system_once(event_update_system);
+        }
+        assert_eq!(world.resource::<Events<TestEvent>>().len(), 1);
+
+        EventRegistry::register_event::<TestEvent>(&mut world);
+        for _ in 0..3 {
+            world.run_system_once(event_update_system);
+        }
+        assert!(world.resource::<Events<TestEvent>>().is_empty());
+    }
+
+    #[test]
+    fn test_event_reader_read_latest() {
+        use crate::system::SystemState;
+
+        let mut world = World::new();
+        world.init_resource::<Events<TestEvent>>();
+        let mut state = SystemState::<EventReader<TestEvent>>::new(&mut world);
+
+        assert_eq!(state.get_mut(&mut world).read_latest(), None);
+
+        world.send_event_batch([TestEvent { i: 0 }, TestEvent { i: 1 }]);
+        let mut reader = state.get_mut(&mut world);
+        assert_eq!(reader.read_latest(), Some(&TestEvent { i: 1 }));
+        assert!(reader.is_empty());
+    }
+
     #[test]
     fn test_update_drain() {
         let mut events = Events::<TestEvent>::default();
@@ -0,0 +1,314 @@
+//! Look up entities by a key derived from one of their components.
+//!
+//! A [`ComponentIndex`] maps the [`IndexedComponent::Key`] of every entity with the component `C`
+//! to the entities that have it, so finding the entity in a grid cell or with a given id
+//! does not require iterating over all of them.
+//!
+//! The index is kept up to date by [component hooks](crate::component::ComponentHooks) when `C` is
+//! inserted, replaced or removed. If `C` is also mutated in place, add [`update_component_index`]
+//! to a schedule to pick up those changes.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::index::{IndexedComponent, QueryByIndex};
+//! #
+//! #[derive(Component)]
+//! struct GridCell(i32, i32);
+//!
+//! impl IndexedComponent for GridCell {
+//!     type Key = (i32, i32);
+//!
+//!     fn index_key(&self) -> Self::Key {
+//!         (self.0, self.1)
+//!     }
+//! }
+//!
+//! #[derive(Component)]
+//! struct Name(&'static str);
+//!
+//! fn print_origin(query: QueryByIndex<GridCell, &Name>) {
+//!     for name in query.get(&(0, 0)) {
+//!         println!("{} is at the origin", name.0);
+//!     }
+//! }
+//!
+//! let mut world = World::new();
+//! world.init_component_index::<GridCell>();
+//! world.spawn((GridCell(0, 0), Name("Alice")));
+//! world.spawn((GridCell(1, 0), Name("Bob")));
+//!
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems(print_origin);
+//! schedule.run(&mut world);
+//! ```
+
+use std::hash::Hash;
+
+use bevy_utils::HashMap;
+
+use crate as bevy_ecs;
+use crate::{
+    component::{Component, ComponentId},
+    entity::{Entity, EntityHashMap, EntityHashSet},
+    query::{Changed, QueryData, QueryFilter, ROQueryItem},
+    system::{Query, Res, ResMut, Resource, SystemParam},
+    world::{DeferredWorld, World},
+};
+
+/// A [`Component`] whose entities can be looked up by a key with a [`ComponentIndex`].
+pub trait IndexedComponent: Component {
+    /// The key entities are looked up by.
+    type Key: Hash + Eq + Clone + Send + Sync + 'static;
+
+    /// Returns the key of this component.
+    fn index_key(&self) -> Self::Key;
+}
+
+/// Maps the [`IndexedComponent::Key`] of each entity with the component `C` to the entities that have it.
+///
+/// Added with [`World::init_component_index`].
+#[derive(Resource)]
+pub struct ComponentIndex<C: IndexedComponent> {
+    entities: HashMap<C::Key, EntityHashSet>,
+    keys: EntityHashMap<C::Key>,
+}
+
+impl<C: IndexedComponent> Default for ComponentIndex<C> {
+    fn default() -> Self {
+        Self {
+            entities: HashMap::default(),
+            keys: EntityHashMap::default(),
+        }
+    }
+}
+
+impl<C: IndexedComponent> ComponentIndex<C> {
+    /// Returns an iterator over the entities whose component has the given key.
+    pub fn get<'a>(&'a self, key: &C::Key) -> impl Iterator<Item = Entity> + 'a {
+        self.entities
+            .get(key)
+            .into_iter()
+            .flat_map(|entities| entities.iter().copied())
+    }
+
+    /// Returns the key of the component on `entity`, if it has one.
+    pub fn key(&self, entity: Entity) -> Option<&C::Key> {
+        self.keys.get(&entity)
+    }
+
+    /// Returns `true` if any entity has a component with the given key.
+    pub fn contains_key(&self, key: &C::Key) -> bool {
+        self.entities.contains_key(key)
+    }
+
+    /// Returns the number of distinct keys in the index.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entity has the component.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    fn insert(&mut self, entity: Entity, key: C::Key) {
+        if self.keys.get(&entity) == Some(&key) {
+            return;
+        }
+        self.remove(entity);
+        self.entities.entry(key.clone()).or_default().insert(entity);
+        self.keys.insert(entity, key);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        let Some(key) = self.keys.remove(&entity) else {
+            return;
+        };
+        if let Some(entities) = self.entities.get_mut(&key) {
+            entities.remove(&entity);
+            if entities.is_empty() {
+                self.entities.remove(&key);
+            }
+        }
+    }
+}
+
+fn index_on_insert<C: IndexedComponent>(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    let Some(key) = world.get::<C>(entity).map(C::index_key) else {
+        return;
+    };
+    world
+        .resource_mut::<ComponentIndex<C>>()
+        .insert(entity, key);
+}
+
+fn index_on_remove<C: IndexedComponent>(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    world.resource_mut::<ComponentIndex<C>>().remove(entity);
+}
+
+/// Updates the [`ComponentIndex`] of `C` for components that were mutated in place.
+///
+/// Inserting, replacing and removing `C` updates the index on its own,
+/// so this system is only needed if `C` is changed through [`Mut`](crate::change_detection::Mut).
+pub fn update_component_index<C: IndexedComponent>(
+    mut index: ResMut<ComponentIndex<C>>,
+    query: Query<(Entity, &C), Changed<C>>,
+) {
+    for (entity, component) in &query {
+        let key = component.index_key();
+        if index.key(entity) != Some(&key) {
+            index.insert(entity, key);
+        }
+    }
+}
+
+impl World {
+    /// Starts maintaining a [`ComponentIndex`] for the component `C`.
+    ///
+    /// Does nothing if the index already exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` already has `on_insert` or `on_remove` hooks,
+    /// or if it has already been added to an entity.
+    pub fn init_component_index<C: IndexedComponent>(&mut self) {
+        if self.contains_resource::<ComponentIndex<C>>() {
+            return;
+        }
+        self.register_component_hooks::<C>()
+            .try_on_insert(index_on_insert::<C>)
+            .and_then(|hooks| hooks.try_on_remove(index_on_remove::<C>))
+            .unwrap_or_else(|| {
+                panic!(
+                    "Cannot index {}: it already has an on_insert or on_remove hook.",
+                    std::any::type_name::<C>()
+                )
+            });
+        self.init_resource::<ComponentIndex<C>>();
+    }
+}
+
+/// A [`SystemParam`] that looks up the entities matching a [`Query`] by the key of their component `C`.
+///
+/// Requires the index to have been added with [`World::init_component_index`].
+#[derive(SystemParam)]
+pub struct QueryByIndex<
+    'w,
+    's,
+    C: IndexedComponent,
+    D: QueryData + 'static,
+    F: QueryFilter + 'static = (),
+> {
+    index: Res<'w, ComponentIndex<C>>,
+    query: Query<'w, 's, D, F>,
+}
+
+impl<'w, 's, C: IndexedComponent, D: QueryData, F: QueryFilter> QueryByIndex<'w, 's, C, D, F> {
+    /// Returns the read-only query items of the entities whose component has the given key.
+    pub fn get<'a>(&'a self, key: &C::Key) -> impl Iterator<Item = ROQueryItem<'a, D>> + 'a {
+        let index: &'a ComponentIndex<C> = &self.index;
+        // Shorten the query's lifetimes so that the returned iterator does not capture `'w` and `'s`.
+        let query: &'a Query<'a, 'a, D, F> = &self.query;
+        index
+            .get(key)
+            .filter_map(move |entity| query.get(entity).ok())
+    }
+
+    /// Calls `f` with the query item of each entity whose component has the given key.
+    pub fn for_each_mut(&mut self, key: &C::Key, mut f: impl FnMut(D::Item<'_>)) {
+        for entity in self.index.get(key) {
+            if let Ok(item) = self.query.get_mut(entity) {
+                f(item);
+            }
+        }
+    }
+
+    /// Returns the underlying [`ComponentIndex`].
+    pub fn index(&self) -> &ComponentIndex<C> {
+        &self.index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::system::RunSystemOnce;
+
+    #[derive(Component)]
+    struct Cell(i32);
+
+    impl IndexedComponent for Cell {
+        type Key = i32;
+
+        fn index_key(&self) -> i32 {
+            self.0
+        }
+    }
+
+    #[derive(Component, PartialEq, Debug)]
+    struct Value(u32);
+
+    fn sorted(index: &ComponentIndex<Cell>, key: i32) -> Vec<Entity> {
+        let mut entities = index.get(&key).collect::<Vec<_>>();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn index_follows_inserts_and_removals() {
+        let mut world = World::new();
+        world.init_component_index::<Cell>();
+
+        let a = world.spawn(Cell(1)).id();
+        let b = world.spawn(Cell(1)).id();
+        let c = world.spawn(Cell(2)).id();
+
+        let index = world.resource::<ComponentIndex<Cell>>();
+        assert_eq!(sorted(index, 1), vec![a, b]);
+        assert_eq!(sorted(index, 2), vec![c]);
+
+        world.entity_mut(a).insert(Cell(2));
+        world.entity_mut(b).remove::<Cell>();
+        world.despawn(c);
+
+        let index = world.resource::<ComponentIndex<Cell>>();
+        assert!(!index.contains_key(&1));
+        assert_eq!(sorted(index, 2), vec![a]);
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn mutations_are_picked_up_by_system() {
+        let mut world = World::new();
+        world.init_component_index::<Cell>();
+        let entity = world.spawn(Cell(1)).id();
+
+        world.get_mut::<Cell>(entity).unwrap().0 = 5;
+        world.run_system_once(update_component_index::<Cell>);
+
+        let index = world.resource::<ComponentIndex<Cell>>();
+        assert_eq!(sorted(index, 5), vec![entity]);
+        assert!(!index.contains_key(&1));
+    }
+
+    #[test]
+    fn query_by_index() {
+        let mut world = World::new();
+        world.init_component_index::<Cell>();
+        world.spawn((Cell(1), Value(1)));
+        world.spawn((Cell(1), Value(2)));
+        world.spawn((Cell(2), Value(3)));
+        world.spawn(Cell(1));
+
+        world.run_system_once(|mut query: QueryByIndex<Cell, &mut Value>| {
+            query.for_each_mut(&1, |mut value| value.0 *= 10);
+        });
+        world.run_system_once(|query: QueryByIndex<Cell, &Value>| {
+            let mut values = query.get(&1).map(|value| value.0).collect::<Vec<_>>();
+            values.sort();
+            assert_eq!(values, vec![10, 20]);
+            assert_eq!(query.get(&2).next(), Some(&Value(3)));
+        });
+    }
+}
@@ -11,13 +11,31 @@
 #[cfg(target_pointer_width = "16")]
 compile_error!("bevy_ecs cannot safely compile for a 16-bit platform.");
 
+/// Invokes [`all_tuples!`](bevy_utils::all_tuples) for the tuple impls of the ECS traits,
+/// such as `Bundle`, `SystemParam` and `WorldQuery`.
+///
+/// Tuples are supported up to the given arity, or up to 32 elements with the `large_tuples` feature.
+macro_rules! all_ecs_tuples {
+    ($macro: ident, $start: tt, $end: tt, $($ident: ident),*) => {
+        #[cfg(not(feature = "large_tuples"))]
+        bevy_utils::all_tuples!($macro, $start, $end, $($ident),*);
+        #[cfg(feature = "large_tuples")]
+        bevy_utils::all_tuples!($macro, $start, 32, $($ident),*);
+    };
+}
+
 pub mod archetype;
+pub mod archetype_invariants;
+pub mod binary;
 pub mod bundle;
 pub mod change_detection;
 pub mod component;
 pub mod entity;
+pub mod entity_disabling;
+pub mod error;
 pub mod event;
 pub mod identifier;
+pub mod index;
 pub mod intern;
 pub mod label;
 pub mod query;
@@ -41,9 +59,12 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         bundle::Bundle,
-        change_detection::{DetectChanges, DetectChangesMut, Mut, Ref},
+        change_detection::{
+            DetectAdded, DetectAddedMut, DetectChanges, DetectChangesMut, Mut, Ref,
+        },
         component::Component,
         entity::{Entity, EntityMapper},
+        entity_disabling::Disabled,
         event::{Event, EventReader, EventWriter, Events},
         query::{Added, AnyOf, Changed, Has, Or, QueryBuilder, QueryState, With, Without},
         removal_detection::RemovedComponents,
@@ -69,7 +90,10 @@ mod tests {
         change_detection::Ref,
         component::{Component, ComponentId},
         entity::Entity,
-        query::{Added, Changed, FilteredAccess, QueryFilter, With, Without},
+        query::{
+            Added, BatchSizing, BatchingStrategy, Changed, FilteredAccess, QueryFilter, With,
+            Without,
+        },
         system::Resource,
         world::{EntityRef, Mut, World},
     };
@@ -426,6 +450,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn par_for_each_total_entity_sizing() {
+        ComputeTaskPool::get_or_init(TaskPool::default);
+        let mut world = World::new();
+        world.spawn_batch((0..10).map(A));
+        world.spawn_batch((10..20).map(|i| (A(i), B(1))));
+        world.spawn_batch((20..30).map(|i| (A(i), SparseStored(1))));
+        let results = Arc::new(Mutex::new(Vec::new()));
+        world
+            .query::<&A>()
+            .par_iter(&world)
+            .batching_strategy(BatchingStrategy::new().sizing(BatchSizing::TotalEntities))
+            .for_each(|&A(i)| results.lock().unwrap().push(i));
+        results.lock().unwrap().sort();
+        assert_eq!(*results.lock().unwrap(), (0..30).collect::<Vec<_>>());
+    }
+
     #[test]
     fn par_for_each_sparse() {
         ComputeTaskPool::get_or_init(TaskPool::default);
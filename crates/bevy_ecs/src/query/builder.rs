@@ -251,7 +251,7 @@ impl<'w, D: QueryData, F: QueryFilter> QueryBuilder<'w, D, F> {
 mod tests {
     use crate as bevy_ecs;
     use crate::prelude::*;
-    use crate::world::FilteredEntityRef;
+    use crate::world::{FilteredEntityMut, FilteredEntityRef};
 
     #[derive(Component, PartialEq, Debug)]
     struct A(usize);
@@ -396,4 +396,56 @@ mod tests {
             assert_eq!(1, b.deref::<B>().0);
         }
     }
+
+    #[test]
+    fn builder_dynamic_components_mut() {
+        let mut world = World::new();
+        let entity = world.spawn((A(0), B(1))).id();
+        let component_id_a = world.init_component::<A>();
+        let component_id_b = world.init_component::<B>();
+
+        let mut query = QueryBuilder::<FilteredEntityMut>::new(&mut world)
+            .mut_id(component_id_a)
+            .ref_id(component_id_b)
+            .build();
+
+        let mut entity_mut = query.single_mut(&mut world);
+
+        assert_eq!(entity, entity_mut.id());
+        assert!(entity_mut.get_mut_by_id(component_id_b).is_none());
+
+        let mut a = entity_mut.get_mut_by_id(component_id_a).unwrap();
+        // SAFETY: We set this pointer to point to this component
+        unsafe {
+            a.as_mut().deref_mut::<A>().0 = 2;
+        }
+
+        assert_eq!(2, world.get::<A>(entity).unwrap().0);
+    }
+
+    #[test]
+    fn builder_optional_dynamic_components() {
+        let mut world = World::new();
+        world.spawn((A(0), B(1)));
+        world.spawn(A(2));
+        let component_id_a = world.init_component::<A>();
+        let component_id_b = world.init_component::<B>();
+
+        let mut query = QueryBuilder::<FilteredEntityRef>::new(&mut world)
+            .ref_id(component_id_a)
+            .optional(|builder| {
+                builder.ref_id(component_id_b);
+            })
+            .build();
+
+        let mut with_b = 0;
+        for entity_ref in query.iter(&world) {
+            assert!(entity_ref.get_by_id(component_id_a).is_some());
+            if entity_ref.get_by_id(component_id_b).is_some() {
+                with_b += 1;
+            }
+        }
+        assert_eq!(2, query.iter(&world).count());
+        assert_eq!(1, with_b);
+    }
 }
@@ -32,3 +32,20 @@ pub enum QuerySingleError {
     #[error("Multiple entities fit the query {0}")]
     MultipleEntities(&'static str),
 }
+
+/// An error that occurs when transmuting a [`QueryState`](crate::query::QueryState) to a query
+/// with a different signature, for example with [`QueryState::try_transmute`](crate::query::QueryState::try_transmute).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Error)]
+pub enum QueryTransmuteError {
+    /// A component referenced by the new query has not been initialized in the world.
+    #[error("Could not create the state of {0}. Please initialize all referenced components before transmuting.")]
+    UninitializedComponent(&'static str),
+    /// The new query accesses terms that the original query does not.
+    #[error("Transmuted state for {new} attempts to access terms that are not allowed by original state {original}.")]
+    MissingAccess {
+        /// The type name of the new query.
+        new: &'static str,
+        /// The type name of the original query.
+        original: &'static str,
+    },
+}
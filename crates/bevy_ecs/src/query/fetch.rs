@@ -11,7 +11,6 @@ use crate::{
     },
 };
 use bevy_ptr::{ThinSlicePtr, UnsafeCellDeref};
-use bevy_utils::all_tuples;
 use std::{cell::UnsafeCell, marker::PhantomData};
 
 /// Types that can be fetched from a [`World`] using a [`Query`].
@@ -23,7 +22,8 @@ use std::{cell::UnsafeCell, marker::PhantomData};
 /// - **`QueryData` tuples.**
 ///   If every element of a tuple implements `QueryData`, then the tuple itself also implements the same trait.
 ///   This enables a single `Query` to access multiple components.
-///   Due to the current lack of variadic generics in Rust, the trait has been implemented for tuples from 0 to 15 elements,
+///   Due to the current lack of variadic generics in Rust, the trait has been implemented for tuples from 0 to 15 elements
+///   (or 32 with the `large_tuples` feature),
 ///   but nesting of tuples allows infinite `WorldQuery`s.
 /// - **[`Entity`].**
 ///   Gets the identifier of the queried entity.
@@ -243,6 +243,34 @@ use std::{cell::UnsafeCell, marker::PhantomData};
 /// }
 /// ```
 ///
+/// ## Field visibility
+///
+/// The fields of the generated item structs have the same visibility as the fields of the query struct.
+/// The `#[query_data(visibility = ...)]` field attribute sets a different one, for example to expose
+/// the items of a query whose fields are private.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// mod queries {
+///     # use bevy_ecs::prelude::*;
+///     # use bevy_ecs::query::QueryData;
+///     # #[derive(Component)]
+///     # pub struct ComponentA;
+///     #[derive(QueryData)]
+///     pub struct MyQuery {
+///         #[query_data(visibility = pub)]
+///         component_a: &'static ComponentA,
+///     }
+/// }
+///
+/// fn my_system(query: Query<queries::MyQuery>) {
+///     for item in &query {
+///         let _ = item.component_a;
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(my_system);
+/// ```
+///
 /// # Generic Queries
 ///
 /// When writing generic code, it is often necessary to use [`PhantomData`]
@@ -1322,6 +1350,7 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
                         added: added.deref_mut(),
                         changed: changed.deref_mut(),
                         this_run: fetch.this_run,
+                        change_detection: T::CHANGE_DETECTION,
                         last_run: fetch.last_run,
                     },
                 }
@@ -1339,7 +1368,8 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
 
                 Mut {
                     value: component.assert_unique().deref_mut(),
-                    ticks: TicksMut::from_tick_cells(ticks, fetch.last_run, fetch.this_run),
+                    ticks: TicksMut::from_tick_cells(ticks, fetch.last_run, fetch.this_run)
+                        .with_change_detection(T::CHANGE_DETECTION),
                 }
             }
         }
@@ -1803,8 +1833,8 @@ macro_rules! impl_anytuple_fetch {
     };
 }
 
-all_tuples!(impl_tuple_query_data, 0, 15, F, S);
-all_tuples!(impl_anytuple_fetch, 0, 15, F, S);
+all_ecs_tuples!(impl_tuple_query_data, 0, 15, F, S);
+all_ecs_tuples!(impl_anytuple_fetch, 0, 15, F, S);
 
 /// [`WorldQuery`] used to nullify queries by turning `Query<D>` into `Query<NopWorldQuery<D>>`
 ///
@@ -2018,6 +2048,32 @@ mod tests {
         assert_is_system(my_system);
     }
 
+    // Ensures that the `visibility` field attribute applies to the generated item structs.
+    #[test]
+    fn item_field_visibility() {
+        mod private {
+            use super::*;
+
+            #[derive(QueryData)]
+            #[query_data(mutable)]
+            pub struct D {
+                #[query_data(visibility = pub)]
+                a: &'static mut A,
+            }
+        }
+
+        fn my_system(mut query: Query<private::D>) {
+            for q in &query {
+                let _: &A = q.a;
+            }
+            for q in &mut query {
+                let _: Mut<A> = q.a;
+            }
+        }
+
+        assert_is_system(my_system);
+    }
+
     // Ensures that metadata types generated by the WorldQuery macro
     // do not conflict with user-defined types.
     // Regression test for https://github.com/bevyengine/bevy/issues/8010.
@@ -7,7 +7,6 @@ use crate::{
     world::{unsafe_world_cell::UnsafeWorldCell, World},
 };
 use bevy_ptr::{ThinSlicePtr, UnsafeCellDeref};
-use bevy_utils::all_tuples;
 use std::{cell::UnsafeCell, marker::PhantomData};
 
 /// Types that filter the results of a [`Query`].
@@ -20,7 +19,8 @@ use std::{cell::UnsafeCell, marker::PhantomData};
 /// - **`QueryFilter` tuples.**
 ///   If every element of a tuple implements `QueryFilter`, then the tuple itself also implements the same trait.
 ///   This enables a single `Query` to filter over multiple conditions.
-///   Due to the current lack of variadic generics in Rust, the trait has been implemented for tuples from 0 to 15 elements,
+///   Due to the current lack of variadic generics in Rust, the trait has been implemented for tuples from 0 to 15 elements
+///   (or 32 with the `large_tuples` feature),
 ///   but nesting of tuples allows infinite `QueryFilter`s.
 /// - **Filter disjunction operator.**
 ///   By default, tuples compose query filters in such a way that all conditions must be satisfied to generate a query item for a given entity.
@@ -511,8 +511,8 @@ macro_rules! impl_tuple_query_filter {
     };
 }
 
-all_tuples!(impl_tuple_query_filter, 0, 15, F);
-all_tuples!(impl_or_query_filter, 0, 15, F, S);
+all_ecs_tuples!(impl_tuple_query_filter, 0, 15, F);
+all_ecs_tuples!(impl_or_query_filter, 0, 15, F, S);
 
 /// A filter on a component that only retains results added after the system last ran.
 ///
@@ -951,4 +951,4 @@ macro_rules! impl_archetype_filter_tuple {
     };
 }
 
-all_tuples!(impl_archetype_filter_tuple, 0, 15, F);
+all_ecs_tuples!(impl_archetype_filter_tuple, 0, 15, F);
@@ -1,8 +1,8 @@
 use crate::{
     archetype::{Archetype, ArchetypeEntity, Archetypes},
     component::Tick,
-    entity::{Entities, Entity},
-    query::{ArchetypeFilter, DebugCheckedUnwrap, QueryState, StorageId},
+    entity::{Entities, Entity, EntityHashSet},
+    query::{ArchetypeFilter, DebugCheckedUnwrap, QueryEntityError, QueryState, StorageId},
     storage::{Table, TableRow, Tables},
     world::unsafe_world_cell::UnsafeWorldCell,
 };
@@ -414,6 +414,69 @@ where
 {
 }
 
+/// An [`Iterator`] over the query items generated from a list of unique [`Entity`]s.
+///
+/// Items are returned in the order of the provided list.
+/// Entities that don't match the query are skipped.
+///
+/// Unlike [`QueryManyIter`], this is a real [`Iterator`] even for mutable queries:
+/// the list is checked for duplicates up front, so no two items can alias.
+///
+/// This struct is created by the [`Query::iter_many_unique_mut`](crate::system::Query::iter_many_unique_mut) method.
+pub struct QueryManyUniqueIter<'w, 's, D: QueryData, F: QueryFilter> {
+    inner: QueryManyIter<'w, 's, D, F, std::vec::IntoIter<Entity>>,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter> QueryManyUniqueIter<'w, 's, D, F> {
+    /// # Safety
+    /// - `world` must have permission to access any of the components registered in `query_state`.
+    /// - `world` must be the same one used to initialize `query_state`.
+    pub(crate) unsafe fn new<EntityList: IntoIterator>(
+        world: UnsafeWorldCell<'w>,
+        query_state: &'s QueryState<D, F>,
+        entity_list: EntityList,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Result<QueryManyUniqueIter<'w, 's, D, F>, QueryEntityError>
+    where
+        EntityList::Item: Borrow<Entity>,
+    {
+        let entity_iter = entity_list.into_iter();
+        let mut seen =
+            EntityHashSet::with_capacity_and_hasher(entity_iter.size_hint().0, Default::default());
+        let mut entities = Vec::with_capacity(entity_iter.size_hint().0);
+        for entity in entity_iter {
+            let entity = *entity.borrow();
+            if !seen.insert(entity) {
+                return Err(QueryEntityError::AliasedMutability(entity));
+            }
+            entities.push(entity);
+        }
+        Ok(QueryManyUniqueIter {
+            inner: QueryManyIter::new(world, query_state, entities, last_run, this_run),
+        })
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter> Iterator for QueryManyUniqueIter<'w, 's, D, F> {
+    type Item = D::Item<'w>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: Every entity in the list is unique, so each item is fetched at most once
+        // and the returned items can never alias.
+        unsafe { self.inner.fetch_next_aliased_unchecked() }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, max_size) = self.inner.entity_iter.size_hint();
+        (0, max_size)
+    }
+}
+
+// This is correct as [`QueryManyUniqueIter`] always returns `None` once exhausted.
+impl<'w, 's, D: QueryData, F: QueryFilter> FusedIterator for QueryManyUniqueIter<'w, 's, D, F> {}
+
 /// An iterator over `K`-sized combinations of query items without repetition.
 ///
 /// A combination is an arrangement of a collection of items where order does not matter.
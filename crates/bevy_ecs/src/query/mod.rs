@@ -72,7 +72,9 @@ mod tests {
     use bevy_ecs_macros::{QueryData, QueryFilter};
 
     use crate::prelude::{AnyOf, Changed, Entity, Or, QueryState, With, Without};
-    use crate::query::{ArchetypeFilter, Has, QueryCombinationIter, ReadOnlyQueryData};
+    use crate::query::{
+        ArchetypeFilter, Has, QueryCombinationIter, QueryEntityError, ReadOnlyQueryData,
+    };
     use crate::schedule::{IntoSystemConfigs, Schedule};
     use crate::system::{IntoSystem, Query, System, SystemState};
     use crate::{self as bevy_ecs, component::Component, world::World};
@@ -739,6 +741,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn many_unique_entities() {
+        let mut world = World::new();
+        let e1 = world.spawn(A(1)).id();
+        let e2 = world.spawn(A(2)).id();
+        let e3 = world.spawn(B(3)).id();
+
+        let mut query = world.query::<&mut A>();
+        let values: Vec<usize> = query
+            .iter_many_unique_mut(&mut world, [e2, e3, e1])
+            .unwrap()
+            .map(|mut a| {
+                a.0 *= 10;
+                a.0
+            })
+            .collect();
+        assert_eq!(values, vec![20, 10]);
+
+        let result = query.iter_many_unique_mut(&mut world, [e1, e2, e1]);
+        assert!(matches!(
+            result.err(),
+            Some(QueryEntityError::AliasedMutability(entity)) if entity == e1
+        ));
+    }
+
     #[test]
     fn mut_to_immut_query_methods_have_immut_item() {
         #[derive(Component)]
@@ -14,7 +14,7 @@ use super::{QueryData, QueryFilter, QueryItem, QueryState};
 /// of threads (rounded up). This attempts to minimize the overhead of scheduling
 /// tasks onto multiple threads, but assumes each entity has roughly the
 /// same amount of work to be done, which may not hold true in every
-/// workload.
+/// workload. See [`BatchSizing`] for the other ways the batch size can be derived.
 ///
 /// See [`Query::par_iter`] for more information.
 ///
@@ -36,6 +36,41 @@ pub struct BatchingStrategy {
     ///
     /// [`ComputeTaskPool`]: bevy_tasks::ComputeTaskPool
     pub batches_per_thread: usize,
+    /// How the batch size is derived from the matched tables or archetypes.
+    ///
+    /// Defaults to [`BatchSizing::LargestStorage`].
+    pub sizing: BatchSizing,
+}
+
+/// How a [`BatchingStrategy`] derives the batch size from the tables or archetypes matched by a query.
+///
+/// In both cases the resulting size is divided by the number of batches,
+/// which is the number of threads times [`BatchingStrategy::batches_per_thread`],
+/// and then clamped to [`BatchingStrategy::batch_size_limits`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BatchSizing {
+    /// Uses the number of entities in the largest matched table or archetype.
+    ///
+    /// This splits the largest storage evenly between the threads, which works well
+    /// when most of the matched entities share a single archetype.
+    #[default]
+    LargestStorage,
+    /// Uses the total number of entities matched by the query.
+    ///
+    /// Storages smaller than the batch size are merged into a single batch, so this
+    /// avoids spawning many small tasks when the matched entities are spread
+    /// over many archetypes of similar size.
+    TotalEntities,
+}
+
+impl BatchSizing {
+    #[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
+    fn combine(self, storage_sizes: impl Iterator<Item = usize>) -> usize {
+        match self {
+            BatchSizing::LargestStorage => storage_sizes.max().unwrap_or(0),
+            BatchSizing::TotalEntities => storage_sizes.sum(),
+        }
+    }
 }
 
 impl BatchingStrategy {
@@ -44,6 +79,7 @@ impl BatchingStrategy {
         Self {
             batch_size_limits: 1..usize::MAX,
             batches_per_thread: 1,
+            sizing: BatchSizing::LargestStorage,
         }
     }
 
@@ -52,6 +88,7 @@ impl BatchingStrategy {
         Self {
             batch_size_limits: batch_size..batch_size,
             batches_per_thread: 1,
+            sizing: BatchSizing::LargestStorage,
         }
     }
 
@@ -76,6 +113,12 @@ impl BatchingStrategy {
         self.batches_per_thread = batches_per_thread;
         self
     }
+
+    /// Configures how the batch size of this instance is derived from the matched entities.
+    pub const fn sizing(mut self, sizing: BatchSizing) -> Self {
+        self.sizing = sizing;
+        self
+    }
 }
 
 impl Default for BatchingStrategy {
@@ -167,21 +210,23 @@ impl<'w, 's, D: QueryData, F: QueryFilter> QueryParIter<'w, 's, D, F> {
             "Attempted to run parallel iteration over a query with an empty TaskPool"
         );
         let id_iter = self.state.matched_storage_ids.iter();
+        let sizing = self.batching_strategy.sizing;
         let max_size = if D::IS_DENSE && F::IS_DENSE {
             // SAFETY: We only access table metadata.
             let tables = unsafe { &self.world.world_metadata().storages().tables };
-            id_iter
-                // SAFETY: The if check ensures that matched_storage_ids stores TableIds
-                .map(|id| unsafe { tables[id.table_id].entity_count() })
-                .max()
+            sizing.combine(
+                id_iter
+                    // SAFETY: The if check ensures that matched_storage_ids stores TableIds
+                    .map(|id| unsafe { tables[id.table_id].entity_count() }),
+            )
         } else {
             let archetypes = &self.world.archetypes();
-            id_iter
-                // SAFETY: The if check ensures that matched_storage_ids stores ArchetypeIds
-                .map(|id| unsafe { archetypes[id.archetype_id].len() })
-                .max()
+            sizing.combine(
+                id_iter
+                    // SAFETY: The if check ensures that matched_storage_ids stores ArchetypeIds
+                    .map(|id| unsafe { archetypes[id.archetype_id].len() }),
+            )
         };
-        let max_size = max_size.unwrap_or(0);
 
         let batches = thread_count * self.batching_strategy.batches_per_thread;
         // Round up to the nearest batch size.
@@ -2,6 +2,7 @@ use crate::{
     archetype::{Archetype, ArchetypeComponentId, ArchetypeGeneration, ArchetypeId},
     component::{ComponentId, Tick},
     entity::Entity,
+    entity_disabling,
     prelude::FromWorld,
     query::{
         Access, BatchingStrategy, DebugCheckedUnwrap, FilteredAccess, QueryCombinationIter,
@@ -18,7 +19,7 @@ use std::{borrow::Borrow, fmt, mem::MaybeUninit, ptr};
 
 use super::{
     NopWorldQuery, QueryBuilder, QueryData, QueryEntityError, QueryFilter, QueryManyIter,
-    QuerySingleError, ROQueryItem,
+    QueryManyUniqueIter, QuerySingleError, QueryTransmuteError, ROQueryItem,
 };
 
 /// An ID for either a table or an archetype. Used for Query iteration.
@@ -66,6 +67,9 @@ pub struct QueryState<D: QueryData, F: QueryFilter = ()> {
     /// [`FilteredAccess`] computed by combining the `D` and `F` access. Used to check which other queries
     /// this query can run in parallel with.
     pub(crate) component_access: FilteredAccess<ComponentId>,
+    /// Whether archetypes containing [`Disabled`](crate::entity_disabling::Disabled) are skipped.
+    /// This is not part of `component_access`, so it does not affect which queries can run in parallel.
+    pub(crate) skip_disabled: bool,
     // NOTE: we maintain both a bitset and a vec because iterating the vec is faster
     pub(super) matched_storage_ids: Vec<StorageId>,
     pub(crate) fetch_state: D::State,
@@ -190,6 +194,8 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         // properly considered in a global "cross-query" context (both within systems and across systems).
         component_access.extend(&filter_component_access);
 
+        let skip_disabled = entity_disabling::skips_disabled(world, &component_access);
+
         Self {
             world_id: world.id(),
             archetype_generation: ArchetypeGeneration::initial(),
@@ -197,6 +203,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             fetch_state,
             filter_state,
             component_access,
+            skip_disabled,
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             #[cfg(feature = "trace")]
@@ -214,6 +221,8 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         let filter_state = F::init_state(builder.world_mut());
         D::set_access(&mut fetch_state, builder.access());
 
+        let skip_disabled = entity_disabling::skips_disabled(builder.world(), builder.access());
+
         let mut state = Self {
             world_id: builder.world().id(),
             archetype_generation: ArchetypeGeneration::initial(),
@@ -221,6 +230,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             fetch_state,
             filter_state,
             component_access: builder.access().clone(),
+            skip_disabled,
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             #[cfg(feature = "trace")]
@@ -390,6 +400,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         if D::matches_component_set(&self.fetch_state, &|id| archetype.contains(id))
             && F::matches_component_set(&self.filter_state, &|id| archetype.contains(id))
             && self.matches_component_set(&|id| archetype.contains(id))
+            && !(self.skip_disabled && archetype.is_disabled())
         {
             let archetype_index = archetype.id().index();
             if !self.matched_archetypes.contains(archetype_index) {
@@ -468,9 +479,47 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         &self,
         world: &World,
     ) -> QueryState<NewD, NewF> {
+        self.try_transmute_filtered(world)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Equivalent to [`Self::transmute`], but returns an error instead of panicking
+    /// if `NewD` requires accesses that this query does not have.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::query::QueryTransmuteError;
+    /// # #[derive(Component)] struct Transform;
+    /// # #[derive(Component)] struct Velocity;
+    /// let mut world = World::new();
+    /// let state = world.query::<(&Transform, &Velocity)>();
+    ///
+    /// assert!(state.try_transmute::<&Transform>(&world).is_ok());
+    /// assert!(matches!(
+    ///     state.try_transmute::<&mut Transform>(&world),
+    ///     Err(QueryTransmuteError::MissingAccess { .. })
+    /// ));
+    /// ```
+    pub fn try_transmute<NewD: QueryData>(
+        &self,
+        world: &World,
+    ) -> Result<QueryState<NewD>, QueryTransmuteError> {
+        self.try_transmute_filtered::<NewD, ()>(world)
+    }
+
+    /// Equivalent to [`Self::transmute_filtered`], but returns an error instead of panicking
+    /// if `NewD` or `NewF` require accesses that this query does not have.
+    pub fn try_transmute_filtered<NewD: QueryData, NewF: QueryFilter>(
+        &self,
+        world: &World,
+    ) -> Result<QueryState<NewD, NewF>, QueryTransmuteError> {
         let mut component_access = FilteredAccess::default();
-        let mut fetch_state = NewD::get_state(world).expect("Could not create fetch_state, Please initialize all referenced components before transmuting.");
-        let filter_state = NewF::get_state(world).expect("Could not create filter_state, Please initialize all referenced components before transmuting.");
+        let mut fetch_state = NewD::get_state(world).ok_or(
+            QueryTransmuteError::UninitializedComponent(std::any::type_name::<NewD>()),
+        )?;
+        let filter_state = NewF::get_state(world).ok_or(
+            QueryTransmuteError::UninitializedComponent(std::any::type_name::<NewF>()),
+        )?;
 
         NewD::set_access(&mut fetch_state, &self.component_access);
         NewD::update_component_access(&fetch_state, &mut component_access);
@@ -479,19 +528,21 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         NewF::update_component_access(&filter_state, &mut filter_component_access);
 
         component_access.extend(&filter_component_access);
-        assert!(
-            component_access.is_subset(&self.component_access),
-            "Transmuted state for {} attempts to access terms that are not allowed by original state {}.",
-            std::any::type_name::<(NewD, NewF)>(), std::any::type_name::<(D, F)>()
-        );
+        if !component_access.is_subset(&self.component_access) {
+            return Err(QueryTransmuteError::MissingAccess {
+                new: std::any::type_name::<(NewD, NewF)>(),
+                original: std::any::type_name::<(D, F)>(),
+            });
+        }
 
-        QueryState {
+        Ok(QueryState {
             world_id: self.world_id,
             archetype_generation: self.archetype_generation,
             matched_storage_ids: self.matched_storage_ids.clone(),
             fetch_state,
             filter_state,
             component_access: self.component_access.clone(),
+            skip_disabled: self.skip_disabled,
             matched_tables: self.matched_tables.clone(),
             matched_archetypes: self.matched_archetypes.clone(),
             #[cfg(feature = "trace")]
@@ -500,7 +551,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
                 query = std::any::type_name::<NewD>(),
                 filter = std::any::type_name::<NewF>(),
             ),
-        }
+        })
     }
 
     /// Use this to combine two queries. The data accessed will be the intersection
@@ -522,12 +573,12 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
     /// ## Panics
     ///
     /// Will panic if `NewD` contains accesses not in `Q` or `OtherQ`.
-    pub fn join<OtherD: QueryData, NewD: QueryData>(
+    pub fn join<OtherD: QueryData, NewD: QueryData, OtherF: QueryFilter>(
         &self,
         world: &World,
-        other: &QueryState<OtherD>,
+        other: &QueryState<OtherD, OtherF>,
     ) -> QueryState<NewD, ()> {
-        self.join_filtered::<_, (), NewD, ()>(world, other)
+        self.join_filtered::<_, _, NewD, ()>(world, other)
     }
 
     /// Use this to combine two queries. The data accessed will be the intersection
@@ -605,6 +656,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             fetch_state: new_fetch_state,
             filter_state: new_filter_state,
             component_access: joined_component_access,
+            skip_disabled: self.skip_disabled || other.skip_disabled,
             matched_tables,
             matched_archetypes,
             #[cfg(feature = "trace")]
@@ -1162,6 +1214,38 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         }
     }
 
+    /// Returns an [`Iterator`] over the query items generated from a list of unique [`Entity`]s.
+    ///
+    /// Items are returned in the order of the list of entities.
+    /// Entities that don't match the query are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QueryEntityError::AliasedMutability`] if the same entity appears more than once in `entities`.
+    #[inline]
+    pub fn iter_many_unique_mut<'w, 's, EntityList: IntoIterator>(
+        &'s mut self,
+        world: &'w mut World,
+        entities: EntityList,
+    ) -> Result<QueryManyUniqueIter<'w, 's, D, F>, QueryEntityError>
+    where
+        EntityList::Item: Borrow<Entity>,
+    {
+        self.update_archetypes(world);
+        let change_tick = world.change_tick();
+        let last_change_tick = world.last_change_tick();
+        // SAFETY: Query has unique world access.
+        unsafe {
+            QueryManyUniqueIter::new(
+                world.as_unsafe_world_cell(),
+                self,
+                entities,
+                last_change_tick,
+                change_tick,
+            )
+        }
+    }
+
     /// Returns an [`Iterator`] over the query results for the given [`World`].
     ///
     /// This iterator is always guaranteed to return results from each matching entity once and only once.
@@ -1627,7 +1711,11 @@ impl<D: QueryData, F: QueryFilter> From<QueryBuilder<'_, D, F>> for QueryState<D
 mod tests {
     use crate as bevy_ecs;
     use crate::world::FilteredEntityRef;
-    use crate::{component::Component, prelude::*, query::QueryEntityError};
+    use crate::{
+        component::Component,
+        prelude::*,
+        query::{QueryEntityError, QueryTransmuteError},
+    };
 
     #[test]
     fn get_many_unchecked_manual_uniqueness() {
@@ -1890,6 +1978,24 @@ mod tests {
         assert_eq!(1, entity_ref.get::<B>().unwrap().0);
     }
 
+    #[test]
+    fn try_transmute_returns_errors() {
+        let mut world = World::new();
+        world.init_component::<A>();
+        let query = QueryState::<&A>::new(&mut world);
+
+        assert!(query.try_transmute::<(Entity, Ref<A>)>(&world).is_ok());
+        assert!(matches!(
+            query.try_transmute::<&B>(&world),
+            Err(QueryTransmuteError::UninitializedComponent(_))
+        ));
+        world.init_component::<B>();
+        assert!(matches!(
+            query.try_transmute::<&B>(&world),
+            Err(QueryTransmuteError::MissingAccess { .. })
+        ));
+    }
+
     #[test]
     fn can_transmute_added() {
         let mut world = World::new();
@@ -1974,6 +2080,20 @@ mod tests {
         assert!(new_query.get(&world, entity_abc).is_err());
     }
 
+    #[test]
+    fn join_filtered_other() {
+        let mut world = World::new();
+        world.spawn((A(0), B(1)));
+        world.spawn((A(2), B(3), C(4)));
+
+        let query_1 = QueryState::<&A>::new(&mut world);
+        let query_2 = QueryState::<&B, With<C>>::new(&mut world);
+        let mut new_query: QueryState<(&A, &B)> = query_1.join(&world, &query_2);
+
+        let (a, b) = new_query.single(&world);
+        assert_eq!((a.0, b.0), (2, 3));
+    }
+
     #[test]
     #[should_panic(expected = "Joined state for (&bevy_ecs::query::state::tests::C, ()) \
             attempts to access terms that are not allowed by state \
@@ -168,6 +168,104 @@ pub trait Condition<Marker, In = ()>: sealed::Condition<Marker, In> {
         let name = format!("{} || {}", a.name(), b.name());
         CombinatorSystem::new(a, b, Cow::Owned(name))
     }
+
+    /// Returns a new run condition that returns `true`
+    /// if either this one or the passed `nand` return `false`.
+    ///
+    /// The returned run condition is short-circuiting, meaning
+    /// `nand` will only be invoked if `self` returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    ///
+    /// #[derive(Resource, PartialEq)]
+    /// struct Paused;
+    ///
+    /// #[derive(Resource, PartialEq)]
+    /// struct InMenu;
+    ///
+    /// # let mut app = Schedule::default();
+    /// # let mut world = World::new();
+    /// # fn my_system() {}
+    /// app.add_systems(
+    ///     // Runs unless the game is both paused and in a menu.
+    ///     my_system.run_if(resource_exists::<Paused>.nand(resource_exists::<InMenu>)),
+    /// );
+    /// # app.run(&mut world);
+    /// ```
+    fn nand<M, C: Condition<M, In>>(self, nand: C) -> Nand<Self::System, C::System> {
+        let a = IntoSystem::into_system(self);
+        let b = IntoSystem::into_system(nand);
+        let name = format!("!({} && {})", a.name(), b.name());
+        CombinatorSystem::new(a, b, Cow::Owned(name))
+    }
+
+    /// Returns a new run condition that returns `true`
+    /// if both this one and the passed `nor` return `false`.
+    ///
+    /// The returned run condition is short-circuiting, meaning
+    /// `nor` will only be invoked if `self` returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    ///
+    /// #[derive(Resource, PartialEq)]
+    /// struct Paused;
+    ///
+    /// #[derive(Resource, PartialEq)]
+    /// struct InMenu;
+    ///
+    /// # let mut app = Schedule::default();
+    /// # let mut world = World::new();
+    /// # fn my_system() {}
+    /// app.add_systems(
+    ///     // Only runs if the game is neither paused nor in a menu.
+    ///     my_system.run_if(resource_exists::<Paused>.nor(resource_exists::<InMenu>)),
+    /// );
+    /// # app.run(&mut world);
+    /// ```
+    fn nor<M, C: Condition<M, In>>(self, nor: C) -> Nor<Self::System, C::System> {
+        let a = IntoSystem::into_system(self);
+        let b = IntoSystem::into_system(nor);
+        let name = format!("!({} || {})", a.name(), b.name());
+        CombinatorSystem::new(a, b, Cow::Owned(name))
+    }
+
+    /// Returns a new run condition that returns `true`
+    /// if exactly one of this one and the passed `xor` returns `true`.
+    ///
+    /// The returned run condition always invokes both conditions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    ///
+    /// #[derive(Resource, PartialEq)]
+    /// struct A;
+    ///
+    /// #[derive(Resource, PartialEq)]
+    /// struct B;
+    ///
+    /// # let mut app = Schedule::default();
+    /// # let mut world = World::new();
+    /// # fn my_system() {}
+    /// app.add_systems(
+    ///     // Only runs if exactly one of `A` and `B` exists.
+    ///     my_system.run_if(resource_exists::<A>.xor(resource_exists::<B>)),
+    /// );
+    /// # app.run(&mut world);
+    /// ```
+    fn xor<M, C: Condition<M, In>>(self, xor: C) -> Xor<Self::System, C::System> {
+        let a = IntoSystem::into_system(self);
+        let b = IntoSystem::into_system(xor);
+        let name = format!("({} ^ {})", a.name(), b.name());
+        CombinatorSystem::new(a, b, Cow::Owned(name))
+    }
 }
 
 impl<Marker, In, F> Condition<Marker, In> for F where F: sealed::Condition<Marker, In> {}
@@ -984,6 +1082,15 @@ pub type AndThen<A, B> = CombinatorSystem<AndThenMarker, A, B>;
 /// Combines the outputs of two systems using the `||` operator.
 pub type OrElse<A, B> = CombinatorSystem<OrElseMarker, A, B>;
 
+/// Combines and inverts the outputs of two systems using the `&&` and `!` operators.
+pub type Nand<A, B> = CombinatorSystem<NandMarker, A, B>;
+
+/// Combines and inverts the outputs of two systems using the `||` and `!` operators.
+pub type Nor<A, B> = CombinatorSystem<NorMarker, A, B>;
+
+/// Combines the outputs of two systems using the `^` operator.
+pub type Xor<A, B> = CombinatorSystem<XorMarker, A, B>;
+
 #[doc(hidden)]
 pub struct AndThenMarker;
 
@@ -1026,6 +1133,69 @@ where
     }
 }
 
+#[doc(hidden)]
+pub struct NandMarker;
+
+impl<In, A, B> Combine<A, B> for NandMarker
+where
+    In: Copy,
+    A: System<In = In, Out = bool>,
+    B: System<In = In, Out = bool>,
+{
+    type In = In;
+    type Out = bool;
+
+    fn combine(
+        input: Self::In,
+        a: impl FnOnce(<A as System>::In) -> <A as System>::Out,
+        b: impl FnOnce(<B as System>::In) -> <B as System>::Out,
+    ) -> Self::Out {
+        !(a(input) && b(input))
+    }
+}
+
+#[doc(hidden)]
+pub struct NorMarker;
+
+impl<In, A, B> Combine<A, B> for NorMarker
+where
+    In: Copy,
+    A: System<In = In, Out = bool>,
+    B: System<In = In, Out = bool>,
+{
+    type In = In;
+    type Out = bool;
+
+    fn combine(
+        input: Self::In,
+        a: impl FnOnce(<A as System>::In) -> <A as System>::Out,
+        b: impl FnOnce(<B as System>::In) -> <B as System>::Out,
+    ) -> Self::Out {
+        !(a(input) || b(input))
+    }
+}
+
+#[doc(hidden)]
+pub struct XorMarker;
+
+impl<In, A, B> Combine<A, B> for XorMarker
+where
+    In: Copy,
+    A: System<In = In, Out = bool>,
+    B: System<In = In, Out = bool>,
+{
+    type In = In;
+    type Out = bool;
+
+    fn combine(
+        input: Self::In,
+        a: impl FnOnce(<A as System>::In) -> <A as System>::Out,
+        b: impl FnOnce(<B as System>::In) -> <B as System>::Out,
+    ) -> Self::Out {
+        a(input) ^ b(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{common_conditions::*, Condition};
@@ -1094,6 +1264,27 @@ mod tests {
         assert_eq!(world.resource::<Counter>().0, 3);
     }
 
+    #[test]
+    fn run_condition_negated_combinators() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::default();
+
+        // Run every other cycle, starting with the second
+        schedule.add_systems(increment_counter.run_if(every_other_time.nand(|| true)));
+        // Never run
+        schedule.add_systems(increment_counter.run_if(every_other_time.nor(|| true)));
+        // Run every other cycle, starting with the second
+        schedule.add_systems(increment_counter.run_if(every_other_time.xor(|| true)));
+        // Run every other cycle, starting with the first
+        schedule.add_systems(increment_counter.run_if(every_other_time.xor(|| false)));
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 3);
+    }
+
     #[test]
     fn multiple_run_conditions() {
         let mut world = World::new();
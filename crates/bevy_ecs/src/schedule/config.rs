@@ -1,6 +1,7 @@
 use bevy_utils::all_tuples;
 
 use crate::{
+    error::{self, Result},
     schedule::{
         condition::{BoxedCondition, Condition},
         graph_utils::{Ambiguity, Dependency, DependencyKind, GraphInfo},
@@ -33,7 +34,7 @@ fn ambiguous_with(graph_info: &mut GraphInfo, set: InternedSystemSet) {
     }
 }
 
-impl<Marker, F> IntoSystemConfigs<Marker> for F
+impl<Marker, F> IntoSystemConfigs<(fn(), Marker)> for F
 where
     F: IntoSystem<(), (), Marker>,
 {
@@ -42,6 +43,19 @@ where
     }
 }
 
+/// Systems returning a [`Result`] pass their errors to the
+/// [`DefaultErrorHandler`](crate::error::DefaultErrorHandler) of the world they run in.
+impl<Marker, F> IntoSystemConfigs<(fn() -> Result, Marker)> for F
+where
+    F: IntoSystem<(), Result, Marker>,
+{
+    fn into_configs(self) -> SystemConfigs {
+        SystemConfigs::new_system(Box::new(error::handle_errors(IntoSystem::into_system(
+            self,
+        ))))
+    }
+}
+
 impl IntoSystemConfigs<()> for BoxedSystem<(), ()> {
     fn into_configs(self) -> SystemConfigs {
         SystemConfigs::new_system(self)
@@ -631,12 +645,12 @@ where
         self.into_configs().chain()
     }
 
-    /// Treat this collection as a sequence of systems.
+    /// Treat this collection as a sequence of system sets.
     ///
     /// Ordering constraints will be applied between the successive elements.
     ///
     /// Unlike [`chain`](Self::chain) this will **not** add [`apply_deferred`](crate::schedule::apply_deferred) on the edges.
-    fn chain_ignore_deferred(self) -> SystemConfigs {
+    fn chain_ignore_deferred(self) -> SystemSetConfigs {
         self.into_configs().chain_ignore_deferred()
     }
 }
@@ -707,6 +721,10 @@ impl IntoSystemSetConfigs for SystemSetConfigs {
     fn chain(self) -> Self {
         self.chain_inner()
     }
+
+    fn chain_ignore_deferred(self) -> Self {
+        self.chain_ignore_deferred_inner()
+    }
 }
 
 impl<S: SystemSet> IntoSystemSetConfigs for S {
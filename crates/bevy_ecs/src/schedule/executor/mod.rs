@@ -1,10 +1,13 @@
 mod multi_threaded;
 mod simple;
 mod single_threaded;
+mod timings;
 
 pub use self::multi_threaded::{MainThreadExecutor, MultiThreadedExecutor};
 pub use self::simple::SimpleExecutor;
 pub use self::single_threaded::SingleThreadedExecutor;
+pub(crate) use self::timings::TimingRecorder;
+pub use self::timings::{ScheduleTimings, SystemTiming, SystemTimings};
 
 use fixedbitset::FixedBitSet;
 
@@ -73,6 +76,8 @@ pub struct SystemSchedule {
     pub(super) set_conditions: Vec<Vec<BoxedCondition>>,
     /// Indexed by system set node id.
     pub(super) systems_in_sets_with_conditions: Vec<FixedBitSet>,
+    /// Set while [`SystemTimings`] are being recorded for the current run.
+    pub(super) timings: Option<TimingRecorder>,
 }
 
 impl SystemSchedule {
@@ -88,6 +93,7 @@ impl SystemSchedule {
             system_dependents: Vec::new(),
             sets_with_conditions_of_systems: Vec::new(),
             systems_in_sets_with_conditions: Vec::new(),
+            timings: None,
         }
     }
 }
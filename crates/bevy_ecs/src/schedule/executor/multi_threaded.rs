@@ -4,10 +4,10 @@ use std::{
 };
 
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
-use bevy_utils::default;
 use bevy_utils::syncunsafecell::SyncUnsafeCell;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::{info_span, Span};
+use bevy_utils::{default, Duration, Instant};
 use std::panic::AssertUnwindSafe;
 
 use concurrent_queue::ConcurrentQueue;
@@ -24,7 +24,7 @@ use crate::{
 
 use crate as bevy_ecs;
 
-use super::__rust_begin_short_backtrace;
+use super::{__rust_begin_short_backtrace, TimingRecorder};
 
 /// Borrowed data used by the [`MultiThreadedExecutor`].
 struct Environment<'env, 'sys> {
@@ -32,6 +32,8 @@ struct Environment<'env, 'sys> {
     systems: &'sys [SyncUnsafeCell<BoxedSystem>],
     conditions: Mutex<Conditions<'sys>>,
     world_cell: UnsafeWorldCell<'env>,
+    /// Whether system tasks should measure their [`TaskTiming`].
+    record_timings: bool,
 }
 
 struct Conditions<'a> {
@@ -46,6 +48,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
         executor: &'env MultiThreadedExecutor,
         schedule: &'sys mut SystemSchedule,
         world: &'env mut World,
+        record_timings: bool,
     ) -> Self {
         Environment {
             executor,
@@ -57,6 +60,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
                 systems_in_sets_with_conditions: &schedule.systems_in_sets_with_conditions,
             }),
             world_cell: world.as_unsafe_world_cell(),
+            record_timings,
         }
     }
 }
@@ -78,6 +82,16 @@ struct SystemTaskMetadata {
 struct SystemResult {
     system_index: usize,
     success: bool,
+    timing: TaskTiming,
+}
+
+/// Timings measured by a system task while [`SystemTimings`](super::SystemTimings) are being recorded.
+#[derive(Default)]
+struct TaskTiming {
+    /// When the system started running and for how long it ran.
+    run: Option<(Instant, Duration)>,
+    /// The systems whose buffers were applied by this task and how long each took.
+    applied: Vec<(usize, Duration)>,
 }
 
 /// Runs the schedule using a thread pool. Non-conflicting systems can run in parallel.
@@ -129,6 +143,8 @@ pub struct ExecutorState {
     unapplied_systems: FixedBitSet,
     /// When set, stops the executor from running any more systems.
     stop_spawning: bool,
+    /// Set while [`SystemTimings`](super::SystemTimings) are being recorded for the current run.
+    timings: Option<TimingRecorder>,
 }
 
 /// References to data required by the executor.
@@ -223,12 +239,15 @@ impl SystemExecutor for MultiThreadedExecutor {
             }
         }
 
+        state.timings = schedule.timings.take();
+        let record_timings = state.timings.is_some();
+
         let thread_executor = world
             .get_resource::<MainThreadExecutor>()
             .map(|e| e.0.clone());
         let thread_executor = thread_executor.as_deref();
 
-        let environment = &Environment::new(self, schedule, world);
+        let environment = &Environment::new(self, schedule, world, record_timings);
 
         ComputeTaskPool::get_or_init(TaskPool::default).scope_with_executor(
             false,
@@ -249,14 +268,24 @@ impl SystemExecutor for MultiThreadedExecutor {
         if self.apply_final_deferred {
             // Do one final apply buffers after all systems have completed
             // Commands should be applied while on the scope's thread, not the executor's thread
-            let res = apply_deferred(&state.unapplied_systems, systems, world);
-            if let Err(payload) = res {
-                let mut panic_payload = self.panic_payload.lock().unwrap();
-                *panic_payload = Some(payload);
+            let res = apply_deferred(&state.unapplied_systems, systems, world, record_timings);
+            match res {
+                Ok(applied) => {
+                    if let Some(timings) = &mut state.timings {
+                        for (system_index, duration) in applied {
+                            timings.record_apply_deferred(system_index, duration);
+                        }
+                    }
+                }
+                Err(payload) => {
+                    let mut panic_payload = self.panic_payload.lock().unwrap();
+                    *panic_payload = Some(payload);
+                }
             }
             state.unapplied_systems.clear();
             debug_assert!(state.unapplied_systems.is_clear());
         }
+        schedule.timings = state.timings.take();
 
         // check to see if there was a panic
         let mut payload = self.panic_payload.lock().unwrap();
@@ -283,6 +312,7 @@ impl<'scope, 'env: 'scope, 'sys> Context<'scope, 'env, 'sys> {
         system_index: usize,
         res: Result<(), Box<dyn Any + Send>>,
         system: &BoxedSystem,
+        timing: TaskTiming,
     ) {
         // tell the executor that the system finished
         self.environment
@@ -291,6 +321,7 @@ impl<'scope, 'env: 'scope, 'sys> Context<'scope, 'env, 'sys> {
             .push(SystemResult {
                 system_index,
                 success: res.is_ok(),
+                timing,
             })
             .unwrap_or_else(|error| unreachable!("{}", error));
         if let Err(payload) = res {
@@ -359,6 +390,7 @@ impl ExecutorState {
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
             stop_spawning: false,
+            timings: None,
         }
     }
 
@@ -603,6 +635,7 @@ impl ExecutorState {
         let system_meta = &self.system_task_metadata[system_index];
 
         let task = async move {
+            let started = context.environment.record_timings.then(Instant::now);
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 // SAFETY:
                 // - The caller ensures that we have permission to
@@ -615,7 +648,11 @@ impl ExecutorState {
                     );
                 };
             }));
-            context.system_completed(system_index, res, system);
+            let timing = TaskTiming {
+                run: started.map(|started| (started, started.elapsed())),
+                ..default()
+            };
+            context.system_completed(system_index, res, system, timing);
         };
 
         self.active_access
@@ -645,17 +682,36 @@ impl ExecutorState {
             let unapplied_systems = self.unapplied_systems.clone();
             self.unapplied_systems.clear();
             let task = async move {
-                let res = apply_deferred(&unapplied_systems, context.environment.systems, world);
-                context.system_completed(system_index, res, system);
+                let started = context.environment.record_timings.then(Instant::now);
+                let res = apply_deferred(
+                    &unapplied_systems,
+                    context.environment.systems,
+                    world,
+                    context.environment.record_timings,
+                );
+                let (res, applied) = match res {
+                    Ok(applied) => (Ok(()), applied),
+                    Err(payload) => (Err(payload), Vec::new()),
+                };
+                let timing = TaskTiming {
+                    run: started.map(|started| (started, started.elapsed())),
+                    applied,
+                };
+                context.system_completed(system_index, res, system, timing);
             };
 
             context.scope.spawn_on_scope(task);
         } else {
             let task = async move {
+                let started = context.environment.record_timings.then(Instant::now);
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     __rust_begin_short_backtrace::run(&mut **system, world);
                 }));
-                context.system_completed(system_index, res, system);
+                let timing = TaskTiming {
+                    run: started.map(|started| (started, started.elapsed())),
+                    ..default()
+                };
+                context.system_completed(system_index, res, system, timing);
             };
 
             context.scope.spawn_on_scope(task);
@@ -669,8 +725,18 @@ impl ExecutorState {
         let SystemResult {
             system_index,
             success,
+            timing,
         } = result;
 
+        if let Some(timings) = &mut self.timings {
+            if let Some((started, run)) = timing.run {
+                timings.record_run(system_index, started, run);
+            }
+            for (applied_index, duration) in timing.applied {
+                timings.record_apply_deferred(applied_index, duration);
+            }
+        }
+
         if self.system_task_metadata[system_index].is_exclusive {
             self.exclusive_running = false;
         }
@@ -727,14 +793,19 @@ impl ExecutorState {
     }
 }
 
+/// Applies the buffers of `unapplied_systems`.
+/// If `record_timings` is set, returns how long each system took to apply.
 fn apply_deferred(
     unapplied_systems: &FixedBitSet,
     systems: &[SyncUnsafeCell<BoxedSystem>],
     world: &mut World,
-) -> Result<(), Box<dyn Any + Send>> {
+    record_timings: bool,
+) -> Result<Vec<(usize, Duration)>, Box<dyn Any + Send>> {
+    let mut applied = Vec::new();
     for system_index in unapplied_systems.ones() {
         // SAFETY: none of these systems are running, no other references exist
         let system = unsafe { &mut *systems[system_index].get() };
+        let started = record_timings.then(Instant::now);
         let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
             system.apply_deferred(world);
         }));
@@ -745,8 +816,11 @@ fn apply_deferred(
             );
             return Err(payload);
         }
+        if let Some(started) = started {
+            applied.push((system_index, started.elapsed()));
+        }
     }
-    Ok(())
+    Ok(applied)
 }
 
 /// # Safety
@@ -1,5 +1,6 @@
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
+use bevy_utils::Instant;
 use fixedbitset::FixedBitSet;
 use std::panic::AssertUnwindSafe;
 
@@ -94,6 +95,7 @@ impl SystemExecutor for SimpleExecutor {
                 continue;
             }
 
+            let started = schedule.timings.is_some().then(Instant::now);
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 __rust_begin_short_backtrace::run(&mut **system, world);
             }));
@@ -101,6 +103,9 @@ impl SystemExecutor for SimpleExecutor {
                 eprintln!("Encountered a panic in system `{}`!", &*system.name());
                 std::panic::resume_unwind(payload);
             }
+            if let (Some(timings), Some(started)) = (&mut schedule.timings, started) {
+                timings.record_run(system_index, started, started.elapsed());
+            }
         }
 
         self.evaluated_sets.clear();
@@ -1,5 +1,6 @@
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
+use bevy_utils::Instant;
 use fixedbitset::FixedBitSet;
 use std::panic::AssertUnwindSafe;
 
@@ -95,9 +96,13 @@ impl SystemExecutor for SingleThreadedExecutor {
                 continue;
             }
 
+            let started = schedule.timings.is_some().then(Instant::now);
             let system = &mut schedule.systems[system_index];
             if is_apply_deferred(system) {
                 self.apply_deferred(schedule, world);
+                if let (Some(timings), Some(started)) = (&mut schedule.timings, started) {
+                    timings.record_run(system_index, started, started.elapsed());
+                }
                 continue;
             }
 
@@ -117,6 +122,9 @@ impl SystemExecutor for SingleThreadedExecutor {
                 eprintln!("Encountered a panic in system `{}`!", &*system.name());
                 std::panic::resume_unwind(payload);
             }
+            if let (Some(timings), Some(started)) = (&mut schedule.timings, started) {
+                timings.record_run(system_index, started, started.elapsed());
+            }
             self.unapplied_systems.insert(system_index);
         }
 
@@ -148,7 +156,11 @@ impl SingleThreadedExecutor {
     fn apply_deferred(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
         for system_index in self.unapplied_systems.ones() {
             let system = &mut schedule.systems[system_index];
+            let started = schedule.timings.is_some().then(Instant::now);
             system.apply_deferred(world);
+            if let (Some(timings), Some(started)) = (&mut schedule.timings, started) {
+                timings.record_apply_deferred(system_index, started.elapsed());
+            }
         }
 
         self.unapplied_systems.clear();
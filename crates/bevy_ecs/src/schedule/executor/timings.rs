@@ -0,0 +1,218 @@
+use std::borrow::Cow;
+
+use bevy_utils::{Duration, HashMap, Instant};
+
+use crate::{
+    self as bevy_ecs,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+    system::{BoxedSystem, Resource},
+};
+
+/// Records how long the systems of each [`Schedule`](crate::schedule::Schedule) took during its last run.
+///
+/// Timings are only measured while this resource exists in the [`World`](crate::world::World),
+/// so insert it to enable recording and remove it to stop.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::{ScheduleLabel, SystemTimings};
+/// #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct Update;
+///
+/// let mut world = World::new();
+/// world.init_resource::<SystemTimings>();
+///
+/// let mut schedule = Schedule::new(Update);
+/// schedule.add_systems(|| {});
+/// schedule.run(&mut world);
+///
+/// let timings = world.resource::<SystemTimings>().get(Update).unwrap();
+/// for system in &timings.systems {
+///     println!("{}: {:?}", system.name, system.run);
+/// }
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct SystemTimings {
+    schedules: HashMap<InternedScheduleLabel, ScheduleTimings>,
+}
+
+impl SystemTimings {
+    /// Returns the timings of the last run of the schedule with the given label, if it has run while recording.
+    pub fn get(&self, label: impl ScheduleLabel) -> Option<&ScheduleTimings> {
+        self.schedules.get(&label.intern())
+    }
+
+    /// Returns an iterator over the timings of every schedule that has run while recording.
+    pub fn iter(&self) -> impl Iterator<Item = (InternedScheduleLabel, &ScheduleTimings)> {
+        self.schedules
+            .iter()
+            .map(|(label, timings)| (*label, timings))
+    }
+
+    pub(crate) fn insert(&mut self, label: InternedScheduleLabel, timings: ScheduleTimings) {
+        self.schedules.insert(label, timings);
+    }
+}
+
+/// The timings of a single run of a schedule.
+#[derive(Clone, Debug, Default)]
+pub struct ScheduleTimings {
+    /// The wall-clock time of the whole run, including evaluating run conditions and applying commands.
+    pub total: Duration,
+    /// The systems that ran, in the order of the schedule. Skipped systems are not included.
+    pub systems: Vec<SystemTiming>,
+}
+
+impl ScheduleTimings {
+    /// Returns the part of [`ScheduleTimings::total`] during which no system was running.
+    ///
+    /// This is the time spent by the executor evaluating run conditions, spawning systems
+    /// and applying the commands that are left at the end of the schedule.
+    pub fn executor_overhead(&self) -> Duration {
+        let mut intervals = self
+            .systems
+            .iter()
+            .map(|system| (system.wait, system.wait + system.run))
+            .collect::<Vec<_>>();
+        intervals.sort_unstable();
+
+        let mut busy = Duration::ZERO;
+        let mut covered_until = Duration::ZERO;
+        for (start, end) in intervals {
+            let start = start.max(covered_until);
+            if end > start {
+                busy += end - start;
+                covered_until = end;
+            }
+        }
+        self.total.saturating_sub(busy)
+    }
+
+    /// Returns the fraction of the time available on `threads` threads during the run
+    /// that was spent running systems, between `0.0` and `1.0`.
+    pub fn thread_utilization(&self, threads: usize) -> f32 {
+        if self.total.is_zero() || threads == 0 {
+            return 0.0;
+        }
+        let busy = self
+            .systems
+            .iter()
+            .map(|system| system.run)
+            .sum::<Duration>();
+        (busy.as_secs_f32() / (self.total.as_secs_f32() * threads as f32)).min(1.0)
+    }
+}
+
+/// The timing of a single system during a run of its schedule.
+#[derive(Clone, Debug)]
+pub struct SystemTiming {
+    /// The name of the system.
+    pub name: Cow<'static, str>,
+    /// The time between the start of the schedule run and the start of the system,
+    /// spent waiting for its dependencies and for systems with conflicting access.
+    pub wait: Duration,
+    /// The time spent running the system.
+    pub run: Duration,
+    /// The time spent applying the system's deferred buffers, such as [`Commands`](crate::system::Commands).
+    ///
+    /// With [`ExecutorKind::Simple`](crate::schedule::ExecutorKind::Simple), buffers are applied
+    /// as part of running the system and this is always zero.
+    pub apply_deferred: Duration,
+}
+
+/// Collects the timings of the systems of a [`SystemSchedule`](super::SystemSchedule) during a single run.
+pub(crate) struct TimingRecorder {
+    start: Instant,
+    systems: Vec<Option<RecordedTiming>>,
+}
+
+#[derive(Default, Clone, Copy)]
+struct RecordedTiming {
+    wait: Duration,
+    run: Duration,
+    apply_deferred: Duration,
+}
+
+impl TimingRecorder {
+    pub(crate) fn new(system_count: usize) -> Self {
+        Self {
+            start: Instant::now(),
+            systems: vec![None; system_count],
+        }
+    }
+
+    pub(crate) fn record_run(&mut self, system_index: usize, started: Instant, run: Duration) {
+        let timing = self.systems[system_index].get_or_insert_with(Default::default);
+        timing.wait = started.saturating_duration_since(self.start);
+        timing.run = run;
+    }
+
+    pub(crate) fn record_apply_deferred(&mut self, system_index: usize, duration: Duration) {
+        self.systems[system_index]
+            .get_or_insert_with(Default::default)
+            .apply_deferred += duration;
+    }
+
+    pub(crate) fn finish(self, systems: &[BoxedSystem]) -> ScheduleTimings {
+        ScheduleTimings {
+            total: self.start.elapsed(),
+            systems: self
+                .systems
+                .iter()
+                .zip(systems)
+                .filter_map(|(timing, system)| {
+                    timing.map(|timing| SystemTiming {
+                        name: system.name(),
+                        wait: timing.wait,
+                        run: timing.run,
+                        apply_deferred: timing.apply_deferred,
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::schedule::ExecutorKind;
+
+    #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct TestSchedule;
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn_empty();
+    }
+
+    fn skipped() {}
+
+    #[test]
+    fn timings_are_recorded() {
+        for kind in [
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::Simple,
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = World::new();
+            let mut schedule = Schedule::new(TestSchedule);
+            schedule.set_executor_kind(kind);
+            schedule.add_systems((spawn, skipped.run_if(|| false)));
+
+            schedule.run(&mut world);
+            assert!(world.get_resource::<SystemTimings>().is_none());
+
+            world.init_resource::<SystemTimings>();
+            schedule.run(&mut world);
+            let timings = world.resource::<SystemTimings>().get(TestSchedule).unwrap();
+            assert_eq!(timings.systems.len(), 1);
+            assert!(timings.systems[0].name.ends_with("spawn"));
+            assert!(timings.systems[0].wait + timings.systems[0].run <= timings.total);
+            assert!(timings.executor_overhead() <= timings.total);
+            assert!(timings.thread_utilization(1) <= 1.0);
+            assert_eq!(world.entities().len(), 2);
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Exports the graph of a [`Schedule`] for visualization and tooling.
+
+use std::fmt::Write;
+
+use petgraph::Direction;
+
+use crate::{component::Components, schedule::*};
+
+/// A node of an exported schedule graph.
+struct ExportNode {
+    id: NodeId,
+    name: String,
+}
+
+/// The parts of a [`ScheduleGraph`] that are written out by [`Schedule::graph_to_dot`] and [`Schedule::graph_to_json`].
+struct ExportGraph {
+    systems: Vec<ExportNode>,
+    sets: Vec<ExportNode>,
+    hierarchy: Vec<(NodeId, NodeId)>,
+    dependencies: Vec<(NodeId, NodeId)>,
+    ambiguities: Vec<(NodeId, NodeId, Vec<String>)>,
+}
+
+impl ExportGraph {
+    fn new(graph: &ScheduleGraph, components: &Components) -> Self {
+        // Systems are moved out of the graph when the schedule is built, but their names are kept.
+        let systems = graph
+            .system_ids()
+            .map(|id| ExportNode {
+                id,
+                name: graph.get_node_name(&id),
+            })
+            .collect::<Vec<_>>();
+
+        // Every system has an implicit set of its own type, which would only add noise.
+        let mut sets = graph
+            .system_sets()
+            .filter(|(_, set, _)| set.system_type().is_none())
+            .map(|(id, _, _)| ExportNode {
+                id,
+                name: graph.get_node_name(&id),
+            })
+            .collect::<Vec<_>>();
+        sets.sort_by_key(|set| set.id);
+
+        let is_exported = |id: NodeId| id.is_system() || sets.iter().any(|set| set.id == id);
+        let hierarchy = graph
+            .hierarchy()
+            .graph()
+            .all_edges()
+            .map(|(parent, child, _)| (parent, child))
+            .filter(|&(parent, child)| is_exported(parent) && is_exported(child))
+            .collect();
+        // Ordering against a system goes through the set of its type, so resolve those sets to their systems.
+        let resolve = |id: NodeId| -> Vec<NodeId> {
+            if is_exported(id) {
+                vec![id]
+            } else {
+                graph
+                    .hierarchy()
+                    .graph()
+                    .neighbors_directed(id, Direction::Outgoing)
+                    .collect()
+            }
+        };
+        let dependencies = graph
+            .dependency()
+            .graph()
+            .all_edges()
+            .flat_map(|(before, after, _)| {
+                let after = resolve(after);
+                resolve(before).into_iter().flat_map(move |before| {
+                    after.clone().into_iter().map(move |after| (before, after))
+                })
+            })
+            .collect();
+        let ambiguities = graph
+            .conflicting_systems()
+            .iter()
+            .map(|(a, b, conflicts)| {
+                let conflicts = conflicts
+                    .iter()
+                    .filter_map(|&id| components.get_name(id))
+                    .map(ToString::to_string)
+                    .collect();
+                (*a, *b, conflicts)
+            })
+            .collect();
+
+        Self {
+            systems,
+            sets,
+            hierarchy,
+            dependencies,
+            ambiguities,
+        }
+    }
+}
+
+fn node_key(id: NodeId) -> String {
+    match id {
+        NodeId::System(index) => format!("system_{index}"),
+        NodeId::Set(index) => format!("set_{index}"),
+    }
+}
+
+/// Escapes `"`, `\` and line breaks for use in a DOT string.
+fn escape_dot(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes `"`, `\` and the control characters U+0000 to U+001F for use in a JSON string.
+fn escape_json(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{0}'..='\u{1f}' => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn write_nodes_json(json: &mut String, nodes: &[ExportNode]) {
+    json.push('[');
+    for (i, node) in nodes.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        write!(
+            json,
+            "{{\"id\":\"{}\",\"name\":\"{}\"}}",
+            node_key(node.id),
+            escape_json(&node.name)
+        )
+        .unwrap();
+    }
+    json.push(']');
+}
+
+fn write_edges_json(json: &mut String, edges: &[(NodeId, NodeId)]) {
+    json.push('[');
+    for (i, (from, to)) in edges.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        write!(json, "[\"{}\",\"{}\"]", node_key(*from), node_key(*to)).unwrap();
+    }
+    json.push(']');
+}
+
+impl Schedule {
+    /// Writes the systems, system sets, hierarchy, ordering dependencies and ambiguities
+    /// of this schedule as a [DOT](https://graphviz.org/doc/info/lang.html) graph.
+    ///
+    /// Systems are drawn as boxes and sets as ellipses. Solid edges point from a node to the nodes
+    /// that run after it, dashed edges point from a set to its members and red edges connect
+    /// ambiguous systems, labeled with the components they conflict on.
+    ///
+    /// Ambiguities and automatically inserted sync points are only known once the schedule
+    /// has been initialized, for example with [`Schedule::initialize`].
+    pub fn graph_to_dot(&self, components: &Components) -> String {
+        let graph = ExportGraph::new(self.graph(), components);
+        let mut dot = String::new();
+
+        writeln!(
+            dot,
+            "digraph \"{}\" {{",
+            escape_dot(&format!("{:?}", self.label()))
+        )
+        .unwrap();
+        for system in &graph.systems {
+            writeln!(
+                dot,
+                "    {} [label=\"{}\", shape=box];",
+                node_key(system.id),
+                escape_dot(&system.name)
+            )
+            .unwrap();
+        }
+        for set in &graph.sets {
+            writeln!(
+                dot,
+                "    {} [label=\"{}\", shape=ellipse];",
+                node_key(set.id),
+                escape_dot(&set.name)
+            )
+            .unwrap();
+        }
+        for (parent, child) in &graph.hierarchy {
+            writeln!(
+                dot,
+                "    {} -> {} [style=dashed];",
+                node_key(*parent),
+                node_key(*child)
+            )
+            .unwrap();
+        }
+        for (before, after) in &graph.dependencies {
+            writeln!(dot, "    {} -> {};", node_key(*before), node_key(*after)).unwrap();
+        }
+        for (a, b, conflicts) in &graph.ambiguities {
+            writeln!(
+                dot,
+                "    {} -> {} [dir=none, color=red, constraint=false, label=\"{}\"];",
+                node_key(*a),
+                node_key(*b),
+                escape_dot(&conflicts.join(", "))
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    /// Writes the same information as [`Schedule::graph_to_dot`] as a JSON object, for use by external tools.
+    ///
+    /// The object has the fields `label`, `systems` and `sets` (lists of `{"id", "name"}` objects),
+    /// `hierarchy` (`[set, member]` pairs), `dependencies` (`[before, after]` pairs)
+    /// and `ambiguities` (`{"a", "b", "conflicts"}` objects).
+    pub fn graph_to_json(&self, components: &Components) -> String {
+        let graph = ExportGraph::new(self.graph(), components);
+        let mut json = String::new();
+
+        write!(
+            json,
+            "{{\"label\":\"{}\",\"systems\":",
+            escape_json(&format!("{:?}", self.label()))
+        )
+        .unwrap();
+        write_nodes_json(&mut json, &graph.systems);
+        json.push_str(",\"sets\":");
+        write_nodes_json(&mut json, &graph.sets);
+        json.push_str(",\"hierarchy\":");
+        write_edges_json(&mut json, &graph.hierarchy);
+        json.push_str(",\"dependencies\":");
+        write_edges_json(&mut json, &graph.dependencies);
+        json.push_str(",\"ambiguities\":[");
+        for (i, (a, b, conflicts)) in graph.ambiguities.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write!(
+                json,
+                "{{\"a\":\"{}\",\"b\":\"{}\",\"conflicts\":[",
+                node_key(*a),
+                node_key(*b)
+            )
+            .unwrap();
+            for (j, conflict) in conflicts.iter().enumerate() {
+                if j > 0 {
+                    json.push(',');
+                }
+                write!(json, "\"{}\"", escape_json(conflict)).unwrap();
+            }
+            json.push_str("]}");
+        }
+        json.push_str("]}");
+
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_json;
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::schedule::ScheduleLabel;
+
+    #[derive(ScheduleLabel, Hash, PartialEq, Eq, Debug, Clone)]
+    struct TestSchedule;
+
+    #[derive(SystemSet, Hash, PartialEq, Eq, Debug, Clone)]
+    struct TestSet;
+
+    #[derive(Resource)]
+    struct R;
+
+    fn first(_: ResMut<R>) {}
+    fn second() {}
+    fn ambiguous(_: ResMut<R>) {}
+
+    #[test]
+    fn export_dot_and_json() {
+        let mut world = World::new();
+        world.insert_resource(R);
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems((first, second.after(first).in_set(TestSet), ambiguous));
+        schedule.initialize(&mut world).unwrap();
+
+        let dot = schedule.graph_to_dot(world.components());
+        assert!(dot.starts_with("digraph \"TestSchedule\" {"));
+        assert!(dot.contains("system_0 [label=\"first\", shape=box];"));
+        assert!(dot.contains("system_1 [label=\"second (in set TestSet)\", shape=box];"));
+        assert!(dot.contains("system_2 [label=\"ambiguous\", shape=box];"));
+        assert!(dot.contains("system_0 -> system_1;"));
+        assert!(dot.contains("shape=ellipse"));
+        assert!(dot.contains("color=red"));
+
+        let json = schedule.graph_to_json(world.components());
+        assert!(json.starts_with("{\"label\":\"TestSchedule\""));
+        assert!(json.contains("{\"id\":\"system_0\",\"name\":\"first\"}"));
+        assert!(json.contains("\"dependencies\":[[\"system_0\",\"system_1\"]]"));
+        assert!(json.contains("\"conflicts\":["));
+    }
+
+    #[test]
+    fn json_escapes_control_characters() {
+        assert_eq!(escape_json("a\"b\\c\nd\u{1}"), "a\\\"b\\\\c\\nd\\u0001");
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Debug};
 
 use bevy_utils::{HashMap, HashSet};
 use fixedbitset::FixedBitSet;
@@ -238,6 +238,43 @@ where
     }
 }
 
+/// Returns a topological order of `graph` that only depends on its nodes and edges,
+/// or `None` if the graph contains a cycle.
+///
+/// Whenever several nodes are ready to be visited, the smallest one is picked first,
+/// so nodes that are not ordered relative to each other stay in ascending order.
+///
+/// This is Kahn's algorithm, using a min-heap as the set of ready nodes.
+pub(crate) fn stable_topsort<N>(graph: &DiGraphMap<N, ()>) -> Option<Vec<N>>
+where
+    N: NodeTrait + Ord,
+{
+    let mut in_degrees = graph
+        .nodes()
+        .map(|node| (node, graph.neighbors_directed(node, Incoming).count()))
+        .collect::<HashMap<_, _>>();
+    let mut ready = in_degrees
+        .iter()
+        .filter(|(_, &degree)| degree == 0)
+        .map(|(&node, _)| Reverse(node))
+        .collect::<BinaryHeap<_>>();
+
+    let mut sorted = Vec::with_capacity(graph.node_count());
+    while let Some(Reverse(node)) = ready.pop() {
+        sorted.push(node);
+        for successor in graph.neighbors_directed(node, Outgoing) {
+            let degree = in_degrees.get_mut(&successor).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                ready.push(Reverse(successor));
+            }
+        }
+    }
+
+    // Nodes on a cycle never become ready.
+    (sorted.len() == graph.node_count()).then_some(sorted)
+}
+
 /// Returns the simple cycles in a strongly-connected component of a directed graph.
 ///
 /// The algorithm implemented comes from
@@ -1,8 +1,47 @@
 //! Contains APIs for ordering systems and executing them on a [`World`](crate::world::World)
+//!
+//! A [`Schedule`] holds all of its systems in a single graph. There are no stages:
+//! systems are ordered relative to each other or to [`SystemSet`]s with
+//! [`before`](IntoSystemConfigs::before) and [`after`](IntoSystemConfigs::after),
+//! and any two systems without an ordering between them may run in parallel.
+//!
+//! Commands and other deferred mutations are applied at sync points, which are
+//! [`apply_deferred`] systems. The schedule inserts them automatically between ordered systems
+//! when the earlier one has deferred work (see [`ScheduleBuildSettings::auto_insert_apply_deferred`]),
+//! and they can be added explicitly with [`Schedule::add_sync_point_between`].
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! #
+//! #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+//! enum GameSet {
+//!     Input,
+//!     Movement,
+//!     Render,
+//! }
+//!
+//! fn read_input() {}
+//! fn move_player() {}
+//! fn move_enemies() {}
+//! fn draw() {}
+//!
+//! let mut schedule = Schedule::default();
+//! schedule.configure_sets((GameSet::Input, GameSet::Movement, GameSet::Render).chain());
+//! schedule.add_systems((
+//!     read_input.in_set(GameSet::Input),
+//!     // These two have no ordering between them, so they can run in parallel.
+//!     (move_player, move_enemies).in_set(GameSet::Movement),
+//!     draw.in_set(GameSet::Render),
+//! ));
+//!
+//! let mut world = World::new();
+//! schedule.run(&mut world);
+//! ```
 
 mod condition;
 mod config;
 mod executor;
+mod export;
 mod graph_utils;
 #[allow(clippy::module_inception)]
 mod schedule;
@@ -353,6 +392,31 @@ mod tests {
             assert_eq!(world.resource::<Counter>().0.load(Ordering::Relaxed), 1);
         }
 
+        #[test]
+        fn shared_condition_is_evaluated_once() {
+            #[derive(Resource, Default)]
+            struct Evaluations(AtomicU32);
+
+            let mut world = World::default();
+            let mut schedule = Schedule::default();
+
+            world.init_resource::<Counter>();
+            world.init_resource::<Evaluations>();
+
+            let shared = schedule.add_shared_condition(|evaluations: Res<Evaluations>| {
+                evaluations.0.fetch_add(1, Ordering::Relaxed);
+                true
+            });
+            schedule.add_systems((counting_system, counting_system).in_set(shared));
+            schedule.add_systems(counting_system.in_set(shared));
+            schedule.configure_sets(TestSet::A.in_set(shared));
+            schedule.add_systems(counting_system.in_set(TestSet::A));
+
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0.load(Ordering::Relaxed), 4);
+            assert_eq!(world.resource::<Evaluations>().0.load(Ordering::Relaxed), 1);
+        }
+
         #[test]
         fn systems_nested_in_system_sets() {
             let mut world = World::default();
@@ -1097,6 +1161,51 @@ mod tests {
             schedule.initialize(&mut world).unwrap();
             assert!(schedule.graph().conflicting_systems().is_empty());
         }
+
+        #[test]
+        fn ambiguity_report() {
+            let mut world = World::new();
+            world.insert_resource(R);
+            let r_id = world.components().resource_id::<R>().unwrap();
+
+            let mut schedule = Schedule::new(TestSchedule);
+            schedule.add_systems((resmut_system, res_system));
+            schedule.initialize(&mut world).unwrap();
+
+            let report = schedule.graph().ambiguity_report(world.components());
+            assert_eq!(report.len(), 1);
+            let names = [report[0].name_a.as_str(), report[0].name_b.as_str()];
+            assert!(names.contains(&"resmut_system"));
+            assert!(names.contains(&"res_system"));
+            assert_eq!(
+                report[0].conflicts,
+                vec![(
+                    r_id,
+                    "bevy_ecs::schedule::tests::system_ambiguity::R".to_string()
+                )]
+            );
+        }
+
+        #[test]
+        fn ignore_ambiguity_with_reason() {
+            let mut world = World::new();
+            world.insert_resource(R);
+
+            let mut schedule = Schedule::new(TestSchedule);
+            schedule
+                .add_systems((resmut_system, res_system))
+                .ignore_ambiguity_with_reason(
+                    resmut_system,
+                    res_system,
+                    "res_system tolerates stale values",
+                );
+            schedule.initialize(&mut world).unwrap();
+            assert!(schedule.graph().conflicting_systems().is_empty());
+
+            let ignored: Vec<_> = schedule.graph().ignored_ambiguities().collect();
+            assert_eq!(ignored.len(), 1);
+            assert_eq!(ignored[0].2, Some("res_system tolerates stale values"));
+        }
     }
 
     #[cfg(feature = "bevy_debug_stepping")]
@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     collections::BTreeSet,
     fmt::{Debug, Write},
 };
@@ -23,7 +24,8 @@ use crate::{
     world::World,
 };
 
-pub use stepping::Stepping;
+use super::executor::TimingRecorder;
+pub use stepping::{SteppedSystem, Stepping};
 
 /// Resource that stores [`Schedule`]s mapped to [`ScheduleLabel`]s excluding the current running [`Schedule`].
 #[derive(Default, Resource)]
@@ -280,6 +282,34 @@ impl Schedule {
         self
     }
 
+    /// Like [`Schedule::ignore_ambiguity`], but also records why the ambiguity between `a` and `b` is intentional.
+    ///
+    /// The reasons can be inspected with [`ScheduleGraph::ignored_ambiguities`], for example to
+    /// audit every suppressed ambiguity in CI.
+    #[track_caller]
+    pub fn ignore_ambiguity_with_reason<M1, M2, S1, S2>(
+        &mut self,
+        a: S1,
+        b: S2,
+        reason: impl Into<Cow<'static, str>>,
+    ) -> &mut Self
+    where
+        S1: IntoSystemSet<M1>,
+        S2: IntoSystemSet<M2>,
+    {
+        let a = a.into_system_set().intern();
+        let b = b.into_system_set().intern();
+        self.ignore_ambiguity(a, b);
+
+        let a_id = self.graph.system_set_ids[&a];
+        let b_id = self.graph.system_set_ids[&b];
+        self.graph
+            .ambiguity_reasons
+            .insert(ambiguity_key(a_id, b_id), reason.into());
+
+        self
+    }
+
     /// Configures a collection of system sets in this schedule, adding them if they does not exist.
     #[track_caller]
     pub fn configure_sets(&mut self, sets: impl IntoSystemSetConfigs) -> &mut Self {
@@ -287,6 +317,48 @@ impl Schedule {
         self
     }
 
+    /// Adds a run condition that can be shared by any number of systems and sets in this schedule,
+    /// returning the set that they should be added to with `in_set`.
+    ///
+    /// The condition is evaluated at most once per run of the schedule, however many systems use it.
+    /// Conditions combined with [`Condition::and_then`] or piped into other systems can be shared as well.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Resource)]
+    /// # struct Paused;
+    /// # fn physics() {}
+    /// # fn animation() {}
+    /// # fn ai() {}
+    /// let mut schedule = Schedule::default();
+    /// let unpaused = schedule.add_shared_condition(not(resource_exists::<Paused>));
+    /// schedule.add_systems((physics, animation).in_set(unpaused));
+    /// schedule.add_systems(ai.in_set(unpaused));
+    /// ```
+    pub fn add_shared_condition<M>(&mut self, condition: impl Condition<M>) -> AnonymousSet {
+        let set = self.graph.create_anonymous_set();
+        self.configure_sets(set.run_if(condition));
+        set
+    }
+
+    /// Adds an explicit sync point that applies the deferred buffers of the systems in `a`,
+    /// such as [`Commands`](crate::system::Commands), before any system in `b` runs.
+    ///
+    /// This also orders `b` after `a`. Unlike ordering them with `after`, the sync point does not
+    /// depend on [`ScheduleBuildSettings::auto_insert_apply_deferred`], so it can be used to place
+    /// every sync point by hand once automatic insertion is disabled.
+    pub fn add_sync_point_between<M1, M2>(
+        &mut self,
+        a: impl IntoSystemSet<M1>,
+        b: impl IntoSystemSet<M2>,
+    ) -> &mut Self {
+        self.add_systems(
+            apply_deferred
+                .after_ignore_deferred(a)
+                .before_ignore_deferred(b),
+        )
+    }
+
     /// Changes miscellaneous build settings.
     pub fn set_build_settings(&mut self, settings: ScheduleBuildSettings) -> &mut Self {
         self.graph.settings = settings;
@@ -330,6 +402,10 @@ impl Schedule {
         self.initialize(world)
             .unwrap_or_else(|e| panic!("Error when initializing schedule {:?}: {e}", self.label));
 
+        if world.contains_resource::<SystemTimings>() {
+            self.executable.timings = Some(TimingRecorder::new(self.executable.systems.len()));
+        }
+
         #[cfg(not(feature = "bevy_debug_stepping"))]
         self.executor.run(&mut self.executable, world, None);
 
@@ -343,6 +419,13 @@ impl Schedule {
             self.executor
                 .run(&mut self.executable, world, skip_systems.as_ref());
         }
+
+        if let Some(timings) = self.executable.timings.take() {
+            let timings = timings.finish(&self.executable.systems);
+            if let Some(mut system_timings) = world.get_resource_mut::<SystemTimings>() {
+                system_timings.insert(self.label, timings);
+            }
+        }
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
@@ -513,11 +596,14 @@ impl SystemSetNode {
 /// A [`BoxedSystem`] with metadata, stored in a [`ScheduleGraph`].
 struct SystemNode {
     inner: Option<BoxedSystem>,
+    /// The name of the system, kept here so it is still known while the system is moved into the built schedule.
+    name: Cow<'static, str>,
 }
 
 impl SystemNode {
     pub fn new(system: BoxedSystem) -> Self {
         Self {
+            name: system.name(),
             inner: Some(system),
         }
     }
@@ -545,6 +631,7 @@ pub struct ScheduleGraph {
     ambiguous_with: UnGraphMap<NodeId, ()>,
     ambiguous_with_all: HashSet<NodeId>,
     conflicting_systems: Vec<(NodeId, NodeId, Vec<ComponentId>)>,
+    ambiguity_reasons: HashMap<(NodeId, NodeId), Cow<'static, str>>,
     anonymous_sets: usize,
     changed: bool,
     settings: ScheduleBuildSettings,
@@ -567,6 +654,7 @@ impl ScheduleGraph {
             ambiguous_with: UnGraphMap::new(),
             ambiguous_with_all: HashSet::new(),
             conflicting_systems: Vec::new(),
+            ambiguity_reasons: HashMap::new(),
             anonymous_sets: 0,
             changed: false,
             settings: default(),
@@ -627,6 +715,12 @@ impl ScheduleGraph {
             })
     }
 
+    /// Returns the ids of all systems in this schedule, including the systems that were moved
+    /// into the built schedule and aren't returned by [`ScheduleGraph::systems`].
+    pub(crate) fn system_ids(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.systems.len()).map(NodeId::System)
+    }
+
     /// Returns an iterator over all system sets in this schedule.
     pub fn system_sets(&self) -> impl Iterator<Item = (NodeId, &dyn SystemSet, &[BoxedCondition])> {
         self.system_set_ids.iter().map(|(_, &node_id)| {
@@ -661,6 +755,37 @@ impl ScheduleGraph {
         &self.conflicting_systems
     }
 
+    /// Returns a structured report of the ambiguities found in this schedule.
+    ///
+    /// Like [`ScheduleGraph::conflicting_systems`], this is only populated after [`ScheduleGraph::build_schedule`].
+    pub fn ambiguity_report(&self, components: &Components) -> Vec<SystemAmbiguity> {
+        self.conflicting_systems
+            .iter()
+            .map(|(system_a, system_b, conflicts)| SystemAmbiguity {
+                system_a: *system_a,
+                system_b: *system_b,
+                name_a: self.get_node_name(system_a),
+                name_b: self.get_node_name(system_b),
+                conflicts: conflicts
+                    .iter()
+                    .map(|&id| (id, components.get_name(id).unwrap_or_default().to_string()))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Returns every pair of systems or sets whose ambiguities are ignored,
+    /// along with the reason given to [`Schedule::ignore_ambiguity_with_reason`], if any.
+    ///
+    /// This does not include systems and sets configured with
+    /// [`ambiguous_with_all`](crate::schedule::IntoSystemConfigs::ambiguous_with_all).
+    pub fn ignored_ambiguities(&self) -> impl Iterator<Item = (NodeId, NodeId, Option<&str>)> {
+        self.ambiguous_with.all_edges().map(|(a, b, _)| {
+            let reason = self.ambiguity_reasons.get(&ambiguity_key(a, b));
+            (a, b, reason.map(AsRef::as_ref))
+        })
+    }
+
     fn process_config<T: ProcessNodeConfig>(
         &mut self,
         config: NodeConfig<T>,
@@ -1030,8 +1155,18 @@ impl ScheduleGraph {
         }
 
         // topsort
+        let stable_order = self
+            .settings
+            .stable_order
+            .then(|| stable_topsort(&dependency_flattened))
+            .flatten();
+        let topsort = match stable_order {
+            Some(topsort) => topsort,
+            // Also used to find and report the cycles that prevented a stable order.
+            None => self.topsort_graph(&dependency_flattened, ReportCycles::Dependency)?,
+        };
         let mut dependency_flattened_dag = Dag {
-            topsort: self.topsort_graph(&dependency_flattened, ReportCycles::Dependency)?,
+            topsort,
             graph: dependency_flattened,
         };
 
@@ -1394,6 +1529,7 @@ impl ScheduleGraph {
             system_dependents,
             sets_with_conditions_of_systems,
             systems_in_sets_with_conditions,
+            timings: None,
         }
     }
 
@@ -1483,7 +1619,7 @@ enum ReportCycles {
 
 // methods for reporting errors
 impl ScheduleGraph {
-    fn get_node_name(&self, id: &NodeId) -> String {
+    pub(crate) fn get_node_name(&self, id: &NodeId) -> String {
         self.get_node_name_inner(id, self.settings.report_sets)
     }
 
@@ -1491,7 +1627,7 @@ impl ScheduleGraph {
     fn get_node_name_inner(&self, id: &NodeId, report_sets: bool) -> String {
         let mut name = match id {
             NodeId::System(_) => {
-                let name = self.systems[id.index()].get().unwrap().name().to_string();
+                let name = self.systems[id.index()].name.to_string();
                 if report_sets {
                     let sets = self.names_of_sets_containing_node(id);
                     if sets.is_empty() {
@@ -1841,6 +1977,32 @@ impl ScheduleGraph {
     }
 }
 
+/// Two systems with conflicting access and no ordering between them, as reported by [`ScheduleGraph::ambiguity_report`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemAmbiguity {
+    /// The first system.
+    pub system_a: NodeId,
+    /// The second system.
+    pub system_b: NodeId,
+    /// The name of the first system.
+    pub name_a: String,
+    /// The name of the second system.
+    pub name_b: String,
+    /// The components and resources both systems access, at least one of them mutably.
+    ///
+    /// If this is empty, the systems conflict on [`World`] access.
+    pub conflicts: Vec<(ComponentId, String)>,
+}
+
+/// Orders the two nodes of an ambiguity, since the ambiguity graph is undirected.
+fn ambiguity_key(a: NodeId, b: NodeId) -> (NodeId, NodeId) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
 /// Category of errors encountered during schedule construction.
 #[derive(Error, Debug)]
 #[non_exhaustive]
@@ -1924,6 +2086,17 @@ pub struct ScheduleBuildSettings {
     ///
     /// Defaults to `true`.
     pub report_sets: bool,
+    /// If set to true, systems that are not ordered relative to each other are kept in the
+    /// order they were added to the schedule, instead of an order that depends on how the
+    /// dependency graph happens to be traversed.
+    ///
+    /// Combined with [`ExecutorKind::SingleThreaded`] or [`ExecutorKind::Simple`], this makes
+    /// the order in which the systems of this schedule run reproducible, as long as the same systems
+    /// are added in the same order. It does not make the systems themselves deterministic:
+    /// parallel iteration, `HashMap` iteration order and the multi-threaded executor are unaffected.
+    ///
+    /// Defaults to `false`.
+    pub stable_order: bool,
 }
 
 impl Default for ScheduleBuildSettings {
@@ -1942,6 +2115,7 @@ impl ScheduleBuildSettings {
             auto_insert_apply_deferred: true,
             use_shortnames: true,
             report_sets: true,
+            stable_order: false,
         }
     }
 }
@@ -1958,7 +2132,8 @@ mod tests {
         self as bevy_ecs,
         prelude::{Res, Resource},
         schedule::{
-            IntoSystemConfigs, IntoSystemSetConfigs, Schedule, ScheduleBuildSettings, SystemSet,
+            IntoSystemConfigs, IntoSystemSetConfigs, NodeId, Schedule, ScheduleBuildSettings,
+            SystemSet,
         },
         system::Commands,
         world::World,
@@ -2077,6 +2252,53 @@ mod tests {
         assert_eq!(schedule.executable.systems.len(), 2);
     }
 
+    #[test]
+    fn stable_order() {
+        fn a() {}
+        fn b() {}
+        fn c() {}
+
+        let mut schedule = Schedule::default();
+        schedule.set_build_settings(ScheduleBuildSettings {
+            stable_order: true,
+            ..Default::default()
+        });
+        let mut world = World::default();
+        schedule.add_systems((a, b, c.before(a)));
+        schedule.run(&mut world);
+
+        // `b` and `c` are both ready first and keep their insertion order; `a` has to wait for `c`.
+        assert_eq!(
+            schedule.executable.system_ids,
+            vec![NodeId::System(1), NodeId::System(2), NodeId::System(0)]
+        );
+    }
+
+    #[test]
+    fn explicit_sync_point_between_sets() {
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        enum Sets {
+            A,
+            B,
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.set_build_settings(ScheduleBuildSettings {
+            auto_insert_apply_deferred: false,
+            ..Default::default()
+        });
+        let mut world = World::default();
+        schedule
+            .add_systems((
+                (|mut commands: Commands| commands.insert_resource(Resource1)).in_set(Sets::A),
+                (|res: Option<Res<Resource1>>| assert!(res.is_some())).in_set(Sets::B),
+            ))
+            .add_sync_point_between(Sets::A, Sets::B);
+        schedule.run(&mut world);
+
+        assert_eq!(schedule.executable.systems.len(), 3);
+    }
+
     mod no_sync_edges {
         use super::*;
 
@@ -2165,6 +2387,18 @@ mod tests {
                     .configure_sets(Sets::A.before_ignore_deferred(Sets::B));
             });
         }
+
+        #[test]
+        fn chained_sets() {
+            check_no_sync_edges(|schedule| {
+                schedule
+                    .add_systems((
+                        insert_resource.in_set(Sets::A),
+                        resource_does_not_exist.in_set(Sets::B),
+                    ))
+                    .configure_sets((Sets::A, Sets::B).chain_ignore_deferred());
+            });
+        }
     }
 
     mod no_sync_chain {
@@ -42,7 +42,48 @@ pub use bevy_ecs_macros::States;
 /// }
 ///
 /// ```
-pub trait States: 'static + Send + Sync + Clone + PartialEq + Eq + Hash + Debug {}
+///
+/// # Variants and transitions
+///
+/// When derived for an enum whose variants have no fields, [`States::VARIANTS`] lists every variant.
+///
+/// The transitions allowed out of a variant can be restricted with `#[states(to(...))]`.
+/// Transitions out of variants without this attribute are not restricted.
+/// In debug builds, [`apply_state_transition`] panics if the queued state is not allowed.
+///
+/// ```
+/// use bevy_ecs::prelude::States;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     #[states(to(InGame))]
+///     Loading,
+///     #[states(to(Paused))]
+///     InGame,
+///     Paused,
+/// }
+///
+/// assert_eq!(GameState::VARIANTS, [GameState::Loading, GameState::InGame, GameState::Paused]);
+/// assert!(GameState::Loading.is_transition_allowed(&GameState::InGame));
+/// assert!(!GameState::Loading.is_transition_allowed(&GameState::Paused));
+/// assert!(GameState::Paused.is_transition_allowed(&GameState::Loading));
+/// ```
+pub trait States: 'static + Send + Sync + Clone + PartialEq + Eq + Hash + Debug {
+    /// Every value of this state, in declaration order.
+    ///
+    /// This is only filled in by the derive, for enums whose variants have no fields,
+    /// and is empty otherwise.
+    const VARIANTS: &'static [Self] = &[];
+
+    /// Returns `true` if this state is allowed to transition to `to`.
+    ///
+    /// All transitions are allowed by default.
+    fn is_transition_allowed(&self, to: &Self) -> bool {
+        let _ = to;
+        true
+    }
+}
 
 /// The label of a [`Schedule`](super::Schedule) that runs whenever [`State<S>`]
 /// enters this state.
@@ -177,6 +218,11 @@ impl<S: States> NextState<S> {
     pub fn set(&mut self, state: S) {
         self.0 = Some(state);
     }
+
+    /// Remove any planned changes to [`State<S>`].
+    pub fn reset(&mut self) {
+        self.0 = None;
+    }
 }
 
 /// Event sent when any state transition of `S` happens.
@@ -215,6 +261,13 @@ pub fn apply_state_transition<S: States>(world: &mut World) {
         match world.get_resource_mut::<State<S>>() {
             Some(mut state_resource) => {
                 if *state_resource != entered {
+                    debug_assert!(
+                        state_resource.is_transition_allowed(&entered),
+                        "{} is not allowed to transition from {:?} to {:?}",
+                        std::any::type_name::<S>(),
+                        state_resource.0,
+                        entered,
+                    );
                     let exited = mem::replace(&mut state_resource.0, entered.clone());
                     world.send_event(StateTransitionEvent {
                         before: exited.clone(),
@@ -238,3 +291,123 @@ pub fn apply_state_transition<S: States>(world: &mut World) {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Events;
+    use crate::schedule::Schedule;
+    use crate::system::ResMut;
+
+    #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+    enum TestState {
+        #[default]
+        A,
+        B,
+    }
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    fn log(entry: &'static str) -> impl FnMut(ResMut<Log>) {
+        move |mut log: ResMut<Log>| log.0.push(entry)
+    }
+
+    fn setup() -> World {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.init_resource::<State<TestState>>();
+        world.init_resource::<NextState<TestState>>();
+        world.init_resource::<Events<StateTransitionEvent<TestState>>>();
+
+        for (label, entry) in [
+            (OnExit(TestState::A).intern(), "exit A"),
+            (
+                OnTransition {
+                    from: TestState::A,
+                    to: TestState::B,
+                }
+                .intern(),
+                "A -> B",
+            ),
+            (OnEnter(TestState::B).intern(), "enter B"),
+        ] {
+            let mut schedule = Schedule::new(label);
+            schedule.add_systems(log(entry));
+            world.add_schedule(schedule);
+        }
+        world
+    }
+
+    #[test]
+    fn transition_schedules_run_in_order() {
+        let mut world = setup();
+        world
+            .resource_mut::<NextState<TestState>>()
+            .set(TestState::B);
+        apply_state_transition::<TestState>(&mut world);
+
+        assert_eq!(world.resource::<State<TestState>>().get(), &TestState::B);
+        assert_eq!(world.resource::<Log>().0, ["exit A", "A -> B", "enter B"]);
+        assert_eq!(
+            world
+                .resource::<Events<StateTransitionEvent<TestState>>>()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn reset_cancels_transition() {
+        let mut world = setup();
+        let mut next_state = world.resource_mut::<NextState<TestState>>();
+        next_state.set(TestState::B);
+        next_state.reset();
+        apply_state_transition::<TestState>(&mut world);
+
+        assert_eq!(world.resource::<State<TestState>>().get(), &TestState::A);
+        assert!(world.resource::<Log>().0.is_empty());
+    }
+
+    #[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+    enum OneWayState {
+        #[default]
+        #[states(to(Done))]
+        Start,
+        #[states(to())]
+        Done,
+    }
+
+    #[test]
+    fn derived_variants() {
+        assert_eq!(
+            OneWayState::VARIANTS,
+            [OneWayState::Start, OneWayState::Done]
+        );
+        assert!(OneWayState::Start.is_transition_allowed(&OneWayState::Done));
+        assert!(!OneWayState::Done.is_transition_allowed(&OneWayState::Start));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "is not allowed to transition from Done to Start")]
+    fn disallowed_transition_panics() {
+        let mut world = World::new();
+        world.init_resource::<State<OneWayState>>();
+        world.init_resource::<NextState<OneWayState>>();
+        world.init_resource::<Events<StateTransitionEvent<OneWayState>>>();
+        world
+            .resource_mut::<NextState<OneWayState>>()
+            .set(OneWayState::Done);
+        apply_state_transition::<OneWayState>(&mut world);
+        assert_eq!(
+            world.resource::<State<OneWayState>>().get(),
+            &OneWayState::Done
+        );
+
+        world
+            .resource_mut::<NextState<OneWayState>>()
+            .set(OneWayState::Start);
+        apply_state_transition::<OneWayState>(&mut world);
+    }
+}
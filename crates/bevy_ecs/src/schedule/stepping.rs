@@ -1,8 +1,11 @@
 use fixedbitset::FixedBitSet;
 use std::any::TypeId;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use crate::{
+    component::ComponentId,
+    query::Access,
     schedule::{InternedScheduleLabel, NodeId, Schedule, ScheduleLabel},
     system::{IntoSystem, ResMut, Resource},
 };
@@ -91,6 +94,21 @@ enum Update {
 #[error("not available until all configured schedules have been run; try again next frame")]
 pub struct NotReady;
 
+/// A system that stepping allowed to run during the current stepping frame.
+///
+/// See [`Stepping::stepped_systems`].
+#[derive(Debug, Clone)]
+pub struct SteppedSystem {
+    /// The schedule the system belongs to.
+    pub schedule: InternedScheduleLabel,
+    /// The [`NodeId`] of the system within its schedule.
+    pub node_id: NodeId,
+    /// The name of the system.
+    pub name: Cow<'static, str>,
+    /// The components and resources the system reads and writes.
+    pub access: Access<ComponentId>,
+}
+
 #[derive(Resource, Default)]
 /// Resource for controlling system stepping behavior
 pub struct Stepping {
@@ -111,6 +129,10 @@ pub struct Stepping {
 
     // Updates apply at the start of the next render frame
     updates: Vec<Update>,
+
+    // systems that were not skipped during this render frame, in the order
+    // their schedules requested skip lists
+    stepped_systems: Vec<SteppedSystem>,
 }
 
 impl std::fmt::Debug for Stepping {
@@ -182,6 +204,18 @@ impl Stepping {
             .map(|node_id| (*label, *node_id))
     }
 
+    /// Return the systems that were allowed to run so far in this render
+    /// frame, along with the components and resources they access.
+    ///
+    /// This includes systems marked with [`Stepping::always_run`]. Systems
+    /// listed here may still have been skipped by their run conditions.
+    ///
+    /// NOTE: The list is empty while stepping is disabled, and is cleared at
+    /// the start of every render frame.
+    pub fn stepped_systems(&self) -> &[SteppedSystem] {
+        &self.stepped_systems
+    }
+
     /// Enable stepping for the provided schedule
     pub fn add_schedule(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
         self.updates.push(Update::AddSchedule(schedule.intern()));
@@ -397,6 +431,7 @@ impl Stepping {
         if self.action != Action::RunAll {
             self.action = Action::Waiting;
             self.previous_schedule = None;
+            self.stepped_systems.clear();
 
             // if the cursor passed the last schedule, reset it
             if self.cursor.schedule >= self.schedule_order.len() {
@@ -413,6 +448,9 @@ impl Stepping {
             match update {
                 Update::SetAction(Action::RunAll) => {
                     self.action = Action::RunAll;
+                    // nothing is recorded while stepping is disabled, so drop
+                    // the systems of the last stepped frame
+                    self.stepped_systems.clear();
                     reset_cursor = true;
                 }
                 Update::SetAction(action) => {
@@ -597,6 +635,17 @@ impl Stepping {
             }
         }
 
+        for (i, (node_id, system)) in schedule.systems().unwrap().enumerate() {
+            if !skip_list.contains(i) {
+                self.stepped_systems.push(SteppedSystem {
+                    schedule: label,
+                    node_id,
+                    name: system.name(),
+                    access: system.component_access().clone(),
+                });
+            }
+        }
+
         Some(skip_list)
     }
 }
@@ -1010,6 +1059,46 @@ mod tests {
         assert_schedule_runs!(&schedule, &mut stepping, first_system, second_system);
     }
 
+    #[test]
+    fn stepped_systems_are_recorded() {
+        #[derive(Resource)]
+        struct R;
+
+        fn read_system(_: Res<R>) {}
+
+        let mut world = World::new();
+        world.insert_resource(R);
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems((first_system, read_system).chain());
+        schedule.initialize(&mut world).unwrap();
+        let resource_id = world.components().resource_id::<R>().unwrap();
+
+        let mut stepping = Stepping::new();
+        stepping.add_schedule(TestSchedule).enable().next_frame();
+        stepping.skipped_systems(&schedule);
+        assert!(stepping.stepped_systems().is_empty());
+
+        // first_system
+        stepping.step_frame().next_frame();
+        stepping.skipped_systems(&schedule);
+        let stepped = stepping.stepped_systems();
+        assert_eq!(stepped.len(), 1);
+        assert!(stepped[0].name.ends_with("first_system"));
+        assert_eq!(stepped[0].schedule, TestSchedule.intern());
+
+        // read_system
+        stepping.step_frame().next_frame();
+        stepping.skipped_systems(&schedule);
+        let stepped = stepping.stepped_systems();
+        assert_eq!(stepped.len(), 1);
+        assert!(stepped[0].name.ends_with("read_system"));
+        assert!(stepped[0].access.has_read(resource_id));
+
+        // disabling stepping clears the recorded systems
+        stepping.disable().next_frame();
+        assert!(stepping.stepped_systems().is_empty());
+    }
+
     #[test]
     fn continue_always_run() {
         let (schedule, _world) = setup();
@@ -595,7 +595,10 @@ impl SparseSets {
         if !self.sets.contains(component_info.id()) {
             self.sets.insert(
                 component_info.id(),
-                ComponentSparseSet::new(component_info, 64),
+                ComponentSparseSet::new(
+                    component_info,
+                    component_info.capacity_hint().unwrap_or(64),
+                ),
             );
         }
 
@@ -720,4 +723,31 @@ mod tests {
             sets.get_or_insert(&info);
         }
     }
+
+    #[test]
+    fn sparse_set_capacity_hint() {
+        let mut sets = SparseSets::default();
+
+        #[derive(Component)]
+        #[component(storage = "SparseSet", capacity = 4096)]
+        struct WithCapacity;
+
+        #[derive(Component)]
+        #[component(storage = "SparseSet")]
+        struct WithoutCapacity;
+
+        let info = ComponentInfo::new(
+            ComponentId::new(1),
+            ComponentDescriptor::new::<WithCapacity>(),
+        );
+        assert_eq!(info.capacity_hint(), Some(4096));
+        assert!(sets.get_or_insert(&info).entities.capacity() >= 4096);
+
+        let info = ComponentInfo::new(
+            ComponentId::new(2),
+            ComponentDescriptor::new::<WithoutCapacity>(),
+        );
+        assert_eq!(info.capacity_hint(), None);
+        assert!(sets.get_or_insert(&info).entities.capacity() < 4096);
+    }
 }
@@ -152,6 +152,7 @@ pub struct Column {
     data: BlobVec,
     added_ticks: Vec<UnsafeCell<Tick>>,
     changed_ticks: Vec<UnsafeCell<Tick>>,
+    check_tracked_ticks: Option<unsafe fn(PtrMut<'_>, Tick)>,
 }
 
 impl Column {
@@ -163,6 +164,7 @@ impl Column {
             data: unsafe { BlobVec::new(component_info.layout(), component_info.drop(), capacity) },
             added_ticks: Vec::with_capacity(capacity),
             changed_ticks: Vec::with_capacity(capacity),
+            check_tracked_ticks: component_info.check_tracked_ticks(),
         }
     }
 
@@ -500,6 +502,12 @@ impl Column {
         for component_ticks in &mut self.changed_ticks {
             component_ticks.get_mut().check_tick(change_tick);
         }
+        if let Some(check_tracked_ticks) = self.check_tracked_ticks {
+            for row in 0..self.data.len() {
+                // SAFETY: `row` is in bounds, and `check_tracked_ticks` is valid for the component type stored in this column.
+                unsafe { check_tracked_ticks(self.data.get_unchecked_mut(row), change_tick) };
+            }
+        }
     }
 }
 
@@ -0,0 +1,155 @@
+use std::{
+    cell::Cell,
+    future::{poll_fn, Future},
+    pin::Pin,
+    ptr,
+    task::Poll,
+};
+
+use bevy_utils::{futures::check_ready, synccell::SyncCell};
+
+use crate::world::World;
+
+thread_local! {
+    /// The world of the [`async_system`] that is currently polling its future on this thread.
+    static CURRENT_WORLD: Cell<*mut World> = const { Cell::new(ptr::null_mut()) };
+}
+
+/// Gives the future of an [`async_system`] access to the [`World`].
+///
+/// The world is only reachable through [`AsyncWorld::with_world`], which takes a closure,
+/// so no borrow of the world can be held across an `.await`.
+#[derive(Clone, Copy, Debug)]
+pub struct AsyncWorld {
+    _private: (),
+}
+
+impl AsyncWorld {
+    /// Runs `f` with exclusive access to the world the system is running in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of the future of an [`async_system`] while it is being polled,
+    /// for example from a task spawned on another thread or from inside another `with_world` call.
+    pub fn with_world<R>(&self, f: impl FnOnce(&mut World) -> R) -> R {
+        let world = CURRENT_WORLD.with(|current| current.replace(ptr::null_mut()));
+        assert!(
+            !world.is_null(),
+            "`AsyncWorld::with_world` can only be called while the future of its `async_system` is being polled."
+        );
+        // SAFETY: the pointer was set by `async_system` from a `&mut World` that is borrowed for the whole poll,
+        // and it is taken out of `CURRENT_WORLD` while `f` runs, so there is no other reference to the world.
+        let result = f(unsafe { &mut *world });
+        CURRENT_WORLD.with(|current| current.set(world));
+        result
+    }
+
+    /// Suspends the future until the next time the system runs.
+    pub fn next_frame(&self) -> impl Future<Output = ()> {
+        let mut yielded = false;
+        poll_fn(move |_| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                Poll::Pending
+            }
+        })
+    }
+}
+
+/// Turns an async closure into an exclusive system.
+///
+/// Every time the system runs it polls the future returned by `f` once.
+/// A pending future is resumed the next time the system runs, so it can await tasks,
+/// asset loads or timers across frames. Once the future completes, `f` is called again
+/// to start a new one the next time the system runs.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::async_system;
+/// #
+/// #[derive(Resource, Default)]
+/// struct Frames(u32);
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(async_system(|world| async move {
+///     world.with_world(|world| world.resource_mut::<Frames>().0 += 1);
+///     world.next_frame().await;
+///     world.with_world(|world| world.resource_mut::<Frames>().0 += 10);
+/// }));
+///
+/// let mut world = World::new();
+/// world.init_resource::<Frames>();
+/// schedule.run(&mut world);
+/// assert_eq!(world.resource::<Frames>().0, 1);
+/// schedule.run(&mut world);
+/// assert_eq!(world.resource::<Frames>().0, 11);
+/// ```
+pub fn async_system<F, Fut>(mut f: F) -> impl FnMut(&mut World) + Send + Sync + 'static
+where
+    F: FnMut(AsyncWorld) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let mut future: SyncCell<Option<Pin<Box<dyn Future<Output = ()> + Send>>>> =
+        SyncCell::new(None);
+    move |world: &mut World| {
+        let slot = future.get();
+        let running = slot.get_or_insert_with(|| Box::pin(f(AsyncWorld { _private: () })));
+
+        let previous = CURRENT_WORLD.with(|current| current.replace(world));
+        let _reset = ResetCurrentWorld(previous);
+        if check_ready(running).is_some() {
+            *slot = None;
+        }
+    }
+}
+
+/// Restores [`CURRENT_WORLD`] when dropped, even if polling the future panics.
+struct ResetCurrentWorld(*mut World);
+
+impl Drop for ResetCurrentWorld {
+    fn drop(&mut self) {
+        CURRENT_WORLD.with(|current| current.set(self.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+
+    #[derive(Resource, Default)]
+    struct Counter(Vec<u32>);
+
+    #[test]
+    fn resumes_across_frames() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(async_system(|world| async move {
+            world.with_world(|world| world.resource_mut::<Counter>().0.push(1));
+            world.next_frame().await;
+            world.next_frame().await;
+            world.with_world(|world| world.resource_mut::<Counter>().0.push(2));
+        }));
+
+        for _ in 0..4 {
+            schedule.run(&mut world);
+        }
+        // The future finishes on the third run and a new one is started on the fourth.
+        assert_eq!(world.resource::<Counter>().0, vec![1, 2, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn nested_world_access_panics() {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(async_system(|world| async move {
+            world.with_world(|_| world.with_world(|_| {}));
+        }));
+        schedule.run(&mut world);
+    }
+}
@@ -0,0 +1,237 @@
+use bevy_utils::synccell::SyncCell;
+
+use crate::{
+    prelude::QueryBuilder,
+    query::{QueryData, QueryFilter, QueryState},
+    system::{
+        system_param::{init_query_param, Local},
+        FunctionSystem, Query, SystemMeta, SystemParam, SystemParamFunction, SystemState,
+    },
+    world::{FromWorld, World},
+};
+
+/// Creates the state of a [`SystemParam`] with runtime configuration,
+/// instead of the default state created by [`SystemParam::init_state`].
+///
+/// Builders are combined in tuples to build the parameters of a system,
+/// and [`SystemParam`]s derived with `#[system_param(builder)]` get a builder struct
+/// with one builder per field. The built state can then be used with
+/// [`build_state`](Self::build_state) or [`build_system`](Self::build_system).
+///
+/// The builders provided by Bevy are:
+/// - [`ParamBuilder`], which uses [`SystemParam::init_state`] for any parameter.
+/// - [`LocalBuilder`], which sets the initial value of a [`Local`].
+/// - [`QueryParamBuilder`], which adds terms to a [`Query`] with a [`QueryBuilder`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::{LocalBuilder, QueryParamBuilder, SystemParamBuilder};
+/// #
+/// # #[derive(Component)]
+/// # struct Health(u32);
+/// #
+/// # #[derive(Component)]
+/// # struct Player;
+/// #
+/// // The value of the `Local` and the filter of the query are chosen when the system is built.
+/// fn heal(amount: Local<u32>, mut query: Query<&mut Health>) {
+///     for mut health in &mut query {
+///         health.0 += *amount;
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.spawn((Health(1), Player));
+/// world.spawn(Health(1));
+///
+/// let system = (
+///     LocalBuilder(5),
+///     QueryParamBuilder::new(|builder| {
+///         builder.with::<Player>();
+///     }),
+/// )
+///     .build_system(&mut world, heal);
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(system);
+/// schedule.run(&mut world);
+///
+/// let mut query = world.query::<&Health>();
+/// let mut healths: Vec<u32> = query.iter(&world).map(|health| health.0).collect();
+/// healths.sort();
+/// assert_eq!(healths, [1, 6]);
+/// ```
+///
+/// # Safety
+///
+/// The implementor must ensure that the state returned by [`build`](Self::build) is a valid state
+/// for `P`, and that it registers all of the [`World`] accesses of the parameter in the [`SystemMeta`],
+/// as [`SystemParam::init_state`] would.
+pub unsafe trait SystemParamBuilder<P: SystemParam>: Sized {
+    /// Registers any [`World`] access used by the parameter and creates its state.
+    fn build(self, world: &mut World, meta: &mut SystemMeta) -> P::State;
+
+    /// Creates a [`SystemState`] for the parameter, using this builder.
+    fn build_state(self, world: &mut World) -> SystemState<P> {
+        SystemState::from_builder(world, self)
+    }
+
+    /// Creates a system from `func`, using this builder for its parameters.
+    ///
+    /// The returned system can only be added to a schedule that runs on `world`.
+    fn build_system<Marker, F>(self, world: &mut World, func: F) -> FunctionSystem<Marker, F>
+    where
+        F: SystemParamFunction<Marker, Param = P>,
+    {
+        FunctionSystem::from_builder(world, func, self)
+    }
+}
+
+/// A [`SystemParamBuilder`] for any [`SystemParam`], which creates its default state
+/// with [`SystemParam::init_state`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ParamBuilder;
+
+// SAFETY: The state is created by the parameter itself.
+unsafe impl<P: SystemParam> SystemParamBuilder<P> for ParamBuilder {
+    fn build(self, world: &mut World, meta: &mut SystemMeta) -> P::State {
+        P::init_state(world, meta)
+    }
+}
+
+/// A [`SystemParamBuilder`] for a [`Local`], which sets its initial value.
+#[derive(Clone, Debug, Default)]
+pub struct LocalBuilder<T>(pub T);
+
+// SAFETY: `Local` does not access the world.
+unsafe impl<'s, T: FromWorld + Send + 'static> SystemParamBuilder<Local<'s, T>>
+    for LocalBuilder<T>
+{
+    fn build(self, _world: &mut World, _meta: &mut SystemMeta) -> SyncCell<T> {
+        SyncCell::new(self.0)
+    }
+}
+
+/// A [`SystemParamBuilder`] for a [`Query`], which can add terms to the query with a [`QueryBuilder`].
+///
+/// The terms added by the builder can only narrow the query: its data and filter types stay the same.
+pub struct QueryParamBuilder<T>(T);
+
+impl<T> QueryParamBuilder<T> {
+    /// Creates a builder that passes the [`QueryBuilder`] of the query to `func`.
+    pub fn new<D: QueryData, F: QueryFilter>(func: T) -> Self
+    where
+        T: FnOnce(&mut QueryBuilder<D, F>),
+    {
+        Self(func)
+    }
+}
+
+// SAFETY: The access of the built query is registered in `meta`,
+// and the query state is valid for the data and filter types of the parameter.
+unsafe impl<'w, 's, D, F, T> SystemParamBuilder<Query<'w, 's, D, F>> for QueryParamBuilder<T>
+where
+    D: QueryData + 'static,
+    F: QueryFilter + 'static,
+    T: FnOnce(&mut QueryBuilder<D, F>),
+{
+    fn build(self, world: &mut World, meta: &mut SystemMeta) -> QueryState<D, F> {
+        let mut builder = QueryBuilder::new(world);
+        (self.0)(&mut builder);
+        let state = builder.build();
+        init_query_param(world, meta, &state);
+        state
+    }
+}
+
+macro_rules! impl_system_param_builder_tuple {
+    ($(($param: ident, $builder: ident)),*) => {
+        // SAFETY: Each builder registers the access of its own parameter.
+        unsafe impl<$($param: SystemParam,)* $($builder: SystemParamBuilder<$param>,)*> SystemParamBuilder<($($param,)*)> for ($($builder,)*) {
+            #[allow(non_snake_case)]
+            #[allow(unused_variables)]
+            fn build(self, world: &mut World, meta: &mut SystemMeta) -> <($($param,)*) as SystemParam>::State {
+                let ($($builder,)*) = self;
+                ($($builder.build(world, meta),)*)
+            }
+        }
+    };
+}
+
+// SAFETY: The empty tuple has no access to register.
+unsafe impl SystemParamBuilder<()> for () {
+    fn build(self, _world: &mut World, _meta: &mut SystemMeta) {}
+}
+
+all_ecs_tuples!(impl_system_param_builder_tuple, 1, 16, P, B);
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::system::{
+        LocalBuilder, ParamBuilder, QueryParamBuilder, SystemParam, SystemParamBuilder, SystemState,
+    };
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    struct B;
+
+    fn count_query(query: Query<()>) -> usize {
+        query.iter().count()
+    }
+
+    fn local_value(local: Local<u64>) -> u64 {
+        *local
+    }
+
+    #[test]
+    fn builders() {
+        let mut world = World::new();
+        world.spawn(A);
+        world.spawn((A, B));
+        world.spawn(B);
+
+        let mut system = (ParamBuilder,).build_system(&mut world, count_query);
+        system.initialize(&mut world);
+        assert_eq!(system.run((), &mut world), 3);
+
+        let mut system = (QueryParamBuilder::new(|builder| {
+            builder.with::<A>().without::<B>();
+        }),)
+            .build_system(&mut world, count_query);
+        system.initialize(&mut world);
+        assert_eq!(system.run((), &mut world), 1);
+
+        let mut system = (LocalBuilder(10),).build_system(&mut world, local_value);
+        system.initialize(&mut world);
+        assert_eq!(system.run((), &mut world), 10);
+    }
+
+    #[derive(SystemParam)]
+    #[system_param(builder)]
+    struct CustomParam<'w, 's> {
+        query: Query<'w, 's, ()>,
+        local: Local<'s, usize>,
+    }
+
+    #[test]
+    fn derived_param_builder() {
+        let mut world = World::new();
+        world.spawn(A);
+        world.spawn((A, B));
+        world.spawn(B);
+
+        let mut state: SystemState<CustomParam> = CustomParamBuilder {
+            query: QueryParamBuilder::new(|builder| {
+                builder.with::<B>();
+            }),
+            local: LocalBuilder(100),
+        }
+        .build_state(&mut world);
+        let param = state.get(&world);
+        assert_eq!(param.query.iter().count() + *param.local, 102);
+    }
+}
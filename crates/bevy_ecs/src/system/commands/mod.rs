@@ -3,10 +3,11 @@ mod parallel_scope;
 use super::{Deferred, IntoSystem, RegisterSystem, Resource};
 use crate::{
     self as bevy_ecs,
-    bundle::Bundle,
-    component::ComponentId,
+    bundle::{Bundle, VariantBundle},
+    component::{ComponentId, ComponentInfo},
     entity::{Entities, Entity},
-    system::{RunSystemWithInput, SystemId},
+    schedule::ScheduleLabel,
+    system::{RunSystemCachedWith, RunSystemWithInput, SystemId},
     world::{Command, CommandQueue, EntityWorldMut, FromWorld, World},
 };
 use bevy_ecs_macros::SystemParam;
@@ -503,7 +504,9 @@ impl<'w, 's> Commands<'w, 's> {
     /// There is no way to get the output of a system when run as a command, because the
     /// execution of the system happens later. To get the output of a system, use
     /// [`World::run_system`] or [`World::run_system_with_input`] instead of running the system as a command.
-    pub fn run_system(&mut self, id: SystemId) {
+    /// Errors returned by systems that return a [`Result`](crate::error::Result) are passed to the
+    /// [`World::error_handler`].
+    pub fn run_system<O: 'static + Send>(&mut self, id: SystemId<(), O>) {
         self.run_system_with_input(id, ());
     }
 
@@ -516,11 +519,64 @@ impl<'w, 's> Commands<'w, 's> {
     /// There is no way to get the output of a system when run as a command, because the
     /// execution of the system happens later. To get the output of a system, use
     /// [`World::run_system`] or [`World::run_system_with_input`] instead of running the system as a command.
-    pub fn run_system_with_input<I: 'static + Send>(&mut self, id: SystemId<I>, input: I) {
+    /// Errors returned by systems that return a [`Result`](crate::error::Result) are passed to the
+    /// [`World::error_handler`].
+    pub fn run_system_with_input<I: 'static + Send, O: 'static + Send>(
+        &mut self,
+        id: SystemId<I, O>,
+        input: I,
+    ) {
         self.queue
             .push(RunSystemWithInput::new_with_input(id, input));
     }
 
+    /// Runs a cached system, registering it if necessary.
+    ///
+    /// Calls [`World::run_system_cached`](World::run_system_cached).
+    ///
+    /// The system is registered the first time it is run, so it keeps its state
+    /// between runs. See [`World::register_system_cached`] for more information.
+    pub fn run_system_cached<
+        O: 'static + Send,
+        M: 'static,
+        S: IntoSystem<(), O, M> + Send + 'static,
+    >(
+        &mut self,
+        system: S,
+    ) {
+        self.run_system_cached_with(system, ());
+    }
+
+    /// Runs a cached system with an input, registering it if necessary.
+    ///
+    /// Calls [`World::run_system_cached_with`](World::run_system_cached_with).
+    pub fn run_system_cached_with<
+        I: 'static + Send,
+        O: 'static + Send,
+        M: 'static,
+        S: IntoSystem<I, O, M> + Send + 'static,
+    >(
+        &mut self,
+        system: S,
+        input: I,
+    ) {
+        self.add(RunSystemCachedWith::new(system, input));
+    }
+
+    /// Runs the schedule corresponding to the given [`ScheduleLabel`].
+    ///
+    /// Calls [`World::run_schedule`](World::run_schedule).
+    ///
+    /// The schedule runs when the commands are applied, in an exclusive and single threaded way,
+    /// so it can be used to drive composable sub-schedules such as turn phases from ordinary systems.
+    ///
+    /// # Panics
+    ///
+    /// The command will panic when applied if the schedule does not exist.
+    pub fn run_schedule(&mut self, label: impl ScheduleLabel) {
+        self.add(move |world: &mut World| world.run_schedule(label));
+    }
+
     /// Registers a system and returns a [`SystemId`] so it can later be called by [`World::run_system`].
     ///
     /// It's possible to register the same systems more than once, they'll be stored separately.
@@ -800,6 +856,17 @@ impl EntityCommands<'_> {
         self.add(insert(bundle))
     }
 
+    /// Adds the components of the active variant of a [`VariantBundle`] to the entity.
+    ///
+    /// This will overwrite any previous value(s) of the same component type.
+    ///
+    /// # Panics
+    ///
+    /// The command will panic when applied if the associated entity does not exist.
+    pub fn insert_variant(&mut self, bundle: impl VariantBundle) -> &mut Self {
+        self.add(insert_variant(bundle))
+    }
+
     /// Tries to add a [`Bundle`] of components to the entity.
     ///
     /// This will overwrite any previous value(s) of the same component type.
@@ -998,6 +1065,53 @@ impl EntityCommands<'_> {
         self.add(retain::<T>)
     }
 
+    /// Removes every component of the entity for which `predicate` returns `true`.
+    ///
+    /// Unlike [`retain`](EntityCommands::retain), this does not require knowing the components to keep
+    /// at compile time, which makes it possible to reset pooled entities to a base state
+    /// without enumerating every component they may have been given.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::any::TypeId;
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Resource)]
+    /// # struct PooledBullet(Entity);
+    /// #[derive(Component)]
+    /// struct Bullet;
+    ///
+    /// fn reset_pooled_bullet(mut commands: Commands, bullet: Res<PooledBullet>) {
+    ///     commands
+    ///         .entity(bullet.0)
+    ///         .remove_components_matching(|info| info.type_id() != Some(TypeId::of::<Bullet>()));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(reset_pooled_bullet);
+    /// ```
+    pub fn remove_components_matching(
+        &mut self,
+        predicate: impl FnMut(&ComponentInfo) -> bool + Send + 'static,
+    ) -> &mut Self {
+        self.add(remove_components_matching(predicate))
+    }
+
+    /// Spawns a new entity with a copy of every component of this entity,
+    /// and returns the [`EntityCommands`] of the clone.
+    ///
+    /// See [`World::clone_entity`] for how each component is copied.
+    ///
+    /// # Panics
+    ///
+    /// The command will panic when applied if the associated entity does not exist.
+    pub fn clone_and_spawn(&mut self) -> EntityCommands {
+        let source = self.entity;
+        let mut clone = self.commands.spawn_empty();
+        clone.add(move |target: Entity, world: &mut World| {
+            world.clone_components(source, target);
+        });
+        clone
+    }
+
     /// Logs the components of the entity at the info level.
     ///
     /// # Panics
@@ -1095,6 +1209,17 @@ fn insert<T: Bundle>(bundle: T) -> impl EntityCommand {
     }
 }
 
+/// An [`EntityCommand`] that adds the components of the active variant of a [`VariantBundle`] to an entity.
+fn insert_variant<T: VariantBundle>(bundle: T) -> impl EntityCommand {
+    move |entity: Entity, world: &mut World| {
+        if let Some(mut entity) = world.get_entity_mut(entity) {
+            entity.insert_variant(bundle);
+        } else {
+            panic!("error[B0003]: Could not insert a bundle (of type `{}`) for entity {:?} because it doesn't exist in this World. See: https://bevyengine.org/learn/errors/#b0003", std::any::type_name::<T>(), entity);
+        }
+    }
+}
+
 /// An [`EntityCommand`] that attempts to add the components in a [`Bundle`] to an entity.
 fn try_insert(bundle: impl Bundle) -> impl EntityCommand {
     move |entity, world: &mut World| {
@@ -1134,6 +1259,17 @@ fn retain<T: Bundle>(entity: Entity, world: &mut World) {
     }
 }
 
+/// An [`EntityCommand`] that removes every component of an entity for which `predicate` returns `true`.
+fn remove_components_matching(
+    predicate: impl FnMut(&ComponentInfo) -> bool + Send + 'static,
+) -> impl EntityCommand {
+    move |entity: Entity, world: &mut World| {
+        if let Some(mut entity_mut) = world.get_entity_mut(entity) {
+            entity_mut.remove_components_matching(predicate);
+        }
+    }
+}
+
 /// A [`Command`] that inserts a [`Resource`] into the world using a value
 /// created with the [`FromWorld`] trait.
 fn init_resource<R: Resource + FromWorld>(world: &mut World) {
@@ -1398,4 +1534,28 @@ mod tests {
         assert!(world.contains_resource::<W<i32>>());
         assert!(world.contains_resource::<W<f64>>());
     }
+
+    #[test]
+    fn run_schedule() {
+        use crate::schedule::{Schedule, ScheduleLabel};
+        use crate::system::ResMut;
+
+        #[derive(ScheduleLabel, Hash, Debug, PartialEq, Eq, Clone)]
+        struct TurnPhase;
+
+        let mut world = World::default();
+        world.insert_resource(W(0usize));
+        let mut schedule = Schedule::new(TurnPhase);
+        schedule.add_systems(|mut counter: ResMut<W<usize>>| counter.0 += 1);
+        world.add_schedule(schedule);
+
+        let mut queue = CommandQueue::default();
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.run_schedule(TurnPhase);
+            commands.run_schedule(TurnPhase);
+        }
+        queue.apply(&mut world);
+        assert_eq!(world.resource::<W<usize>>().0, 2);
+    }
 }
@@ -11,12 +11,23 @@ use super::{CommandQueue, Commands};
 
 #[derive(Default)]
 struct ParallelCommandQueue {
-    thread_queues: Parallel<CommandQueue>,
+    thread_queues: Parallel<ThreadCommandQueues>,
+    /// Scratch space used to sort the recorded queues when they are applied.
+    sorted: Vec<(Option<u64>, usize, CommandQueue)>,
+}
+
+#[derive(Default)]
+struct ThreadCommandQueues {
+    /// The queues recorded on this thread in submission order, along with the sort key of ordered scopes.
+    queues: Vec<(Option<u64>, CommandQueue)>,
+    /// Queues that were already applied, kept around so that recording a scope doesn't allocate.
+    spare: Vec<CommandQueue>,
 }
 
 /// An alternative to [`Commands`] that can be used in parallel contexts, such as those in [`Query::par_iter`](crate::system::Query::par_iter)
 ///
 /// Note: Because command application order will depend on how many threads are ran, non-commutative commands may result in non-deterministic results.
+/// Use [`ParallelCommands::command_scope_ordered`] when the application order matters.
 ///
 /// Example:
 /// ```
@@ -51,8 +62,22 @@ impl SystemBuffer for ParallelCommandQueue {
     fn apply(&mut self, _system_meta: &SystemMeta, world: &mut World) {
         #[cfg(feature = "trace")]
         let _system_span = _system_meta.commands_span.enter();
-        for cq in self.thread_queues.iter_mut() {
+        let mut threads: Vec<_> = self.thread_queues.iter_mut().collect();
+        for (thread, queues) in threads.iter_mut().enumerate() {
+            // Unordered commands inherit the key of the ordered scope submitted before them on
+            // the same thread, so every thread's commands are applied in submission order.
+            let mut key = None;
+            for (sort_key, cq) in queues.queues.drain(..) {
+                key = sort_key.or(key);
+                self.sorted.push((key, thread, cq));
+            }
+        }
+        // Sorting by key makes the application order of ordered scopes independent of how work
+        // was split between threads. The sort is stable, which keeps the submission order of equal keys.
+        self.sorted.sort_by_key(|(key, _, _)| *key);
+        for (_, thread, mut cq) in self.sorted.drain(..) {
             cq.apply(world);
+            threads[thread].spare.push(cq);
         }
     }
 }
@@ -62,9 +87,127 @@ impl<'w, 's> ParallelCommands<'w, 's> {
     ///
     /// For an example, see the type-level documentation for [`ParallelCommands`].
     pub fn command_scope<R>(&self, f: impl FnOnce(Commands) -> R) -> R {
-        self.state.thread_queues.scope(|queue| {
-            let commands = Commands::new_from_entities(queue, self.entities);
-            f(commands)
-        })
+        let mut queue = self.state.thread_queues.scope(|thread| {
+            if let Some((None, _)) = thread.queues.last() {
+                thread.queues.pop().unwrap().1
+            } else {
+                thread.spare.pop().unwrap_or_default()
+            }
+        });
+        let ret = f(Commands::new_from_entities(&mut queue, self.entities));
+        self.state
+            .thread_queues
+            .scope(|thread| thread.push(None, queue));
+        ret
+    }
+
+    /// Like [`command_scope`](Self::command_scope), but the recorded commands are applied in
+    /// ascending order of `sort_key`, independent of which thread recorded them.
+    ///
+    /// Commands recorded with [`command_scope`](Self::command_scope) on the same thread after an
+    /// ordered scope are applied right after it, so each thread's commands keep their submission order.
+    /// Scopes sharing the same key are applied in submission order on a single thread, but in an
+    /// unspecified order across threads, so keys should be unique for fully deterministic results.
+    /// A natural choice is the [`Entity`](crate::entity::Entity) being processed:
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Spawner;
+    /// fn spawn_in_parallel(query: Query<Entity, With<Spawner>>, par_commands: ParallelCommands) {
+    ///     query.par_iter().for_each(|entity| {
+    ///         par_commands.command_scope_ordered(entity.to_bits(), |mut commands| {
+    ///             commands.spawn_empty();
+    ///         });
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(spawn_in_parallel);
+    /// ```
+    pub fn command_scope_ordered<R>(&self, sort_key: u64, f: impl FnOnce(Commands) -> R) -> R {
+        let mut queue = self
+            .state
+            .thread_queues
+            .scope(|thread| thread.spare.pop().unwrap_or_default());
+        let ret = f(Commands::new_from_entities(&mut queue, self.entities));
+        self.state
+            .thread_queues
+            .scope(|thread| thread.push(Some(sort_key), queue));
+        ret
+    }
+}
+
+impl ThreadCommandQueues {
+    fn push(&mut self, sort_key: Option<u64>, queue: CommandQueue) {
+        if queue.is_empty() {
+            self.spare.push(queue);
+        } else {
+            self.queues.push((sort_key, queue));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use bevy_tasks::{ComputeTaskPool, TaskPool};
+
+    #[derive(Component)]
+    struct Order(u64);
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<u64>);
+
+    #[test]
+    fn ordered_scopes_apply_in_key_order() {
+        ComputeTaskPool::get_or_init(TaskPool::default);
+
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.spawn_batch((0..100).rev().map(Order));
+
+        fn record(query: Query<&Order>, par_commands: ParallelCommands) {
+            query.par_iter().for_each(|order| {
+                let key = order.0;
+                par_commands.command_scope_ordered(key, |mut commands| {
+                    commands.add(move |world: &mut World| world.resource_mut::<Log>().0.push(key));
+                });
+            });
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(record);
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Log>().0, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn unordered_commands_follow_the_previous_ordered_scope() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+
+        fn log(key: u64) -> impl FnOnce(&mut World) + Send + 'static {
+            move |world: &mut World| world.resource_mut::<Log>().0.push(key)
+        }
+
+        fn record(par_commands: ParallelCommands) {
+            par_commands.command_scope(|mut commands| commands.add(log(100)));
+            par_commands.command_scope_ordered(2, |mut commands| commands.add(log(2)));
+            par_commands.command_scope(|mut commands| commands.add(log(101)));
+            par_commands.command_scope_ordered(1, |mut commands| commands.add(log(1)));
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(record);
+        schedule.run(&mut world);
+        // The applied queues are reused on the next run.
+        schedule.run(&mut world);
+
+        assert_eq!(
+            world.resource::<Log>().0,
+            vec![100, 1, 2, 101, 100, 1, 2, 101]
+        );
     }
 }
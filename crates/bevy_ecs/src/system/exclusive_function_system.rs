@@ -10,7 +10,6 @@ use crate::{
     world::{unsafe_world_cell::UnsafeWorldCell, World},
 };
 
-use bevy_utils::all_tuples;
 use std::{borrow::Cow, marker::PhantomData};
 
 /// A function system that runs with exclusive [`World`] access.
@@ -243,7 +242,7 @@ macro_rules! impl_exclusive_system_function {
 }
 // Note that we rely on the highest impl to be <= the highest order of the tuple impls
 // of `SystemParam` created.
-all_tuples!(impl_exclusive_system_function, 0, 16, F);
+all_ecs_tuples!(impl_exclusive_system_function, 0, 16, F);
 
 #[cfg(test)]
 mod tests {
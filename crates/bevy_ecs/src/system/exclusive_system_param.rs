@@ -4,7 +4,6 @@ use crate::{
     system::{Local, SystemMeta, SystemParam, SystemState},
     world::World,
 };
-use bevy_utils::all_tuples;
 use bevy_utils::synccell::SyncCell;
 use std::marker::PhantomData;
 
@@ -109,7 +108,7 @@ macro_rules! impl_exclusive_system_param_tuple {
     };
 }
 
-all_tuples!(impl_exclusive_system_param_tuple, 0, 16, P);
+all_ecs_tuples!(impl_exclusive_system_param_tuple, 0, 16, P);
 
 #[cfg(test)]
 mod tests {
@@ -4,11 +4,13 @@ use crate::{
     prelude::FromWorld,
     query::{Access, FilteredAccessSet},
     schedule::{InternedSystemSet, SystemSet},
-    system::{check_system_change_tick, ReadOnlySystemParam, System, SystemParam, SystemParamItem},
+    system::{
+        check_system_change_tick, ReadOnlySystemParam, System, SystemParam, SystemParamBuilder,
+        SystemParamItem,
+    },
     world::{unsafe_world_cell::UnsafeWorldCell, World, WorldId},
 };
 
-use bevy_utils::all_tuples;
 use std::{borrow::Cow, marker::PhantomData};
 
 #[cfg(feature = "trace")]
@@ -202,6 +204,22 @@ impl<Param: SystemParam> SystemState<Param> {
         }
     }
 
+    /// Creates a new [`SystemState`] whose parameter state is created by `builder`,
+    /// instead of with [`SystemParam::init_state`].
+    ///
+    /// See [`SystemParamBuilder`] for more information.
+    pub fn from_builder(world: &mut World, builder: impl SystemParamBuilder<Param>) -> Self {
+        let mut meta = SystemMeta::new::<Param>();
+        meta.last_run = world.change_tick().relative_to(Tick::MAX);
+        let param_state = builder.build(world, &mut meta);
+        Self {
+            meta,
+            param_state,
+            world_id: world.id(),
+            archetype_generation: ArchetypeGeneration::initial(),
+        }
+    }
+
     /// Gets the metadata for this instance.
     #[inline]
     pub fn meta(&self) -> &SystemMeta {
@@ -441,6 +459,27 @@ where
     // When lines get too long, rustfmt can sometimes refuse to format them.
     // Work around this by storing the message separately.
     const PARAM_MESSAGE: &'static str = "System's param_state was not found. Did you forget to initialize this system before running it?";
+
+    /// Creates a system from `func`, whose parameter state is created by `builder`
+    /// instead of with [`SystemParam::init_state`].
+    ///
+    /// The returned system can only be run in `world`. See [`SystemParamBuilder`] for more information.
+    pub fn from_builder(
+        world: &mut World,
+        func: F,
+        builder: impl SystemParamBuilder<F::Param>,
+    ) -> Self {
+        let mut system_meta = SystemMeta::new::<F>();
+        let param_state = builder.build(world, &mut system_meta);
+        Self {
+            func,
+            param_state: Some(param_state),
+            system_meta,
+            world_id: Some(world.id()),
+            archetype_generation: ArchetypeGeneration::initial(),
+            marker: PhantomData,
+        }
+    }
 }
 
 impl<Marker, F> System for FunctionSystem<Marker, F>
@@ -514,9 +553,14 @@ where
 
     #[inline]
     fn initialize(&mut self, world: &mut World) {
-        self.world_id = Some(world.id());
+        if let Some(world_id) = self.world_id {
+            // The state was created by a `SystemParamBuilder`, or this system is being re-initialized.
+            assert_eq!(world_id, world.id(), "Encountered a mismatched World. A System cannot be used with Worlds other than the one it was initialized with.");
+        } else {
+            self.world_id = Some(world.id());
+            self.param_state = Some(F::Param::init_state(world, &mut self.system_meta));
+        }
         self.system_meta.last_run = world.change_tick().relative_to(Tick::MAX);
-        self.param_state = Some(F::Param::init_state(world, &mut self.system_meta));
     }
 
     fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
@@ -694,7 +738,7 @@ macro_rules! impl_system_function {
 
 // Note that we rely on the highest impl to be <= the highest order of the tuple impls
 // of `SystemParam` created.
-all_tuples!(impl_system_function, 0, 16, F);
+all_ecs_tuples!(impl_system_function, 0, 16, F);
 
 #[cfg(test)]
 mod tests {
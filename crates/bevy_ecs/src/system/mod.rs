@@ -98,10 +98,12 @@
 //! - [`Bundles`](crate::bundle::Bundles) (Provides Bundles metadata)
 //! - [`Components`](crate::component::Components) (Provides Components metadata)
 //! - [`Entities`](crate::entity::Entities) (Provides Entities metadata)
-//! - All tuples between 1 to 16 elements where each element implements [`SystemParam`]
+//! - All tuples between 1 to 16 elements (or 32 with the `large_tuples` feature) where each element implements [`SystemParam`]
 //! - [`()` (unit primitive type)](https://doc.rust-lang.org/stable/std/primitive.unit.html)
 
 mod adapter_system;
+mod async_system;
+mod builder;
 mod combinator;
 mod commands;
 mod exclusive_function_system;
@@ -117,6 +119,8 @@ mod system_registry;
 use std::{any::TypeId, borrow::Cow};
 
 pub use adapter_system::*;
+pub use async_system::*;
+pub use builder::*;
 pub use combinator::*;
 pub use commands::*;
 pub use exclusive_function_system::*;
@@ -1682,4 +1686,31 @@ mod tests {
         sched.run(&mut world);
         assert_eq!(world.get_resource(), Some(&C(3)));
     }
+
+    #[test]
+    fn query_join_with_filtered_query() {
+        let mut world = World::new();
+        world.spawn((W(1u32), A));
+        world.spawn((W(2u32), A, B));
+        world.spawn((W(3u32), B));
+
+        fn sys(mut numbers: Query<&mut W<u32>>, mut with_a: Query<Entity, With<A>>) {
+            let mut lens = numbers.join::<_, (Entity, &mut W<u32>), _>(&mut with_a);
+            for (_, mut number) in &mut lens.query() {
+                number.0 *= 10;
+            }
+        }
+
+        let mut system = IntoSystem::into_system(sys);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+
+        let mut values: Vec<u32> = world
+            .query::<&W<u32>>()
+            .iter(&world)
+            .map(|number| number.0)
+            .collect();
+        values.sort_unstable();
+        assert_eq!(values, vec![3, 10, 20]);
+    }
 }
@@ -3,8 +3,8 @@ use crate::{
     entity::Entity,
     query::{
         BatchingStrategy, QueryCombinationIter, QueryData, QueryEntityError, QueryFilter,
-        QueryIter, QueryManyIter, QueryParIter, QuerySingleError, QueryState, ROQueryItem,
-        ReadOnlyQueryData,
+        QueryIter, QueryManyIter, QueryManyUniqueIter, QueryParIter, QuerySingleError, QueryState,
+        QueryTransmuteError, ROQueryItem, ReadOnlyQueryData,
     },
     world::unsafe_world_cell::UnsafeWorldCell,
 };
@@ -658,6 +658,60 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
         }
     }
 
+    /// Returns an [`Iterator`] over the query items generated from a list of unique [`Entity`]s.
+    ///
+    /// Items are returned in the order of the list of entities.
+    /// Entities that don't match the query are skipped.
+    ///
+    /// Unlike [`iter_many_mut`](Self::iter_many_mut), this returns a real [`Iterator`], so it can be
+    /// used with `for` loops and iterator adapters. In exchange, the list is checked for duplicates
+    /// before iterating.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QueryEntityError::AliasedMutability`] if the same entity appears more than once in `entities`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Counter {
+    ///     value: i32
+    /// }
+    ///
+    /// #[derive(Resource)]
+    /// struct Selection(Vec<Entity>);
+    ///
+    /// fn system(selection: Res<Selection>, mut counters: Query<&mut Counter>) {
+    ///     if let Ok(selected) = counters.iter_many_unique_mut(&selection.0) {
+    ///         for mut counter in selected {
+    ///             counter.value += 1;
+    ///         }
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn iter_many_unique_mut<EntityList: IntoIterator>(
+        &mut self,
+        entities: EntityList,
+    ) -> Result<QueryManyUniqueIter<'_, 's, D, F>, QueryEntityError>
+    where
+        EntityList::Item: Borrow<Entity>,
+    {
+        // SAFETY: `self.world` has permission to access the required components.
+        unsafe {
+            QueryManyUniqueIter::new(
+                self.world,
+                self.state,
+                entities,
+                self.last_run,
+                self.this_run,
+            )
+        }
+    }
+
     /// Returns an [`Iterator`] over the query items.
     ///
     /// This iterator is always guaranteed to return results from each matching entity once and only once.
@@ -1378,6 +1432,33 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
         }
     }
 
+    /// Equivalent to [`Self::transmute_lens`], but returns an error instead of panicking
+    /// if `NewD` is not a subset of the original fetch `Q`.
+    pub fn try_transmute_lens<NewD: QueryData>(
+        &mut self,
+    ) -> Result<QueryLens<'_, NewD>, QueryTransmuteError> {
+        self.try_transmute_lens_filtered::<NewD, ()>()
+    }
+
+    /// Equivalent to [`Self::transmute_lens_filtered`], but returns an error instead of panicking
+    /// if `NewD` or `NewF` require accesses that the original query does not have.
+    pub fn try_transmute_lens_filtered<NewD: QueryData, NewF: QueryFilter>(
+        &mut self,
+    ) -> Result<QueryLens<'_, NewD, NewF>, QueryTransmuteError> {
+        // SAFETY:
+        // - We have exclusive access to the query
+        // - `self` has correctly captured it's access
+        // - Access is checked to be a subset of the query's access when the state is created.
+        let world = unsafe { self.world.world() };
+        let state = self.state.try_transmute_filtered::<NewD, NewF>(world)?;
+        Ok(QueryLens {
+            world: self.world,
+            state,
+            last_run: self.last_run,
+            this_run: self.this_run,
+        })
+    }
+
     /// Gets a [`QueryLens`] with the same accesses as the existing query
     pub fn as_query_lens(&mut self) -> QueryLens<'_, D> {
         self.transmute_lens()
@@ -1385,9 +1466,11 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
 
     /// Returns a [`QueryLens`] that can be used to get a query with the combined fetch.
     ///
-    /// For example, this can take a `Query<&A>` and a `Queryy<&B>` and return a `Query<&A, &B>`.
-    /// The returned query will only return items with both `A` and `B`. Note that since filter
-    /// are dropped, non-archetypal filters like `Added` and `Changed` will no be respected.
+    /// For example, this can take a `Query<&A>` and a `Query<&B>` and return a `Query<(&A, &B)>`.
+    /// The returned query will only return items with both `A` and `B`, iterating the intersection of
+    /// the tables and archetypes matched by both queries without any per-entity lookups.
+    /// Archetypal filters of either query (like `With` and `Without`) are respected, but since
+    /// filters are dropped from the type, non-archetypal filters like `Added` and `Changed` will not be.
     /// To maintain or change filter terms see `Self::join_filtered`.
     ///
     /// ## Example
@@ -1416,12 +1499,12 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     /// ) {
     ///     let mut players_transforms: QueryLens<(&Transform, &Player)> = transforms.join(&mut players);
     ///     for (transform, player) in &players_transforms.query() {
-    ///         // do something with a and b
+    ///         // do something with transform and player
     ///     }
     ///
     ///     let mut enemies_transforms: QueryLens<(&Transform, &Enemy)> = transforms.join(&mut enemies);
     ///     for (transform, enemy) in &enemies_transforms.query() {
-    ///         // do something with a and b
+    ///         // do something with transform and enemy
     ///     }
     /// }
     ///
@@ -1437,16 +1520,16 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     ///
     /// Like `transmute_lens` the query terms can be changed with some restrictions.
     /// See [`Self::transmute_lens`] for more details.
-    pub fn join<OtherD: QueryData, NewD: QueryData>(
+    pub fn join<OtherD: QueryData, NewD: QueryData, OtherF: QueryFilter>(
         &mut self,
-        other: &mut Query<OtherD>,
+        other: &mut Query<OtherD, OtherF>,
     ) -> QueryLens<'_, NewD> {
         self.join_filtered(other)
     }
 
     /// Equivalent to [`Self::join`] but also includes a [`QueryFilter`] type.
     ///
-    /// Note that the lens with iterate a subset of the original queries tables
+    /// Note that the lens will iterate a subset of the original queries tables
     /// and archetypes. This means that additional archetypal query terms like
     /// `With` and `Without` will not necessarily be respected and non-archetypal
     /// terms like `Added` and `Changed` will only be respected if they are in
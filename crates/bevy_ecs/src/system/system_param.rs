@@ -10,13 +10,13 @@ use crate::{
         ReadOnlyQueryData,
     },
     system::{Query, SystemMeta},
-    world::{unsafe_world_cell::UnsafeWorldCell, FromWorld, World},
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, FromWorld, World},
 };
 use bevy_ecs_macros::impl_param_set;
 pub use bevy_ecs_macros::Resource;
 pub use bevy_ecs_macros::SystemParam;
 use bevy_ptr::UnsafeCellDeref;
-use bevy_utils::{all_tuples, synccell::SyncCell};
+use bevy_utils::synccell::SyncCell;
 use std::{
     fmt::Debug,
     marker::PhantomData,
@@ -92,6 +92,40 @@ use std::{
 /// # bevy_ecs::system::assert_is_system(my_system::<()>);
 /// ```
 ///
+/// ## Builders
+///
+/// With `#[system_param(builder)]`, the macro also generates a `{Name}Builder` struct with a
+/// [`SystemParamBuilder`](super::SystemParamBuilder) for each field, so that the state of the
+/// parameter can be configured when the system is built, instead of only with [`FromWorld`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::system::{LocalBuilder, ParamBuilder, SystemParam, SystemParamBuilder};
+///
+/// #[derive(SystemParam)]
+/// #[system_param(builder)]
+/// struct Limited<'w, 's> {
+///     entities: Query<'w, 's, Entity>,
+///     limit: Local<'s, usize>,
+/// }
+///
+/// fn my_system(param: Limited) -> usize {
+///     param.entities.iter().take(*param.limit).count()
+/// }
+///
+/// let mut world = World::new();
+/// for _ in 0..3 {
+///     world.spawn_empty();
+/// }
+/// let builder = LimitedBuilder {
+///     entities: ParamBuilder,
+///     limit: LocalBuilder(2),
+/// };
+/// let mut system = (builder,).build_system(&mut world, my_system);
+/// system.initialize(&mut world);
+/// assert_eq!(system.run((), &mut world), 2);
+/// ```
+///
 /// # Generic `SystemParam`s
 ///
 /// When using the derive macro, you may see an error in the form of:
@@ -194,17 +228,7 @@ unsafe impl<D: QueryData + 'static, F: QueryFilter + 'static> SystemParam for Qu
 
     fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
         let state = QueryState::new_with_access(world, &mut system_meta.archetype_component_access);
-        assert_component_access_compatibility(
-            &system_meta.name,
-            std::any::type_name::<D>(),
-            std::any::type_name::<F>(),
-            &system_meta.component_access_set,
-            &state.component_access,
-            world,
-        );
-        system_meta
-            .component_access_set
-            .add(state.component_access.clone());
+        init_query_param(world, system_meta, &state);
         state
     }
 
@@ -226,6 +250,29 @@ unsafe impl<D: QueryData + 'static, F: QueryFilter + 'static> SystemParam for Qu
     }
 }
 
+/// Registers the component access of a [`Query`] parameter using `state` in `system_meta`.
+///
+/// # Panics
+///
+/// Panics if the access of `state` conflicts with the access already registered by the system.
+pub(crate) fn init_query_param<D: QueryData, F: QueryFilter>(
+    world: &World,
+    system_meta: &mut SystemMeta,
+    state: &QueryState<D, F>,
+) {
+    assert_component_access_compatibility(
+        &system_meta.name,
+        std::any::type_name::<D>(),
+        std::any::type_name::<F>(),
+        &system_meta.component_access_set,
+        &state.component_access,
+        world,
+    );
+    system_meta
+        .component_access_set
+        .add(state.component_access.clone());
+}
+
 fn assert_component_access_compatibility(
     system_name: &str,
     query_type: &'static str,
@@ -394,6 +441,32 @@ impl_param_set!();
 /// # schedule.run(&mut world);
 /// ```
 ///
+/// # Initialization
+///
+/// The derive can also implement [`FromWorld`] with `#[resource(init = path)]`, where `path` is
+/// a function taking a `&mut World` and returning the resource. The resource can then be added
+/// with [`World::init_resource`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # #[derive(Resource)]
+/// # struct WindowSize(f32);
+/// #[derive(Resource)]
+/// #[resource(init = Scale::from_window)]
+/// struct Scale(f32);
+///
+/// impl Scale {
+///     fn from_window(world: &mut World) -> Self {
+///         Scale(world.resource::<WindowSize>().0 / 100.0)
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(WindowSize(200.0));
+/// world.init_resource::<Scale>();
+/// assert_eq!(world.resource::<Scale>().0, 2.0);
+/// ```
+///
 /// # `!Sync` Resources
 /// A `!Sync` type cannot implement `Resource`. However, it is possible to wrap a `Send` but not `Sync`
 /// type in [`SyncCell`] or the currently unstable [`Exclusive`] to make it `Sync`. This forces only
@@ -578,6 +651,7 @@ unsafe impl<'a, T: Resource> SystemParam for ResMut<'a, T> {
                 changed: value.ticks.changed,
                 last_run: system_meta.last_run,
                 this_run: change_tick,
+                change_detection: true,
             },
         }
     }
@@ -608,6 +682,7 @@ unsafe impl<'a, T: Resource> SystemParam for Option<ResMut<'a, T>> {
                     changed: value.ticks.changed,
                     last_run: system_meta.last_run,
                     this_run: change_tick,
+                    change_detection: true,
                 },
             })
     }
@@ -656,6 +731,57 @@ unsafe impl SystemParam for &'_ World {
     }
 }
 
+/// SAFETY: `DeferredWorld` can read and write all components and resources, which is registered in `init_state`.
+/// Structural changes made through it are queued in the world's command queue and applied in `apply`.
+unsafe impl SystemParam for DeferredWorld<'_> {
+    type State = ();
+    type Item<'w, 's> = DeferredWorld<'w>;
+
+    fn init_state(_world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        let mut access = Access::default();
+        access.read_all();
+        access.write_all();
+        if !system_meta
+            .archetype_component_access
+            .is_compatible(&access)
+        {
+            panic!("DeferredWorld conflicts with a previous system parameter. Allowing this would break Rust's mutability rules");
+        }
+        system_meta.archetype_component_access.extend(&access);
+
+        let mut filtered_access = FilteredAccess::default();
+        filtered_access.read_all();
+        filtered_access.write_all();
+        if !system_meta
+            .component_access_set
+            .get_conflicts_single(&filtered_access)
+            .is_empty()
+        {
+            panic!("DeferredWorld conflicts with a previous system parameter. Allowing this would break Rust's mutability rules");
+        }
+        system_meta.component_access_set.add(filtered_access);
+        system_meta.set_has_deferred();
+    }
+
+    fn apply(_state: &mut Self::State, _system_meta: &SystemMeta, world: &mut World) {
+        // Entities spawned through `DeferredWorld::commands` are only reserved, and queue no command
+        // unless components are inserted, so the entities must be flushed as well.
+        world.flush_entities();
+        world.flush_commands();
+    }
+
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: Access to all components and resources was registered in `init_state`,
+        // so no other system can hold references to world data while this one runs.
+        unsafe { world.into_deferred() }
+    }
+}
+
 /// A system local [`SystemParam`].
 ///
 /// A local may only be accessed by the system itself and is therefore not visible to other systems.
@@ -1368,7 +1494,7 @@ macro_rules! impl_system_param_tuple {
     };
 }
 
-all_tuples!(impl_system_param_tuple, 0, 16, P);
+all_ecs_tuples!(impl_system_param_tuple, 0, 16, P);
 
 /// Contains type aliases for built-in [`SystemParam`]s with `'static` lifetimes.
 /// This makes it more convenient to refer to these types in contexts where
@@ -1745,4 +1871,39 @@ mod tests {
         schedule.add_systems((non_send_param_set, non_send_param_set, non_send_param_set));
         schedule.run(&mut world);
     }
+
+    #[test]
+    fn deferred_world_param() {
+        #[derive(Resource)]
+        struct Counter(u32);
+
+        fn deferred(mut world: DeferredWorld) {
+            world.resource_mut::<Counter>().0 += 1;
+            world.commands().spawn_empty();
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+        let mut schedule = crate::schedule::Schedule::default();
+        schedule.add_systems(deferred);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+        assert_eq!(world.entities().len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn deferred_world_conflicts_with_res() {
+        use crate::system::{IntoSystem, System};
+
+        #[derive(Resource)]
+        struct R;
+
+        fn conflicting(_: DeferredWorld, _: Res<R>) {}
+
+        let mut world = World::new();
+        world.insert_resource(R);
+        let mut system = IntoSystem::into_system(conflicting);
+        system.initialize(&mut world);
+    }
 }
@@ -1,8 +1,10 @@
 use crate::entity::Entity;
+use crate::error;
 use crate::system::{BoxedSystem, IntoSystem};
 use crate::world::{Command, World};
 use crate::{self as bevy_ecs};
-use bevy_ecs_macros::Component;
+use bevy_ecs_macros::{Component, Resource};
+use std::marker::PhantomData;
 use thiserror::Error;
 
 /// A small wrapper for [`BoxedSystem`] that also keeps track whether or not the system has been initialized.
@@ -90,6 +92,12 @@ impl<I, O> From<SystemId<I, O>> for Entity {
     }
 }
 
+/// A cached [`SystemId`] distinguished by the unique function type of its system.
+///
+/// This resource is inserted by [`World::register_system_cached`].
+#[derive(Resource)]
+pub struct CachedSystemId<S: 'static>(pub Entity, PhantomData<fn() -> S>);
+
 impl World {
     /// Registers a system and returns a [`SystemId`] so it can later be called by [`World::run_system`].
     ///
@@ -126,6 +134,86 @@ impl World {
         }
     }
 
+    /// Registers a system or returns its cached [`SystemId`].
+    ///
+    /// Unlike [`World::register_system`], calling this repeatedly with the same system
+    /// always returns the same [`SystemId`], so the system's state (such as [`Local`](crate::system::Local)s
+    /// and change detection ticks) is kept between runs.
+    /// The cache is keyed by the system's type, which is why only zero-sized systems
+    /// such as function items and non-capturing closures are supported.
+    ///
+    /// If the cached system was removed with [`World::remove_system`], it is registered again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `S` is not a zero-sized type.
+    pub fn register_system_cached<I: 'static, O: 'static, M, S: IntoSystem<I, O, M> + 'static>(
+        &mut self,
+        system: S,
+    ) -> SystemId<I, O> {
+        assert!(
+            std::mem::size_of::<S>() == 0,
+            "Non-ZST systems (e.g. capturing closures, function pointers) cannot be cached.",
+        );
+
+        if let Some(cached) = self.get_resource::<CachedSystemId<S>>() {
+            let entity = cached.0;
+            if self
+                .get_entity(entity)
+                .is_some_and(|entity| entity.contains::<RegisteredSystem<I, O>>())
+            {
+                return SystemId {
+                    entity,
+                    marker: PhantomData,
+                };
+            }
+        }
+
+        let id = self.register_system(system);
+        self.insert_resource(CachedSystemId::<S>(id.entity, PhantomData));
+        id
+    }
+
+    /// Runs a cached system, registering it if necessary.
+    ///
+    /// See [`World::register_system_cached`] for more information.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Resource, Default)]
+    /// struct Clicks(u32);
+    ///
+    /// fn on_click(mut clicks: ResMut<Clicks>, mut runs: Local<u32>) -> u32 {
+    ///     *runs += 1;
+    ///     clicks.0 += 1;
+    ///     *runs
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<Clicks>();
+    /// assert_eq!(world.run_system_cached(on_click).unwrap(), 1);
+    /// // The `Local` is preserved because the same registered system is run again.
+    /// assert_eq!(world.run_system_cached(on_click).unwrap(), 2);
+    /// ```
+    pub fn run_system_cached<O: 'static, M, S: IntoSystem<(), O, M> + 'static>(
+        &mut self,
+        system: S,
+    ) -> Result<O, RegisteredSystemError<(), O>> {
+        self.run_system_cached_with(system, ())
+    }
+
+    /// Runs a cached system with an input, registering it if necessary.
+    ///
+    /// See [`World::register_system_cached`] for more information.
+    pub fn run_system_cached_with<I: 'static, O: 'static, M, S: IntoSystem<I, O, M> + 'static>(
+        &mut self,
+        system: S,
+        input: I,
+    ) -> Result<O, RegisteredSystemError<I, O>> {
+        let id = self.register_system_cached(system);
+        self.run_system_with_input(id, input)
+    }
+
     /// Removes a registered system and returns the system, if it exists.
     /// After removing a system, the [`SystemId`] becomes invalid and attempting to use it afterwards will result in errors.
     /// Re-adding the removed system will register it on a new [`SystemId`].
@@ -318,9 +406,10 @@ impl World {
 /// There is no way to get the output of a system when run as a command, because the
 /// execution of the system happens later. To get the output of a system, use
 /// [`World::run_system`] or [`World::run_system_with_input`] instead of running the system as a command.
+/// Errors returned by systems that return a [`Result`](error::Result) are passed to the [`World::error_handler`].
 #[derive(Debug, Clone)]
-pub struct RunSystemWithInput<I: 'static> {
-    system_id: SystemId<I>,
+pub struct RunSystemWithInput<I: 'static, O: 'static = ()> {
+    system_id: SystemId<I, O>,
     input: I,
 }
 
@@ -335,27 +424,43 @@ pub struct RunSystemWithInput<I: 'static> {
 /// There is no way to get the output of a system when run as a command, because the
 /// execution of the system happens later. To get the output of a system, use
 /// [`World::run_system`] or [`World::run_system_with_input`] instead of running the system as a command.
-pub type RunSystem = RunSystemWithInput<()>;
+/// Errors returned by systems that return a [`Result`](error::Result) are passed to the [`World::error_handler`].
+pub type RunSystem<O = ()> = RunSystemWithInput<(), O>;
 
-impl RunSystem {
+impl<O: 'static> RunSystem<O> {
     /// Creates a new [`Command`] struct, which can be added to [`Commands`](crate::system::Commands)
-    pub fn new(system_id: SystemId) -> Self {
+    pub fn new(system_id: SystemId<(), O>) -> Self {
         Self::new_with_input(system_id, ())
     }
 }
 
-impl<I: 'static> RunSystemWithInput<I> {
+impl<I: 'static, O: 'static> RunSystemWithInput<I, O> {
     /// Creates a new [`Command`] struct, which can be added to [`Commands`](crate::system::Commands)
     /// in order to run the specified system with the provided [`In<_>`](crate::system::In) input value.
-    pub fn new_with_input(system_id: SystemId<I>, input: I) -> Self {
+    pub fn new_with_input(system_id: SystemId<I, O>, input: I) -> Self {
         Self { system_id, input }
     }
 }
 
-impl<I: 'static + Send> Command for RunSystemWithInput<I> {
+impl<I: 'static + Send, O: 'static + Send> Command for RunSystemWithInput<I, O> {
     #[inline]
     fn apply(self, world: &mut World) {
-        let _ = world.run_system_with_input(self.system_id, self.input);
+        run_system_and_handle_errors(world, self.system_id, self.input);
+    }
+}
+
+/// Runs a registered system and passes the errors it returns to the [`World::error_handler`].
+fn run_system_and_handle_errors<I: 'static, O: 'static>(
+    world: &mut World,
+    id: SystemId<I, O>,
+    input: I,
+) {
+    if let Ok(output) = world.run_system_with_input(id, input) {
+        error::handle_system_output(world, output, || {
+            world
+                .get::<RegisteredSystem<I, O>>(id.entity)
+                .map_or_else(|| format!("{id:?}").into(), |system| system.system.name())
+        });
     }
 }
 
@@ -388,6 +493,52 @@ impl<I: 'static + Send, O: 'static + Send> Command for RegisterSystem<I, O> {
     }
 }
 
+/// The [`Command`] type for [`World::run_system_cached_with`].
+///
+/// This command registers the system on first use and runs it in an exclusive and single threaded way.
+/// Systems without an input are run with `()` as their input.
+/// Errors returned by systems that return a [`Result`](error::Result) are passed to the [`World::error_handler`].
+pub struct RunSystemCachedWith<S, I, O, M>
+where
+    I: 'static,
+    O: 'static,
+    S: IntoSystem<I, O, M> + 'static,
+{
+    system: S,
+    input: I,
+    _marker: PhantomData<fn() -> (O, M)>,
+}
+
+impl<S, I, O, M> RunSystemCachedWith<S, I, O, M>
+where
+    I: 'static,
+    O: 'static,
+    S: IntoSystem<I, O, M> + 'static,
+{
+    /// Creates a new [`Command`] struct, which can be added to [`Commands`](crate::system::Commands).
+    pub fn new(system: S, input: I) -> Self {
+        Self {
+            system,
+            input,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S, I, O, M> Command for RunSystemCachedWith<S, I, O, M>
+where
+    I: 'static + Send,
+    O: 'static + Send,
+    S: IntoSystem<I, O, M> + Send + 'static,
+    M: 'static,
+{
+    #[inline]
+    fn apply(self, world: &mut World) {
+        let id = world.register_system_cached(self.system);
+        run_system_and_handle_errors(world, id, self.input);
+    }
+}
+
 /// An operation with stored systems failed.
 #[derive(Error)]
 pub enum RegisteredSystemError<I = (), O = ()> {
@@ -610,4 +761,49 @@ mod tests {
         let _ = world.run_system(nested_id);
         assert_eq!(*world.resource::<Counter>(), Counter(5));
     }
+
+    #[test]
+    fn cached_system() {
+        fn four() -> i32 {
+            4
+        }
+
+        let mut world = World::new();
+        let old = world.register_system_cached(four);
+        let new = world.register_system_cached(four);
+        assert_eq!(old, new);
+
+        let result = world.remove_system(old);
+        assert!(result.is_ok());
+        let new = world.register_system_cached(four);
+        assert_ne!(old, new);
+
+        let output = world.run_system(old);
+        assert!(matches!(
+            output,
+            Err(crate::system::RegisteredSystemError::<(), i32>::SystemIdNotRegistered(x)) if x == old,
+        ));
+        let output = world.run_system(new);
+        assert!(matches!(output, Ok(x) if x == four()));
+        let output = world.run_system_cached(four);
+        assert!(matches!(output, Ok(x) if x == four()));
+        let output = world.run_system_cached_with(four, ());
+        assert!(matches!(output, Ok(x) if x == four()));
+    }
+
+    #[test]
+    fn cached_system_commands() {
+        fn sys(mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+
+        world.commands().run_system_cached(sys);
+        world.flush_commands();
+        world.commands().run_system_cached(sys);
+        world.flush_commands();
+        assert_eq!(*world.resource::<Counter>(), Counter(2));
+    }
 }
@@ -0,0 +1,331 @@
+//! Duplicating entities together with all of their components.
+
+use bevy_utils::{tracing::warn, HashMap};
+
+use crate as bevy_ecs;
+use crate::{
+    component::{Component, ComponentId},
+    entity::{Entity, EntityMapper, MapEntities},
+    system::Resource,
+    world::World,
+};
+
+/// A function that copies a single component from [`ComponentCloneContext::source`]
+/// to [`ComponentCloneContext::target`].
+pub type ComponentCloneFn = fn(&mut World, &ComponentCloneContext);
+
+/// Describes the component being copied by a [`ComponentCloneFn`].
+#[derive(Clone, Copy, Debug)]
+pub struct ComponentCloneContext {
+    source: Entity,
+    target: Entity,
+    component_id: ComponentId,
+}
+
+impl ComponentCloneContext {
+    /// The entity the component is copied from.
+    pub fn source(&self) -> Entity {
+        self.source
+    }
+
+    /// The entity the component is copied to.
+    pub fn target(&self) -> Entity {
+        self.target
+    }
+
+    /// The [`ComponentId`] of the component being copied.
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+}
+
+/// Controls how a component is copied by [`World::clone_entity`].
+#[derive(Clone, Copy, Debug)]
+pub enum ComponentCloneHandler {
+    /// The component is left off the clone.
+    Ignore,
+    /// The component is copied with the given function.
+    Custom(ComponentCloneFn),
+}
+
+impl ComponentCloneHandler {
+    /// Copies the component with its [`Clone`] implementation.
+    pub fn via_clone<C: Component + Clone>() -> Self {
+        Self::Custom(component_clone_via_clone::<C>)
+    }
+
+    /// Copies the component with its [`Clone`] implementation,
+    /// then points every reference to the source entity at the clone instead.
+    pub fn via_clone_and_map<C: Component + Clone + MapEntities>() -> Self {
+        Self::Custom(component_clone_via_clone_and_map::<C>)
+    }
+}
+
+/// Stores the [`ComponentCloneHandler`]s used by [`World::clone_entity`].
+///
+/// Components without a handler are copied through reflection if their type is registered
+/// with [`ReflectComponent`](crate::reflect::ReflectComponent) in the [`AppTypeRegistry`](crate::reflect::AppTypeRegistry),
+/// and skipped with a warning otherwise.
+#[derive(Resource, Default)]
+pub struct ComponentCloneHandlers {
+    handlers: HashMap<ComponentId, ComponentCloneHandler>,
+}
+
+impl ComponentCloneHandlers {
+    /// Returns the handler registered for the component with the given [`ComponentId`], if any.
+    pub fn get(&self, component_id: ComponentId) -> Option<ComponentCloneHandler> {
+        self.handlers.get(&component_id).copied()
+    }
+}
+
+/// A [`ComponentCloneFn`] that copies `C` with its [`Clone`] implementation.
+pub fn component_clone_via_clone<C: Component + Clone>(
+    world: &mut World,
+    context: &ComponentCloneContext,
+) {
+    if let Some(component) = world.get::<C>(context.source).cloned() {
+        world.entity_mut(context.target).insert(component);
+    }
+}
+
+/// A [`ComponentCloneFn`] that copies `C` with its [`Clone`] implementation
+/// and remaps references to the source entity to the clone.
+pub fn component_clone_via_clone_and_map<C: Component + Clone + MapEntities>(
+    world: &mut World,
+    context: &ComponentCloneContext,
+) {
+    if let Some(mut component) = world.get::<C>(context.source).cloned() {
+        component.map_entities(&mut SourceToTarget(context.source, context.target));
+        world.entity_mut(context.target).insert(component);
+    }
+}
+
+struct SourceToTarget(Entity, Entity);
+
+impl EntityMapper for SourceToTarget {
+    fn map_entity(&mut self, entity: Entity) -> Entity {
+        if entity == self.0 {
+            self.1
+        } else {
+            entity
+        }
+    }
+}
+
+/// The fallback used for components without a registered [`ComponentCloneHandler`].
+fn component_clone_default(world: &mut World, context: &ComponentCloneContext) {
+    #[cfg(feature = "bevy_reflect")]
+    if component_clone_via_reflect(world, context) {
+        return;
+    }
+
+    let name = world
+        .components()
+        .get_name(context.component_id)
+        .unwrap_or("<unknown>");
+    warn!(
+        "Component {name} was not cloned to {:?}: it is neither reflected nor has a clone handler.",
+        context.target
+    );
+}
+
+/// Copies the component through its [`ReflectComponent`](crate::reflect::ReflectComponent) data.
+/// Returns `false` if the component's type is not registered.
+#[cfg(feature = "bevy_reflect")]
+fn component_clone_via_reflect(world: &mut World, context: &ComponentCloneContext) -> bool {
+    use crate::reflect::{AppTypeRegistry, ReflectComponent};
+
+    let Some(type_id) = world
+        .components()
+        .get_info(context.component_id)
+        .and_then(|info| info.type_id())
+    else {
+        return false;
+    };
+    let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+        return false;
+    };
+    let registry = registry.read();
+    let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(type_id) else {
+        return false;
+    };
+    let Some(component) = reflect_component
+        .reflect(world.entity(context.source))
+        .map(|component| component.clone_value())
+    else {
+        return false;
+    };
+    reflect_component.insert(
+        &mut world.entity_mut(context.target),
+        component.as_ref(),
+        &registry,
+    );
+    true
+}
+
+impl World {
+    /// Sets how the component with the given [`ComponentId`] is copied by [`World::clone_entity`].
+    pub fn set_component_clone_handler(
+        &mut self,
+        component_id: ComponentId,
+        handler: ComponentCloneHandler,
+    ) {
+        self.get_resource_or_insert_with(ComponentCloneHandlers::default)
+            .handlers
+            .insert(component_id, handler);
+    }
+
+    /// Spawns a new entity with a copy of every component of `source`, returning its id.
+    ///
+    /// Each component is copied with the [`ComponentCloneHandler`] registered for it in
+    /// [`ComponentCloneHandlers`], or through reflection if there is none.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::world::ComponentCloneHandler;
+    /// #
+    /// #[derive(Component, Clone, PartialEq, Debug)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// let health = world.init_component::<Health>();
+    /// world.set_component_clone_handler(health, ComponentCloneHandler::via_clone::<Health>());
+    ///
+    /// let entity = world.spawn(Health(10)).id();
+    /// let clone = world.clone_entity(entity);
+    /// assert_eq!(world.get::<Health>(clone), Some(&Health(10)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` does not exist.
+    pub fn clone_entity(&mut self, source: Entity) -> Entity {
+        let target = self.spawn_empty().id();
+        self.clone_components(source, target);
+        target
+    }
+
+    /// Copies every component of `source` onto `target`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` or `target` does not exist.
+    pub(crate) fn clone_components(&mut self, source: Entity, target: Entity) {
+        let Some(source_ref) = self.get_entity(source) else {
+            panic!("Attempting to clone entity {source:?}, which doesn't exist.");
+        };
+        let handlers = self.get_resource::<ComponentCloneHandlers>();
+        let clone_fns = source_ref
+            .archetype()
+            .components()
+            .filter_map(|component_id| {
+                let clone_fn = match handlers.and_then(|handlers| handlers.get(component_id)) {
+                    Some(ComponentCloneHandler::Ignore) => return None,
+                    Some(ComponentCloneHandler::Custom(clone_fn)) => clone_fn,
+                    None => component_clone_default,
+                };
+                Some((component_id, clone_fn))
+            })
+            .collect::<Vec<_>>();
+
+        for (component_id, clone_fn) in clone_fns {
+            let context = ComponentCloneContext {
+                source,
+                target,
+                component_id,
+            };
+            clone_fn(self, &context);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct A(u32);
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct B;
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Target(Entity);
+
+    impl MapEntities for Target {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            self.0 = entity_mapper.map_entity(self.0);
+        }
+    }
+
+    #[test]
+    fn clone_with_handlers() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+        world.set_component_clone_handler(a, ComponentCloneHandler::via_clone::<A>());
+        world.set_component_clone_handler(b, ComponentCloneHandler::Ignore);
+
+        let source = world.spawn((A(5), B)).id();
+        let clone = world.clone_entity(source);
+
+        assert_ne!(source, clone);
+        assert_eq!(world.get::<A>(clone), Some(&A(5)));
+        assert!(world.get::<B>(clone).is_none());
+        assert_eq!(world.get::<A>(source), Some(&A(5)));
+    }
+
+    #[test]
+    fn clone_remaps_self_references() {
+        let mut world = World::new();
+        let target = world.init_component::<Target>();
+        world.set_component_clone_handler(
+            target,
+            ComponentCloneHandler::via_clone_and_map::<Target>(),
+        );
+
+        let other = world.spawn_empty().id();
+        let source = world.spawn_empty().id();
+        world.entity_mut(source).insert(Target(source));
+        let pointing_elsewhere = world.spawn(Target(other)).id();
+
+        let clone = world.clone_entity(source);
+        assert_eq!(world.get::<Target>(clone), Some(&Target(clone)));
+
+        let clone = world.clone_entity(pointing_elsewhere);
+        assert_eq!(world.get::<Target>(clone), Some(&Target(other)));
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn clone_via_reflect() {
+        use crate::reflect::{AppTypeRegistry, ReflectComponent};
+        use bevy_reflect::Reflect;
+
+        #[derive(Component, Reflect, Default, PartialEq, Debug)]
+        #[reflect(Component)]
+        struct Reflected(u32);
+
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Reflected>();
+        world.insert_resource(registry);
+
+        let source = world.spawn(Reflected(7)).id();
+        let clone = world.clone_entity(source);
+        assert_eq!(world.get::<Reflected>(clone), Some(&Reflected(7)));
+    }
+
+    #[test]
+    fn clone_with_commands() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+        world.set_component_clone_handler(a, ComponentCloneHandler::via_clone::<A>());
+        let source = world.spawn(A(3)).id();
+
+        let clone = world.commands().entity(source).clone_and_spawn().id();
+        world.flush_commands();
+        assert_eq!(world.get::<A>(clone), Some(&A(3)));
+    }
+}
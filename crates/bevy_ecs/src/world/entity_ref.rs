@@ -1,10 +1,10 @@
 use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes},
-    bundle::{Bundle, BundleId, BundleInfo, BundleInserter, DynamicBundle},
+    bundle::{Bundle, BundleId, BundleInfo, BundleInserter, DynamicBundle, VariantBundle},
     change_detection::MutUntyped,
-    component::{Component, ComponentId, ComponentTicks, Components, StorageType},
+    component::{Component, ComponentId, ComponentInfo, ComponentTicks, Components, StorageType},
     entity::{Entities, Entity, EntityLocation},
-    query::{Access, DebugCheckedUnwrap},
+    query::{Access, DebugCheckedUnwrap, FilteredAccess, QueryData},
     removal_detection::RemovedComponentEvents,
     storage::Storages,
     world::{Mut, World},
@@ -467,6 +467,91 @@ impl<'w> EntityMut<'w> {
         // consuming `self` ensures that no references exist to this entity's components.
         unsafe { self.0.get_mut_by_id(component_id) }
     }
+
+    /// Gets [`MutUntyped`]s of the components of the given [`ComponentId`]s from the entity at once.
+    ///
+    /// Returns `None` if the entity does not have all of the components.
+    ///
+    /// **You should prefer to use the typed API [`EntityMut::components_mut`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    ///
+    /// # Panics
+    ///
+    /// Panics if the same [`ComponentId`] is given more than once.
+    #[inline]
+    pub fn get_many_mut_by_id<const N: usize>(
+        &mut self,
+        component_ids: [ComponentId; N],
+    ) -> Option<[MutUntyped<'_>; N]> {
+        // SAFETY:
+        // - `&mut self` ensures that no references exist to this entity's components.
+        // - the components are distinct, as checked by `get_many_mut_by_id`
+        unsafe { get_many_mut_by_id(self.0, component_ids) }
+    }
+
+    /// Gets the components of the entity selected by the [`QueryData`] `Q`,
+    /// which can borrow several components mutably at once.
+    ///
+    /// Returns `None` if the entity does not match `Q`.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)] struct Health(u32);
+    /// # #[derive(Component)] struct Shield(u32);
+    /// # let mut world = World::new();
+    /// # let entity = world.spawn((Health(10), Shield(5))).id();
+    /// let mut entity = world.entity_mut(entity);
+    /// let (mut health, mut shield) = entity
+    ///     .components_mut::<(&mut Health, &mut Shield)>()
+    ///     .unwrap();
+    /// health.0 += shield.0;
+    /// shield.0 = 0;
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `Q` has conflicting accesses, such as `(&mut A, &A)`.
+    #[inline]
+    pub fn components_mut<Q: QueryData>(&mut self) -> Option<Q::Item<'_>> {
+        // SAFETY:
+        // - `&mut self` ensures that no references exist to this entity's components.
+        // - the accesses of `Q` do not conflict, as checked by `get_components_mut`
+        unsafe { get_components_mut::<Q>(self.reborrow().0) }
+    }
+}
+
+/// # Safety
+/// - `cell` must have permission to mutate every component of the entity.
+/// - No accesses to any of the entity's components may exist at the same time.
+unsafe fn get_many_mut_by_id<const N: usize>(
+    cell: UnsafeEntityCell<'_>,
+    component_ids: [ComponentId; N],
+) -> Option<[MutUntyped<'_>; N]> {
+    for (i, id) in component_ids.iter().enumerate() {
+        assert!(
+            !component_ids[..i].contains(id),
+            "Cannot access component {id:?} mutably more than once."
+        );
+    }
+    if !component_ids.iter().all(|&id| cell.contains_id(id)) {
+        return None;
+    }
+    // SAFETY: the components exist and are distinct, so the returned references do not alias.
+    Some(component_ids.map(|id| unsafe { cell.get_mut_by_id(id).debug_checked_unwrap() }))
+}
+
+/// # Safety
+/// - `cell` must have permission to mutate every component of the entity.
+/// - No accesses to any of the entity's components may exist at the same time.
+unsafe fn get_components_mut<Q: QueryData>(cell: UnsafeEntityCell<'_>) -> Option<Q::Item<'_>> {
+    let world = cell.world();
+    // SAFETY: only metadata is read.
+    let state = Q::get_state(unsafe { world.world_metadata() })?;
+    // Panics if `Q` accesses the same component mutably more than once.
+    Q::update_component_access(&state, &mut FilteredAccess::default());
+    // SAFETY: the caller has exclusive access to the entity, and the accesses of `Q` do not conflict.
+    unsafe { cell.get_components::<Q>() }
 }
 
 impl<'w> From<&'w mut EntityMut<'_>> for EntityMut<'w> {
@@ -762,6 +847,45 @@ impl<'w> EntityWorldMut<'w> {
         unsafe { self.into_unsafe_entity_cell().get_mut_by_id(component_id) }
     }
 
+    /// Gets [`MutUntyped`]s of the components of the given [`ComponentId`]s from the entity at once.
+    ///
+    /// Returns `None` if the entity does not have all of the components.
+    ///
+    /// **You should prefer to use the typed API [`EntityWorldMut::components_mut`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    ///
+    /// # Panics
+    ///
+    /// Panics if the same [`ComponentId`] is given more than once.
+    #[inline]
+    pub fn get_many_mut_by_id<const N: usize>(
+        &mut self,
+        component_ids: [ComponentId; N],
+    ) -> Option<[MutUntyped<'_>; N]> {
+        // SAFETY:
+        // - `&mut self` ensures that no references exist to this entity's components.
+        // - `as_unsafe_entity_cell` gives mutable permission for all components on this entity
+        unsafe { get_many_mut_by_id(self.as_unsafe_entity_cell(), component_ids) }
+    }
+
+    /// Gets the components of the entity selected by the [`QueryData`] `Q`,
+    /// which can borrow several components mutably at once.
+    ///
+    /// Returns `None` if the entity does not match `Q`.
+    /// See [`EntityMut::components_mut`] for an example.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `Q` has conflicting accesses, such as `(&mut A, &A)`.
+    #[inline]
+    pub fn components_mut<Q: QueryData>(&mut self) -> Option<Q::Item<'_>> {
+        // SAFETY:
+        // - `&mut self` ensures that no references exist to this entity's components.
+        // - `as_unsafe_entity_cell` gives mutable permission for all components on this entity
+        unsafe { get_components_mut::<Q>(self.as_unsafe_entity_cell()) }
+    }
+
     /// Adds a [`Bundle`] of components to the entity.
     ///
     /// This will overwrite any previous value(s) of the same component type.
@@ -774,6 +898,26 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Adds a [`Bundle`] of components to the entity like [`insert`](Self::insert),
+    /// without triggering the `on_add` and `on_insert` hooks.
+    pub(crate) fn insert_without_hooks<T: Bundle>(&mut self, bundle: T) -> &mut Self {
+        let change_tick = self.world.change_tick();
+        let mut bundle_inserter =
+            BundleInserter::new::<T>(self.world, self.location.archetype_id, change_tick)
+                .without_hooks();
+        // SAFETY: location matches current entity. `T` matches `bundle_info`
+        self.location = unsafe { bundle_inserter.insert(self.entity, self.location, bundle) };
+        self
+    }
+
+    /// Adds the components of the active variant of a [`VariantBundle`] to the entity.
+    ///
+    /// This will overwrite any previous value(s) of the same component type.
+    pub fn insert_variant<T: VariantBundle>(&mut self, bundle: T) -> &mut Self {
+        bundle.insert_into(self);
+        self
+    }
+
     /// Inserts a dynamic [`Component`] into the entity.
     ///
     /// This will overwrite any previous value(s) of the same component type.
@@ -862,6 +1006,18 @@ impl<'w> EntityWorldMut<'w> {
     // TODO: BundleRemover?
     #[must_use]
     pub fn take<T: Bundle>(&mut self) -> Option<T> {
+        self.take_internal::<T, true>()
+    }
+
+    /// Removes all components in the [`Bundle`] from the entity like [`take`](Self::take),
+    /// without triggering the `on_remove` hooks or sending [`RemovedComponents`](crate::removal_detection::RemovedComponents) events.
+    #[must_use]
+    pub(crate) fn take_without_hooks<T: Bundle>(&mut self) -> Option<T> {
+        self.take_internal::<T, false>()
+    }
+
+    /// When `HOOKS` is false, the `on_remove` hooks are skipped and no removal events are sent.
+    fn take_internal<T: Bundle, const HOOKS: bool>(&mut self) -> Option<T> {
         let world = &mut self.world;
         let storages = &mut world.storages;
         let components = &mut world.components;
@@ -898,7 +1054,7 @@ impl<'w> EntityWorldMut<'w> {
             )
         };
 
-        if old_archetype.has_on_remove() {
+        if HOOKS && old_archetype.has_on_remove() {
             // SAFETY: All components in the archetype exist in world
             unsafe {
                 deferred_world.trigger_on_remove(entity, bundle_info.iter_components());
@@ -925,7 +1081,7 @@ impl<'w> EntityWorldMut<'w> {
                 take_component(
                     storages,
                     components,
-                    removed_components,
+                    HOOKS.then_some(&mut *removed_components),
                     component_id,
                     entity,
                     old_location,
@@ -1157,6 +1313,43 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Removes every component of the entity for which `predicate` returns `true`.
+    ///
+    /// See [`EntityCommands::remove_components_matching`](crate::system::EntityCommands::remove_components_matching) for more details.
+    pub fn remove_components_matching(
+        &mut self,
+        mut predicate: impl FnMut(&ComponentInfo) -> bool,
+    ) -> &mut Self {
+        let components = &mut self.world.components;
+        let to_remove = self.world.archetypes[self.location.archetype_id]
+            .components()
+            .filter(|&id| components.get_info(id).is_some_and(&mut predicate))
+            .collect::<Vec<_>>();
+        if to_remove.is_empty() {
+            return self;
+        }
+        let remove_bundle = self.world.bundles.init_dynamic_info(components, &to_remove);
+
+        // SAFETY: the `BundleInfo` for the components to remove is initialized above
+        self.location = unsafe { self.remove_bundle(remove_bundle) };
+        self
+    }
+
+    /// Removes the components of every variant of the [`VariantBundle`] `T` from the entity.
+    pub fn remove_variants<T: VariantBundle>(&mut self) -> &mut Self {
+        let storages = &mut self.world.storages;
+        let components = &mut self.world.components;
+        let mut to_remove = Vec::new();
+        T::component_ids(components, storages, &mut |id| to_remove.push(id));
+        to_remove.sort_unstable();
+        to_remove.dedup();
+        let remove_bundle = self.world.bundles.init_dynamic_info(components, &to_remove);
+
+        // SAFETY: the `BundleInfo` for the components to remove is initialized above
+        self.location = unsafe { self.remove_bundle(remove_bundle) };
+        self
+    }
+
     /// Removes a dynamic [`Component`] from the entity if it exists.
     ///
     /// You should prefer to use the typed API [`EntityWorldMut::remove`] where possible.
@@ -2146,6 +2339,37 @@ impl<'w> FilteredEntityMut<'w> {
                 .debug_checked_unwrap()
         })
     }
+
+    /// Consumes self and gets mutable access to the component of type `T`
+    /// with the world `'w` lifetime for the current entity.
+    /// Returns `None` if the entity does not have a component of type `T`.
+    #[inline]
+    pub fn into_mut<T: Component>(self) -> Option<Mut<'w, T>> {
+        let id = self.entity.world().components().get_id(TypeId::of::<T>())?;
+        self.access
+            .has_write(id)
+            // SAFETY: We have write access so we must have the component
+            .then(|| unsafe { self.entity.get_mut().debug_checked_unwrap() })
+    }
+
+    /// Consumes self and gets a [`MutUntyped<'w>`] of the component of the given [`ComponentId`]
+    /// from the entity.
+    ///
+    /// **You should prefer to use the typed API [`Self::into_mut`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    ///
+    /// Unlike [`FilteredEntityMut::get_mut_by_id`], the returned pointer is valid for the
+    /// lifetime of the query the entity was fetched from.
+    #[inline]
+    pub fn into_mut_by_id(self, component_id: ComponentId) -> Option<MutUntyped<'w>> {
+        // SAFETY: We have write access so we must have the component
+        self.access.has_write(component_id).then(|| unsafe {
+            self.entity
+                .get_mut_by_id(component_id)
+                .debug_checked_unwrap()
+        })
+    }
 }
 
 impl<'a> From<EntityMut<'a>> for FilteredEntityMut<'a> {
@@ -2370,6 +2594,7 @@ fn sorted_remove<T: Eq + Ord + Copy>(source: &mut Vec<T>, remove: &[T]) {
 /// This function leaves the underlying memory unchanged, but the component behind
 /// returned pointer is semantically owned by the caller and will not be dropped in its original location.
 /// Caller is responsible to drop component data behind returned pointer.
+/// A removal event is sent to `removed_components` if it is given.
 ///
 /// # Safety
 /// - `location.table_row` must be in bounds of column of component id `component_id`
@@ -2380,14 +2605,16 @@ fn sorted_remove<T: Eq + Ord + Copy>(source: &mut Vec<T>, remove: &[T]) {
 pub(crate) unsafe fn take_component<'a>(
     storages: &'a mut Storages,
     components: &Components,
-    removed_components: &mut RemovedComponentEvents,
+    removed_components: Option<&mut RemovedComponentEvents>,
     component_id: ComponentId,
     entity: Entity,
     location: EntityLocation,
 ) -> OwningPtr<'a> {
     // SAFETY: caller promises component_id to be valid
     let component_info = unsafe { components.get_info_unchecked(component_id) };
-    removed_components.send(component_id, entity);
+    if let Some(removed_components) = removed_components {
+        removed_components.send(component_id, entity);
+    }
     match component_info.storage_type() {
         StorageType::Table => {
             let table = &mut storages.tables[location.table_id];
@@ -2510,6 +2737,68 @@ mod tests {
         assert!(entity.get_mut_by_id(invalid_component_id).is_none());
     }
 
+    #[test]
+    fn entity_mut_get_many_mut_by_id() {
+        let mut world = World::new();
+        let entity = world.spawn((TestComponent(1), TestComponent2(2))).id();
+        let a = world.init_component::<TestComponent>();
+        let b = world.init_component::<TestComponent2>();
+
+        let mut entity_mut = world.entity_mut(entity);
+        let [a_ptr, b_ptr] = entity_mut.get_many_mut_by_id([a, b]).unwrap();
+        // SAFETY: the pointers point to the components of the given ids
+        unsafe {
+            a_ptr.into_inner().deref_mut::<TestComponent>().0 = 10;
+            b_ptr.into_inner().deref_mut::<TestComponent2>().0 = 20;
+        }
+        assert_eq!(entity_mut.get::<TestComponent>(), Some(&TestComponent(10)));
+        assert_eq!(
+            entity_mut.get::<TestComponent2>(),
+            Some(&TestComponent2(20))
+        );
+
+        entity_mut.remove::<TestComponent2>();
+        assert!(entity_mut.get_many_mut_by_id([a, b]).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn entity_mut_get_many_mut_by_id_duplicate() {
+        let mut world = World::new();
+        let a = world.init_component::<TestComponent>();
+        let mut entity = world.spawn(TestComponent(1));
+        entity.get_many_mut_by_id([a, a]);
+    }
+
+    #[test]
+    fn entity_mut_components_mut() {
+        let mut world = World::new();
+        let entity = world.spawn((TestComponent(1), TestComponent2(2))).id();
+
+        let mut entity_mut = world.entity_mut(entity);
+        let (mut a, mut b) = entity_mut
+            .components_mut::<(&mut TestComponent, &mut TestComponent2)>()
+            .unwrap();
+        std::mem::swap(&mut a.0, &mut b.0);
+        assert_eq!(entity_mut.get::<TestComponent>(), Some(&TestComponent(2)));
+        assert_eq!(entity_mut.get::<TestComponent2>(), Some(&TestComponent2(1)));
+
+        let mut entity_mut = EntityMut::from(entity_mut);
+        let (id, a) = entity_mut
+            .components_mut::<(Entity, Ref<TestComponent>)>()
+            .unwrap();
+        assert_eq!(id, entity);
+        assert!(a.is_changed());
+    }
+
+    #[test]
+    #[should_panic]
+    fn entity_mut_components_mut_conflict() {
+        let mut world = World::new();
+        let mut entity = world.spawn(TestComponent(1));
+        entity.components_mut::<(&mut TestComponent, &TestComponent)>();
+    }
+
     // regression test for https://github.com/bevyengine/bevy/pull/7387
     #[test]
     fn entity_mut_world_scope_panic() {
@@ -2534,6 +2823,34 @@ mod tests {
         assert_ne!(entity.location(), old_location);
     }
 
+    #[test]
+    fn remove_components_matching() {
+        #[derive(Component)]
+        struct Marker<const N: usize>;
+
+        #[derive(Component)]
+        #[component(storage = "SparseSet")]
+        struct Sparse;
+
+        let mut world = World::new();
+        let ent = world
+            .spawn((Marker::<1>, Marker::<2>, Marker::<3>, Sparse))
+            .id();
+        let keep = world.init_component::<Marker<2>>();
+
+        world
+            .entity_mut(ent)
+            .remove_components_matching(|info| info.name().ends_with("Sparse"));
+        assert!(!world.entity(ent).contains::<Sparse>());
+        assert_eq!(world.entity(ent).archetype().components().count(), 3);
+
+        world
+            .entity_mut(ent)
+            .remove_components_matching(|info| info.id() != keep);
+        assert!(world.entity(ent).contains::<Marker<2>>());
+        assert_eq!(world.entity(ent).archetype().components().count(), 1);
+    }
+
     // regression test for https://github.com/bevyengine/bevy/pull/7805
     #[test]
     fn removing_sparse_updates_archetype_row() {
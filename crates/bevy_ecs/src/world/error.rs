@@ -10,3 +10,13 @@ use crate::schedule::InternedScheduleLabel;
 #[derive(Error, Debug)]
 #[error("The schedule with the label {0:?} was not found.")]
 pub struct TryRunScheduleError(pub InternedScheduleLabel);
+
+/// The error type returned by [`ResourceInitializer::initialize`].
+///
+/// [`ResourceInitializer::initialize`]: crate::world::ResourceInitializer::initialize
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ResourceInitError {
+    /// The dependencies of the listed resources form a cycle, so none of them can be initialized first.
+    #[error("The dependencies of these resources form a cycle: {0:?}")]
+    DependencyCycle(Vec<&'static str>),
+}
@@ -1,23 +1,38 @@
 //! Defines the [`World`] and APIs for accessing it directly.
 
+mod clone_entity;
 mod command_queue;
 mod deferred_world;
 mod entity_ref;
 pub mod error;
+mod reserved_bundles;
+mod resource_init;
+mod resource_scope;
+mod snapshot;
 mod spawn_batch;
+mod system_state_scope;
 pub mod unsafe_world_cell;
 
 pub use crate::change_detection::{Mut, Ref, CHECK_TICK_THRESHOLD};
 pub use crate::world::command_queue::CommandQueue;
+pub use clone_entity::{
+    component_clone_via_clone, component_clone_via_clone_and_map, ComponentCloneContext,
+    ComponentCloneFn, ComponentCloneHandler, ComponentCloneHandlers,
+};
 pub use deferred_world::DeferredWorld;
 pub use entity_ref::{
     EntityMut, EntityRef, EntityWorldMut, Entry, FilteredEntityMut, FilteredEntityRef,
     OccupiedEntry, VacantEntry,
 };
+pub use reserved_bundles::{apply_reserved_bundles, ReservedBundles};
+pub use resource_init::{ResourceDependencies, ResourceInitializer};
+pub use resource_scope::ResourceScope;
+pub use snapshot::{SnapshotRegistry, WorldDiff, WorldSnapshot};
 pub use spawn_batch::*;
 
 use crate::{
     archetype::{ArchetypeComponentId, ArchetypeId, ArchetypeRow, Archetypes},
+    archetype_invariants::ArchetypeInvariant,
     bundle::{Bundle, BundleInfo, BundleInserter, BundleSpawner, Bundles},
     change_detection::{MutUntyped, TicksMut},
     component::{
@@ -30,7 +45,7 @@ use crate::{
     removal_detection::RemovedComponentEvents,
     schedule::{Schedule, ScheduleLabel, Schedules},
     storage::{ResourceData, Storages},
-    system::{Commands, Res, Resource},
+    system::{Commands, Res, Resource, SystemParam, SystemState},
     world::error::TryRunScheduleError,
 };
 use bevy_ptr::{OwningPtr, Ptr};
@@ -43,6 +58,8 @@ use std::{
 };
 mod identifier;
 
+use self::resource_scope::ScopedResource;
+use self::system_state_scope::CachedSystemStates;
 use self::unsafe_world_cell::{UnsafeEntityCell, UnsafeWorldCell};
 pub use identifier::WorldId;
 
@@ -188,6 +205,18 @@ impl World {
         &self.archetypes
     }
 
+    /// Registers an [`ArchetypeInvariant`] that every archetype of this world must uphold.
+    ///
+    /// Invariants are checked against all existing archetypes immediately, and against every
+    /// new archetype as it is created. Checking only happens when `debug_assertions` are enabled.
+    ///
+    /// # Panics
+    ///
+    /// With `debug_assertions` enabled, panics if an archetype violates the invariant.
+    pub fn add_archetype_invariant(&mut self, invariant: ArchetypeInvariant) {
+        self.archetypes.add_invariant(&self.components, invariant);
+    }
+
     /// Retrieves this world's [`Components`] collection.
     #[inline]
     pub fn components(&self) -> &Components {
@@ -858,6 +887,36 @@ impl World {
         SpawnBatchIter::new(self, iter.into_iter())
     }
 
+    /// Spawns several batches of entities, each with its own component [`Bundle`] type,
+    /// and returns the spawned [`Entity`]s in order.
+    ///
+    /// Each batch is spawned as with [`World::spawn_batch`], so every group of entities
+    /// only looks up its archetype and reserves storage once. This makes it possible to
+    /// bulk-spawn different kinds of entities, for example when loading a level, without
+    /// spawning them one at a time.
+    ///
+    /// ```
+    /// use bevy_ecs::{component::Component, world::World};
+    ///
+    /// #[derive(Component)]
+    /// struct Wall;
+    /// #[derive(Component)]
+    /// struct Enemy(u32);
+    ///
+    /// let mut world = World::new();
+    /// let entities = world.spawn_batches((
+    ///     (0..10).map(|_| Wall),
+    ///     vec![(Enemy(1), Wall), (Enemy(2), Wall)],
+    /// ));
+    ///
+    /// assert_eq!(entities.len(), 12);
+    /// ```
+    pub fn spawn_batches(&mut self, batches: impl BundleBatches) -> Vec<Entity> {
+        let mut entities = Vec::new();
+        batches.spawn_batches(self, &mut entities);
+        entities
+    }
+
     /// Retrieves a reference to the given `entity`'s [`Component`] of the given type.
     /// Returns `None` if the `entity` does not have a [`Component`] of the given type.
     /// ```
@@ -1690,19 +1749,100 @@ impl World {
         let last_change_tick = self.last_change_tick();
         let change_tick = self.change_tick();
 
-        let component_id = self
-            .components
-            .get_resource_id(TypeId::of::<R>())
-            .unwrap_or_else(|| panic!("resource does not exist: {}", std::any::type_name::<R>()));
-        let (ptr, mut ticks) = self
-            .storages
-            .resources
-            .get_mut(component_id)
-            .and_then(|info| info.remove())
-            .unwrap_or_else(|| panic!("resource does not exist: {}", std::any::type_name::<R>()));
-        // Read the value onto the stack to avoid potential mut aliasing.
-        // SAFETY: `ptr` was obtained from the TypeId of `R`.
-        let mut value = unsafe { ptr.read::<R>() };
+        let mut resource = ScopedResource::<R>::take(self);
+        let result = f(self, resource.as_mut(last_change_tick, change_tick));
+        resource.reinsert(self);
+        result
+    }
+
+    /// Temporarily removes all the resources in the tuple `R` from this [`World`], runs custom user code,
+    /// then re-adds the resources before returning.
+    ///
+    /// This is the same as [`World::resource_scope`], for several resources at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the resources does not exist, appears more than once in `R`,
+    /// or is inserted again by `f`.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    /// #[derive(Resource)]
+    /// struct A(u32);
+    /// #[derive(Resource)]
+    /// struct B(u32);
+    /// #[derive(Component)]
+    /// struct C(u32);
+    /// let mut world = World::new();
+    /// world.insert_resource(A(1));
+    /// world.insert_resource(B(2));
+    /// let entity = world.spawn(C(3)).id();
+    ///
+    /// world.resources_scope::<(A, B), _>(|world, (mut a, b)| {
+    ///     let c = world.get_mut::<C>(entity).unwrap();
+    ///     a.0 += b.0 * c.0;
+    /// });
+    /// assert_eq!(world.resource::<A>().0, 7);
+    /// ```
+    pub fn resources_scope<R: ResourceScope, U>(
+        &mut self,
+        f: impl FnOnce(&mut World, R::Item<'_>) -> U,
+    ) -> U {
+        R::scope(self, f)
+    }
+
+    /// Temporarily removes the component `C` from `entity`, runs custom user code,
+    /// then re-adds the component before returning.
+    ///
+    /// This enables safe simultaneous mutable access to both a component and the rest of the [`World`].
+    /// The change ticks of the component are preserved, and neither its hooks nor
+    /// [`RemovedComponents`](crate::removal_detection::RemovedComponents) see the removal and re-insertion,
+    /// but the entity does change archetype twice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `entity` does not exist or does not have the component `C`,
+    /// or if `f` despawns `entity` or inserts `C` on it again.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct A(u32);
+    /// #[derive(Component)]
+    /// struct B(u32);
+    /// let mut world = World::new();
+    /// let entity = world.spawn(A(1)).id();
+    /// world.spawn(B(2));
+    ///
+    /// world.component_scope(entity, |world, mut a: Mut<A>| {
+    ///     for b in world.query::<&B>().iter(world) {
+    ///         a.0 += b.0;
+    ///     }
+    /// });
+    /// assert_eq!(world.get::<A>(entity).unwrap().0, 3);
+    /// ```
+    pub fn component_scope<C: Component, U>(
+        &mut self,
+        entity: Entity,
+        f: impl FnOnce(&mut World, Mut<C>) -> U,
+    ) -> U {
+        let last_change_tick = self.last_change_tick();
+        let change_tick = self.change_tick();
+
+        let mut entity_mut = self
+            .get_entity_mut(entity)
+            .unwrap_or_else(|| panic!("entity does not exist: {entity:?}"));
+        let mut ticks = entity_mut.get_change_ticks::<C>().unwrap_or_else(|| {
+            panic!(
+                "component does not exist on {entity:?}: {}",
+                std::any::type_name::<C>()
+            )
+        });
+        // SAFETY: the entity has the component, as it has change ticks for it.
+        let mut value = unsafe { entity_mut.take_without_hooks::<C>().debug_checked_unwrap() };
+
         let value_mut = Mut {
             value: &mut value,
             ticks: TicksMut {
@@ -1710,33 +1850,80 @@ impl World {
                 changed: &mut ticks.changed,
                 last_run: last_change_tick,
                 this_run: change_tick,
+                change_detection: C::CHANGE_DETECTION,
             },
         };
         let result = f(self, value_mut);
-        assert!(!self.contains_resource::<R>(),
-            "Resource `{}` was inserted during a call to World::resource_scope.\n\
-            This is not allowed as the original resource is reinserted to the world after the closure is invoked.",
-            std::any::type_name::<R>());
 
-        OwningPtr::make(value, |ptr| {
-            // SAFETY: pointer is of type R
-            unsafe {
-                self.storages
-                    .resources
-                    .get_mut(component_id)
-                    .map(|info| info.insert_with_ticks(ptr, ticks))
-                    .unwrap_or_else(|| {
-                        panic!(
-                            "No resource of type {} exists in the World.",
-                            std::any::type_name::<R>()
-                        )
-                    });
-            }
+        let mut entity_mut = self.get_entity_mut(entity).unwrap_or_else(|| {
+            panic!("Entity {entity:?} was despawned during a call to World::component_scope.")
         });
+        assert!(!entity_mut.contains::<C>(),
+            "Component `{}` was inserted on {entity:?} during a call to World::component_scope.\n\
+            This is not allowed as the original component is reinserted to the entity after the closure is invoked.",
+            std::any::type_name::<C>());
+        entity_mut.insert_without_hooks(value);
+        // SAFETY: the component was just inserted.
+        let component = unsafe { entity_mut.get_mut::<C>().debug_checked_unwrap() };
+        *component.ticks.added = ticks.added;
+        *component.ticks.changed = ticks.changed;
 
         result
     }
 
+    /// Temporarily removes the [`SystemState`] cached in this [`World`] for the calling code,
+    /// runs custom user code, then caches the state again before returning.
+    ///
+    /// The state is keyed by the location of the call in the code and by the type of `P`.
+    /// It is created the first time this is called, and is then reused by every later call
+    /// from the same location with the same parameters. Note that a helper function calling this
+    /// shares a single state between all of its callers, as the example below does.
+    /// This keeps change detection, [`Local`](crate::system::Local)s and [`EventReader`](crate::event::EventReader)s
+    /// working across runs, and avoids rebuilding the state every time.
+    ///
+    /// As with any [`SystemState`], deferred operations such as [`Commands`]
+    /// are only applied when [`SystemState::apply`] is called.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// fn count_new_entities(world: &mut World) -> usize {
+    ///     world.system_state_scope::<Query<(), Added<Health>>, _>(|world, state| {
+    ///         state.get(world).iter().count()
+    ///     })
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.spawn(Health(10));
+    /// assert_eq!(count_new_entities(&mut world), 1);
+    /// assert_eq!(count_new_entities(&mut world), 0);
+    /// ```
+    #[track_caller]
+    pub fn system_state_scope<P: SystemParam + 'static, U>(
+        &mut self,
+        f: impl FnOnce(&mut World, &mut SystemState<P>) -> U,
+    ) -> U {
+        let key = (std::panic::Location::caller(), TypeId::of::<P>());
+        let cached = self
+            .get_resource_mut::<CachedSystemStates>()
+            .and_then(|mut states| states.0.remove(&key));
+        let mut state = match cached {
+            // The key includes the `TypeId` of `P`, so the cached state has this type.
+            Some(state) => *state.downcast::<SystemState<P>>().unwrap(),
+            None => SystemState::new(self),
+        };
+
+        let result = f(self, &mut state);
+
+        self.get_resource_or_insert_with(CachedSystemStates::default)
+            .0
+            .insert(key, Box::new(state));
+        result
+    }
+
     /// Sends an [`Event`].
     /// This method returns the [ID](`EventId`) of the sent `event`,
     /// or [`None`] if the `event` could not be sent.
@@ -3124,4 +3311,22 @@ mod tests {
         let mut world = World::new();
         world.spawn(());
     }
+
+    #[test]
+    fn spawn_batches() {
+        #[derive(Component, Debug, PartialEq)]
+        struct A(u32);
+        #[derive(Component, Debug, PartialEq)]
+        struct B(u32);
+
+        let mut world = World::new();
+        let entities = world.spawn_batches(((0..2).map(A), vec![(A(2), B(0))], [B(1)]));
+
+        assert_eq!(entities.len(), 4);
+        assert_eq!(world.entity(entities[1]).get(), Some(&A(1)));
+        assert_eq!(world.entity(entities[2]).get(), Some(&A(2)));
+        assert_eq!(world.entity(entities[2]).get(), Some(&B(0)));
+        assert_eq!(world.entity(entities[3]).get::<A>(), None);
+        assert_eq!(world.entity(entities[3]).get(), Some(&B(1)));
+    }
 }
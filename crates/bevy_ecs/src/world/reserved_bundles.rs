@@ -0,0 +1,131 @@
+//! Attaching bundles to reserved entities from other threads.
+
+use std::sync::{Arc, Mutex};
+
+use bevy_utils::tracing::warn;
+
+use crate as bevy_ecs;
+use crate::{
+    bundle::Bundle,
+    entity::Entity,
+    system::Resource,
+    world::{EntityWorldMut, World},
+};
+
+type InsertBundle = Box<dyn FnOnce(&mut EntityWorldMut) + Send>;
+
+/// Queues [`Bundle`]s to be inserted on entities from any thread, without access to the [`World`].
+///
+/// Entities can be reserved through a shared `&World` with [`Entities::reserve_entity`](crate::entity::Entities::reserve_entity),
+/// so their ids can be handed out to tasks such as asset loaders before they exist.
+/// The tasks then queue the components of those entities on a clone of this handle,
+/// and the main thread inserts them all with [`ReservedBundles::apply`].
+///
+/// The `ReservedBundlesPlugin` of `bevy_app` inserts this resource and applies it with
+/// [`apply_reserved_bundles`] at the start of every frame, in the `First` schedule.
+///
+/// Bundles are applied ordered by entity, and in the order they were queued for the same entity,
+/// so the result does not depend on the order in which the tasks finished.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::world::ReservedBundles;
+/// #[derive(Component)]
+/// struct Mesh(&'static str);
+///
+/// let mut world = World::new();
+/// let bundles = ReservedBundles::default();
+///
+/// let entity = world.entities().reserve_entity();
+/// let task_bundles = bundles.clone();
+/// std::thread::spawn(move || task_bundles.insert(entity, Mesh("tree.gltf")))
+///     .join()
+///     .unwrap();
+///
+/// bundles.apply(&mut world);
+/// assert_eq!(world.get::<Mesh>(entity).unwrap().0, "tree.gltf");
+/// ```
+#[derive(Resource, Clone, Default)]
+pub struct ReservedBundles {
+    queue: Arc<Mutex<Vec<(Entity, InsertBundle)>>>,
+}
+
+impl ReservedBundles {
+    /// Queues `bundle` to be inserted on `entity` the next time [`ReservedBundles::apply`] is called.
+    pub fn insert(&self, entity: Entity, bundle: impl Bundle) {
+        let insert: InsertBundle = Box::new(move |entity| {
+            entity.insert(bundle);
+        });
+        self.queue.lock().unwrap().push((entity, insert));
+    }
+
+    /// Returns `true` if no bundles are waiting to be applied.
+    pub fn is_empty(&self) -> bool {
+        self.queue.lock().unwrap().is_empty()
+    }
+
+    /// Inserts every queued bundle on its entity.
+    ///
+    /// Bundles queued for entities that have been despawned in the meantime are dropped with a warning.
+    pub fn apply(&self, world: &mut World) {
+        let mut queue = std::mem::take(&mut *self.queue.lock().unwrap());
+        if queue.is_empty() {
+            return;
+        }
+        // A stable sort keeps the bundles of each entity in the order they were queued.
+        queue.sort_by_key(|(entity, _)| *entity);
+
+        world.flush_entities();
+        for (entity, insert) in queue {
+            match world.get_entity_mut(entity) {
+                Some(mut entity) => insert(&mut entity),
+                None => warn!("Could not insert a bundle on {entity:?} because it does not exist."),
+            }
+        }
+    }
+}
+
+/// An exclusive system that applies the [`ReservedBundles`] resource, if it exists.
+///
+/// The `ReservedBundlesPlugin` of `bevy_app` runs this system in the `First` schedule.
+pub fn apply_reserved_bundles(world: &mut World) {
+    if let Some(bundles) = world.get_resource::<ReservedBundles>() {
+        bundles.clone().apply(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::system::RunSystemOnce;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Order(Vec<u32>);
+
+    #[test]
+    fn bundles_are_applied_in_entity_order() {
+        let mut world = World::new();
+        world.init_resource::<ReservedBundles>();
+        let bundles = world.resource::<ReservedBundles>().clone();
+        let entities = world.entities().reserve_entities(3).collect::<Vec<_>>();
+
+        std::thread::scope(|scope| {
+            for (i, &entity) in entities.iter().enumerate().rev() {
+                let bundles = bundles.clone();
+                scope.spawn(move || bundles.insert(entity, Order(vec![i as u32])));
+            }
+        });
+        bundles.insert(entities[0], Order(vec![10]));
+
+        let despawned = world.spawn_empty().id();
+        world.despawn(despawned);
+        bundles.insert(despawned, Order(vec![]));
+
+        world.run_system_once(apply_reserved_bundles);
+        assert!(bundles.is_empty());
+        assert_eq!(world.get::<Order>(entities[0]), Some(&Order(vec![10])));
+        assert_eq!(world.get::<Order>(entities[2]), Some(&Order(vec![2])));
+        assert!(world.get_entity(despawned).is_none());
+    }
+}
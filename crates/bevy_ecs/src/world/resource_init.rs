@@ -0,0 +1,228 @@
+use std::any::{type_name, TypeId};
+
+use bevy_utils::{all_tuples, HashMap};
+
+use crate::{
+    system::Resource,
+    world::{error::ResourceInitError, FromWorld, World},
+};
+
+/// A set of [`Resource`] types, used to declare the dependencies of a resource
+/// added to a [`ResourceInitializer`].
+///
+/// Implemented for tuples of up to 15 resource types.
+pub trait ResourceDependencies {
+    /// Returns the [`TypeId`]s of the resources in this set.
+    fn type_ids() -> Vec<TypeId>;
+}
+
+macro_rules! impl_resource_dependencies {
+    ($($name: ident),*) => {
+        impl<$($name: Resource),*> ResourceDependencies for ($($name,)*) {
+            fn type_ids() -> Vec<TypeId> {
+                vec![$(TypeId::of::<$name>()),*]
+            }
+        }
+    };
+}
+
+all_tuples!(impl_resource_dependencies, 0, 15, R);
+
+struct PendingResource {
+    name: &'static str,
+    dependencies: Vec<TypeId>,
+    init: fn(&mut World),
+}
+
+/// Initializes [`Resource`]s in the order of their dependencies, so that the [`FromWorld`]
+/// implementation of a resource can read the resources it depends on, no matter in which
+/// order they were added.
+///
+/// Apps keep one of these for the resources added with `App::init_resource_after`,
+/// and initialize them when the app finishes building its plugins.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::world::ResourceInitializer;
+/// #[derive(Resource)]
+/// struct Config {
+///     scale: f32,
+/// }
+///
+/// impl Default for Config {
+///     fn default() -> Self {
+///         Self { scale: 2.0 }
+///     }
+/// }
+///
+/// #[derive(Resource)]
+/// struct Layout {
+///     width: f32,
+/// }
+///
+/// impl FromWorld for Layout {
+///     fn from_world(world: &mut World) -> Self {
+///         Self {
+///             width: 100.0 * world.resource::<Config>().scale,
+///         }
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// let mut initializer = ResourceInitializer::default();
+/// initializer.add_after::<Layout, (Config,)>().add::<Config>();
+/// initializer.initialize(&mut world).unwrap();
+/// assert_eq!(world.resource::<Layout>().width, 200.0);
+/// ```
+#[derive(Default)]
+pub struct ResourceInitializer {
+    resources: Vec<(TypeId, PendingResource)>,
+}
+
+impl ResourceInitializer {
+    /// Adds the resource `R`, which does not depend on any other resource of this initializer.
+    pub fn add<R: Resource + FromWorld>(&mut self) -> &mut Self {
+        self.add_after::<R, ()>()
+    }
+
+    /// Adds the resource `R`, which is initialized after the resources in `D`.
+    ///
+    /// Dependencies that are not added to this initializer are expected to already exist
+    /// when [`ResourceInitializer::initialize`] is called.
+    pub fn add_after<R: Resource + FromWorld, D: ResourceDependencies>(&mut self) -> &mut Self {
+        self.resources.push((
+            TypeId::of::<R>(),
+            PendingResource {
+                name: type_name::<R>(),
+                dependencies: D::type_ids(),
+                init: |world| {
+                    world.init_resource::<R>();
+                },
+            },
+        ));
+        self
+    }
+
+    /// Initializes every added resource with [`World::init_resource`], each one after its dependencies.
+    ///
+    /// Returns an error listing the resources involved if their dependencies form a cycle,
+    /// in which case no resource is initialized.
+    pub fn initialize(self, world: &mut World) -> Result<(), ResourceInitError> {
+        let indices = self
+            .resources
+            .iter()
+            .enumerate()
+            .map(|(index, (type_id, _))| (*type_id, index))
+            .collect::<HashMap<_, _>>();
+
+        #[derive(Clone, Copy, PartialEq)]
+        enum Visit {
+            Unvisited,
+            InProgress,
+            Done,
+        }
+
+        fn visit(
+            index: usize,
+            resources: &[(TypeId, PendingResource)],
+            indices: &HashMap<TypeId, usize>,
+            visits: &mut [Visit],
+            path: &mut Vec<usize>,
+            order: &mut Vec<usize>,
+        ) -> Result<(), ResourceInitError> {
+            match visits[index] {
+                Visit::Done => return Ok(()),
+                Visit::InProgress => {
+                    let start = path.iter().position(|&i| i == index).unwrap();
+                    let cycle = path[start..].iter().map(|&i| resources[i].1.name).collect();
+                    return Err(ResourceInitError::DependencyCycle(cycle));
+                }
+                Visit::Unvisited => {}
+            }
+            visits[index] = Visit::InProgress;
+            path.push(index);
+            for dependency in &resources[index].1.dependencies {
+                if let Some(&dependency) = indices.get(dependency) {
+                    visit(dependency, resources, indices, visits, path, order)?;
+                }
+            }
+            path.pop();
+            visits[index] = Visit::Done;
+            order.push(index);
+            Ok(())
+        }
+
+        let mut visits = vec![Visit::Unvisited; self.resources.len()];
+        let mut order = Vec::with_capacity(self.resources.len());
+        for index in 0..self.resources.len() {
+            visit(
+                index,
+                &self.resources,
+                &indices,
+                &mut visits,
+                &mut Vec::new(),
+                &mut order,
+            )?;
+        }
+
+        for index in order {
+            (self.resources[index].1.init)(world);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_ecs;
+
+    #[derive(Resource, Default)]
+    struct A(u32);
+
+    #[derive(Resource)]
+    struct B(u32);
+
+    impl FromWorld for B {
+        fn from_world(world: &mut World) -> Self {
+            Self(world.resource::<A>().0 + 1)
+        }
+    }
+
+    #[derive(Resource)]
+    struct C(u32);
+
+    impl FromWorld for C {
+        fn from_world(world: &mut World) -> Self {
+            Self(world.resource::<B>().0 + 1)
+        }
+    }
+
+    #[test]
+    fn resources_are_initialized_in_dependency_order() {
+        let mut world = World::new();
+        let mut initializer = ResourceInitializer::default();
+        initializer
+            .add_after::<C, (B,)>()
+            .add_after::<B, (A,)>()
+            .add::<A>();
+        initializer.initialize(&mut world).unwrap();
+        assert_eq!(world.resource::<C>().0, 2);
+    }
+
+    #[test]
+    fn dependency_cycles_are_reported() {
+        let mut world = World::new();
+        let mut initializer = ResourceInitializer::default();
+        initializer
+            .add::<A>()
+            .add_after::<B, (C,)>()
+            .add_after::<C, (A, B)>();
+        let Err(ResourceInitError::DependencyCycle(cycle)) = initializer.initialize(&mut world)
+        else {
+            panic!("expected a dependency cycle");
+        };
+        assert_eq!(cycle, vec![type_name::<B>(), type_name::<C>()]);
+        assert!(!world.contains_resource::<A>());
+    }
+}
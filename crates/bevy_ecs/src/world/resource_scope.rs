@@ -0,0 +1,267 @@
+use bevy_ptr::OwningPtr;
+use bevy_utils::all_tuples;
+
+use crate::{
+    change_detection::{Mut, TicksMut},
+    component::{ComponentId, ComponentTicks, Tick},
+    system::Resource,
+    world::World,
+};
+
+/// A resource temporarily removed from a [`World`] by a resource scope.
+pub(crate) struct ScopedResource<R: Resource> {
+    component_id: ComponentId,
+    value: R,
+    ticks: ComponentTicks,
+}
+
+impl<R: Resource> ScopedResource<R> {
+    /// Returns the [`ComponentId`] of the resource `R`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource does not exist.
+    pub(crate) fn component_id(world: &World) -> ComponentId {
+        world
+            .components
+            .get_resource_id(std::any::TypeId::of::<R>())
+            .filter(|&component_id| {
+                world
+                    .storages
+                    .resources
+                    .get(component_id)
+                    .is_some_and(|info| info.is_present())
+            })
+            .unwrap_or_else(|| panic!("resource does not exist: {}", std::any::type_name::<R>()))
+    }
+
+    /// Removes the resource `R` from `world`, keeping its change ticks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource does not exist.
+    pub(crate) fn take(world: &mut World) -> Self {
+        let component_id = Self::component_id(world);
+        let (ptr, ticks) = world
+            .storages
+            .resources
+            .get_mut(component_id)
+            .and_then(|info| info.remove())
+            .unwrap_or_else(|| panic!("resource does not exist: {}", std::any::type_name::<R>()));
+        // Read the value onto the stack to avoid potential mut aliasing.
+        // SAFETY: `ptr` was obtained from the TypeId of `R`.
+        let value = unsafe { ptr.read::<R>() };
+        Self {
+            component_id,
+            value,
+            ticks,
+        }
+    }
+
+    /// Returns a change-detecting reference to the removed resource.
+    pub(crate) fn as_mut(&mut self, last_run: Tick, this_run: Tick) -> Mut<'_, R> {
+        Mut {
+            value: &mut self.value,
+            ticks: TicksMut {
+                added: &mut self.ticks.added,
+                changed: &mut self.ticks.changed,
+                last_run,
+                this_run,
+                change_detection: true,
+            },
+        }
+    }
+
+    /// Inserts the resource back into `world`, with its updated change ticks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource has been inserted again while it was removed.
+    pub(crate) fn reinsert(self, world: &mut World) {
+        assert!(!world.contains_resource::<R>(),
+            "Resource `{}` was inserted during a call to World::resource_scope.\n\
+            This is not allowed as the original resource is reinserted to the world after the closure is invoked.",
+            std::any::type_name::<R>());
+
+        let Self {
+            component_id,
+            value,
+            ticks,
+        } = self;
+        OwningPtr::make(value, |ptr| {
+            // SAFETY: pointer is of type R
+            unsafe {
+                world
+                    .storages
+                    .resources
+                    .get_mut(component_id)
+                    .map(|info| info.insert_with_ticks(ptr, ticks))
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "No resource of type {} exists in the World.",
+                            std::any::type_name::<R>()
+                        )
+                    });
+            }
+        });
+    }
+}
+
+/// A tuple of [`Resource`]s that can be removed from the [`World`] together by [`World::resources_scope`].
+///
+/// Implemented for tuples of up to 15 resource types.
+pub trait ResourceScope {
+    /// The mutable references to the resources passed to the scope.
+    type Item<'a>;
+
+    /// Removes the resources from `world`, runs `f` and then reinserts the resources.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the resources does not exist, appears more than once in the tuple,
+    /// or is inserted again by `f`.
+    fn scope<U>(world: &mut World, f: impl FnOnce(&mut World, Self::Item<'_>) -> U) -> U;
+}
+
+macro_rules! impl_resource_scope {
+    ($($name: ident),*) => {
+        impl<$($name: Resource),*> ResourceScope for ($($name,)*) {
+            type Item<'a> = ($(Mut<'a, $name>,)*);
+
+            #[allow(non_snake_case)]
+            fn scope<U>(world: &mut World, f: impl FnOnce(&mut World, Self::Item<'_>) -> U) -> U {
+                let last_run = world.last_change_tick();
+                let this_run = world.change_tick();
+                // Check every resource before taking any, so that a missing or repeated resource
+                // panics without losing the resources that would already have been taken.
+                let component_ids = [$(ScopedResource::<$name>::component_id(world)),*];
+                for (index, component_id) in component_ids.iter().enumerate() {
+                    assert!(
+                        !component_ids[..index].contains(component_id),
+                        "resource appears more than once in World::resources_scope: {}",
+                        world.components.get_name(*component_id).unwrap_or_default(),
+                    );
+                }
+                $(let mut $name = ScopedResource::<$name>::take(world);)*
+                let result = f(world, ($($name.as_mut(last_run, this_run),)*));
+                $($name.reinsert(world);)*
+                result
+            }
+        }
+    };
+}
+
+all_tuples!(impl_resource_scope, 1, 15, R);
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::component::ComponentId;
+    use crate::prelude::*;
+
+    #[derive(Resource)]
+    struct Score(u32);
+
+    #[derive(Resource)]
+    struct Multiplier(u32);
+
+    #[derive(Component)]
+    struct Points(u32);
+
+    #[test]
+    fn resources_scope_removes_and_reinserts_all_resources() {
+        let mut world = World::new();
+        world.insert_resource(Score(0));
+        world.insert_resource(Multiplier(3));
+        world.spawn(Points(2));
+        world.spawn(Points(5));
+
+        world.resources_scope::<(Score, Multiplier), _>(|world, (mut score, multiplier)| {
+            assert!(!world.contains_resource::<Score>());
+            assert!(!world.contains_resource::<Multiplier>());
+            for points in world.query::<&Points>().iter(world) {
+                score.0 += points.0 * multiplier.0;
+            }
+        });
+        assert_eq!(world.resource::<Score>().0, 21);
+        assert_eq!(world.resource::<Multiplier>().0, 3);
+    }
+
+    #[test]
+    fn resources_scope_keeps_change_ticks() {
+        let mut world = World::new();
+        world.insert_resource(Score(0));
+        world.insert_resource(Multiplier(1));
+        world.increment_change_tick();
+        let tick = world.change_tick();
+
+        world.resources_scope::<(Score, Multiplier), _>(|_, (mut score, _)| {
+            score.0 += 1;
+        });
+        assert_eq!(world.resource_ref::<Score>().last_changed(), tick);
+        assert_ne!(world.resource_ref::<Multiplier>().last_changed(), tick);
+    }
+
+    #[test]
+    #[should_panic(expected = "resource appears more than once")]
+    fn resources_scope_panics_on_duplicate_resources() {
+        let mut world = World::new();
+        world.insert_resource(Score(0));
+        world.resources_scope::<(Score, Score), _>(|_, _| {});
+    }
+
+    #[test]
+    fn resources_scope_keeps_resources_when_a_resource_is_missing() {
+        let mut world = World::new();
+        world.insert_resource(Score(1));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            world.resources_scope::<(Score, Multiplier), _>(|_, _| {});
+        }));
+        assert!(result.is_err());
+        assert_eq!(world.resource::<Score>().0, 1);
+
+        world.insert_resource(Multiplier(2));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            world.resources_scope::<(Score, Multiplier, Score), _>(|_, _| {});
+        }));
+        assert!(result.is_err());
+        assert_eq!(world.resource::<Score>().0, 1);
+        assert_eq!(world.resource::<Multiplier>().0, 2);
+    }
+
+    #[test]
+    fn component_scope_removes_and_reinserts_component() {
+        let mut world = World::new();
+        world.insert_resource(Score(4));
+        let entity = world.spawn(Points(1)).id();
+
+        let ticks = world.entity(entity).get_change_ticks::<Points>().unwrap();
+        world.component_scope(entity, |world, mut points: Mut<Points>| {
+            assert!(!world.entity(entity).contains::<Points>());
+            points.0 += world.resource::<Score>().0;
+        });
+        assert_eq!(world.get::<Points>(entity).unwrap().0, 5);
+        let new_ticks = world.entity(entity).get_change_ticks::<Points>().unwrap();
+        assert_eq!(new_ticks.added, ticks.added);
+    }
+
+    #[test]
+    fn component_scope_skips_hooks_and_removal_events() {
+        #[derive(Component)]
+        #[component(on_add = count_hook, on_insert = count_hook, on_remove = count_hook)]
+        struct Hooked;
+
+        fn count_hook(mut world: bevy_ecs::world::DeferredWorld, _: Entity, _: ComponentId) {
+            world.resource_mut::<Score>().0 += 1;
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Score(0));
+        let entity = world.spawn(Hooked).id();
+        assert_eq!(world.resource::<Score>().0, 2);
+
+        world.component_scope(entity, |_, _: Mut<Hooked>| {});
+        assert_eq!(world.resource::<Score>().0, 2);
+        assert_eq!(world.removed::<Hooked>().count(), 0);
+    }
+}
@@ -0,0 +1,1128 @@
+//! Capturing, diffing and restoring the state of a [`World`] for rollback and undo.
+
+use std::any::Any;
+
+use bevy_ptr::Ptr;
+use bevy_utils::HashMap;
+
+use crate as bevy_ecs;
+use crate::{
+    binary::{deserialize_len, serialize_len, BinaryError, BinarySerialize},
+    change_detection::MutUntyped,
+    component::{Component, ComponentId, ComponentTicks, Tick},
+    entity::Entity,
+    query::DebugCheckedUnwrap,
+    system::Resource,
+    world::{EntityWorldMut, World},
+};
+
+type SnapshotValue = Box<dyn Any + Send + Sync>;
+
+/// Type-erased functions used to copy a single component or resource in and out of a [`WorldSnapshot`].
+#[derive(Clone, Copy)]
+struct SnapshotFns {
+    clone: unsafe fn(Ptr<'_>) -> SnapshotValue,
+    duplicate: fn(&SnapshotValue) -> SnapshotValue,
+    restore_component: fn(&mut EntityWorldMut, SnapshotValue),
+    restore_resource: fn(&mut World, SnapshotValue),
+    serialize: Option<fn(&SnapshotValue, &mut Vec<u8>)>,
+    deserialize: Option<fn(&mut &[u8]) -> Result<SnapshotValue, BinaryError>>,
+}
+
+impl SnapshotFns {
+    fn of_component<T: Component + Clone>() -> Self {
+        Self {
+            clone: clone_value::<T>,
+            duplicate: duplicate_value::<T>,
+            restore_component: |entity, value| {
+                entity.insert(downcast_value::<T>(value));
+            },
+            restore_resource: |_, _| unreachable!("components are never restored as resources"),
+            serialize: None,
+            deserialize: None,
+        }
+    }
+
+    fn of_resource<R: Resource + Clone>() -> Self {
+        Self {
+            clone: clone_value::<R>,
+            duplicate: duplicate_value::<R>,
+            restore_component: |_, _| unreachable!("resources are never restored as components"),
+            restore_resource: |world, value| world.insert_resource(downcast_value::<R>(value)),
+            serialize: None,
+            deserialize: None,
+        }
+    }
+
+    fn with_binary<T: BinarySerialize + Send + Sync + 'static>(self) -> Self {
+        Self {
+            serialize: Some(serialize_value::<T>),
+            deserialize: Some(deserialize_value::<T>),
+            ..self
+        }
+    }
+}
+
+/// # Safety
+/// `ptr` must point to a valid value of type `T`.
+unsafe fn clone_value<T: Clone + Send + Sync + 'static>(ptr: Ptr<'_>) -> SnapshotValue {
+    Box::new(ptr.deref::<T>().clone())
+}
+
+fn duplicate_value<T: Clone + Send + Sync + 'static>(value: &SnapshotValue) -> SnapshotValue {
+    Box::new(
+        value
+            .downcast_ref::<T>()
+            .expect("snapshot value did not match the registered type")
+            .clone(),
+    )
+}
+
+fn serialize_value<T: BinarySerialize + 'static>(value: &SnapshotValue, buffer: &mut Vec<u8>) {
+    value
+        .downcast_ref::<T>()
+        .expect("snapshot value did not match the registered type")
+        .serialize(buffer);
+}
+
+fn deserialize_value<T: BinarySerialize + Send + Sync + 'static>(
+    bytes: &mut &[u8],
+) -> Result<SnapshotValue, BinaryError> {
+    Ok(Box::new(T::deserialize(bytes)?))
+}
+
+/// Overwrites the ticks of a restored value with the captured ones, clamped so they are not
+/// older than the oldest tick change detection can still compare against `change_tick`.
+fn restore_ticks(value: MutUntyped, mut ticks: ComponentTicks, change_tick: Tick) {
+    ticks.added.check_tick(change_tick);
+    ticks.changed.check_tick(change_tick);
+    *value.ticks.added = ticks.added;
+    *value.ticks.changed = ticks.changed;
+}
+
+fn downcast_value<T: 'static>(value: SnapshotValue) -> T {
+    *value
+        .downcast::<T>()
+        .expect("snapshot value did not match the registered type")
+}
+
+/// Stores which components and resources take part in [`World::snapshot`] and [`World::restore`].
+///
+/// Only registered types are captured: everything else is left untouched on restore.
+/// Use [`World::register_snapshot_component`] and [`World::register_snapshot_resource`] to populate it.
+#[derive(Resource, Default)]
+pub struct SnapshotRegistry {
+    components: HashMap<ComponentId, SnapshotFns>,
+    resources: HashMap<ComponentId, SnapshotFns>,
+    /// The components and resources a [`WorldDiff`] can be serialized with, in registration order.
+    serializable: Vec<ComponentId>,
+    serializable_ids: HashMap<ComponentId, u16>,
+}
+
+impl SnapshotRegistry {
+    /// Returns `true` if the component with the given [`ComponentId`] is captured by snapshots.
+    pub fn contains_component(&self, component_id: ComponentId) -> bool {
+        self.components.contains_key(&component_id)
+    }
+
+    /// Returns `true` if the resource with the given [`ComponentId`] is captured by snapshots.
+    pub fn contains_resource(&self, component_id: ComponentId) -> bool {
+        self.resources.contains_key(&component_id)
+    }
+
+    /// Returns the id the component or resource with the given [`ComponentId`] is written with
+    /// by [`WorldDiff::serialize`], if it was registered as serializable.
+    pub fn serializable_id(&self, component_id: ComponentId) -> Option<u16> {
+        self.serializable_ids.get(&component_id).copied()
+    }
+
+    fn fns(&self, component_id: ComponentId) -> Option<&SnapshotFns> {
+        self.components
+            .get(&component_id)
+            .or_else(|| self.resources.get(&component_id))
+    }
+
+    fn register_serializable(&mut self, component_id: ComponentId) {
+        if !self.serializable_ids.contains_key(&component_id) {
+            let id = u16::try_from(self.serializable.len())
+                .expect("cannot register more than u16::MAX serializable snapshot types");
+            self.serializable.push(component_id);
+            self.serializable_ids.insert(component_id, id);
+        }
+    }
+}
+
+/// Writes the id `component_id` was registered with by [`World::register_serializable_snapshot_component`]
+/// or [`World::register_serializable_snapshot_resource`].
+fn serialize_component_id(
+    world: &World,
+    registry: Option<&SnapshotRegistry>,
+    component_id: ComponentId,
+    buffer: &mut Vec<u8>,
+) {
+    let Some(id) = registry.and_then(|registry| registry.serializable_id(component_id)) else {
+        panic!(
+            "{} cannot be serialized: it was not registered as a serializable snapshot type",
+            world
+                .components()
+                .get_name(component_id)
+                .unwrap_or("unknown component")
+        );
+    };
+    id.serialize(buffer);
+}
+
+fn serialize_snapshot_value(
+    world: &World,
+    registry: Option<&SnapshotRegistry>,
+    component_id: ComponentId,
+    value: &SnapshotValue,
+    buffer: &mut Vec<u8>,
+) {
+    serialize_component_id(world, registry, component_id, buffer);
+    // The component has a serializable id, so it was registered along with its binary functions.
+    let serialize = registry
+        .and_then(|registry| registry.fns(component_id)?.serialize)
+        .expect("serializable snapshot types have binary functions");
+    serialize(value, buffer);
+}
+
+fn deserialize_component_id(
+    registry: Option<&SnapshotRegistry>,
+    bytes: &mut &[u8],
+) -> Result<ComponentId, BinaryError> {
+    let id = u16::deserialize(bytes)?;
+    registry
+        .and_then(|registry| registry.serializable.get(id as usize).copied())
+        .ok_or(BinaryError::UnknownComponent(id))
+}
+
+fn deserialize_snapshot_value(
+    registry: Option<&SnapshotRegistry>,
+    bytes: &mut &[u8],
+) -> Result<(ComponentId, SnapshotValue), BinaryError> {
+    let component_id = deserialize_component_id(registry, bytes)?;
+    // The component has a serializable id, so it was registered along with its binary functions.
+    let deserialize = registry
+        .and_then(|registry| registry.fns(component_id)?.deserialize)
+        .expect("serializable snapshot types have binary functions");
+    Ok((component_id, deserialize(bytes)?))
+}
+
+/// A single captured component value and its change ticks.
+struct SnapshotComponent {
+    id: ComponentId,
+    ticks: ComponentTicks,
+    value: SnapshotValue,
+}
+
+/// The captured components of a single entity.
+struct EntitySnapshot {
+    entity: Entity,
+    components: Vec<SnapshotComponent>,
+}
+
+/// A copy of the entities, components and resources of a [`World`], created by [`World::snapshot`].
+///
+/// Values are copied with their [`Clone`] implementation, which avoids the cost of reflection
+/// and serialization and makes snapshots cheap enough to take every tick.
+pub struct WorldSnapshot {
+    entities: Vec<EntitySnapshot>,
+    resources: Vec<SnapshotComponent>,
+    /// The set of components this snapshot was filtered to, or `None` if every registered component was captured.
+    filter: Option<Vec<ComponentId>>,
+    /// The components this snapshot covers: the filter, or the components registered when it was taken.
+    components: Vec<ComponentId>,
+    change_tick: Tick,
+}
+
+impl WorldSnapshot {
+    /// Returns the change tick of the [`World`] at the time the snapshot was taken.
+    pub fn change_tick(&self) -> Tick {
+        self.change_tick
+    }
+
+    /// Returns an iterator over the entities captured by this snapshot.
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.iter().map(|snapshot| snapshot.entity)
+    }
+
+    /// Returns `true` if this snapshot only contains a subset of the registered components.
+    pub fn is_partial(&self) -> bool {
+        self.filter.is_some()
+    }
+
+    /// Returns the change ticks `entity`'s component had when the snapshot was taken.
+    pub fn component_ticks(
+        &self,
+        entity: Entity,
+        component_id: ComponentId,
+    ) -> Option<ComponentTicks> {
+        self.entities
+            .iter()
+            .find(|snapshot| snapshot.entity == entity)?
+            .components
+            .iter()
+            .find(|component| component.id == component_id)
+            .map(|component| component.ticks)
+    }
+}
+
+/// The changes made to a [`World`] since a [`WorldSnapshot`] was taken, created by [`World::diff`].
+///
+/// Only the components and resources registered in the [`SnapshotRegistry`] are tracked.
+/// A diff can be applied to any world with [`World::apply_diff`], for example to replay changes
+/// on a copy of the world, or to redo them after restoring the snapshot.
+///
+/// Diffs can be stored or sent to another world with [`WorldDiff::serialize`] and [`WorldDiff::deserialize`],
+/// as long as their components and resources were registered with
+/// [`World::register_serializable_snapshot_component`] and [`World::register_serializable_snapshot_resource`].
+pub struct WorldDiff {
+    spawned: Vec<Entity>,
+    despawned: Vec<Entity>,
+    /// The components inserted or changed since the snapshot, including those of spawned entities.
+    changed: Vec<EntitySnapshot>,
+    removed: Vec<(Entity, ComponentId)>,
+    resources: Vec<(ComponentId, SnapshotValue)>,
+}
+
+impl WorldDiff {
+    /// Returns the entities spawned since the snapshot was taken.
+    pub fn spawned(&self) -> &[Entity] {
+        &self.spawned
+    }
+
+    /// Returns the entities despawned since the snapshot was taken.
+    pub fn despawned(&self) -> &[Entity] {
+        &self.despawned
+    }
+
+    /// Returns an iterator over the components inserted or changed since the snapshot was taken.
+    pub fn changed_components(&self) -> impl Iterator<Item = (Entity, ComponentId)> + '_ {
+        self.changed.iter().flat_map(|snapshot| {
+            snapshot
+                .components
+                .iter()
+                .map(|component| (snapshot.entity, component.id))
+        })
+    }
+
+    /// Returns the components removed from entities that still exist.
+    pub fn removed_components(&self) -> &[(Entity, ComponentId)] {
+        &self.removed
+    }
+
+    /// Returns an iterator over the resources changed since the snapshot was taken.
+    pub fn changed_resources(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.resources.iter().map(|(component_id, _)| *component_id)
+    }
+
+    /// Returns `true` if nothing changed since the snapshot was taken.
+    pub fn is_empty(&self) -> bool {
+        self.spawned.is_empty()
+            && self.despawned.is_empty()
+            && self.changed.is_empty()
+            && self.removed.is_empty()
+            && self.resources.is_empty()
+    }
+
+    /// Appends the binary representation of this diff to `buffer`, so it can be read back
+    /// with [`WorldDiff::deserialize`].
+    ///
+    /// Components and resources are identified by the order in which they were registered
+    /// as serializable, so the reading world must register the same types in the same order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a component or resource of the diff was not registered in `world` with
+    /// [`World::register_serializable_snapshot_component`] or [`World::register_serializable_snapshot_resource`].
+    pub fn serialize(&self, world: &World, buffer: &mut Vec<u8>) {
+        let registry = world.get_resource::<SnapshotRegistry>();
+
+        self.spawned.serialize(buffer);
+        self.despawned.serialize(buffer);
+
+        serialize_len(self.changed.len(), buffer);
+        for entity_diff in &self.changed {
+            entity_diff.entity.serialize(buffer);
+            serialize_len(entity_diff.components.len(), buffer);
+            for component in &entity_diff.components {
+                serialize_snapshot_value(world, registry, component.id, &component.value, buffer);
+            }
+        }
+
+        serialize_len(self.removed.len(), buffer);
+        for &(entity, component_id) in &self.removed {
+            entity.serialize(buffer);
+            serialize_component_id(world, registry, component_id, buffer);
+        }
+
+        serialize_len(self.resources.len(), buffer);
+        for (component_id, value) in &self.resources {
+            serialize_snapshot_value(world, registry, *component_id, value, buffer);
+        }
+    }
+
+    /// Reads a diff written by [`WorldDiff::serialize`], using the serializable types registered in `world`.
+    ///
+    /// Change ticks are not serialized: the deserialized components carry the current change tick of `world`.
+    pub fn deserialize(world: &World, mut bytes: &[u8]) -> Result<WorldDiff, BinaryError> {
+        let registry = world.get_resource::<SnapshotRegistry>();
+        let ticks = ComponentTicks::new(world.read_change_tick());
+        let bytes = &mut bytes;
+
+        let spawned = Vec::<Entity>::deserialize(bytes)?;
+        let despawned = Vec::<Entity>::deserialize(bytes)?;
+
+        let mut changed = Vec::new();
+        for _ in 0..deserialize_len(bytes)? {
+            let entity = Entity::deserialize(bytes)?;
+            let mut components = Vec::new();
+            for _ in 0..deserialize_len(bytes)? {
+                let (id, value) = deserialize_snapshot_value(registry, bytes)?;
+                components.push(SnapshotComponent { id, ticks, value });
+            }
+            changed.push(EntitySnapshot { entity, components });
+        }
+
+        let mut removed = Vec::new();
+        for _ in 0..deserialize_len(bytes)? {
+            let entity = Entity::deserialize(bytes)?;
+            removed.push((entity, deserialize_component_id(registry, bytes)?));
+        }
+
+        let mut resources = Vec::new();
+        for _ in 0..deserialize_len(bytes)? {
+            resources.push(deserialize_snapshot_value(registry, bytes)?);
+        }
+
+        Ok(WorldDiff {
+            spawned,
+            despawned,
+            changed,
+            removed,
+            resources,
+        })
+    }
+}
+
+impl World {
+    /// Registers `T` to be captured by [`World::snapshot`] and restored by [`World::restore`].
+    pub fn register_snapshot_component<T: Component + Clone>(&mut self) -> ComponentId {
+        let component_id = self.init_component::<T>();
+        self.get_resource_or_insert_with(SnapshotRegistry::default)
+            .components
+            .insert(component_id, SnapshotFns::of_component::<T>());
+        component_id
+    }
+
+    /// Registers the resource `R` to be captured by [`World::snapshot`] and restored by [`World::restore`].
+    pub fn register_snapshot_resource<R: Resource + Clone>(&mut self) -> ComponentId {
+        let component_id = self.components.init_resource::<R>();
+        self.get_resource_or_insert_with(SnapshotRegistry::default)
+            .resources
+            .insert(component_id, SnapshotFns::of_resource::<R>());
+        component_id
+    }
+
+    /// Like [`World::register_snapshot_component`], but also allows `T` to be written by [`WorldDiff::serialize`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u16::MAX` components and resources are registered as serializable.
+    pub fn register_serializable_snapshot_component<T: Component + Clone + BinarySerialize>(
+        &mut self,
+    ) -> ComponentId {
+        let component_id = self.init_component::<T>();
+        let mut registry = self.get_resource_or_insert_with(SnapshotRegistry::default);
+        registry.components.insert(
+            component_id,
+            SnapshotFns::of_component::<T>().with_binary::<T>(),
+        );
+        registry.register_serializable(component_id);
+        component_id
+    }
+
+    /// Like [`World::register_snapshot_resource`], but also allows `R` to be written by [`WorldDiff::serialize`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u16::MAX` components and resources are registered as serializable.
+    pub fn register_serializable_snapshot_resource<R: Resource + Clone + BinarySerialize>(
+        &mut self,
+    ) -> ComponentId {
+        let component_id = self.components.init_resource::<R>();
+        let mut registry = self.get_resource_or_insert_with(SnapshotRegistry::default);
+        registry.resources.insert(
+            component_id,
+            SnapshotFns::of_resource::<R>().with_binary::<R>(),
+        );
+        registry.register_serializable(component_id);
+        component_id
+    }
+
+    /// Captures every entity along with all of its components and resources registered in the [`SnapshotRegistry`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Component, Clone, PartialEq, Debug)]
+    /// struct Position(f32);
+    ///
+    /// let mut world = World::new();
+    /// world.register_snapshot_component::<Position>();
+    /// let entity = world.spawn(Position(0.0)).id();
+    ///
+    /// let snapshot = world.snapshot();
+    /// world.entity_mut(entity).insert(Position(10.0));
+    /// let spawned = world.spawn(Position(5.0)).id();
+    ///
+    /// world.restore(&snapshot);
+    /// assert_eq!(world.get::<Position>(entity), Some(&Position(0.0)));
+    /// assert!(world.get_entity(spawned).is_none());
+    /// ```
+    pub fn snapshot(&self) -> WorldSnapshot {
+        self.snapshot_internal(None)
+    }
+
+    /// Like [`World::snapshot`], but only captures the given components, and no resources.
+    ///
+    /// Restoring a partial snapshot leaves entities that were spawned or despawned since alone,
+    /// and only resets the filtered components on the captured entities.
+    pub fn snapshot_filtered(&self, components: &[ComponentId]) -> WorldSnapshot {
+        self.snapshot_internal(Some(components.to_vec()))
+    }
+
+    fn snapshot_internal(&self, filter: Option<Vec<ComponentId>>) -> WorldSnapshot {
+        let Some(registry) = self.get_resource::<SnapshotRegistry>() else {
+            return WorldSnapshot {
+                entities: self
+                    .iter_entities()
+                    .filter(|_| filter.is_none())
+                    .map(|entity| EntitySnapshot {
+                        entity: entity.id(),
+                        components: Vec::new(),
+                    })
+                    .collect(),
+                resources: Vec::new(),
+                components: filter.clone().unwrap_or_default(),
+                filter,
+                change_tick: self.read_change_tick(),
+            };
+        };
+
+        let is_captured = |component_id: ComponentId| {
+            registry.contains_component(component_id)
+                && filter
+                    .as_ref()
+                    .map_or(true, |filter| filter.contains(&component_id))
+        };
+
+        let mut entities = Vec::new();
+        for entity in self.iter_entities() {
+            let components: Vec<_> = entity
+                .archetype()
+                .components()
+                .filter(|&component_id| is_captured(component_id))
+                .map(|component_id| {
+                    let fns = registry.components[&component_id];
+                    // SAFETY: the component exists on the entity since it is part of its archetype.
+                    let (ptr, ticks) = unsafe {
+                        (
+                            entity.get_by_id(component_id).debug_checked_unwrap(),
+                            entity
+                                .get_change_ticks_by_id(component_id)
+                                .debug_checked_unwrap(),
+                        )
+                    };
+                    SnapshotComponent {
+                        id: component_id,
+                        ticks,
+                        // SAFETY: `ptr` points to a value of the type `fns` was registered with.
+                        value: unsafe { (fns.clone)(ptr) },
+                    }
+                })
+                .collect();
+            if filter.is_some() && components.is_empty() {
+                continue;
+            }
+            entities.push(EntitySnapshot {
+                entity: entity.id(),
+                components,
+            });
+        }
+
+        let mut resources = Vec::new();
+        if filter.is_none() {
+            for (&component_id, fns) in &registry.resources {
+                let Some(ticks) = self.get_resource_change_ticks_by_id(component_id) else {
+                    continue;
+                };
+                // SAFETY: the resource exists since it has change ticks.
+                let ptr = unsafe { self.get_resource_by_id(component_id).debug_checked_unwrap() };
+                resources.push(SnapshotComponent {
+                    id: component_id,
+                    ticks,
+                    // SAFETY: `ptr` points to a value of the type `fns` was registered with.
+                    value: unsafe { (fns.clone)(ptr) },
+                });
+            }
+        }
+
+        let components = match &filter {
+            Some(filter) => filter.clone(),
+            None => registry.components.keys().copied().collect(),
+        };
+
+        WorldSnapshot {
+            entities,
+            resources,
+            filter,
+            components,
+            change_tick: self.read_change_tick(),
+        }
+    }
+
+    /// Restores the state captured in `snapshot`.
+    ///
+    /// For a full snapshot, entities spawned after the snapshot was taken are despawned,
+    /// despawned entities are respawned with their original [`Entity`] id, and every component
+    /// that was registered when the snapshot was taken and every captured resource is reset to
+    /// its captured value along with its captured change ticks. Components registered later are
+    /// left alone.
+    ///
+    /// Restoring a full snapshot rolls back entity allocation too, so the ids of entities that
+    /// were spawned after the snapshot was taken can be handed out again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entity in a partial snapshot can no longer be respawned because its id
+    /// has been handed out again since it was despawned. The world is left untouched in that case.
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        let (component_fns, resource_fns) = self
+            .get_resource::<SnapshotRegistry>()
+            .map(|registry| (registry.components.clone(), registry.resources.clone()))
+            .unwrap_or_default();
+
+        self.flush_entities();
+        // Every generation of a captured slot that was handed out since the snapshot was taken
+        // belongs to an entity that a full restore despawns.
+        if snapshot.filter.is_some() {
+            for entity_snapshot in &snapshot.entities {
+                if !self.entities.can_respawn(entity_snapshot.entity, |_| false) {
+                    panic!(
+                        "Entity {:?} cannot be restored: its id has been reused",
+                        entity_snapshot.entity
+                    );
+                }
+            }
+        }
+        let change_tick = self.change_tick();
+
+        if snapshot.filter.is_none() {
+            let captured: bevy_utils::HashSet<Entity> = snapshot.entities().collect();
+            let stale: Vec<Entity> = self
+                .iter_entities()
+                .map(|entity| entity.id())
+                .filter(|entity| !captured.contains(entity))
+                .collect();
+            for entity in stale {
+                self.despawn(entity);
+            }
+        }
+
+        for entity_snapshot in &snapshot.entities {
+            let mut entity = self
+                .get_or_spawn(entity_snapshot.entity)
+                .expect("restored entities can be respawned");
+            for &component_id in &snapshot.components {
+                if entity.contains_id(component_id)
+                    && !entity_snapshot
+                        .components
+                        .iter()
+                        .any(|component| component.id == component_id)
+                {
+                    entity.remove_by_id(component_id);
+                }
+            }
+            for component in &entity_snapshot.components {
+                let fns = component_fns[&component.id];
+                (fns.restore_component)(&mut entity, (fns.duplicate)(&component.value));
+                // SAFETY: the component was just inserted.
+                let value = unsafe { entity.get_mut_by_id(component.id).debug_checked_unwrap() };
+                restore_ticks(value, component.ticks, change_tick);
+            }
+        }
+
+        for resource in &snapshot.resources {
+            let fns = resource_fns[&resource.id];
+            (fns.restore_resource)(self, (fns.duplicate)(&resource.value));
+            // SAFETY: the resource was just inserted.
+            let value = unsafe {
+                self.get_resource_mut_by_id(resource.id)
+                    .debug_checked_unwrap()
+            };
+            restore_ticks(value, resource.ticks, change_tick);
+        }
+    }
+
+    /// Computes the changes made to the registered components and resources since `snapshot` was taken.
+    ///
+    /// Changes are found by comparing change ticks with the captured ones, so a component that was mutably
+    /// accessed counts as changed even if its value is the same. Taking a snapshot does not advance the
+    /// change tick: a value that was already changed during the tick the snapshot was taken in is only seen
+    /// as changed again once the tick has advanced, for example with [`World::increment_change_tick`].
+    /// For a partial snapshot, only the filtered components are compared, and spawned and despawned
+    /// entities are not tracked.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Component, Clone, PartialEq, Debug)]
+    /// struct Position(f32);
+    ///
+    /// let mut world = World::new();
+    /// world.register_snapshot_component::<Position>();
+    /// let entity = world.spawn(Position(0.0)).id();
+    ///
+    /// let snapshot = world.snapshot();
+    /// world.increment_change_tick();
+    /// world.entity_mut(entity).insert(Position(10.0));
+    /// let spawned = world.spawn(Position(5.0)).id();
+    /// let diff = world.diff(&snapshot);
+    /// assert_eq!(diff.spawned(), &[spawned]);
+    ///
+    /// // Undo, then redo the changes.
+    /// world.restore(&snapshot);
+    /// world.apply_diff(&diff);
+    /// assert_eq!(world.get::<Position>(entity), Some(&Position(10.0)));
+    /// assert_eq!(world.get::<Position>(spawned), Some(&Position(5.0)));
+    /// ```
+    pub fn diff(&self, snapshot: &WorldSnapshot) -> WorldDiff {
+        let mut diff = WorldDiff {
+            spawned: Vec::new(),
+            despawned: Vec::new(),
+            changed: Vec::new(),
+            removed: Vec::new(),
+            resources: Vec::new(),
+        };
+        let Some(registry) = self.get_resource::<SnapshotRegistry>() else {
+            return diff;
+        };
+        let is_full = snapshot.filter.is_none();
+        let is_unchanged = |ticks: ComponentTicks, captured: Option<&SnapshotComponent>| {
+            captured.is_some_and(|captured| {
+                captured.ticks.added == ticks.added && captured.ticks.changed == ticks.changed
+            })
+        };
+        let is_captured = |component_id: ComponentId| {
+            registry.contains_component(component_id)
+                && snapshot
+                    .filter
+                    .as_ref()
+                    .map_or(true, |filter| filter.contains(&component_id))
+        };
+
+        let captured: HashMap<Entity, &EntitySnapshot> = snapshot
+            .entities
+            .iter()
+            .map(|snapshot| (snapshot.entity, snapshot))
+            .collect();
+
+        for entity in self.iter_entities() {
+            let previous = captured.get(&entity.id());
+            if is_full && previous.is_none() {
+                diff.spawned.push(entity.id());
+            }
+
+            let mut changed = Vec::new();
+            for component_id in entity.archetype().components() {
+                if !is_captured(component_id) {
+                    continue;
+                }
+                // SAFETY: the component exists on the entity since it is part of its archetype.
+                let ticks = unsafe {
+                    entity
+                        .get_change_ticks_by_id(component_id)
+                        .debug_checked_unwrap()
+                };
+                let captured = previous.and_then(|previous| {
+                    previous
+                        .components
+                        .iter()
+                        .find(|component| component.id == component_id)
+                });
+                if is_unchanged(ticks, captured) {
+                    continue;
+                }
+                let fns = registry.components[&component_id];
+                // SAFETY: the component exists on the entity since it is part of its archetype.
+                let ptr = unsafe { entity.get_by_id(component_id).debug_checked_unwrap() };
+                changed.push(SnapshotComponent {
+                    id: component_id,
+                    ticks,
+                    // SAFETY: `ptr` points to a value of the type `fns` was registered with.
+                    value: unsafe { (fns.clone)(ptr) },
+                });
+            }
+            if !changed.is_empty() {
+                diff.changed.push(EntitySnapshot {
+                    entity: entity.id(),
+                    components: changed,
+                });
+            }
+
+            if let Some(previous) = previous {
+                for component in &previous.components {
+                    if !entity.contains_id(component.id) {
+                        diff.removed.push((entity.id(), component.id));
+                    }
+                }
+            }
+        }
+
+        if is_full {
+            diff.despawned = snapshot
+                .entities()
+                .filter(|&entity| self.get_entity(entity).is_none())
+                .collect();
+
+            for (&component_id, fns) in &registry.resources {
+                let captured = snapshot
+                    .resources
+                    .iter()
+                    .find(|resource| resource.id == component_id);
+                let is_changed = self
+                    .get_resource_change_ticks_by_id(component_id)
+                    .is_some_and(|ticks| !is_unchanged(ticks, captured));
+                if is_changed {
+                    // SAFETY: the resource exists since it has change ticks.
+                    let ptr =
+                        unsafe { self.get_resource_by_id(component_id).debug_checked_unwrap() };
+                    // SAFETY: `ptr` points to a value of the type `fns` was registered with.
+                    diff.resources
+                        .push((component_id, unsafe { (fns.clone)(ptr) }));
+                }
+            }
+        }
+
+        diff
+    }
+
+    /// Applies the changes recorded in `diff`, spawning, despawning and updating entities
+    /// with their original [`Entity`] ids.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entity of the diff cannot be spawned because its id has been reused
+    /// with a different generation, in which case the world is left untouched, or if a
+    /// component or resource of the diff is not registered in this world's [`SnapshotRegistry`].
+    pub fn apply_diff(&mut self, diff: &WorldDiff) {
+        let (component_fns, resource_fns) = self
+            .get_resource::<SnapshotRegistry>()
+            .map(|registry| (registry.components.clone(), registry.resources.clone()))
+            .unwrap_or_default();
+
+        self.flush_entities();
+        let respawned = diff
+            .changed
+            .iter()
+            .map(|entity_diff| entity_diff.entity)
+            .chain(diff.spawned.iter().copied());
+        for entity in respawned {
+            if !self
+                .entities
+                .can_respawn(entity, |current| diff.despawned.contains(&current))
+            {
+                panic!("Entity {entity:?} cannot be spawned: its id has been reused");
+            }
+        }
+
+        for &entity in &diff.despawned {
+            self.despawn(entity);
+        }
+
+        for &(entity, component_id) in &diff.removed {
+            if let Some(mut entity) = self.get_entity_mut(entity) {
+                entity.remove_by_id(component_id);
+            }
+        }
+
+        for entity_diff in &diff.changed {
+            let mut entity = self
+                .get_or_spawn(entity_diff.entity)
+                .expect("updated entities were checked with `can_respawn`");
+            for component in &entity_diff.components {
+                let fns = component_fns[&component.id];
+                (fns.restore_component)(&mut entity, (fns.duplicate)(&component.value));
+            }
+        }
+
+        for &entity in &diff.spawned {
+            self.get_or_spawn(entity)
+                .expect("spawned entities were checked with `can_respawn`");
+        }
+
+        for (component_id, value) in &diff.resources {
+            let fns = resource_fns[component_id];
+            (fns.restore_resource)(self, (fns.duplicate)(value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WorldDiff;
+    use crate as bevy_ecs;
+    use crate::binary::{BinaryError, BinarySerialize};
+    use crate::prelude::*;
+
+    #[derive(Component, BinarySerialize, Clone, PartialEq, Debug)]
+    struct A(usize);
+
+    #[derive(Component, BinarySerialize, Clone, PartialEq, Debug)]
+    struct B(usize);
+
+    #[derive(Component)]
+    struct NotRegistered;
+
+    #[derive(Resource, BinarySerialize, Clone, PartialEq, Debug)]
+    struct Score(usize);
+
+    #[test]
+    fn restore_full_snapshot() {
+        let mut world = World::new();
+        world.register_snapshot_component::<A>();
+        world.register_snapshot_component::<B>();
+        world.register_snapshot_resource::<Score>();
+        world.insert_resource(Score(0));
+
+        let e1 = world.spawn((A(1), B(1))).id();
+        let e2 = world.spawn(A(2)).id();
+        let snapshot = world.snapshot();
+
+        world.entity_mut(e1).insert(A(10)).remove::<B>();
+        world.entity_mut(e2).insert(B(20));
+        world.despawn(e2);
+        let e3 = world.spawn(A(3)).id();
+        world.resource_mut::<Score>().0 = 5;
+
+        world.restore(&snapshot);
+
+        assert_eq!(world.get::<A>(e1), Some(&A(1)));
+        assert_eq!(world.get::<B>(e1), Some(&B(1)));
+        assert_eq!(world.get::<A>(e2), Some(&A(2)));
+        assert_eq!(world.get::<B>(e2), None);
+        assert!(world.get_entity(e3).is_none());
+        assert_eq!(world.resource::<Score>(), &Score(0));
+
+        // Snapshots can be restored more than once.
+        world.entity_mut(e1).insert(A(100));
+        world.restore(&snapshot);
+        assert_eq!(world.get::<A>(e1), Some(&A(1)));
+    }
+
+    #[test]
+    fn restore_keeps_change_ticks() {
+        let mut world = World::new();
+        world.register_snapshot_component::<A>();
+        let entity = world.spawn(A(0)).id();
+        let ticks = world.entity(entity).get_change_ticks::<A>().unwrap();
+        let snapshot = world.snapshot();
+
+        world.increment_change_tick();
+        world.entity_mut(entity).insert(A(1));
+        world.restore(&snapshot);
+
+        let restored = world.entity(entity).get_change_ticks::<A>().unwrap();
+        assert_eq!(restored.added, ticks.added);
+        assert_eq!(restored.changed, ticks.changed);
+    }
+
+    #[test]
+    #[should_panic]
+    fn restore_rejects_reused_entities() {
+        let mut world = World::new();
+        let a_id = world.register_snapshot_component::<A>();
+        let entity = world.spawn(A(0)).id();
+        let snapshot = world.snapshot_filtered(&[a_id]);
+
+        world.despawn(entity);
+        let reused = world.spawn_empty().id();
+        assert_eq!(reused.index(), entity.index());
+        world.despawn(reused);
+
+        world.restore(&snapshot);
+    }
+
+    #[test]
+    fn unregistered_components_are_untouched() {
+        let mut world = World::new();
+        world.register_snapshot_component::<A>();
+        let entity = world.spawn(A(0)).id();
+        let snapshot = world.snapshot();
+
+        world.entity_mut(entity).insert(NotRegistered);
+        world.restore(&snapshot);
+
+        assert!(world.entity(entity).contains::<NotRegistered>());
+    }
+
+    #[test]
+    fn components_registered_after_the_snapshot_are_untouched() {
+        let mut world = World::new();
+        world.register_snapshot_component::<A>();
+        let entity = world.spawn(A(0)).id();
+        let snapshot = world.snapshot();
+
+        world.register_snapshot_component::<B>();
+        world.entity_mut(entity).insert((A(1), B(1)));
+        world.restore(&snapshot);
+
+        assert_eq!(world.get::<A>(entity), Some(&A(0)));
+        assert_eq!(world.get::<B>(entity), Some(&B(1)));
+    }
+
+    #[test]
+    fn restore_partial_snapshot() {
+        let mut world = World::new();
+        let a_id = world.register_snapshot_component::<A>();
+        world.register_snapshot_component::<B>();
+        let entity = world.spawn((A(0), B(0))).id();
+
+        let snapshot = world.snapshot_filtered(&[a_id]);
+        assert!(snapshot.is_partial());
+
+        world.entity_mut(entity).insert((A(1), B(1)));
+        let spawned = world.spawn(B(2)).id();
+        world.restore(&snapshot);
+
+        assert_eq!(world.get::<A>(entity), Some(&A(0)));
+        assert_eq!(world.get::<B>(entity), Some(&B(1)));
+        assert!(world.get_entity(spawned).is_some());
+    }
+
+    #[test]
+    fn diff_and_apply() {
+        let mut world = World::new();
+        let a_id = world.register_snapshot_component::<A>();
+        let b_id = world.register_snapshot_component::<B>();
+        world.register_snapshot_resource::<Score>();
+        world.insert_resource(Score(0));
+
+        let unchanged = world.spawn(A(0)).id();
+        let changed = world.spawn((A(1), B(1))).id();
+        let despawned = world.spawn(A(2)).id();
+        let snapshot = world.snapshot();
+        assert!(world.diff(&snapshot).is_empty());
+
+        world.increment_change_tick();
+        world.get_mut::<A>(changed).unwrap().0 = 10;
+        world.entity_mut(changed).remove::<B>();
+        world.despawn(despawned);
+        let spawned = world.spawn((A(3), NotRegistered)).id();
+        world.resource_mut::<Score>().0 = 5;
+
+        let diff = world.diff(&snapshot);
+        assert_eq!(diff.spawned(), &[spawned]);
+        assert_eq!(diff.despawned(), &[despawned]);
+        let mut changed_components = diff.changed_components().collect::<Vec<_>>();
+        changed_components.sort();
+        assert_eq!(changed_components, vec![(changed, a_id), (spawned, a_id)]);
+        assert_eq!(diff.removed_components(), &[(changed, b_id)]);
+        assert_eq!(diff.changed_resources().count(), 1);
+
+        world.restore(&snapshot);
+        assert_eq!(world.get::<A>(changed), Some(&A(1)));
+        world.apply_diff(&diff);
+
+        assert_eq!(world.get::<A>(unchanged), Some(&A(0)));
+        assert_eq!(world.get::<A>(changed), Some(&A(10)));
+        assert_eq!(world.get::<B>(changed), None);
+        assert!(world.get_entity(despawned).is_none());
+        assert_eq!(world.get::<A>(spawned), Some(&A(3)));
+        assert_eq!(world.resource::<Score>(), &Score(5));
+    }
+
+    #[test]
+    fn snapshot_does_not_advance_change_tick() {
+        let mut world = World::new();
+        world.register_snapshot_component::<A>();
+        world.spawn(A(0));
+
+        let change_tick = world.read_change_tick();
+        let snapshot = world.snapshot();
+        assert_eq!(snapshot.change_tick(), change_tick);
+        assert_eq!(world.read_change_tick(), change_tick);
+        world.snapshot_filtered(&[]);
+        assert_eq!(world.read_change_tick(), change_tick);
+    }
+
+    #[test]
+    fn apply_diff_rejects_reused_entities() {
+        let mut world = World::new();
+        world.register_snapshot_component::<A>();
+        let kept = world.spawn(A(0)).id();
+        let snapshot = world.snapshot();
+        world.increment_change_tick();
+        world.get_mut::<A>(kept).unwrap().0 = 1;
+        let spawned = world.spawn(A(1)).id();
+        let diff = world.diff(&snapshot);
+
+        world.restore(&snapshot);
+        let reused = world.spawn_empty().id();
+        assert_eq!(reused.index(), spawned.index());
+        world.despawn(reused);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            world.apply_diff(&diff);
+        }));
+        assert!(result.is_err());
+        assert_eq!(world.get::<A>(kept), Some(&A(0)));
+    }
+
+    #[test]
+    fn serialized_diff_round_trip() {
+        fn setup() -> World {
+            let mut world = World::new();
+            world.register_serializable_snapshot_component::<A>();
+            world.register_serializable_snapshot_component::<B>();
+            world.register_serializable_snapshot_resource::<Score>();
+            world.insert_resource(Score(0));
+            world.spawn((A(0), B(0)));
+            world.spawn(A(1));
+            world
+        }
+
+        let mut source = setup();
+        let mut target = setup();
+        let entities: Vec<_> = source.iter_entities().map(|entity| entity.id()).collect();
+        let snapshot = source.snapshot();
+
+        source.increment_change_tick();
+        source.get_mut::<A>(entities[0]).unwrap().0 = 10;
+        source.entity_mut(entities[0]).remove::<B>();
+        source.despawn(entities[1]);
+        let spawned = source.spawn((A(2), B(2))).id();
+        source.resource_mut::<Score>().0 = 5;
+
+        let mut bytes = Vec::new();
+        source.diff(&snapshot).serialize(&source, &mut bytes);
+        let diff = WorldDiff::deserialize(&target, &bytes).unwrap();
+        assert_eq!(diff.spawned(), &[spawned]);
+        target.apply_diff(&diff);
+
+        assert_eq!(target.get::<A>(entities[0]), Some(&A(10)));
+        assert_eq!(target.get::<B>(entities[0]), None);
+        assert!(target.get_entity(entities[1]).is_none());
+        assert_eq!(target.get::<A>(spawned), Some(&A(2)));
+        assert_eq!(target.get::<B>(spawned), Some(&B(2)));
+        assert_eq!(target.resource::<Score>(), &Score(5));
+
+        let unregistered = World::new();
+        assert!(matches!(
+            WorldDiff::deserialize(&unregistered, &bytes),
+            Err(BinaryError::UnknownComponent(_))
+        ));
+    }
+}
@@ -3,6 +3,7 @@ use crate::{
     entity::Entity,
     world::World,
 };
+use bevy_utils::all_tuples;
 use std::iter::FusedIterator;
 
 /// An iterator that spawns a series of entities and returns the [ID](Entity) of
@@ -93,3 +94,29 @@ where
     T: Bundle,
 {
 }
+
+/// A tuple of [`Bundle`] iterators, each of which may yield a different [`Bundle`] type,
+/// that can be spawned at once with [`World::spawn_batches`].
+///
+/// Implemented for tuples of up to 15 [`IntoIterator`]s whose items are bundles.
+pub trait BundleBatches {
+    /// Spawns the bundles of each iterator in turn and pushes the spawned entities to `entities`.
+    fn spawn_batches(self, world: &mut World, entities: &mut Vec<Entity>);
+}
+
+macro_rules! impl_bundle_batches {
+    ($($name: ident),*) => {
+        impl<$($name: IntoIterator),*> BundleBatches for ($($name,)*)
+        where
+            $($name::Item: Bundle,)*
+        {
+            #[allow(non_snake_case)]
+            fn spawn_batches(self, world: &mut World, entities: &mut Vec<Entity>) {
+                let ($($name,)*) = self;
+                $(entities.extend(world.spawn_batch($name));)*
+            }
+        }
+    };
+}
+
+all_tuples!(impl_bundle_batches, 1, 15, B);
@@ -0,0 +1,76 @@
+use std::{
+    any::{Any, TypeId},
+    panic::Location,
+};
+
+use bevy_utils::HashMap;
+
+use crate as bevy_ecs;
+use crate::system::Resource;
+
+/// The [`SystemState`](crate::system::SystemState)s cached in a [`World`](crate::world::World)
+/// by [`World::system_state_scope`](crate::world::World::system_state_scope).
+///
+/// Each state is keyed by the code location of the call that uses it and by its parameter type,
+/// so that every call site gets its own state.
+#[derive(Resource, Default)]
+pub(crate) struct CachedSystemStates(
+    pub(crate) HashMap<(&'static Location<'static>, TypeId), Box<dyn Any + Send + Sync>>,
+);
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+
+    #[derive(Component)]
+    struct A;
+
+    fn added(world: &mut World) -> usize {
+        world.system_state_scope::<Query<(), Added<A>>, _>(|world, state| {
+            state.get(world).iter().count()
+        })
+    }
+
+    fn also_added(world: &mut World) -> usize {
+        world.system_state_scope::<Query<(), Added<A>>, _>(|world, state| {
+            state.get(world).iter().count()
+        })
+    }
+
+    #[test]
+    fn system_state_scope_caches_state() {
+        let mut world = World::new();
+        world.spawn(A);
+        assert_eq!(added(&mut world), 1);
+        assert_eq!(added(&mut world), 0);
+        world.spawn(A);
+        assert_eq!(added(&mut world), 1);
+    }
+
+    #[test]
+    fn system_state_scope_caches_state_per_call_site() {
+        let mut world = World::new();
+        world.spawn(A);
+        assert_eq!(added(&mut world), 1);
+        // The same parameters used by another system have their own state.
+        assert_eq!(also_added(&mut world), 1);
+        assert_eq!(also_added(&mut world), 0);
+        assert_eq!(added(&mut world), 0);
+    }
+
+    #[test]
+    fn system_state_scope_shares_state_between_callers_of_a_helper() {
+        let mut world = World::new();
+        let mut first = IntoSystem::into_system(|world: &mut World| added(world));
+        let mut second = IntoSystem::into_system(|world: &mut World| added(world));
+        first.initialize(&mut world);
+        second.initialize(&mut world);
+
+        world.spawn(A);
+        assert_eq!(first.run((), &mut world), 1);
+        // Both systems call `system_state_scope` from the same location in `added`,
+        // so the entity was already seen through the shared state.
+        assert_eq!(second.run((), &mut world), 0);
+    }
+}
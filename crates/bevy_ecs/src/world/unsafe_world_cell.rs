@@ -10,6 +10,7 @@ use crate::{
     component::{ComponentId, ComponentTicks, Components, StorageType, Tick, TickCells},
     entity::{Entities, Entity, EntityLocation},
     prelude::Component,
+    query::{DebugCheckedUnwrap, QueryData},
     removal_detection::RemovedComponentEvents,
     storage::{Column, ComponentSparseSet, Storages},
     system::{Res, Resource},
@@ -814,7 +815,8 @@ impl<'w> UnsafeEntityCell<'w> {
             .map(|(value, cells)| Mut {
                 // SAFETY: returned component is of type T
                 value: value.assert_unique().deref_mut::<T>(),
-                ticks: TicksMut::from_tick_cells(cells, last_change_tick, change_tick),
+                ticks: TicksMut::from_tick_cells(cells, last_change_tick, change_tick)
+                    .with_change_detection(T::CHANGE_DETECTION),
             })
         }
     }
@@ -878,10 +880,47 @@ impl<'w> UnsafeEntityCell<'w> {
                     cells,
                     self.world.last_change_tick(),
                     self.world.change_tick(),
-                ),
+                )
+                .with_change_detection(info.change_detection()),
             })
         }
     }
+
+    /// Fetches the [`QueryData`] `Q` for the entity, or returns `None` if the entity does not match it.
+    ///
+    /// # Safety
+    /// It is the callers responsibility to ensure that
+    /// - the [`UnsafeEntityCell`] has permission to access the components accessed by `Q` mutably
+    /// - no other references to these components exist at the same time
+    /// - `Q` does not access any component mutably more than once
+    pub(crate) unsafe fn get_components<Q: QueryData>(self) -> Option<Q::Item<'w>> {
+        // SAFETY: only metadata is read.
+        let state = Q::get_state(unsafe { self.world.world_metadata() })?;
+        let archetype = self.archetype();
+        if !Q::matches_component_set(&state, &|id| archetype.contains(id)) {
+            return None;
+        }
+        // SAFETY:
+        // - `state` was created from this world
+        // - access is ensured by the caller
+        // - `table` is the table of `archetype` and `table_row` is the row of the entity in it
+        unsafe {
+            let table = self
+                .world
+                .storages()
+                .tables
+                .get(self.location.table_id)
+                .debug_checked_unwrap();
+            let mut fetch = Q::init_fetch(
+                self.world,
+                &state,
+                self.world.last_change_tick(),
+                self.world.change_tick(),
+            );
+            Q::set_archetype(&mut fetch, &state, archetype, table);
+            Some(Q::fetch(&mut fetch, self.entity, self.location.table_row))
+        }
+    }
 }
 
 impl<'w> UnsafeWorldCell<'w> {
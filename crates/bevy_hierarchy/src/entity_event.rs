@@ -0,0 +1,152 @@
+use bevy_ecs::prelude::*;
+
+use crate::Parent;
+
+/// An [`Event`] addressed to a specific [`Entity`], which bubbles up its [`Parent`] chain
+/// until it is handled by a [`Listener`].
+///
+/// Events are only propagated once [`propagate_entity_events`] has been added for their type.
+pub trait EntityEvent: Event {
+    /// The entity the event is addressed to, and the first one it is delivered to.
+    fn target(&self) -> Entity;
+}
+
+/// Whether an [`EntityEvent`] continues to bubble up to the parent of the entity that handled it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Propagation {
+    /// Deliver the event to the parent as well.
+    Continue,
+    /// The event has been handled and is not delivered to any other entity.
+    Stop,
+}
+
+/// A component that handles the [`EntityEvent`]s of type `E` that reach its entity.
+///
+/// The handler is called with the entity the listener is on and the event,
+/// and returns whether the event should keep bubbling up the hierarchy.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_hierarchy::{BuildWorldChildren, EntityEvent, Listener, Propagation, propagate_entity_events};
+/// #[derive(Event)]
+/// struct Clicked(Entity);
+///
+/// impl EntityEvent for Clicked {
+///     fn target(&self) -> Entity {
+///         self.0
+///     }
+/// }
+///
+/// #[derive(Component)]
+/// struct Pressed;
+///
+/// let mut world = World::new();
+/// world.init_resource::<Events<Clicked>>();
+/// let panel = world
+///     .spawn(Listener::new(|commands, entity, _: &Clicked| {
+///         commands.entity(entity).insert(Pressed);
+///         Propagation::Stop
+///     }))
+///     .id();
+/// let button = world.spawn_empty().set_parent(panel).id();
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(propagate_entity_events::<Clicked>);
+/// world.send_event(Clicked(button));
+/// schedule.run(&mut world);
+/// assert!(world.entity(panel).contains::<Pressed>());
+/// ```
+#[derive(Component)]
+pub struct Listener<E: EntityEvent> {
+    handler: Box<dyn Fn(&mut Commands, Entity, &E) -> Propagation + Send + Sync>,
+}
+
+impl<E: EntityEvent> Listener<E> {
+    /// Creates a listener that calls `handler` for every event of type `E` that reaches its entity.
+    pub fn new(
+        handler: impl Fn(&mut Commands, Entity, &E) -> Propagation + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            handler: Box::new(handler),
+        }
+    }
+}
+
+/// Delivers each [`EntityEvent`] of type `E` to the [`Listener`]s on its target and the target's ancestors,
+/// starting with the target, until one of them returns [`Propagation::Stop`].
+pub fn propagate_entity_events<E: EntityEvent>(
+    mut events: EventReader<E>,
+    listeners: Query<&Listener<E>>,
+    parents: Query<&Parent>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        let mut current = Some(event.target());
+        while let Some(entity) = current {
+            if let Ok(listener) = listeners.get(entity) {
+                if (listener.handler)(&mut commands, entity, event) == Propagation::Stop {
+                    break;
+                }
+            }
+            current = parents.get(entity).ok().map(Parent::get);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::prelude::*;
+
+    use super::*;
+    use crate::BuildWorldChildren;
+
+    #[derive(Event)]
+    struct Damage(Entity);
+
+    impl EntityEvent for Damage {
+        fn target(&self) -> Entity {
+            self.0
+        }
+    }
+
+    #[derive(Component, Default)]
+    struct Hits(u32);
+
+    fn count_hits(propagation: Propagation) -> (Listener<Damage>, Hits) {
+        let listener = Listener::new(move |commands, entity, _: &Damage| {
+            commands.add(move |world: &mut World| {
+                world.get_mut::<Hits>(entity).unwrap().0 += 1;
+            });
+            propagation
+        });
+        (listener, Hits::default())
+    }
+
+    #[test]
+    fn events_bubble_until_stopped() {
+        let mut world = World::new();
+        world.init_resource::<Events<Damage>>();
+        let root = world.spawn(count_hits(Propagation::Continue)).id();
+        let middle = world
+            .spawn(count_hits(Propagation::Stop))
+            .set_parent(root)
+            .id();
+        let child = world.spawn(Hits::default()).set_parent(middle).id();
+        let other = world
+            .spawn(count_hits(Propagation::Continue))
+            .set_parent(root)
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(propagate_entity_events::<Damage>);
+        world.send_event(Damage(child));
+        world.send_event(Damage(other));
+        schedule.run(&mut world);
+
+        let hits = |entity| world.get::<Hits>(entity).unwrap().0;
+        assert_eq!(hits(child), 0);
+        assert_eq!(hits(middle), 1);
+        assert_eq!(hits(other), 1);
+        assert_eq!(hits(root), 1);
+    }
+}
@@ -22,6 +22,7 @@
 //! More advanced users may also appreciate
 //! [query extension methods] to traverse hierarchies,
 //! and [events] to notify hierarchical changes.
+//! [Entity events] can be addressed to an entity and bubble up to its ancestors.
 //! There is also a [diagnostic plugin] to validate property propagation.
 //!
 //! # Hierarchy management
@@ -46,6 +47,7 @@
 //!
 //! [command]: BuildChildren
 //! [diagnostic plugin]: ValidParentCheckPlugin
+//! [Entity events]: EntityEvent
 //! [events]: HierarchyEvent
 //! [hierarchical despawn extension methods]: DespawnRecursiveExt
 //! [plugin]: HierarchyPlugin
@@ -64,6 +66,9 @@ pub use child_builder::*;
 mod events;
 pub use events::*;
 
+mod entity_event;
+pub use entity_event::*;
+
 mod valid_parent_check_plugin;
 pub use valid_parent_check_plugin::*;
 
@@ -82,6 +87,8 @@ pub mod prelude {
 
 #[cfg(feature = "bevy_app")]
 use bevy_app::prelude::*;
+#[cfg(feature = "bevy_app")]
+use bevy_ecs::world::{ComponentCloneContext, ComponentCloneHandler, World};
 
 /// Provides hierarchy functionality to a Bevy app.
 ///
@@ -98,5 +105,19 @@ impl Plugin for HierarchyPlugin {
         app.register_type::<Children>()
             .register_type::<Parent>()
             .add_event::<HierarchyEvent>();
+
+        // Clones join their source's parent, but never take its children with them.
+        let world = app.world_mut();
+        let parent = world.init_component::<Parent>();
+        let children = world.init_component::<Children>();
+        world.set_component_clone_handler(parent, ComponentCloneHandler::Custom(clone_parent));
+        world.set_component_clone_handler(children, ComponentCloneHandler::Ignore);
+    }
+}
+
+#[cfg(feature = "bevy_app")]
+fn clone_parent(world: &mut World, context: &ComponentCloneContext) {
+    if let Some(parent) = world.get::<Parent>(context.source()).map(Parent::get) {
+        world.entity_mut(parent).add_child(context.target());
     }
 }
@@ -6,6 +6,7 @@ use bevy_app::{Plugin, PluginGroup, PluginGroupBuilder};
 /// * [`TaskPoolPlugin`](crate::core::TaskPoolPlugin)
 /// * [`TypeRegistrationPlugin`](crate::core::TypeRegistrationPlugin)
 /// * [`FrameCountPlugin`](crate::core::FrameCountPlugin)
+/// * [`PersistentIdPlugin`](crate::core::PersistentIdPlugin)
 /// * [`TimePlugin`](crate::time::TimePlugin)
 /// * [`TransformPlugin`](crate::transform::TransformPlugin)
 /// * [`HierarchyPlugin`](crate::hierarchy::HierarchyPlugin)
@@ -48,6 +49,7 @@ impl PluginGroup for DefaultPlugins {
             .add(bevy_core::TaskPoolPlugin::default())
             .add(bevy_core::TypeRegistrationPlugin)
             .add(bevy_core::FrameCountPlugin)
+            .add(bevy_core::PersistentIdPlugin)
             .add(bevy_time::TimePlugin)
             .add(bevy_transform::TransformPlugin)
             .add(bevy_hierarchy::HierarchyPlugin)
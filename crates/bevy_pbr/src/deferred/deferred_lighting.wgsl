@@ -9,7 +9,7 @@
 }
 
 #ifdef SCREEN_SPACE_AMBIENT_OCCLUSION
-#import bevy_pbr::mesh_view_bindings::screen_space_ambient_occlusion_texture
+#import bevy_pbr::mesh_view_bindings::{screen_space_ambient_occlusion_texture, view}
 #import bevy_pbr::gtao_utils::gtao_multibounce
 #endif
 
@@ -63,7 +63,9 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
     if ((pbr_input.material.flags & STANDARD_MATERIAL_FLAGS_UNLIT_BIT) == 0u) {
 
 #ifdef SCREEN_SPACE_AMBIENT_OCCLUSION
-        let ssao = textureLoad(screen_space_ambient_occlusion_texture, vec2<i32>(in.position.xy), 0i).r;
+        // The ambient occlusion texture may be at a lower resolution than the viewport
+        let ssao_scale = vec2<f32>(textureDimensions(screen_space_ambient_occlusion_texture)) / view.viewport.zw;
+        let ssao = textureLoad(screen_space_ambient_occlusion_texture, vec2<i32>(in.position.xy * ssao_scale), 0i).r;
         let ssao_multibounce = gtao_multibounce(ssao, pbr_input.material.base_color.rgb);
         pbr_input.diffuse_occlusion = min(pbr_input.diffuse_occlusion, ssao_multibounce);
 
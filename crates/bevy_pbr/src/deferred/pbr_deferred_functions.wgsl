@@ -52,6 +52,13 @@ fn deferred_gbuffer_from_pbr_input(in: PbrInput) -> vec4<u32> {
         emissive = in.material.base_color.rgb;
     } else {
         base_color_srgb = pow(in.material.base_color.rgb, vec3(1.0 / 2.2));
+#ifdef LIGHTMAP
+        // The G-buffer has no room for the lightmap light, so fold it into the
+        // emissive light. The lighting pass skips other diffuse indirect light
+        // for lightmapped meshes.
+        let diffuse_color = in.material.base_color.rgb * (1.0 - in.material.metallic);
+        emissive += in.lightmap_light * diffuse_color;
+#endif
     }
     let deferred = vec4(
         deferred_types::pack_unorm4x8_(vec4(base_color_srgb, in.material.perceptual_roughness)),
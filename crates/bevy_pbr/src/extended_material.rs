@@ -41,6 +41,14 @@ pub trait MaterialExtension: Asset + AsBindGroup + Clone + Sized {
         ShaderRef::Default
     }
 
+    /// Returns whether this extension's fragment shader writes the outputs of weighted blended
+    /// order-independent transparency. See [`Material::supports_order_independent_transparency`].
+    ///
+    /// This is only used if the extension has its own fragment shader.
+    fn supports_order_independent_transparency() -> bool {
+        false
+    }
+
     /// Returns this material's prepass vertex shader. If [`ShaderRef::Default`] is returned, the base material prepass vertex shader
     /// will be used.
     fn prepass_vertex_shader() -> ShaderRef {
@@ -191,6 +199,13 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
         }
     }
 
+    fn supports_order_independent_transparency() -> bool {
+        match E::fragment_shader() {
+            ShaderRef::Default => B::supports_order_independent_transparency(),
+            _ => E::supports_order_independent_transparency(),
+        }
+    }
+
     fn alpha_mode(&self) -> crate::AlphaMode {
         B::alpha_mode(&self.base)
     }
@@ -269,6 +284,7 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
         let MaterialPipeline::<Self> {
             mesh_pipeline,
             material_layout,
+            bindless_layout,
             vertex_shader,
             fragment_shader,
             ..
@@ -276,6 +292,7 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
         let base_pipeline = MaterialPipeline::<B> {
             mesh_pipeline,
             material_layout,
+            bindless_layout,
             vertex_shader,
             fragment_shader,
             marker: Default::default(),
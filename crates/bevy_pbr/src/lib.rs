@@ -34,6 +34,8 @@ mod pbr_material;
 mod prepass;
 mod render;
 mod ssao;
+mod ssgi;
+mod volumetric_fog;
 
 use bevy_color::{Color, LinearRgba};
 pub use bundle::*;
@@ -48,6 +50,8 @@ pub use pbr_material::*;
 pub use prepass::*;
 pub use render::*;
 pub use ssao::*;
+pub use ssgi::*;
+pub use volumetric_fog::*;
 
 pub mod prelude {
     #[doc(hidden)]
@@ -66,6 +70,8 @@ pub mod prelude {
         parallax::ParallaxMappingMethod,
         pbr_material::StandardMaterial,
         ssao::ScreenSpaceAmbientOcclusionPlugin,
+        ssgi::ScreenSpaceGlobalIlluminationPlugin,
+        volumetric_fog::{FogVolume, FogVolumeBundle, VolumetricFogSettings},
     };
 }
 
@@ -78,7 +84,14 @@ pub mod graph {
         ShadowPass,
         /// Label for the screen space ambient occlusion render node.
         ScreenSpaceAmbientOcclusion,
+        /// Label for the screen space global illumination render node.
+        ScreenSpaceGlobalIllumination,
+        /// Label for the node that copies the main pass color into the screen
+        /// space global illumination history.
+        ScreenSpaceGlobalIlluminationHistory,
         DeferredLightingPass,
+        /// Label for the volumetric fog render node.
+        VolumetricFog,
         /// Label for the compute shader instance data building pass.
         GpuPreprocess,
     }
@@ -94,6 +107,7 @@ use bevy_render::{
     camera::{CameraUpdateSystem, Projection},
     extract_component::ExtractComponentPlugin,
     extract_resource::ExtractResourcePlugin,
+    mesh::GpuMesh,
     render_asset::prepare_assets,
     render_graph::RenderGraph,
     render_resource::Shader,
@@ -275,6 +289,7 @@ impl Plugin for PbrPlugin {
             .register_type::<DirectionalLight>()
             .register_type::<DirectionalLightShadowMap>()
             .register_type::<NotShadowCaster>()
+            .register_type::<StaticShadowCaster>()
             .register_type::<NotShadowReceiver>()
             .register_type::<PointLight>()
             .register_type::<PointLightShadowMap>()
@@ -296,6 +311,8 @@ impl Plugin for PbrPlugin {
                     ..Default::default()
                 },
                 ScreenSpaceAmbientOcclusionPlugin,
+                ScreenSpaceGlobalIlluminationPlugin,
+                VolumetricFogPlugin,
                 ExtractResourcePlugin::<AmbientLight>::default(),
                 FogPlugin,
                 ExtractResourcePlugin::<DefaultOpaqueRendererMethod>::default(),
@@ -390,17 +407,30 @@ impl Plugin for PbrPlugin {
 
         // Extract the required data from the main world
         render_app
-            .add_systems(ExtractSchedule, (extract_clusters, extract_lights))
+            .add_systems(
+                ExtractSchedule,
+                (
+                    extract_clusters,
+                    extract_lights,
+                    extract_changed_static_shadow_casters,
+                ),
+            )
             .add_systems(
                 Render,
                 (
+                    collect_changed_static_shadow_caster_meshes
+                        .in_set(RenderSet::PrepareAssets)
+                        .before(prepare_assets::<GpuMesh>),
                     prepare_lights
                         .in_set(RenderSet::ManageViews)
                         .after(prepare_assets::<GpuImage>),
+                    commit_shadow_map_layers.in_set(RenderSet::PhaseSort),
                     prepare_clusters.in_set(RenderSet::PrepareResources),
                 ),
             )
-            .init_resource::<LightMeta>();
+            .init_resource::<LightMeta>()
+            .init_resource::<ChangedStaticShadowCasters>()
+            .init_resource::<ShadowMapCache>();
 
         let shadow_pass_node = ShadowPassNode::new(render_app.world_mut());
         let mut graph = render_app.world_mut().resource_mut::<RenderGraph>();
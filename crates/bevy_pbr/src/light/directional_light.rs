@@ -63,6 +63,15 @@ pub struct DirectionalLight {
     /// A bias applied along the direction of the fragment's surface normal. It is scaled to the
     /// shadow map's texel size so that it is automatically adjusted to the orthographic projection.
     pub shadow_normal_bias: f32,
+    /// The angular diameter of the light in radians, used to soften its shadows with
+    /// percentage-closer soft shadows (PCSS).
+    ///
+    /// Shadows get softer the further they are from their caster. The sun is about
+    /// `0.0093` radians wide as seen from the Earth. If `None`, shadows have the same
+    /// softness everywhere, set by the [`ShadowFilteringMethod`] of the camera.
+    ///
+    /// PCSS isn't supported on WebGL 2, where this is ignored.
+    pub soft_shadow_size: Option<f32>,
 }
 
 impl Default for DirectionalLight {
@@ -73,6 +82,7 @@ impl Default for DirectionalLight {
             shadows_enabled: false,
             shadow_depth_bias: Self::DEFAULT_SHADOW_DEPTH_BIAS,
             shadow_normal_bias: Self::DEFAULT_SHADOW_NORMAL_BIAS,
+            soft_shadow_size: None,
         }
     }
 }
@@ -138,6 +138,13 @@ pub struct CascadeShadowConfig {
     pub overlap_proportion: f32,
     /// The (positive) distance to the near boundary of the first cascade.
     pub minimum_distance: f32,
+    /// The depth bias of each cascade, used instead of the
+    /// [`shadow_depth_bias`](DirectionalLight::shadow_depth_bias) of the light.
+    /// Cascades without an entry use the depth bias of the light.
+    pub depth_biases: Vec<f32>,
+    /// Whether to move each cascade in whole shadow map texels, which stops shadow edges from
+    /// shimmering when the camera moves.
+    pub texel_snapping: bool,
 }
 
 impl Default for CascadeShadowConfig {
@@ -160,6 +167,27 @@ fn calculate_cascade_bounds(
         .collect()
 }
 
+/// Splits the distance between `minimum_distance` and `maximum_distance` with the practical
+/// split scheme, which blends logarithmic splits with uniform splits by `lambda`.
+///
+/// See "Parallel-Split Shadow Maps for Large-scale Virtual Environments", Zhang et al. 2006.
+fn calculate_practical_cascade_bounds(
+    num_cascades: usize,
+    minimum_distance: f32,
+    maximum_distance: f32,
+    lambda: f32,
+) -> Vec<f32> {
+    (1..=num_cascades)
+        .map(|i| {
+            let proportion = i as f32 / num_cascades as f32;
+            let logarithmic =
+                minimum_distance * (maximum_distance / minimum_distance).powf(proportion);
+            let uniform = minimum_distance + (maximum_distance - minimum_distance) * proportion;
+            lambda * logarithmic + (1.0 - lambda) * uniform
+        })
+        .collect()
+}
+
 /// Builder for [`CascadeShadowConfig`].
 pub struct CascadeShadowConfigBuilder {
     /// The number of shadow cascades.
@@ -195,6 +223,22 @@ pub struct CascadeShadowConfigBuilder {
     /// The overlap is used to make the transition from one cascade's shadow map to the next
     /// less abrupt by blending between both shadow maps.
     pub overlap_proportion: f32,
+    /// If set, places the cascade bounds with the practical split scheme instead of
+    /// `first_cascade_far_bound`, blending logarithmically spaced bounds, with weight `1.0`, and
+    /// uniformly spaced bounds, with weight `0.0`, between `minimum_distance` and
+    /// `maximum_distance`.
+    ///
+    /// Logarithmic bounds give the best resolution near the camera, while uniform bounds spread
+    /// it evenly. Requires a positive `minimum_distance`.
+    pub split_lambda: Option<f32>,
+    /// The depth bias of each cascade. Cascades without an entry use the
+    /// [`shadow_depth_bias`](DirectionalLight::shadow_depth_bias) of the light.
+    ///
+    /// Further cascades cover more of the scene with each texel, so they usually need a larger bias.
+    pub depth_biases: Vec<f32>,
+    /// Whether to move each cascade in whole shadow map texels, which stops shadow edges from
+    /// shimmering when the camera moves.
+    pub texel_snapping: bool,
 }
 
 impl CascadeShadowConfigBuilder {
@@ -225,14 +269,37 @@ impl CascadeShadowConfigBuilder {
             "overlap_proportion must be in [0.0, 1.0) but was {}",
             self.overlap_proportion
         );
-        CascadeShadowConfig {
-            bounds: calculate_cascade_bounds(
+        let bounds = match self.split_lambda {
+            Some(split_lambda) => {
+                assert!(
+                    (0.0..=1.0).contains(&split_lambda),
+                    "split_lambda must be in [0.0, 1.0] but was {}",
+                    split_lambda
+                );
+                assert!(
+                    self.minimum_distance > 0.0,
+                    "minimum_distance must be positive when using split_lambda, but was {}",
+                    self.minimum_distance
+                );
+                calculate_practical_cascade_bounds(
+                    self.num_cascades,
+                    self.minimum_distance,
+                    self.maximum_distance,
+                    split_lambda,
+                )
+            }
+            None => calculate_cascade_bounds(
                 self.num_cascades,
                 self.first_cascade_far_bound,
                 self.maximum_distance,
             ),
+        };
+        CascadeShadowConfig {
+            bounds,
             overlap_proportion: self.overlap_proportion,
             minimum_distance: self.minimum_distance,
+            depth_biases: self.depth_biases.clone(),
+            texel_snapping: self.texel_snapping,
         }
     }
 }
@@ -251,6 +318,9 @@ impl Default for CascadeShadowConfigBuilder {
                 maximum_distance: 100.0,
                 first_cascade_far_bound: 5.0,
                 overlap_proportion: 0.2,
+                split_lambda: None,
+                depth_biases: Vec::new(),
+                texel_snapping: true,
             }
         } else {
             Self {
@@ -259,6 +329,9 @@ impl Default for CascadeShadowConfigBuilder {
                 maximum_distance: 1000.0,
                 first_cascade_far_bound: 5.0,
                 overlap_proportion: 0.2,
+                split_lambda: None,
+                depth_biases: Vec::new(),
+                texel_snapping: true,
             }
         }
     }
@@ -358,6 +431,7 @@ pub fn build_directional_light_cascades<P: CameraProjection + Component>(
                         directional_light_shadow_map.size as f32,
                         light_to_world,
                         camera_to_light_view,
+                        cascades_config.texel_snapping,
                     )
                 })
                 .collect();
@@ -374,6 +448,7 @@ fn calculate_cascade(
     cascade_texture_size: f32,
     light_to_world: Mat4,
     camera_to_light: Mat4,
+    texel_snapping: bool,
 ) -> Cascade {
     let mut min = Vec3A::splat(f32::MAX);
     let mut max = Vec3A::splat(f32::MIN);
@@ -401,12 +476,12 @@ fn calculate_cascade(
     let cascade_texel_size = cascade_diameter / cascade_texture_size;
     // NOTE: For shadow stability it is very important that the near_plane_center is at integer
     //       multiples of the texel size to be exactly representable in a floating point value.
-    let near_plane_center = Vec3A::new(
-        (0.5 * (min.x + max.x) / cascade_texel_size).floor() * cascade_texel_size,
-        (0.5 * (min.y + max.y) / cascade_texel_size).floor() * cascade_texel_size,
-        // NOTE: max.z is the near plane for right-handed y-up
-        max.z,
-    );
+    let mut center = 0.5 * (min.xy() + max.xy());
+    if texel_snapping {
+        center = (center / cascade_texel_size).floor() * cascade_texel_size;
+    }
+    // NOTE: max.z is the near plane for right-handed y-up
+    let near_plane_center: Vec3A = center.extend(max.z).into();
 
     // It is critical for `world_to_cascade` to be stable. So rather than forming `cascade_to_world`
     // and inverting it, which risks instability due to numerical precision, we directly form
@@ -441,6 +516,19 @@ fn calculate_cascade(
 #[derive(Component, Reflect, Default)]
 #[reflect(Component, Default)]
 pub struct NotShadowCaster;
+/// Add this component to a [`Mesh`] that casts shadows and rarely moves, so that shadow maps
+/// only containing such meshes are reused across frames instead of being rendered again.
+///
+/// A shadow map is rendered again when its light moves, when a mesh is added to or removed
+/// from it, or when one of its [`StaticShadowCaster`] meshes changes its [`GlobalTransform`],
+/// its mesh or its material, including modifications of the mesh and material assets. It is
+/// also rendered again until all of its meshes could be drawn, for instance while their
+/// assets or pipelines are still loading.
+///
+/// Shadow maps in which a mesh without this component is drawn are rendered every frame.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+pub struct StaticShadowCaster;
 /// Add this component to make a [`Mesh`] not receive shadows.
 ///
 /// **Note:** If you're using diffuse transmission, setting [`NotShadowReceiver`] will
@@ -2172,4 +2260,25 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn practical_split_cascade_bounds() {
+        let builder = |split_lambda| CascadeShadowConfigBuilder {
+            num_cascades: 4,
+            minimum_distance: 1.0,
+            maximum_distance: 16.0,
+            split_lambda: Some(split_lambda),
+            ..Default::default()
+        };
+
+        let logarithmic = builder(1.0).build();
+        for (bound, expected) in logarithmic.bounds.iter().zip([2.0, 4.0, 8.0, 16.0]) {
+            assert!((bound - expected).abs() < 1e-4);
+        }
+
+        let uniform = builder(0.0).build();
+        for (bound, expected) in uniform.bounds.iter().zip([4.75, 8.5, 12.25, 16.0]) {
+            assert!((bound - expected).abs() < 1e-4);
+        }
+    }
 }
@@ -29,6 +29,15 @@ pub struct SpotLight {
     /// Light is attenuated from `inner_angle` to `outer_angle` to give a smooth falloff.
     /// `inner_angle` should be <= `outer_angle`
     pub inner_angle: f32,
+    /// Whether to soften shadows with percentage-closer soft shadows (PCSS), treating the
+    /// light as a disk of [`radius`](Self::radius).
+    ///
+    /// Shadows get softer the further they are from their caster, and the larger the radius.
+    /// If `false`, shadows have the same softness everywhere, set by the
+    /// [`ShadowFilteringMethod`] of the camera.
+    ///
+    /// PCSS isn't supported on WebGL 2, where this is ignored.
+    pub soft_shadows_enabled: bool,
 }
 
 impl SpotLight {
@@ -52,6 +61,7 @@ impl Default for SpotLight {
             shadow_normal_bias: Self::DEFAULT_SHADOW_NORMAL_BIAS,
             inner_angle: 0.0,
             outer_angle: std::f32::consts::FRAC_PI_4,
+            soft_shadows_enabled: false,
         }
     }
 }
@@ -1,6 +1,6 @@
 #define_import_path bevy_pbr::environment_map
 
-#import bevy_pbr::light_probe::query_light_probe
+#import bevy_pbr::light_probe::light_probe_influence
 #import bevy_pbr::mesh_view_bindings as bindings
 #import bevy_pbr::mesh_view_bindings::light_probes
 
@@ -20,48 +20,85 @@ struct EnvironmentMapRadiances {
 
 #ifdef MULTIPLE_LIGHT_PROBES_IN_ARRAY
 
-fn compute_radiances(
+// Samples the diffuse and specular cubemaps at `texture_index` in the binding
+// arrays, scaled by `intensity`.
+fn sample_environment_map(
+    texture_index: i32,
+    intensity: f32,
     perceptual_roughness: f32,
     N: vec3<f32>,
     R: vec3<f32>,
-    world_position: vec3<f32>,
     found_diffuse_indirect: bool,
 ) -> EnvironmentMapRadiances {
     var radiances: EnvironmentMapRadiances;
-
-    // Search for a reflection probe that contains the fragment.
-    var query_result = query_light_probe(world_position, /*is_irradiance_volume=*/ false);
-
-    // If we didn't find a reflection probe, use the view environment map if applicable.
-    if (query_result.texture_index < 0) {
-        query_result.texture_index = light_probes.view_cubemap_index;
-        query_result.intensity = light_probes.intensity_for_view;
-    }
-
-    // If there's no cubemap, bail out.
-    if (query_result.texture_index < 0) {
-        radiances.irradiance = vec3(0.0);
-        radiances.radiance = vec3(0.0);
-        return radiances;
-    }
+    radiances.irradiance = vec3(0.0);
 
     // Split-sum approximation for image based lighting: https://cdn2.unrealengine.com/Resources/files/2013SiggraphPresentationsNotes-26915738.pdf
     let radiance_level = perceptual_roughness * f32(textureNumLevels(
-        bindings::specular_environment_maps[query_result.texture_index]) - 1u);
+        bindings::specular_environment_maps[texture_index]) - 1u);
 
     if (!found_diffuse_indirect) {
         radiances.irradiance = textureSampleLevel(
-            bindings::diffuse_environment_maps[query_result.texture_index],
+            bindings::diffuse_environment_maps[texture_index],
             bindings::environment_map_sampler,
             vec3(N.xy, -N.z),
-            0.0).rgb * query_result.intensity;
+            0.0).rgb * intensity;
     }
 
     radiances.radiance = textureSampleLevel(
-        bindings::specular_environment_maps[query_result.texture_index],
+        bindings::specular_environment_maps[texture_index],
         bindings::environment_map_sampler,
         vec3(R.xy, -R.z),
-        radiance_level).rgb * query_result.intensity;
+        radiance_level).rgb * intensity;
+
+    return radiances;
+}
+
+fn compute_radiances(
+    perceptual_roughness: f32,
+    N: vec3<f32>,
+    R: vec3<f32>,
+    world_position: vec3<f32>,
+    found_diffuse_indirect: bool,
+) -> EnvironmentMapRadiances {
+    var radiances: EnvironmentMapRadiances;
+    radiances.irradiance = vec3(0.0);
+    radiances.radiance = vec3(0.0);
+
+    // Blend the reflection probes that contain the fragment, closest to the
+    // view first, until their influences add up to 1.
+    var remaining_weight = 1.0;
+    for (var light_probe_index: i32 = 0;
+            light_probe_index < light_probes.reflection_probe_count && remaining_weight > 0.0;
+            light_probe_index += 1) {
+        let light_probe = light_probes.reflection_probes[light_probe_index];
+        let weight = min(light_probe_influence(light_probe, world_position), remaining_weight);
+        if (weight > 0.0) {
+            let probe_radiances = sample_environment_map(
+                light_probe.cubemap_index,
+                light_probe.intensity * weight,
+                perceptual_roughness,
+                N,
+                R,
+                found_diffuse_indirect);
+            radiances.irradiance += probe_radiances.irradiance;
+            radiances.radiance += probe_radiances.radiance;
+            remaining_weight -= weight;
+        }
+    }
+
+    // Give the rest to the view environment map, if applicable.
+    if (remaining_weight > 0.0 && light_probes.view_cubemap_index >= 0) {
+        let view_radiances = sample_environment_map(
+            light_probes.view_cubemap_index,
+            light_probes.intensity_for_view * remaining_weight,
+            perceptual_roughness,
+            N,
+            R,
+            found_diffuse_indirect);
+        radiances.irradiance += view_radiances.irradiance;
+        radiances.radiance += view_radiances.radiance;
+    }
 
     return radiances;
 }
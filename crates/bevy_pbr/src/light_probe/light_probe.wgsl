@@ -3,6 +3,11 @@
 #import bevy_pbr::mesh_view_bindings::light_probes
 #import bevy_pbr::mesh_view_types::LightProbe
 
+// The light probe affects the unit cube centered on the origin.
+const LIGHT_PROBE_SHAPE_BOX: u32 = 0u;
+// The light probe affects the sphere of diameter 1 centered on the origin.
+const LIGHT_PROBE_SHAPE_SPHERE: u32 = 1u;
+
 // The result of searching for a light probe.
 struct LightProbeQueryResult {
     // The index of the light probe texture or textures in the binding array or
@@ -25,9 +30,36 @@ fn transpose_affine_matrix(matrix: mat3x4<f32>) -> mat4x4<f32> {
     return transpose(matrix4x4);
 }
 
+// Returns how much the light probe affects a fragment at `world_position`: 0
+// outside its region, fading in over its falloff to 1 inside.
+fn light_probe_influence(light_probe: LightProbe, world_position: vec3<f32>) -> f32 {
+    let inverse_transform =
+        transpose_affine_matrix(light_probe.inverse_transpose_transform);
+    let probe_space_pos = (inverse_transform * vec4<f32>(world_position, 1.0f)).xyz;
+
+    // The distance of the fragment from the center of the light probe, where 1
+    // is the boundary of its region.
+    var distance: f32;
+    if (light_probe.shape == LIGHT_PROBE_SHAPE_SPHERE) {
+        distance = length(probe_space_pos) * 2.0;
+    } else {
+        let abs_pos = abs(probe_space_pos);
+        distance = max(max(abs_pos.x, abs_pos.y), abs_pos.z) * 2.0;
+    }
+
+    if (distance > 1.0) {
+        return 0.0;
+    }
+    if (light_probe.falloff <= 0.0) {
+        return 1.0;
+    }
+    return saturate((1.0 - distance) / light_probe.falloff);
+}
+
 // Searches for a light probe that contains the fragment.
 //
-// TODO: Interpolate between multiple light probes.
+// TODO: Interpolate between multiple irradiance volumes. Reflection probes are
+// blended in `environment_map::compute_radiances` instead.
 fn query_light_probe(
     world_position: vec3<f32>,
     is_irradiance_volume: bool,
@@ -56,10 +88,8 @@ fn query_light_probe(
         let inverse_transform =
             transpose_affine_matrix(light_probe.inverse_transpose_transform);
 
-        // Check to see if the transformed point is inside the unit cube
-        // centered at the origin.
-        let probe_space_pos = (inverse_transform * vec4<f32>(world_position, 1.0f)).xyz;
-        if (all(abs(probe_space_pos) <= vec3(0.5f))) {
+        // Check to see if the point is inside the region of the light probe.
+        if (light_probe_influence(light_probe, world_position) > 0.0) {
             result.texture_index = light_probe.cubemap_index;
             result.intensity = light_probe.intensity;
             result.inverse_transform = inverse_transform;
@@ -38,12 +38,15 @@ use crate::{
     },
 };
 
-use self::irradiance_volume::IrradianceVolume;
+use self::{
+    irradiance_volume::IrradianceVolume, reflection_probe_capture::ReflectionProbeCapturePlugin,
+};
 
 pub const LIGHT_PROBE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(8954249792581071582);
 
 pub mod environment_map;
 pub mod irradiance_volume;
+pub mod reflection_probe_capture;
 
 /// The maximum number of each type of light probe that each view will consider.
 ///
@@ -92,6 +95,11 @@ pub struct LightProbePlugin;
 /// not participate in the ranking. That is, ambient light is applied in
 /// addition to, not instead of, the light sources above.
 ///
+/// Reflection probes can be given a spherical region and made to fade out
+/// towards their boundary with a [`LightProbeInfluence`]. Overlapping
+/// reflection probes are then blended, closest to the view first, and any
+/// remaining influence goes to the view environment map.
+///
 /// A terminology note: Unfortunately, there is little agreement across game and
 /// graphics engines as to what to call the various techniques that Bevy groups
 /// under the term *light probe*. In Bevy, a *light probe* is the generic term
@@ -106,6 +114,39 @@ pub struct LightProbePlugin;
 #[reflect(Component, Default)]
 pub struct LightProbe;
 
+/// The shape of the region affected by a [`LightProbe`], in the probe's model
+/// space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub enum LightProbeShape {
+    /// The unit cube centered on the origin.
+    #[default]
+    Box,
+    /// The sphere of diameter 1 centered on the origin.
+    ///
+    /// Nonuniform scales in the [`bevy_transform::prelude::Transform`] of the
+    /// light probe turn this into an ellipsoid.
+    Sphere,
+}
+
+/// Configures the region in which a reflection probe applies and how it
+/// blends with the reflection probes and view environment map around it.
+///
+/// Light probes without this component are boxes with hard edges.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct LightProbeInfluence {
+    /// The shape of the region affected by the light probe.
+    pub shape: LightProbeShape,
+
+    /// The fraction of the light probe's region, measured inward from its
+    /// boundary, over which its influence fades out.
+    ///
+    /// 0.0, the default, makes the probe's influence end abruptly at its
+    /// boundary. 1.0 fades it out all the way from the probe's center.
+    pub falloff: f32,
+}
+
 /// A GPU type that stores information about a light probe.
 #[derive(Clone, Copy, ShaderType, Default)]
 struct RenderLightProbe {
@@ -124,6 +165,16 @@ struct RenderLightProbe {
     ///
     /// See the comment in [`EnvironmentMapLight`] for details.
     intensity: f32,
+
+    /// The [`LightProbeShape`] of the light probe: 0 for a box and 1 for a
+    /// sphere.
+    shape: u32,
+
+    /// The fraction of the light probe's region over which its influence fades
+    /// out.
+    ///
+    /// See the comment in [`LightProbeInfluence`] for details.
+    falloff: f32,
 }
 
 /// A per-view shader uniform that specifies all the light probes that the view
@@ -190,6 +241,13 @@ where
     // See the comment in [`EnvironmentMapLight`] for details.
     intensity: f32,
 
+    // The shape of the region affected by this light probe.
+    shape: LightProbeShape,
+
+    // The fraction of the region over which the influence of this light probe
+    // fades out.
+    falloff: f32,
+
     // The IDs of all assets associated with this light probe.
     //
     // Because each type of light probe component may reference different types
@@ -296,6 +354,25 @@ impl LightProbe {
     }
 }
 
+impl Default for LightProbeInfluence {
+    fn default() -> Self {
+        Self {
+            shape: LightProbeShape::Box,
+            falloff: 0.0,
+        }
+    }
+}
+
+impl LightProbeShape {
+    /// Returns the value identifying this shape in the shader.
+    fn shader_index(self) -> u32 {
+        match self {
+            LightProbeShape::Box => 0,
+            LightProbeShape::Sphere => 1,
+        }
+    }
+}
+
 impl Plugin for LightProbePlugin {
     fn build(&self, app: &mut App) {
         load_internal_asset!(
@@ -318,8 +395,11 @@ impl Plugin for LightProbePlugin {
         );
 
         app.register_type::<LightProbe>()
+            .register_type::<LightProbeShape>()
+            .register_type::<LightProbeInfluence>()
             .register_type::<EnvironmentMapLight>()
-            .register_type::<IrradianceVolume>();
+            .register_type::<IrradianceVolume>()
+            .add_plugins(ReflectionProbeCapturePlugin);
     }
 
     fn finish(&self, app: &mut App) {
@@ -343,7 +423,9 @@ impl Plugin for LightProbePlugin {
 /// to views, performing frustum culling and distance sorting in the process.
 fn gather_light_probes<C>(
     image_assets: Res<RenderAssets<GpuImage>>,
-    light_probe_query: Extract<Query<(&GlobalTransform, &C), With<LightProbe>>>,
+    light_probe_query: Extract<
+        Query<(&GlobalTransform, &C, Option<&LightProbeInfluence>), With<LightProbe>>,
+    >,
     view_query: Extract<Query<(Entity, &GlobalTransform, &Frustum, Option<&C>), With<Camera3d>>>,
     mut reflection_probes: Local<Vec<LightProbeInfo<C>>>,
     mut view_reflection_probes: Local<Vec<LightProbeInfo<C>>>,
@@ -504,14 +586,21 @@ where
     /// [`LightProbeInfo`]. This is done for every light probe in the scene
     /// every frame.
     fn new(
-        (light_probe_transform, environment_map): (&GlobalTransform, &C),
+        (light_probe_transform, environment_map, influence): (
+            &GlobalTransform,
+            &C,
+            Option<&LightProbeInfluence>,
+        ),
         image_assets: &RenderAssets<GpuImage>,
     ) -> Option<LightProbeInfo<C>> {
+        let influence = influence.copied().unwrap_or_default();
         environment_map.id(image_assets).map(|id| LightProbeInfo {
             affine_transform: light_probe_transform.affine(),
             inverse_transform: light_probe_transform.compute_matrix().inverse(),
             asset_id: id,
             intensity: environment_map.intensity(),
+            shape: influence.shape,
+            falloff: influence.falloff.clamp(0.0, 1.0),
         })
     }
 
@@ -609,6 +698,8 @@ where
                 ],
                 texture_index: cubemap_index as i32,
                 intensity: light_probe.intensity,
+                shape: light_probe.shape.shader_index(),
+                falloff: light_probe.falloff,
             });
         }
     }
@@ -623,6 +714,8 @@ where
             inverse_transform: self.inverse_transform,
             affine_transform: self.affine_transform,
             intensity: self.intensity,
+            shape: self.shape,
+            falloff: self.falloff,
             asset_id: self.asset_id.clone(),
         }
     }
@@ -0,0 +1,637 @@
+//! Rendering reflection probes at runtime.
+//!
+//! A [`ReflectionProbeCapture`] on a [`LightProbe`] renders the surroundings
+//! of the probe from its center into a pair of cubemaps, and makes them the
+//! [`EnvironmentMapLight`] of the probe. This lets reflection probes pick up
+//! dynamic objects and lighting, at the cost of rendering the scene six more
+//! times per capture.
+//!
+//! Captures happen on demand, via [`ReflectionProbeCapture::request`], or
+//! every frame, depending on the [`ReflectionProbeRefresh`] mode.
+//!
+//! The specular cubemap is downsampled into its mip levels with a box filter
+//! rather than pre-filtered with the GGX distribution, and the diffuse cubemap
+//! is one of its small mip levels rather than a Lambertian convolution. This
+//! is fast enough to run every frame, but rough surfaces look blotchier than
+//! with a baked environment map.
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, AssetId, Assets, Handle};
+use bevy_core_pipeline::{
+    core_3d::Camera3dBundle,
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    tonemapping::{DebandDither, Tonemapping},
+};
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
+    prelude::*,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{Camera, Exposure, PerspectiveProjection, Projection, RenderTarget},
+    graph::CameraDriverLabel,
+    render_asset::{RenderAssetUsages, RenderAssets},
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext, RenderLabel},
+    render_resource::{
+        binding_types::{sampler, texture_2d},
+        BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, CachedRenderPipelineId,
+        ColorTargetState, ColorWrites, Extent3d, FilterMode, FragmentState, ImageCopyTexture,
+        LoadOp, MultisampleState, Operations, Origin3d, PipelineCache, PrimitiveState,
+        RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor, Sampler,
+        SamplerBindingType, SamplerDescriptor, Shader, ShaderStages, StoreOp, TextureAspect,
+        TextureDimension, TextureFormat, TextureSampleType, TextureUsages, TextureViewDescriptor,
+        TextureViewDimension,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::{GpuImage, Image, ImageSampler},
+    view::VisibilitySystems,
+    Extract, ExtractSchedule, RenderApp,
+};
+use bevy_transform::{components::Transform, prelude::GlobalTransform, TransformSystem};
+
+use crate::{
+    light_probe::environment_map::EnvironmentMapLight, CubeMapFace, LightProbe, CUBE_MAP_FACES,
+};
+
+/// A handle to the shader that downsamples captured cubemaps.
+pub const REFLECTION_PROBE_DOWNSAMPLE_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(3309582736120458187);
+
+/// The texture format of captured cubemaps.
+const CAPTURE_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// The approximate size of each face of the diffuse cubemap of a capture.
+const DIFFUSE_RESOLUTION: u32 = 8;
+
+/// Adds support for [`ReflectionProbeCapture`].
+pub struct ReflectionProbeCapturePlugin;
+
+/// Renders the surroundings of a [`LightProbe`] into its
+/// [`EnvironmentMapLight`] at runtime.
+///
+/// The environment map of the light probe is replaced by the captured one.
+/// See [`crate::reflection_probe_capture`] for details.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct ReflectionProbeCapture {
+    /// The width and height of each face of the captured specular cubemap.
+    pub resolution: u32,
+    /// The distance from the center of the probe to the near clipping plane of
+    /// the capture.
+    pub near: f32,
+    /// The distance from the center of the probe beyond which objects aren't
+    /// captured.
+    pub far: f32,
+    /// The exposure of the capture.
+    ///
+    /// The intensity of the resulting [`EnvironmentMapLight`] compensates for
+    /// it, so this only matters for the precision of the captured values.
+    pub exposure: Exposure,
+    /// When the probe is captured.
+    pub refresh: ReflectionProbeRefresh,
+    /// Whether a capture has been requested and not started yet.
+    requested: bool,
+}
+
+/// When a [`ReflectionProbeCapture`] renders the probe.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub enum ReflectionProbeRefresh {
+    /// Only when the capture is added and whenever
+    /// [`ReflectionProbeCapture::request`] is called.
+    #[default]
+    OnDemand,
+    /// Every frame.
+    EveryFrame,
+}
+
+/// The cubemaps and cameras of a [`ReflectionProbeCapture`].
+///
+/// This is added to light probes automatically.
+#[derive(Component)]
+pub struct ReflectionProbeCaptureState {
+    resolution: u32,
+    cameras: [Entity; 6],
+    faces: [Handle<Image>; 6],
+    specular_map: Handle<Image>,
+    diffuse_map: Handle<Image>,
+    diffuse_mip_level: u32,
+    capturing: bool,
+}
+
+impl ReflectionProbeCapture {
+    /// Creates a capture with faces of `resolution` × `resolution` texels,
+    /// rendered on demand.
+    pub fn new(resolution: u32) -> Self {
+        Self {
+            resolution,
+            ..Self::default()
+        }
+    }
+
+    /// Renders the probe again at the end of this frame.
+    pub fn request(&mut self) {
+        self.requested = true;
+    }
+}
+
+impl Default for ReflectionProbeCapture {
+    fn default() -> Self {
+        Self {
+            resolution: 256,
+            near: 0.1,
+            far: 1000.0,
+            exposure: Exposure::default(),
+            refresh: ReflectionProbeRefresh::OnDemand,
+            requested: true,
+        }
+    }
+}
+
+impl ReflectionProbeCaptureState {
+    /// The cameras rendering the faces of the cubemap, in the order +X, -X,
+    /// +Y, -Y, +Z, -Z.
+    ///
+    /// Components such as a skybox can be added to them to customize the
+    /// capture.
+    pub fn cameras(&self) -> &[Entity; 6] {
+        &self.cameras
+    }
+}
+
+impl Plugin for ReflectionProbeCapturePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            REFLECTION_PROBE_DOWNSAMPLE_SHADER_HANDLE,
+            "reflection_probe_capture.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<ReflectionProbeCapture>()
+            .register_type::<ReflectionProbeRefresh>()
+            .add_systems(
+                PostUpdate,
+                update_reflection_probe_captures
+                    .after(TransformSystem::TransformPropagate)
+                    .before(VisibilitySystems::UpdateProjectionFrusta),
+            );
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<ExtractedReflectionProbeCaptures>()
+            .add_systems(ExtractSchedule, extract_reflection_probe_captures);
+
+        let mut render_graph = render_app.world_mut().resource_mut::<RenderGraph>();
+        render_graph.add_node(ReflectionProbeCaptureLabel, ReflectionProbeCaptureNode);
+        render_graph.add_node_edge(CameraDriverLabel, ReflectionProbeCaptureLabel);
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<ReflectionProbeDownsamplePipeline>();
+    }
+}
+
+/// Creates the cubemaps and cameras of new captures, and activates the cameras
+/// of the captures that render this frame.
+fn update_reflection_probe_captures(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut probes: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &mut ReflectionProbeCapture,
+            Option<&mut ReflectionProbeCaptureState>,
+        ),
+        With<LightProbe>,
+    >,
+    mut cameras: Query<
+        (
+            &mut Camera,
+            &mut Transform,
+            &mut GlobalTransform,
+            &mut Projection,
+        ),
+        Without<LightProbe>,
+    >,
+    mut removed_captures: RemovedComponents<ReflectionProbeCapture>,
+    mut capture_cameras: Local<EntityHashMap<[Entity; 6]>>,
+) {
+    // Despawn the cameras of removed captures.
+    for probe in removed_captures.read() {
+        if let Some(cameras) = capture_cameras.remove(&probe) {
+            for camera in cameras {
+                commands.entity(camera).despawn();
+            }
+        }
+    }
+
+    for (probe, probe_transform, mut capture, state) in &mut probes {
+        let resolution = capture.resolution.max(1);
+
+        let Some(mut state) = state.filter(|state| state.resolution == resolution) else {
+            // Replace the cameras of captures whose resolution changed.
+            if let Some(cameras) = capture_cameras.remove(&probe) {
+                for camera in cameras {
+                    commands.entity(camera).despawn();
+                }
+            }
+
+            let state = create_capture_state(&mut commands, &mut images, resolution);
+            capture_cameras.insert(probe, state.cameras);
+            commands.entity(probe).insert((
+                EnvironmentMapLight {
+                    diffuse_map: state.diffuse_map.clone(),
+                    specular_map: state.specular_map.clone(),
+                    intensity: 1.0 / capture.exposure.exposure(),
+                },
+                state,
+            ));
+            capture.request();
+            continue;
+        };
+
+        // The capture requested last frame has been rendered.
+        if state.capturing {
+            state.capturing = false;
+            for &camera in &state.cameras {
+                if let Ok((mut camera, ..)) = cameras.get_mut(camera) {
+                    camera.is_active = false;
+                }
+            }
+        }
+
+        if !capture.requested && capture.refresh == ReflectionProbeRefresh::OnDemand {
+            continue;
+        }
+        capture.requested = false;
+        state.capturing = true;
+
+        let translation = probe_transform.translation();
+        for (&camera, CubeMapFace { target, up }) in state.cameras.iter().zip(&CUBE_MAP_FACES) {
+            let Ok((mut camera, mut transform, mut global_transform, mut projection)) =
+                cameras.get_mut(camera)
+            else {
+                continue;
+            };
+
+            camera.is_active = true;
+
+            // The cameras aren't parented to the probe so that its scale doesn't
+            // distort them, so place them here.
+            *transform =
+                Transform::from_translation(translation).looking_at(translation + *target, *up);
+            *global_transform = GlobalTransform::from(*transform);
+
+            if let Projection::Perspective(perspective) = &mut *projection {
+                perspective.near = capture.near;
+                perspective.far = capture.far;
+            }
+        }
+    }
+}
+
+/// Creates the cubemaps and the inactive cameras of a capture.
+fn create_capture_state(
+    commands: &mut Commands,
+    images: &mut Assets<Image>,
+    resolution: u32,
+) -> ReflectionProbeCaptureState {
+    let mip_level_count = resolution.ilog2() + 1;
+    let diffuse_mip_level = (resolution / DIFFUSE_RESOLUTION).max(1).ilog2();
+    let diffuse_resolution = (resolution >> diffuse_mip_level).max(1);
+
+    let specular_map = images.add(new_cubemap(
+        resolution,
+        mip_level_count,
+        TextureUsages::TEXTURE_BINDING
+            | TextureUsages::COPY_DST
+            | TextureUsages::COPY_SRC
+            | TextureUsages::RENDER_ATTACHMENT,
+    ));
+    let diffuse_map = images.add(new_cubemap(
+        diffuse_resolution,
+        1,
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+    ));
+
+    let faces: [Handle<Image>; 6] = std::array::from_fn(|_| {
+        let mut face = Image::new_fill(
+            Extent3d {
+                width: resolution,
+                height: resolution,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0; 8],
+            CAPTURE_FORMAT,
+            RenderAssetUsages::default(),
+        );
+        face.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+            | TextureUsages::COPY_SRC
+            | TextureUsages::RENDER_ATTACHMENT;
+        images.add(face)
+    });
+
+    let cameras = faces.clone().map(|face| {
+        commands
+            .spawn(Camera3dBundle {
+                camera: Camera {
+                    // Render before the cameras that may see the probe.
+                    order: -1,
+                    is_active: false,
+                    target: RenderTarget::Image(face),
+                    hdr: true,
+                    ..Camera::default()
+                },
+                projection: Projection::Perspective(PerspectiveProjection {
+                    fov: std::f32::consts::FRAC_PI_2,
+                    aspect_ratio: 1.0,
+                    ..PerspectiveProjection::default()
+                }),
+                tonemapping: Tonemapping::None,
+                deband_dither: DebandDither::Disabled,
+                ..Camera3dBundle::default()
+            })
+            .id()
+    });
+
+    ReflectionProbeCaptureState {
+        resolution,
+        cameras,
+        faces,
+        specular_map,
+        diffuse_map,
+        diffuse_mip_level,
+        capturing: false,
+    }
+}
+
+/// Creates a blank cubemap that only lives in the render world.
+fn new_cubemap(resolution: u32, mip_level_count: u32, usage: TextureUsages) -> Image {
+    let texel_count: usize = (0..mip_level_count)
+        .map(|mip_level| {
+            let size = (resolution >> mip_level).max(1) as usize;
+            size * size * 6
+        })
+        .sum();
+    let texel_size = CAPTURE_FORMAT.block_copy_size(None).unwrap() as usize;
+
+    let mut image = Image {
+        data: vec![0; texel_count * texel_size],
+        sampler: ImageSampler::linear(),
+        texture_view_descriptor: Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..TextureViewDescriptor::default()
+        }),
+        asset_usage: RenderAssetUsages::RENDER_WORLD,
+        ..Image::default()
+    };
+    image.texture_descriptor.size = Extent3d {
+        width: resolution,
+        height: resolution,
+        depth_or_array_layers: 6,
+    };
+    image.texture_descriptor.mip_level_count = mip_level_count;
+    image.texture_descriptor.format = CAPTURE_FORMAT;
+    image.texture_descriptor.usage = usage;
+    image
+}
+
+/// The captures rendered this frame, in the render world.
+#[derive(Resource, Default)]
+struct ExtractedReflectionProbeCaptures(Vec<ExtractedReflectionProbeCapture>);
+
+struct ExtractedReflectionProbeCapture {
+    faces: [AssetId<Image>; 6],
+    specular_map: AssetId<Image>,
+    diffuse_map: AssetId<Image>,
+    diffuse_mip_level: u32,
+}
+
+fn extract_reflection_probe_captures(
+    mut extracted_captures: ResMut<ExtractedReflectionProbeCaptures>,
+    captures: Extract<Query<&ReflectionProbeCaptureState>>,
+) {
+    extracted_captures.0.clear();
+    extracted_captures.0.extend(
+        captures
+            .iter()
+            .filter(|state| state.capturing)
+            .map(|state| ExtractedReflectionProbeCapture {
+                faces: state.faces.each_ref().map(Handle::id),
+                specular_map: state.specular_map.id(),
+                diffuse_map: state.diffuse_map.id(),
+                diffuse_mip_level: state.diffuse_mip_level,
+            }),
+    );
+}
+
+/// The pipeline that downsamples a face of a captured cubemap into its next
+/// mip level.
+#[derive(Resource)]
+struct ReflectionProbeDownsamplePipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+    pipeline_id: CachedRenderPipelineId,
+}
+
+impl FromWorld for ReflectionProbeDownsamplePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let layout = render_device.create_bind_group_layout(
+            "reflection_probe_downsample_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..SamplerDescriptor::default()
+        });
+
+        let pipeline_id =
+            world
+                .resource_mut::<PipelineCache>()
+                .queue_render_pipeline(RenderPipelineDescriptor {
+                    label: Some("reflection_probe_downsample_pipeline".into()),
+                    layout: vec![layout.clone()],
+                    vertex: fullscreen_shader_vertex_state(),
+                    fragment: Some(FragmentState {
+                        shader: REFLECTION_PROBE_DOWNSAMPLE_SHADER_HANDLE,
+                        shader_defs: vec![],
+                        entry_point: "downsample".into(),
+                        targets: vec![Some(ColorTargetState {
+                            format: CAPTURE_FORMAT,
+                            blend: None,
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                    push_constant_ranges: vec![],
+                });
+
+        Self {
+            layout,
+            sampler,
+            pipeline_id,
+        }
+    }
+}
+
+/// The label of the render graph node that assembles the cubemaps of captured
+/// reflection probes.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct ReflectionProbeCaptureLabel;
+
+/// Copies the faces rendered by the capture cameras into the specular cubemap,
+/// generates its mip levels, and copies one of them into the diffuse cubemap.
+///
+/// This runs after all cameras have rendered.
+struct ReflectionProbeCaptureNode;
+
+impl Node for ReflectionProbeCaptureNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let captures = world.resource::<ExtractedReflectionProbeCaptures>();
+        if captures.0.is_empty() {
+            return Ok(());
+        }
+
+        let pipeline = world.resource::<ReflectionProbeDownsamplePipeline>();
+        let Some(render_pipeline) = world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(pipeline.pipeline_id)
+        else {
+            return Ok(());
+        };
+        let images = world.resource::<RenderAssets<GpuImage>>();
+
+        for capture in &captures.0 {
+            let (Some(specular_map), Some(diffuse_map)) = (
+                images.get(capture.specular_map),
+                images.get(capture.diffuse_map),
+            ) else {
+                continue;
+            };
+            let Some(faces) = capture
+                .faces
+                .iter()
+                .map(|face| images.get(*face))
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+
+            let command_encoder = render_context.command_encoder();
+
+            // Copy the rendered faces into the first mip level.
+            for (layer, face) in faces.iter().enumerate() {
+                command_encoder.copy_texture_to_texture(
+                    face.texture.as_image_copy(),
+                    ImageCopyTexture {
+                        texture: &specular_map.texture,
+                        mip_level: 0,
+                        origin: Origin3d {
+                            x: 0,
+                            y: 0,
+                            z: layer as u32,
+                        },
+                        aspect: TextureAspect::All,
+                    },
+                    Extent3d {
+                        width: face.size.x,
+                        height: face.size.y,
+                        depth_or_array_layers: 1,
+                    },
+                );
+            }
+
+            // Downsample each face into the following mip levels.
+            for mip_level in 1..specular_map.mip_level_count {
+                for layer in 0..6 {
+                    let face_view = |mip_level| {
+                        specular_map.texture.create_view(&TextureViewDescriptor {
+                            label: Some("reflection_probe_capture_face_view"),
+                            dimension: Some(TextureViewDimension::D2),
+                            base_mip_level: mip_level,
+                            mip_level_count: Some(1),
+                            base_array_layer: layer,
+                            array_layer_count: Some(1),
+                            ..TextureViewDescriptor::default()
+                        })
+                    };
+                    let source = face_view(mip_level - 1);
+                    let destination = face_view(mip_level);
+
+                    let bind_group = render_context.render_device().create_bind_group(
+                        "reflection_probe_downsample_bind_group",
+                        &pipeline.layout,
+                        &BindGroupEntries::sequential((&source, &pipeline.sampler)),
+                    );
+
+                    let mut render_pass =
+                        render_context
+                            .command_encoder()
+                            .begin_render_pass(&RenderPassDescriptor {
+                                label: Some("reflection_probe_downsample_pass"),
+                                color_attachments: &[Some(RenderPassColorAttachment {
+                                    view: &destination,
+                                    resolve_target: None,
+                                    ops: Operations {
+                                        load: LoadOp::Clear(Default::default()),
+                                        store: StoreOp::Store,
+                                    },
+                                })],
+                                depth_stencil_attachment: None,
+                                timestamp_writes: None,
+                                occlusion_query_set: None,
+                            });
+                    render_pass.set_pipeline(render_pipeline);
+                    render_pass.set_bind_group(0, &bind_group, &[]);
+                    render_pass.draw(0..3, 0..1);
+                }
+            }
+
+            // Use a small mip level as the diffuse cubemap.
+            let diffuse_size = (specular_map.size.x >> capture.diffuse_mip_level).max(1);
+            render_context.command_encoder().copy_texture_to_texture(
+                ImageCopyTexture {
+                    texture: &specular_map.texture,
+                    mip_level: capture.diffuse_mip_level,
+                    origin: Origin3d::ZERO,
+                    aspect: TextureAspect::All,
+                },
+                diffuse_map.texture.as_image_copy(),
+                Extent3d {
+                    width: diffuse_size,
+                    height: diffuse_size,
+                    depth_or_array_layers: 6,
+                },
+            );
+        }
+
+        Ok(())
+    }
+}
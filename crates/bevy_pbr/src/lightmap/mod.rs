@@ -19,6 +19,12 @@
 //! multiple meshes can share the same material, whereas sharing lightmaps is
 //! nonsensical).
 //!
+//! Lightmaps work with both the forward and deferred renderers. A lightmap
+//! replaces any other diffuse indirect light, such as irradiance volumes and
+//! environment maps, but realtime direct lighting is still applied on top of
+//! it. The glTF loader imports the second UV set of meshes (`TEXCOORD_1`) as
+//! [`Mesh::ATTRIBUTE_UV_1`].
+//!
 //! Note that meshes can't be instanced if they use different lightmap textures.
 //! If you want to instance a lightmapped mesh, combine the lightmap textures
 //! into a single atlas, and set the `uv_rect` field on [`Lightmap`]
@@ -10,6 +10,7 @@ use bevy_core_pipeline::{
         AlphaMask3d, Camera3d, Opaque3d, Opaque3dBinKey, ScreenSpaceTransmissionQuality,
         Transmissive3d, Transparent3d,
     },
+    oit::OrderIndependentTransparent3d,
     prepass::{
         DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass, OpaqueNoLightmap3dBinKey,
     },
@@ -26,14 +27,16 @@ use bevy_render::{
     extract_instances::{ExtractInstancesPlugin, ExtractedInstances},
     extract_resource::ExtractResource,
     mesh::{GpuMesh, MeshVertexBufferLayoutRef},
-    render_asset::{PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssets},
+    render_asset::{
+        ExtractedAssets, PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssets,
+    },
     render_phase::*,
-    render_resource::*,
-    renderer::RenderDevice,
-    texture::FallbackImage,
-    view::{ExtractedView, Msaa, VisibleEntities, WithMesh},
+    render_resource::{binding_types::sampler, *},
+    renderer::{RenderDevice, RenderQueue},
+    texture::{DefaultImageSampler, FallbackImage},
+    view::{ExtractedView, Msaa, VisibleEntities, VisibleMeshLods, WithMesh},
 };
-use bevy_utils::tracing::error;
+use bevy_utils::{tracing::error, HashMap};
 use std::marker::PhantomData;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::{hash::Hash, num::NonZeroU32};
@@ -144,6 +147,17 @@ pub trait Material: Asset + AsBindGroup + Clone + Sized {
         false
     }
 
+    /// Returns whether this material's fragment shader writes the outputs of weighted blended
+    /// order-independent transparency when the `OIT_ENABLED` shader def is set, with
+    /// `weighted_blended_oit_output` from `bevy_pbr::forward_io`.
+    ///
+    /// Blended materials that don't are still sorted in [`Transparent3d`] in views with
+    /// [`OrderIndependentTransparency`](bevy_core_pipeline::oit::OrderIndependentTransparency).
+    #[inline]
+    fn supports_order_independent_transparency() -> bool {
+        false
+    }
+
     /// Returns this material's prepass vertex shader. If [`ShaderRef::Default`] is returned, the default prepass vertex shader
     /// will be used.
     ///
@@ -260,6 +274,7 @@ where
                 .add_render_command::<Shadow, DrawPrepass<M>>()
                 .add_render_command::<Transmissive3d, DrawMaterial<M>>()
                 .add_render_command::<Transparent3d, DrawMaterial<M>>()
+                .add_render_command::<OrderIndependentTransparent3d, DrawMaterial<M>>()
                 .add_render_command::<Opaque3d, DrawMaterial<M>>()
                 .add_render_command::<AlphaMask3d, DrawMaterial<M>>()
                 .init_resource::<SpecializedMeshPipelines<MaterialPipeline<M>>>()
@@ -271,12 +286,23 @@ where
                 );
 
             if self.shadows_enabled {
-                render_app.add_systems(
-                    Render,
-                    queue_shadows::<M>
-                        .in_set(RenderSet::QueueMeshes)
-                        .after(prepare_assets::<PreparedMaterial<M>>),
-                );
+                render_app
+                    .add_systems(
+                        ExtractSchedule,
+                        extract_changed_static_shadow_caster_materials::<M>
+                            .after(extract_changed_static_shadow_casters),
+                    )
+                    .add_systems(
+                        Render,
+                        (
+                            collect_changed_static_shadow_caster_materials::<M>
+                                .in_set(RenderSet::PrepareAssets)
+                                .before(prepare_assets::<PreparedMaterial<M>>),
+                            queue_shadows::<M>
+                                .in_set(RenderSet::QueueMeshes)
+                                .after(prepare_assets::<PreparedMaterial<M>>),
+                        ),
+                    );
             }
 
             #[cfg(feature = "meshlet")]
@@ -309,6 +335,216 @@ where
     }
 }
 
+/// A [`Material`] that can be drawn with a single bind group shared by all the materials of its
+/// type, so that meshes with different materials of this type are batched together.
+///
+/// The data of each material is stored in a [`MaterialTable`] and its textures in
+/// [`BindlessTextures`], which shaders index with the slot of the material of the mesh, returned
+/// by `get_material_bind_group_slot` in `bevy_pbr::mesh_functions`. This is enabled by adding a
+/// [`BindlessMaterialPlugin`] next to the [`MaterialPlugin`] of the material, on devices where
+/// [`bindless_supported`] is `true`.
+///
+/// The main pass pipelines of bindless materials have the `BINDLESS` shader def, and bind:
+///
+/// ```wgsl
+/// @group(2) @binding(0) var<storage> materials: array<MyMaterialData>;
+/// @group(2) @binding(1) var textures: binding_array<texture_2d<f32>>;
+/// @group(2) @binding(2) var texture_sampler: sampler;
+/// ```
+///
+/// Prepasses and shadows still bind the bind group of each material.
+pub trait BindlessMaterial: Material {
+    /// The data of each material in the [`MaterialTable`].
+    type BindlessData: GpuArrayBufferable + Send + Sync;
+
+    /// Returns the data of this material, adding its textures to `textures` and storing their
+    /// indices in the data.
+    ///
+    /// Returning `None`, e.g. because a texture isn't loaded yet or `textures` is full, retries
+    /// on the next frame.
+    fn bindless_data(
+        &self,
+        images: &RenderAssets<GpuImage>,
+        textures: &mut BindlessTextures,
+    ) -> Option<Self::BindlessData>;
+}
+
+/// Draws all the materials of type `M` with the bind group in [`BindlessMaterials<M>`], where
+/// [`bindless_supported`] is `true`.
+///
+/// This must be added next to the [`MaterialPlugin<M>`].
+pub struct BindlessMaterialPlugin<M: BindlessMaterial> {
+    /// The maximum number of textures used by all the materials of type `M`.
+    pub texture_capacity: NonZeroU32,
+    pub _marker: PhantomData<M>,
+}
+
+impl<M: BindlessMaterial> Default for BindlessMaterialPlugin<M> {
+    fn default() -> Self {
+        Self {
+            texture_capacity: NonZeroU32::new(64).unwrap(),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<M: BindlessMaterial> Plugin for BindlessMaterialPlugin<M> {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(
+                Render,
+                prepare_bindless_materials::<M>
+                    .in_set(RenderSet::PrepareAssets)
+                    .after(prepare_assets::<GpuImage>)
+                    .before(prepare_assets::<PreparedMaterial<M>>)
+                    .run_if(resource_exists::<BindlessMaterials<M>>),
+            );
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        let render_device = render_app.world().resource::<RenderDevice>().clone();
+        if !bindless_supported(&render_device) {
+            return;
+        }
+
+        let textures = BindlessTextures::new(self.texture_capacity);
+        let layout = render_device.create_bind_group_layout(
+            "bindless_material_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::VERTEX_FRAGMENT,
+                (
+                    MaterialTable::<M::BindlessData>::binding_layout(),
+                    textures.binding_layout(),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+
+        // The material pipeline is initialized by the `MaterialPlugin`, which may finish first.
+        let world = render_app.world_mut();
+        if let Some(mut material_pipeline) = world.get_resource_mut::<MaterialPipeline<M>>() {
+            material_pipeline.bindless_layout = Some(layout.clone());
+        }
+        world.insert_resource(BindlessMaterials::<M> {
+            layout,
+            bind_group: None,
+            slots: HashMap::default(),
+        });
+        world.insert_resource(BindlessMaterialStorage::<M> {
+            table: MaterialTable::new(),
+            textures,
+            pending: HashMap::default(),
+        });
+    }
+}
+
+/// The bind group shared by all the materials of type `M`, when they're drawn bindless.
+///
+/// See [`BindlessMaterial`].
+#[derive(Resource)]
+pub struct BindlessMaterials<M: Material> {
+    layout: BindGroupLayout,
+    bind_group: Option<BindGroup>,
+    slots: HashMap<AssetId<M>, u32>,
+}
+
+impl<M: Material> BindlessMaterials<M> {
+    /// Returns the layout of the bind group.
+    pub fn layout(&self) -> &BindGroupLayout {
+        &self.layout
+    }
+
+    /// Returns the bind group, once a material has been prepared.
+    pub fn bind_group(&self) -> Option<&BindGroup> {
+        self.bind_group.as_ref()
+    }
+
+    /// Returns the slot of the material in the [`MaterialTable`] of the bind group, once it has
+    /// been prepared.
+    pub fn slot(&self, id: AssetId<M>) -> Option<u32> {
+        self.slots.get(&id).copied()
+    }
+}
+
+/// The material data and textures bound by [`BindlessMaterials<M>`].
+#[derive(Resource)]
+struct BindlessMaterialStorage<M: BindlessMaterial> {
+    table: MaterialTable<M::BindlessData>,
+    textures: BindlessTextures,
+    /// The materials whose data couldn't be built yet.
+    pending: HashMap<AssetId<M>, M>,
+}
+
+/// Writes the materials of type `M` added or modified this frame to the table of
+/// [`BindlessMaterials<M>`], and recreates its bind group if anything changed.
+#[allow(clippy::too_many_arguments)]
+fn prepare_bindless_materials<M: BindlessMaterial>(
+    mut bindless_materials: ResMut<BindlessMaterials<M>>,
+    mut storage: ResMut<BindlessMaterialStorage<M>>,
+    extracted_assets: Res<ExtractedAssets<PreparedMaterial<M>>>,
+    images: Res<RenderAssets<GpuImage>>,
+    fallback_image: Res<FallbackImage>,
+    default_sampler: Res<DefaultImageSampler>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let bindless_materials = &mut *bindless_materials;
+    let storage = &mut *storage;
+
+    let mut changed = false;
+    for id in extracted_assets.removed() {
+        storage.pending.remove(id);
+        if let Some(slot) = bindless_materials.slots.remove(id) {
+            storage.table.remove(slot);
+            changed = true;
+        }
+    }
+    for (id, material) in extracted_assets.extracted() {
+        storage.pending.insert(*id, material.clone());
+    }
+
+    storage.pending.retain(|id, material| {
+        let Some(data) = material.bindless_data(&images, &mut storage.textures) else {
+            return true;
+        };
+        match bindless_materials.slots.get(id) {
+            Some(&slot) => storage.table.set(slot, data),
+            None => {
+                let slot = storage.table.insert(data);
+                bindless_materials.slots.insert(*id, slot);
+            }
+        }
+        changed = true;
+        false
+    });
+
+    if !changed {
+        return;
+    }
+
+    storage.table.write_buffer(&render_device, &render_queue);
+    let Some(table) = storage.table.binding() else {
+        return;
+    };
+    let texture_views = storage
+        .textures
+        .texture_views(&fallback_image.d2.texture_view);
+    bindless_materials.bind_group = Some(render_device.create_bind_group(
+        "bindless_material_bind_group",
+        &bindless_materials.layout,
+        &BindGroupEntries::sequential((
+            table,
+            BindingResource::TextureViewArray(&texture_views),
+            &**default_sampler,
+        )),
+    ));
+}
+
 /// A key uniquely identifying a specialized [`MaterialPipeline`].
 pub struct MaterialPipelineKey<M: Material> {
     pub mesh_key: MeshPipelineKey,
@@ -353,16 +589,44 @@ where
 pub struct MaterialPipeline<M: Material> {
     pub mesh_pipeline: MeshPipeline,
     pub material_layout: BindGroupLayout,
+    pub(crate) bindless_layout: Option<BindGroupLayout>,
     pub vertex_shader: Option<Handle<Shader>>,
     pub fragment_shader: Option<Handle<Shader>>,
     pub marker: PhantomData<M>,
 }
 
+impl<M: Material> MaterialPipeline<M> {
+    /// Creates a pipeline for materials that aren't drawn bindless.
+    pub fn new(
+        mesh_pipeline: MeshPipeline,
+        material_layout: BindGroupLayout,
+        vertex_shader: Option<Handle<Shader>>,
+        fragment_shader: Option<Handle<Shader>>,
+    ) -> Self {
+        Self {
+            mesh_pipeline,
+            material_layout,
+            bindless_layout: None,
+            vertex_shader,
+            fragment_shader,
+            marker: PhantomData,
+        }
+    }
+
+    /// The layout of the bind group shared by all the materials, if they're drawn bindless.
+    ///
+    /// See [`BindlessMaterial`].
+    pub fn bindless_layout(&self) -> Option<&BindGroupLayout> {
+        self.bindless_layout.as_ref()
+    }
+}
+
 impl<M: Material> Clone for MaterialPipeline<M> {
     fn clone(&self) -> Self {
         Self {
             mesh_pipeline: self.mesh_pipeline.clone(),
             material_layout: self.material_layout.clone(),
+            bindless_layout: self.bindless_layout.clone(),
             vertex_shader: self.vertex_shader.clone(),
             fragment_shader: self.fragment_shader.clone(),
             marker: PhantomData,
@@ -390,7 +654,16 @@ where
             descriptor.fragment.as_mut().unwrap().shader = fragment_shader.clone();
         }
 
-        descriptor.layout.insert(2, self.material_layout.clone());
+        match self.bindless_layout {
+            Some(ref bindless_layout) => {
+                descriptor.layout.insert(2, bindless_layout.clone());
+                descriptor.vertex.shader_defs.push("BINDLESS".into());
+                if let Some(fragment) = descriptor.fragment.as_mut() {
+                    fragment.shader_defs.push("BINDLESS".into());
+                }
+            }
+            None => descriptor.layout.insert(2, self.material_layout.clone()),
+        }
 
         M::specialize(self, &mut descriptor, layout, key)?;
         Ok(descriptor)
@@ -405,6 +678,9 @@ impl<M: Material> FromWorld for MaterialPipeline<M> {
         MaterialPipeline {
             mesh_pipeline: world.resource::<MeshPipeline>().clone(),
             material_layout: M::bind_group_layout(render_device),
+            bindless_layout: world
+                .get_resource::<BindlessMaterials<M>>()
+                .map(|bindless_materials| bindless_materials.layout.clone()),
             vertex_shader: match M::vertex_shader() {
                 ShaderRef::Default => None,
                 ShaderRef::Handle(handle) => Some(handle),
@@ -424,7 +700,7 @@ type DrawMaterial<M> = (
     SetItemPipeline,
     SetMeshViewBindGroup<0>,
     SetMeshBindGroup<1>,
-    SetMaterialBindGroup<M, 2>,
+    SetBindlessMaterialBindGroup<M, 2>,
     DrawMesh,
 );
 
@@ -460,6 +736,48 @@ impl<P: PhaseItem, M: Material, const I: usize> RenderCommand<P> for SetMaterial
     }
 }
 
+/// Sets the bind group shared by all the materials of type `M` at the configured `I` index if
+/// they're drawn bindless, or the bind group of the given [`Material`] otherwise.
+///
+/// See [`BindlessMaterial`].
+pub struct SetBindlessMaterialBindGroup<M: Material, const I: usize>(PhantomData<M>);
+impl<P: PhaseItem, M: Material, const I: usize> RenderCommand<P>
+    for SetBindlessMaterialBindGroup<M, I>
+{
+    type Param = (
+        Option<SRes<BindlessMaterials<M>>>,
+        SRes<RenderAssets<PreparedMaterial<M>>>,
+        SRes<RenderMaterialInstances<M>>,
+    );
+    type ViewQuery = ();
+    type ItemQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        _item_query: Option<()>,
+        (bindless_materials, materials, material_instances): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(bindless_materials) = bindless_materials else {
+            return <SetMaterialBindGroup<M, I> as RenderCommand<P>>::render(
+                item,
+                (),
+                None,
+                (materials, material_instances),
+                pass,
+            );
+        };
+
+        let Some(bind_group) = bindless_materials.into_inner().bind_group() else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_bind_group(I, bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
+
 pub type RenderMaterialInstances<M> = ExtractedInstances<AssetId<M>>;
 
 pub const fn alpha_mode_pipeline_key(alpha_mode: AlphaMode) -> MeshPipelineKey {
@@ -516,18 +834,20 @@ pub fn queue_material_meshes<M: Material>(
     alpha_mask_draw_functions: Res<DrawFunctions<AlphaMask3d>>,
     transmissive_draw_functions: Res<DrawFunctions<Transmissive3d>>,
     transparent_draw_functions: Res<DrawFunctions<Transparent3d>>,
+    oit_draw_functions: Res<DrawFunctions<OrderIndependentTransparent3d>>,
     material_pipeline: Res<MaterialPipeline<M>>,
     mut pipelines: ResMut<SpecializedMeshPipelines<MaterialPipeline<M>>>,
     pipeline_cache: Res<PipelineCache>,
     msaa: Res<Msaa>,
     render_meshes: Res<RenderAssets<GpuMesh>>,
     render_materials: Res<RenderAssets<PreparedMaterial<M>>>,
+    bindless_materials: Option<Res<BindlessMaterials<M>>>,
     render_mesh_instances: Res<RenderMeshInstances>,
     render_material_instances: Res<RenderMaterialInstances<M>>,
     render_lightmaps: Res<RenderLightmaps>,
     mut views: Query<(
         &ExtractedView,
-        &VisibleEntities,
+        (&VisibleEntities, Option<&VisibleMeshLods>),
         Option<&Tonemapping>,
         Option<&DebandDither>,
         Option<&ShadowFilteringMethod>,
@@ -544,7 +864,10 @@ pub fn queue_material_meshes<M: Material>(
         &mut BinnedRenderPhase<Opaque3d>,
         &mut BinnedRenderPhase<AlphaMask3d>,
         &mut SortedRenderPhase<Transmissive3d>,
-        &mut SortedRenderPhase<Transparent3d>,
+        (
+            &mut SortedRenderPhase<Transparent3d>,
+            Option<&mut SortedRenderPhase<OrderIndependentTransparent3d>>,
+        ),
         (
             Has<RenderViewLightProbes<EnvironmentMapLight>>,
             Has<RenderViewLightProbes<IrradianceVolume>>,
@@ -555,7 +878,7 @@ pub fn queue_material_meshes<M: Material>(
 {
     for (
         view,
-        visible_entities,
+        (visible_entities, visible_mesh_lods),
         tonemapping,
         dither,
         shadow_filter_method,
@@ -567,7 +890,7 @@ pub fn queue_material_meshes<M: Material>(
         mut opaque_phase,
         mut alpha_mask_phase,
         mut transmissive_phase,
-        mut transparent_phase,
+        (mut transparent_phase, mut oit_phase),
         (has_environment_maps, has_irradiance_volumes),
     ) in &mut views
     {
@@ -575,6 +898,7 @@ pub fn queue_material_meshes<M: Material>(
         let draw_alpha_mask_pbr = alpha_mask_draw_functions.read().id::<DrawMaterial<M>>();
         let draw_transmissive_pbr = transmissive_draw_functions.read().id::<DrawMaterial<M>>();
         let draw_transparent_pbr = transparent_draw_functions.read().id::<DrawMaterial<M>>();
+        let draw_oit_pbr = oit_draw_functions.read().id::<DrawMaterial<M>>();
 
         let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
             | MeshPipelineKey::from_hdr(view.hdr);
@@ -653,13 +977,33 @@ pub fn queue_material_meshes<M: Material>(
             else {
                 continue;
             };
-            let Some(mesh) = render_meshes.get(mesh_instance.mesh_asset_id) else {
+            let mesh_asset_id =
+                mesh_instance.mesh_asset_id_for_view(*visible_entity, visible_mesh_lods);
+            let Some(mesh) = render_meshes.get(mesh_asset_id) else {
                 continue;
             };
             let Some(material) = render_materials.get(*material_asset_id) else {
                 continue;
             };
 
+            // Bindless materials all share one bind group, and each instance looks up its
+            // material with the slot in its mesh uniform.
+            let material_bind_group_id = match bindless_materials {
+                Some(ref bindless_materials) => {
+                    let (Some(bind_group), Some(slot)) = (
+                        bindless_materials.bind_group(),
+                        bindless_materials.slot(*material_asset_id),
+                    ) else {
+                        continue;
+                    };
+                    mesh_instance
+                        .material_bind_group_slot
+                        .store(slot, Ordering::Relaxed);
+                    MaterialBindGroupId::new(bind_group.id())
+                }
+                None => material.get_bind_group_id(),
+            };
+
             let mut mesh_key = view_key
                 | MeshPipelineKey::from_bits_retain(mesh.key_bits.bits())
                 | material.properties.mesh_pipeline_key_bits;
@@ -672,6 +1016,19 @@ pub fn queue_material_meshes<M: Material>(
                 mesh_key |= MeshPipelineKey::LIGHTMAPPED;
             }
 
+            // Only alpha blending can be approximated by weighted blended order-independent
+            // transparency. Additive and multiplicative blending are still sorted, as are
+            // materials whose shaders don't write its outputs.
+            let order_independent = oit_phase.is_some()
+                && M::supports_order_independent_transparency()
+                && matches!(
+                    material.properties.alpha_mode,
+                    AlphaMode::Blend | AlphaMode::Premultiplied
+                );
+            if order_independent {
+                mesh_key |= MeshPipelineKey::ORDER_INDEPENDENT_TRANSPARENCY;
+            }
+
             let pipeline_id = pipelines.specialize(
                 &pipeline_cache,
                 &material_pipeline,
@@ -691,7 +1048,7 @@ pub fn queue_material_meshes<M: Material>(
 
             mesh_instance
                 .material_bind_group_id
-                .set(material.get_bind_group_id());
+                .set(material_bind_group_id);
 
             match material.properties.alpha_mode {
                 AlphaMode::Opaque => {
@@ -710,8 +1067,8 @@ pub fn queue_material_meshes<M: Material>(
                         let bin_key = Opaque3dBinKey {
                             draw_function: draw_opaque_pbr,
                             pipeline: pipeline_id,
-                            asset_id: mesh_instance.mesh_asset_id,
-                            material_bind_group_id: material.get_bind_group_id().0,
+                            asset_id: mesh_asset_id,
+                            material_bind_group_id: material_bind_group_id.0,
                             lightmap_image,
                         };
                         opaque_phase.add(bin_key, *visible_entity, mesh_instance.should_batch());
@@ -733,8 +1090,8 @@ pub fn queue_material_meshes<M: Material>(
                         let bin_key = OpaqueNoLightmap3dBinKey {
                             draw_function: draw_alpha_mask_pbr,
                             pipeline: pipeline_id,
-                            asset_id: mesh_instance.mesh_asset_id,
-                            material_bind_group_id: material.get_bind_group_id().0,
+                            asset_id: mesh_asset_id,
+                            material_bind_group_id: material_bind_group_id.0,
                         };
                         alpha_mask_phase.add(
                             bin_key,
@@ -749,14 +1106,28 @@ pub fn queue_material_meshes<M: Material>(
                 | AlphaMode::Multiply => {
                     let distance = rangefinder.distance_translation(&mesh_instance.translation)
                         + material.properties.depth_bias;
-                    transparent_phase.add(Transparent3d {
-                        entity: *visible_entity,
-                        draw_function: draw_transparent_pbr,
-                        pipeline: pipeline_id,
-                        distance,
-                        batch_range: 0..1,
-                        dynamic_offset: None,
-                    });
+                    match oit_phase.as_mut() {
+                        Some(oit_phase) if order_independent => {
+                            oit_phase.add(OrderIndependentTransparent3d {
+                                entity: *visible_entity,
+                                draw_function: draw_oit_pbr,
+                                pipeline: pipeline_id,
+                                distance,
+                                batch_range: 0..1,
+                                dynamic_offset: None,
+                            });
+                        }
+                        _ => {
+                            transparent_phase.add(Transparent3d {
+                                entity: *visible_entity,
+                                draw_function: draw_transparent_pbr,
+                                pipeline: pipeline_id,
+                                distance,
+                                batch_range: 0..1,
+                                dynamic_offset: None,
+                            });
+                        }
+                    }
                 }
             }
         }
@@ -905,6 +905,11 @@ impl Material for StandardMaterial {
         self.specular_transmission > 0.0
     }
 
+    #[inline]
+    fn supports_order_independent_transparency() -> bool {
+        true
+    }
+
     fn prepass_fragment_shader() -> ShaderRef {
         PBR_PREPASS_SHADER_HANDLE.into()
     }
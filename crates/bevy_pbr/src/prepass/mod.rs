@@ -23,7 +23,10 @@ use bevy_render::{
     render_phase::*,
     render_resource::*,
     renderer::{RenderDevice, RenderQueue},
-    view::{ExtractedView, Msaa, ViewUniform, ViewUniformOffset, ViewUniforms, VisibleEntities},
+    view::{
+        ExtractedView, Msaa, ViewUniform, ViewUniformOffset, ViewUniforms, VisibleEntities,
+        VisibleMeshLods,
+    },
     Extract,
 };
 use bevy_transform::prelude::GlobalTransform;
@@ -147,8 +150,8 @@ where
                     ),
                 )
                 .add_plugins((
-                    BinnedRenderPhasePlugin::<Opaque3dPrepass, MeshPipeline>::default(),
-                    BinnedRenderPhasePlugin::<AlphaMask3dPrepass, MeshPipeline>::default(),
+                    BinnedRenderPhasePlugin::<Opaque3dPrepass, MeshPipeline>::parallel(),
+                    BinnedRenderPhasePlugin::<AlphaMask3dPrepass, MeshPipeline>::parallel(),
                 ));
         }
 
@@ -416,6 +419,11 @@ where
 
         if key.mesh_key.contains(MeshPipelineKey::DEFERRED_PREPASS) {
             shader_defs.push("DEFERRED_PREPASS".into());
+
+            // The lightmap is written into the G-buffer.
+            if key.mesh_key.contains(MeshPipelineKey::LIGHTMAPPED) {
+                shader_defs.push("LIGHTMAP".into());
+            }
         }
 
         if layout.0.contains(Mesh::ATTRIBUTE_COLOR) {
@@ -716,6 +724,7 @@ pub fn queue_prepass_material_meshes<M: Material>(
         (
             &ExtractedView,
             &VisibleEntities,
+            Option<&VisibleMeshLods>,
             Option<&mut BinnedRenderPhase<Opaque3dPrepass>>,
             Option<&mut BinnedRenderPhase<AlphaMask3dPrepass>>,
             Option<&mut BinnedRenderPhase<Opaque3dDeferred>>,
@@ -754,6 +763,7 @@ pub fn queue_prepass_material_meshes<M: Material>(
     for (
         _view,
         visible_entities,
+        visible_mesh_lods,
         mut opaque_phase,
         mut alpha_mask_phase,
         mut opaque_deferred_phase,
@@ -786,7 +796,9 @@ pub fn queue_prepass_material_meshes<M: Material>(
             let Some(material) = render_materials.get(*material_asset_id) else {
                 continue;
             };
-            let Some(mesh) = render_meshes.get(mesh_instance.mesh_asset_id) else {
+            let mesh_asset_id =
+                mesh_instance.mesh_asset_id_for_view(*visible_entity, visible_mesh_lods);
+            let Some(mesh) = render_meshes.get(mesh_asset_id) else {
                 continue;
             };
 
@@ -820,11 +832,11 @@ pub fn queue_prepass_material_meshes<M: Material>(
                 mesh_key |= MeshPipelineKey::DEFERRED_PREPASS;
             }
 
-            // Even though we don't use the lightmap in the prepass, the
-            // `SetMeshBindGroup` render command will bind the data for it. So
-            // we need to include the appropriate flag in the mesh pipeline key
-            // to ensure that the necessary bind group layout entries are
-            // present.
+            // The deferred prepass writes the lightmap into the G-buffer. Other
+            // prepasses don't use it, but the `SetMeshBindGroup` render command
+            // will bind the data for it. So we need to include the appropriate
+            // flag in the mesh pipeline key to ensure that the necessary bind
+            // group layout entries are present.
             if render_lightmaps
                 .render_lightmaps
                 .contains_key(visible_entity)
@@ -856,7 +868,7 @@ pub fn queue_prepass_material_meshes<M: Material>(
                             OpaqueNoLightmap3dBinKey {
                                 draw_function: opaque_draw_deferred,
                                 pipeline: pipeline_id,
-                                asset_id: mesh_instance.mesh_asset_id,
+                                asset_id: mesh_asset_id,
                                 material_bind_group_id: material.get_bind_group_id().0,
                             },
                             *visible_entity,
@@ -867,7 +879,7 @@ pub fn queue_prepass_material_meshes<M: Material>(
                             OpaqueNoLightmap3dBinKey {
                                 draw_function: opaque_draw_prepass,
                                 pipeline: pipeline_id,
-                                asset_id: mesh_instance.mesh_asset_id,
+                                asset_id: mesh_asset_id,
                                 material_bind_group_id: material.get_bind_group_id().0,
                             },
                             *visible_entity,
@@ -880,7 +892,7 @@ pub fn queue_prepass_material_meshes<M: Material>(
                         let bin_key = OpaqueNoLightmap3dBinKey {
                             pipeline: pipeline_id,
                             draw_function: alpha_mask_draw_deferred,
-                            asset_id: mesh_instance.mesh_asset_id,
+                            asset_id: mesh_asset_id,
                             material_bind_group_id: material.get_bind_group_id().0,
                         };
                         alpha_mask_deferred_phase.as_mut().unwrap().add(
@@ -892,7 +904,7 @@ pub fn queue_prepass_material_meshes<M: Material>(
                         let bin_key = OpaqueNoLightmap3dBinKey {
                             pipeline: pipeline_id,
                             draw_function: alpha_mask_draw_prepass,
-                            asset_id: mesh_instance.mesh_asset_id,
+                            asset_id: mesh_asset_id,
                             material_bind_group_id: material.get_bind_group_id().0,
                         };
                         alpha_mask_phase.add(
@@ -30,6 +30,23 @@ fn morph_vertex(vertex_in: Vertex) -> Vertex {
     }
     return vertex;
 }
+
+#ifdef MOTION_VECTOR_PREPASS
+// Applies the morph targets with the weights of the previous frame. Only the
+// position is needed to compute motion vectors.
+fn morph_prev_vertex(vertex_in: Vertex) -> Vertex {
+    var vertex = vertex_in;
+    let weight_count = morph::layer_count();
+    for (var i: u32 = 0u; i < weight_count; i ++) {
+        let weight = morph::prev_weight_at(i);
+        if weight == 0.0 {
+            continue;
+        }
+        vertex.position += weight * morph::morph(vertex.index, morph::position_offset, i);
+    }
+    return vertex;
+}
+#endif // MOTION_VECTOR_PREPASS
 #endif
 
 @vertex
@@ -94,11 +111,23 @@ fn vertex(vertex_no_morph: Vertex) -> VertexOutput {
     out.world_position = mesh_functions::mesh_position_local_to_world(model, vec4<f32>(vertex.position, 1.0));
 
 #ifdef MOTION_VECTOR_PREPASS
+#ifdef MORPH_TARGETS
+    let prev_vertex = morph_prev_vertex(vertex_no_morph);
+#else // MORPH_TARGETS
+    let prev_vertex = vertex_no_morph;
+#endif // MORPH_TARGETS
+
+#ifdef SKINNED
+    let prev_model = skinning::skin_prev_model(prev_vertex.joint_indices, prev_vertex.joint_weights);
+#else // SKINNED
     // Use vertex_no_morph.instance_index instead of vertex.instance_index to work around a wgpu dx12 bug.
     // See https://github.com/gfx-rs/naga/issues/2416
+    let prev_model = mesh_functions::get_previous_model_matrix(vertex_no_morph.instance_index);
+#endif // SKINNED
+
     out.previous_world_position = mesh_functions::mesh_position_local_to_world(
-        mesh_functions::get_previous_model_matrix(vertex_no_morph.instance_index),
-        vec4<f32>(vertex.position, 1.0)
+        prev_model,
+        vec4<f32>(prev_vertex.position, 1.0)
     );
 #endif // MOTION_VECTOR_PREPASS
 
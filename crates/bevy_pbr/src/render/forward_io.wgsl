@@ -53,5 +53,34 @@ struct VertexOutput {
 }
 
 struct FragmentOutput {
+#ifdef OIT_ENABLED
+    // The weighted, premultiplied color and the weighted alpha of the fragment
     @location(0) color: vec4<f32>,
+    // The alpha of the fragment, which multiplies the revealage by `1 - alpha`
+    @location(1) revealage: f32,
+#else
+    @location(0) color: vec4<f32>,
+#endif
+}
+
+#ifdef OIT_ENABLED
+// Returns the outputs of a fragment of `color` at the depth `frag_depth` for weighted
+// blended order-independent transparency.
+// McGuire and Bavoil 2013, "Weighted Blended Order-Independent Transparency"
+fn weighted_blended_oit_output(color: vec4<f32>, frag_depth: f32) -> FragmentOutput {
+#ifdef BLEND_PREMULTIPLIED_ALPHA
+    let premultiplied_color = color.rgb;
+#else
+    let premultiplied_color = color.rgb * color.a;
+#endif
+
+    // Closer fragments weigh more. The depth is reversed, so it is 1 at the near plane and
+    // decreases with distance. The weight is clamped to stay in the range of 16-bit floats.
+    let weight = color.a * clamp(3e3 * frag_depth * frag_depth * frag_depth, 1e-2, 3e3);
+
+    var out: FragmentOutput;
+    out.color = vec4<f32>(premultiplied_color, color.a) * weight;
+    out.revealage = color.a;
+    return out;
 }
+#endif
@@ -1,7 +1,10 @@
-use bevy_asset::AssetId;
+use bevy_asset::{AssetId, Handle};
 use bevy_core_pipeline::core_3d::{Transparent3d, CORE_3D_DEPTH_FORMAT};
 use bevy_ecs::prelude::*;
-use bevy_ecs::{entity::EntityHashMap, system::lifetimeless::Read};
+use bevy_ecs::{
+    entity::{EntityHashMap, EntityHashSet},
+    system::{lifetimeless::Read, SystemParam},
+};
 use bevy_math::{Mat4, UVec3, UVec4, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles};
 use bevy_render::mesh::Mesh;
 use bevy_render::{
@@ -9,7 +12,7 @@ use bevy_render::{
     diagnostic::RecordDiagnostics,
     mesh::GpuMesh,
     primitives::{CascadesFrusta, CubemapFrusta, Frustum, HalfSpace},
-    render_asset::RenderAssets,
+    render_asset::{ExtractedAssets, PrepareNextFrameAssets, RenderAssets},
     render_graph::{Node, NodeRunError, RenderGraphContext},
     render_phase::*,
     render_resource::*,
@@ -21,9 +24,16 @@ use bevy_render::{
 use bevy_transform::{components::GlobalTransform, prelude::Transform};
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
-use bevy_utils::tracing::{error, warn};
+use bevy_utils::{
+    tracing::{error, warn},
+    FixedState, HashSet,
+};
 use nonmax::NonMaxU32;
-use std::{hash::Hash, num::NonZeroU64, ops::Range};
+use std::{
+    hash::{BuildHasher, Hash},
+    num::NonZeroU64,
+    ops::Range,
+};
 
 use crate::*;
 
@@ -39,6 +49,7 @@ pub struct ExtractedPointLight {
     pub shadow_depth_bias: f32,
     pub shadow_normal_bias: f32,
     pub spot_light_angles: Option<(f32, f32)>,
+    pub soft_shadows_enabled: bool,
 }
 
 #[derive(Component, Debug)]
@@ -53,6 +64,7 @@ pub struct ExtractedDirectionalLight {
     pub cascades: EntityHashMap<Vec<Cascade>>,
     pub frusta: EntityHashMap<Vec<Frustum>>,
     pub render_layers: RenderLayers,
+    pub soft_shadow_size: Option<f32>,
 }
 
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
@@ -151,6 +163,7 @@ bitflags::bitflags! {
     struct PointLightFlags: u32 {
         const SHADOWS_ENABLED            = 1 << 0;
         const SPOT_LIGHT_Y_NEGATIVE      = 1 << 1;
+        const SOFT_SHADOWS               = 1 << 2;
         const NONE                       = 0;
         const UNINITIALIZED              = 0xFFFF;
     }
@@ -161,6 +174,7 @@ pub struct GpuDirectionalCascade {
     view_projection: Mat4,
     texel_size: f32,
     far_bound: f32,
+    depth_bias: f32,
 }
 
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
@@ -175,6 +189,7 @@ pub struct GpuDirectionalLight {
     cascades_overlap_proportion: f32,
     depth_texture_base_index: u32,
     render_layers: u32,
+    soft_shadow_size: f32,
 }
 
 // NOTE: These must match the bit flags in bevy_pbr/src/render/mesh_view_types.wgsl!
@@ -400,6 +415,7 @@ pub fn extract_lights(
                 * point_light_texel_size
                 * std::f32::consts::SQRT_2,
             spot_light_angles: None,
+            soft_shadows_enabled: false,
         };
         point_lights_values.push((
             entity,
@@ -449,6 +465,7 @@ pub fn extract_lights(
                             * texel_size
                             * std::f32::consts::SQRT_2,
                         spot_light_angles: Some((spot_light.inner_angle, spot_light.outer_angle)),
+                        soft_shadows_enabled: spot_light.soft_shadows_enabled,
                     },
                     render_visible_entities,
                     *frustum,
@@ -490,6 +507,7 @@ pub fn extract_lights(
                 cascades: cascades.cascades.clone(),
                 frusta: frusta.frusta.clone(),
                 render_layers: maybe_layers.copied().unwrap_or_default(),
+                soft_shadow_size: directional_light.soft_shadow_size,
             },
             render_visible_entities,
         ));
@@ -627,6 +645,317 @@ pub enum LightEntity {
         light_entity: Entity,
     },
 }
+
+/// The [`StaticShadowCaster`] meshes that changed since the last frame, so that the shadow
+/// maps they are drawn in must be rendered again.
+#[derive(Resource, Default)]
+pub struct ChangedStaticShadowCasters {
+    /// The entities that moved, or whose mesh or material was replaced or modified.
+    pub entities: EntityHashSet,
+    /// The mesh assets that were modified or removed, or that aren't prepared yet.
+    pub meshes: HashSet<AssetId<Mesh>>,
+}
+
+pub fn extract_changed_static_shadow_casters(
+    mut changed_static_shadow_casters: ResMut<ChangedStaticShadowCasters>,
+    static_shadow_casters: Extract<
+        Query<
+            Entity,
+            (
+                With<StaticShadowCaster>,
+                Or<(
+                    Added<StaticShadowCaster>,
+                    Changed<GlobalTransform>,
+                    Changed<Handle<Mesh>>,
+                )>,
+            ),
+        >,
+    >,
+) {
+    changed_static_shadow_casters.entities.clear();
+    changed_static_shadow_casters.meshes.clear();
+    changed_static_shadow_casters
+        .entities
+        .extend(static_shadow_casters.iter());
+}
+
+/// Adds the [`StaticShadowCaster`]s whose material of type `M` was replaced to the
+/// [`ChangedStaticShadowCasters`].
+pub fn extract_changed_static_shadow_caster_materials<M: Material>(
+    mut changed_static_shadow_casters: ResMut<ChangedStaticShadowCasters>,
+    static_shadow_casters: Extract<Query<Entity, (With<StaticShadowCaster>, Changed<Handle<M>>)>>,
+) {
+    changed_static_shadow_casters
+        .entities
+        .extend(static_shadow_casters.iter());
+}
+
+/// Adds the meshes that were modified or removed this frame, or that are still waiting to be
+/// prepared, to the [`ChangedStaticShadowCasters`].
+///
+/// This must run before [`prepare_assets::<GpuMesh>`](bevy_render::render_asset::prepare_assets)
+/// consumes the [`ExtractedAssets`].
+pub fn collect_changed_static_shadow_caster_meshes(
+    mut changed_static_shadow_casters: ResMut<ChangedStaticShadowCasters>,
+    extracted_meshes: Res<ExtractedAssets<GpuMesh>>,
+    pending_meshes: Res<PrepareNextFrameAssets<GpuMesh>>,
+) {
+    changed_static_shadow_casters.meshes.extend(
+        extracted_meshes
+            .extracted()
+            .iter()
+            .map(|(id, _)| *id)
+            .chain(extracted_meshes.removed().iter().copied())
+            .chain(pending_meshes.ids()),
+    );
+}
+
+/// Adds the entities whose material of type `M` was modified or removed this frame, or is
+/// still waiting to be prepared, to the [`ChangedStaticShadowCasters`].
+///
+/// This must run before
+/// [`prepare_assets::<PreparedMaterial<M>>`](bevy_render::render_asset::prepare_assets)
+/// consumes the [`ExtractedAssets`].
+pub fn collect_changed_static_shadow_caster_materials<M: Material>(
+    mut changed_static_shadow_casters: ResMut<ChangedStaticShadowCasters>,
+    extracted_materials: Res<ExtractedAssets<PreparedMaterial<M>>>,
+    pending_materials: Res<PrepareNextFrameAssets<PreparedMaterial<M>>>,
+    render_material_instances: Res<RenderMaterialInstances<M>>,
+) {
+    let changed_materials: HashSet<AssetId<M>> = extracted_materials
+        .extracted()
+        .iter()
+        .map(|(id, _)| *id)
+        .chain(extracted_materials.removed().iter().copied())
+        .chain(pending_materials.ids())
+        .collect();
+    if changed_materials.is_empty() {
+        return;
+    }
+
+    changed_static_shadow_casters.entities.extend(
+        render_material_instances
+            .iter()
+            .filter(|(_, material_asset_id)| changed_materials.contains(*material_asset_id))
+            .map(|(entity, _)| *entity),
+    );
+}
+
+/// The shadow map textures of each view, kept across frames so that the layers whose
+/// contents didn't change don't need to be rendered again.
+#[derive(Resource, Default)]
+pub struct ShadowMapCache {
+    views: EntityHashMap<ViewShadowMaps>,
+}
+
+#[derive(Default)]
+struct ViewShadowMaps {
+    point_light: Option<CachedShadowMap>,
+    directional_light: Option<CachedShadowMap>,
+}
+
+struct CachedShadowMap {
+    descriptor: TextureDescriptor<'static>,
+    texture: CachedTexture,
+    /// The key of the contents drawn to each layer, or `None` if the layer must be rendered
+    /// again.
+    layer_keys: Vec<Option<u64>>,
+}
+
+/// Identifies what a shadow view renders, see [`ShadowCasters::shadow_view_key`].
+#[derive(Clone, Copy)]
+struct ShadowViewKey {
+    hash: u64,
+    caster_count: u32,
+}
+
+/// A shadow view rendering to a [`ShadowMapCache`] layer that is only kept once every one
+/// of its casters was queued with a ready pipeline, so that meshes and materials that
+/// aren't prepared yet don't leave the layer incomplete.
+#[derive(Component)]
+pub struct PendingShadowMapLayer {
+    view_entity: Entity,
+    point_light: bool,
+    layer: u32,
+    key: ShadowViewKey,
+    drawn_caster_count: u32,
+}
+
+impl PendingShadowMapLayer {
+    fn new(view_entity: Entity, point_light: bool, layer: u32, key: ShadowViewKey) -> Self {
+        Self {
+            view_entity,
+            point_light,
+            layer,
+            key,
+            drawn_caster_count: 0,
+        }
+    }
+}
+
+impl CachedShadowMap {
+    /// Returns the shadow map in `cached`, creating it again if its descriptor changed.
+    fn get_or_create<'a>(
+        cached: &'a mut Option<CachedShadowMap>,
+        render_device: &RenderDevice,
+        descriptor: TextureDescriptor<'static>,
+    ) -> &'a mut CachedShadowMap {
+        if cached
+            .as_ref()
+            .map_or(true, |cached| cached.descriptor != descriptor)
+        {
+            let texture = render_device.create_texture(&descriptor);
+            let default_view = texture.create_view(&TextureViewDescriptor::default());
+            *cached = Some(CachedShadowMap {
+                layer_keys: vec![None; descriptor.size.depth_or_array_layers as usize],
+                descriptor,
+                texture: CachedTexture {
+                    texture,
+                    default_view,
+                },
+            });
+        }
+        cached.as_mut().unwrap()
+    }
+
+    /// Returns whether `layer` already contains the contents identified by `key`, in which
+    /// case it doesn't need to be rendered. Otherwise the layer is invalidated until
+    /// [`commit_shadow_map_layers`] finds that all of its casters were drawn.
+    fn is_up_to_date(&mut self, layer: u32, key: Option<ShadowViewKey>) -> bool {
+        let layer_key = &mut self.layer_keys[layer as usize];
+        if key.is_some_and(|key| *layer_key == Some(key.hash)) {
+            return true;
+        }
+        *layer_key = None;
+        false
+    }
+}
+
+/// Keeps the shadow map layers whose casters were all queued with a ready pipeline this
+/// frame, so that they aren't rendered again until their contents change.
+pub fn commit_shadow_map_layers(
+    mut shadow_map_cache: ResMut<ShadowMapCache>,
+    pending_layers: Query<&PendingShadowMapLayer>,
+) {
+    for pending_layer in &pending_layers {
+        if pending_layer.drawn_caster_count != pending_layer.key.caster_count {
+            continue;
+        }
+        let Some(view_shadow_maps) = shadow_map_cache.views.get_mut(&pending_layer.view_entity)
+        else {
+            continue;
+        };
+        let shadow_map = if pending_layer.point_light {
+            &mut view_shadow_maps.point_light
+        } else {
+            &mut view_shadow_maps.directional_light
+        };
+        if let Some(shadow_map) = shadow_map {
+            shadow_map.layer_keys[pending_layer.layer as usize] = Some(pending_layer.key.hash);
+        }
+    }
+}
+
+/// The data needed to find out whether the contents of a shadow map changed.
+#[derive(SystemParam)]
+pub struct ShadowCasters<'w, 's> {
+    render_mesh_instances: Res<'w, RenderMeshInstances>,
+    changed_static_shadow_casters: Res<'w, ChangedStaticShadowCasters>,
+    point_light_entities: Query<'w, 's, &'static CubemapVisibleEntities, With<ExtractedPointLight>>,
+    spot_light_entities: Query<'w, 's, &'static VisibleEntities, With<ExtractedPointLight>>,
+    directional_light_entities:
+        Query<'w, 's, &'static CascadesVisibleEntities, With<ExtractedDirectionalLight>>,
+}
+
+impl ShadowCasters<'_, '_> {
+    /// Returns a key identifying what the shadow view of `light` for `view_entity` renders,
+    /// or `None` if it must be rendered every frame because it contains meshes that aren't
+    /// [`StaticShadowCaster`]s or that changed.
+    fn shadow_view_key(
+        &self,
+        view_entity: Entity,
+        light: &LightEntity,
+        view_transform: &GlobalTransform,
+        projection: &Mat4,
+    ) -> Option<ShadowViewKey> {
+        let (light_entity, index, visible_entities) = match *light {
+            LightEntity::Directional {
+                light_entity,
+                cascade_index,
+            } => (
+                light_entity,
+                cascade_index,
+                self.directional_light_entities
+                    .get(light_entity)
+                    .ok()?
+                    .entities
+                    .get(&view_entity)?
+                    .get(cascade_index)?,
+            ),
+            LightEntity::Point {
+                light_entity,
+                face_index,
+            } => (
+                light_entity,
+                face_index,
+                self.point_light_entities
+                    .get(light_entity)
+                    .ok()?
+                    .get(face_index),
+            ),
+            LightEntity::Spot { light_entity } => (
+                light_entity,
+                0,
+                self.spot_light_entities.get(light_entity).ok()?,
+            ),
+        };
+
+        // Combine the drawn meshes independently of the order they are visible in.
+        let mut caster_count = 0u32;
+        let mut casters_hash = 0u64;
+        for &entity in visible_entities.iter::<WithMesh>() {
+            let Some(mesh_instance) = self.render_mesh_instances.render_mesh_queue_data(entity)
+            else {
+                continue;
+            };
+            if !mesh_instance
+                .flags
+                .contains(RenderMeshInstanceFlags::SHADOW_CASTER)
+            {
+                continue;
+            }
+            if !mesh_instance
+                .flags
+                .contains(RenderMeshInstanceFlags::STATIC_SHADOW_CASTER)
+                || self
+                    .changed_static_shadow_casters
+                    .entities
+                    .contains(&entity)
+                || self
+                    .changed_static_shadow_casters
+                    .meshes
+                    .contains(&mesh_instance.mesh_asset_id)
+            {
+                return None;
+            }
+            caster_count += 1;
+            casters_hash = casters_hash.wrapping_add(FixedState.hash_one(entity));
+        }
+
+        let matrix_bits = |matrix: Mat4| matrix.to_cols_array().map(f32::to_bits);
+        Some(ShadowViewKey {
+            hash: FixedState.hash_one((
+                light_entity,
+                index,
+                matrix_bits(view_transform.compute_matrix()),
+                matrix_bits(*projection),
+                caster_count,
+                casters_hash,
+            )),
+            caster_count,
+        })
+    }
+}
 pub fn calculate_cluster_factors(
     near: f32,
     far: f32,
@@ -679,7 +1008,7 @@ pub(crate) fn spot_light_projection_matrix(angle: f32) -> Mat4 {
 #[allow(clippy::too_many_arguments)]
 pub fn prepare_lights(
     mut commands: Commands,
-    mut texture_cache: ResMut<TextureCache>,
+    mut shadow_map_cache: ResMut<ShadowMapCache>,
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
     mut global_light_meta: ResMut<GlobalLightMeta>,
@@ -699,6 +1028,7 @@ pub fn prepare_lights(
         AnyOf<(&CubemapFrusta, &Frustum)>,
     )>,
     directional_lights: Query<(Entity, &ExtractedDirectionalLight)>,
+    shadow_casters: ShadowCasters,
 ) {
     let views_iter = views.iter();
     let views_count = views_iter.len();
@@ -836,6 +1166,10 @@ pub fn prepare_lights(
             flags |= PointLightFlags::SHADOWS_ENABLED;
         }
 
+        if light.soft_shadows_enabled {
+            flags |= PointLightFlags::SOFT_SHADOWS;
+        }
+
         let (light_custom_data, spot_light_tan_angle) = match light.spot_light_angles {
             Some((inner, outer)) => {
                 let light_direction = light.transform.forward();
@@ -919,6 +1253,7 @@ pub fn prepare_lights(
             cascades_overlap_proportion: light.cascade_shadow_config.overlap_proportion,
             depth_texture_base_index: num_directional_cascades_enabled as u32,
             render_layers: light.render_layers.bits(),
+            soft_shadow_size: light.soft_shadow_size.unwrap_or(0.0),
         };
         if index < directional_shadow_enabled_count {
             num_directional_cascades_enabled += num_cascades;
@@ -930,9 +1265,15 @@ pub fn prepare_lights(
         .gpu_point_lights
         .write_buffer(&render_device, &render_queue);
 
+    shadow_map_cache
+        .views
+        .retain(|entity, _| views.contains(*entity));
+
     // set up light data for each view
     for (entity, extracted_view, clusters) in &views {
-        let point_light_depth_texture = texture_cache.get(
+        let view_shadow_maps = shadow_map_cache.views.entry(entity).or_default();
+        let point_light_shadow_map_cache = CachedShadowMap::get_or_create(
+            &mut view_shadow_maps.point_light,
             &render_device,
             TextureDescriptor {
                 size: Extent3d {
@@ -949,7 +1290,9 @@ pub fn prepare_lights(
                 view_formats: &[],
             },
         );
-        let directional_light_depth_texture = texture_cache.get(
+        let point_light_depth_texture = point_light_shadow_map_cache.texture.clone();
+        let directional_light_shadow_map_cache = CachedShadowMap::get_or_create(
+            &mut view_shadow_maps.directional_light,
             &render_device,
             TextureDescriptor {
                 size: Extent3d {
@@ -970,6 +1313,7 @@ pub fn prepare_lights(
                 view_formats: &[],
             },
         );
+        let directional_light_depth_texture = directional_light_shadow_map_cache.texture.clone();
         let mut view_lights = Vec::new();
 
         let is_orthographic = extracted_view.projection.w_axis.w == 1.0;
@@ -1022,6 +1366,23 @@ pub fn prepare_lights(
                 .zip(&point_light_frusta.unwrap().frusta)
                 .enumerate()
             {
+                let layer = (light_index * 6 + face_index) as u32;
+                let shadow_light = LightEntity::Point {
+                    light_entity,
+                    face_index,
+                };
+                let view_transform = view_translation * *view_rotation;
+                let key = shadow_casters.shadow_view_key(
+                    entity,
+                    &shadow_light,
+                    &view_transform,
+                    &cube_face_projection,
+                );
+                // The face still contains what would be rendered to it.
+                if point_light_shadow_map_cache.is_up_to_date(layer, key) {
+                    continue;
+                }
+
                 let depth_texture_view =
                     point_light_depth_texture
                         .texture
@@ -1032,7 +1393,7 @@ pub fn prepare_lights(
                             aspect: TextureAspect::All,
                             base_mip_level: 0,
                             mip_level_count: None,
-                            base_array_layer: (light_index * 6 + face_index) as u32,
+                            base_array_layer: layer,
                             array_layer_count: Some(1u32),
                         });
 
@@ -1053,7 +1414,7 @@ pub fn prepare_lights(
                                 point_light_shadow_map.size as u32,
                                 point_light_shadow_map.size as u32,
                             ),
-                            transform: view_translation * *view_rotation,
+                            transform: view_transform,
                             view_projection: None,
                             projection: cube_face_projection,
                             hdr: false,
@@ -1061,12 +1422,14 @@ pub fn prepare_lights(
                         },
                         *frustum,
                         BinnedRenderPhase::<Shadow>::default(),
-                        LightEntity::Point {
-                            light_entity,
-                            face_index,
-                        },
+                        shadow_light,
                     ))
                     .id();
+                if let Some(key) = key {
+                    commands
+                        .entity(view_light_entity)
+                        .insert(PendingShadowMapLayer::new(entity, true, layer, key));
+                }
                 view_lights.push(view_light_entity);
             }
         }
@@ -1085,6 +1448,18 @@ pub fn prepare_lights(
                 [point_light_count..point_light_count + spot_light_shadow_maps_count] are spot lights").1;
             let spot_projection = spot_light_projection_matrix(angle);
 
+            let layer = (num_directional_cascades_enabled + light_index) as u32;
+            let shadow_light = LightEntity::Spot { light_entity };
+            let key = shadow_casters.shadow_view_key(
+                entity,
+                &shadow_light,
+                &spot_view_transform,
+                &spot_projection,
+            );
+            if directional_light_shadow_map_cache.is_up_to_date(layer, key) {
+                continue;
+            }
+
             let depth_texture_view =
                 directional_light_depth_texture
                     .texture
@@ -1095,7 +1470,7 @@ pub fn prepare_lights(
                         aspect: TextureAspect::All,
                         base_mip_level: 0,
                         mip_level_count: None,
-                        base_array_layer: (num_directional_cascades_enabled + light_index) as u32,
+                        base_array_layer: layer,
                         array_layer_count: Some(1u32),
                     });
 
@@ -1120,9 +1495,14 @@ pub fn prepare_lights(
                     },
                     *spot_light_frustum.unwrap(),
                     BinnedRenderPhase::<Shadow>::default(),
-                    LightEntity::Spot { light_entity },
+                    shadow_light,
                 ))
                 .id();
+            if let Some(key) = key {
+                commands
+                    .entity(view_light_entity)
+                    .insert(PendingShadowMapLayer::new(entity, false, layer, key));
+            }
 
             view_lights.push(view_light_entity);
         }
@@ -1156,8 +1536,32 @@ pub fn prepare_lights(
                         view_projection: cascade.view_projection,
                         texel_size: cascade.texel_size,
                         far_bound: *bound,
+                        depth_bias: light
+                            .cascade_shadow_config
+                            .depth_biases
+                            .get(cascade_index)
+                            .copied()
+                            .unwrap_or(light.shadow_depth_bias),
                     };
 
+                let layer = directional_depth_texture_array_index;
+                directional_depth_texture_array_index += 1;
+
+                let shadow_light = LightEntity::Directional {
+                    light_entity,
+                    cascade_index,
+                };
+                let view_transform = GlobalTransform::from(cascade.view_transform);
+                let key = shadow_casters.shadow_view_key(
+                    entity,
+                    &shadow_light,
+                    &view_transform,
+                    &cascade.projection,
+                );
+                if directional_light_shadow_map_cache.is_up_to_date(layer, key) {
+                    continue;
+                }
+
                 let depth_texture_view =
                     directional_light_depth_texture
                         .texture
@@ -1168,10 +1572,9 @@ pub fn prepare_lights(
                             aspect: TextureAspect::All,
                             base_mip_level: 0,
                             mip_level_count: None,
-                            base_array_layer: directional_depth_texture_array_index,
+                            base_array_layer: layer,
                             array_layer_count: Some(1u32),
                         });
-                directional_depth_texture_array_index += 1;
 
                 let mut frustum = *frustum;
                 // Push the near clip plane out to infinity for directional lights
@@ -1192,7 +1595,7 @@ pub fn prepare_lights(
                                 directional_light_shadow_map.size as u32,
                                 directional_light_shadow_map.size as u32,
                             ),
-                            transform: GlobalTransform::from(cascade.view_transform),
+                            transform: view_transform,
                             projection: cascade.projection,
                             view_projection: Some(cascade.view_projection),
                             hdr: false,
@@ -1200,12 +1603,14 @@ pub fn prepare_lights(
                         },
                         frustum,
                         BinnedRenderPhase::<Shadow>::default(),
-                        LightEntity::Directional {
-                            light_entity,
-                            cascade_index,
-                        },
+                        shadow_light,
                     ))
                     .id();
+                if let Some(key) = key {
+                    commands
+                        .entity(view_light_entity)
+                        .insert(PendingShadowMapLayer::new(entity, false, layer, key));
+                }
                 view_lights.push(view_light_entity);
             }
         }
@@ -1611,7 +2016,11 @@ pub fn queue_shadows<M: Material>(
     pipeline_cache: Res<PipelineCache>,
     render_lightmaps: Res<RenderLightmaps>,
     view_lights: Query<(Entity, &ViewLightEntities)>,
-    mut view_light_shadow_phases: Query<(&LightEntity, &mut BinnedRenderPhase<Shadow>)>,
+    mut view_light_shadow_phases: Query<(
+        &LightEntity,
+        &mut BinnedRenderPhase<Shadow>,
+        Option<&mut PendingShadowMapLayer>,
+    )>,
     point_light_entities: Query<&CubemapVisibleEntities, With<ExtractedPointLight>>,
     directional_light_entities: Query<&CascadesVisibleEntities, With<ExtractedDirectionalLight>>,
     spot_light_entities: Query<&VisibleEntities, With<ExtractedPointLight>>,
@@ -1621,7 +2030,7 @@ pub fn queue_shadows<M: Material>(
     for (entity, view_lights) in &view_lights {
         let draw_shadow_mesh = shadow_draw_functions.read().id::<DrawPrepass<M>>();
         for view_light_entity in view_lights.lights.iter().copied() {
-            let (light_entity, mut shadow_phase) =
+            let (light_entity, mut shadow_phase, mut pending_layer) =
                 view_light_shadow_phases.get_mut(view_light_entity).unwrap();
             let is_directional_light = matches!(light_entity, LightEntity::Directional { .. });
             let visible_entities = match light_entity {
@@ -1711,15 +2120,30 @@ pub fn queue_shadows<M: Material>(
                     }
                 };
 
+                // The cached layer is only kept once every caster was actually drawn to it.
+                if let Some(pending_layer) = pending_layer.as_deref_mut() {
+                    if pipeline_cache.get_render_pipeline(pipeline_id).is_some() {
+                        pending_layer.drawn_caster_count += 1;
+                    }
+                }
+
                 mesh_instance
                     .material_bind_group_id
                     .set(material.get_bind_group_id());
 
+                // The default prepass shaders only read the material when they
+                // may discard fragments.
+                let reads_material = mesh_key.contains(MeshPipelineKey::MAY_DISCARD)
+                    || prepass_pipeline.prepass_material_vertex_shader.is_some()
+                    || prepass_pipeline.prepass_material_fragment_shader.is_some();
+
                 shadow_phase.add(
                     ShadowBinKey {
                         draw_function: draw_shadow_mesh,
                         pipeline: pipeline_id,
                         asset_id: mesh_instance.mesh_asset_id,
+                        material_bind_group_id: material.get_bind_group_id().0,
+                        reads_material,
                     },
                     entity,
                     mesh_instance.should_batch(),
@@ -1746,6 +2170,16 @@ pub struct ShadowBinKey {
 
     /// The mesh.
     pub asset_id: AssetId<Mesh>,
+
+    /// The ID of the material bind group.
+    pub material_bind_group_id: Option<BindGroupId>,
+
+    /// Whether the shaders read the material, for example to discard
+    /// alpha-masked fragments.
+    ///
+    /// Bins of materials that aren't read are merged, see
+    /// [`BinnedPhaseItem::can_merge_bins`].
+    pub reads_material: bool,
 }
 
 impl PhaseItem for Shadow {
@@ -1797,6 +2231,17 @@ impl BinnedPhaseItem for Shadow {
             dynamic_offset,
         }
     }
+
+    #[inline]
+    fn can_merge_bins(previous: &Self::BinKey, next: &Self::BinKey) -> bool {
+        // The material bind group is only set from the first bin, which is
+        // fine as long as neither bin reads it.
+        previous.pipeline == next.pipeline
+            && previous.draw_function == next.draw_function
+            && previous.asset_id == next.asset_id
+            && !previous.reads_material
+            && !next.reads_material
+    }
 }
 
 impl CachedRenderPipelinePhaseItem for Shadow {
@@ -1,9 +1,14 @@
-use std::mem;
+use std::{
+    mem,
+    ops::Range,
+    sync::atomic::{AtomicU32, Ordering},
+};
 
 use bevy_asset::{load_internal_asset, AssetId};
 use bevy_core_pipeline::{
     core_3d::{AlphaMask3d, Opaque3d, Transmissive3d, Transparent3d, CORE_3D_DEPTH_FORMAT},
     deferred::{AlphaMask3dDeferred, Opaque3dDeferred},
+    oit::{OrderIndependentTransparent3d, OIT_ACCUMULATION_FORMAT, OIT_REVEALAGE_FORMAT},
 };
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::entity::EntityHashMap;
@@ -15,8 +20,9 @@ use bevy_ecs::{
 use bevy_math::{Affine3, Rect, UVec2, Vec3, Vec4};
 use bevy_render::{
     batching::{
-        clear_batched_instance_buffers, gpu_preprocessing, no_gpu_preprocessing, GetBatchData,
-        GetFullBatchData, NoAutomaticBatching,
+        clear_batched_instance_buffers,
+        gpu_preprocessing::{self, IndirectParameters, IndirectParametersBuffer},
+        no_gpu_preprocessing, GetBatchData, GetFullBatchData, NoAutomaticBatching,
     },
     mesh::*,
     render_asset::RenderAssets,
@@ -27,7 +33,10 @@ use bevy_render::{
     render_resource::*,
     renderer::{RenderDevice, RenderQueue},
     texture::{BevyDefault, DefaultImageSampler, ImageSampler, TextureFormatPixelInfo},
-    view::{prepare_view_targets, ViewTarget, ViewUniformOffset, ViewVisibility},
+    view::{
+        prepare_view_targets, MeshLod, ViewTarget, ViewUniformOffset, ViewVisibility,
+        VisibleMeshLods,
+    },
     Extract,
 };
 use bevy_transform::components::GlobalTransform;
@@ -123,13 +132,14 @@ impl Plugin for MeshRenderPlugin {
             (no_automatic_skin_batching, no_automatic_morph_batching),
         )
         .add_plugins((
-            BinnedRenderPhasePlugin::<Opaque3d, MeshPipeline>::default(),
-            BinnedRenderPhasePlugin::<AlphaMask3d, MeshPipeline>::default(),
-            BinnedRenderPhasePlugin::<Shadow, MeshPipeline>::default(),
-            BinnedRenderPhasePlugin::<Opaque3dDeferred, MeshPipeline>::default(),
-            BinnedRenderPhasePlugin::<AlphaMask3dDeferred, MeshPipeline>::default(),
-            SortedRenderPhasePlugin::<Transmissive3d, MeshPipeline>::default(),
-            SortedRenderPhasePlugin::<Transparent3d, MeshPipeline>::default(),
+            BinnedRenderPhasePlugin::<Opaque3d, MeshPipeline>::parallel(),
+            BinnedRenderPhasePlugin::<AlphaMask3d, MeshPipeline>::parallel(),
+            BinnedRenderPhasePlugin::<Shadow, MeshPipeline>::parallel(),
+            BinnedRenderPhasePlugin::<Opaque3dDeferred, MeshPipeline>::parallel(),
+            BinnedRenderPhasePlugin::<AlphaMask3dDeferred, MeshPipeline>::parallel(),
+            SortedRenderPhasePlugin::<Transmissive3d, MeshPipeline>::parallel(),
+            SortedRenderPhasePlugin::<Transparent3d, MeshPipeline>::parallel(),
+            SortedRenderPhasePlugin::<OrderIndependentTransparent3d, MeshPipeline>::parallel(),
         ));
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
@@ -163,11 +173,18 @@ impl Plugin for MeshRenderPlugin {
             let render_device = render_app.world().resource::<RenderDevice>();
             let use_gpu_instance_buffer_builder = self.use_gpu_instance_buffer_builder
                 && gpu_preprocessing::can_preprocess_on_gpu(render_device);
+            // Batches are drawn indirectly whenever the device allows it.
+            let use_indirect_drawing = use_gpu_instance_buffer_builder
+                && gpu_preprocessing::can_draw_indirect(render_device);
 
             let render_mesh_instances = RenderMeshInstances::new(use_gpu_instance_buffer_builder);
             render_app.insert_resource(render_mesh_instances);
 
             if use_gpu_instance_buffer_builder {
+                if use_indirect_drawing {
+                    render_app.init_resource::<IndirectParametersBuffer<MeshPipeline>>();
+                }
+
                 render_app
                     .init_resource::<gpu_preprocessing::BatchedInstanceBuffers<MeshUniform, MeshInputUniform>>(
                     )
@@ -178,6 +195,8 @@ impl Plugin for MeshRenderPlugin {
                     .add_systems(
                         Render,
                         (
+                            write_material_bind_group_slots
+                                .in_set(RenderSet::PrepareResources),
                             gpu_preprocessing::write_batched_instance_buffers::<MeshPipeline>
                                 .in_set(RenderSet::PrepareResourcesFlush),
                             gpu_preprocessing::delete_old_work_item_buffers::<MeshPipeline>
@@ -256,6 +275,9 @@ pub struct MeshUniform {
     //
     // (MSB: most significant bit; LSB: least significant bit.)
     pub lightmap_uv_rect: UVec2,
+    /// The slot of the mesh's material in the bind group shared by all the
+    /// materials of its type, if they're drawn bindless.
+    pub material_bind_group_slot: u32,
 }
 
 /// Information that has to be transferred from CPU to GPU in order to produce
@@ -286,19 +308,36 @@ pub struct MeshInputUniform {
     ///
     /// This is used for TAA. If not present, this will be `u32::MAX`.
     pub previous_input_index: u32,
+    /// The slot of the mesh's material in the bind group shared by all the
+    /// materials of its type, if they're drawn bindless.
+    ///
+    /// This is filled in by [`write_material_bind_group_slots`].
+    pub material_bind_group_slot: u32,
+    _padding_0: u32,
+    _padding_1: u32,
+    _padding_2: u32,
 }
 
 impl MeshUniform {
-    pub fn new(mesh_transforms: &MeshTransforms, maybe_lightmap_uv_rect: Option<Rect>) -> Self {
+    pub fn new(
+        mesh_transforms: &MeshTransforms,
+        material_bind_group_slot: u32,
+        maybe_lightmap_uv_rect: Option<Rect>,
+    ) -> Self {
         let (inverse_transpose_model_a, inverse_transpose_model_b) =
             mesh_transforms.transform.inverse_transpose_3x3();
+        let mut flags = mesh_transforms.flags;
+        if maybe_lightmap_uv_rect.is_some() {
+            flags |= MeshFlags::LIGHTMAPPED.bits();
+        }
         Self {
             transform: mesh_transforms.transform.to_transpose(),
             previous_transform: mesh_transforms.previous_transform.to_transpose(),
             lightmap_uv_rect: lightmap::pack_lightmap_uv_rect(maybe_lightmap_uv_rect),
             inverse_transpose_model_a,
             inverse_transpose_model_b,
-            flags: mesh_transforms.flags,
+            flags,
+            material_bind_group_slot,
         }
     }
 }
@@ -309,6 +348,9 @@ bitflags::bitflags! {
     pub struct MeshFlags: u32 {
         const SHADOW_RECEIVER             = 1 << 0;
         const TRANSMITTED_SHADOW_RECEIVER = 1 << 1;
+        // Indicates that the mesh has a lightmap, which replaces any other
+        // diffuse indirect light.
+        const LIGHTMAPPED                 = 1 << 2;
         // Indicates the sign of the determinant of the 3x3 model matrix. If the sign is positive,
         // then the flag should be set, else it should not be set.
         const SIGN_DETERMINANT_MODEL_3X3  = 1 << 31;
@@ -349,6 +391,12 @@ bitflags::bitflags! {
         const AUTOMATIC_BATCHING      = 1 << 1;
         /// The mesh had a transform last frame and so is eligible for TAA.
         const HAVE_PREVIOUS_TRANSFORM = 1 << 2;
+        /// The mesh has a [`MeshLod`], so the mesh rendered in each view is the
+        /// one in the [`VisibleMeshLods`] of the view.
+        const HAS_MESH_LOD            = 1 << 3;
+        /// The mesh has a [`StaticShadowCaster`], so shadow maps it is drawn
+        /// in can be reused until it changes.
+        const STATIC_SHADOW_CASTER    = 1 << 4;
     }
 }
 
@@ -392,6 +440,11 @@ pub struct RenderMeshInstanceShared {
     ///
     /// This is filled in during [`crate::material::queue_material_meshes`].
     pub material_bind_group_id: AtomicMaterialBindGroupId,
+    /// A slot for the slot of the material in the bindless material bind
+    /// group, if the material is drawn bindless.
+    ///
+    /// This is filled in during [`crate::material::queue_material_meshes`].
+    pub material_bind_group_slot: AtomicU32,
     /// Various flags.
     pub flags: RenderMeshInstanceFlags,
 }
@@ -427,10 +480,16 @@ impl RenderMeshInstanceShared {
         previous_transform: Option<&PreviousGlobalTransform>,
         handle: &Handle<Mesh>,
         not_shadow_caster: bool,
+        static_shadow_caster: bool,
         no_automatic_batching: bool,
+        has_mesh_lod: bool,
     ) -> Self {
         let mut mesh_instance_flags = RenderMeshInstanceFlags::empty();
         mesh_instance_flags.set(RenderMeshInstanceFlags::SHADOW_CASTER, !not_shadow_caster);
+        mesh_instance_flags.set(
+            RenderMeshInstanceFlags::STATIC_SHADOW_CASTER,
+            static_shadow_caster,
+        );
         mesh_instance_flags.set(
             RenderMeshInstanceFlags::AUTOMATIC_BATCHING,
             !no_automatic_batching,
@@ -439,12 +498,14 @@ impl RenderMeshInstanceShared {
             RenderMeshInstanceFlags::HAVE_PREVIOUS_TRANSFORM,
             previous_transform.is_some(),
         );
+        mesh_instance_flags.set(RenderMeshInstanceFlags::HAS_MESH_LOD, has_mesh_lod);
 
         RenderMeshInstanceShared {
             mesh_asset_id: handle.id(),
 
             flags: mesh_instance_flags,
             material_bind_group_id: AtomicMaterialBindGroupId::default(),
+            material_bind_group_slot: AtomicU32::default(),
         }
     }
 
@@ -455,6 +516,29 @@ impl RenderMeshInstanceShared {
             .contains(RenderMeshInstanceFlags::AUTOMATIC_BATCHING)
             && self.material_bind_group_id.get().is_some()
     }
+
+    /// Returns true if this entity can be batched with others in sorted render
+    /// phases.
+    ///
+    /// Batches of sorted phase items are formed across views, so entities with
+    /// a [`MeshLod`], which may render a different mesh in each view, are never
+    /// batched there.
+    pub fn should_batch_sorted(&self) -> bool {
+        self.should_batch() && !self.flags.contains(RenderMeshInstanceFlags::HAS_MESH_LOD)
+    }
+
+    /// Returns the mesh to render for this entity in a view with the given
+    /// [`VisibleMeshLods`].
+    pub fn mesh_asset_id_for_view(
+        &self,
+        entity: Entity,
+        visible_mesh_lods: Option<&VisibleMeshLods>,
+    ) -> AssetId<Mesh> {
+        match visible_mesh_lods {
+            Some(visible_mesh_lods) => visible_mesh_lods.mesh(entity, self.mesh_asset_id),
+            None => self.mesh_asset_id,
+        }
+    }
 }
 
 /// Information that the render world keeps about each entity that contains a
@@ -584,7 +668,9 @@ pub fn extract_meshes_for_cpu_building(
             Has<NotShadowReceiver>,
             Has<TransmittedShadowReceiver>,
             Has<NotShadowCaster>,
+            Has<StaticShadowCaster>,
             Has<NoAutomaticBatching>,
+            Has<MeshLod>,
         )>,
     >,
 ) {
@@ -598,7 +684,9 @@ pub fn extract_meshes_for_cpu_building(
             not_shadow_receiver,
             transmitted_receiver,
             not_shadow_caster,
+            static_shadow_caster,
             no_automatic_batching,
+            has_mesh_lod,
         )| {
             if !view_visibility.get() {
                 return;
@@ -611,7 +699,9 @@ pub fn extract_meshes_for_cpu_building(
                 previous_transform,
                 handle,
                 not_shadow_caster,
+                static_shadow_caster,
                 no_automatic_batching,
+                has_mesh_lod,
             );
 
             render_mesh_instance_queues.scope(|queue| {
@@ -672,7 +762,9 @@ pub fn extract_meshes_for_gpu_building(
             Has<NotShadowReceiver>,
             Has<TransmittedShadowReceiver>,
             Has<NotShadowCaster>,
+            Has<StaticShadowCaster>,
             Has<NoAutomaticBatching>,
+            Has<MeshLod>,
         )>,
     >,
 ) {
@@ -687,20 +779,27 @@ pub fn extract_meshes_for_gpu_building(
             not_shadow_receiver,
             transmitted_receiver,
             not_shadow_caster,
+            static_shadow_caster,
             no_automatic_batching,
+            has_mesh_lod,
         )| {
             if !view_visibility.get() {
                 return;
             }
 
-            let mesh_flags =
+            let mut mesh_flags =
                 MeshFlags::from_components(transform, not_shadow_receiver, transmitted_receiver);
+            if lightmap.is_some() {
+                mesh_flags |= MeshFlags::LIGHTMAPPED;
+            }
 
             let shared = RenderMeshInstanceShared::from_components(
                 previous_transform,
                 handle,
                 not_shadow_caster,
+                static_shadow_caster,
                 no_automatic_batching,
+                has_mesh_lod,
             );
 
             let lightmap_uv_rect =
@@ -728,6 +827,32 @@ pub fn extract_meshes_for_gpu_building(
     );
 }
 
+/// Copies the material bind group slots that the materials recorded for each
+/// mesh while queuing into the [`MeshInputUniform`]s, when GPU mesh uniforms
+/// are built.
+pub fn write_material_bind_group_slots(
+    render_mesh_instances: Res<RenderMeshInstances>,
+    mut batched_instance_buffers: ResMut<
+        gpu_preprocessing::BatchedInstanceBuffers<MeshUniform, MeshInputUniform>,
+    >,
+) {
+    let RenderMeshInstances::GpuBuilding(ref render_mesh_instances) = *render_mesh_instances else {
+        return;
+    };
+
+    let current_input_buffer = batched_instance_buffers.current_input_buffer.values_mut();
+    for render_mesh_instance in render_mesh_instances.values() {
+        let Some(mesh_input_uniform) = current_input_buffer
+            .get_mut(u32::from(render_mesh_instance.current_uniform_index) as usize)
+        else {
+            continue;
+        };
+        mesh_input_uniform.material_bind_group_slot = render_mesh_instance
+            .material_bind_group_slot
+            .load(Ordering::Relaxed);
+    }
+}
+
 /// Creates the [`RenderMeshInstanceGpu`]s and [`MeshInputUniform`]s when GPU
 /// mesh uniforms are built.
 fn collect_meshes_for_gpu_building(
@@ -783,6 +908,10 @@ fn collect_meshes_for_gpu_building(
                     Some(previous_input_index) => previous_input_index.into(),
                     None => u32::MAX,
                 },
+                material_bind_group_slot: 0,
+                _padding_0: 0,
+                _padding_1: 0,
+                _padding_2: 0,
             }) as u32;
 
             // Record the [`RenderMeshInstance`].
@@ -917,7 +1046,11 @@ impl MeshPipeline {
 }
 
 impl GetBatchData for MeshPipeline {
-    type Param = (SRes<RenderMeshInstances>, SRes<RenderLightmaps>);
+    type Param = (
+        SRes<RenderMeshInstances>,
+        SRes<RenderLightmaps>,
+        SRes<RenderAssets<GpuMesh>>,
+    );
     // The material bind group ID, the mesh ID, and the lightmap ID,
     // respectively.
     type CompareData = (MaterialBindGroupId, AssetId<Mesh>, Option<AssetId<Image>>);
@@ -925,7 +1058,7 @@ impl GetBatchData for MeshPipeline {
     type BufferData = MeshUniform;
 
     fn get_batch_data(
-        (mesh_instances, lightmaps): &SystemParamItem<Self::Param>,
+        (mesh_instances, lightmaps, _): &SystemParamItem<Self::Param>,
         entity: Entity,
     ) -> Option<(Self::BufferData, Option<Self::CompareData>)> {
         let RenderMeshInstances::CpuBuilding(ref mesh_instances) = **mesh_instances else {
@@ -941,9 +1074,12 @@ impl GetBatchData for MeshPipeline {
         Some((
             MeshUniform::new(
                 &mesh_instance.transforms,
+                mesh_instance
+                    .material_bind_group_slot
+                    .load(Ordering::Relaxed),
                 maybe_lightmap.map(|lightmap| lightmap.uv_rect),
             ),
-            mesh_instance.should_batch().then_some((
+            mesh_instance.should_batch_sorted().then_some((
                 mesh_instance.material_bind_group_id.get(),
                 mesh_instance.mesh_asset_id,
                 maybe_lightmap.map(|lightmap| lightmap.image),
@@ -956,7 +1092,7 @@ impl GetFullBatchData for MeshPipeline {
     type BufferInputData = MeshInputUniform;
 
     fn get_index_and_compare_data(
-        (mesh_instances, lightmaps): &SystemParamItem<Self::Param>,
+        (mesh_instances, lightmaps, _): &SystemParamItem<Self::Param>,
         entity: Entity,
     ) -> Option<(NonMaxU32, Option<Self::CompareData>)> {
         // This should only be called during GPU building.
@@ -973,7 +1109,7 @@ impl GetFullBatchData for MeshPipeline {
 
         Some((
             mesh_instance.current_uniform_index,
-            mesh_instance.should_batch().then_some((
+            mesh_instance.should_batch_sorted().then_some((
                 mesh_instance.material_bind_group_id.get(),
                 mesh_instance.mesh_asset_id,
                 maybe_lightmap.map(|lightmap| lightmap.image),
@@ -982,7 +1118,7 @@ impl GetFullBatchData for MeshPipeline {
     }
 
     fn get_binned_batch_data(
-        (mesh_instances, lightmaps): &SystemParamItem<Self::Param>,
+        (mesh_instances, lightmaps, _): &SystemParamItem<Self::Param>,
         entity: Entity,
     ) -> Option<Self::BufferData> {
         let RenderMeshInstances::CpuBuilding(ref mesh_instances) = **mesh_instances else {
@@ -996,12 +1132,15 @@ impl GetFullBatchData for MeshPipeline {
 
         Some(MeshUniform::new(
             &mesh_instance.transforms,
+            mesh_instance
+                .material_bind_group_slot
+                .load(Ordering::Relaxed),
             maybe_lightmap.map(|lightmap| lightmap.uv_rect),
         ))
     }
 
     fn get_binned_index(
-        (mesh_instances, _): &SystemParamItem<Self::Param>,
+        (mesh_instances, _, _): &SystemParamItem<Self::Param>,
         entity: Entity,
     ) -> Option<NonMaxU32> {
         // This should only be called during GPU building.
@@ -1017,6 +1156,32 @@ impl GetFullBatchData for MeshPipeline {
             .get(&entity)
             .map(|entity| entity.current_uniform_index)
     }
+
+    fn get_batch_indirect_parameters(
+        (mesh_instances, _, meshes): &SystemParamItem<Self::Param>,
+        representative_entity: Entity,
+        instance_range: Range<u32>,
+    ) -> Option<IndirectParameters> {
+        // The mesh of entities with a `MeshLod` depends on the view, so their
+        // batches are drawn directly.
+        let mesh_instance = mesh_instances.render_mesh_queue_data(representative_entity)?;
+        if mesh_instance
+            .flags
+            .contains(RenderMeshInstanceFlags::HAS_MESH_LOD)
+        {
+            return None;
+        }
+        let gpu_mesh = meshes.get(mesh_instance.mesh_asset_id)?;
+
+        Some(match gpu_mesh.buffer_info {
+            GpuBufferInfo::Indexed { count, .. } => {
+                IndirectParameters::indexed(count, instance_range)
+            }
+            GpuBufferInfo::NonIndexed => {
+                IndirectParameters::non_indexed(gpu_mesh.vertex_count, instance_range)
+            }
+        })
+    }
 }
 
 bitflags::bitflags! {
@@ -1024,7 +1189,7 @@ bitflags::bitflags! {
     #[repr(transparent)]
     // NOTE: Apparently quadro drivers support up to 64x MSAA.
     /// MSAA uses the highest 3 bits for the MSAA log2(sample count) to support up to 128x MSAA.
-    pub struct MeshPipelineKey: u32 {
+    pub struct MeshPipelineKey: u64 {
         // Nothing
         const NONE                              = 0;
 
@@ -1048,7 +1213,8 @@ bitflags::bitflags! {
         const READS_VIEW_TRANSMISSION_TEXTURE   = 1 << 12;
         const LIGHTMAPPED                       = 1 << 13;
         const IRRADIANCE_VOLUME                 = 1 << 14;
-        const LAST_FLAG                         = Self::IRRADIANCE_VOLUME.bits();
+        const ORDER_INDEPENDENT_TRANSPARENCY    = 1 << 15;
+        const LAST_FLAG                         = Self::ORDER_INDEPENDENT_TRANSPARENCY.bits();
 
         // Bitfields
         const BLEND_RESERVED_BITS               = Self::BLEND_MASK_BITS << Self::BLEND_SHIFT_BITS; // ← Bitmask reserving bits for the blend state
@@ -1091,31 +1257,31 @@ bitflags::bitflags! {
 }
 
 impl MeshPipelineKey {
-    const MSAA_MASK_BITS: u32 = 0b111;
+    const MSAA_MASK_BITS: u64 = 0b111;
     const MSAA_SHIFT_BITS: u32 = Self::LAST_FLAG.bits().trailing_zeros() + 1;
 
-    const BLEND_MASK_BITS: u32 = 0b11;
+    const BLEND_MASK_BITS: u64 = 0b11;
     const BLEND_SHIFT_BITS: u32 = Self::MSAA_MASK_BITS.count_ones() + Self::MSAA_SHIFT_BITS;
 
-    const TONEMAP_METHOD_MASK_BITS: u32 = 0b111;
+    const TONEMAP_METHOD_MASK_BITS: u64 = 0b111;
     const TONEMAP_METHOD_SHIFT_BITS: u32 =
         Self::BLEND_MASK_BITS.count_ones() + Self::BLEND_SHIFT_BITS;
 
-    const SHADOW_FILTER_METHOD_MASK_BITS: u32 = 0b11;
+    const SHADOW_FILTER_METHOD_MASK_BITS: u64 = 0b11;
     const SHADOW_FILTER_METHOD_SHIFT_BITS: u32 =
         Self::TONEMAP_METHOD_MASK_BITS.count_ones() + Self::TONEMAP_METHOD_SHIFT_BITS;
 
-    const VIEW_PROJECTION_MASK_BITS: u32 = 0b11;
+    const VIEW_PROJECTION_MASK_BITS: u64 = 0b11;
     const VIEW_PROJECTION_SHIFT_BITS: u32 =
         Self::SHADOW_FILTER_METHOD_MASK_BITS.count_ones() + Self::SHADOW_FILTER_METHOD_SHIFT_BITS;
 
-    const SCREEN_SPACE_SPECULAR_TRANSMISSION_MASK_BITS: u32 = 0b11;
+    const SCREEN_SPACE_SPECULAR_TRANSMISSION_MASK_BITS: u64 = 0b11;
     const SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS: u32 =
         Self::VIEW_PROJECTION_MASK_BITS.count_ones() + Self::VIEW_PROJECTION_SHIFT_BITS;
 
     pub fn from_msaa_samples(msaa_samples: u32) -> Self {
-        let msaa_bits =
-            (msaa_samples.trailing_zeros() & Self::MSAA_MASK_BITS) << Self::MSAA_SHIFT_BITS;
+        let msaa_bits = (u64::from(msaa_samples.trailing_zeros()) & Self::MSAA_MASK_BITS)
+            << Self::MSAA_SHIFT_BITS;
         Self::from_bits_retain(msaa_bits)
     }
 
@@ -1128,11 +1294,11 @@ impl MeshPipelineKey {
     }
 
     pub fn msaa_samples(&self) -> u32 {
-        1 << ((self.bits() >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS)
+        1 << ((self.bits() >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS) as u32
     }
 
     pub fn from_primitive_topology(primitive_topology: PrimitiveTopology) -> Self {
-        let primitive_topology_bits = ((primitive_topology as u32)
+        let primitive_topology_bits = ((primitive_topology as u64)
             & BaseMeshPipelineKey::PRIMITIVE_TOPOLOGY_MASK_BITS)
             << BaseMeshPipelineKey::PRIMITIVE_TOPOLOGY_SHIFT_BITS;
         Self::from_bits_retain(primitive_topology_bits)
@@ -1143,11 +1309,11 @@ impl MeshPipelineKey {
             >> BaseMeshPipelineKey::PRIMITIVE_TOPOLOGY_SHIFT_BITS)
             & BaseMeshPipelineKey::PRIMITIVE_TOPOLOGY_MASK_BITS;
         match primitive_topology_bits {
-            x if x == PrimitiveTopology::PointList as u32 => PrimitiveTopology::PointList,
-            x if x == PrimitiveTopology::LineList as u32 => PrimitiveTopology::LineList,
-            x if x == PrimitiveTopology::LineStrip as u32 => PrimitiveTopology::LineStrip,
-            x if x == PrimitiveTopology::TriangleList as u32 => PrimitiveTopology::TriangleList,
-            x if x == PrimitiveTopology::TriangleStrip as u32 => PrimitiveTopology::TriangleStrip,
+            x if x == PrimitiveTopology::PointList as u64 => PrimitiveTopology::PointList,
+            x if x == PrimitiveTopology::LineList as u64 => PrimitiveTopology::LineList,
+            x if x == PrimitiveTopology::LineStrip as u64 => PrimitiveTopology::LineStrip,
+            x if x == PrimitiveTopology::TriangleList as u64 => PrimitiveTopology::TriangleList,
+            x if x == PrimitiveTopology::TriangleStrip as u64 => PrimitiveTopology::TriangleStrip,
             _ => PrimitiveTopology::default(),
         }
     }
@@ -1425,12 +1591,59 @@ impl SpecializedMeshPipeline for MeshPipeline {
             shader_defs.push("IRRADIANCE_VOLUMES_ARE_USABLE".into());
         }
 
+        // Views without SSGI bind a black fallback texture, so this doesn't
+        // need its own pipeline key bit.
+        if SCREEN_SPACE_GLOBAL_ILLUMINATION_IS_USABLE {
+            shader_defs.push("SCREEN_SPACE_GLOBAL_ILLUMINATION".into());
+        }
+
         let format = if key.contains(MeshPipelineKey::HDR) {
             ViewTarget::TEXTURE_FORMAT_HDR
         } else {
             TextureFormat::bevy_default()
         };
 
+        let targets = if key.contains(MeshPipelineKey::ORDER_INDEPENDENT_TRANSPARENCY) {
+            shader_defs.push("OIT_ENABLED".into());
+            // Weighted blended order-independent transparency sums the weighted colors of
+            // the fragments, and multiplies their transparencies, so that the result
+            // doesn't depend on their order.
+            let accumulate = BlendComponent {
+                src_factor: BlendFactor::One,
+                dst_factor: BlendFactor::One,
+                operation: BlendOperation::Add,
+            };
+            let reveal = BlendComponent {
+                src_factor: BlendFactor::Zero,
+                dst_factor: BlendFactor::OneMinusSrc,
+                operation: BlendOperation::Add,
+            };
+            vec![
+                Some(ColorTargetState {
+                    format: OIT_ACCUMULATION_FORMAT,
+                    blend: Some(BlendState {
+                        color: accumulate,
+                        alpha: accumulate,
+                    }),
+                    write_mask: ColorWrites::ALL,
+                }),
+                Some(ColorTargetState {
+                    format: OIT_REVEALAGE_FORMAT,
+                    blend: Some(BlendState {
+                        color: reveal,
+                        alpha: reveal,
+                    }),
+                    write_mask: ColorWrites::RED,
+                }),
+            ]
+        } else {
+            vec![Some(ColorTargetState {
+                format,
+                blend,
+                write_mask: ColorWrites::ALL,
+            })]
+        };
+
         // This is defined here so that custom shaders that use something other than
         // the mesh binding from bevy_pbr::mesh_bindings can easily make use of this
         // in their own shaders.
@@ -1464,11 +1677,7 @@ impl SpecializedMeshPipeline for MeshPipeline {
                 shader: MESH_SHADER_HANDLE,
                 shader_defs,
                 entry_point: "fragment".into(),
-                targets: vec![Some(ColorTargetState {
-                    format,
-                    blend,
-                    write_mask: ColorWrites::ALL,
-                })],
+                targets,
             }),
             layout: bind_group_layout,
             push_constant_ranges,
@@ -1575,18 +1784,34 @@ pub fn prepare_mesh_bind_group(
 
     groups.model_only = Some(layouts.model_only(&render_device, &model));
 
-    let skin = skins_uniform.buffer.buffer();
-    if let Some(skin) = skin {
-        groups.skinned = Some(layouts.skinned(&render_device, &model, skin));
+    let skin = skins_uniform
+        .buffer
+        .buffer()
+        .zip(skins_uniform.prev_buffer.buffer());
+    if let Some((skin, prev_skin)) = skin {
+        groups.skinned = Some(layouts.skinned(&render_device, &model, skin, prev_skin));
     }
 
-    if let Some(weights) = weights_uniform.buffer.buffer() {
+    if let (Some(weights), Some(prev_weights)) = (
+        weights_uniform.buffer.buffer(),
+        weights_uniform.prev_buffer.buffer(),
+    ) {
         for (id, gpu_mesh) in meshes.iter() {
             if let Some(targets) = gpu_mesh.morph_targets.as_ref() {
-                let group = if let Some(skin) = skin.filter(|_| is_skinned(&gpu_mesh.layout)) {
-                    layouts.morphed_skinned(&render_device, &model, skin, weights, targets)
+                let group = if let Some((skin, prev_skin)) =
+                    skin.filter(|_| is_skinned(&gpu_mesh.layout))
+                {
+                    layouts.morphed_skinned(
+                        &render_device,
+                        &model,
+                        skin,
+                        weights,
+                        targets,
+                        prev_skin,
+                        prev_weights,
+                    )
                 } else {
-                    layouts.morphed(&render_device, &model, weights, targets)
+                    layouts.morphed(&render_device, &model, weights, targets, prev_weights)
                 };
                 groups.morph_targets.insert(id, group);
             }
@@ -1696,18 +1921,25 @@ impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetMeshBindGroup<I> {
             return RenderCommandResult::Failure;
         };
 
-        let mut dynamic_offsets: [u32; 3] = Default::default();
+        // The buffers of the previous frame's joint matrices and morph weights
+        // have the same layout as the current ones, so they're bound at the
+        // same offsets. Dynamic offsets are ordered by binding index.
+        let mut dynamic_offsets: [u32; 5] = Default::default();
         let mut offset_count = 0;
         if let Some(dynamic_offset) = item.dynamic_offset() {
             dynamic_offsets[offset_count] = dynamic_offset.get();
             offset_count += 1;
         }
-        if let Some(skin_index) = skin_index {
-            dynamic_offsets[offset_count] = skin_index.index;
-            offset_count += 1;
-        }
-        if let Some(morph_index) = morph_index {
-            dynamic_offsets[offset_count] = morph_index.index;
+        for index in [
+            skin_index.map(|skin_index| skin_index.index),
+            morph_index.map(|morph_index| morph_index.index),
+            skin_index.map(|skin_index| skin_index.index),
+            morph_index.map(|morph_index| morph_index.index),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            dynamic_offsets[offset_count] = index;
             offset_count += 1;
         }
         pass.set_bind_group(I, bind_group, &dynamic_offsets[0..offset_count]);
@@ -1723,19 +1955,22 @@ impl<P: PhaseItem> RenderCommand<P> for DrawMesh {
         SRes<RenderMeshInstances>,
         SRes<PipelineCache>,
         Option<SRes<PreprocessPipeline>>,
+        Option<SRes<IndirectParametersBuffer<MeshPipeline>>>,
     );
-    type ViewQuery = Has<PreprocessBindGroup>;
+    type ViewQuery = (Has<PreprocessBindGroup>, Option<Read<VisibleMeshLods>>);
     type ItemQuery = ();
     #[inline]
     fn render<'w>(
         item: &P,
-        has_preprocess_bind_group: ROQueryItem<Self::ViewQuery>,
+        (has_preprocess_bind_group, visible_mesh_lods): ROQueryItem<Self::ViewQuery>,
         _item_query: Option<()>,
-        (meshes, mesh_instances, pipeline_cache, preprocess_pipeline): SystemParamItem<
-            'w,
-            '_,
-            Self::Param,
-        >,
+        (
+            meshes,
+            mesh_instances,
+            pipeline_cache,
+            preprocess_pipeline,
+            indirect_parameters_buffer,
+        ): SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
         // If we're using GPU preprocessing, then we're dependent on that
@@ -1757,10 +1992,13 @@ impl<P: PhaseItem> RenderCommand<P> for DrawMesh {
 
         let meshes = meshes.into_inner();
         let mesh_instances = mesh_instances.into_inner();
+        let indirect_parameters_buffer =
+            indirect_parameters_buffer.map(|buffer| buffer.into_inner());
 
-        let Some(mesh_asset_id) = mesh_instances.mesh_asset_id(item.entity()) else {
+        let Some(mesh_instance) = mesh_instances.render_mesh_queue_data(item.entity()) else {
             return RenderCommandResult::Failure;
         };
+        let mesh_asset_id = mesh_instance.mesh_asset_id_for_view(item.entity(), visible_mesh_lods);
         let Some(gpu_mesh) = meshes.get(mesh_asset_id) else {
             return RenderCommandResult::Failure;
         };
@@ -1774,6 +2012,14 @@ impl<P: PhaseItem> RenderCommand<P> for DrawMesh {
             0,
             &(batch_range.start as i32).to_le_bytes(),
         );
+
+        // Batches of binned phase items have indirect parameters if the
+        // device supports them.
+        let indirect_parameters =
+            indirect_parameters_buffer.and_then(|indirect_parameters_buffer| {
+                indirect_parameters_buffer.get(batch_range.start)
+            });
+
         match &gpu_mesh.buffer_info {
             GpuBufferInfo::Indexed {
                 buffer,
@@ -1781,11 +2027,27 @@ impl<P: PhaseItem> RenderCommand<P> for DrawMesh {
                 count,
             } => {
                 pass.set_index_buffer(buffer.slice(..), 0, *index_format);
-                pass.draw_indexed(0..*count, 0, batch_range.clone());
-            }
-            GpuBufferInfo::NonIndexed => {
-                pass.draw(0..gpu_mesh.vertex_count, batch_range.clone());
+                match indirect_parameters {
+                    // The batch is drawn along with the first batch of its set.
+                    Some((_, _, 0)) => {}
+                    Some((indirect_buffer, offset, draw_count)) => {
+                        pass.multi_draw_indexed_indirect(indirect_buffer, offset, draw_count);
+                    }
+                    None => pass.draw_indexed(0..*count, 0, batch_range.clone()),
+                }
             }
+            GpuBufferInfo::NonIndexed => match indirect_parameters {
+                Some((indirect_buffer, offset, draw_count)) => {
+                    // Non-indexed parameters are laid out like indexed ones,
+                    // so they're wider than the tightly packed arguments that
+                    // a non-indexed multi-draw reads, and are drawn one by one.
+                    let stride = mem::size_of::<IndirectParameters>() as u64;
+                    for draw in 0..u64::from(draw_count) {
+                        pass.draw_indirect(indirect_buffer, offset + draw * stride);
+                    }
+                }
+                None => pass.draw(0..gpu_mesh.vertex_count, batch_range.clone()),
+            },
         }
         RenderCommandResult::Success
     }
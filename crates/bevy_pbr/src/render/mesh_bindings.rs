@@ -103,10 +103,12 @@ pub struct MeshLayouts {
     /// Includes the lightmap texture and uniform.
     pub lightmapped: BindGroupLayout,
 
-    /// Also includes the uniform for skinning
+    /// Also includes the uniforms for skinning, for the current and the
+    /// previous frame.
     pub skinned: BindGroupLayout,
 
-    /// Also includes the uniform and [`MorphAttributes`] for morph targets.
+    /// Also includes the uniform and [`MorphAttributes`] for morph targets, and
+    /// the uniform for the morph weights of the previous frame.
     ///
     /// [`MorphAttributes`]: bevy_render::mesh::morph::MorphAttributes
     pub morphed: BindGroupLayout,
@@ -151,6 +153,7 @@ impl MeshLayouts {
                 (
                     (0, layout_entry::model(render_device)),
                     (1, layout_entry::skinning()),
+                    (6, layout_entry::skinning()),
                 ),
             ),
         )
@@ -164,6 +167,7 @@ impl MeshLayouts {
                     (0, layout_entry::model(render_device)),
                     (2, layout_entry::weights()),
                     (3, layout_entry::targets()),
+                    (7, layout_entry::weights()),
                 ),
             ),
         )
@@ -178,6 +182,8 @@ impl MeshLayouts {
                     (1, layout_entry::skinning()),
                     (2, layout_entry::weights()),
                     (3, layout_entry::targets()),
+                    (6, layout_entry::skinning()),
+                    (7, layout_entry::weights()),
                 ),
             ),
         )
@@ -226,11 +232,16 @@ impl MeshLayouts {
         render_device: &RenderDevice,
         model: &BindingResource,
         skin: &Buffer,
+        prev_skin: &Buffer,
     ) -> BindGroup {
         render_device.create_bind_group(
             "skinned_mesh_bind_group",
             &self.skinned,
-            &[entry::model(0, model.clone()), entry::skinning(1, skin)],
+            &[
+                entry::model(0, model.clone()),
+                entry::skinning(1, skin),
+                entry::skinning(6, prev_skin),
+            ],
         )
     }
     pub fn morphed(
@@ -239,6 +250,7 @@ impl MeshLayouts {
         model: &BindingResource,
         weights: &Buffer,
         targets: &TextureView,
+        prev_weights: &Buffer,
     ) -> BindGroup {
         render_device.create_bind_group(
             "morphed_mesh_bind_group",
@@ -247,6 +259,7 @@ impl MeshLayouts {
                 entry::model(0, model.clone()),
                 entry::weights(2, weights),
                 entry::targets(3, targets),
+                entry::weights(7, prev_weights),
             ],
         )
     }
@@ -257,6 +270,8 @@ impl MeshLayouts {
         skin: &Buffer,
         weights: &Buffer,
         targets: &TextureView,
+        prev_skin: &Buffer,
+        prev_weights: &Buffer,
     ) -> BindGroup {
         render_device.create_bind_group(
             "morphed_skinned_mesh_bind_group",
@@ -266,6 +281,8 @@ impl MeshLayouts {
                 entry::skinning(1, skin),
                 entry::weights(2, weights),
                 entry::targets(3, targets),
+                entry::skinning(6, prev_skin),
+                entry::weights(7, prev_weights),
             ],
         )
     }
@@ -17,6 +17,12 @@ fn get_previous_model_matrix(instance_index: u32) -> mat4x4<f32> {
     return affine3_to_square(mesh[instance_index].previous_model);
 }
 
+// Returns the slot of the material in the bindless material bind group, for materials drawn
+// bindless.
+fn get_material_bind_group_slot(instance_index: u32) -> u32 {
+    return mesh[instance_index].material_bind_group_slot;
+}
+
 fn mesh_position_local_to_world(model: mat4x4<f32>, vertex_position: vec4<f32>) -> vec4<f32> {
     return model * vertex_position;
 }
@@ -21,6 +21,9 @@ struct MeshInput {
     // The index of this mesh's `MeshInput` in the `previous_input` array, if
     // applicable. If not present, this is `u32::MAX`.
     previous_input_index: u32,
+    // The slot of the material in the bindless material bind group, if the
+    // material is drawn bindless.
+    material_bind_group_slot: u32,
 }
 
 // One invocation of this compute shader: i.e. one mesh instance in a view.
@@ -83,4 +86,6 @@ fn main(@builtin(global_invocation_id) global_invocation_id: vec3<u32>) {
     output[output_index].inverse_transpose_model_b = inverse_transpose_model_b;
     output[output_index].flags = current_input[mesh_index].flags;
     output[output_index].lightmap_uv_rect = current_input[mesh_index].lightmap_uv_rect;
+    output[output_index].material_bind_group_slot =
+        current_input[mesh_index].material_bind_group_slot;
 }
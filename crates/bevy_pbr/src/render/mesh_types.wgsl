@@ -15,6 +15,9 @@ struct Mesh {
     // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
     flags: u32,
     lightmap_uv_rect: vec2<u32>,
+    // The slot of the material in the bindless material bind group, if the material is drawn
+    // bindless.
+    material_bind_group_slot: u32,
 };
 
 #ifdef SKINNED
@@ -31,5 +34,6 @@ struct MorphWeights {
 
 const MESH_FLAGS_SHADOW_RECEIVER_BIT: u32 = 1u;
 const MESH_FLAGS_TRANSMITTED_SHADOW_RECEIVER_BIT: u32 = 2u;
+const MESH_FLAGS_LIGHTMAPPED_BIT: u32 = 4u;
 // 2^31 - if the flag is set, the sign is positive, else it is negative
 const MESH_FLAGS_SIGN_DETERMINANT_MODEL_3X3_BIT: u32 = 2147483648u;
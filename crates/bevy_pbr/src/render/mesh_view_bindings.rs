@@ -33,7 +33,8 @@ use crate::{
     },
     prepass, FogMeta, GlobalLightMeta, GpuFog, GpuLights, GpuPointLights, LightMeta,
     LightProbesBuffer, LightProbesUniform, MeshPipeline, MeshPipelineKey, RenderViewLightProbes,
-    ScreenSpaceAmbientOcclusionTextures, ShadowSamplers, ViewClusterBindings, ViewShadowBindings,
+    ScreenSpaceAmbientOcclusionTextures, ScreenSpaceGlobalIlluminationTextures, ShadowSamplers,
+    ViewClusterBindings, ViewShadowBindings, SCREEN_SPACE_GLOBAL_ILLUMINATION_IS_USABLE,
 };
 
 #[derive(Clone)]
@@ -315,6 +316,14 @@ fn layout_entries(
         (25, sampler(SamplerBindingType::Filtering)),
     ));
 
+    // Screen space global illumination texture
+    if SCREEN_SPACE_GLOBAL_ILLUMINATION_IS_USABLE {
+        entries = entries.extend_with_indices(((
+            26,
+            texture_2d(TextureSampleType::Float { filterable: false }),
+        ),));
+    }
+
     entries.to_vec()
 }
 
@@ -362,7 +371,10 @@ pub fn prepare_mesh_view_bind_groups(
         Entity,
         &ViewShadowBindings,
         &ViewClusterBindings,
-        Option<&ScreenSpaceAmbientOcclusionTextures>,
+        (
+            Option<&ScreenSpaceAmbientOcclusionTextures>,
+            Option<&ScreenSpaceGlobalIlluminationTextures>,
+        ),
         Option<&ViewPrepassTextures>,
         Option<&ViewTransmissionTexture>,
         &Tonemapping,
@@ -399,7 +411,7 @@ pub fn prepare_mesh_view_bind_groups(
             entity,
             shadow_bindings,
             cluster_bindings,
-            ssao_textures,
+            (ssao_textures, ssgi_textures),
             prepass_textures,
             transmission_texture,
             tonemapping,
@@ -526,6 +538,13 @@ pub fn prepare_mesh_view_bind_groups(
             entries =
                 entries.extend_with_indices(((24, transmission_view), (25, transmission_sampler)));
 
+            if SCREEN_SPACE_GLOBAL_ILLUMINATION_IS_USABLE {
+                let ssgi_view = ssgi_textures
+                    .map(|t| &t.screen_space_global_illumination_texture.default_view)
+                    .unwrap_or(&fallback_image_zero.texture_view);
+                entries = entries.extend_with_indices(((26, ssgi_view),));
+            }
+
             commands.entity(entity).insert(MeshViewBindGroup {
                 value: render_device.create_bind_group("mesh_view_bind_group", layout, &entries),
             });
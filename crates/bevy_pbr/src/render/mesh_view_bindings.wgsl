@@ -90,3 +90,7 @@
 
 @group(0) @binding(24) var view_transmission_texture: texture_2d<f32>;
 @group(0) @binding(25) var view_transmission_sampler: sampler;
+
+#ifdef SCREEN_SPACE_GLOBAL_ILLUMINATION
+@group(0) @binding(26) var screen_space_global_illumination_texture: texture_2d<f32>;
+#endif // SCREEN_SPACE_GLOBAL_ILLUMINATION
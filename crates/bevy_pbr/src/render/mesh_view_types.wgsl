@@ -15,11 +15,13 @@ struct PointLight {
 
 const POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32   = 1u;
 const POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE: u32 = 2u;
+const POINT_LIGHT_FLAGS_SOFT_SHADOWS_BIT: u32      = 4u;
 
 struct DirectionalCascade {
     view_projection: mat4x4<f32>,
     texel_size: f32,
     far_bound: f32,
+    depth_bias: f32,
 }
 
 struct DirectionalLight {
@@ -34,6 +36,8 @@ struct DirectionalLight {
     cascades_overlap_proportion: f32,
     depth_texture_base_index: u32,
     render_layers: u32,
+    // The angular diameter of the light for soft shadows, or 0 if they are disabled.
+    soft_shadow_size: f32,
 };
 
 const DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32 = 1u;
@@ -117,6 +121,10 @@ struct LightProbe {
     inverse_transpose_transform: mat3x4<f32>,
     cubemap_index: i32,
     intensity: f32,
+    // One of the `LIGHT_PROBE_SHAPE_*` constants.
+    shape: u32,
+    // The fraction of the light probe's region over which its influence fades out.
+    falloff: f32,
 };
 
 struct LightProbes {
@@ -18,18 +18,32 @@ pub struct MorphIndex {
     pub(super) index: u32,
 }
 
+impl MorphIndex {
+    /// The index of the first weight of this entity in [`MorphUniform`].
+    fn start(&self) -> usize {
+        self.index as usize / mem::size_of::<f32>()
+    }
+}
+
 #[derive(Default, Resource, Deref, DerefMut)]
 pub struct MorphIndices(EntityHashMap<MorphIndex>);
 
 #[derive(Resource)]
 pub struct MorphUniform {
     pub buffer: BufferVec<f32>,
+    /// The morph weights of the previous frame, used to compute motion
+    /// vectors.
+    ///
+    /// This buffer has the same layout as `buffer`, so a [`MorphIndex`] is
+    /// valid for both.
+    pub prev_buffer: BufferVec<f32>,
 }
 
 impl Default for MorphUniform {
     fn default() -> Self {
         Self {
             buffer: BufferVec::new(BufferUsages::UNIFORM),
+            prev_buffer: BufferVec::new(BufferUsages::UNIFORM),
         }
     }
 }
@@ -45,6 +59,12 @@ pub fn prepare_morphs(
     let len = uniform.buffer.len();
     uniform.buffer.reserve(len, &render_device);
     uniform.buffer.write_buffer(&render_device, &render_queue);
+
+    let len = uniform.prev_buffer.len();
+    uniform.prev_buffer.reserve(len, &render_device);
+    uniform
+        .prev_buffer
+        .write_buffer(&render_device, &render_queue);
 }
 
 const fn can_align(step: usize, target: usize) -> bool {
@@ -84,18 +104,31 @@ pub fn extract_morphs(
     mut uniform: ResMut<MorphUniform>,
     query: Extract<Query<(Entity, &ViewVisibility, &MeshMorphWeights)>>,
 ) {
-    morph_indices.clear();
-    uniform.buffer.clear();
+    let prev_morph_indices = mem::take(&mut morph_indices.0);
+    let prev_weights = mem::take(uniform.buffer.values_mut());
+    uniform.prev_buffer.clear();
 
     for (entity, view_visibility, morph_weights) in &query {
         if !view_visibility.get() {
             continue;
         }
-        let start = uniform.buffer.len();
+        let MorphUniform {
+            buffer,
+            prev_buffer,
+        } = &mut *uniform;
+        let start = buffer.len();
         let weights = morph_weights.weights();
-        let legal_weights = weights.iter().take(MAX_MORPH_WEIGHTS).copied();
-        uniform.buffer.extend(legal_weights);
-        add_to_alignment::<f32>(&mut uniform.buffer);
+        let legal_weights = &weights[..weights.len().min(MAX_MORPH_WEIGHTS)];
+        buffer.extend(legal_weights.iter().copied());
+
+        let prev = prev_morph_indices
+            .get(&entity)
+            .map(|prev_morph_index| prev_morph_index.start())
+            .and_then(|prev_start| prev_weights.get(prev_start..prev_start + legal_weights.len()));
+        prev_buffer.extend(prev.unwrap_or(legal_weights).iter().copied());
+
+        add_to_alignment::<f32>(buffer);
+        add_to_alignment::<f32>(prev_buffer);
 
         let index = (start * mem::size_of::<f32>()) as u32;
         morph_indices.insert(entity, MorphIndex { index });
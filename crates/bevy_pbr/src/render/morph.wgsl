@@ -6,6 +6,10 @@
 
 @group(1) @binding(2) var<uniform> morph_weights: MorphWeights;
 @group(1) @binding(3) var morph_targets: texture_3d<f32>;
+#ifdef MOTION_VECTOR_PREPASS
+// The morph weights of the previous frame, used to compute motion vectors.
+@group(1) @binding(7) var<uniform> prev_morph_weights: MorphWeights;
+#endif
 
 // NOTE: Those are the "hardcoded" values found in `MorphAttributes` struct
 // in crates/bevy_render/src/mesh/morph/visitors.rs
@@ -29,6 +33,12 @@ fn weight_at(weight_index: u32) -> f32 {
     let i = weight_index;
     return morph_weights.weights[i / 4u][i % 4u];
 }
+#ifdef MOTION_VECTOR_PREPASS
+fn prev_weight_at(weight_index: u32) -> f32 {
+    let i = weight_index;
+    return prev_morph_weights.weights[i / 4u][i % 4u];
+}
+#endif
 fn morph_pixel(vertex: u32, component: u32, weight: u32) -> f32 {
     let coord = component_texture_coord(vertex, component);
     // Due to https://gpuweb.github.io/gpuweb/wgsl/#texel-formats
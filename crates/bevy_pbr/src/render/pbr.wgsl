@@ -14,6 +14,9 @@
     pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
     pbr_types::STANDARD_MATERIAL_FLAGS_UNLIT_BIT,
 }
+#ifdef OIT_ENABLED
+#import bevy_pbr::forward_io::weighted_blended_oit_output
+#endif
 #endif
 
 #ifdef MESHLET_MESH_MATERIAL_PASS
@@ -56,6 +59,10 @@ fn fragment(
     // apply in-shader post processing (fog, alpha-premultiply, and also tonemapping, debanding if the camera is non-hdr)
     // note this does not include fullscreen postprocessing effects like bloom.
     out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+
+#ifdef OIT_ENABLED
+    out = weighted_blended_oit_output(out.color, in.position.z);
+#endif
 #endif
 
     return out;
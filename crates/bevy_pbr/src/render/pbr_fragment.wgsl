@@ -211,7 +211,9 @@ fn pbr_input_from_standard_material(
         }
 #endif
 #ifdef SCREEN_SPACE_AMBIENT_OCCLUSION
-        let ssao = textureLoad(screen_space_ambient_occlusion_texture, vec2<i32>(in.position.xy), 0i).r;
+        // The ambient occlusion texture may be at a lower resolution than the viewport
+        let ssao_scale = vec2<f32>(textureDimensions(screen_space_ambient_occlusion_texture)) / view.viewport.zw;
+        let ssao = textureLoad(screen_space_ambient_occlusion_texture, vec2<i32>(in.position.xy * ssao_scale), 0i).r;
         let ssao_multibounce = gtao_multibounce(ssao, pbr_input.material.base_color.rgb);
         diffuse_occlusion = min(diffuse_occlusion, ssao_multibounce);
         // Use SSAO to estimate the specular occlusion.
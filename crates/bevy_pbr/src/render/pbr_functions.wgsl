@@ -11,7 +11,11 @@
     shadows,
     ambient,
     irradiance_volume,
-    mesh_types::{MESH_FLAGS_SHADOW_RECEIVER_BIT, MESH_FLAGS_TRANSMITTED_SHADOW_RECEIVER_BIT},
+    mesh_types::{
+        MESH_FLAGS_SHADOW_RECEIVER_BIT,
+        MESH_FLAGS_TRANSMITTED_SHADOW_RECEIVER_BIT,
+        MESH_FLAGS_LIGHTMAPPED_BIT,
+    },
     utils::E,
 }
 
@@ -339,6 +343,10 @@ fn apply_pbr_lighting(
     // When we find a source of diffuse indirect lighting, we stop accumulating
     // any more diffuse indirect light. This avoids double-counting if, for
     // example, both lightmaps and irradiance volumes are present.
+    //
+    // In the deferred lighting pass, the lightmap light has already been added
+    // to the emissive light, so we check the mesh flag instead.
+    let lightmapped = (in.flags & MESH_FLAGS_LIGHTMAPPED_BIT) != 0u;
 
 #ifdef LIGHTMAP
     if (all(indirect_light == vec3(0.0f))) {
@@ -348,7 +356,7 @@ fn apply_pbr_lighting(
 
 #ifdef IRRADIANCE_VOLUME {
     // Irradiance volume light (indirect)
-    if (all(indirect_light == vec3(0.0f))) {
+    if (all(indirect_light == vec3(0.0f)) && !lightmapped) {
         let irradiance_volume_light = irradiance_volume::irradiance_volume_light(
             in.world_position.xyz, in.N);
         indirect_light += irradiance_volume_light * diffuse_color * diffuse_occlusion;
@@ -370,7 +378,7 @@ fn apply_pbr_lighting(
         R,
         F0,
         in.world_position.xyz,
-        any(indirect_light != vec3(0.0f)));
+        any(indirect_light != vec3(0.0f)) || lightmapped);
 
     indirect_light += environment_light.diffuse * diffuse_occlusion +
         environment_light.specular * specular_occlusion;
@@ -422,6 +430,18 @@ fn apply_pbr_lighting(
     let specular_transmitted_environment_light = vec3<f32>(0.0);
 #endif
 
+#ifdef SCREEN_SPACE_GLOBAL_ILLUMINATION
+    // Screen space global illumination (indirect). This is added on top of the
+    // other diffuse sources, since it only captures light bounced by on-screen
+    // surfaces. Lightmaps already include bounced light, so we skip it there.
+    if (!lightmapped) {
+        // The global illumination texture may be at a lower resolution than the viewport
+        let ssgi_scale = vec2<f32>(textureDimensions(view_bindings::screen_space_global_illumination_texture)) / view_bindings::view.viewport.zw;
+        let ssgi = textureLoad(view_bindings::screen_space_global_illumination_texture, vec2<i32>(in.frag_coord.xy * ssgi_scale), 0i).rgb;
+        indirect_light += ssgi * diffuse_color * diffuse_occlusion;
+    }
+#endif // SCREEN_SPACE_GLOBAL_ILLUMINATION
+
     // Ambient light (indirect)
     indirect_light += ambient::ambient_light(in.world_position, in.N, in.V, NdotV, diffuse_color, F0, perceptual_roughness, diffuse_occlusion);
 
@@ -169,6 +169,80 @@ fn sample_shadow_map(light_local: vec2<f32>, depth: f32, array_index: i32, texel
 #endif
 }
 
+// Percentage-closer soft shadows (PCSS) need to read the depths of the shadow map, which
+// isn't supported without array textures, i.e. on WebGL 2. Lights fall back to the filtering
+// method of the view there.
+// https://developer.download.nvidia.com/shaderlibrary/docs/shadow_PCSS.pdf
+#ifndef NO_ARRAY_TEXTURES_SUPPORT
+
+// The maximum radius, in texels, of the regions searched for blockers and filtered by PCSS.
+const PCSS_MAX_RADIUS_TEXELS: f32 = 32.0;
+
+// Returns the average depth of the texels within `search_radius` of `light_local` that are
+// closer to the light than `depth`, or 0.0 if there are none.
+fn search_for_blockers_in_shadow_map(
+    light_local: vec2<f32>,
+    depth: f32,
+    array_index: i32,
+    search_radius: f32,
+) -> f32 {
+    let shadow_map_size = vec2<f32>(textureDimensions(view_bindings::directional_shadow_textures));
+    let max_texel = vec2<i32>(shadow_map_size) - 1;
+    let radius = min(search_radius, PCSS_MAX_RADIUS_TEXELS / shadow_map_size.x);
+    let rotation_matrix = random_rotation_matrix(light_local * shadow_map_size);
+
+    var sample_positions = D3D_SAMPLE_POINT_POSITIONS;
+    var blocker_depth_sum = 0.0;
+    var blocker_count = 0.0;
+    for (var i = 0u; i < 8u; i += 1u) {
+        let offset = rotation_matrix * sample_positions[i] * radius;
+        let texel = clamp(vec2<i32>((light_local + offset) * shadow_map_size), vec2(0), max_texel);
+        let sample_depth = textureLoad(
+            view_bindings::directional_shadow_textures,
+            texel,
+            array_index,
+            0,
+        );
+        // With reversed Z, texels closer to the light have greater depths.
+        if (sample_depth > depth) {
+            blocker_depth_sum += sample_depth;
+            blocker_count += 1.0;
+        }
+    }
+
+    if (blocker_count == 0.0) {
+        return 0.0;
+    }
+    return blocker_depth_sum / blocker_count;
+}
+
+// Filters the shadow map over a disk of `filter_radius` around `light_local`, for the
+// variable penumbra of PCSS.
+fn sample_shadow_map_pcss(
+    light_local: vec2<f32>,
+    depth: f32,
+    array_index: i32,
+    filter_radius: f32,
+) -> f32 {
+    let shadow_map_size = vec2<f32>(textureDimensions(view_bindings::directional_shadow_textures));
+    let radius = clamp(
+        filter_radius,
+        1.0 / shadow_map_size.x,
+        PCSS_MAX_RADIUS_TEXELS / shadow_map_size.x,
+    );
+    let rotation_matrix = random_rotation_matrix(light_local * shadow_map_size);
+
+    var sample_positions = D3D_SAMPLE_POINT_POSITIONS;
+    var sum = 0.0;
+    for (var i = 0u; i < 8u; i += 1u) {
+        let offset = rotation_matrix * sample_positions[i] * radius;
+        sum += sample_shadow_map_hardware(light_local + offset, depth, array_index);
+    }
+    return sum / 8.0;
+}
+
+#endif
+
 // NOTE: Due to the non-uniform control flow in `shadows::fetch_point_shadow`,
 // we must use the Level variant of textureSampleCompare to avoid undefined
 // behavior due to some of the fragments in a quad (2x2 fragments) being
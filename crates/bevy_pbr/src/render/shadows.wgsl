@@ -1,12 +1,19 @@
 #define_import_path bevy_pbr::shadows
 
 #import bevy_pbr::{
-    mesh_view_types::POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE,
+    mesh_view_types::{
+        POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE,
+        POINT_LIGHT_FLAGS_SOFT_SHADOWS_BIT,
+    },
     mesh_view_bindings as view_bindings,
     utils::hsv2rgb,
     shadow_sampling::{SPOT_SHADOW_TEXEL_SIZE, sample_shadow_cubemap, sample_shadow_map}
 }
 
+#ifndef NO_ARRAY_TEXTURES_SUPPORT
+#import bevy_pbr::shadow_sampling::{search_for_blockers_in_shadow_map, sample_shadow_map_pcss}
+#endif
+
 const flip_z: vec3<f32> = vec3<f32>(1.0, 1.0, -1.0);
 
 fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
@@ -90,6 +97,19 @@ fn fetch_spot_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: ve
     // 0.1 must match POINT_LIGHT_NEAR_Z
     let depth = 0.1 / -projected_position.z;
 
+#ifndef NO_ARRAY_TEXTURES_SUPPORT
+    if (((*light).flags & POINT_LIGHT_FLAGS_SOFT_SHADOWS_BIT) != 0u) {
+        return sample_spot_shadow_pcss(
+            shadow_uv,
+            depth,
+            i32(light_id) + view_bindings::lights.spot_light_shadowmap_offset,
+            -projected_position.z,
+            (*light).position_radius.w,
+            (*light).spot_light_tan_angle,
+        );
+    }
+#endif
+
     return sample_shadow_map(
         shadow_uv,
         depth,
@@ -115,7 +135,7 @@ fn sample_directional_cascade(light_id: u32, cascade_index: u32, frag_position:
 
     // The normal bias is scaled to the texel size.
     let normal_offset = (*light).shadow_normal_bias * (*cascade).texel_size * surface_normal.xyz;
-    let depth_offset = (*light).shadow_depth_bias * (*light).direction_to_light.xyz;
+    let depth_offset = (*cascade).depth_bias * (*light).direction_to_light.xyz;
     let offset_position = vec4<f32>(frag_position.xyz + normal_offset + depth_offset, frag_position.w);
 
     let offset_position_clip = (*cascade).view_projection * offset_position;
@@ -137,9 +157,88 @@ fn sample_directional_cascade(light_id: u32, cascade_index: u32, frag_position:
     let depth = offset_position_ndc.z;
 
     let array_index = i32((*light).depth_texture_base_index + cascade_index);
+
+#ifndef NO_ARRAY_TEXTURES_SUPPORT
+    if ((*light).soft_shadow_size > 0.0) {
+        return sample_directional_cascade_pcss(light_local, depth, array_index, light_id, cascade_index);
+    }
+#endif
+
     return sample_shadow_map(light_local, depth, array_index, (*cascade).texel_size);
 }
 
+#ifndef NO_ARRAY_TEXTURES_SUPPORT
+
+// PCSS for spot lights, treating the light as a disk of `light_radius` facing the receiver,
+// `distance_to_light` away along the direction of the light.
+fn sample_spot_shadow_pcss(
+    shadow_uv: vec2<f32>,
+    depth: f32,
+    array_index: i32,
+    distance_to_light: f32,
+    light_radius: f32,
+    tan_angle: f32,
+) -> f32 {
+    // 0.1 must match POINT_LIGHT_NEAR_Z
+    let near_z = 0.1;
+    // The size of a world unit in shadow map UVs, at a distance of one world unit from the light.
+    let uv_scale = 0.5 / tan_angle;
+
+    // Blockers can be anywhere between the light and the receiver: search the projection of the
+    // light onto the near plane as seen from the receiver.
+    let search_radius = light_radius * (distance_to_light - near_z) / distance_to_light
+        * uv_scale / near_z;
+    let blocker_depth = search_for_blockers_in_shadow_map(shadow_uv, depth, array_index, search_radius);
+    if (blocker_depth == 0.0) {
+        return 1.0;
+    }
+
+    let blocker_distance = near_z / blocker_depth;
+    let penumbra_width = light_radius * (distance_to_light - blocker_distance) / blocker_distance;
+    return sample_shadow_map_pcss(
+        shadow_uv,
+        depth,
+        array_index,
+        penumbra_width * uv_scale / distance_to_light,
+    );
+}
+
+// PCSS for directional lights, whose penumbrae grow with the distance between blockers and
+// receivers by the angular size of the light.
+fn sample_directional_cascade_pcss(
+    light_local: vec2<f32>,
+    depth: f32,
+    array_index: i32,
+    light_id: u32,
+    cascade_index: u32,
+) -> f32 {
+    let light = &view_bindings::lights.directional_lights[light_id];
+    let cascade = &(*light).cascades[cascade_index];
+
+    // The projection is orthographic, so depths are proportional to distances along the
+    // direction of the light.
+    let view_projection = (*cascade).view_projection;
+    let depth_per_world_unit =
+        length(vec3(view_projection[0].z, view_projection[1].z, view_projection[2].z));
+    let shadow_map_size = f32(textureDimensions(view_bindings::directional_shadow_textures).x);
+    let uv_per_world_unit = 1.0 / ((*cascade).texel_size * shadow_map_size);
+    let tan_half_angle = tan(0.5 * (*light).soft_shadow_size);
+
+    // Blockers can be anywhere between the near plane of the cascade, at a depth of 1.0 with
+    // reversed Z, and the receiver.
+    let search_radius = (1.0 - depth) / depth_per_world_unit * tan_half_angle * uv_per_world_unit;
+    let blocker_depth = search_for_blockers_in_shadow_map(light_local, depth, array_index, search_radius);
+    if (blocker_depth == 0.0) {
+        return 1.0;
+    }
+
+    let blocker_distance = (blocker_depth - depth) / depth_per_world_unit;
+    let penumbra_radius = blocker_distance * tan_half_angle * uv_per_world_unit;
+    return sample_shadow_map_pcss(light_local, depth, array_index, penumbra_radius);
+}
+
+#endif
+
 fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>, view_z: f32) -> f32 {
     let light = &view_bindings::lights.directional_lights[light_id];
     let cascade_index = get_cascade_index(light_id, view_z);
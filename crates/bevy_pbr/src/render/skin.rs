@@ -1,3 +1,5 @@
+use std::mem;
+
 use bevy_asset::Assets;
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::entity::EntityHashMap;
@@ -25,9 +27,14 @@ impl SkinIndex {
     /// Index to be in address space based on [`SkinUniform`] size.
     const fn new(start: usize) -> Self {
         SkinIndex {
-            index: (start * std::mem::size_of::<Mat4>()) as u32,
+            index: (start * mem::size_of::<Mat4>()) as u32,
         }
     }
+
+    /// The index of the first joint matrix of this skin in [`SkinUniform`].
+    fn start(&self) -> usize {
+        self.index as usize / mem::size_of::<Mat4>()
+    }
 }
 
 #[derive(Default, Resource, Deref, DerefMut)]
@@ -37,12 +44,19 @@ pub struct SkinIndices(EntityHashMap<SkinIndex>);
 #[derive(Resource)]
 pub struct SkinUniform {
     pub buffer: BufferVec<Mat4>,
+    /// The joint matrices of the previous frame, used to compute motion
+    /// vectors.
+    ///
+    /// This buffer has the same layout as `buffer`, so a [`SkinIndex`] is valid
+    /// for both.
+    pub prev_buffer: BufferVec<Mat4>,
 }
 
 impl Default for SkinUniform {
     fn default() -> Self {
         Self {
             buffer: BufferVec::new(BufferUsages::UNIFORM),
+            prev_buffer: BufferVec::new(BufferUsages::UNIFORM),
         }
     }
 }
@@ -59,6 +73,12 @@ pub fn prepare_skins(
     let len = uniform.buffer.len();
     uniform.buffer.reserve(len, &render_device);
     uniform.buffer.write_buffer(&render_device, &render_queue);
+
+    let len = uniform.prev_buffer.len();
+    uniform.prev_buffer.reserve(len, &render_device);
+    uniform
+        .prev_buffer
+        .write_buffer(&render_device, &render_queue);
 }
 
 // Notes on implementation:
@@ -87,6 +107,11 @@ pub fn prepare_skins(
 // In this way, we can pack ‘variable sized arrays’ into uniform buffer bindings
 // which normally only support fixed size arrays. You just have to make sure
 // in the shader that you only read the values that are valid for that binding.
+//
+// The joint matrices of the previous frame are written to a second buffer with
+// the exact same layout, so that the same dynamic offset can be used to bind
+// both. Skins that weren't extracted in the previous frame reuse their current
+// joint matrices, which means they have no motion.
 pub fn extract_skins(
     mut skin_indices: ResMut<SkinIndices>,
     mut uniform: ResMut<SkinUniform>,
@@ -94,8 +119,9 @@ pub fn extract_skins(
     inverse_bindposes: Extract<Res<Assets<SkinnedMeshInverseBindposes>>>,
     joints: Extract<Query<&GlobalTransform>>,
 ) {
-    uniform.buffer.clear();
-    skin_indices.clear();
+    let prev_skin_indices = mem::take(&mut skin_indices.0);
+    let prev_joints = mem::take(uniform.buffer.values_mut());
+    uniform.prev_buffer.clear();
     let mut last_start = 0;
 
     // PERF: This can be expensive, can we move this to prepare?
@@ -103,7 +129,10 @@ pub fn extract_skins(
         if !view_visibility.get() {
             continue;
         }
-        let buffer = &mut uniform.buffer;
+        let SkinUniform {
+            buffer,
+            prev_buffer,
+        } = &mut *uniform;
         let Some(inverse_bindposes) = inverse_bindposes.get(&skin.inverse_bindposes) else {
             continue;
         };
@@ -125,10 +154,24 @@ pub fn extract_skins(
         }
         last_start = last_start.max(start);
 
+        let joint_count = target - start;
+        let prev = prev_skin_indices
+            .get(&entity)
+            .map(|prev_skin_index| prev_skin_index.start())
+            .and_then(|prev_start| prev_joints.get(prev_start..prev_start + joint_count));
+        prev_buffer.extend(
+            prev.unwrap_or(&buffer.values()[start..target])
+                .iter()
+                .copied(),
+        );
+
         // Pad to 256 byte alignment
         while buffer.len() % 4 != 0 {
             buffer.push(Mat4::ZERO);
         }
+        while prev_buffer.len() < buffer.len() {
+            prev_buffer.push(Mat4::ZERO);
+        }
 
         skin_indices.insert(entity, SkinIndex::new(start));
     }
@@ -137,6 +180,9 @@ pub fn extract_skins(
     while uniform.buffer.len() - last_start < MAX_JOINTS {
         uniform.buffer.push(Mat4::ZERO);
     }
+    while uniform.prev_buffer.len() < uniform.buffer.len() {
+        uniform.prev_buffer.push(Mat4::ZERO);
+    }
 }
 
 // NOTE: The skinned joints uniform buffer has to be bound at a dynamic offset per
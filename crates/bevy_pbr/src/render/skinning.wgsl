@@ -6,6 +6,11 @@
 
 @group(1) @binding(1) var<uniform> joint_matrices: SkinnedMesh;
 
+#ifdef MOTION_VECTOR_PREPASS
+// The joint matrices of the previous frame, used to compute motion vectors.
+@group(1) @binding(6) var<uniform> prev_joint_matrices: SkinnedMesh;
+#endif
+
 fn skin_model(
     indexes: vec4<u32>,
     weights: vec4<f32>,
@@ -16,6 +21,19 @@ fn skin_model(
         + weights.w * joint_matrices.data[indexes.w];
 }
 
+#ifdef MOTION_VECTOR_PREPASS
+// Returns the skinning matrix of the previous frame.
+fn skin_prev_model(
+    indexes: vec4<u32>,
+    weights: vec4<f32>,
+) -> mat4x4<f32> {
+    return weights.x * prev_joint_matrices.data[indexes.x]
+        + weights.y * prev_joint_matrices.data[indexes.y]
+        + weights.z * prev_joint_matrices.data[indexes.z]
+        + weights.w * prev_joint_matrices.data[indexes.w];
+}
+#endif
+
 fn inverse_transpose_3x3m(in: mat3x3<f32>) -> mat3x3<f32> {
     let x = cross(in[1], in[2]);
     let y = cross(in[2], in[0]);
@@ -35,9 +35,9 @@ fn load_noise(pixel_coordinates: vec2<i32>) -> vec2<f32> {
 }
 
 // Calculate differences in depth between neighbor pixels (later used by the spatial denoiser pass to preserve object edges)
-fn calculate_neighboring_depth_differences(pixel_coordinates: vec2<i32>) -> f32 {
+fn calculate_neighboring_depth_differences(pixel_coordinates: vec2<i32>, viewport_coordinates: vec2<i32>) -> f32 {
     // Sample the pixel's depth and 4 depths around it
-    let uv = vec2<f32>(pixel_coordinates) / view.viewport.zw;
+    let uv = vec2<f32>(viewport_coordinates) / view.viewport.zw;
     let depths_upper_left = textureGather(0, preprocessed_depth, point_clamp_sampler, uv);
     let depths_bottom_right = textureGather(0, preprocessed_depth, point_clamp_sampler, uv, vec2<i32>(1i, 1i));
     let depth_center = depths_upper_left.y;
@@ -98,9 +98,16 @@ fn gtao(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let falloff_add = falloff_from / falloff_range + 1.0;
 
     let pixel_coordinates = vec2<i32>(global_id.xy);
+#ifdef HALF_RESOLUTION
+    // Each pixel of the ambient occlusion texture covers 2x2 pixels of the viewport
+    let viewport_coordinates = pixel_coordinates * 2i;
+    let uv = (vec2<f32>(viewport_coordinates) + 1.0) / view.viewport.zw;
+#else
+    let viewport_coordinates = pixel_coordinates;
     let uv = (vec2<f32>(pixel_coordinates) + 0.5) / view.viewport.zw;
+#endif
 
-    var pixel_depth = calculate_neighboring_depth_differences(pixel_coordinates);
+    var pixel_depth = calculate_neighboring_depth_differences(pixel_coordinates, viewport_coordinates);
     pixel_depth += 0.00001; // Avoid depth precision issues
 
     let pixel_position = reconstruct_view_space_position(pixel_depth, uv);
@@ -14,6 +14,7 @@ use bevy_ecs::{
     system::{Commands, Query, Res, ResMut, Resource},
     world::{FromWorld, World},
 };
+use bevy_math::UVec2;
 use bevy_reflect::Reflect;
 use bevy_render::{
     camera::{ExtractedCamera, TemporalJitter},
@@ -157,6 +158,21 @@ pub struct ScreenSpaceAmbientOcclusionBundle {
 #[reflect(Component)]
 pub struct ScreenSpaceAmbientOcclusionSettings {
     pub quality_level: ScreenSpaceAmbientOcclusionQualityLevel,
+    /// Computes ambient occlusion at half of the resolution of the viewport in each
+    /// dimension, which is about four times cheaper, at the cost of blurrier occlusion
+    /// around edges.
+    pub half_resolution: bool,
+}
+
+impl ScreenSpaceAmbientOcclusionSettings {
+    /// Returns the size of the ambient occlusion texture of a viewport of the given size.
+    fn texture_size(&self, viewport_size: UVec2) -> UVec2 {
+        if self.half_resolution {
+            UVec2::new(div_ceil(viewport_size.x, 2), div_ceil(viewport_size.y, 2))
+        } else {
+            viewport_size
+        }
+    }
 }
 
 #[derive(Reflect, PartialEq, Eq, Hash, Clone, Copy, Default)]
@@ -198,13 +214,16 @@ impl ViewNode for SsaoNode {
         &'static SsaoPipelineId,
         &'static SsaoBindGroups,
         &'static ViewUniformOffset,
+        &'static ScreenSpaceAmbientOcclusionSettings,
     );
 
     fn run(
         &self,
         _graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
-        (camera, pipeline_id, bind_groups, view_uniform_offset): QueryItem<Self::ViewQuery>,
+        (camera, pipeline_id, bind_groups, view_uniform_offset, ssao_settings): QueryItem<
+            Self::ViewQuery,
+        >,
         world: &World,
     ) -> Result<(), NodeRunError> {
         let pipelines = world.resource::<SsaoPipelines>();
@@ -224,6 +243,8 @@ impl ViewNode for SsaoNode {
             return Ok(());
         };
 
+        let ssao_size = ssao_settings.texture_size(camera_size);
+
         render_context.command_encoder().push_debug_group("ssao");
 
         {
@@ -263,11 +284,7 @@ impl ViewNode for SsaoNode {
                 &bind_groups.common_bind_group,
                 &[view_uniform_offset.offset],
             );
-            gtao_pass.dispatch_workgroups(
-                div_ceil(camera_size.x, 8),
-                div_ceil(camera_size.y, 8),
-                1,
-            );
+            gtao_pass.dispatch_workgroups(div_ceil(ssao_size.x, 8), div_ceil(ssao_size.y, 8), 1);
         }
 
         {
@@ -286,8 +303,8 @@ impl ViewNode for SsaoNode {
                 &[view_uniform_offset.offset],
             );
             spatial_denoise_pass.dispatch_workgroups(
-                div_ceil(camera_size.x, 8),
-                div_ceil(camera_size.y, 8),
+                div_ceil(ssao_size.x, 8),
+                div_ceil(ssao_size.y, 8),
                 1,
             );
         }
@@ -466,6 +483,10 @@ impl SpecializedComputePipeline for SsaoPipelines {
             shader_defs.push("TEMPORAL_JITTER".into());
         }
 
+        if key.ssao_settings.half_resolution {
+            shader_defs.push("HALF_RESOLUTION".into());
+        }
+
         ComputePipelineDescriptor {
             label: Some("ssao_gtao_pipeline".into()),
             layout: vec![
@@ -517,9 +538,13 @@ fn prepare_ssao_textures(
     mut commands: Commands,
     mut texture_cache: ResMut<TextureCache>,
     render_device: Res<RenderDevice>,
-    views: Query<(Entity, &ExtractedCamera), With<ScreenSpaceAmbientOcclusionSettings>>,
+    views: Query<(
+        Entity,
+        &ExtractedCamera,
+        &ScreenSpaceAmbientOcclusionSettings,
+    )>,
 ) {
-    for (entity, camera) in &views {
+    for (entity, camera, ssao_settings) in &views {
         let Some(physical_viewport_size) = camera.physical_viewport_size else {
             continue;
         };
@@ -528,6 +553,12 @@ fn prepare_ssao_textures(
             height: physical_viewport_size.y,
             depth_or_array_layers: 1,
         };
+        let ssao_size = ssao_settings.texture_size(physical_viewport_size);
+        let ssao_size = Extent3d {
+            width: ssao_size.x,
+            height: ssao_size.y,
+            depth_or_array_layers: 1,
+        };
 
         let preprocessed_depth_texture = texture_cache.get(
             &render_device,
@@ -547,7 +578,7 @@ fn prepare_ssao_textures(
             &render_device,
             TextureDescriptor {
                 label: Some("ssao_noisy_texture"),
-                size,
+                size: ssao_size,
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: TextureDimension::D2,
@@ -561,7 +592,7 @@ fn prepare_ssao_textures(
             &render_device,
             TextureDescriptor {
                 label: Some("ssao_texture"),
-                size,
+                size: ssao_size,
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: TextureDimension::D2,
@@ -575,7 +606,7 @@ fn prepare_ssao_textures(
             &render_device,
             TextureDescriptor {
                 label: Some("ssao_depth_differences_texture"),
-                size,
+                size: ssao_size,
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: TextureDimension::D2,
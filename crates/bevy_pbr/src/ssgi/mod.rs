@@ -0,0 +1,648 @@
+use crate::NodePbr;
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_core_pipeline::{
+    core_3d::graph::{Core3d, Node3d},
+    prelude::Camera3d,
+    prepass::{DepthPrepass, NormalPrepass, ViewPrepassTextures},
+};
+use bevy_ecs::{
+    prelude::{Bundle, Component, Entity},
+    query::{QueryItem, With},
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use bevy_math::UVec2;
+use bevy_reflect::Reflect;
+use bevy_render::{
+    camera::ExtractedCamera,
+    extract_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
+    globals::{GlobalsBuffer, GlobalsUniform},
+    prelude::Camera,
+    render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
+    render_resource::{
+        binding_types::{texture_2d, texture_depth_2d, texture_storage_2d, uniform_buffer},
+        *,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::{CachedTexture, TextureCache},
+    view::{Msaa, ViewTarget, ViewUniform, ViewUniformOffset, ViewUniforms},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_utils::tracing::error;
+
+const SSGI_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(319257184659034);
+
+/// On WebGL and WebGPU, we don't add the screen space global illumination
+/// texture to the mesh view bind group, as otherwise we can overflow the number
+/// of texture bindings (see issue #11885).
+pub(crate) const SCREEN_SPACE_GLOBAL_ILLUMINATION_IS_USABLE: bool =
+    cfg!(not(target_arch = "wasm32"));
+
+/// Plugin for screen space global illumination.
+pub struct ScreenSpaceGlobalIlluminationPlugin;
+
+impl Plugin for ScreenSpaceGlobalIlluminationPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, SSGI_SHADER_HANDLE, "ssgi.wgsl", Shader::from_wgsl);
+
+        app.register_type::<ScreenSpaceGlobalIlluminationSettings>()
+            .add_plugins(UniformComponentPlugin::<ScreenSpaceGlobalIlluminationUniform>::default());
+    }
+
+    fn finish(&self, app: &mut App) {
+        if !SCREEN_SPACE_GLOBAL_ILLUMINATION_IS_USABLE {
+            return;
+        }
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SsgiPipelines>()
+            .init_resource::<SpecializedComputePipelines<SsgiPipelines>>()
+            .add_systems(ExtractSchedule, extract_ssgi_settings)
+            .add_systems(
+                Render,
+                (
+                    prepare_ssgi_pipelines.in_set(RenderSet::Prepare),
+                    prepare_ssgi_textures.in_set(RenderSet::PrepareResources),
+                    prepare_ssgi_bind_groups.in_set(RenderSet::PrepareBindGroups),
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<SsgiNode>>(
+                Core3d,
+                NodePbr::ScreenSpaceGlobalIllumination,
+            )
+            .add_render_graph_node::<ViewNodeRunner<SsgiHistoryNode>>(
+                Core3d,
+                NodePbr::ScreenSpaceGlobalIlluminationHistory,
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    // END_PRE_PASSES -> SCREEN_SPACE_GLOBAL_ILLUMINATION -> MAIN_PASS
+                    Node3d::EndPrepasses,
+                    NodePbr::ScreenSpaceGlobalIllumination,
+                    Node3d::StartMainPass,
+                ),
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    // END_MAIN_PASS -> SCREEN_SPACE_GLOBAL_ILLUMINATION_HISTORY -> TONEMAPPING
+                    Node3d::EndMainPass,
+                    NodePbr::ScreenSpaceGlobalIlluminationHistory,
+                    Node3d::Tonemapping,
+                ),
+            );
+    }
+}
+
+/// Bundle to apply screen space global illumination.
+#[derive(Bundle, Default)]
+pub struct ScreenSpaceGlobalIlluminationBundle {
+    pub settings: ScreenSpaceGlobalIlluminationSettings,
+    pub depth_prepass: DepthPrepass,
+    pub normal_prepass: NormalPrepass,
+}
+
+/// Component to apply screen space global illumination (SSGI) to a 3d camera.
+///
+/// SSGI approximates diffuse light bouncing between surfaces that are visible
+/// on-screen. Every frame, a few rays per pixel are marched through the depth
+/// buffer, and the previous frame's color at the surfaces they hit is added to
+/// the diffuse indirect light of the main pass. Because the previous frame
+/// already contains the bounced light of the frame before it, multiple bounces
+/// accumulate over time.
+///
+/// Surfaces that are off-screen or hidden behind other objects don't
+/// contribute, so SSGI works best as a complement to baked or probe-based
+/// indirect lighting (e.g. [`crate::EnvironmentMapLight`]) rather than as a
+/// replacement. Meshes with a [`crate::Lightmap`] don't receive SSGI.
+///
+/// # Usage Notes
+///
+/// Requires that you add [`DepthPrepass`] and [`NormalPrepass`] components to
+/// your camera, and that [`Msaa`] is off. The camera should be HDR, as
+/// otherwise the previous frame's color is already tonemapped.
+///
+/// It is strongly recommended that you use SSGI in conjunction with
+/// TAA ([`bevy_core_pipeline::experimental::taa::TemporalAntiAliasSettings`]).
+/// Doing so greatly reduces SSGI noise.
+///
+/// SSGI is not supported on `WebGL2` or `WebGPU`.
+#[derive(Component, Reflect, PartialEq, Clone)]
+#[reflect(Component)]
+pub struct ScreenSpaceGlobalIlluminationSettings {
+    /// Multiplier for the bounced light.
+    pub intensity: f32,
+    /// Maximum distance, in world units, that a ray travels before giving up.
+    pub max_distance: f32,
+    /// How thick, in world units, surfaces in the depth buffer are assumed to be.
+    ///
+    /// Rays that pass behind a surface by more than this distance don't hit it.
+    pub thickness: f32,
+    /// Number of rays traced per pixel each frame.
+    pub ray_count: u32,
+    /// Number of depth buffer samples taken along each ray.
+    pub step_count: u32,
+    /// Computes global illumination at half of the resolution of the viewport in each
+    /// dimension, which is about four times cheaper, at the cost of blurrier lighting
+    /// around edges.
+    pub half_resolution: bool,
+}
+
+impl Default for ScreenSpaceGlobalIlluminationSettings {
+    fn default() -> Self {
+        Self {
+            intensity: 1.0,
+            max_distance: 2.0,
+            thickness: 0.25,
+            ray_count: 4,
+            step_count: 16,
+            half_resolution: true,
+        }
+    }
+}
+
+impl ScreenSpaceGlobalIlluminationSettings {
+    /// Returns the size of the global illumination texture of a viewport of the given size.
+    fn texture_size(&self, viewport_size: UVec2) -> UVec2 {
+        if self.half_resolution {
+            UVec2::new(div_ceil(viewport_size.x, 2), div_ceil(viewport_size.y, 2))
+        } else {
+            viewport_size
+        }
+    }
+}
+
+/// The GPU representation of the non-structural parts of
+/// [`ScreenSpaceGlobalIlluminationSettings`].
+#[derive(Component, ShaderType, Clone)]
+pub struct ScreenSpaceGlobalIlluminationUniform {
+    intensity: f32,
+    max_distance: f32,
+    thickness: f32,
+}
+
+#[derive(Default)]
+struct SsgiNode {}
+
+impl ViewNode for SsgiNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static SsgiPipelineIds,
+        &'static SsgiBindGroups,
+        &'static ViewUniformOffset,
+        &'static DynamicUniformIndex<ScreenSpaceGlobalIlluminationUniform>,
+        &'static ScreenSpaceGlobalIlluminationSettings,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, pipeline_ids, bind_groups, view_uniform_offset, ssgi_uniform_index, ssgi_settings): QueryItem<
+            Self::ViewQuery,
+        >,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let (Some(camera_size), Some(trace_pipeline), Some(spatial_denoise_pipeline)) = (
+            camera.physical_viewport_size,
+            pipeline_cache.get_compute_pipeline(pipeline_ids.trace),
+            pipeline_cache.get_compute_pipeline(pipeline_ids.spatial_denoise),
+        ) else {
+            return Ok(());
+        };
+
+        let ssgi_size = ssgi_settings.texture_size(camera_size);
+        let dynamic_offsets = [view_uniform_offset.offset, ssgi_uniform_index.index()];
+
+        render_context.command_encoder().push_debug_group("ssgi");
+
+        {
+            let mut trace_pass =
+                render_context
+                    .command_encoder()
+                    .begin_compute_pass(&ComputePassDescriptor {
+                        label: Some("ssgi_trace_pass"),
+                        timestamp_writes: None,
+                    });
+            trace_pass.set_pipeline(trace_pipeline);
+            trace_pass.set_bind_group(0, &bind_groups.trace_bind_group, &[]);
+            trace_pass.set_bind_group(1, &bind_groups.common_bind_group, &dynamic_offsets);
+            trace_pass.dispatch_workgroups(div_ceil(ssgi_size.x, 8), div_ceil(ssgi_size.y, 8), 1);
+        }
+
+        {
+            let mut spatial_denoise_pass =
+                render_context
+                    .command_encoder()
+                    .begin_compute_pass(&ComputePassDescriptor {
+                        label: Some("ssgi_spatial_denoise_pass"),
+                        timestamp_writes: None,
+                    });
+            spatial_denoise_pass.set_pipeline(spatial_denoise_pipeline);
+            spatial_denoise_pass.set_bind_group(0, &bind_groups.spatial_denoise_bind_group, &[]);
+            spatial_denoise_pass.set_bind_group(
+                1,
+                &bind_groups.common_bind_group,
+                &dynamic_offsets,
+            );
+            spatial_denoise_pass.dispatch_workgroups(
+                div_ceil(ssgi_size.x, 8),
+                div_ceil(ssgi_size.y, 8),
+                1,
+            );
+        }
+
+        render_context.command_encoder().pop_debug_group();
+        Ok(())
+    }
+}
+
+/// Copies the lit main pass color into the history texture, for the next
+/// frame's trace pass to sample.
+#[derive(Default)]
+struct SsgiHistoryNode {}
+
+impl ViewNode for SsgiHistoryNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static ScreenSpaceGlobalIlluminationTextures,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, ssgi_textures): QueryItem<Self::ViewQuery>,
+        _world: &World,
+    ) -> Result<(), NodeRunError> {
+        let history_texture = &ssgi_textures.history_texture.texture;
+        if view_target.main_texture().size() != history_texture.size() {
+            return Ok(());
+        }
+
+        render_context.command_encoder().copy_texture_to_texture(
+            view_target.main_texture().as_image_copy(),
+            history_texture.as_image_copy(),
+            history_texture.size(),
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Resource)]
+struct SsgiPipelines {
+    common_bind_group_layout: BindGroupLayout,
+    trace_bind_group_layout: BindGroupLayout,
+    spatial_denoise_bind_group_layout: BindGroupLayout,
+}
+
+impl FromWorld for SsgiPipelines {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let common_bind_group_layout = render_device.create_bind_group_layout(
+            "ssgi_common_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    uniform_buffer::<ViewUniform>(true),
+                    uniform_buffer::<GlobalsUniform>(false),
+                    uniform_buffer::<ScreenSpaceGlobalIlluminationUniform>(true),
+                ),
+            ),
+        );
+
+        let trace_bind_group_layout = render_device.create_bind_group_layout(
+            "ssgi_trace_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_depth_2d(),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_storage_2d(TextureFormat::Rgba16Float, StorageTextureAccess::WriteOnly),
+                ),
+            ),
+        );
+
+        let spatial_denoise_bind_group_layout = render_device.create_bind_group_layout(
+            "ssgi_spatial_denoise_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_depth_2d(),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_storage_2d(TextureFormat::Rgba16Float, StorageTextureAccess::WriteOnly),
+                ),
+            ),
+        );
+
+        Self {
+            common_bind_group_layout,
+            trace_bind_group_layout,
+            spatial_denoise_bind_group_layout,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+struct SsgiPipelineKey {
+    spatial_denoise: bool,
+    half_resolution: bool,
+    ray_count: u32,
+    step_count: u32,
+}
+
+impl SpecializedComputePipeline for SsgiPipelines {
+    type Key = SsgiPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> ComputePipelineDescriptor {
+        let mut shader_defs = vec![
+            ShaderDefVal::UInt("RAY_COUNT".to_string(), key.ray_count.max(1)),
+            ShaderDefVal::UInt("STEP_COUNT".to_string(), key.step_count.max(1)),
+        ];
+
+        if key.half_resolution {
+            shader_defs.push("HALF_RESOLUTION".into());
+        }
+
+        let (label, bind_group_layout, entry_point) = if key.spatial_denoise {
+            shader_defs.push("SPATIAL_DENOISE".into());
+            (
+                "ssgi_spatial_denoise_pipeline",
+                &self.spatial_denoise_bind_group_layout,
+                "spatial_denoise",
+            )
+        } else {
+            (
+                "ssgi_trace_pipeline",
+                &self.trace_bind_group_layout,
+                "trace",
+            )
+        };
+
+        ComputePipelineDescriptor {
+            label: Some(label.into()),
+            layout: vec![
+                bind_group_layout.clone(),
+                self.common_bind_group_layout.clone(),
+            ],
+            push_constant_ranges: vec![],
+            shader: SSGI_SHADER_HANDLE,
+            shader_defs,
+            entry_point: entry_point.into(),
+        }
+    }
+}
+
+fn extract_ssgi_settings(
+    mut commands: Commands,
+    cameras: Extract<
+        Query<
+            (Entity, &Camera, &ScreenSpaceGlobalIlluminationSettings),
+            (With<Camera3d>, With<DepthPrepass>, With<NormalPrepass>),
+        >,
+    >,
+    msaa: Extract<Res<Msaa>>,
+) {
+    for (entity, camera, ssgi_settings) in &cameras {
+        if **msaa != Msaa::Off {
+            error!(
+                "SSGI is being used which requires Msaa::Off, but Msaa is currently set to Msaa::{:?}",
+                **msaa
+            );
+            return;
+        }
+
+        if camera.is_active {
+            commands.get_or_spawn(entity).insert((
+                ssgi_settings.clone(),
+                ScreenSpaceGlobalIlluminationUniform {
+                    intensity: ssgi_settings.intensity,
+                    max_distance: ssgi_settings.max_distance,
+                    thickness: ssgi_settings.thickness,
+                },
+            ));
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ScreenSpaceGlobalIlluminationTextures {
+    history_texture: CachedTexture,
+    ssgi_noisy_texture: CachedTexture, // Pre-spatially denoised texture
+    pub screen_space_global_illumination_texture: CachedTexture, // Spatially denoised texture
+}
+
+fn prepare_ssgi_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    views: Query<(
+        Entity,
+        &ExtractedCamera,
+        &ViewTarget,
+        &ScreenSpaceGlobalIlluminationSettings,
+    )>,
+) {
+    for (entity, camera, view_target, ssgi_settings) in &views {
+        let (Some(physical_viewport_size), Some(physical_target_size)) =
+            (camera.physical_viewport_size, camera.physical_target_size)
+        else {
+            continue;
+        };
+        let ssgi_size = ssgi_settings.texture_size(physical_viewport_size);
+        let ssgi_size = Extent3d {
+            width: ssgi_size.x,
+            height: ssgi_size.y,
+            depth_or_array_layers: 1,
+        };
+
+        // The history has to match the main texture exactly, since it's filled by a copy.
+        let history_texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("ssgi_history_texture"),
+                size: Extent3d {
+                    width: physical_target_size.x,
+                    height: physical_target_size.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: view_target.main_texture_format(),
+                usage: TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+
+        let ssgi_noisy_texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("ssgi_noisy_texture"),
+                size: ssgi_size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba16Float,
+                usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+
+        let ssgi_texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("ssgi_texture"),
+                size: ssgi_size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba16Float,
+                usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(ScreenSpaceGlobalIlluminationTextures {
+                history_texture,
+                ssgi_noisy_texture,
+                screen_space_global_illumination_texture: ssgi_texture,
+            });
+    }
+}
+
+#[derive(Component)]
+struct SsgiPipelineIds {
+    trace: CachedComputePipelineId,
+    spatial_denoise: CachedComputePipelineId,
+}
+
+fn prepare_ssgi_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedComputePipelines<SsgiPipelines>>,
+    pipeline: Res<SsgiPipelines>,
+    views: Query<(Entity, &ScreenSpaceGlobalIlluminationSettings)>,
+) {
+    for (entity, ssgi_settings) in &views {
+        let key = SsgiPipelineKey {
+            spatial_denoise: false,
+            half_resolution: ssgi_settings.half_resolution,
+            ray_count: ssgi_settings.ray_count,
+            step_count: ssgi_settings.step_count,
+        };
+
+        let trace = pipelines.specialize(&pipeline_cache, &pipeline, key);
+        let spatial_denoise = pipelines.specialize(
+            &pipeline_cache,
+            &pipeline,
+            SsgiPipelineKey {
+                spatial_denoise: true,
+                ..key
+            },
+        );
+
+        commands.entity(entity).insert(SsgiPipelineIds {
+            trace,
+            spatial_denoise,
+        });
+    }
+}
+
+#[derive(Component)]
+struct SsgiBindGroups {
+    common_bind_group: BindGroup,
+    trace_bind_group: BindGroup,
+    spatial_denoise_bind_group: BindGroup,
+}
+
+fn prepare_ssgi_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    pipelines: Res<SsgiPipelines>,
+    view_uniforms: Res<ViewUniforms>,
+    global_uniforms: Res<GlobalsBuffer>,
+    ssgi_uniforms: Res<ComponentUniforms<ScreenSpaceGlobalIlluminationUniform>>,
+    views: Query<(
+        Entity,
+        &ScreenSpaceGlobalIlluminationTextures,
+        &ViewPrepassTextures,
+    )>,
+) {
+    let (Some(view_uniforms), Some(globals_uniforms), Some(ssgi_uniforms)) = (
+        view_uniforms.uniforms.binding(),
+        global_uniforms.buffer.binding(),
+        ssgi_uniforms.binding(),
+    ) else {
+        return;
+    };
+
+    for (entity, ssgi_textures, prepass_textures) in &views {
+        let (Some(depth_view), Some(normal_view)) = (
+            prepass_textures.depth_view(),
+            prepass_textures.normal_view(),
+        ) else {
+            continue;
+        };
+
+        let common_bind_group = render_device.create_bind_group(
+            "ssgi_common_bind_group",
+            &pipelines.common_bind_group_layout,
+            &BindGroupEntries::sequential((
+                view_uniforms.clone(),
+                globals_uniforms.clone(),
+                ssgi_uniforms.clone(),
+            )),
+        );
+
+        let trace_bind_group = render_device.create_bind_group(
+            "ssgi_trace_bind_group",
+            &pipelines.trace_bind_group_layout,
+            &BindGroupEntries::sequential((
+                depth_view,
+                normal_view,
+                &ssgi_textures.history_texture.default_view,
+                &ssgi_textures.ssgi_noisy_texture.default_view,
+            )),
+        );
+
+        let spatial_denoise_bind_group = render_device.create_bind_group(
+            "ssgi_spatial_denoise_bind_group",
+            &pipelines.spatial_denoise_bind_group_layout,
+            &BindGroupEntries::sequential((
+                &ssgi_textures.ssgi_noisy_texture.default_view,
+                depth_view,
+                normal_view,
+                &ssgi_textures
+                    .screen_space_global_illumination_texture
+                    .default_view,
+            )),
+        );
+
+        commands.entity(entity).insert(SsgiBindGroups {
+            common_bind_group,
+            trace_bind_group,
+            spatial_denoise_bind_group,
+        });
+    }
+}
+
+/// Divide `numerator` by `denominator`, rounded up to the nearest multiple of `denominator`.
+fn div_ceil(numerator: u32, denominator: u32) -> u32 {
+    (numerator + denominator - 1) / denominator
+}
@@ -0,0 +1,207 @@
+// Screen space global illumination (SSGI)
+//
+// Traces a handful of cosine-weighted rays per pixel through the depth
+// prepass. When a ray hits on-screen geometry, the radiance of that surface is
+// fetched from the previous frame's color, which makes the result a single
+// bounce of diffuse indirect light per frame (and more bounces over time, since
+// the previous frame already contained the GI of the frame before it).
+
+#import bevy_pbr::utils::PI
+#import bevy_render::{
+    view::View,
+    globals::Globals,
+}
+
+struct ScreenSpaceGlobalIlluminationUniform {
+    intensity: f32,
+    max_distance: f32,
+    thickness: f32,
+}
+
+#ifdef SPATIAL_DENOISE
+@group(0) @binding(0) var indirect_light_noisy: texture_2d<f32>;
+@group(0) @binding(1) var depth_prepass_texture: texture_depth_2d;
+@group(0) @binding(2) var normal_prepass_texture: texture_2d<f32>;
+@group(0) @binding(3) var indirect_light: texture_storage_2d<rgba16float, write>;
+#else
+@group(0) @binding(0) var depth_prepass_texture: texture_depth_2d;
+@group(0) @binding(1) var normal_prepass_texture: texture_2d<f32>;
+@group(0) @binding(2) var history_texture: texture_2d<f32>;
+@group(0) @binding(3) var indirect_light: texture_storage_2d<rgba16float, write>;
+#endif
+@group(1) @binding(0) var<uniform> view: View;
+@group(1) @binding(1) var<uniform> globals: Globals;
+@group(1) @binding(2) var<uniform> settings: ScreenSpaceGlobalIlluminationUniform;
+
+// Converts a pixel of the SSGI texture to a pixel of the (full resolution)
+// prepass textures.
+fn full_resolution_coordinates(pixel_coordinates: vec2<i32>) -> vec2<i32> {
+#ifdef HALF_RESOLUTION
+    return pixel_coordinates * 2i;
+#else
+    return pixel_coordinates;
+#endif
+}
+
+fn reconstruct_view_space_position(depth: f32, uv: vec2<f32>) -> vec3<f32> {
+    let clip_xy = vec2<f32>(uv.x * 2.0 - 1.0, 1.0 - 2.0 * uv.y);
+    let t = view.inverse_projection * vec4<f32>(clip_xy, depth, 1.0);
+    return t.xyz / t.w;
+}
+
+fn load_world_normal(pixel_coordinates: vec2<i32>) -> vec3<f32> {
+    return normalize(textureLoad(normal_prepass_texture, pixel_coordinates, 0i).xyz * 2.0 - 1.0);
+}
+
+// PCG hash, see https://www.jcgt.org/published/0009/03/02/
+fn pcg_hash(input: u32) -> u32 {
+    let state = input * 747796405u + 2891336453u;
+    let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+fn random_float(seed: ptr<function, u32>) -> f32 {
+    *seed = pcg_hash(*seed);
+    return f32(*seed) / 4294967295.0;
+}
+
+// Returns a cosine-weighted direction in the hemisphere around `normal`.
+fn cosine_weighted_direction(normal: vec3<f32>, seed: ptr<function, u32>) -> vec3<f32> {
+    let u = random_float(seed);
+    let v = random_float(seed);
+    let phi = 2.0 * PI * u;
+    let sin_theta = sqrt(v);
+    let local = vec3<f32>(cos(phi) * sin_theta, sin(phi) * sin_theta, sqrt(1.0 - v));
+
+    // Build an orthonormal basis around the normal (Duff et al. 2017)
+    let s = select(-1.0, 1.0, normal.z >= 0.0);
+    let a = -1.0 / (s + normal.z);
+    let b = normal.x * normal.y * a;
+    let tangent = vec3<f32>(1.0 + s * normal.x * normal.x * a, s * b, -s * normal.x);
+    let bitangent = vec3<f32>(b, s + normal.y * normal.y * a, -normal.y);
+
+    return normalize(tangent * local.x + bitangent * local.y + normal * local.z);
+}
+
+#ifndef SPATIAL_DENOISE
+@compute
+@workgroup_size(8, 8, 1)
+fn trace(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let pixel_coordinates = vec2<i32>(global_id.xy);
+    if any(pixel_coordinates >= vec2<i32>(textureDimensions(indirect_light))) {
+        return;
+    }
+
+    let viewport_coordinates = full_resolution_coordinates(pixel_coordinates);
+    let depth = textureLoad(depth_prepass_texture, viewport_coordinates, 0i);
+
+    // Nothing was rendered at this pixel (reverse Z: 0.0 is the far plane)
+    if depth == 0.0 {
+        textureStore(indirect_light, pixel_coordinates, vec4<f32>(0.0));
+        return;
+    }
+
+    let uv = (vec2<f32>(viewport_coordinates) + 0.5) / view.viewport.zw;
+    let origin = reconstruct_view_space_position(depth, uv);
+    let inverse_view = mat3x3<f32>(
+        view.inverse_view[0].xyz,
+        view.inverse_view[1].xyz,
+        view.inverse_view[2].xyz,
+    );
+    let normal = normalize(inverse_view * load_world_normal(viewport_coordinates));
+
+    var seed = pcg_hash(u32(pixel_coordinates.x) + pcg_hash(u32(pixel_coordinates.y) + pcg_hash(globals.frame_count)));
+
+    let step_length = settings.max_distance / f32(#{STEP_COUNT});
+    // Offset the ray origin along the normal to avoid self-intersection
+    let ray_origin = origin + normal * step_length * 0.5;
+
+    var radiance = vec3<f32>(0.0);
+    for (var ray_index = 0u; ray_index < #{RAY_COUNT}u; ray_index += 1u) {
+        let direction = cosine_weighted_direction(normal, &seed);
+        let jitter = random_float(&seed);
+
+        for (var step_index = 0u; step_index < #{STEP_COUNT}u; step_index += 1u) {
+            let sample_position = ray_origin + direction * step_length * (f32(step_index) + jitter);
+            let clip = view.projection * vec4<f32>(sample_position, 1.0);
+            if clip.w <= 0.0 {
+                break;
+            }
+            let ndc = clip.xy / clip.w;
+            let sample_uv = vec2<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
+            if any(sample_uv < vec2<f32>(0.0)) || any(sample_uv >= vec2<f32>(1.0)) {
+                break;
+            }
+
+            let sample_coordinates = vec2<i32>(sample_uv * view.viewport.zw);
+            let scene_depth = textureLoad(depth_prepass_texture, sample_coordinates, 0i);
+            let scene_position = reconstruct_view_space_position(scene_depth, sample_uv);
+
+            // View space looks down -Z, so the ray is behind the depth buffer
+            // when it's further away than the surface stored there.
+            let depth_difference = scene_position.z - sample_position.z;
+            if depth_difference > 0.0 && depth_difference < settings.thickness {
+                // Ignore hits on surfaces facing away from the ray
+                let hit_normal = normalize(inverse_view * load_world_normal(sample_coordinates));
+                if dot(hit_normal, direction) < 0.0 {
+                    let hit_color = textureLoad(history_texture, sample_coordinates, 0i).rgb;
+                    // The history is pre-exposed, and the main pass applies the
+                    // exposure again.
+                    radiance += hit_color / view.exposure;
+                }
+                break;
+            }
+        }
+    }
+
+    // Rays are cosine-weighted, so the mean radiance is the irradiance divided by PI,
+    // which is what the Lambertian diffuse lobe expects.
+    let indirect = settings.intensity * radiance / f32(#{RAY_COUNT});
+    textureStore(indirect_light, pixel_coordinates, vec4<f32>(indirect, 1.0));
+}
+#endif // SPATIAL_DENOISE
+
+#ifdef SPATIAL_DENOISE
+// 5x5 bilateral filter (edge-preserving blur) using depth and normal weights.
+@compute
+@workgroup_size(8, 8, 1)
+fn spatial_denoise(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let pixel_coordinates = vec2<i32>(global_id.xy);
+    let size = vec2<i32>(textureDimensions(indirect_light_noisy));
+    if any(pixel_coordinates >= size) {
+        return;
+    }
+
+    let viewport_coordinates = full_resolution_coordinates(pixel_coordinates);
+    let center_depth = textureLoad(depth_prepass_texture, viewport_coordinates, 0i);
+    if center_depth == 0.0 {
+        textureStore(indirect_light, pixel_coordinates, vec4<f32>(0.0));
+        return;
+    }
+    let center_uv = (vec2<f32>(viewport_coordinates) + 0.5) / view.viewport.zw;
+    let center_z = reconstruct_view_space_position(center_depth, center_uv).z;
+    let center_normal = load_world_normal(viewport_coordinates);
+
+    var sum = vec3<f32>(0.0);
+    var total_weight = 0.0;
+    for (var y = -2i; y <= 2i; y += 1i) {
+        for (var x = -2i; x <= 2i; x += 1i) {
+            let sample_coordinates = clamp(pixel_coordinates + vec2<i32>(x, y), vec2<i32>(0i), size - 1i);
+            let sample_viewport_coordinates = full_resolution_coordinates(sample_coordinates);
+            let sample_depth = textureLoad(depth_prepass_texture, sample_viewport_coordinates, 0i);
+            let sample_uv = (vec2<f32>(sample_viewport_coordinates) + 0.5) / view.viewport.zw;
+            let sample_z = reconstruct_view_space_position(sample_depth, sample_uv).z;
+            let sample_normal = load_world_normal(sample_viewport_coordinates);
+
+            let depth_weight = exp(-abs(sample_z - center_z) / (0.05 * abs(center_z) + 0.001));
+            let normal_weight = pow(saturate(dot(sample_normal, center_normal)), 8.0);
+            let weight = depth_weight * normal_weight;
+
+            sum += textureLoad(indirect_light_noisy, sample_coordinates, 0i).rgb * weight;
+            total_weight += weight;
+        }
+    }
+
+    textureStore(indirect_light, pixel_coordinates, vec4<f32>(sum / max(total_weight, 0.0001), 1.0));
+}
+#endif // SPATIAL_DENOISE
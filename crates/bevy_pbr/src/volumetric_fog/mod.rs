@@ -0,0 +1,257 @@
+//! Volumetric fog and light shafts.
+//!
+//! Volumetric fog simulates light scattering in participating media such as
+//! mist, smoke or dust. Unlike the cheap distance fog provided by
+//! [`crate::FogSettings`], volumetric fog is lit by the directional, point and
+//! spot lights in the scene and respects their shadows, which produces light
+//! shafts ("god rays").
+//!
+//! The implementation is froxel-based: the view frustum is divided into a grid
+//! of frustum-aligned voxels (*froxels*). Each frame:
+//!
+//! 1. Every froxel computes its fog density and the light scattered towards
+//!    the camera, and blends the result with the reprojected value from the
+//!    previous frame.
+//! 2. The froxels are integrated front to back, producing the accumulated
+//!    scattered light and transmittance at every depth.
+//! 3. The integrated volume is applied on top of the rendered scene, using the
+//!    depth prepass to find how much fog lies in front of each pixel.
+//!
+//! To use it, add [`VolumetricFogSettings`] and a
+//! [`bevy_core_pipeline::prepass::DepthPrepass`] to a 3D camera, and optionally
+//! spawn [`FogVolume`]s to add local patches of denser fog.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_color::Color;
+use bevy_core_pipeline::core_3d::graph::{Core3d, Node3d};
+use bevy_ecs::{
+    bundle::Bundle, component::Component, reflect::ReflectComponent,
+    schedule::IntoSystemConfigs as _,
+};
+use bevy_math::UVec3;
+use bevy_reflect::Reflect;
+use bevy_render::{
+    render_graph::{RenderGraphApp, ViewNodeRunner},
+    render_resource::{Shader, SpecializedRenderPipelines},
+    renderer::RenderDevice,
+    ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::tracing::warn;
+
+use crate::graph::NodePbr;
+
+use self::render::{
+    extract_volumetric_fog, prepare_volumetric_fog_bind_groups, prepare_volumetric_fog_pipelines,
+    prepare_volumetric_fog_textures, prepare_volumetric_fog_uniforms, ExtractedFogVolumes,
+    VolumetricFogHistory, VolumetricFogNode, VolumetricFogPipeline, VolumetricFogUniformBuffer,
+};
+
+mod render;
+
+const VOLUMETRIC_FOG_TYPES_HANDLE: Handle<Shader> = Handle::weak_from_u128(84106385829476351);
+const VOLUMETRIC_FOG_INJECT_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(19308245670316627);
+const VOLUMETRIC_FOG_INTEGRATE_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(60272935046128813);
+const VOLUMETRIC_FOG_APPLY_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(73518890514436299);
+
+/// The maximum number of [`FogVolume`]s that can affect the fog at once.
+///
+/// Any fog volumes beyond this limit are ignored.
+///
+/// This must be kept in sync with `MAX_FOG_VOLUMES` in
+/// `volumetric_fog_types.wgsl`.
+pub const MAX_FOG_VOLUMES: usize = 16;
+
+/// A plugin that renders volumetric fog for cameras with
+/// [`VolumetricFogSettings`].
+pub struct VolumetricFogPlugin;
+
+/// Add this component to a 3D camera to render volumetric fog.
+///
+/// The camera also needs a [`bevy_core_pipeline::prepass::DepthPrepass`], and
+/// [`bevy_render::view::Msaa`] must be off.
+///
+/// Every directional, point and spot light scatters light in the fog. Lights
+/// with shadows enabled cast light shafts.
+///
+/// Volumetric fog isn't supported on WebGL 2, as it requires compute shaders.
+#[derive(Clone, Copy, Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct VolumetricFogSettings {
+    /// The number of froxels along the X, Y and Z axes of the view frustum.
+    ///
+    /// Higher resolutions produce sharper light shafts at a higher cost. The
+    /// froxels are stored in a texture that's `x` texels wide and `y * z`
+    /// texels high, so `y * z` must not exceed the maximum texture size of the
+    /// GPU.
+    pub froxel_resolution: UVec3,
+
+    /// The distance from the camera, in world units, at which the froxel grid
+    /// ends.
+    ///
+    /// Surfaces beyond this distance receive the fog accumulated up to it.
+    /// Froxels are distributed quadratically in depth, so nearby froxels are
+    /// thinner than distant ones.
+    pub max_distance: f32,
+
+    /// The base density of the fog everywhere in the scene, in extinction per
+    /// world unit.
+    ///
+    /// Set this to zero to only have fog inside [`FogVolume`]s.
+    pub density: f32,
+
+    /// The fraction of light that's scattered (rather than absorbed) when it
+    /// interacts with the fog, per color channel.
+    pub albedo: Color,
+
+    /// The anisotropy of the scattering, from -1 to 1.
+    ///
+    /// Positive values scatter light forward, making the fog brighter when
+    /// looking towards a light; negative values scatter light backward. Zero
+    /// scatters light equally in all directions.
+    pub scattering_asymmetry: f32,
+
+    /// The color of the ambient light that illuminates the fog.
+    pub ambient_color: Color,
+
+    /// The brightness of the ambient light that illuminates the fog, in
+    /// cd/m².
+    pub ambient_intensity: f32,
+
+    /// How much of the previous frame's fog is kept each frame, from 0 to 1.
+    ///
+    /// Higher values reduce noise and flickering at the cost of the fog
+    /// reacting to changes more slowly. Zero disables temporal reprojection.
+    pub temporal_reprojection_weight: f32,
+}
+
+impl Default for VolumetricFogSettings {
+    fn default() -> Self {
+        Self {
+            froxel_resolution: UVec3::new(160, 90, 64),
+            max_distance: 64.0,
+            density: 0.02,
+            albedo: Color::WHITE,
+            scattering_asymmetry: 0.6,
+            ambient_color: Color::WHITE,
+            ambient_intensity: 0.0,
+            temporal_reprojection_weight: 0.9,
+        }
+    }
+}
+
+/// A box-shaped region of additional fog density.
+///
+/// The volume is a unit cube centered on the origin of the entity, transformed
+/// by its [`Transform`]. Use the scale of the transform to size it. The
+/// density of overlapping volumes adds up, and is added on top of
+/// [`VolumetricFogSettings::density`].
+///
+/// At most [`MAX_FOG_VOLUMES`] fog volumes are taken into account.
+#[derive(Clone, Copy, Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct FogVolume {
+    /// The density of the fog inside the volume, in extinction per world
+    /// unit.
+    pub density: f32,
+}
+
+impl Default for FogVolume {
+    fn default() -> Self {
+        Self { density: 0.1 }
+    }
+}
+
+/// A convenient bundle for a [`FogVolume`].
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct FogVolumeBundle {
+    /// The fog volume itself.
+    pub fog_volume: FogVolume,
+    /// The local transform, which positions and sizes the volume.
+    pub transform: Transform,
+    /// The global transform of the volume.
+    pub global_transform: GlobalTransform,
+}
+
+impl Plugin for VolumetricFogPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            VOLUMETRIC_FOG_TYPES_HANDLE,
+            "volumetric_fog_types.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            VOLUMETRIC_FOG_INJECT_SHADER_HANDLE,
+            "volumetric_fog_inject.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            VOLUMETRIC_FOG_INTEGRATE_SHADER_HANDLE,
+            "volumetric_fog_integrate.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            VOLUMETRIC_FOG_APPLY_SHADER_HANDLE,
+            "volumetric_fog_apply.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<VolumetricFogSettings>()
+            .register_type::<FogVolume>();
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        if render_app
+            .world()
+            .resource::<RenderDevice>()
+            .limits()
+            .max_storage_textures_per_shader_stage
+            < 1
+        {
+            warn!("VolumetricFogPlugin not loaded. GPU lacks support: Limits::max_storage_textures_per_shader_stage is less than 1.");
+            return;
+        }
+
+        render_app
+            .init_resource::<VolumetricFogPipeline>()
+            .init_resource::<SpecializedRenderPipelines<VolumetricFogPipeline>>()
+            .init_resource::<VolumetricFogUniformBuffer>()
+            .init_resource::<VolumetricFogHistory>()
+            .init_resource::<ExtractedFogVolumes>()
+            .add_systems(ExtractSchedule, extract_volumetric_fog)
+            .add_systems(
+                Render,
+                (
+                    prepare_volumetric_fog_pipelines.in_set(RenderSet::Prepare),
+                    prepare_volumetric_fog_uniforms.in_set(RenderSet::PrepareResources),
+                    prepare_volumetric_fog_textures.in_set(RenderSet::PrepareResources),
+                    prepare_volumetric_fog_bind_groups.in_set(RenderSet::PrepareBindGroups),
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<VolumetricFogNode>>(
+                Core3d,
+                NodePbr::VolumetricFog,
+            )
+            .add_render_graph_edges(
+                Core3d,
+                // END_MAIN_PASS -> VOLUMETRIC_FOG -> TONEMAPPING
+                (
+                    Node3d::EndMainPass,
+                    NodePbr::VolumetricFog,
+                    Node3d::Tonemapping,
+                ),
+            );
+    }
+}
@@ -0,0 +1,748 @@
+//! Rendering of volumetric fog.
+
+use bevy_color::LinearRgba;
+use bevy_core::FrameCount;
+use bevy_core_pipeline::{
+    core_3d::Camera3d,
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    prepass::{
+        DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass, ViewPrepassTextures,
+    },
+};
+use bevy_derive::Deref;
+use bevy_ecs::{
+    component::Component,
+    entity::{Entity, EntityHashMap},
+    query::{Has, QueryItem, With},
+    system::{Commands, Local, Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use bevy_math::{Mat4, UVec3, Vec3, Vec4};
+use bevy_render::{
+    camera::Camera,
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_resource::{
+        binding_types::{
+            sampler, texture_2d, texture_depth_2d, texture_storage_2d, uniform_buffer,
+        },
+        *,
+    },
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+    texture::{BevyDefault as _, CachedTexture, TextureCache},
+    view::{ExtractedView, Msaa, ViewTarget, ViewUniform, ViewUniformOffset, ViewUniforms},
+    Extract,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::{
+    prelude::default,
+    tracing::{error, warn},
+};
+
+use crate::{
+    FogVolume, MeshPipeline, MeshPipelineViewLayoutKey, MeshViewBindGroup, ViewFogUniformOffset,
+    ViewLightProbesUniformOffset, ViewLightsUniformOffset, VolumetricFogSettings, MAX_FOG_VOLUMES,
+    SCREEN_SPACE_GLOBAL_ILLUMINATION_IS_USABLE,
+};
+
+use super::{
+    VOLUMETRIC_FOG_APPLY_SHADER_HANDLE, VOLUMETRIC_FOG_INJECT_SHADER_HANDLE,
+    VOLUMETRIC_FOG_INTEGRATE_SHADER_HANDLE,
+};
+
+/// The texture format of the froxel textures.
+const FROXEL_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// The render-world copy of the [`FogVolume`]s in the scene.
+#[derive(Resource, Default)]
+pub struct ExtractedFogVolumes(Vec<GpuFogVolume>);
+
+/// The GPU representation of a [`FogVolume`].
+#[derive(Clone, Copy, Default, ShaderType)]
+pub struct GpuFogVolume {
+    /// Transforms world space positions into the unit cube of the volume.
+    local_from_world: Mat4,
+    density: f32,
+}
+
+/// The GPU representation of the [`VolumetricFogSettings`] of a view.
+#[derive(Clone, ShaderType)]
+pub struct VolumetricFogUniform {
+    previous_clip_from_world: Mat4,
+    previous_view_from_world: Mat4,
+    froxel_resolution: UVec3,
+    volume_count: u32,
+    albedo: Vec3,
+    density: f32,
+    ambient: Vec3,
+    scattering_asymmetry: f32,
+    max_distance: f32,
+    temporal_reprojection_weight: f32,
+    /// A per-frame offset, from 0 to 1, of the froxel sample positions along
+    /// the view direction.
+    jitter: f32,
+    volumes: [GpuFogVolume; MAX_FOG_VOLUMES],
+}
+
+/// The buffer that holds the [`VolumetricFogUniform`] of every view.
+#[derive(Resource, Default)]
+pub struct VolumetricFogUniformBuffer(DynamicUniformBuffer<VolumetricFogUniform>);
+
+/// The offset of the [`VolumetricFogUniform`] of a view within the
+/// [`VolumetricFogUniformBuffer`].
+#[derive(Component, Deref)]
+pub struct ViewVolumetricFogUniformOffset(u32);
+
+/// The view matrices of each view in the previous frame, used for temporal
+/// reprojection.
+#[derive(Resource, Default)]
+pub struct VolumetricFogHistory(EntityHashMap<(Mat4, Mat4)>);
+
+/// The pipelines and layouts used to render volumetric fog.
+#[derive(Resource)]
+pub struct VolumetricFogPipeline {
+    mesh_pipeline: MeshPipeline,
+    inject_bind_group_layout: BindGroupLayout,
+    integrate_bind_group_layout: BindGroupLayout,
+    apply_bind_group_layout: BindGroupLayout,
+    integrate_pipeline: CachedComputePipelineId,
+    linear_sampler: Sampler,
+}
+
+/// Identifies a render pipeline of the volumetric fog.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VolumetricFogPipelineKey {
+    /// The pass that computes the density and scattered light of every froxel.
+    ///
+    /// This pass binds the mesh view bind group, so it's specialized on its
+    /// layout.
+    Inject(MeshPipelineViewLayoutKey),
+    /// The pass that applies the integrated fog to the rendered scene.
+    Apply {
+        /// Whether the view is HDR.
+        hdr: bool,
+    },
+}
+
+/// The render pipelines of a view with volumetric fog.
+#[derive(Component)]
+pub struct ViewVolumetricFogPipelines {
+    inject: CachedRenderPipelineId,
+    apply: CachedRenderPipelineId,
+}
+
+/// The froxel textures of a view with volumetric fog.
+///
+/// Each froxel texture is `x` texels wide and `y * z` texels high: the depth
+/// slices of the froxel grid are stacked vertically.
+#[derive(Component)]
+pub struct ViewVolumetricFogTextures {
+    /// The density and scattered light of every froxel, written this frame.
+    froxels: CachedTexture,
+    /// The froxels written in the previous frame.
+    history: CachedTexture,
+    /// The accumulated scattered light and transmittance at every froxel.
+    integrated: CachedTexture,
+}
+
+/// The bind groups of a view with volumetric fog that don't depend on the
+/// main texture.
+#[derive(Component)]
+pub struct ViewVolumetricFogBindGroups {
+    inject: BindGroup,
+    integrate: BindGroup,
+}
+
+/// The render graph node that renders volumetric fog.
+#[derive(Default)]
+pub struct VolumetricFogNode;
+
+impl FromWorld for VolumetricFogPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+
+        let inject_bind_group_layout = render_device.create_bind_group_layout(
+            "volumetric_fog_inject_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    uniform_buffer::<VolumetricFogUniform>(true),
+                ),
+            ),
+        );
+
+        let integrate_bind_group_layout = render_device.create_bind_group_layout(
+            "volumetric_fog_integrate_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_storage_2d(FROXEL_TEXTURE_FORMAT, StorageTextureAccess::WriteOnly),
+                    uniform_buffer::<ViewUniform>(true),
+                    uniform_buffer::<VolumetricFogUniform>(true),
+                ),
+            ),
+        );
+
+        let apply_bind_group_layout = render_device.create_bind_group_layout(
+            "volumetric_fog_apply_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_depth_2d(),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    uniform_buffer::<ViewUniform>(true),
+                    uniform_buffer::<VolumetricFogUniform>(true),
+                ),
+            ),
+        );
+
+        let linear_sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("volumetric_fog_linear_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            ..default()
+        });
+
+        let integrate_pipeline = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+            label: Some("volumetric_fog_integrate_pipeline".into()),
+            layout: vec![integrate_bind_group_layout.clone()],
+            push_constant_ranges: vec![],
+            shader: VOLUMETRIC_FOG_INTEGRATE_SHADER_HANDLE,
+            shader_defs: vec![],
+            entry_point: "integrate".into(),
+        });
+
+        Self {
+            mesh_pipeline: world.resource::<MeshPipeline>().clone(),
+            inject_bind_group_layout,
+            integrate_bind_group_layout,
+            apply_bind_group_layout,
+            integrate_pipeline,
+            linear_sampler,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for VolumetricFogPipeline {
+    type Key = VolumetricFogPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        match key {
+            VolumetricFogPipelineKey::Inject(view_layout_key) => {
+                let mut shader_defs = vec![
+                    // The froxels are lit with the default shadow filter, as
+                    // they're far too blurry for the others to make a
+                    // difference.
+                    "SHADOW_FILTER_METHOD_HARDWARE_2X2".into(),
+                ];
+
+                for (flag, shader_def) in [
+                    (MeshPipelineViewLayoutKey::MULTISAMPLED, "MULTISAMPLED"),
+                    (MeshPipelineViewLayoutKey::DEPTH_PREPASS, "DEPTH_PREPASS"),
+                    (MeshPipelineViewLayoutKey::NORMAL_PREPASS, "NORMAL_PREPASS"),
+                    (
+                        MeshPipelineViewLayoutKey::MOTION_VECTOR_PREPASS,
+                        "MOTION_VECTOR_PREPASS",
+                    ),
+                    (
+                        MeshPipelineViewLayoutKey::DEFERRED_PREPASS,
+                        "DEFERRED_PREPASS",
+                    ),
+                ] {
+                    if view_layout_key.contains(flag) {
+                        shader_defs.push(shader_def.into());
+                    }
+                }
+
+                if SCREEN_SPACE_GLOBAL_ILLUMINATION_IS_USABLE {
+                    shader_defs.push("SCREEN_SPACE_GLOBAL_ILLUMINATION".into());
+                }
+
+                #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
+                shader_defs.push("SIXTEEN_BYTE_ALIGNMENT".into());
+
+                RenderPipelineDescriptor {
+                    label: Some("volumetric_fog_inject_pipeline".into()),
+                    layout: vec![
+                        self.mesh_pipeline.get_view_layout(view_layout_key).clone(),
+                        self.inject_bind_group_layout.clone(),
+                    ],
+                    push_constant_ranges: vec![],
+                    vertex: fullscreen_shader_vertex_state(),
+                    primitive: PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                    fragment: Some(FragmentState {
+                        shader: VOLUMETRIC_FOG_INJECT_SHADER_HANDLE,
+                        shader_defs,
+                        entry_point: "inject".into(),
+                        targets: vec![Some(ColorTargetState {
+                            format: FROXEL_TEXTURE_FORMAT,
+                            blend: None,
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                }
+            }
+
+            VolumetricFogPipelineKey::Apply { hdr } => RenderPipelineDescriptor {
+                label: Some("volumetric_fog_apply_pipeline".into()),
+                layout: vec![self.apply_bind_group_layout.clone()],
+                push_constant_ranges: vec![],
+                vertex: fullscreen_shader_vertex_state(),
+                primitive: PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: MultisampleState::default(),
+                fragment: Some(FragmentState {
+                    shader: VOLUMETRIC_FOG_APPLY_SHADER_HANDLE,
+                    shader_defs: vec![],
+                    entry_point: "apply".into(),
+                    targets: vec![Some(ColorTargetState {
+                        format: if hdr {
+                            ViewTarget::TEXTURE_FORMAT_HDR
+                        } else {
+                            TextureFormat::bevy_default()
+                        },
+                        blend: None,
+                        write_mask: ColorWrites::ALL,
+                    })],
+                }),
+            },
+        }
+    }
+}
+
+/// Extracts [`VolumetricFogSettings`] from cameras and [`FogVolume`]s from the
+/// scene into the render world.
+#[allow(clippy::type_complexity)]
+pub fn extract_volumetric_fog(
+    mut commands: Commands,
+    cameras: Extract<
+        Query<(Entity, &Camera, &VolumetricFogSettings), (With<Camera3d>, With<DepthPrepass>)>,
+    >,
+    fog_volumes: Extract<Query<(&FogVolume, &GlobalTransform)>>,
+    mut extracted_fog_volumes: ResMut<ExtractedFogVolumes>,
+    msaa: Extract<Res<Msaa>>,
+    mut warned_about_fog_volume_count: Local<bool>,
+) {
+    extracted_fog_volumes.0.clear();
+    if cameras.is_empty() {
+        return;
+    }
+
+    if **msaa != Msaa::Off {
+        error!(
+            "Volumetric fog is being used which requires Msaa::Off, but Msaa is currently set to Msaa::{:?}",
+            **msaa
+        );
+        return;
+    }
+
+    for (entity, camera, volumetric_fog_settings) in &cameras {
+        if camera.is_active {
+            commands
+                .get_or_spawn(entity)
+                .insert(*volumetric_fog_settings);
+        }
+    }
+
+    for (fog_volume, global_transform) in &fog_volumes {
+        if extracted_fog_volumes.0.len() == MAX_FOG_VOLUMES {
+            if !*warned_about_fog_volume_count {
+                warn!(
+                    "More than {} fog volumes are present; the extra ones will be ignored",
+                    MAX_FOG_VOLUMES
+                );
+                *warned_about_fog_volume_count = true;
+            }
+            break;
+        }
+
+        extracted_fog_volumes.0.push(GpuFogVolume {
+            local_from_world: global_transform.compute_matrix().inverse(),
+            density: fog_volume.density,
+        });
+    }
+}
+
+/// Specializes the render pipelines of each view with volumetric fog.
+#[allow(clippy::type_complexity)]
+pub fn prepare_volumetric_fog_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<VolumetricFogPipeline>>,
+    volumetric_fog_pipeline: Res<VolumetricFogPipeline>,
+    msaa: Res<Msaa>,
+    views: Query<
+        (
+            Entity,
+            &ExtractedView,
+            Has<NormalPrepass>,
+            Has<MotionVectorPrepass>,
+            Has<DeferredPrepass>,
+        ),
+        With<VolumetricFogSettings>,
+    >,
+) {
+    for (entity, view, normal_prepass, motion_vector_prepass, deferred_prepass) in &views {
+        let mut view_layout_key =
+            MeshPipelineViewLayoutKey::from(*msaa) | MeshPipelineViewLayoutKey::DEPTH_PREPASS;
+        if normal_prepass {
+            view_layout_key |= MeshPipelineViewLayoutKey::NORMAL_PREPASS;
+        }
+        if motion_vector_prepass {
+            view_layout_key |= MeshPipelineViewLayoutKey::MOTION_VECTOR_PREPASS;
+        }
+        if deferred_prepass {
+            view_layout_key |= MeshPipelineViewLayoutKey::DEFERRED_PREPASS;
+        }
+
+        let inject = pipelines.specialize(
+            &pipeline_cache,
+            &volumetric_fog_pipeline,
+            VolumetricFogPipelineKey::Inject(view_layout_key),
+        );
+        let apply = pipelines.specialize(
+            &pipeline_cache,
+            &volumetric_fog_pipeline,
+            VolumetricFogPipelineKey::Apply { hdr: view.hdr },
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewVolumetricFogPipelines { inject, apply });
+    }
+}
+
+/// Writes the [`VolumetricFogUniform`] of each view with volumetric fog, and
+/// records its view matrices for reprojection in the next frame.
+#[allow(clippy::too_many_arguments)]
+pub fn prepare_volumetric_fog_uniforms(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    frame_count: Res<FrameCount>,
+    extracted_fog_volumes: Res<ExtractedFogVolumes>,
+    mut uniform_buffer: ResMut<VolumetricFogUniformBuffer>,
+    mut history: ResMut<VolumetricFogHistory>,
+    views: Query<(Entity, &ExtractedView, &VolumetricFogSettings)>,
+) {
+    let views_iter = views.iter();
+    let Some(mut writer) =
+        uniform_buffer
+            .0
+            .get_writer(views_iter.len(), &render_device, &render_queue)
+    else {
+        history.0.clear();
+        return;
+    };
+
+    let mut volumes = [GpuFogVolume::default(); MAX_FOG_VOLUMES];
+    for (gpu_volume, volume) in volumes.iter_mut().zip(extracted_fog_volumes.0.iter()) {
+        *gpu_volume = *volume;
+    }
+
+    let mut new_history = EntityHashMap::default();
+    for (entity, view, settings) in views_iter {
+        let view_from_world = view.transform.compute_matrix().inverse();
+        let clip_from_world = view
+            .view_projection
+            .unwrap_or_else(|| view.projection * view_from_world);
+
+        // Without a previous frame, there's nothing to reproject.
+        let (previous_clip_from_world, previous_view_from_world, temporal_reprojection_weight) =
+            match history.0.get(&entity) {
+                Some(&(previous_clip_from_world, previous_view_from_world)) => (
+                    previous_clip_from_world,
+                    previous_view_from_world,
+                    settings.temporal_reprojection_weight.clamp(0.0, 1.0),
+                ),
+                None => (clip_from_world, view_from_world, 0.0),
+            };
+        new_history.insert(entity, (clip_from_world, view_from_world));
+
+        let ambient = Vec4::from(LinearRgba::from(settings.ambient_color)).truncate()
+            * settings.ambient_intensity;
+
+        let offset = writer.write(&VolumetricFogUniform {
+            previous_clip_from_world,
+            previous_view_from_world,
+            froxel_resolution: settings.froxel_resolution.max(UVec3::ONE),
+            volume_count: extracted_fog_volumes.0.len() as u32,
+            albedo: Vec4::from(LinearRgba::from(settings.albedo)).truncate(),
+            density: settings.density,
+            ambient,
+            scattering_asymmetry: settings.scattering_asymmetry.clamp(-0.99, 0.99),
+            max_distance: settings.max_distance,
+            temporal_reprojection_weight,
+            // The golden ratio gives a low-discrepancy sequence of offsets.
+            jitter: (frame_count.0 as f32 * 0.618_034).fract(),
+            volumes,
+        });
+        commands
+            .entity(entity)
+            .insert(ViewVolumetricFogUniformOffset(offset));
+    }
+
+    history.0 = new_history;
+}
+
+/// Allocates the froxel textures of each view with volumetric fog.
+pub fn prepare_volumetric_fog_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    frame_count: Res<FrameCount>,
+    views: Query<(Entity, &VolumetricFogSettings)>,
+) {
+    for (entity, settings) in &views {
+        let resolution = settings.froxel_resolution.max(UVec3::ONE);
+        let mut texture_descriptor = TextureDescriptor {
+            label: None,
+            size: Extent3d {
+                width: resolution.x,
+                height: resolution.y * resolution.z,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: FROXEL_TEXTURE_FORMAT,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        };
+
+        texture_descriptor.label = Some("volumetric_fog_froxels_1_texture");
+        let froxels_1 = texture_cache.get(&render_device, texture_descriptor.clone());
+
+        texture_descriptor.label = Some("volumetric_fog_froxels_2_texture");
+        let froxels_2 = texture_cache.get(&render_device, texture_descriptor.clone());
+
+        texture_descriptor.label = Some("volumetric_fog_integrated_texture");
+        texture_descriptor.usage = TextureUsages::TEXTURE_BINDING | TextureUsages::STORAGE_BINDING;
+        let integrated = texture_cache.get(&render_device, texture_descriptor);
+
+        let (froxels, history) = if frame_count.0 % 2 == 0 {
+            (froxels_1, froxels_2)
+        } else {
+            (froxels_2, froxels_1)
+        };
+
+        commands.entity(entity).insert(ViewVolumetricFogTextures {
+            froxels,
+            history,
+            integrated,
+        });
+    }
+}
+
+/// Creates the bind groups of each view with volumetric fog that don't depend
+/// on the main texture.
+pub fn prepare_volumetric_fog_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    volumetric_fog_pipeline: Res<VolumetricFogPipeline>,
+    view_uniforms: Res<ViewUniforms>,
+    uniform_buffer: Res<VolumetricFogUniformBuffer>,
+    views: Query<(Entity, &ViewVolumetricFogTextures)>,
+) {
+    let (Some(view_uniforms), Some(fog_uniforms)) =
+        (view_uniforms.uniforms.binding(), uniform_buffer.0.binding())
+    else {
+        return;
+    };
+
+    for (entity, textures) in &views {
+        let inject = render_device.create_bind_group(
+            "volumetric_fog_inject_bind_group",
+            &volumetric_fog_pipeline.inject_bind_group_layout,
+            &BindGroupEntries::sequential((
+                &textures.history.default_view,
+                &volumetric_fog_pipeline.linear_sampler,
+                fog_uniforms.clone(),
+            )),
+        );
+
+        let integrate = render_device.create_bind_group(
+            "volumetric_fog_integrate_bind_group",
+            &volumetric_fog_pipeline.integrate_bind_group_layout,
+            &BindGroupEntries::sequential((
+                &textures.froxels.default_view,
+                &textures.integrated.default_view,
+                view_uniforms.clone(),
+                fog_uniforms.clone(),
+            )),
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewVolumetricFogBindGroups { inject, integrate });
+    }
+}
+
+impl ViewNode for VolumetricFogNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static ViewPrepassTextures,
+        &'static MeshViewBindGroup,
+        (
+            &'static ViewUniformOffset,
+            &'static ViewLightsUniformOffset,
+            &'static ViewFogUniformOffset,
+            &'static ViewLightProbesUniformOffset,
+        ),
+        &'static ViewVolumetricFogUniformOffset,
+        &'static ViewVolumetricFogPipelines,
+        &'static ViewVolumetricFogTextures,
+        &'static ViewVolumetricFogBindGroups,
+        &'static VolumetricFogSettings,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (
+            view_target,
+            prepass_textures,
+            mesh_view_bind_group,
+            (view_uniform_offset, view_lights_offset, view_fog_offset, view_light_probes_offset),
+            volumetric_fog_uniform_offset,
+            pipeline_ids,
+            textures,
+            bind_groups,
+            settings,
+        ): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let volumetric_fog_pipeline = world.resource::<VolumetricFogPipeline>();
+        let view_uniforms = world.resource::<ViewUniforms>();
+        let uniform_buffer = world.resource::<VolumetricFogUniformBuffer>();
+
+        let (
+            Some(inject_pipeline),
+            Some(integrate_pipeline),
+            Some(apply_pipeline),
+            Some(depth_view),
+            Some(view_uniforms),
+            Some(fog_uniforms),
+        ) = (
+            pipeline_cache.get_render_pipeline(pipeline_ids.inject),
+            pipeline_cache.get_compute_pipeline(volumetric_fog_pipeline.integrate_pipeline),
+            pipeline_cache.get_render_pipeline(pipeline_ids.apply),
+            prepass_textures.depth_view(),
+            view_uniforms.uniforms.binding(),
+            uniform_buffer.0.binding(),
+        )
+        else {
+            return Ok(());
+        };
+
+        let resolution = settings.froxel_resolution.max(UVec3::ONE);
+
+        render_context
+            .command_encoder()
+            .push_debug_group("volumetric_fog");
+
+        // Compute the density and scattered light of every froxel.
+        {
+            let mut inject_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some("volumetric_fog_inject_pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &textures.froxels.default_view,
+                    resolve_target: None,
+                    ops: Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            inject_pass.set_render_pipeline(inject_pipeline);
+            inject_pass.set_bind_group(
+                0,
+                &mesh_view_bind_group.value,
+                &[
+                    view_uniform_offset.offset,
+                    view_lights_offset.offset,
+                    view_fog_offset.offset,
+                    **view_light_probes_offset,
+                ],
+            );
+            inject_pass.set_bind_group(1, &bind_groups.inject, &[**volumetric_fog_uniform_offset]);
+            inject_pass.draw(0..3, 0..1);
+        }
+
+        // Integrate the froxels front to back.
+        {
+            let mut integrate_pass =
+                render_context
+                    .command_encoder()
+                    .begin_compute_pass(&ComputePassDescriptor {
+                        label: Some("volumetric_fog_integrate_pass"),
+                        timestamp_writes: None,
+                    });
+            integrate_pass.set_pipeline(integrate_pipeline);
+            integrate_pass.set_bind_group(
+                0,
+                &bind_groups.integrate,
+                &[view_uniform_offset.offset, **volumetric_fog_uniform_offset],
+            );
+            integrate_pass.dispatch_workgroups(
+                resolution.x.div_ceil(8),
+                resolution.y.div_ceil(8),
+                1,
+            );
+        }
+
+        // Apply the fog to the rendered scene.
+        {
+            let post_process = view_target.post_process_write();
+
+            let apply_bind_group = render_context.render_device().create_bind_group(
+                "volumetric_fog_apply_bind_group",
+                &volumetric_fog_pipeline.apply_bind_group_layout,
+                &BindGroupEntries::sequential((
+                    post_process.source,
+                    depth_view,
+                    &textures.integrated.default_view,
+                    &volumetric_fog_pipeline.linear_sampler,
+                    view_uniforms,
+                    fog_uniforms,
+                )),
+            );
+
+            let mut apply_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some("volumetric_fog_apply_pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: post_process.destination,
+                    resolve_target: None,
+                    ops: Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            apply_pass.set_render_pipeline(apply_pipeline);
+            apply_pass.set_bind_group(
+                0,
+                &apply_bind_group,
+                &[view_uniform_offset.offset, **volumetric_fog_uniform_offset],
+            );
+            apply_pass.draw(0..3, 0..1);
+        }
+
+        render_context.command_encoder().pop_debug_group();
+
+        Ok(())
+    }
+}
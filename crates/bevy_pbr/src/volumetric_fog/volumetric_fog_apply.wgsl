@@ -0,0 +1,58 @@
+// Applies the integrated volumetric fog to the rendered scene.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_render::view::View
+#import bevy_pbr::volumetric_fog_types::{VolumetricFog, froxel_atlas_uv, view_depth_to_slice}
+
+@group(0) @binding(0) var color_texture: texture_2d<f32>;
+@group(0) @binding(1) var depth_texture: texture_depth_2d;
+@group(0) @binding(2) var integrated_froxels: texture_2d<f32>;
+@group(0) @binding(3) var froxel_sampler: sampler;
+@group(0) @binding(4) var<uniform> view: View;
+@group(0) @binding(5) var<uniform> volumetric_fog: VolumetricFog;
+
+fn sample_integrated_froxels(uv: vec2<f32>, slice: u32) -> vec4<f32> {
+    return textureSampleLevel(
+        integrated_froxels,
+        froxel_sampler,
+        froxel_atlas_uv(uv, slice, volumetric_fog.froxel_resolution),
+        0.0
+    );
+}
+
+@fragment
+fn apply(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let pixel_coordinates = vec2<i32>(floor(in.position.xy));
+    let color = textureLoad(color_texture, pixel_coordinates, 0);
+    let depth = textureLoad(depth_texture, pixel_coordinates, 0);
+
+    // Nothing was rendered at this pixel (reverse Z: 0.0 is the far plane), so
+    // it receives all the fog up to the end of the froxel grid.
+    var view_depth = volumetric_fog.max_distance;
+    if depth != 0.0 {
+        let ndc = vec2<f32>(in.uv.x * 2.0 - 1.0, 1.0 - 2.0 * in.uv.y);
+        let view_position = view.inverse_projection * vec4<f32>(ndc, depth, 1.0);
+        view_depth = -view_position.z / view_position.w;
+    }
+
+    // Integrated froxels hold the fog up to the far end of their slice, so
+    // interpolate between the two slices that end around this depth. The fog
+    // in front of the first slice's end fades in from no fog at the camera.
+    let resolution = volumetric_fog.froxel_resolution;
+    let slice = view_depth_to_slice(view_depth, volumetric_fog.max_distance) * f32(resolution.z) - 1.0;
+    var fog: vec4<f32>;
+    if slice < 0.0 {
+        fog = mix(vec4<f32>(0.0, 0.0, 0.0, 1.0), sample_integrated_froxels(in.uv, 0u), slice + 1.0);
+    } else {
+        let near_slice = min(u32(slice), resolution.z - 1u);
+        let far_slice = min(near_slice + 1u, resolution.z - 1u);
+        fog = mix(
+            sample_integrated_froxels(in.uv, near_slice),
+            sample_integrated_froxels(in.uv, far_slice),
+            fract(slice)
+        );
+    }
+
+    // The scene is pre-exposed, so the fog must be too.
+    return vec4<f32>(color.rgb * fog.a + fog.rgb * view.exposure, color.a);
+}
@@ -0,0 +1,177 @@
+// Computes the density and the light scattered towards the camera at the center
+// of every froxel, and blends the result with the previous frame.
+//
+// Each texel of the render target is a froxel: the depth slices of the froxel
+// grid are stacked vertically. The output is the scattering coefficient
+// multiplied by the incoming light in RGB, and the extinction coefficient in A.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_pbr::{
+    clustered_forward as clustering,
+    lighting::getDistanceAttenuation,
+    mesh_view_bindings as view_bindings,
+    mesh_view_types::{
+        DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT,
+        POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT,
+        POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE,
+    },
+    shadows,
+    utils::PI,
+    view_transformations::{position_ndc_to_world, uv_to_ndc, view_z_to_depth_ndc},
+    volumetric_fog_types::{
+        VolumetricFog, froxel_atlas_uv, slice_to_view_depth, view_depth_to_slice,
+    },
+}
+
+@group(1) @binding(0) var history_texture: texture_2d<f32>;
+@group(1) @binding(1) var history_sampler: sampler;
+@group(1) @binding(2) var<uniform> volumetric_fog: VolumetricFog;
+
+// The Henyey-Greenstein phase function.
+fn henyey_greenstein(cos_theta: f32, g: f32) -> f32 {
+    let g2 = g * g;
+    let denominator = 1.0 + g2 - 2.0 * g * cos_theta;
+    return (1.0 - g2) / (4.0 * PI * denominator * sqrt(denominator));
+}
+
+fn fog_density(world_position: vec3<f32>) -> f32 {
+    var density = volumetric_fog.density;
+    for (var i = 0u; i < volumetric_fog.volume_count; i += 1u) {
+        let volume = &volumetric_fog.volumes[i];
+        let local_position = ((*volume).local_from_world * vec4<f32>(world_position, 1.0)).xyz;
+        if all(abs(local_position) <= vec3<f32>(0.5)) {
+            density += (*volume).density;
+        }
+    }
+    return max(density, 0.0);
+}
+
+// Returns the spot cone attenuation of a spot light, or 1 for point lights.
+fn spot_attenuation(light_id: u32, light_to_froxel: vec3<f32>) -> f32 {
+    let light = &view_bindings::point_lights.data[light_id];
+
+    // reconstruct spot dir from x/z and y-direction flag
+    var spot_dir = vec3<f32>((*light).light_custom_data.x, 0.0, (*light).light_custom_data.y);
+    spot_dir.y = sqrt(max(0.0, 1.0 - spot_dir.x * spot_dir.x - spot_dir.z * spot_dir.z));
+    if ((*light).flags & POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE) != 0u {
+        spot_dir.y = -spot_dir.y;
+    }
+
+    let cd = dot(-spot_dir, normalize(light_to_froxel));
+    let attenuation = saturate(cd * (*light).light_custom_data.z + (*light).light_custom_data.w);
+    return attenuation * attenuation;
+}
+
+// Returns the light of a point or spot light scattered towards the camera.
+fn clustered_light(light_id: u32, world_position: vec3<f32>, V: vec3<f32>, is_spot: bool) -> vec3<f32> {
+    let light = &view_bindings::point_lights.data[light_id];
+    let light_to_froxel = (*light).position_radius.xyz - world_position;
+    let L = normalize(light_to_froxel);
+
+    var attenuation = getDistanceAttenuation(
+        dot(light_to_froxel, light_to_froxel),
+        (*light).color_inverse_square_range.w
+    );
+    if is_spot {
+        attenuation *= spot_attenuation(light_id, light_to_froxel);
+    }
+    if attenuation <= 0.0 {
+        return vec3<f32>(0.0);
+    }
+
+    var shadow = 1.0;
+    if ((*light).flags & POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u {
+        if is_spot {
+            shadow = shadows::fetch_spot_shadow(light_id, vec4<f32>(world_position, 1.0), L);
+        } else {
+            shadow = shadows::fetch_point_shadow(light_id, vec4<f32>(world_position, 1.0), L);
+        }
+    }
+
+    let phase = henyey_greenstein(dot(-L, V), volumetric_fog.scattering_asymmetry);
+    return (*light).color_inverse_square_range.rgb * attenuation * shadow * phase;
+}
+
+@fragment
+fn inject(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let resolution = volumetric_fog.froxel_resolution;
+    let atlas_coordinates = vec2<u32>(floor(in.position.xy));
+    let froxel = vec3<u32>(
+        atlas_coordinates.x,
+        atlas_coordinates.y % resolution.y,
+        atlas_coordinates.y / resolution.y
+    );
+
+    // Find the world position of the (jittered) sample point of the froxel.
+    let uv = (vec2<f32>(froxel.xy) + 0.5) / vec2<f32>(resolution.xy);
+    let view_depth = slice_to_view_depth(
+        (f32(froxel.z) + volumetric_fog.jitter) / f32(resolution.z),
+        volumetric_fog.max_distance
+    );
+    let view_z = -max(view_depth, 0.0001);
+    let world_position = position_ndc_to_world(vec3<f32>(uv_to_ndc(uv), view_z_to_depth_ndc(view_z)));
+
+    let density = fog_density(world_position);
+
+    var scattering = vec3<f32>(0.0);
+    if density > 0.0 {
+        let V = normalize(view_bindings::view.world_position - world_position);
+        var light = volumetric_fog.ambient;
+
+        // Directional lights
+        for (var i = 0u; i < view_bindings::lights.n_directional_lights; i += 1u) {
+            let directional_light = &view_bindings::lights.directional_lights[i];
+            if ((*directional_light).render_layers & view_bindings::view.render_layers) == 0u {
+                continue;
+            }
+
+            let L = (*directional_light).direction_to_light;
+            var shadow = 1.0;
+            if ((*directional_light).flags & DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u {
+                shadow = shadows::fetch_directional_shadow(i, vec4<f32>(world_position, 1.0), L, view_z);
+            }
+            let phase = henyey_greenstein(dot(-L, V), volumetric_fog.scattering_asymmetry);
+            light += (*directional_light).color.rgb * shadow * phase;
+        }
+
+        // Point and spot lights
+        let frag_coord = view_bindings::view.viewport.xy + uv * view_bindings::view.viewport.zw;
+        let is_orthographic = view_bindings::view.projection[3].w == 1.0;
+        let cluster_index = clustering::fragment_cluster_index(frag_coord, view_z, is_orthographic);
+        let offset_and_counts = clustering::unpack_offset_and_counts(cluster_index);
+        let point_light_end = offset_and_counts[0] + offset_and_counts[1];
+        let spot_light_end = point_light_end + offset_and_counts[2];
+        for (var i = offset_and_counts[0]; i < point_light_end; i += 1u) {
+            light += clustered_light(clustering::get_light_id(i), world_position, V, false);
+        }
+        for (var i = point_light_end; i < spot_light_end; i += 1u) {
+            light += clustered_light(clustering::get_light_id(i), world_position, V, true);
+        }
+
+        scattering = volumetric_fog.albedo * density * light;
+    }
+
+    var result = vec4<f32>(scattering, density);
+
+    // Blend with the froxel that was at the same world position in the
+    // previous frame.
+    if volumetric_fog.temporal_reprojection_weight > 0.0 {
+        let previous_clip = volumetric_fog.previous_clip_from_world * vec4<f32>(world_position, 1.0);
+        let previous_view_depth = -(volumetric_fog.previous_view_from_world * vec4<f32>(world_position, 1.0)).z;
+        if previous_clip.w > 0.0 && previous_view_depth > 0.0 {
+            let previous_uv = previous_clip.xy / previous_clip.w * vec2<f32>(0.5, -0.5) + 0.5;
+            let previous_slice = view_depth_to_slice(previous_view_depth, volumetric_fog.max_distance) * f32(resolution.z);
+            if all(previous_uv >= vec2<f32>(0.0)) && all(previous_uv <= vec2<f32>(1.0)) && previous_slice < f32(resolution.z) {
+                let history = textureSampleLevel(
+                    history_texture,
+                    history_sampler,
+                    froxel_atlas_uv(previous_uv, u32(previous_slice), resolution),
+                    0.0
+                );
+                result = mix(result, history, volumetric_fog.temporal_reprojection_weight);
+            }
+        }
+    }
+
+    return result;
+}
@@ -0,0 +1,61 @@
+// Integrates the froxels front to back.
+//
+// After this pass, every froxel holds the light scattered towards the camera
+// between the camera and the far end of the froxel in RGB, and the
+// transmittance over the same distance in A.
+
+#import bevy_render::view::View
+#import bevy_pbr::volumetric_fog_types::{VolumetricFog, slice_to_view_depth}
+
+@group(0) @binding(0) var froxels: texture_2d<f32>;
+@group(0) @binding(1) var integrated_froxels: texture_storage_2d<rgba16float, write>;
+@group(0) @binding(2) var<uniform> view: View;
+@group(0) @binding(3) var<uniform> volumetric_fog: VolumetricFog;
+
+@compute
+@workgroup_size(8, 8, 1)
+fn integrate(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let resolution = volumetric_fog.froxel_resolution;
+    if any(global_id.xy >= resolution.xy) {
+        return;
+    }
+
+    // Slices are bounded by planes of constant view depth, so the distance a
+    // ray travels through a slice is longer than the thickness of the slice
+    // away from the center of the screen.
+    var ray_length_scale = 1.0;
+    if view.projection[3].w != 1.0 {
+        let uv = (vec2<f32>(global_id.xy) + 0.5) / vec2<f32>(resolution.xy);
+        let ndc = vec2<f32>(uv.x * 2.0 - 1.0, 1.0 - 2.0 * uv.y);
+        let view_position = view.inverse_projection * vec4<f32>(ndc, 1.0, 1.0);
+        let ray_direction = view_position.xyz / view_position.w;
+        ray_length_scale = length(ray_direction) / abs(ray_direction.z);
+    }
+
+    var scattering = vec3<f32>(0.0);
+    var transmittance = 1.0;
+    var slice_start = 0.0;
+    for (var slice = 0u; slice < resolution.z; slice += 1u) {
+        let coordinates = vec2<i32>(vec2<u32>(global_id.x, slice * resolution.y + global_id.y));
+        let froxel = textureLoad(froxels, coordinates, 0);
+
+        let slice_end = slice_to_view_depth(f32(slice + 1u) / f32(resolution.z), volumetric_fog.max_distance);
+        let ray_length = (slice_end - slice_start) * ray_length_scale;
+        slice_start = slice_end;
+
+        // Energy-conserving integration of the scattered light over the
+        // slice, assuming constant density and lighting within it. See
+        // "Physically Based and Unified Volumetric Rendering in Frostbite",
+        // Hillaire 2015.
+        let extinction = froxel.a;
+        let slice_transmittance = exp(-extinction * ray_length);
+        var slice_scattering = froxel.rgb * ray_length;
+        if extinction > 0.0 {
+            slice_scattering = (froxel.rgb - froxel.rgb * slice_transmittance) / extinction;
+        }
+        scattering += transmittance * slice_scattering;
+        transmittance *= slice_transmittance;
+
+        textureStore(integrated_froxels, coordinates, vec4<f32>(scattering, transmittance));
+    }
+}
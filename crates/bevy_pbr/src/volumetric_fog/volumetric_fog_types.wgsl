@@ -0,0 +1,48 @@
+#define_import_path bevy_pbr::volumetric_fog_types
+
+// NOTE: Keep in sync with `MAX_FOG_VOLUMES` in bevy_pbr/src/volumetric_fog/mod.rs
+const MAX_FOG_VOLUMES: u32 = 16u;
+
+struct FogVolume {
+    // Transforms world space positions into the unit cube of the volume.
+    local_from_world: mat4x4<f32>,
+    density: f32,
+}
+
+struct VolumetricFog {
+    previous_clip_from_world: mat4x4<f32>,
+    previous_view_from_world: mat4x4<f32>,
+    froxel_resolution: vec3<u32>,
+    volume_count: u32,
+    albedo: vec3<f32>,
+    density: f32,
+    ambient: vec3<f32>,
+    scattering_asymmetry: f32,
+    max_distance: f32,
+    temporal_reprojection_weight: f32,
+    jitter: f32,
+    volumes: array<FogVolume, MAX_FOG_VOLUMES>,
+}
+
+// Froxel slices are distributed quadratically in depth, so that nearby slices,
+// which cover fewer pixels each, are thinner than distant ones.
+//
+// `t` goes from 0 at the camera to 1 at `max_distance`.
+fn slice_to_view_depth(t: f32, max_distance: f32) -> f32 {
+    return max_distance * t * t;
+}
+
+fn view_depth_to_slice(view_depth: f32, max_distance: f32) -> f32 {
+    return sqrt(saturate(view_depth / max_distance));
+}
+
+// Returns the UV of the froxel atlas at which to sample slice `slice` at the
+// screen space UV `uv`. The depth slices are stacked vertically in the atlas.
+//
+// The UV is clamped to the center of the edge texels so that bilinear
+// filtering doesn't bleed into the neighboring slices.
+fn froxel_atlas_uv(uv: vec2<f32>, slice: u32, froxel_resolution: vec3<u32>) -> vec2<f32> {
+    let half_texel = 0.5 / vec2<f32>(froxel_resolution.xy);
+    let clamped_uv = clamp(uv, half_texel, 1.0 - half_texel);
+    return vec2<f32>(clamped_uv.x, (f32(slice) + clamped_uv.y) / f32(froxel_resolution.z));
+}
@@ -1,24 +1,26 @@
 //! Batching functionality when GPU preprocessing is in use.
 
-use std::marker::PhantomData;
+use std::{marker::PhantomData, mem, ops::Range};
 
 use bevy_ecs::{
     entity::Entity,
     query::With,
-    system::{Query, Res, ResMut, Resource, StaticSystemParam},
+    system::{Local, Query, Res, ResMut, Resource, StaticSystemParam, SystemParamItem},
 };
 use bevy_encase_derive::ShaderType;
-use bevy_utils::EntityHashMap;
+use bevy_utils::{EntityHashMap, HashMap, Parallel};
 use bytemuck::{Pod, Zeroable};
 use smallvec::smallvec;
-use wgpu::{BindingResource, BufferUsages};
+use wgpu::{BindingResource, BufferUsages, Features};
 
 use crate::{
     render_phase::{
         BinnedPhaseItem, BinnedRenderPhase, BinnedRenderPhaseBatch, CachedRenderPipelinePhaseItem,
         SortedPhaseItem, SortedRenderPhase,
     },
-    render_resource::{BufferVec, GpuArrayBufferIndex, GpuArrayBufferable, UninitBufferVec},
+    render_resource::{
+        Buffer, BufferVec, GpuArrayBufferIndex, GpuArrayBufferable, UninitBufferVec,
+    },
     renderer::{RenderDevice, RenderQueue},
     view::ViewTarget,
 };
@@ -80,6 +82,140 @@ pub struct PreprocessWorkItem {
     pub output_index: u32,
 }
 
+/// The arguments of an indirect draw of a batch, as laid out in the indirect
+/// buffer.
+///
+/// The same layout is used for indexed and non-indexed draws. For indexed
+/// draws, this matches `DrawIndexedIndirectArgs`. For non-indexed draws, this
+/// starts with `DrawIndirectArgs`, so `base_vertex_or_first_instance` is the
+/// first instance, and `first_instance` is unused.
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[repr(C)]
+pub struct IndirectParameters {
+    /// The number of indices, or of vertices for non-indexed meshes.
+    pub vertex_or_index_count: u32,
+    /// The number of instances to draw.
+    pub instance_count: u32,
+    /// The first index, or the first vertex for non-indexed meshes.
+    pub first_vertex_or_first_index: u32,
+    /// The value added to each index, or the first instance for non-indexed
+    /// meshes.
+    pub base_vertex_or_first_instance: u32,
+    /// The first instance, for indexed meshes.
+    pub first_instance: u32,
+}
+
+impl IndirectParameters {
+    /// Creates the parameters drawing all `index_count` indices of a mesh for
+    /// each of the `instances`.
+    pub fn indexed(index_count: u32, instances: Range<u32>) -> Self {
+        Self {
+            vertex_or_index_count: index_count,
+            instance_count: instances.len() as u32,
+            first_vertex_or_first_index: 0,
+            base_vertex_or_first_instance: 0,
+            first_instance: instances.start,
+        }
+    }
+
+    /// Creates the parameters drawing all `vertex_count` vertices of a
+    /// non-indexed mesh for each of the `instances`.
+    pub fn non_indexed(vertex_count: u32, instances: Range<u32>) -> Self {
+        Self {
+            vertex_or_index_count: vertex_count,
+            instance_count: instances.len() as u32,
+            first_vertex_or_first_index: 0,
+            base_vertex_or_first_instance: instances.start,
+            first_instance: 0,
+        }
+    }
+}
+
+/// The indirect draw arguments of the batches of binned render phases that
+/// are batched with `GFBD`.
+///
+/// When this resource exists, batches of binned phase items are given
+/// [`IndirectParameters`] by [`GetFullBatchData::get_batch_indirect_parameters`],
+/// so that draw commands can issue indirect draws instead of direct ones. The
+/// parameters of the batches of a batch set are contiguous, so that the whole
+/// set is drawn with a single multi-draw call.
+///
+/// Each batch is looked up by the first instance of its range. This is unique
+/// among the batches of a frame, as every view and phase allocates its own
+/// instances in the [`BatchedInstanceBuffers`] of `GFBD`, which is why there's
+/// one buffer per `GFBD`.
+#[derive(Resource)]
+pub struct IndirectParametersBuffer<GFBD> {
+    buffer: BufferVec<IndirectParameters>,
+    /// The offset of the parameters of each batch, and the number of draws to
+    /// issue from there.
+    offsets: HashMap<u32, (u64, u32)>,
+    marker: PhantomData<fn() -> GFBD>,
+}
+
+impl<GFBD> IndirectParametersBuffer<GFBD> {
+    /// Creates an empty buffer.
+    pub fn new() -> Self {
+        Self {
+            buffer: BufferVec::new(BufferUsages::INDIRECT),
+            offsets: HashMap::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Adds the parameters of the batches of a batch set, along with the first
+    /// instance of each batch.
+    ///
+    /// The first batch draws the whole set, and the other batches draw
+    /// nothing.
+    pub fn push_batch_set(&mut self, batches: impl IntoIterator<Item = (u32, IndirectParameters)>) {
+        let mut first_batch = None;
+        let mut count = 0;
+        for (first_instance, parameters) in batches {
+            let index = self.buffer.push(parameters);
+            self.offsets.insert(
+                first_instance,
+                ((index * mem::size_of::<IndirectParameters>()) as u64, 0),
+            );
+            first_batch.get_or_insert(first_instance);
+            count += 1;
+        }
+
+        if let Some(first_batch) = first_batch {
+            // The batch was inserted above.
+            self.offsets.get_mut(&first_batch).unwrap().1 = count;
+        }
+    }
+
+    /// Returns the indirect buffer, the offset in it of the parameters of the
+    /// batch starting at `first_instance`, and the number of draws to issue
+    /// from there, if the batch has parameters.
+    ///
+    /// The number of draws is zero for the batches that are drawn along with
+    /// the first batch of their set.
+    pub fn get(&self, first_instance: u32) -> Option<(&Buffer, u64, u32)> {
+        let (offset, count) = *self.offsets.get(&first_instance)?;
+        Some((self.buffer.buffer()?, offset, count))
+    }
+
+    /// Returns `true` if no batch has indirect parameters.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Clears out the parameters in preparation for a new frame.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.offsets.clear();
+    }
+}
+
+impl<GFBD> Default for IndirectParametersBuffer<GFBD> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<BD, BDI> BatchedInstanceBuffers<BD, BDI>
 where
     BD: GpuArrayBufferable + Sync + Send + 'static,
@@ -144,9 +280,26 @@ pub fn delete_old_work_item_buffers<GFBD>(
         .retain(|entity, _| view_targets.contains(*entity));
 }
 
+/// The work items of a view, batched separately from the other views.
+///
+/// The output indices of the work items, and the instance indices of the
+/// batches of the view, start at zero. They're offset to their final position
+/// in the [`BatchedInstanceBuffers::data_buffer`] once all views are batched.
+type StagedWorkItems = Vec<(Entity, Vec<PreprocessWorkItem>)>;
+
+/// The work items of a view with a sorted render phase, along with the indices
+/// of the phase items that got a work item.
+///
+/// Only the batch ranges of those phase items are relative to the start of the
+/// view's instances.
+type StagedSortedWorkItems = Vec<(Entity, Vec<PreprocessWorkItem>, Vec<usize>)>;
+
 /// Batch the items in a sorted render phase, when GPU instance buffer building
 /// is in use. This means comparing metadata needed to draw each phase item and
 /// trying to combine the draws into a batch.
+///
+/// See [`par_batch_and_prepare_sorted_render_phase`] for a version that
+/// batches the views in parallel.
 pub fn batch_and_prepare_sorted_render_phase<I, GFBD>(
     gpu_batched_instance_buffers: ResMut<
         BatchedInstanceBuffers<GFBD::BufferData, GFBD::BufferInputData>,
@@ -159,41 +312,148 @@ pub fn batch_and_prepare_sorted_render_phase<I, GFBD>(
 {
     let system_param_item = param.into_inner();
 
+    let staged_work_items = views
+        .iter_mut()
+        .map(|(view, mut phase)| {
+            let (work_items, item_indices) =
+                batch_sorted_render_phase::<I, GFBD>(&mut phase, &system_param_item);
+            (view, work_items, item_indices)
+        })
+        .collect::<StagedSortedWorkItems>();
+
+    prepare_sorted_work_items::<I, GFBD>(
+        gpu_batched_instance_buffers.into_inner(),
+        &mut views,
+        staged_work_items,
+    );
+}
+
+/// Like [`batch_and_prepare_sorted_render_phase`], but batches the views in
+/// parallel.
+///
+/// This requires the items of the [`GetBatchData::Param`](super::GetBatchData::Param)
+/// of `GFBD` to be [`Sync`].
+pub fn par_batch_and_prepare_sorted_render_phase<I, GFBD>(
+    gpu_batched_instance_buffers: ResMut<
+        BatchedInstanceBuffers<GFBD::BufferData, GFBD::BufferInputData>,
+    >,
+    mut views: Query<(Entity, &mut SortedRenderPhase<I>)>,
+    param: StaticSystemParam<GFBD::Param>,
+    mut staged_work_items: Local<Parallel<StagedSortedWorkItems>>,
+) where
+    I: CachedRenderPipelinePhaseItem + SortedPhaseItem,
+    GFBD: GetFullBatchData,
+    for<'w, 's> SystemParamItem<'w, 's, GFBD::Param>: Sync,
+{
+    let system_param_item = param.into_inner();
+
+    views.par_iter_mut().for_each(|(view, mut phase)| {
+        let (work_items, item_indices) =
+            batch_sorted_render_phase::<I, GFBD>(&mut phase, &system_param_item);
+        staged_work_items.scope(|staged| staged.push((view, work_items, item_indices)));
+    });
+
+    prepare_sorted_work_items::<I, GFBD>(
+        gpu_batched_instance_buffers.into_inner(),
+        &mut views,
+        staged_work_items
+            .iter_mut()
+            .flat_map(|staged| staged.drain(..)),
+    );
+}
+
+/// Creates the batches of a single sorted render phase, and returns the work
+/// items of its instances along with the indices of the phase items that got
+/// one.
+///
+/// The instance indices start at zero, see [`StagedSortedWorkItems`].
+fn batch_sorted_render_phase<I, GFBD>(
+    phase: &mut SortedRenderPhase<I>,
+    system_param_item: &SystemParamItem<GFBD::Param>,
+) -> (Vec<PreprocessWorkItem>, Vec<usize>)
+where
+    I: CachedRenderPipelinePhaseItem + SortedPhaseItem,
+    GFBD: GetFullBatchData,
+{
+    let mut work_items = vec![];
+    let mut item_indices = vec![];
+    let mut item_index = 0;
+
+    super::batch_and_prepare_sorted_render_phase::<I, GFBD>(phase, system_param_item, |item| {
+        let index = item_index;
+        item_index += 1;
+
+        let (input_index, compare_data) =
+            GFBD::get_index_and_compare_data(system_param_item, item.entity())?;
+        let output_index = work_items.len() as u32;
+
+        work_items.push(PreprocessWorkItem {
+            input_index: input_index.into(),
+            output_index,
+        });
+
+        *item.batch_range_mut() = output_index..output_index + 1;
+        item_indices.push(index);
+
+        compare_data
+    });
+
+    (work_items, item_indices)
+}
+
+/// Moves the staged work items of each sorted render phase to their final
+/// position in the instance buffers.
+fn prepare_sorted_work_items<I, GFBD>(
+    gpu_batched_instance_buffers: &mut BatchedInstanceBuffers<
+        GFBD::BufferData,
+        GFBD::BufferInputData,
+    >,
+    views: &mut Query<(Entity, &mut SortedRenderPhase<I>)>,
+    staged_work_items: impl IntoIterator<Item = (Entity, Vec<PreprocessWorkItem>, Vec<usize>)>,
+) where
+    I: CachedRenderPipelinePhaseItem + SortedPhaseItem,
+    GFBD: GetFullBatchData,
+{
     // We only process GPU-built batch data in this function.
     let BatchedInstanceBuffers {
         ref mut data_buffer,
         ref mut work_item_buffers,
         ..
-    } = gpu_batched_instance_buffers.into_inner();
+    } = gpu_batched_instance_buffers;
+
+    for (view, work_items, item_indices) in staged_work_items {
+        let Ok((_, mut phase)) = views.get_mut(view) else {
+            continue;
+        };
+
+        // Items without a work item keep the batch range they were queued with.
+        let first_instance = data_buffer.add_multiple(work_items.len()) as u32;
+        for item_index in item_indices {
+            let batch_range = phase.items[item_index].batch_range_mut();
+            *batch_range = (batch_range.start + first_instance)..(batch_range.end + first_instance);
+        }
 
-    for (view, mut phase) in &mut views {
         // Create the work item buffer if necessary.
-        let work_item_buffer = work_item_buffers
+        work_item_buffers
             .entry(view)
-            .or_insert_with(|| BufferVec::new(BufferUsages::STORAGE));
-
-        super::batch_and_prepare_sorted_render_phase::<I, GFBD>(&mut phase, |item| {
-            let (input_index, compare_data) =
-                GFBD::get_index_and_compare_data(&system_param_item, item.entity())?;
-            let output_index = data_buffer.add() as u32;
-
-            work_item_buffer.push(PreprocessWorkItem {
-                input_index: input_index.into(),
-                output_index,
-            });
-
-            *item.batch_range_mut() = output_index..output_index + 1;
-
-            compare_data
-        });
+            .or_insert_with(|| BufferVec::new(BufferUsages::STORAGE))
+            .extend(work_items.into_iter().map(|work_item| PreprocessWorkItem {
+                input_index: work_item.input_index,
+                output_index: work_item.output_index + first_instance,
+            }));
     }
 }
 
 /// Creates batches for a render phase that uses bins.
+///
+/// If the [`IndirectParametersBuffer`] exists, this also records the indirect
+/// draw arguments of each batch. See [`par_batch_and_prepare_binned_render_phase`]
+/// for a version that batches the views in parallel.
 pub fn batch_and_prepare_binned_render_phase<BPI, GFBD>(
     gpu_batched_instance_buffers: ResMut<
         BatchedInstanceBuffers<GFBD::BufferData, GFBD::BufferInputData>,
     >,
+    mut indirect_parameters_buffer: Option<ResMut<IndirectParametersBuffer<GFBD>>>,
     mut views: Query<(Entity, &mut BinnedRenderPhase<BPI>)>,
     param: StaticSystemParam<GFBD::Param>,
 ) where
@@ -202,84 +462,201 @@ pub fn batch_and_prepare_binned_render_phase<BPI, GFBD>(
 {
     let system_param_item = param.into_inner();
 
+    let staged_work_items = views
+        .iter_mut()
+        .map(|(view, mut phase)| {
+            let work_items = batch_binned_render_phase::<BPI, GFBD>(&mut phase, &system_param_item);
+            (view, work_items)
+        })
+        .collect::<StagedWorkItems>();
+
+    prepare_binned_work_items::<BPI, GFBD>(
+        gpu_batched_instance_buffers.into_inner(),
+        indirect_parameters_buffer.as_deref_mut(),
+        &mut views,
+        &system_param_item,
+        staged_work_items,
+    );
+}
+
+/// Like [`batch_and_prepare_binned_render_phase`], but batches the views in
+/// parallel.
+///
+/// This requires the items of the [`GetBatchData::Param`](super::GetBatchData::Param)
+/// of `GFBD` to be [`Sync`].
+pub fn par_batch_and_prepare_binned_render_phase<BPI, GFBD>(
+    gpu_batched_instance_buffers: ResMut<
+        BatchedInstanceBuffers<GFBD::BufferData, GFBD::BufferInputData>,
+    >,
+    mut indirect_parameters_buffer: Option<ResMut<IndirectParametersBuffer<GFBD>>>,
+    mut views: Query<(Entity, &mut BinnedRenderPhase<BPI>)>,
+    param: StaticSystemParam<GFBD::Param>,
+    mut staged_work_items: Local<Parallel<StagedWorkItems>>,
+) where
+    BPI: BinnedPhaseItem,
+    GFBD: GetFullBatchData,
+    for<'w, 's> SystemParamItem<'w, 's, GFBD::Param>: Sync,
+{
+    let system_param_item = param.into_inner();
+
+    views.par_iter_mut().for_each(|(view, mut phase)| {
+        let work_items = batch_binned_render_phase::<BPI, GFBD>(&mut phase, &system_param_item);
+        staged_work_items.scope(|staged| staged.push((view, work_items)));
+    });
+
+    prepare_binned_work_items::<BPI, GFBD>(
+        gpu_batched_instance_buffers.into_inner(),
+        indirect_parameters_buffer.as_deref_mut(),
+        &mut views,
+        &system_param_item,
+        staged_work_items
+            .iter_mut()
+            .flat_map(|staged| staged.drain(..)),
+    );
+}
+
+/// Moves the staged work items of each binned render phase to their final
+/// position in the instance buffers, and records the indirect parameters of
+/// its batches.
+fn prepare_binned_work_items<BPI, GFBD>(
+    gpu_batched_instance_buffers: &mut BatchedInstanceBuffers<
+        GFBD::BufferData,
+        GFBD::BufferInputData,
+    >,
+    mut indirect_parameters_buffer: Option<&mut IndirectParametersBuffer<GFBD>>,
+    views: &mut Query<(Entity, &mut BinnedRenderPhase<BPI>)>,
+    system_param_item: &SystemParamItem<GFBD::Param>,
+    staged_work_items: impl IntoIterator<Item = (Entity, Vec<PreprocessWorkItem>)>,
+) where
+    BPI: BinnedPhaseItem,
+    GFBD: GetFullBatchData,
+{
     let BatchedInstanceBuffers {
         ref mut data_buffer,
         ref mut work_item_buffers,
         ..
-    } = gpu_batched_instance_buffers.into_inner();
+    } = gpu_batched_instance_buffers;
+
+    for (view, work_items) in staged_work_items {
+        let Ok((_, mut phase)) = views.get_mut(view) else {
+            continue;
+        };
 
-    for (view, mut phase) in &mut views {
-        let phase = &mut *phase; // Borrow checker.
+        let first_instance = data_buffer.add_multiple(work_items.len()) as u32;
+        phase.offset_instance_indices(first_instance);
 
         // Create the work item buffer if necessary; otherwise, just mark it as
         // used this frame.
-        let work_item_buffer = work_item_buffers
+        work_item_buffers
             .entry(view)
-            .or_insert_with(|| BufferVec::new(BufferUsages::STORAGE));
+            .or_insert_with(|| BufferVec::new(BufferUsages::STORAGE))
+            .extend(work_items.into_iter().map(|work_item| PreprocessWorkItem {
+                input_index: work_item.input_index,
+                output_index: work_item.output_index + first_instance,
+            }));
+
+        // Record the indirect parameters once the batches are in their final
+        // place.
+        if let Some(ref mut indirect_parameters_buffer) = indirect_parameters_buffer {
+            for batch_set in &phase.batch_sets {
+                indirect_parameters_buffer.push_batch_set(batch_set.iter().filter_map(|batch| {
+                    let parameters = GFBD::get_batch_indirect_parameters(
+                        system_param_item,
+                        batch.representative_entity,
+                        batch.instance_range.clone(),
+                    )?;
+                    Some((batch.instance_range.start, parameters))
+                }));
+            }
+        }
+    }
+}
 
-        // Prepare batchables.
+/// Creates the batches of a single binned render phase, and returns the work
+/// items of its instances.
+///
+/// The instance indices start at zero, see [`StagedWorkItems`].
+fn batch_binned_render_phase<BPI, GFBD>(
+    phase: &mut BinnedRenderPhase<BPI>,
+    system_param_item: &SystemParamItem<GFBD::Param>,
+) -> Vec<PreprocessWorkItem>
+where
+    BPI: BinnedPhaseItem,
+    GFBD: GetFullBatchData,
+{
+    let mut work_items = vec![];
 
-        for key in &phase.batchable_keys {
-            let mut batch: Option<BinnedRenderPhaseBatch> = None;
-            for &entity in &phase.batchable_values[key] {
-                let Some(input_index) = GFBD::get_binned_index(&system_param_item, entity) else {
-                    continue;
-                };
-                let output_index = data_buffer.add() as u32;
+    // Prepare batchables.
 
-                work_item_buffer.push(PreprocessWorkItem {
-                    input_index: input_index.into(),
-                    output_index,
-                });
+    // The index of the batch set that the current bin adds its batch to.
+    // Bins that are merged into the previous one extend its batch, as
+    // their instances directly follow it.
+    let mut batch_set_index = 0;
+    for (key_index, key) in phase.batchable_keys.iter().enumerate() {
+        if key_index == 0 || !BPI::can_merge_bins(&phase.batchable_keys[key_index - 1], key) {
+            batch_set_index = key_index;
+        }
+        phase.batch_sets.push(smallvec![]);
+        let batch_set = &mut phase.batch_sets[batch_set_index];
 
-                batch
-                    .get_or_insert(BinnedRenderPhaseBatch {
-                        representative_entity: entity,
-                        instance_range: output_index..output_index,
-                        dynamic_offset: None,
-                    })
-                    .instance_range
-                    .end = output_index + 1;
-            }
+        for &entity in &phase.batchable_values[key] {
+            let Some(input_index) = GFBD::get_binned_index(system_param_item, entity) else {
+                continue;
+            };
+            let output_index = work_items.len() as u32;
 
-            if let Some(batch) = batch {
-                phase.batch_sets.push(smallvec![batch]);
+            work_items.push(PreprocessWorkItem {
+                input_index: input_index.into(),
+                output_index,
+            });
+
+            match batch_set.last_mut() {
+                Some(batch) => batch.instance_range.end = output_index + 1,
+                None => batch_set.push(BinnedRenderPhaseBatch {
+                    representative_entity: entity,
+                    instance_range: output_index..output_index + 1,
+                    dynamic_offset: None,
+                }),
             }
         }
+    }
 
-        // Prepare unbatchables.
-        for key in &phase.unbatchable_keys {
-            let unbatchables = phase.unbatchable_values.get_mut(key).unwrap();
-            for &entity in &unbatchables.entities {
-                let Some(input_index) = GFBD::get_binned_index(&system_param_item, entity) else {
-                    continue;
-                };
-                let output_index = data_buffer.add() as u32;
-
-                work_item_buffer.push(PreprocessWorkItem {
-                    input_index: input_index.into(),
-                    output_index,
-                });
+    // Prepare unbatchables.
+    for key in &phase.unbatchable_keys {
+        let unbatchables = phase.unbatchable_values.get_mut(key).unwrap();
+        for &entity in &unbatchables.entities {
+            let Some(input_index) = GFBD::get_binned_index(system_param_item, entity) else {
+                continue;
+            };
+            let output_index = work_items.len() as u32;
 
-                unbatchables
-                    .buffer_indices
-                    .add(GpuArrayBufferIndex::<GFBD::BufferData> {
-                        index: output_index,
-                        dynamic_offset: None,
-                        element_type: PhantomData,
-                    });
-            }
+            work_items.push(PreprocessWorkItem {
+                input_index: input_index.into(),
+                output_index,
+            });
+
+            unbatchables
+                .buffer_indices
+                .add(GpuArrayBufferIndex::<GFBD::BufferData> {
+                    index: output_index,
+                    dynamic_offset: None,
+                    element_type: PhantomData,
+                });
         }
     }
+
+    work_items
 }
 
-/// A system that writes all instance buffers to the GPU.
+/// A system that writes all instance buffers, and the indirect parameters if
+/// any, to the GPU.
 pub fn write_batched_instance_buffers<GFBD>(
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
     mut gpu_batched_instance_buffers: ResMut<
         BatchedInstanceBuffers<GFBD::BufferData, GFBD::BufferInputData>,
     >,
+    indirect_parameters_buffer: Option<ResMut<IndirectParametersBuffer<GFBD>>>,
 ) where
     GFBD: GetFullBatchData,
 {
@@ -295,6 +672,12 @@ pub fn write_batched_instance_buffers<GFBD>(
     for work_item_buffer in gpu_batched_instance_buffers.work_item_buffers.values_mut() {
         work_item_buffer.write_buffer(&render_device, &render_queue);
     }
+
+    if let Some(mut indirect_parameters_buffer) = indirect_parameters_buffer {
+        indirect_parameters_buffer
+            .buffer
+            .write_buffer(&render_device, &render_queue);
+    }
 }
 
 /// Determines whether it's possible to run preprocessing on the GPU.
@@ -303,3 +686,157 @@ pub fn write_batched_instance_buffers<GFBD>(
 pub fn can_preprocess_on_gpu(render_device: &RenderDevice) -> bool {
     render_device.limits().max_compute_workgroup_size_x > 0
 }
+
+/// Determines whether batches can be drawn with [`IndirectParameters`].
+///
+/// Batches don't start at instance 0, and batch sets are drawn with a single
+/// call, so this requires the [`Features::INDIRECT_FIRST_INSTANCE`] and
+/// [`Features::MULTI_DRAW_INDIRECT`] features.
+pub fn can_draw_indirect(render_device: &RenderDevice) -> bool {
+    render_device
+        .features()
+        .contains(Features::INDIRECT_FIRST_INSTANCE | Features::MULTI_DRAW_INDIRECT)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use bevy_ecs::{entity::Entity, system::SystemParamItem};
+    use nonmax::NonMaxU32;
+
+    use super::batch_binned_render_phase;
+    use crate::{
+        batching::{GetBatchData, GetFullBatchData},
+        render_phase::{BinnedPhaseItem, BinnedRenderPhase, DrawFunctionId, PhaseItem},
+    };
+
+    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct TestBinKey {
+        mesh: u32,
+        // Not used when drawing, so bins that only differ by it can be merged.
+        material: u32,
+    }
+
+    struct TestPhaseItem {
+        key: TestBinKey,
+        representative_entity: Entity,
+        batch_range: Range<u32>,
+        dynamic_offset: Option<NonMaxU32>,
+    }
+
+    impl PhaseItem for TestPhaseItem {
+        fn entity(&self) -> Entity {
+            self.representative_entity
+        }
+
+        fn draw_function(&self) -> DrawFunctionId {
+            unreachable!("test phase items are only batched, not drawn")
+        }
+
+        fn batch_range(&self) -> &Range<u32> {
+            &self.batch_range
+        }
+
+        fn batch_range_mut(&mut self) -> &mut Range<u32> {
+            &mut self.batch_range
+        }
+
+        fn dynamic_offset(&self) -> Option<NonMaxU32> {
+            self.dynamic_offset
+        }
+
+        fn dynamic_offset_mut(&mut self) -> &mut Option<NonMaxU32> {
+            &mut self.dynamic_offset
+        }
+    }
+
+    impl BinnedPhaseItem for TestPhaseItem {
+        type BinKey = TestBinKey;
+
+        fn new(
+            key: Self::BinKey,
+            representative_entity: Entity,
+            batch_range: Range<u32>,
+            dynamic_offset: Option<NonMaxU32>,
+        ) -> Self {
+            TestPhaseItem {
+                key,
+                representative_entity,
+                batch_range,
+                dynamic_offset,
+            }
+        }
+
+        fn can_merge_bins(previous: &Self::BinKey, next: &Self::BinKey) -> bool {
+            previous.mesh == next.mesh
+        }
+    }
+
+    struct TestBatchData;
+
+    impl GetBatchData for TestBatchData {
+        type Param = ();
+        type CompareData = ();
+        type BufferData = u32;
+
+        fn get_batch_data(
+            _: &SystemParamItem<Self::Param>,
+            _: Entity,
+        ) -> Option<(Self::BufferData, Option<Self::CompareData>)> {
+            None
+        }
+    }
+
+    impl GetFullBatchData for TestBatchData {
+        type BufferInputData = u32;
+
+        fn get_binned_batch_data(
+            _: &SystemParamItem<Self::Param>,
+            _: Entity,
+        ) -> Option<Self::BufferData> {
+            None
+        }
+
+        fn get_index_and_compare_data(
+            _: &SystemParamItem<Self::Param>,
+            _: Entity,
+        ) -> Option<(NonMaxU32, Option<Self::CompareData>)> {
+            None
+        }
+
+        fn get_binned_index(_: &SystemParamItem<Self::Param>, entity: Entity) -> Option<NonMaxU32> {
+            NonMaxU32::new(entity.index())
+        }
+    }
+
+    #[test]
+    fn merged_bins() {
+        let mut phase = BinnedRenderPhase::<TestPhaseItem>::default();
+        let key = |mesh, material| TestBinKey { mesh, material };
+        phase.add(key(0, 0), Entity::from_raw(0), true);
+        phase.add(key(0, 0), Entity::from_raw(1), true);
+        phase.add(key(0, 1), Entity::from_raw(2), true);
+        phase.add(key(1, 0), Entity::from_raw(3), true);
+
+        let work_items = batch_binned_render_phase::<_, TestBatchData>(&mut phase, &());
+        assert_eq!(work_items.len(), 4);
+
+        // The second bin only differs from the first by its material, so its
+        // instance is drawn in the batch of the first bin.
+        assert_eq!(phase.batch_sets.len(), 3);
+        assert_eq!(phase.batch_sets[0].len(), 1);
+        assert_eq!(
+            phase.batch_sets[0][0].representative_entity,
+            Entity::from_raw(0)
+        );
+        assert_eq!(phase.batch_sets[0][0].instance_range, 0..3);
+        assert!(phase.batch_sets[1].is_empty());
+        assert_eq!(phase.batch_sets[2].len(), 1);
+        assert_eq!(
+            phase.batch_sets[2][0].representative_entity,
+            Entity::from_raw(3)
+        );
+        assert_eq!(phase.batch_sets[2][0].instance_range, 3..4);
+    }
+}
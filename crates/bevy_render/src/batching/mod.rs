@@ -1,10 +1,16 @@
 use bevy_ecs::{
     component::Component,
     entity::Entity,
-    system::{Query, ResMut, SystemParam, SystemParamItem},
+    system::{Local, Query, ResMut, SystemParam, SystemParamItem},
 };
+use bevy_utils::FixedState;
 use bytemuck::Pod;
 use nonmax::NonMaxU32;
+use std::{
+    hash::{BuildHasher, Hash},
+    mem,
+    ops::Range,
+};
 
 use crate::{
     render_phase::{
@@ -21,6 +27,27 @@ pub mod no_gpu_preprocessing;
 #[derive(Component)]
 pub struct NoAutomaticBatching;
 
+/// An extra key that has to be equal for two phase items to be batched
+/// together, returned by [`GetBatchData::get_batch_key`].
+///
+/// Small values such as a skin index or user flags can be stored directly,
+/// while any other hashable value can be turned into a key with
+/// [`BatchKey::from_hash`].
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct BatchKey(pub u64);
+
+impl BatchKey {
+    /// The key of phase items that don't need to split batches.
+    pub const NONE: BatchKey = BatchKey(0);
+
+    /// Creates a key from the hash of `value`.
+    ///
+    /// The hash is stable, so equal values always produce equal keys.
+    pub fn from_hash(value: &impl Hash) -> Self {
+        BatchKey(FixedState.hash_one(value))
+    }
+}
+
 /// Data necessary to be equal for two draw commands to be mergeable
 ///
 /// This is based on the following assumptions:
@@ -45,15 +72,18 @@ struct BatchMeta<T: PartialEq> {
     draw_function_id: DrawFunctionId,
     dynamic_offset: Option<NonMaxU32>,
     user_data: T,
+    /// The key returned by [`GetBatchData::get_batch_key`].
+    batch_key: BatchKey,
 }
 
 impl<T: PartialEq> BatchMeta<T> {
-    fn new(item: &impl CachedRenderPipelinePhaseItem, user_data: T) -> Self {
+    fn new(item: &impl CachedRenderPipelinePhaseItem, user_data: T, batch_key: BatchKey) -> Self {
         BatchMeta {
             pipeline_id: item.cached_pipeline(),
             draw_function_id: item.draw_function(),
             dynamic_offset: item.dynamic_offset(),
             user_data,
+            batch_key,
         }
     }
 }
@@ -88,13 +118,31 @@ pub trait GetBatchData {
         param: &SystemParamItem<Self::Param>,
         query_item: Entity,
     ) -> Option<(Self::BufferData, Option<Self::CompareData>)>;
+
+    /// Returns an extra key that has to be equal for two phase items in a
+    /// sorted render phase to be batched together, in addition to the
+    /// [`GetBatchData::CompareData`].
+    ///
+    /// This lets batches be split on per-instance state that the compare data
+    /// doesn't cover, such as a lightmap page, a skin index, user flags, or the
+    /// textures of a material that differ per instance. It's only called for
+    /// phase items that have compare data, in both the CPU and the GPU instance
+    /// buffer building paths. The default implementation returns
+    /// [`BatchKey::NONE`].
+    ///
+    /// Binned render phases don't use this key. Instead, phase items that can't
+    /// be batched together should be given different bin keys.
+    fn get_batch_key(param: &SystemParamItem<Self::Param>, query_item: Entity) -> BatchKey {
+        let _ = (param, query_item);
+        BatchKey::NONE
+    }
 }
 
 /// A trait to support getting data used for batching draw commands via phase
 /// items.
 ///
 /// This version allows for binning and GPU preprocessing.
-pub trait GetFullBatchData: GetBatchData {
+pub trait GetFullBatchData: GetBatchData + 'static {
     /// The per-instance data that was inserted into the
     /// [`crate::render_resource::BufferVec`] during extraction.
     type BufferInputData: Pod + Sync + Send;
@@ -133,6 +181,67 @@ pub trait GetFullBatchData: GetBatchData {
         param: &SystemParamItem<Self::Param>,
         query_item: Entity,
     ) -> Option<NonMaxU32>;
+
+    /// Returns the arguments of the indirect draw of a batch of binned phase
+    /// items, which draws the instances in `instance_range` of the mesh of
+    /// `representative_entity`.
+    ///
+    /// This is only called when the
+    /// [`gpu_preprocessing::IndirectParametersBuffer`] exists. The default
+    /// implementation returns `None`, so that the batch is drawn directly.
+    fn get_batch_indirect_parameters(
+        param: &SystemParamItem<Self::Param>,
+        representative_entity: Entity,
+        instance_range: Range<u32>,
+    ) -> Option<gpu_preprocessing::IndirectParameters> {
+        let _ = (param, representative_entity, instance_range);
+        None
+    }
+}
+
+/// Tracks the peak length of a buffer that's cleared every frame, in order to
+/// shrink the buffer once it holds much more memory than it needs.
+///
+/// Cleared buffers keep their memory, so that instance counts that fluctuate
+/// from frame to frame don't cause reallocations. The memory of a buffer is
+/// only released once every [`BufferHighWaterMark::SHRINK_INTERVAL`] frames,
+/// if the peak length over that period is less than half of the high-water
+/// mark that the buffer was last sized for.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BufferHighWaterMark {
+    /// The largest length since the buffer was last shrunk, which the memory
+    /// of the buffer is sized for.
+    high_water_mark: usize,
+    /// The largest length over the current period.
+    period_peak: usize,
+    /// The number of frames in the current period.
+    period_frames: u32,
+}
+
+impl BufferHighWaterMark {
+    /// The number of frames between two checks of whether a buffer should be
+    /// shrunk.
+    pub const SHRINK_INTERVAL: u32 = 600;
+
+    /// Records the length of the buffer at the end of a frame.
+    ///
+    /// Returns the capacity that the buffer should be shrunk to, if any.
+    pub fn update(&mut self, len: usize) -> Option<usize> {
+        self.high_water_mark = self.high_water_mark.max(len);
+        self.period_peak = self.period_peak.max(len);
+        self.period_frames += 1;
+        if self.period_frames < Self::SHRINK_INTERVAL {
+            return None;
+        }
+
+        self.period_frames = 0;
+        let period_peak = mem::take(&mut self.period_peak);
+        if period_peak >= self.high_water_mark / 2 {
+            return None;
+        }
+        self.high_water_mark = period_peak;
+        Some(period_peak)
+    }
 }
 
 /// A system that runs early in extraction and clears out all the
@@ -141,6 +250,9 @@ pub trait GetFullBatchData: GetBatchData {
 /// We have to run this during extraction because, if GPU preprocessing is in
 /// use, the extraction phase will write to the mesh input uniform buffers
 /// directly, so the buffers need to be cleared before then.
+///
+/// Buffers that have held much fewer instances than they have memory for over
+/// a while are shrunk; see [`BufferHighWaterMark`].
 pub fn clear_batched_instance_buffers<GFBD>(
     cpu_batched_instance_buffer: Option<
         ResMut<no_gpu_preprocessing::BatchedInstanceBuffer<GFBD::BufferData>>,
@@ -148,15 +260,45 @@ pub fn clear_batched_instance_buffers<GFBD>(
     gpu_batched_instance_buffers: Option<
         ResMut<gpu_preprocessing::BatchedInstanceBuffers<GFBD::BufferData, GFBD::BufferInputData>>,
     >,
+    indirect_parameters_buffer: Option<ResMut<gpu_preprocessing::IndirectParametersBuffer<GFBD>>>,
+    mut instance_high_water_mark: Local<BufferHighWaterMark>,
+    mut input_high_water_mark: Local<BufferHighWaterMark>,
 ) where
     GFBD: GetFullBatchData,
 {
     if let Some(mut cpu_batched_instance_buffer) = cpu_batched_instance_buffer {
+        if let Some(capacity) = instance_high_water_mark.update(cpu_batched_instance_buffer.len()) {
+            cpu_batched_instance_buffer.shrink_to(capacity);
+        }
         cpu_batched_instance_buffer.clear();
     }
     if let Some(mut gpu_batched_instance_buffers) = gpu_batched_instance_buffers {
+        let gpu_batched_instance_buffers = &mut *gpu_batched_instance_buffers;
+        if let Some(capacity) =
+            instance_high_water_mark.update(gpu_batched_instance_buffers.data_buffer.len())
+        {
+            gpu_batched_instance_buffers.data_buffer.shrink_to(capacity);
+            // Each view only holds part of the instances, so the work item
+            // buffers are shrunk to their own length.
+            for work_item_buffer in gpu_batched_instance_buffers.work_item_buffers.values_mut() {
+                work_item_buffer.shrink_to(work_item_buffer.len());
+            }
+        }
+        if let Some(capacity) =
+            input_high_water_mark.update(gpu_batched_instance_buffers.current_input_buffer.len())
+        {
+            gpu_batched_instance_buffers
+                .current_input_buffer
+                .shrink_to(capacity);
+            gpu_batched_instance_buffers
+                .previous_input_buffer
+                .shrink_to(capacity);
+        }
         gpu_batched_instance_buffers.clear();
     }
+    if let Some(mut indirect_parameters_buffer) = indirect_parameters_buffer {
+        indirect_parameters_buffer.clear();
+    }
 }
 
 /// Sorts a render phase that uses bins.
@@ -180,6 +322,7 @@ where
 /// [`no_gpu_preprocessing::batch_and_prepare_sorted_render_phase`].
 fn batch_and_prepare_sorted_render_phase<I, GBD>(
     phase: &mut SortedRenderPhase<I>,
+    param: &SystemParamItem<GBD::Param>,
     mut process_item: impl FnMut(&mut I) -> Option<GBD::CompareData>,
 ) where
     I: CachedRenderPipelinePhaseItem + SortedPhaseItem,
@@ -187,7 +330,10 @@ fn batch_and_prepare_sorted_render_phase<I, GBD>(
 {
     let items = phase.items.iter_mut().map(|item| {
         let batch_data = match process_item(item) {
-            Some(compare_data) if I::AUTOMATIC_BATCHING => Some(BatchMeta::new(item, compare_data)),
+            Some(compare_data) if I::AUTOMATIC_BATCHING => {
+                let batch_key = GBD::get_batch_key(param, item.entity());
+                Some(BatchMeta::new(item, compare_data, batch_key))
+            }
             _ => None,
         };
         (item.batch_range_mut(), batch_data)
@@ -202,3 +348,32 @@ fn batch_and_prepare_sorted_render_phase<I, GBD>(
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::BufferHighWaterMark;
+
+    #[test]
+    fn buffer_high_water_mark_shrinks_after_a_quiet_period() {
+        let mut high_water_mark = BufferHighWaterMark::default();
+
+        // A spike is kept for the whole period it happened in.
+        assert_eq!(high_water_mark.update(1000), None);
+        for _ in 1..BufferHighWaterMark::SHRINK_INTERVAL - 1 {
+            assert_eq!(high_water_mark.update(100), None);
+        }
+        assert_eq!(high_water_mark.update(100), None);
+
+        // A full period well below the spike shrinks to its peak.
+        for _ in 0..BufferHighWaterMark::SHRINK_INTERVAL - 1 {
+            assert_eq!(high_water_mark.update(100), None);
+        }
+        assert_eq!(high_water_mark.update(200), Some(200));
+
+        // Fluctuations above half of the high-water mark keep the memory.
+        for _ in 0..BufferHighWaterMark::SHRINK_INTERVAL - 1 {
+            assert_eq!(high_water_mark.update(150), None);
+        }
+        assert_eq!(high_water_mark.update(100), None);
+    }
+}
@@ -2,7 +2,7 @@
 
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::system::{Query, Res, ResMut, Resource, StaticSystemParam};
-use smallvec::{smallvec, SmallVec};
+use smallvec::smallvec;
 use wgpu::BindingResource;
 
 use crate::{
@@ -60,17 +60,21 @@ pub fn batch_and_prepare_sorted_render_phase<I, GBD>(
     let batched_instance_buffer = batched_instance_buffer.into_inner();
 
     for mut phase in &mut views {
-        super::batch_and_prepare_sorted_render_phase::<I, GBD>(&mut phase, |item| {
-            let (buffer_data, compare_data) =
-                GBD::get_batch_data(&system_param_item, item.entity())?;
-            let buffer_index = batched_instance_buffer.push(buffer_data);
-
-            let index = buffer_index.index;
-            *item.batch_range_mut() = index..index + 1;
-            *item.dynamic_offset_mut() = buffer_index.dynamic_offset;
-
-            compare_data
-        });
+        super::batch_and_prepare_sorted_render_phase::<I, GBD>(
+            &mut phase,
+            &system_param_item,
+            |item| {
+                let (buffer_data, compare_data) =
+                    GBD::get_batch_data(&system_param_item, item.entity())?;
+                let buffer_index = batched_instance_buffer.push(buffer_data);
+
+                let index = buffer_index.index;
+                *item.batch_range_mut() = index..index + 1;
+                *item.dynamic_offset_mut() = buffer_index.dynamic_offset;
+
+                compare_data
+            },
+        );
     }
 }
 
@@ -91,8 +95,16 @@ pub fn batch_and_prepare_binned_render_phase<BPI, GFBD>(
 
         // Prepare batchables.
 
-        for key in &phase.batchable_keys {
-            let mut batch_set: SmallVec<[BinnedRenderPhaseBatch; 1]> = smallvec![];
+        // The index of the batch set that the current bin adds its batches to.
+        // Bins that are merged into the previous one add to its batch set.
+        let mut batch_set_index = 0;
+        for (key_index, key) in phase.batchable_keys.iter().enumerate() {
+            if key_index == 0 || !BPI::can_merge_bins(&phase.batchable_keys[key_index - 1], key) {
+                batch_set_index = key_index;
+            }
+            phase.batch_sets.push(smallvec![]);
+            let batch_set = &mut phase.batch_sets[batch_set_index];
+
             for &entity in &phase.batchable_values[key] {
                 let Some(buffer_data) = GFBD::get_binned_batch_data(&system_param_item, entity)
                 else {
@@ -120,8 +132,6 @@ pub fn batch_and_prepare_binned_render_phase<BPI, GFBD>(
                     batch.instance_range.end = instance.index + 1;
                 }
             }
-
-            phase.batch_sets.push(batch_set);
         }
 
         // Prepare unbatchables.
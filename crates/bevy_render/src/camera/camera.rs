@@ -6,7 +6,10 @@ use crate::{
     render_graph::{InternedRenderSubGraph, RenderSubGraph},
     render_resource::TextureView,
     texture::GpuImage,
-    view::{ColorGrading, ExtractedView, ExtractedWindows, RenderLayers, VisibleEntities},
+    view::{
+        ColorGrading, ExtractedView, ExtractedWindows, RenderLayers, VisibleEntities,
+        VisibleMeshLods,
+    },
     Extract,
 };
 use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
@@ -31,7 +34,7 @@ use bevy_window::{
     WindowScaleFactorChanged,
 };
 use std::ops::Range;
-use wgpu::{BlendState, LoadOp, TextureFormat, TextureUsages};
+use wgpu::{BlendState, Extent3d, LoadOp, TextureFormat, TextureUsages};
 
 use super::{ClearColorConfig, Projection};
 
@@ -136,6 +139,12 @@ impl Exposure {
     }
 }
 
+impl From<PhysicalCameraParameters> for Exposure {
+    fn from(physical_camera_parameters: PhysicalCameraParameters) -> Self {
+        Self::from_physical_camera(physical_camera_parameters)
+    }
+}
+
 impl Default for Exposure {
     fn default() -> Self {
         Self::BLENDER
@@ -144,7 +153,8 @@ impl Default for Exposure {
 
 /// Parameters based on physical camera characteristics for calculating
 /// EV100 values for use with [`Exposure`].
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, Reflect)]
+#[reflect(Default)]
 pub struct PhysicalCameraParameters {
     /// <https://en.wikipedia.org/wiki/F-number>
     pub aperture_f_stops: f32,
@@ -189,6 +199,10 @@ pub struct Camera {
     /// If set, this camera will render to the given [`Viewport`] rectangle within the configured [`RenderTarget`].
     pub viewport: Option<Viewport>,
     /// Cameras with a higher order are rendered later, and thus on top of lower order cameras.
+    ///
+    /// Among cameras with the same order, those that render to a [`RenderTarget::Image`] are
+    /// rendered first, so that other cameras sampling that image in the same frame see its
+    /// up-to-date contents.
     pub order: isize,
     /// If this is set to `true`, this camera will be rendered to its specified [`RenderTarget`]. If `false`, this
     /// camera will not be rendered.
@@ -534,6 +548,78 @@ impl From<Handle<Image>> for RenderTarget {
     }
 }
 
+/// Keeps the [`Image`] that a camera renders to sized relative to a window.
+///
+/// Add this component to a camera whose [`Camera::target`] is a [`RenderTarget::Image`]. The
+/// image is resized whenever the window's physical size times the `scale` changes, which is
+/// useful for picture-in-picture views, portals and other effects that should keep a constant
+/// density of pixels on screen. It has no effect on cameras that render to other targets.
+///
+/// The image is resized with [`Image::resize`], so its contents are cleared. Create it with
+/// [`Image::new_render_target`] to get the right texture usages.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct ImageRenderTargetScale {
+    /// The window whose size the image follows.
+    pub window: WindowRef,
+    /// The size of the image relative to the window's physical size.
+    pub scale: f32,
+}
+
+impl Default for ImageRenderTargetScale {
+    fn default() -> Self {
+        Self {
+            window: WindowRef::Primary,
+            scale: 1.0,
+        }
+    }
+}
+
+/// Resizes the images that cameras with an [`ImageRenderTargetScale`] render to.
+///
+/// This system runs in [`PostUpdate`](bevy_app::PostUpdate), before [`CameraUpdateSystem`].
+///
+/// [`CameraUpdateSystem`]: crate::camera::CameraUpdateSystem
+pub fn resize_image_render_targets(
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &ImageRenderTargetScale)>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let primary_window = primary_window.iter().next();
+    for (camera, image_render_target_scale) in &cameras {
+        let RenderTarget::Image(image_handle) = &camera.target else {
+            continue;
+        };
+        let Some(window) = image_render_target_scale
+            .window
+            .normalize(primary_window)
+            .and_then(|window_ref| windows.get(window_ref.entity()).ok())
+        else {
+            continue;
+        };
+
+        let size = (window.physical_size().as_vec2() * image_render_target_scale.scale)
+            .round()
+            .as_uvec2()
+            .max(UVec2::ONE);
+        // Only take the image mutably when it actually changes, as that triggers an
+        // `AssetEvent::Modified` which reallocates the texture.
+        if images
+            .get(image_handle)
+            .is_some_and(|image| image.size() != size)
+        {
+            if let Some(image) = images.get_mut(image_handle) {
+                image.resize(Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                });
+            }
+        }
+    }
+}
+
 /// Normalized version of the render target.
 ///
 /// Once we have this we shouldn't need to resolve it down anymore.
@@ -827,6 +913,7 @@ pub fn extract_cameras(
             Option<&TemporalJitter>,
             Option<&RenderLayers>,
             Option<&Projection>,
+            Option<&VisibleMeshLods>,
         )>,
     >,
     primary_window: Extract<Query<Entity, With<PrimaryWindow>>>,
@@ -844,6 +931,7 @@ pub fn extract_cameras(
         temporal_jitter,
         render_layers,
         projection,
+        visible_mesh_lods,
     ) in query.iter()
     {
         let color_grading = *color_grading.unwrap_or(&ColorGrading::default());
@@ -915,6 +1003,10 @@ pub fn extract_cameras(
             if let Some(perspective) = projection {
                 commands.insert(perspective.clone());
             }
+
+            if let Some(visible_mesh_lods) = visible_mesh_lods {
+                commands.insert(visible_mesh_lods.clone());
+            }
         }
     }
 }
@@ -941,13 +1033,16 @@ pub fn sort_cameras(
             target: camera.target.clone(),
         });
     }
-    // sort by order and ensure within an order, RenderTargets of the same type are packed together
-    sorted_cameras
-        .0
-        .sort_by(|c1, c2| match c1.order.cmp(&c2.order) {
-            std::cmp::Ordering::Equal => c1.target.cmp(&c2.target),
-            ord => ord,
-        });
+    // sort by order and ensure within an order, RenderTargets of the same type are packed together,
+    // with images first so that they're up to date when other cameras of that order sample them
+    let renders_to_image =
+        |camera: &SortedCamera| matches!(camera.target, Some(NormalizedRenderTarget::Image(_)));
+    sorted_cameras.0.sort_by(|c1, c2| {
+        c1.order
+            .cmp(&c2.order)
+            .then_with(|| renders_to_image(c2).cmp(&renders_to_image(c1)))
+            .then_with(|| c1.target.cmp(&c2.target))
+    });
     let mut previous_order_target = None;
     let mut ambiguities = HashSet::new();
     let mut target_counts = HashMap::new();
@@ -0,0 +1,434 @@
+//! Dispatching compute shaders from ECS systems.
+//!
+//! Implement [`ComputeShader`] on a type deriving [`AsBindGroup`], add a
+//! [`ComputeShaderPlugin`] for it, then push dispatches to its [`ComputeDispatches`]
+//! resource from any system. Each dispatch is run once, before the cameras are
+//! rendered, and can read a buffer back to the CPU when it's done.
+//!
+//! ```no_run
+//! # use bevy_app::{App, Update};
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_math::UVec3;
+//! # use bevy_utils::tracing::info;
+//! # use bevy_render::{
+//! #     compute::{ComputeDispatches, ComputeShader, ComputeShaderPlugin},
+//! #     render_resource::{AsBindGroup, Buffer, ShaderRef},
+//! # };
+//! #[derive(AsBindGroup, Clone)]
+//! struct Simulation {
+//!     // The buffer must have the `STORAGE` usage, and `COPY_SRC` to be read back.
+//!     #[storage(0, buffer, visibility(compute))]
+//!     particles: Buffer,
+//! }
+//!
+//! impl ComputeShader for Simulation {
+//!     fn shader() -> ShaderRef {
+//!         "shaders/simulation.wgsl".into()
+//!     }
+//! }
+//!
+//! fn simulate(mut dispatches: ResMut<ComputeDispatches<Simulation>>, simulation: Res<SimulationBuffers>) {
+//!     dispatches.dispatch_with_readback(
+//!         Simulation { particles: simulation.0.clone() },
+//!         UVec3::new(64, 1, 1),
+//!         simulation.0.clone(),
+//!         |bytes| info!("read back {} bytes", bytes.len()),
+//!     );
+//! }
+//! # #[derive(Resource)]
+//! # struct SimulationBuffers(Buffer);
+//!
+//! App::new()
+//!     .add_plugins(ComputeShaderPlugin::<Simulation>::default())
+//!     .add_systems(Update, simulate);
+//! ```
+
+use std::{any::TypeId, borrow::Cow, marker::PhantomData};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::AssetServer;
+use bevy_ecs::prelude::*;
+use bevy_math::UVec3;
+use bevy_tasks::AsyncComputeTaskPool;
+use bevy_utils::tracing::error;
+use wgpu::{BufferDescriptor, BufferUsages, ComputePassDescriptor, MapMode};
+
+use crate::{
+    graph::CameraDriverLabel,
+    render_asset::RenderAssets,
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext, RenderLabel},
+    render_resource::{
+        AsBindGroup, AsBindGroupError, BindGroup, BindGroupLayout, Buffer, CachedComputePipelineId,
+        CachedPipelineState, ComputePipelineDescriptor, PipelineCache, ShaderDefVal, ShaderRef,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::{FallbackImage, GpuImage},
+    ExtractSchedule, MainWorld, Render, RenderApp, RenderSet,
+};
+
+/// A compute shader, with its bindings described by [`AsBindGroup`].
+///
+/// The value passed to a dispatch is turned into the bind group of group 0 of the
+/// shader. See the [module docs](self) for an example.
+pub trait ComputeShader: AsBindGroup + Send + Sync + 'static {
+    /// Returns the shader to dispatch.
+    fn shader() -> ShaderRef;
+
+    /// Returns the name of the entry point of the shader. Defaults to `main`.
+    fn entry_point() -> Cow<'static, str> {
+        Cow::Borrowed("main")
+    }
+
+    /// Returns the shader defs the shader is compiled with.
+    fn shader_defs() -> Vec<ShaderDefVal> {
+        Vec::new()
+    }
+}
+
+/// A function called with the bytes of a buffer read back after a dispatch.
+pub type ReadbackFn = Box<dyn FnOnce(Vec<u8>) + Send + Sync>;
+
+/// A dispatch of a [`ComputeShader`], queued in [`ComputeDispatches`].
+pub struct ComputeDispatch<S: ComputeShader> {
+    /// The bindings of the shader.
+    pub shader: S,
+    /// The number of workgroups to dispatch in each dimension.
+    pub workgroups: UVec3,
+    /// The buffer to read back once the dispatch is done, and the function called with
+    /// its content.
+    ///
+    /// The buffer must have the [`BufferUsages::COPY_SRC`] usage.
+    pub readback: Option<(Buffer, ReadbackFn)>,
+}
+
+/// The dispatches of a [`ComputeShader`] to run in the next frame.
+///
+/// Dispatches are run in the order they were added, before the cameras are rendered.
+/// Those pushed while the shader is still compiling are kept until it is ready, and
+/// dropped if it fails to compile.
+#[derive(Resource)]
+pub struct ComputeDispatches<S: ComputeShader> {
+    dispatches: Vec<ComputeDispatch<S>>,
+}
+
+impl<S: ComputeShader> Default for ComputeDispatches<S> {
+    fn default() -> Self {
+        Self {
+            dispatches: Vec::new(),
+        }
+    }
+}
+
+impl<S: ComputeShader> ComputeDispatches<S> {
+    /// Queues a dispatch of `workgroups` workgroups of the shader with the given bindings.
+    pub fn dispatch(&mut self, shader: S, workgroups: UVec3) {
+        self.push(ComputeDispatch {
+            shader,
+            workgroups,
+            readback: None,
+        });
+    }
+
+    /// Queues a dispatch of `workgroups` workgroups of the shader with the given bindings,
+    /// then reads `buffer` back to the CPU.
+    ///
+    /// The callback will eventually be called on one of the [`AsyncComputeTaskPool`]s threads,
+    /// with the content of the buffer.
+    pub fn dispatch_with_readback(
+        &mut self,
+        shader: S,
+        workgroups: UVec3,
+        buffer: Buffer,
+        callback: impl FnOnce(Vec<u8>) + Send + Sync + 'static,
+    ) {
+        self.push(ComputeDispatch {
+            shader,
+            workgroups,
+            readback: Some((buffer, Box::new(callback))),
+        });
+    }
+
+    /// Queues a dispatch.
+    pub fn push(&mut self, dispatch: ComputeDispatch<S>) {
+        self.dispatches.push(dispatch);
+    }
+
+    /// Returns the number of queued dispatches.
+    pub fn len(&self) -> usize {
+        self.dispatches.len()
+    }
+
+    /// Returns `true` if no dispatch is queued.
+    pub fn is_empty(&self) -> bool {
+        self.dispatches.is_empty()
+    }
+}
+
+/// Adds the [`ComputeDispatches`] of a [`ComputeShader`], and runs them in the render world.
+pub struct ComputeShaderPlugin<S: ComputeShader>(PhantomData<S>);
+
+impl<S: ComputeShader> Default for ComputeShaderPlugin<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: ComputeShader> Plugin for ComputeShaderPlugin<S> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ComputeDispatches<S>>();
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<ComputeDispatches<S>>()
+            .init_resource::<PreparedComputeDispatches<S>>()
+            .add_systems(ExtractSchedule, extract_compute_dispatches::<S>)
+            .add_systems(
+                Render,
+                (
+                    prepare_compute_dispatches::<S>.in_set(RenderSet::PrepareBindGroups),
+                    read_back_compute_dispatches::<S>.in_set(RenderSet::Cleanup),
+                ),
+            );
+
+        let mut render_graph = render_app.world_mut().resource_mut::<RenderGraph>();
+        let label = ComputeShaderLabel(TypeId::of::<S>());
+        render_graph.add_node(label.clone(), ComputeShaderNode::<S>(PhantomData));
+        render_graph.add_node_edge(label, CameraDriverLabel);
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<ComputeShaderPipeline<S>>();
+    }
+}
+
+/// The label of the render graph node running the dispatches of a [`ComputeShader`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct ComputeShaderLabel(pub TypeId);
+
+/// The pipeline of a [`ComputeShader`].
+#[derive(Resource)]
+pub struct ComputeShaderPipeline<S: ComputeShader> {
+    /// The layout of the bind group of the shader.
+    pub layout: BindGroupLayout,
+    /// The id of the pipeline in the [`PipelineCache`].
+    pub pipeline_id: CachedComputePipelineId,
+    marker: PhantomData<S>,
+}
+
+impl<S: ComputeShader> FromWorld for ComputeShaderPipeline<S> {
+    fn from_world(world: &mut World) -> Self {
+        let layout = S::bind_group_layout(world.resource::<RenderDevice>());
+        let shader = match S::shader() {
+            ShaderRef::Handle(handle) => handle,
+            ShaderRef::Path(path) => world.resource::<AssetServer>().load(path),
+            ShaderRef::Default => panic!(
+                "{} has no default shader; `ComputeShader::shader` must return a handle or a path",
+                std::any::type_name::<S>()
+            ),
+        };
+        let pipeline_id = world
+            .resource_mut::<PipelineCache>()
+            .queue_compute_pipeline(ComputePipelineDescriptor {
+                label: S::label().map(Cow::Borrowed),
+                layout: vec![layout.clone()],
+                push_constant_ranges: Vec::new(),
+                shader,
+                shader_defs: S::shader_defs(),
+                entry_point: S::entry_point(),
+            });
+
+        Self {
+            layout,
+            pipeline_id,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// A dispatch whose bind group has been created, ready to be run.
+struct PreparedComputeDispatch {
+    bind_group: BindGroup,
+    workgroups: UVec3,
+    /// The buffer to read back, the staging buffer it's copied to, and the callback.
+    readback: Option<(Buffer, Buffer, ReadbackFn)>,
+}
+
+#[derive(Resource)]
+struct PreparedComputeDispatches<S: ComputeShader> {
+    dispatches: Vec<PreparedComputeDispatch>,
+    /// Whether the failure to create the pipeline has been reported.
+    pipeline_error_reported: bool,
+    marker: PhantomData<S>,
+}
+
+impl<S: ComputeShader> Default for PreparedComputeDispatches<S> {
+    fn default() -> Self {
+        Self {
+            dispatches: Vec::new(),
+            pipeline_error_reported: false,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Moves the dispatches queued in the main world to the render world.
+fn extract_compute_dispatches<S: ComputeShader>(
+    mut main_world: ResMut<MainWorld>,
+    mut dispatches: ResMut<ComputeDispatches<S>>,
+) {
+    let Some(mut main_world_dispatches) = main_world.get_resource_mut::<ComputeDispatches<S>>()
+    else {
+        return;
+    };
+    dispatches
+        .dispatches
+        .append(&mut main_world_dispatches.dispatches);
+}
+
+/// Creates the bind groups of the dispatches, once the pipeline is ready, or drops
+/// them if it failed to be created.
+fn prepare_compute_dispatches<S: ComputeShader>(
+    mut dispatches: ResMut<ComputeDispatches<S>>,
+    mut prepared: ResMut<PreparedComputeDispatches<S>>,
+    pipeline: Res<ComputeShaderPipeline<S>>,
+    pipeline_cache: Res<PipelineCache>,
+    render_device: Res<RenderDevice>,
+    images: Res<RenderAssets<GpuImage>>,
+    fallback_image: Res<FallbackImage>,
+) {
+    match pipeline_cache.get_compute_pipeline_state(pipeline.pipeline_id) {
+        CachedPipelineState::Ok(_) => prepared.pipeline_error_reported = false,
+        CachedPipelineState::Queued | CachedPipelineState::Creating(_) => return,
+        CachedPipelineState::Err(err) => {
+            if !prepared.pipeline_error_reported {
+                error!(
+                    "Dropping the dispatches of {}, as its pipeline failed to be created: {err}",
+                    std::any::type_name::<S>()
+                );
+                prepared.pipeline_error_reported = true;
+            }
+            dispatches.dispatches.clear();
+            return;
+        }
+    }
+
+    let mut retry = Vec::new();
+    for dispatch in dispatches.dispatches.drain(..) {
+        let bind_group = match dispatch.shader.as_bind_group(
+            &pipeline.layout,
+            &render_device,
+            &images,
+            &fallback_image,
+        ) {
+            Ok(prepared) => prepared.bind_group,
+            Err(AsBindGroupError::RetryNextUpdate) => {
+                retry.push(dispatch);
+                continue;
+            }
+        };
+
+        let readback = dispatch.readback.map(|(buffer, callback)| {
+            let staging_buffer = render_device.create_buffer(&BufferDescriptor {
+                label: Some("compute_readback_buffer"),
+                size: buffer.size(),
+                usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            (buffer, staging_buffer, callback)
+        });
+
+        prepared.dispatches.push(PreparedComputeDispatch {
+            bind_group,
+            workgroups: dispatch.workgroups,
+            readback,
+        });
+    }
+    dispatches.dispatches = retry;
+}
+
+/// Maps the staging buffers of the dispatches that read a buffer back, once the
+/// commands of the frame have been submitted, and calls their callbacks.
+fn read_back_compute_dispatches<S: ComputeShader>(
+    mut prepared: ResMut<PreparedComputeDispatches<S>>,
+) {
+    for dispatch in prepared.dispatches.drain(..) {
+        let Some((_, staging_buffer, callback)) = dispatch.readback else {
+            continue;
+        };
+
+        let finish = async move {
+            let (tx, rx) = async_channel::bounded(1);
+            let buffer_slice = staging_buffer.slice(..);
+            // The polling for this map call is done every frame when the command queue is submitted.
+            buffer_slice.map_async(MapMode::Read, move |result| {
+                if let Err(err) = result {
+                    panic!("{}", err.to_string());
+                }
+                tx.try_send(()).unwrap();
+            });
+            rx.recv().await.unwrap();
+            let data = buffer_slice.get_mapped_range();
+            // Move the data to CPU memory right away, to avoid holding the mapped view for long.
+            let result = Vec::from(&*data);
+            drop(data);
+            drop(staging_buffer);
+            callback(result);
+        };
+
+        AsyncComputeTaskPool::get().spawn(finish).detach();
+    }
+}
+
+/// Runs the prepared dispatches of a [`ComputeShader`].
+struct ComputeShaderNode<S: ComputeShader>(PhantomData<S>);
+
+impl<S: ComputeShader> Node for ComputeShaderNode<S> {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let prepared = world.resource::<PreparedComputeDispatches<S>>();
+        if prepared.dispatches.is_empty() {
+            return Ok(());
+        }
+        let pipeline = world.resource::<ComputeShaderPipeline<S>>();
+        let Some(compute_pipeline) = world
+            .resource::<PipelineCache>()
+            .get_compute_pipeline(pipeline.pipeline_id)
+        else {
+            return Ok(());
+        };
+
+        let command_encoder = render_context.command_encoder();
+        for dispatch in &prepared.dispatches {
+            {
+                let mut pass = command_encoder.begin_compute_pass(&ComputePassDescriptor {
+                    label: S::label(),
+                    timestamp_writes: None,
+                });
+                pass.set_pipeline(compute_pipeline);
+                pass.set_bind_group(0, &dispatch.bind_group, &[]);
+                pass.dispatch_workgroups(
+                    dispatch.workgroups.x,
+                    dispatch.workgroups.y,
+                    dispatch.workgroups.z,
+                );
+            }
+
+            if let Some((buffer, staging_buffer, _)) = &dispatch.readback {
+                command_encoder.copy_buffer_to_buffer(buffer, 0, staging_buffer, 0, buffer.size());
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -22,7 +22,7 @@ use crate::renderer::{RenderDevice, WgpuWrapper};
 use super::RecordDiagnostics;
 
 // buffer offset must be divisible by 256, so this constant must be divisible by 32 (=256/8)
-const MAX_TIMESTAMP_QUERIES: u32 = 256;
+const MAX_TIMESTAMP_QUERIES: u32 = 1024;
 const MAX_PIPELINE_STATISTICS: u32 = 128;
 
 const TIMESTAMP_SIZE: u64 = 8;
@@ -125,6 +125,22 @@ impl DiagnosticsRecorder {
     }
 }
 
+impl DiagnosticsRecorder {
+    /// Begins a time span covering a whole render graph node.
+    ///
+    /// Unlike other spans, node spans never become the parent of the spans recorded while they
+    /// are open, so that the paths of the spans recorded by the node itself don't depend on
+    /// the render graph layout.
+    pub(crate) fn begin_node_span(&self, encoder: &mut CommandEncoder, name: Cow<'static, str>) {
+        self.current_frame_lock().begin_node_span(encoder, name);
+    }
+
+    /// Ends the span started with [`DiagnosticsRecorder::begin_node_span`].
+    pub(crate) fn end_node_span(&self, encoder: &mut CommandEncoder) {
+        self.current_frame_lock().end_node_span(encoder);
+    }
+}
+
 impl RecordDiagnostics for DiagnosticsRecorder {
     fn begin_time_span<E: WriteTimestamp>(&self, encoder: &mut E, span_name: Cow<'static, str>) {
         self.current_frame_lock()
@@ -146,6 +162,7 @@ impl RecordDiagnostics for DiagnosticsRecorder {
 
 struct SpanRecord {
     thread_id: ThreadId,
+    is_node: bool,
     path_range: Range<usize>,
     pass_kind: Option<PassKind>,
     begin_timestamp_index: Option<u32>,
@@ -285,6 +302,7 @@ impl FrameData {
     fn open_span(
         &mut self,
         pass_kind: Option<PassKind>,
+        is_node: bool,
         name: Cow<'static, str>,
     ) -> &mut SpanRecord {
         let thread_id = thread::current().id();
@@ -292,7 +310,7 @@ impl FrameData {
         let parent = self
             .open_spans
             .iter()
-            .filter(|v| v.thread_id == thread_id)
+            .filter(|v| v.thread_id == thread_id && !v.is_node && !is_node)
             .last();
 
         let path_range = match &parent {
@@ -311,6 +329,7 @@ impl FrameData {
 
         self.open_spans.push(SpanRecord {
             thread_id,
+            is_node,
             path_range,
             pass_kind,
             begin_timestamp_index: None,
@@ -323,13 +342,13 @@ impl FrameData {
         self.open_spans.last_mut().unwrap()
     }
 
-    fn close_span(&mut self) -> &mut SpanRecord {
+    fn close_span(&mut self, is_node: bool) -> &mut SpanRecord {
         let thread_id = thread::current().id();
 
         let iter = self.open_spans.iter();
         let (index, _) = iter
             .enumerate()
-            .filter(|(_, v)| v.thread_id == thread_id)
+            .filter(|(_, v)| v.thread_id == thread_id && v.is_node == is_node)
             .last()
             .unwrap();
 
@@ -342,7 +361,7 @@ impl FrameData {
         let begin_instant = Instant::now();
         let begin_timestamp_index = self.write_timestamp(encoder, false);
 
-        let span = self.open_span(None, name);
+        let span = self.open_span(None, false, name);
         span.begin_instant = Some(begin_instant);
         span.begin_timestamp_index = begin_timestamp_index;
     }
@@ -350,7 +369,24 @@ impl FrameData {
     fn end_time_span(&mut self, encoder: &mut impl WriteTimestamp) {
         let end_timestamp_index = self.write_timestamp(encoder, false);
 
-        let span = self.close_span();
+        let span = self.close_span(false);
+        span.end_timestamp_index = end_timestamp_index;
+        span.end_instant = Some(Instant::now());
+    }
+
+    fn begin_node_span(&mut self, encoder: &mut impl WriteTimestamp, name: Cow<'static, str>) {
+        let begin_instant = Instant::now();
+        let begin_timestamp_index = self.write_timestamp(encoder, false);
+
+        let span = self.open_span(None, true, name);
+        span.begin_instant = Some(begin_instant);
+        span.begin_timestamp_index = begin_timestamp_index;
+    }
+
+    fn end_node_span(&mut self, encoder: &mut impl WriteTimestamp) {
+        let end_timestamp_index = self.write_timestamp(encoder, false);
+
+        let span = self.close_span(true);
         span.end_timestamp_index = end_timestamp_index;
         span.end_instant = Some(Instant::now());
     }
@@ -361,7 +397,7 @@ impl FrameData {
         let begin_timestamp_index = self.write_timestamp(pass, true);
         let pipeline_statistics_index = self.write_pipeline_statistics(pass);
 
-        let span = self.open_span(Some(P::KIND), name);
+        let span = self.open_span(Some(P::KIND), false, name);
         span.begin_instant = Some(begin_instant);
         span.begin_timestamp_index = begin_timestamp_index;
         span.pipeline_statistics_index = pipeline_statistics_index;
@@ -370,7 +406,7 @@ impl FrameData {
     fn end_pass(&mut self, pass: &mut impl Pass) {
         let end_timestamp_index = self.write_timestamp(pass, true);
 
-        let span = self.close_span();
+        let span = self.close_span(false);
         span.end_timestamp_index = end_timestamp_index;
 
         if span.pipeline_statistics_index.is_some() {
@@ -495,9 +531,11 @@ impl FrameData {
                 let begin = timestamps[begin as usize] as f64;
                 let end = timestamps[end as usize] as f64;
                 let value = (end - begin) * (timestamp_period_ns as f64) / 1e6;
+                let path = self.diagnostic_path(&span.path_range, "elapsed_gpu");
 
+                tracing::trace!(path = path.as_str(), elapsed_gpu_ms = value, "render span");
                 diagnostics.push(RenderDiagnostic {
-                    path: self.diagnostic_path(&span.path_range, "elapsed_gpu"),
+                    path,
                     suffix: "ms",
                     value,
                 });
@@ -21,6 +21,10 @@ use super::{RenderDevice, RenderQueue};
 ///
 /// To access the diagnostics, you can use [`DiagnosticsStore`](bevy_diagnostic::DiagnosticsStore) resource,
 /// or add [`LogDiagnosticsPlugin`](bevy_diagnostic::LogDiagnosticsPlugin).
+/// GPU timings are also emitted as `trace` level events.
+///
+/// Every render graph node is timed automatically, under `render/nodes/<graph>/<node>`.
+/// Spans recorded by the nodes themselves are not nested under these.
 ///
 /// To record diagnostics in your own passes:
 ///  1. First, obtain the diagnostic recorder using [`RenderContext::diagnostic_recorder`](crate::renderer::RenderContext::diagnostic_recorder).
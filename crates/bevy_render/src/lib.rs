@@ -15,6 +15,7 @@ extern crate core;
 pub mod alpha;
 pub mod batching;
 pub mod camera;
+pub mod compute;
 pub mod diagnostic;
 pub mod extract_component;
 pub mod extract_instances;
@@ -67,7 +68,7 @@ use crate::{
     camera::CameraPlugin,
     mesh::{morph::MorphPlugin, MeshPlugin},
     render_asset::prepare_assets,
-    render_resource::{PipelineCache, Shader, ShaderLoader},
+    render_resource::{PipelineCache, Shader, ShaderErrors, ShaderLoader},
     renderer::{render_system, RenderInstance},
     settings::RenderCreation,
     view::{ViewPlugin, WindowRenderPlugin},
@@ -95,6 +96,10 @@ pub struct RenderPlugin {
     /// If `true`, disables asynchronous pipeline compilation.
     /// This has no effect on macOS, Wasm, iOS, or without the `multi-threaded` feature.
     pub synchronous_pipeline_compilation: bool,
+    /// The maximum number of pipelines whose creation is started each frame, or `None` for no limit.
+    ///
+    /// See [`PipelineCache::set_max_pipeline_compilations_per_frame`].
+    pub max_pipeline_compilations_per_frame: Option<usize>,
 }
 
 /// The systems sets of the default [`App`] rendering schedule.
@@ -365,13 +370,17 @@ impl Plugin for RenderPlugin {
 
             let render_app = app.sub_app_mut(RenderApp);
 
+            let mut pipeline_cache = PipelineCache::new(
+                device.clone(),
+                render_adapter.clone(),
+                self.synchronous_pipeline_compilation,
+            );
+            pipeline_cache
+                .set_max_pipeline_compilations_per_frame(self.max_pipeline_compilations_per_frame);
+
             render_app
                 .insert_resource(instance)
-                .insert_resource(PipelineCache::new(
-                    device.clone(),
-                    render_adapter.clone(),
-                    self.synchronous_pipeline_compilation,
-                ))
+                .insert_resource(pipeline_cache)
                 .insert_resource(device)
                 .insert_resource(queue)
                 .insert_resource(render_adapter)
@@ -402,7 +411,8 @@ fn extract(main_world: &mut World, render_world: &mut World) {
 
 /// SAFETY: this function must be called from the main thread.
 unsafe fn initialize_render_app(app: &mut App) {
-    app.init_resource::<ScratchMainWorld>();
+    app.init_resource::<ScratchMainWorld>()
+        .init_resource::<ShaderErrors>();
 
     let mut render_app = SubApp::new();
     render_app.update_schedule = Some(Render.intern());
@@ -421,7 +431,13 @@ unsafe fn initialize_render_app(app: &mut App) {
         .add_schedule(Render::base_schedule())
         .init_resource::<render_graph::RenderGraph>()
         .insert_resource(app.world().resource::<AssetServer>().clone())
-        .add_systems(ExtractSchedule, PipelineCache::extract_shaders)
+        .add_systems(
+            ExtractSchedule,
+            (
+                PipelineCache::extract_shaders,
+                PipelineCache::extract_shader_errors,
+            ),
+        )
         .add_systems(
             Render,
             (
@@ -2,6 +2,7 @@ mod capsule;
 mod cuboid;
 mod cylinder;
 mod plane;
+mod rounded_cuboid;
 mod sphere;
 mod torus;
 pub(crate) mod triangle3d;
@@ -9,5 +10,6 @@ pub(crate) mod triangle3d;
 pub use capsule::*;
 pub use cylinder::*;
 pub use plane::*;
+pub use rounded_cuboid::*;
 pub use sphere::*;
 pub use torus::*;
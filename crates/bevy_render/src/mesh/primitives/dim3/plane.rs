@@ -13,6 +13,11 @@ pub struct PlaneMeshBuilder {
     pub plane: Plane3d,
     /// Half the size of the plane mesh.
     pub half_size: Vec2,
+    /// The number of subdivisions along each axis of the plane.
+    /// A value of `0` produces a single quad, `1` splits it into 2x2 quads, and so on.
+    ///
+    /// The default is `0`.
+    pub subdivisions: u32,
 }
 
 impl Default for PlaneMeshBuilder {
@@ -20,6 +25,7 @@ impl Default for PlaneMeshBuilder {
         Self {
             plane: Plane3d::default(),
             half_size: Vec2::ONE,
+            subdivisions: 0,
         }
     }
 }
@@ -31,6 +37,7 @@ impl PlaneMeshBuilder {
         Self {
             plane: Plane3d { normal },
             half_size: size / 2.0,
+            ..Default::default()
         }
     }
 
@@ -68,19 +75,55 @@ impl PlaneMeshBuilder {
         self
     }
 
+    /// Sets the number of subdivisions along each axis of the plane.
+    /// A value of `0` produces a single quad, `1` splits it into 2x2 quads, and so on.
+    #[inline]
+    pub const fn subdivisions(mut self, subdivisions: u32) -> Self {
+        self.subdivisions = subdivisions;
+        self
+    }
+
     /// Builds a [`Mesh`] based on the configuration in `self`.
     pub fn build(&self) -> Mesh {
         let rotation = Quat::from_rotation_arc(Vec3::Y, *self.plane.normal);
-        let positions = vec![
-            rotation * Vec3::new(self.half_size.x, 0.0, -self.half_size.y),
-            rotation * Vec3::new(-self.half_size.x, 0.0, -self.half_size.y),
-            rotation * Vec3::new(-self.half_size.x, 0.0, self.half_size.y),
-            rotation * Vec3::new(self.half_size.x, 0.0, self.half_size.y),
-        ];
-
-        let normals = vec![self.plane.normal.to_array(); 4];
-        let uvs = vec![[1.0, 0.0], [0.0, 0.0], [0.0, 1.0], [1.0, 1.0]];
-        let indices = Indices::U32(vec![0, 1, 2, 0, 2, 3]);
+        let vertices_per_side = self.subdivisions + 2;
+        let num_vertices = (vertices_per_side * vertices_per_side) as usize;
+        let num_indices = ((vertices_per_side - 1) * (vertices_per_side - 1) * 6) as usize;
+
+        let mut positions: Vec<Vec3> = Vec::with_capacity(num_vertices);
+        let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(num_vertices);
+        let mut indices: Vec<u32> = Vec::with_capacity(num_indices);
+
+        for z in 0..vertices_per_side {
+            for x in 0..vertices_per_side {
+                let tx = x as f32 / (vertices_per_side - 1) as f32;
+                let tz = z as f32 / (vertices_per_side - 1) as f32;
+                let position = Vec3::new(
+                    (-1.0 + 2.0 * tx) * self.half_size.x,
+                    0.0,
+                    (-1.0 + 2.0 * tz) * self.half_size.y,
+                );
+                positions.push(rotation * position);
+                uvs.push([tx, tz]);
+            }
+        }
+
+        for z in 0..vertices_per_side - 1 {
+            for x in 0..vertices_per_side - 1 {
+                let quad = z * vertices_per_side + x;
+                indices.extend_from_slice(&[
+                    quad + vertices_per_side + 1,
+                    quad + 1,
+                    quad + vertices_per_side,
+                    quad,
+                    quad + vertices_per_side,
+                    quad + 1,
+                ]);
+            }
+        }
+
+        let normals = vec![self.plane.normal.to_array(); num_vertices];
+        let indices = Indices::U32(indices);
 
         Mesh::new(
             PrimitiveTopology::TriangleList,
@@ -0,0 +1,140 @@
+use bevy_math::{primitives::Cuboid, Vec3};
+use wgpu::PrimitiveTopology;
+
+use crate::{
+    mesh::{Indices, Mesh},
+    render_asset::RenderAssetUsages,
+};
+
+/// A builder used for creating a [`Mesh`] with a [`Cuboid`] shape whose edges and corners are rounded.
+#[derive(Clone, Copy, Debug)]
+pub struct RoundedCuboidMeshBuilder {
+    /// The [`Cuboid`] shape. The rounded mesh fits exactly inside of it.
+    pub cuboid: Cuboid,
+    /// The radius of the rounded edges and corners.
+    ///
+    /// The radius is clamped to the smallest half size of the cuboid.
+    /// A radius of `0.0` produces a regular cuboid.
+    ///
+    /// The default is `0.1`.
+    pub radius: f32,
+    /// The number of segments used for each rounded edge.
+    ///
+    /// The default is `4`.
+    pub resolution: usize,
+}
+
+impl Default for RoundedCuboidMeshBuilder {
+    fn default() -> Self {
+        Self {
+            cuboid: Cuboid::default(),
+            radius: 0.1,
+            resolution: 4,
+        }
+    }
+}
+
+impl RoundedCuboidMeshBuilder {
+    /// Creates a new [`RoundedCuboidMeshBuilder`] from a full size and the radius of the rounded edges.
+    #[inline]
+    pub fn new(size: Vec3, radius: f32) -> Self {
+        Self {
+            cuboid: Cuboid::from_size(size),
+            radius,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the radius of the rounded edges and corners.
+    #[inline]
+    pub const fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the number of segments used for each rounded edge.
+    #[inline]
+    pub const fn resolution(mut self, resolution: usize) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Builds a [`Mesh`] according to the configuration in `self`.
+    pub fn build(&self) -> Mesh {
+        let half_size = self.cuboid.half_size;
+        let radius = self.radius.clamp(0.0, half_size.min_element());
+        let segments = self.resolution.max(1);
+        let inner_half_size = half_size - Vec3::splat(radius);
+
+        // Each face is a grid whose rows and columns are denser near the edges,
+        // so that projecting the grid onto the rounded shape yields evenly shaded edges.
+        let grid_coordinates = |half_extent: f32| -> Vec<f32> {
+            let start = (0..=segments).map(|i| -half_extent + radius * i as f32 / segments as f32);
+            let end =
+                (0..=segments).map(|i| half_extent - radius + radius * i as f32 / segments as f32);
+            start.chain(end).collect()
+        };
+
+        // (normal, u, v) for every face, with `u.cross(v) == normal`
+        let faces = [
+            (Vec3::X, Vec3::NEG_Z, Vec3::Y),
+            (Vec3::NEG_X, Vec3::Z, Vec3::Y),
+            (Vec3::Y, Vec3::X, Vec3::NEG_Z),
+            (Vec3::NEG_Y, Vec3::X, Vec3::Z),
+            (Vec3::Z, Vec3::X, Vec3::Y),
+            (Vec3::NEG_Z, Vec3::NEG_X, Vec3::Y),
+        ];
+
+        let grid_size = 2 * (segments + 1);
+        let n_vertices = faces.len() * grid_size * grid_size;
+        let mut positions: Vec<[f32; 3]> = Vec::with_capacity(n_vertices);
+        let mut normals: Vec<[f32; 3]> = Vec::with_capacity(n_vertices);
+        let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(n_vertices);
+        let mut indices: Vec<u32> = Vec::with_capacity(faces.len() * (grid_size - 1).pow(2) * 6);
+
+        for (normal, u, v) in faces {
+            let normal_extent = half_size.dot(normal.abs());
+            let u_extent = half_size.dot(u.abs());
+            let v_extent = half_size.dot(v.abs());
+            let base_index = positions.len() as u32;
+
+            for b in grid_coordinates(v_extent) {
+                for a in grid_coordinates(u_extent) {
+                    let point = normal * normal_extent + u * a + v * b;
+                    let inner = point.clamp(-inner_half_size, inner_half_size);
+                    let direction = (point - inner).try_normalize().unwrap_or(normal);
+
+                    positions.push((inner + direction * radius).to_array());
+                    normals.push(direction.to_array());
+                    uvs.push([
+                        (a + u_extent) / (2.0 * u_extent),
+                        1.0 - (b + v_extent) / (2.0 * v_extent),
+                    ]);
+                }
+            }
+
+            for row in 0..grid_size as u32 - 1 {
+                for column in 0..grid_size as u32 - 1 {
+                    let i = base_index + row * grid_size as u32 + column;
+                    let above = i + grid_size as u32;
+                    indices.extend_from_slice(&[i, i + 1, above + 1, i, above + 1, above]);
+                }
+            }
+        }
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_indices(Indices::U32(indices))
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    }
+}
+
+impl From<RoundedCuboidMeshBuilder> for Mesh {
+    fn from(rounded_cuboid: RoundedCuboidMeshBuilder) -> Self {
+        rounded_cuboid.build()
+    }
+}
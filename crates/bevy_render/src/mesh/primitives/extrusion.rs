@@ -0,0 +1,192 @@
+use bevy_math::{Vec2, Vec3};
+use bevy_utils::HashMap;
+use wgpu::PrimitiveTopology;
+
+use crate::mesh::{Indices, Mesh, VertexAttributeValues};
+
+/// A builder used for creating a 3D [`Mesh`] by extruding a flat 2D [`Mesh`] along the Z axis.
+///
+/// The base mesh is expected to lie in the XY plane with its triangles facing +Z,
+/// which is the case for every 2D shape implementing [`Meshable`](super::Meshable).
+/// It must use [`PrimitiveTopology::TriangleList`] and have a [`Mesh::ATTRIBUTE_POSITION`]
+/// attribute of type `float3`.
+///
+/// The outline of the shape is found through the edges that belong to a single triangle,
+/// so vertices on the outline must be shared by the triangles using them.
+///
+/// ```
+/// # use bevy_math::prelude::Circle;
+/// # use bevy_render::prelude::*;
+/// # use bevy_render::mesh::ExtrusionMeshBuilder;
+/// // A coin with a radius of 1 and a thickness of 0.1
+/// let coin = ExtrusionMeshBuilder::new(Circle::new(1.0).mesh().resolution(64), 0.1).build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ExtrusionMeshBuilder {
+    /// The flat mesh that is extruded.
+    pub base: Mesh,
+    /// Half the depth of the extrusion.
+    ///
+    /// The resulting mesh spans from `-half_depth` to `half_depth` along the Z axis.
+    pub half_depth: f32,
+}
+
+impl ExtrusionMeshBuilder {
+    /// Creates a new [`ExtrusionMeshBuilder`] from a flat base mesh and the full depth of the extrusion.
+    #[inline]
+    pub fn new(base: impl Into<Mesh>, depth: f32) -> Self {
+        Self {
+            base: base.into(),
+            half_depth: depth / 2.0,
+        }
+    }
+
+    /// Sets the full depth of the extrusion.
+    #[inline]
+    pub fn depth(mut self, depth: f32) -> Self {
+        self.half_depth = depth / 2.0;
+        self
+    }
+
+    /// Builds a [`Mesh`] according to the configuration in `self`.
+    ///
+    /// # Panics
+    /// Panics if the base mesh has any other topology than [`PrimitiveTopology::TriangleList`],
+    /// or if its [`Mesh::ATTRIBUTE_POSITION`] is missing or not of type `float3`.
+    pub fn build(&self) -> Mesh {
+        assert!(
+            matches!(
+                self.base.primitive_topology(),
+                PrimitiveTopology::TriangleList
+            ),
+            "`ExtrusionMeshBuilder` can only extrude `TriangleList`s"
+        );
+
+        let base_positions = self
+            .base
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+            .expect("the base mesh should have `Mesh::ATTRIBUTE_POSITION` of type `float3`");
+        let base_uvs = match self.base.attribute(Mesh::ATTRIBUTE_UV_0) {
+            Some(VertexAttributeValues::Float32x2(uvs)) => uvs.clone(),
+            _ => vec![[0.0, 0.0]; base_positions.len()],
+        };
+        let base_indices: Vec<u32> = match self.base.indices() {
+            Some(indices) => indices.iter().map(|i| i as u32).collect(),
+            None => (0..base_positions.len() as u32).collect(),
+        };
+
+        let n_base = base_positions.len();
+        let mut positions: Vec<[f32; 3]> = Vec::with_capacity(n_base * 2);
+        let mut normals: Vec<[f32; 3]> = Vec::with_capacity(n_base * 2);
+        let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(n_base * 2);
+        let mut indices: Vec<u32> = Vec::with_capacity(base_indices.len() * 2);
+
+        // Front cap
+        for (position, uv) in base_positions.iter().zip(&base_uvs) {
+            positions.push([position[0], position[1], self.half_depth]);
+            normals.push([0.0, 0.0, 1.0]);
+            uvs.push(*uv);
+        }
+        indices.extend_from_slice(&base_indices);
+
+        // Back cap, with the winding order reversed so it faces -Z
+        for (position, uv) in base_positions.iter().zip(&base_uvs) {
+            positions.push([position[0], position[1], -self.half_depth]);
+            normals.push([0.0, 0.0, -1.0]);
+            uvs.push([1.0 - uv[0], uv[1]]);
+        }
+        for triangle in base_indices.chunks_exact(3) {
+            let offset = n_base as u32;
+            indices.extend_from_slice(&[
+                triangle[0] + offset,
+                triangle[2] + offset,
+                triangle[1] + offset,
+            ]);
+        }
+
+        // Sides: every edge used by a single triangle lies on the outline of the shape.
+        // Counter-clockwise triangles keep the inside of the shape to the left of their edges,
+        // so the outward normal of such an edge is its direction rotated clockwise.
+        let directed_edges: Vec<(u32, u32)> = base_indices
+            .chunks_exact(3)
+            .flat_map(|t| [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])])
+            .collect();
+        let mut edge_counts: HashMap<(u32, u32), usize> = HashMap::default();
+        for &(a, b) in &directed_edges {
+            *edge_counts.entry((a.min(b), a.max(b))).or_default() += 1;
+        }
+
+        for &(a, b) in &directed_edges {
+            if edge_counts[&(a.min(b), a.max(b))] != 1 {
+                continue;
+            }
+
+            let start = Vec3::from(base_positions[a as usize]);
+            let end = Vec3::from(base_positions[b as usize]);
+            let direction = (end - start).truncate();
+            let normal = Vec2::new(direction.y, -direction.x)
+                .normalize_or_zero()
+                .extend(0.0)
+                .to_array();
+
+            let first = positions.len() as u32;
+            positions.extend_from_slice(&[
+                [start.x, start.y, -self.half_depth],
+                [end.x, end.y, -self.half_depth],
+                [end.x, end.y, self.half_depth],
+                [start.x, start.y, self.half_depth],
+            ]);
+            normals.extend_from_slice(&[normal; 4]);
+            uvs.extend_from_slice(&[[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]);
+            indices.extend_from_slice(&[first, first + 1, first + 2, first, first + 2, first + 3]);
+        }
+
+        Mesh::new(PrimitiveTopology::TriangleList, self.base.asset_usage)
+            .with_inserted_indices(Indices::U32(indices))
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    }
+}
+
+impl From<ExtrusionMeshBuilder> for Mesh {
+    fn from(extrusion: ExtrusionMeshBuilder) -> Self {
+        extrusion.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::primitives::Rectangle;
+
+    use super::ExtrusionMeshBuilder;
+    use crate::mesh::{Mesh, VertexAttributeValues};
+
+    #[test]
+    fn extruded_rectangle_is_closed_box() {
+        let mesh = ExtrusionMeshBuilder::new(Rectangle::new(2.0, 2.0), 2.0).build();
+
+        // Two caps of 4 vertices, plus 4 sides of 4 vertices
+        assert_eq!(mesh.count_vertices(), 4 * 2 + 4 * 4);
+        // Two caps of 2 triangles, plus 4 sides of 2 triangles
+        assert_eq!(mesh.indices().unwrap().len(), (2 * 2 + 4 * 2) * 3);
+
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("Expected positions f32x3");
+        };
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("Expected normals f32x3");
+        };
+
+        // Every side normal points away from the center of the box
+        for (position, normal) in positions.iter().zip(normals).skip(8) {
+            let outward = position[0] * normal[0] + position[1] * normal[1];
+            assert!(outward > 0.0);
+        }
+    }
+}
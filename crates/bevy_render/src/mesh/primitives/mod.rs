@@ -25,6 +25,9 @@ pub use dim2::{CircleMeshBuilder, EllipseMeshBuilder};
 mod dim3;
 pub use dim3::*;
 
+mod extrusion;
+pub use extrusion::*;
+
 /// A trait for shapes that can be turned into a [`Mesh`](super::Mesh).
 pub trait Meshable {
     /// The output of [`Self::mesh`]. This can either be a [`Mesh`](super::Mesh)
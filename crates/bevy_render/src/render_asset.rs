@@ -172,6 +172,18 @@ impl<A: RenderAsset> Default for ExtractedAssets<A> {
     }
 }
 
+impl<A: RenderAsset> ExtractedAssets<A> {
+    /// Returns the assets that were added or modified this frame.
+    pub fn extracted(&self) -> &[(AssetId<A::SourceAsset>, A::SourceAsset)] {
+        &self.extracted
+    }
+
+    /// Returns the assets that were removed this frame.
+    pub fn removed(&self) -> &[AssetId<A::SourceAsset>] {
+        &self.removed
+    }
+}
+
 /// Stores all GPU representations ([`RenderAsset`])
 /// of [`RenderAsset::SourceAsset`] as long as they exist.
 #[derive(Resource)]
@@ -284,6 +296,14 @@ pub struct PrepareNextFrameAssets<A: RenderAsset> {
     assets: Vec<(AssetId<A::SourceAsset>, A::SourceAsset)>,
 }
 
+impl<A: RenderAsset> PrepareNextFrameAssets<A> {
+    /// Returns the ids of the assets that couldn't be prepared yet and will be retried next
+    /// frame.
+    pub fn ids(&self) -> impl Iterator<Item = AssetId<A::SourceAsset>> + '_ {
+        self.assets.iter().map(|(id, _)| *id)
+    }
+}
+
 impl<A: RenderAsset> Default for PrepareNextFrameAssets<A> {
     fn default() -> Self {
         Self {
@@ -1,13 +1,13 @@
 use crate::{
     render_graph::{
         Edge, Node, NodeRunError, NodeState, RenderGraphContext, RenderGraphError, RenderLabel,
-        SlotInfo, SlotLabel,
+        SlotInfo, SlotLabel, SlotType,
     },
     renderer::RenderContext,
 };
 use bevy_ecs::{define_label, intern::Interned, prelude::World, system::Resource};
 use bevy_utils::HashMap;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 use super::{EdgeExistence, InternedRenderLabel, IntoRenderNodeArray};
 
@@ -38,6 +38,12 @@ pub type InternedRenderSubGraph = Interned<dyn RenderSubGraph>;
 /// Additionally a render graph can contain multiple sub graphs, which are run by the
 /// corresponding nodes. Every render graph can have its own optional input node.
 ///
+/// The graph is a resource of the render world, so it can be edited at any time
+/// outside of its execution, not only while plugins are built: a system in the
+/// render world can take a `ResMut<RenderGraph>` to add, remove or
+/// [insert](Self::insert_node_between) nodes. Use [`validate`](Self::validate)
+/// afterwards to check the result.
+///
 /// ## Example
 /// Here is a simple render graph example with two nodes connected by a node edge.
 /// ```ignore
@@ -201,6 +207,43 @@ impl RenderGraph {
         Ok(())
     }
 
+    /// Adds the `node` with the `label` to the graph, and schedules it to run after
+    /// `output_node` and before `input_node`.
+    ///
+    /// If there is a node edge from `output_node` to `input_node`, it is replaced by the
+    /// edges to and from the new node. Other edges are left untouched.
+    ///
+    /// Fails without modifying the graph if `output_node` or `input_node` does not exist.
+    pub fn insert_node_between<T>(
+        &mut self,
+        output_node: impl RenderLabel,
+        label: impl RenderLabel,
+        node: T,
+        input_node: impl RenderLabel,
+    ) -> Result<(), RenderGraphError>
+    where
+        T: Node,
+    {
+        let output_node = output_node.intern();
+        let label = label.intern();
+        let input_node = input_node.intern();
+
+        self.get_node_state(output_node)?;
+        self.get_node_state(input_node)?;
+
+        let edge = Edge::NodeEdge {
+            output_node,
+            input_node,
+        };
+        if self.has_edge(&edge) {
+            self.remove_node_edge(output_node, input_node)?;
+        }
+
+        self.add_node(label, node);
+        self.try_add_node_edge(output_node, label)?;
+        self.try_add_node_edge(label, input_node)
+    }
+
     /// Retrieves the [`NodeState`] referenced by the `label`.
     pub fn get_node_state(&self, label: impl RenderLabel) -> Result<&NodeState, RenderGraphError> {
         let label = label.intern();
@@ -236,6 +279,34 @@ impl RenderGraph {
         self.get_node_state_mut(label).and_then(|n| n.node_mut())
     }
 
+    /// Returns the [`SlotType`] of the input `slot` of the node referenced by the `label`.
+    pub fn get_input_slot_type(
+        &self,
+        label: impl RenderLabel,
+        slot: impl Into<SlotLabel>,
+    ) -> Result<SlotType, RenderGraphError> {
+        let slot = slot.into();
+        self.get_node_state(label)?
+            .input_slots
+            .get_slot(slot.clone())
+            .map(|slot_info| slot_info.slot_type)
+            .ok_or(RenderGraphError::InvalidInputNodeSlot(slot))
+    }
+
+    /// Returns the [`SlotType`] of the output `slot` of the node referenced by the `label`.
+    pub fn get_output_slot_type(
+        &self,
+        label: impl RenderLabel,
+        slot: impl Into<SlotLabel>,
+    ) -> Result<SlotType, RenderGraphError> {
+        let slot = slot.into();
+        self.get_node_state(label)?
+            .output_slots
+            .get_slot(slot.clone())
+            .map(|slot_info| slot_info.slot_type)
+            .ok_or(RenderGraphError::InvalidOutputNodeSlot(slot))
+    }
+
     /// Adds the [`Edge::SlotEdge`] to the graph. This guarantees that the `output_node`
     /// is run before the `input_node` and also connects the `output_slot` to the `input_slot`.
     ///
@@ -498,6 +569,117 @@ impl RenderGraph {
         Ok(())
     }
 
+    /// Checks the whole graph, including its sub graphs, for problems that would prevent it
+    /// from running.
+    ///
+    /// Unlike the methods that edit the graph, which only check the edge being added, this
+    /// reports every problem at once:
+    /// - input slots that aren't connected to any output slot,
+    /// - edges to or from nodes that do not exist,
+    /// - slot edges between slots of different types,
+    /// - cycles.
+    pub fn validate(&self) -> Result<(), RenderGraphValidationReport> {
+        let mut report = RenderGraphValidationReport::default();
+
+        for node in self.iter_nodes() {
+            for input_slot in 0..node.input_slots.len() {
+                if node.edges.get_input_slot_edge(input_slot).is_err() {
+                    report
+                        .errors
+                        .push(RenderGraphError::UnconnectedNodeInputSlot {
+                            node: node.label,
+                            input_slot,
+                        });
+                }
+            }
+
+            for edge in node.edges.output_edges() {
+                let Ok(input_node) = self.get_node_state(edge.get_input_node()) else {
+                    report
+                        .errors
+                        .push(RenderGraphError::DanglingEdge(edge.clone()));
+                    continue;
+                };
+                if let Edge::SlotEdge {
+                    output_node,
+                    output_index,
+                    input_node: input_node_label,
+                    input_index,
+                } = *edge
+                {
+                    let output_slot = node.output_slots.get_slot(output_index);
+                    let input_slot = input_node.input_slots.get_slot(input_index);
+                    if output_slot.map(|slot| slot.slot_type)
+                        != input_slot.map(|slot| slot.slot_type)
+                    {
+                        report.errors.push(RenderGraphError::MismatchedNodeSlots {
+                            output_node,
+                            output_slot: output_index,
+                            input_node: input_node_label,
+                            input_slot: input_index,
+                        });
+                    }
+                }
+            }
+
+            for edge in node.edges.input_edges() {
+                if self.get_node_state(edge.get_output_node()).is_err() {
+                    report
+                        .errors
+                        .push(RenderGraphError::DanglingEdge(edge.clone()));
+                }
+            }
+        }
+
+        // Kahn's algorithm: any node that can't be scheduled is part of a cycle, or runs after
+        // one.
+        let mut remaining_inputs: HashMap<InternedRenderLabel, usize> = self
+            .iter_nodes()
+            .map(|node| {
+                let input_count = node
+                    .edges
+                    .input_edges()
+                    .iter()
+                    .filter(|edge| self.nodes.contains_key(&edge.get_output_node()))
+                    .count();
+                (node.label, input_count)
+            })
+            .collect();
+        let mut ready: Vec<InternedRenderLabel> = remaining_inputs
+            .iter()
+            .filter(|(_, &count)| count == 0)
+            .map(|(&label, _)| label)
+            .collect();
+        while let Some(label) = ready.pop() {
+            remaining_inputs.remove(&label);
+            for edge in self.nodes[&label].edges.output_edges() {
+                if let Some(count) = remaining_inputs.get_mut(&edge.get_input_node()) {
+                    *count -= 1;
+                    if *count == 0 {
+                        ready.push(edge.get_input_node());
+                    }
+                }
+            }
+        }
+        if !remaining_inputs.is_empty() {
+            report.errors.push(RenderGraphError::NodeCycle(
+                remaining_inputs.into_keys().collect(),
+            ));
+        }
+
+        for (label, sub_graph) in self.iter_sub_graphs() {
+            if let Err(sub_graph_report) = sub_graph.validate() {
+                report.sub_graphs.push((label, sub_graph_report));
+            }
+        }
+
+        if report.errors.is_empty() && report.sub_graphs.is_empty() {
+            Ok(())
+        } else {
+            Err(report)
+        }
+    }
+
     /// Checks whether the `edge` already exists in the graph.
     pub fn has_edge(&self, edge: &Edge) -> bool {
         let output_node_state = self.get_node_state(edge.get_output_node());
@@ -625,7 +807,7 @@ impl RenderGraph {
 }
 
 impl Debug for RenderGraph {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for node in self.iter_nodes() {
             writeln!(f, "{:?}", node.label)?;
             writeln!(f, "  in: {:?}", node.input_slots)?;
@@ -636,6 +818,36 @@ impl Debug for RenderGraph {
     }
 }
 
+/// The problems found in a [`RenderGraph`] by [`RenderGraph::validate`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RenderGraphValidationReport {
+    /// The problems found in the graph itself.
+    pub errors: Vec<RenderGraphError>,
+    /// The reports of the sub graphs that have problems.
+    pub sub_graphs: Vec<(InternedRenderSubGraph, RenderGraphValidationReport)>,
+}
+
+impl RenderGraphValidationReport {
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        for error in &self.errors {
+            writeln!(f, "{:indent$}- {error}", "")?;
+        }
+        for (label, report) in &self.sub_graphs {
+            writeln!(f, "{:indent$}in sub graph {label:?}:", "")?;
+            report.fmt_indented(f, indent + 2)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RenderGraphValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl std::error::Error for RenderGraphValidationReport {}
+
 /// A [`Node`] which acts as an entry point for a [`RenderGraph`] with custom inputs.
 /// It has the same input and output slots and simply copies them over when run.
 pub struct GraphInputNode {
@@ -670,7 +882,8 @@ mod tests {
     use crate::{
         render_graph::{
             node::IntoRenderNodeArray, Edge, InternedRenderLabel, Node, NodeRunError, RenderGraph,
-            RenderGraphContext, RenderGraphError, RenderLabel, SlotInfo, SlotType,
+            RenderGraphContext, RenderGraphError, RenderLabel, RenderSubGraph, SlotInfo, SlotLabel,
+            SlotType,
         },
         renderer::RenderContext,
     };
@@ -685,6 +898,9 @@ mod tests {
         D,
     }
 
+    #[derive(Debug, Hash, PartialEq, Eq, Clone, RenderSubGraph)]
+    struct TestSubGraph;
+
     #[derive(Debug)]
     struct TestNode {
         inputs: Vec<SlotInfo>,
@@ -912,4 +1128,118 @@ mod tests {
             "B -> C"
         );
     }
+
+    #[test]
+    fn test_insert_node_between() {
+        let mut graph = RenderGraph::default();
+        graph.add_node(TestLabel::A, TestNode::new(0, 0));
+        graph.add_node(TestLabel::B, TestNode::new(0, 0));
+        graph.add_node_edge(TestLabel::A, TestLabel::B);
+
+        graph
+            .insert_node_between(
+                TestLabel::A,
+                TestLabel::C,
+                TestNode::new(0, 0),
+                TestLabel::B,
+            )
+            .unwrap();
+
+        assert_eq!(
+            output_nodes(TestLabel::A, &graph),
+            HashSet::from_iter((TestLabel::C,).into_array()),
+            "A -> C"
+        );
+        assert_eq!(
+            output_nodes(TestLabel::C, &graph),
+            HashSet::from_iter((TestLabel::B,).into_array()),
+            "C -> B"
+        );
+        assert_eq!(
+            input_nodes(TestLabel::B, &graph),
+            HashSet::from_iter((TestLabel::C,).into_array()),
+            "the A -> B edge was replaced"
+        );
+
+        assert_eq!(
+            graph.insert_node_between(
+                TestLabel::A,
+                TestLabel::D,
+                TestNode::new(0, 0),
+                TestLabel::D
+            ),
+            Err(RenderGraphError::InvalidNode(TestLabel::D.intern())),
+            "Inserting before a node that does not exist should return an error"
+        );
+        assert!(
+            graph.get_node_state(TestLabel::D).is_err(),
+            "A failed insertion should not add the node"
+        );
+    }
+
+    #[test]
+    fn test_slot_types() {
+        let mut graph = RenderGraph::default();
+        graph.add_node(TestLabel::A, TestNode::new(1, 1));
+
+        assert_eq!(
+            graph.get_input_slot_type(TestLabel::A, "in_0"),
+            Ok(SlotType::TextureView)
+        );
+        assert_eq!(
+            graph.get_output_slot_type(TestLabel::A, 0),
+            Ok(SlotType::TextureView)
+        );
+        assert_eq!(
+            graph.get_output_slot_type(TestLabel::A, 1),
+            Err(RenderGraphError::InvalidOutputNodeSlot(SlotLabel::Index(1)))
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut graph = RenderGraph::default();
+        graph.add_node(TestLabel::A, TestNode::new(0, 1));
+        graph.add_node(TestLabel::B, TestNode::new(1, 0));
+        graph.add_slot_edge(TestLabel::A, 0, TestLabel::B, 0);
+        assert_eq!(graph.validate(), Ok(()));
+
+        let mut sub_graph = RenderGraph::default();
+        sub_graph.add_node(TestLabel::A, TestNode::new(0, 0));
+        sub_graph.add_node(TestLabel::B, TestNode::new(0, 0));
+        sub_graph.add_node(TestLabel::C, TestNode::new(1, 0));
+        sub_graph.add_node_edges((TestLabel::A, TestLabel::B, TestLabel::A));
+        graph.add_sub_graph(TestSubGraph, sub_graph);
+
+        let report = graph.validate().unwrap_err();
+        assert!(report.errors.is_empty(), "the main graph is still valid");
+        assert_eq!(report.sub_graphs.len(), 1);
+
+        let (label, sub_graph_report) = &report.sub_graphs[0];
+        assert_eq!(*label, TestSubGraph.intern());
+        assert_eq!(sub_graph_report.errors.len(), 2);
+        assert!(sub_graph_report
+            .errors
+            .contains(&RenderGraphError::UnconnectedNodeInputSlot {
+                node: TestLabel::C.intern(),
+                input_slot: 0,
+            }));
+        let cycle = sub_graph_report
+            .errors
+            .iter()
+            .find_map(|error| match error {
+                RenderGraphError::NodeCycle(nodes) => Some(
+                    nodes
+                        .iter()
+                        .copied()
+                        .collect::<HashSet<InternedRenderLabel>>(),
+                ),
+                _ => None,
+            })
+            .expect("the cycle should be reported");
+        assert_eq!(
+            cycle,
+            HashSet::from_iter((TestLabel::A, TestLabel::B).into_array())
+        );
+    }
 }
@@ -51,4 +51,8 @@ pub enum RenderGraphError {
         input_slot: usize,
         occupied_by_node: InternedRenderLabel,
     },
+    #[error("edge {0:?} references a node that does not exist")]
+    DanglingEdge(Edge),
+    #[error("nodes {0:?} are part of or depend on a cycle")]
+    NodeCycle(Vec<InternedRenderLabel>),
 }
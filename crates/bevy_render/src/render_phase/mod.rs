@@ -48,6 +48,7 @@ use crate::{
 };
 use bevy_ecs::{
     prelude::*,
+    schedule::SystemConfigs,
     system::{lifetimeless::SRes, SystemParamItem},
 };
 use smallvec::SmallVec;
@@ -80,8 +81,10 @@ where
 
     /// The batchable bins themselves.
     ///
-    /// Each bin corresponds to a single batch set. For unbatchable entities,
-    /// prefer `unbatchable_values` instead.
+    /// Each bin corresponds to a single batch set, which is empty if the bin
+    /// was merged into the previous one (see
+    /// [`BinnedPhaseItem::can_merge_bins`]). For unbatchable entities, prefer
+    /// `unbatchable_values` instead.
     pub(crate) batchable_values: HashMap<BPI::BinKey, Vec<Entity>>,
 
     /// A list of `BinKey`s for unbatchable items.
@@ -283,6 +286,21 @@ where
     pub fn is_empty(&self) -> bool {
         self.batchable_keys.is_empty() && self.unbatchable_keys.is_empty()
     }
+
+    /// Adds `offset` to the instance indices of all batches and unbatchable
+    /// entities.
+    ///
+    /// This is used when a phase is batched with instance indices relative to
+    /// the start of its view's instances.
+    pub(crate) fn offset_instance_indices(&mut self, offset: u32) {
+        for batch in self.batch_sets.iter_mut().flatten() {
+            batch.instance_range =
+                (batch.instance_range.start + offset)..(batch.instance_range.end + offset);
+        }
+        for unbatchables in self.unbatchable_values.values_mut() {
+            unbatchables.buffer_indices.offset(offset);
+        }
+    }
 }
 
 impl<BPI> Default for BinnedRenderPhase<BPI>
@@ -305,10 +323,18 @@ where
 ///
 /// This is the version used when the pipeline supports GPU preprocessing: e.g.
 /// 3D PBR meshes.
-pub struct BinnedRenderPhasePlugin<BPI, GFBD>(PhantomData<(BPI, GFBD)>)
+///
+/// By default, the views are batched one after another. Use
+/// [`BinnedRenderPhasePlugin::parallel`] to batch them in parallel when GPU
+/// preprocessing is in use.
+pub struct BinnedRenderPhasePlugin<BPI, GFBD>
 where
     BPI: BinnedPhaseItem,
-    GFBD: GetFullBatchData;
+    GFBD: GetFullBatchData,
+{
+    gpu_preprocessing_batching: fn() -> SystemConfigs,
+    phantom: PhantomData<(BPI, GFBD)>,
+}
 
 impl<BPI, GFBD> Default for BinnedRenderPhasePlugin<BPI, GFBD>
 where
@@ -316,7 +342,34 @@ where
     GFBD: GetFullBatchData,
 {
     fn default() -> Self {
-        Self(PhantomData)
+        Self {
+            gpu_preprocessing_batching: || {
+                gpu_preprocessing::batch_and_prepare_binned_render_phase::<BPI, GFBD>.into_configs()
+            },
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<BPI, GFBD> BinnedRenderPhasePlugin<BPI, GFBD>
+where
+    BPI: BinnedPhaseItem,
+    GFBD: GetFullBatchData,
+{
+    /// Batches the views in parallel when GPU preprocessing is in use, which
+    /// requires the items of the [`GetBatchData::Param`](crate::batching::GetBatchData::Param)
+    /// of `GFBD` to be [`Sync`].
+    pub fn parallel() -> Self
+    where
+        for<'w, 's> SystemParamItem<'w, 's, GFBD::Param>: Sync,
+    {
+        Self {
+            gpu_preprocessing_batching: || {
+                gpu_preprocessing::par_batch_and_prepare_binned_render_phase::<BPI, GFBD>
+                    .into_configs()
+            },
+            phantom: PhantomData,
+        }
     }
 }
 
@@ -337,7 +390,7 @@ where
                 (
                     no_gpu_preprocessing::batch_and_prepare_binned_render_phase::<BPI, GFBD>
                         .run_if(resource_exists::<BatchedInstanceBuffer<GFBD::BufferData>>),
-                    gpu_preprocessing::batch_and_prepare_binned_render_phase::<BPI, GFBD>.run_if(
+                    (self.gpu_preprocessing_batching)().run_if(
                         resource_exists::<
                             BatchedInstanceBuffers<GFBD::BufferData, GFBD::BufferInputData>,
                         >,
@@ -354,18 +407,53 @@ where
 ///
 /// This is the version used when the pipeline supports GPU preprocessing: e.g.
 /// 3D PBR meshes.
-pub struct SortedRenderPhasePlugin<SPI, GFBD>(PhantomData<(SPI, GFBD)>)
+///
+/// By default, the views are batched one after another. Use
+/// [`SortedRenderPhasePlugin::parallel`] to batch them in parallel when GPU
+/// preprocessing is in use.
+pub struct SortedRenderPhasePlugin<SPI, GFBD>
 where
     SPI: SortedPhaseItem,
-    GFBD: GetFullBatchData;
+    GFBD: GetFullBatchData,
+{
+    gpu_preprocessing_batching: fn() -> SystemConfigs,
+    phantom: PhantomData<(SPI, GFBD)>,
+}
 
 impl<SPI, GFBD> Default for SortedRenderPhasePlugin<SPI, GFBD>
 where
-    SPI: SortedPhaseItem,
+    SPI: SortedPhaseItem + CachedRenderPipelinePhaseItem,
     GFBD: GetFullBatchData,
 {
     fn default() -> Self {
-        Self(PhantomData)
+        Self {
+            gpu_preprocessing_batching: || {
+                gpu_preprocessing::batch_and_prepare_sorted_render_phase::<SPI, GFBD>.into_configs()
+            },
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<SPI, GFBD> SortedRenderPhasePlugin<SPI, GFBD>
+where
+    SPI: SortedPhaseItem + CachedRenderPipelinePhaseItem,
+    GFBD: GetFullBatchData,
+{
+    /// Batches the views in parallel when GPU preprocessing is in use, which
+    /// requires the items of the [`GetBatchData::Param`](crate::batching::GetBatchData::Param)
+    /// of `GFBD` to be [`Sync`].
+    pub fn parallel() -> Self
+    where
+        for<'w, 's> SystemParamItem<'w, 's, GFBD::Param>: Sync,
+    {
+        Self {
+            gpu_preprocessing_batching: || {
+                gpu_preprocessing::par_batch_and_prepare_sorted_render_phase::<SPI, GFBD>
+                    .into_configs()
+            },
+            phantom: PhantomData,
+        }
     }
 }
 
@@ -384,7 +472,7 @@ where
             (
                 no_gpu_preprocessing::batch_and_prepare_sorted_render_phase::<SPI, GFBD>
                     .run_if(resource_exists::<BatchedInstanceBuffer<GFBD::BufferData>>),
-                gpu_preprocessing::batch_and_prepare_sorted_render_phase::<SPI, GFBD>.run_if(
+                (self.gpu_preprocessing_batching)().run_if(
                     resource_exists::<
                         BatchedInstanceBuffers<GFBD::BufferData, GFBD::BufferInputData>,
                     >,
@@ -396,6 +484,21 @@ where
 }
 
 impl UnbatchableBinnedEntityBufferIndex {
+    /// Adds `offset` to the instance indices of all entities.
+    fn offset(&mut self, offset: u32) {
+        match self {
+            UnbatchableBinnedEntityBufferIndex::NoEntities => {}
+            UnbatchableBinnedEntityBufferIndex::NoDynamicOffsets { instance_range } => {
+                *instance_range = (instance_range.start + offset)..(instance_range.end + offset);
+            }
+            UnbatchableBinnedEntityBufferIndex::DynamicOffsets(dynamic_offsets) => {
+                for dynamic_offset in dynamic_offsets {
+                    dynamic_offset.instance_index += offset;
+                }
+            }
+        }
+    }
+
     /// Adds a new entity to the list of unbatchable binned entities.
     pub fn add<T>(&mut self, gpu_array_buffer_index: GpuArrayBufferIndex<T>)
     where
@@ -635,6 +738,21 @@ pub trait BinnedPhaseItem: PhaseItem {
         batch_range: Range<u32>,
         dynamic_offset: Option<NonMaxU32>,
     ) -> Self;
+
+    /// Returns `true` if the items of two adjacent bins can be drawn as a
+    /// single batch.
+    ///
+    /// This is the case when the keys only differ in data that doesn't affect
+    /// the draw, so that both bins use the same pipeline, bind groups and mesh.
+    /// The merged batch is drawn with the key of the first bin, and the bins
+    /// that were merged into it have no batches of their own.
+    ///
+    /// The default implementation returns `false`, as bin keys usually only
+    /// contain data that affects the draw.
+    fn can_merge_bins(previous: &Self::BinKey, next: &Self::BinKey) -> bool {
+        let _ = (previous, next);
+        false
+    }
 }
 
 /// Represents phase items that must be sorted. The `SortKey` specifies the
@@ -46,6 +46,8 @@ pub struct BatchedUniformBuffer<T: GpuArrayBufferable> {
     temp: MaxCapacityArray<Vec<T>>,
     current_offset: u32,
     dynamic_offset_alignment: u32,
+    // The number of T pushed since the buffer was last cleared.
+    len: usize,
 }
 
 impl<T: GpuArrayBufferable> BatchedUniformBuffer<T> {
@@ -65,6 +67,7 @@ impl<T: GpuArrayBufferable> BatchedUniformBuffer<T> {
             temp: MaxCapacityArray(Vec::with_capacity(capacity), capacity),
             current_offset: 0,
             dynamic_offset_alignment: alignment,
+            len: 0,
         }
     }
 
@@ -73,10 +76,31 @@ impl<T: GpuArrayBufferable> BatchedUniformBuffer<T> {
         self.temp.size()
     }
 
+    /// Returns the number of elements pushed since the buffer was last cleared.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     pub fn clear(&mut self) {
         self.uniforms.clear();
         self.current_offset = 0;
         self.temp.0.clear();
+        self.len = 0;
+    }
+
+    /// Shrinks the memory held by the buffer to the batches needed for
+    /// `capacity` elements, or to the size of the data if it's larger.
+    pub fn shrink_to(&mut self, capacity: usize) {
+        let batch_count = capacity.div_ceil(self.temp.1.max(1)) as u64;
+        let batch_size =
+            align_to_next(self.temp.size().get(), self.dynamic_offset_alignment as u64);
+        self.uniforms.shrink_to(batch_count * batch_size);
     }
 
     pub fn push(&mut self, component: T) -> GpuArrayBufferIndex<T> {
@@ -86,6 +110,7 @@ impl<T: GpuArrayBufferable> BatchedUniformBuffer<T> {
             element_type: PhantomData,
         };
         self.temp.0.push(component);
+        self.len += 1;
         if self.temp.0.len() == self.temp.1 {
             self.flush();
         }
@@ -0,0 +1,218 @@
+use super::{
+    binding_types::{storage_buffer_read_only, texture_2d},
+    BindGroupLayoutEntryBuilder, GpuArrayBufferable, StorageBuffer, TextureView, TextureViewId,
+};
+use crate::renderer::{RenderDevice, RenderQueue};
+use bevy_utils::HashMap;
+use std::num::NonZeroU32;
+use wgpu::{BindingResource, Features, TextureSampleType};
+
+/// Returns `true` if `render_device` supports bindless resources.
+///
+/// Bindless resources let a shader index into arrays of textures and material data
+/// with an index that can differ between the instances of a draw, so that draws
+/// using different materials don't need different bind groups. This requires
+/// texture binding arrays, non-uniform indexing and storage buffers.
+pub fn bindless_supported(render_device: &RenderDevice) -> bool {
+    render_device.features().contains(
+        Features::TEXTURE_BINDING_ARRAY
+            | Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING,
+    ) && render_device.limits().max_storage_buffers_per_shader_stage > 0
+}
+
+/// A table of material data in a storage buffer, indexed by shaders with the slot of
+/// each material.
+///
+/// Slots of removed values are reused by later insertions, so slots stay stable for
+/// as long as their value is in the table.
+pub struct MaterialTable<T: GpuArrayBufferable> {
+    buffer: StorageBuffer<Vec<T>>,
+    free_slots: Vec<u32>,
+}
+
+impl<T: GpuArrayBufferable> MaterialTable<T> {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self {
+            buffer: StorageBuffer::default(),
+            free_slots: Vec::new(),
+        }
+    }
+
+    /// Adds `value` to the table and returns its slot.
+    pub fn insert(&mut self, value: T) -> u32 {
+        let values = self.buffer.get_mut();
+        match self.free_slots.pop() {
+            Some(slot) => {
+                values[slot as usize] = value;
+                slot
+            }
+            None => {
+                values.push(value);
+                values.len() as u32 - 1
+            }
+        }
+    }
+
+    /// Replaces the value in `slot`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slot` was not returned by [`insert`](Self::insert).
+    pub fn set(&mut self, slot: u32, value: T) {
+        self.buffer.get_mut()[slot as usize] = value;
+    }
+
+    /// Returns the value in `slot`, if it is in use.
+    pub fn get(&self, slot: u32) -> Option<&T> {
+        if self.free_slots.contains(&slot) {
+            return None;
+        }
+        self.buffer.get().get(slot as usize)
+    }
+
+    /// Frees `slot`, so that it can be reused by a later insertion.
+    ///
+    /// The value stays in the buffer until then, as other slots can't move.
+    pub fn remove(&mut self, slot: u32) {
+        if (slot as usize) < self.buffer.get().len() && !self.free_slots.contains(&slot) {
+            self.free_slots.push(slot);
+        }
+    }
+
+    /// Returns the number of values in the table.
+    pub fn len(&self) -> usize {
+        self.buffer.get().len() - self.free_slots.len()
+    }
+
+    /// Returns `true` if the table has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Queues writing the table to the GPU.
+    pub fn write_buffer(&mut self, device: &RenderDevice, queue: &RenderQueue) {
+        self.buffer.write_buffer(device, queue);
+    }
+
+    /// Returns the binding of the table, once it has been written to the GPU.
+    pub fn binding(&self) -> Option<BindingResource> {
+        self.buffer.binding()
+    }
+
+    /// Returns the layout entry of a binding of the table.
+    pub fn binding_layout() -> BindGroupLayoutEntryBuilder {
+        storage_buffer_read_only::<T>(false)
+    }
+}
+
+impl<T: GpuArrayBufferable> Default for MaterialTable<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An array of 2D textures bound at once, indexed by shaders.
+///
+/// The array has a fixed capacity, which is the size of its binding in the bind group
+/// layout. Each texture view is only stored once.
+pub struct BindlessTextures {
+    views: Vec<TextureView>,
+    indices: HashMap<TextureViewId, u32>,
+    capacity: NonZeroU32,
+}
+
+impl BindlessTextures {
+    /// Creates an empty array that can hold up to `capacity` textures.
+    pub fn new(capacity: NonZeroU32) -> Self {
+        Self {
+            views: Vec::new(),
+            indices: HashMap::default(),
+            capacity,
+        }
+    }
+
+    /// Adds `view` to the array, if it isn't in it already, and returns its index.
+    ///
+    /// Returns `None` if the array is full.
+    pub fn insert(&mut self, view: &TextureView) -> Option<u32> {
+        if let Some(&index) = self.indices.get(&view.id()) {
+            return Some(index);
+        }
+        let index = self.views.len() as u32;
+        if index >= self.capacity.get() {
+            return None;
+        }
+        self.views.push(view.clone());
+        self.indices.insert(view.id(), index);
+        Some(index)
+    }
+
+    /// Returns the index of `view` in the array, if it is in it.
+    pub fn index(&self, view: &TextureView) -> Option<u32> {
+        self.indices.get(&view.id()).copied()
+    }
+
+    /// Returns the number of textures in the array.
+    pub fn len(&self) -> usize {
+        self.views.len()
+    }
+
+    /// Returns `true` if the array has no textures.
+    pub fn is_empty(&self) -> bool {
+        self.views.is_empty()
+    }
+
+    /// Returns the maximum number of textures in the array.
+    pub fn capacity(&self) -> NonZeroU32 {
+        self.capacity
+    }
+
+    /// Removes all the textures.
+    pub fn clear(&mut self) {
+        self.views.clear();
+        self.indices.clear();
+    }
+
+    /// Returns the texture views to bind, with `fallback` in the unused elements, as
+    /// every element of the binding must be filled.
+    ///
+    /// The binding is created from a slice of the returned views, with
+    /// [`BindingResource::TextureViewArray`].
+    pub fn texture_views<'a>(&'a self, fallback: &'a TextureView) -> Vec<&'a wgpu::TextureView> {
+        let mut views: Vec<&wgpu::TextureView> = self.views.iter().map(|view| &**view).collect();
+        views.resize(self.capacity.get() as usize, &**fallback);
+        views
+    }
+
+    /// Returns the layout entry of the binding of the array, for filterable float textures.
+    pub fn binding_layout(&self) -> BindGroupLayoutEntryBuilder {
+        texture_2d(TextureSampleType::Float { filterable: true }).count(self.capacity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MaterialTable;
+
+    #[test]
+    fn material_table_reuses_slots() {
+        let mut table = MaterialTable::<u32>::new();
+        let a = table.insert(1);
+        let b = table.insert(2);
+        assert_ne!(a, b);
+        assert_eq!(table.len(), 2);
+
+        table.remove(a);
+        assert_eq!(table.get(a), None);
+        assert_eq!(table.len(), 1);
+
+        let c = table.insert(3);
+        assert_eq!(c, a);
+        assert_eq!(table.get(c), Some(&3));
+        assert_eq!(table.get(b), Some(&2));
+
+        table.set(b, 4);
+        assert_eq!(table.get(b), Some(&4));
+    }
+}
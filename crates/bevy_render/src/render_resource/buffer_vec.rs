@@ -149,6 +149,20 @@ impl<T: NoUninit> BufferVec<T> {
         self.values.clear();
     }
 
+    /// Shrinks the memory held by the buffer, on both the CPU and the GPU, to
+    /// `capacity` elements, or to the number of elements if it's larger.
+    ///
+    /// If the GPU-side buffer is larger than that, it's released, and a new one
+    /// is created on the next [`reserve`](BufferVec::reserve).
+    pub fn shrink_to(&mut self, capacity: usize) {
+        let capacity = capacity.max(self.values.len());
+        self.values.shrink_to(capacity);
+        if self.capacity > capacity {
+            self.buffer = None;
+            self.capacity = 0;
+        }
+    }
+
     pub fn values(&self) -> &Vec<T> {
         &self.values
     }
@@ -218,6 +232,19 @@ where
         index
     }
 
+    /// Reserves space for `count` more elements in the buffer and returns the
+    /// index of the first one.
+    pub fn add_multiple(&mut self, count: usize) -> usize {
+        let index = self.len;
+        self.len += count;
+        index
+    }
+
+    /// Returns the number of elements added to this [`UninitBufferVec`].
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
     /// Returns true if no elements have been added to this [`UninitBufferVec`].
     pub fn is_empty(&self) -> bool {
         self.len == 0
@@ -228,6 +255,18 @@ where
         self.len = 0;
     }
 
+    /// Shrinks the GPU-side buffer to `capacity` elements, or to the number of
+    /// elements if it's larger.
+    ///
+    /// If the buffer is larger than that, it's released, and a new one is
+    /// created on the next [`reserve`](UninitBufferVec::reserve).
+    pub fn shrink_to(&mut self, capacity: usize) {
+        if self.capacity > capacity.max(self.len) {
+            self.buffer = None;
+            self.capacity = 0;
+        }
+    }
+
     /// Materializes the buffer on the GPU with space for `capacity` elements.
     ///
     /// If the buffer is already big enough, this function doesn't reallocate
@@ -45,6 +45,19 @@ impl<T: GpuArrayBufferable> GpuArrayBuffer<T> {
         }
     }
 
+    /// Returns the number of elements pushed since the buffer was last cleared.
+    pub fn len(&self) -> usize {
+        match self {
+            GpuArrayBuffer::Uniform(buffer) => buffer.len(),
+            GpuArrayBuffer::Storage(buffer) => buffer.get().len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clears the buffer, keeping the memory allocated for its elements.
     pub fn clear(&mut self) {
         match self {
             GpuArrayBuffer::Uniform(buffer) => buffer.clear(),
@@ -52,6 +65,22 @@ impl<T: GpuArrayBufferable> GpuArrayBuffer<T> {
         }
     }
 
+    /// Shrinks the memory held by the buffer, on both the CPU and the GPU, to
+    /// `capacity` elements, or to the number of elements if it's larger.
+    ///
+    /// The GPU-side buffer is reallocated on the next
+    /// [`write_buffer`](GpuArrayBuffer::write_buffer).
+    pub fn shrink_to(&mut self, capacity: usize) {
+        match self {
+            GpuArrayBuffer::Uniform(buffer) => buffer.shrink_to(capacity),
+            GpuArrayBuffer::Storage(buffer) => {
+                buffer.get_mut().shrink_to(capacity);
+                let capacity = capacity.max(buffer.get().len());
+                buffer.shrink_to(capacity as u64 * T::min_size().get());
+            }
+        }
+    }
+
     pub fn push(&mut self, value: T) -> GpuArrayBufferIndex<T> {
         match self {
             GpuArrayBuffer::Uniform(buffer) => buffer.push(value),
@@ -1,5 +1,5 @@
 use crate::renderer::RenderAdapter;
-use crate::{render_resource::*, renderer::RenderDevice, Extract};
+use crate::{render_resource::*, renderer::RenderDevice, Extract, MainWorld};
 use bevy_asset::{AssetEvent, AssetId, Assets};
 use bevy_ecs::system::{Res, ResMut};
 use bevy_ecs::{event::EventReader, system::Resource};
@@ -7,7 +7,7 @@ use bevy_tasks::Task;
 use bevy_utils::hashbrown::hash_map::EntryRef;
 use bevy_utils::{
     default,
-    tracing::{debug, error},
+    tracing::{debug, error, warn},
     HashMap, HashSet,
 };
 use naga::valid::Capabilities;
@@ -16,7 +16,7 @@ use std::{
     future::Future,
     hash::Hash,
     mem,
-    ops::Deref,
+    ops::{Deref, Range},
     sync::{Arc, Mutex, PoisonError},
 };
 use thiserror::Error;
@@ -308,6 +308,9 @@ impl ShaderCache {
                             "Enable feature \"shader_format_spirv\" to use SPIR-V shaders"
                         )
                     }
+                    Source::Naga(module) => {
+                        wgpu::ShaderSource::Naga(Cow::Owned(module.as_ref().clone()))
+                    }
                     _ => {
                         for import in shader.imports() {
                             Self::add_import_to_composer(
@@ -491,6 +494,15 @@ pub struct PipelineCache {
     /// If `true`, disables asynchronous pipeline compilation.
     /// This has no effect on MacOS, wasm, or without the `multi_threaded` feature.
     synchronous_pipeline_compilation: bool,
+    /// See [`PipelineCache::set_max_pipeline_compilations_per_frame`].
+    max_pipeline_compilations_per_frame: Option<usize>,
+    /// Pipelines that were working before one of their shaders changed, kept around until the
+    /// new version is created successfully so that a broken hot-reload doesn't stop rendering.
+    last_good_pipelines: HashMap<CachedPipelineId, Pipeline>,
+    /// The shader errors of the pipelines that currently fail, see [`ShaderErrors`].
+    shader_errors: HashMap<CachedPipelineId, ShaderError>,
+    /// Whether `shader_errors` changed since it was last copied to [`ShaderErrors`].
+    shader_errors_changed: bool,
 }
 
 impl PipelineCache {
@@ -518,9 +530,32 @@ impl PipelineCache {
             new_pipelines: default(),
             pipelines: default(),
             synchronous_pipeline_compilation,
+            max_pipeline_compilations_per_frame: None,
+            last_good_pipelines: default(),
+            shader_errors: default(),
+            shader_errors_changed: false,
         }
     }
 
+    /// Returns the maximum number of pipelines whose creation is started each frame.
+    ///
+    /// See [`PipelineCache::set_max_pipeline_compilations_per_frame`].
+    pub fn max_pipeline_compilations_per_frame(&self) -> Option<usize> {
+        self.max_pipeline_compilations_per_frame
+    }
+
+    /// Limits the number of pipelines whose creation is started each frame, or removes the
+    /// limit with `None`, which is the default.
+    ///
+    /// Pipelines over the budget stay queued and are started on a later frame, oldest first.
+    /// Where compilation is synchronous (macOS, Wasm, or without the `multi_threaded` feature),
+    /// this bounds how long a frame can stall when many new pipelines show up at once, e.g.
+    /// when new materials come into view. Elsewhere it bounds how many compilation tasks are
+    /// spawned per frame. Entities whose pipeline isn't ready yet are skipped when drawing.
+    pub fn set_max_pipeline_compilations_per_frame(&mut self, max: Option<usize>) {
+        self.max_pipeline_compilations_per_frame = max;
+    }
+
     /// Get the state of a cached render pipeline.
     ///
     /// See [`PipelineCache::queue_render_pipeline()`].
@@ -572,11 +607,12 @@ impl PipelineCache {
     /// This method returns a successfully created render pipeline if any, or `None` if the pipeline
     /// was not created yet or if there was an error during creation. You can check the actual creation
     /// state with [`PipelineCache::get_render_pipeline_state()`].
+    ///
+    /// While a pipeline is being recreated because one of its shaders changed, or if recreating it
+    /// failed, this returns the last version that was created successfully.
     #[inline]
     pub fn get_render_pipeline(&self, id: CachedRenderPipelineId) -> Option<&RenderPipeline> {
-        if let CachedPipelineState::Ok(Pipeline::RenderPipeline(pipeline)) =
-            &self.pipelines[id.0].state
-        {
+        if let Some(Pipeline::RenderPipeline(pipeline)) = self.get_pipeline(id.0) {
             Some(pipeline)
         } else {
             None
@@ -593,7 +629,10 @@ impl PipelineCache {
         let state = &mut self.pipelines[id.0].state;
         if let CachedPipelineState::Creating(task) = state {
             *state = match bevy_tasks::block_on(task) {
-                Ok(p) => CachedPipelineState::Ok(p),
+                Ok(p) => {
+                    self.last_good_pipelines.remove(&id.0);
+                    CachedPipelineState::Ok(p)
+                }
                 Err(e) => CachedPipelineState::Err(e),
             };
         }
@@ -606,17 +645,25 @@ impl PipelineCache {
     /// This method returns a successfully created compute pipeline if any, or `None` if the pipeline
     /// was not created yet or if there was an error during creation. You can check the actual creation
     /// state with [`PipelineCache::get_compute_pipeline_state()`].
+    ///
+    /// While a pipeline is being recreated because one of its shaders changed, or if recreating it
+    /// failed, this returns the last version that was created successfully.
     #[inline]
     pub fn get_compute_pipeline(&self, id: CachedComputePipelineId) -> Option<&ComputePipeline> {
-        if let CachedPipelineState::Ok(Pipeline::ComputePipeline(pipeline)) =
-            &self.pipelines[id.0].state
-        {
+        if let Some(Pipeline::ComputePipeline(pipeline)) = self.get_pipeline(id.0) {
             Some(pipeline)
         } else {
             None
         }
     }
 
+    fn get_pipeline(&self, id: CachedPipelineId) -> Option<&Pipeline> {
+        match &self.pipelines[id].state {
+            CachedPipelineState::Ok(pipeline) => Some(pipeline),
+            _ => self.last_good_pipelines.get(&id),
+        }
+    }
+
     /// Insert a render pipeline into the cache, and queue its creation.
     ///
     /// The pipeline is always inserted and queued for creation. There is no attempt to deduplicate it with
@@ -679,17 +726,24 @@ impl PipelineCache {
         let mut shader_cache = self.shader_cache.lock().unwrap();
         let pipelines_to_queue = shader_cache.set_shader(id, shader.clone());
         for cached_pipeline in pipelines_to_queue {
-            self.pipelines[cached_pipeline].state = CachedPipelineState::Queued;
+            let state = mem::replace(
+                &mut self.pipelines[cached_pipeline].state,
+                CachedPipelineState::Queued,
+            );
+            if let CachedPipelineState::Ok(pipeline) = state {
+                self.last_good_pipelines.insert(cached_pipeline, pipeline);
+            }
             self.waiting_pipelines.insert(cached_pipeline);
         }
     }
 
     fn remove_shader(&mut self, shader: AssetId<Shader>) {
-        let mut shader_cache = self.shader_cache.lock().unwrap();
-        let pipelines_to_queue = shader_cache.remove(shader);
+        let pipelines_to_queue = self.shader_cache.lock().unwrap().remove(shader);
         for cached_pipeline in pipelines_to_queue {
             self.pipelines[cached_pipeline].state = CachedPipelineState::Queued;
+            self.last_good_pipelines.remove(&cached_pipeline);
             self.waiting_pipelines.insert(cached_pipeline);
+            self.clear_shader_error(cached_pipeline);
         }
     }
 
@@ -865,16 +919,33 @@ impl PipelineCache {
             }
         }
 
+        let mut compilation_budget = self
+            .max_pipeline_compilations_per_frame
+            .unwrap_or(usize::MAX);
+        let mut waiting_pipelines: Vec<_> = waiting_pipelines.into_iter().collect();
+        // Start with the oldest pipelines so that a compilation budget can't starve any of them.
+        waiting_pipelines.sort_unstable();
         for id in waiting_pipelines {
-            self.process_pipeline(&mut pipelines[id], id);
+            self.process_pipeline(&mut pipelines[id], id, &mut compilation_budget);
         }
 
         self.pipelines = pipelines;
     }
 
-    fn process_pipeline(&mut self, cached_pipeline: &mut CachedPipeline, id: usize) {
+    fn process_pipeline(
+        &mut self,
+        cached_pipeline: &mut CachedPipeline,
+        id: usize,
+        compilation_budget: &mut usize,
+    ) {
         match &mut cached_pipeline.state {
             CachedPipelineState::Queued => {
+                if *compilation_budget == 0 {
+                    // Over budget for this frame, start it on a later one.
+                    self.waiting_pipelines.insert(id);
+                    return;
+                }
+                *compilation_budget -= 1;
                 cached_pipeline.state = match &cached_pipeline.descriptor {
                     PipelineDescriptor::RenderPipelineDescriptor(descriptor) => {
                         self.start_create_render_pipeline(id, *descriptor.clone())
@@ -889,6 +960,8 @@ impl PipelineCache {
                 match bevy_utils::futures::check_ready(task) {
                     Some(Ok(pipeline)) => {
                         cached_pipeline.state = CachedPipelineState::Ok(pipeline);
+                        self.last_good_pipelines.remove(&id);
+                        self.clear_shader_error(id);
                         return;
                     }
                     Some(Err(err)) => cached_pipeline.state = CachedPipelineState::Err(err),
@@ -905,13 +978,27 @@ impl PipelineCache {
 
                 // Shader could not be processed ... retrying won't help
                 PipelineCacheError::ProcessShaderError(err) => {
-                    let error_detail =
-                        err.emit_to_string(&self.shader_cache.lock().unwrap().composer);
-                    error!("failed to process shader:\n{}", error_detail);
+                    let shader_error = {
+                        let shader_cache = self.shader_cache.lock().unwrap();
+                        ShaderError::from_composer_error(err, &shader_cache.composer)
+                    };
+                    error!("failed to process shader:\n{}", shader_error.message);
+                    self.warn_if_keeping_last_good_pipeline(id);
+                    self.set_shader_error(id, shader_error);
                     return;
                 }
                 PipelineCacheError::CreateShaderModule(description) => {
                     error!("failed to create shader module: {}", description);
+                    self.warn_if_keeping_last_good_pipeline(id);
+                    self.set_shader_error(
+                        id,
+                        ShaderError {
+                            path: None,
+                            line: None,
+                            span: None,
+                            message: description.clone(),
+                        },
+                    );
                     return;
                 }
             },
@@ -923,10 +1010,51 @@ impl PipelineCache {
         self.waiting_pipelines.insert(id);
     }
 
+    fn warn_if_keeping_last_good_pipeline(&self, id: CachedPipelineId) {
+        if self.last_good_pipelines.contains_key(&id) {
+            warn!(
+                "keeping the last working version of pipeline {} until its shaders are fixed",
+                id
+            );
+        }
+    }
+
+    fn set_shader_error(&mut self, id: CachedPipelineId, shader_error: ShaderError) {
+        self.shader_errors.insert(id, shader_error);
+        self.shader_errors_changed = true;
+    }
+
+    fn clear_shader_error(&mut self, id: CachedPipelineId) {
+        if self.shader_errors.remove(&id).is_some() {
+            self.shader_errors_changed = true;
+        }
+    }
+
     pub(crate) fn process_pipeline_queue_system(mut cache: ResMut<Self>) {
         cache.process_queue();
     }
 
+    /// Copies the shader errors to the [`ShaderErrors`] of the main world when they changed.
+    pub(crate) fn extract_shader_errors(
+        mut cache: ResMut<Self>,
+        mut main_world: ResMut<MainWorld>,
+    ) {
+        if !cache.shader_errors_changed {
+            return;
+        }
+        let Some(mut shader_errors) = main_world.get_resource_mut::<ShaderErrors>() else {
+            return;
+        };
+        cache.shader_errors_changed = false;
+
+        let mut errors: Vec<_> = cache.shader_errors.iter().collect();
+        errors.sort_unstable_by_key(|(id, _)| **id);
+        shader_errors.0 = errors
+            .into_iter()
+            .map(|(_, shader_error)| shader_error.clone())
+            .collect();
+    }
+
     pub(crate) fn extract_shaders(
         mut cache: ResMut<Self>,
         shaders: Extract<Res<Assets<Shader>>>,
@@ -985,6 +1113,109 @@ fn create_pipeline_task(
     }
 }
 
+/// The errors of the shaders that currently fail to compile, e.g. for displaying them in an
+/// overlay while hot-reloading shaders.
+///
+/// This resource lives in the main world. An error is added when a pipeline fails to be
+/// created because of one of its shaders, and removed once the pipeline is created
+/// successfully or the shader is removed.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct ShaderErrors(Vec<ShaderError>);
+
+impl ShaderErrors {
+    /// Returns an iterator over the shader errors, in the order the failing pipelines were
+    /// queued.
+    pub fn iter(&self) -> impl Iterator<Item = &ShaderError> {
+        self.0.iter()
+    }
+
+    /// Returns the number of shader errors.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no shader currently fails to compile.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// An error in a shader, see [`ShaderErrors`].
+#[derive(Clone, Debug)]
+pub struct ShaderError {
+    /// The path of the shader file the error is in, if known.
+    pub path: Option<String>,
+    /// The line of the error in the shader file, starting at 1, if known.
+    pub line: Option<usize>,
+    /// The byte range of the error in the shader source after preprocessing, if known.
+    pub span: Option<Range<usize>>,
+    /// The description of the error, including the annotated source when available.
+    pub message: String,
+}
+
+/// The number of low bits of the spans `naga_oil` gives to naga that hold the source offset.
+/// The higher bits hold the index of the module the span belongs to.
+///
+/// This mirrors the private `SPAN_SHIFT` constant in `src/compose/mod.rs` of `naga_oil` 0.13,
+/// which has no public API to map these spans back to the source. Check it when updating `naga_oil`.
+const NAGA_OIL_SPAN_SHIFT: usize = 21;
+
+impl ShaderError {
+    fn from_composer_error(
+        err: &naga_oil::compose::ComposerError,
+        composer: &naga_oil::compose::Composer,
+    ) -> Self {
+        use naga_oil::compose::ComposerErrorInner;
+
+        // The spans also include the header `naga_oil` prepends to the source.
+        const SPAN_MASK: usize = (1 << NAGA_OIL_SPAN_SHIFT) - 1;
+        let offset = err.source.offset();
+        let map_span = |span: naga::Span| {
+            span.to_range().map(|range| {
+                (range.start & SPAN_MASK).saturating_sub(offset)
+                    ..(range.end & SPAN_MASK).saturating_sub(offset)
+            })
+        };
+
+        let span = match &err.inner {
+            ComposerErrorInner::WgslParseError(e) => {
+                e.labels().next().and_then(|(span, _)| map_span(span))
+            }
+            ComposerErrorInner::HeaderValidationError(e)
+            | ComposerErrorInner::ShaderValidationError(e) => {
+                e.spans().next().and_then(|(span, _)| map_span(*span))
+            }
+            ComposerErrorInner::InvalidIdentifier { at, .. } => map_span(*at),
+            ComposerErrorInner::DecorationInSource(range) => Some(range.clone()),
+            ComposerErrorInner::ImportParseError(_, pos)
+            | ComposerErrorInner::ImportNotFound(_, pos)
+            | ComposerErrorInner::NotEnoughEndIfs(pos)
+            | ComposerErrorInner::TooManyEndIfs(pos)
+            | ComposerErrorInner::ElseWithoutCondition(pos)
+            | ComposerErrorInner::UnknownShaderDef { pos, .. }
+            | ComposerErrorInner::UnknownShaderDefOperator { pos, .. }
+            | ComposerErrorInner::InvalidShaderDefComparisonValue { pos, .. }
+            | ComposerErrorInner::OverrideNotVirtual { pos, .. }
+            | ComposerErrorInner::GlslInvalidVersion(pos)
+            | ComposerErrorInner::DefineInModule(pos)
+            | ComposerErrorInner::InvalidShaderDefDefinitionValue { pos, .. } => Some(*pos..*pos),
+            _ => None,
+        };
+        let line = span.as_ref().and_then(|span| {
+            let source = err.source.source(composer);
+            let before_span = source.get(..span.start)?;
+            Some(before_span.matches('\n').count() + 1)
+        });
+
+        Self {
+            path: Some(err.source.path(composer).clone()),
+            line,
+            span,
+            message: err.emit_to_string(composer),
+        }
+    }
+}
+
 /// Type of error returned by a [`PipelineCache`] when the creation of a GPU pipeline object failed.
 #[derive(Error, Debug)]
 pub enum PipelineCacheError {
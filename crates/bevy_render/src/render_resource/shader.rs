@@ -1,11 +1,12 @@
-use super::ShaderDefVal;
+use super::{reflect_bind_group_layout_entries, ShaderDefVal};
 use crate::define_atomic_id;
 use bevy_asset::{io::Reader, Asset, AssetLoader, AssetPath, Handle, LoadContext};
 use bevy_reflect::TypePath;
 use bevy_utils::tracing::error;
 use futures_lite::AsyncReadExt;
-use std::{borrow::Cow, marker::Copy};
+use std::{borrow::Cow, collections::BTreeMap, marker::Copy};
 use thiserror::Error;
+use wgpu::BindGroupLayoutEntry;
 
 define_atomic_id!(ShaderId);
 
@@ -21,6 +22,12 @@ pub enum ShaderReflectError {
     SpirVParse(#[from] naga::front::spv::Error),
     #[error(transparent)]
     Validation(#[from] naga::WithSpan<naga::valid::ValidationError>),
+    #[error("shaders with imports or preprocessor directives can't be reflected")]
+    UnresolvedImports,
+    #[error("reflecting this kind of shader source is not supported")]
+    UnsupportedSource,
+    #[error("binding {binding} of group {group} has a type that can't be reflected")]
+    UnsupportedBinding { group: u32, binding: u32 },
 }
 /// A shader, as defined by its [`ShaderSource`](wgpu::ShaderSource) and [`ShaderStage`](naga::ShaderStage)
 /// This is an "unprocessed" shader. It can contain preprocessor directives.
@@ -125,6 +132,48 @@ impl Shader {
         }
     }
 
+    /// Creates a shader from a [`naga::Module`], e.g. one produced by another shader toolchain.
+    ///
+    /// Like SPIR-V shaders, these are passed to wgpu as is and can't import or be imported by
+    /// other shaders.
+    pub fn from_naga(module: naga::Module, path: impl Into<String>) -> Shader {
+        let path = path.into();
+        Shader {
+            path: path.clone(),
+            imports: Vec::new(),
+            import_path: ShaderImport::AssetPath(path),
+            source: Source::Naga(Box::new(module)),
+            additional_imports: Default::default(),
+            shader_defs: Default::default(),
+            file_dependencies: Default::default(),
+        }
+    }
+
+    /// Derives the bind group layout entries this shader declares, keyed by bind group index.
+    ///
+    /// This works for SPIR-V (with the `shader_format_spirv` feature) and [`naga::Module`] shaders,
+    /// and for WGSL shaders that don't use imports or other preprocessor directives.
+    /// See [`reflect_bind_group_layout_entries`] for the assumptions made.
+    pub fn reflect_bind_group_layout_entries(
+        &self,
+    ) -> Result<BTreeMap<u32, Vec<BindGroupLayoutEntry>>, ShaderReflectError> {
+        let module = match &self.source {
+            Source::Wgsl(source) => {
+                if !self.imports.is_empty() {
+                    return Err(ShaderReflectError::UnresolvedImports);
+                }
+                naga::front::wgsl::parse_str(source)?
+            }
+            #[cfg(feature = "shader_format_spirv")]
+            Source::SpirV(data) => {
+                naga::front::spv::parse_u8_slice(data, &naga::front::spv::Options::default())?
+            }
+            Source::Naga(module) => return reflect_bind_group_layout_entries(module),
+            _ => return Err(ShaderReflectError::UnsupportedSource),
+        };
+        reflect_bind_group_layout_entries(&module)
+    }
+
     pub fn set_import_path<P: Into<String>>(&mut self, import_path: P) {
         self.import_path = ShaderImport::Custom(import_path.into());
     }
@@ -195,9 +244,9 @@ pub enum Source {
     Wgsl(Cow<'static, str>),
     Glsl(Cow<'static, str>, naga::ShaderStage),
     SpirV(Cow<'static, [u8]>),
+    Naga(Box<naga::Module>),
     // TODO: consider the following
     // PrecompiledSpirVMacros(HashMap<HashSet<String>, Vec<u32>>)
-    // NagaModule(Module) ... Module impls Serialize/Deserialize
 }
 
 impl Source {
@@ -205,6 +254,7 @@ impl Source {
         match self {
             Source::Wgsl(s) | Source::Glsl(s, _) => s,
             Source::SpirV(_) => panic!("spirv not yet implemented"),
+            Source::Naga(_) => panic!("naga modules have no source string"),
         }
     }
 }
@@ -220,6 +270,7 @@ impl From<&Source> for naga_oil::compose::ShaderLanguage {
                 "GLSL is not supported in this configuration; use the feature `shader_format_glsl`"
             ),
             Source::SpirV(_) => panic!("spirv not yet implemented"),
+            Source::Naga(_) => panic!("naga modules can't be composed"),
         }
     }
 }
@@ -239,6 +290,7 @@ impl From<&Source> for naga_oil::compose::ShaderType {
                 "GLSL is not supported in this configuration; use the feature `shader_format_glsl`"
             ),
             Source::SpirV(_) => panic!("spirv not yet implemented"),
+            Source::Naga(_) => panic!("naga modules can't be composed"),
         }
     }
 }
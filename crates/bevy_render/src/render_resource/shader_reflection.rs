@@ -0,0 +1,268 @@
+use super::ShaderReflectError;
+use bevy_utils::tracing::warn;
+use naga::{
+    valid::{Capabilities, ValidationFlags, Validator},
+    AddressSpace, ArraySize, Handle, ImageClass, ImageDimension, ResourceBinding, ScalarKind,
+    StorageAccess, StorageFormat, Type, TypeInner,
+};
+use std::collections::BTreeMap;
+use wgpu::{
+    BindGroupLayoutEntry, BindingType, BufferBindingType, SamplerBindingType, ShaderStages,
+    StorageTextureAccess, TextureFormat, TextureSampleType, TextureViewDimension,
+};
+
+/// Derives the bind group layout entries declared by a shader module, keyed by bind group index.
+///
+/// This lets shaders that weren't written against Bevy's bind group layouts, such as SPIR-V
+/// produced by rust-gpu or glslang, be used in a [`RenderPipelineDescriptor`] by creating the
+/// layouts from the shader itself.
+///
+/// Each entry is visible to the stages of the entry points that use it, and bindings that no
+/// entry point uses are left out. Float textures are assumed to be filterable unless they are
+/// multisampled, samplers are assumed to be filtering, and buffers get no dynamic offset or
+/// minimum binding size, as none of these can be known from the shader alone.
+///
+/// [`RenderPipelineDescriptor`]: super::RenderPipelineDescriptor
+pub fn reflect_bind_group_layout_entries(
+    module: &naga::Module,
+) -> Result<BTreeMap<u32, Vec<BindGroupLayoutEntry>>, ShaderReflectError> {
+    let module_info =
+        Validator::new(ValidationFlags::all(), Capabilities::all()).validate(module)?;
+
+    let mut groups: BTreeMap<u32, Vec<BindGroupLayoutEntry>> = BTreeMap::new();
+    for (handle, global) in module.global_variables.iter() {
+        let Some(&ResourceBinding { group, binding }) = global.binding.as_ref() else {
+            continue;
+        };
+
+        let mut visibility = ShaderStages::NONE;
+        for (index, entry_point) in module.entry_points.iter().enumerate() {
+            if !module_info.get_entry_point(index)[handle].is_empty() {
+                visibility |= match entry_point.stage {
+                    naga::ShaderStage::Vertex => ShaderStages::VERTEX,
+                    naga::ShaderStage::Fragment => ShaderStages::FRAGMENT,
+                    naga::ShaderStage::Compute => ShaderStages::COMPUTE,
+                };
+            }
+        }
+        if visibility.is_empty() {
+            continue;
+        }
+
+        let unsupported = || ShaderReflectError::UnsupportedBinding { group, binding };
+        let (ty, count) = match module.types[global.ty].inner {
+            TypeInner::BindingArray { base, size } => {
+                let ArraySize::Constant(count) = size else {
+                    return Err(unsupported());
+                };
+                let ty = binding_type(module, global.space, base).ok_or_else(unsupported)?;
+                (ty, Some(count))
+            }
+            _ => {
+                let ty = binding_type(module, global.space, global.ty).ok_or_else(unsupported)?;
+                (ty, None)
+            }
+        };
+
+        groups.entry(group).or_default().push(BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty,
+            count,
+        });
+    }
+
+    for entries in groups.values_mut() {
+        entries.sort_by_key(|entry| entry.binding);
+    }
+    Ok(groups)
+}
+
+fn binding_type(
+    module: &naga::Module,
+    space: AddressSpace,
+    ty: Handle<Type>,
+) -> Option<BindingType> {
+    Some(match space {
+        AddressSpace::Uniform => BindingType::Buffer {
+            ty: BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        AddressSpace::Storage { access } => BindingType::Buffer {
+            ty: BufferBindingType::Storage {
+                read_only: !access.contains(StorageAccess::STORE),
+            },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        AddressSpace::Handle => match module.types[ty].inner {
+            TypeInner::Sampler { comparison } => BindingType::Sampler(if comparison {
+                SamplerBindingType::Comparison
+            } else {
+                SamplerBindingType::Filtering
+            }),
+            TypeInner::Image {
+                dim,
+                arrayed,
+                class,
+            } => {
+                let view_dimension = match (dim, arrayed) {
+                    (ImageDimension::D1, false) => TextureViewDimension::D1,
+                    (ImageDimension::D2, false) => TextureViewDimension::D2,
+                    (ImageDimension::D2, true) => TextureViewDimension::D2Array,
+                    (ImageDimension::D3, false) => TextureViewDimension::D3,
+                    (ImageDimension::Cube, false) => TextureViewDimension::Cube,
+                    (ImageDimension::Cube, true) => TextureViewDimension::CubeArray,
+                    _ => return None,
+                };
+                match class {
+                    ImageClass::Sampled { kind, multi } => BindingType::Texture {
+                        sample_type: match kind {
+                            ScalarKind::Float => TextureSampleType::Float { filterable: !multi },
+                            ScalarKind::Sint => TextureSampleType::Sint,
+                            ScalarKind::Uint => TextureSampleType::Uint,
+                            _ => return None,
+                        },
+                        view_dimension,
+                        multisampled: multi,
+                    },
+                    ImageClass::Depth { multi } => BindingType::Texture {
+                        sample_type: TextureSampleType::Depth,
+                        view_dimension,
+                        multisampled: multi,
+                    },
+                    ImageClass::Storage { format, access } => BindingType::StorageTexture {
+                        access: match (
+                            access.contains(StorageAccess::LOAD),
+                            access.contains(StorageAccess::STORE),
+                        ) {
+                            (true, true) => StorageTextureAccess::ReadWrite,
+                            (true, false) => StorageTextureAccess::ReadOnly,
+                            (false, true) => StorageTextureAccess::WriteOnly,
+                            (false, false) => return None,
+                        },
+                        format: storage_texture_format(format)?,
+                        view_dimension,
+                    },
+                }
+            }
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+fn storage_texture_format(format: StorageFormat) -> Option<TextureFormat> {
+    Some(match format {
+        StorageFormat::R32Uint => TextureFormat::R32Uint,
+        StorageFormat::R32Sint => TextureFormat::R32Sint,
+        StorageFormat::R32Float => TextureFormat::R32Float,
+        StorageFormat::Rg32Uint => TextureFormat::Rg32Uint,
+        StorageFormat::Rg32Sint => TextureFormat::Rg32Sint,
+        StorageFormat::Rg32Float => TextureFormat::Rg32Float,
+        StorageFormat::Rgba8Unorm => TextureFormat::Rgba8Unorm,
+        StorageFormat::Rgba8Snorm => TextureFormat::Rgba8Snorm,
+        StorageFormat::Rgba8Uint => TextureFormat::Rgba8Uint,
+        StorageFormat::Rgba8Sint => TextureFormat::Rgba8Sint,
+        StorageFormat::Bgra8Unorm => TextureFormat::Bgra8Unorm,
+        StorageFormat::Rgba16Uint => TextureFormat::Rgba16Uint,
+        StorageFormat::Rgba16Sint => TextureFormat::Rgba16Sint,
+        StorageFormat::Rgba16Float => TextureFormat::Rgba16Float,
+        StorageFormat::Rgba32Uint => TextureFormat::Rgba32Uint,
+        StorageFormat::Rgba32Sint => TextureFormat::Rgba32Sint,
+        StorageFormat::Rgba32Float => TextureFormat::Rgba32Float,
+        format => {
+            warn!("reflecting storage textures with format {format:?} is not supported");
+            return None;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::render_resource::Shader;
+    use wgpu::{
+        BindingType, BufferBindingType, SamplerBindingType, ShaderStages, TextureSampleType,
+        TextureViewDimension,
+    };
+
+    #[test]
+    fn reflect_wgsl_bindings() {
+        let shader = Shader::from_wgsl(
+            r"
+@group(0) @binding(0) var<uniform> color: vec4<f32>;
+@group(0) @binding(2) var color_sampler: sampler;
+@group(0) @binding(1) var color_texture: texture_2d<f32>;
+@group(1) @binding(0) var<storage, read_write> output: array<u32>;
+@group(1) @binding(1) var unused_texture: texture_2d<f32>;
+
+@fragment
+fn fragment() -> @location(0) vec4<f32> {
+    return color * textureSample(color_texture, color_sampler, vec2(0.5));
+}
+
+@compute @workgroup_size(1)
+fn compute() {
+    output[0] = 1u;
+}
+",
+            "reflect_wgsl_bindings.wgsl",
+        );
+
+        let groups = shader.reflect_bind_group_layout_entries().unwrap();
+        assert_eq!(groups.len(), 2);
+
+        let group_0 = &groups[&0];
+        assert_eq!(
+            group_0
+                .iter()
+                .map(|entry| entry.binding)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert!(group_0
+            .iter()
+            .all(|entry| entry.visibility == ShaderStages::FRAGMENT));
+        assert!(matches!(
+            group_0[0].ty,
+            BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                ..
+            }
+        ));
+        assert!(matches!(
+            group_0[1].ty,
+            BindingType::Texture {
+                sample_type: TextureSampleType::Float { filterable: true },
+                view_dimension: TextureViewDimension::D2,
+                multisampled: false,
+            }
+        ));
+        assert!(matches!(
+            group_0[2].ty,
+            BindingType::Sampler(SamplerBindingType::Filtering)
+        ));
+
+        // `unused_texture` isn't used by any entry point, so it's left out.
+        let group_1 = &groups[&1];
+        assert_eq!(group_1.len(), 1);
+        assert_eq!(group_1[0].visibility, ShaderStages::COMPUTE);
+        assert!(matches!(
+            group_1[0].ty,
+            BindingType::Buffer {
+                ty: BufferBindingType::Storage { read_only: false },
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn reflect_wgsl_with_imports_fails() {
+        let shader = Shader::from_wgsl(
+            "#import bevy_render::view::View\n@fragment fn fragment() {}",
+            "reflect_wgsl_with_imports_fails.wgsl",
+        );
+        assert!(shader.reflect_bind_group_layout_entries().is_err());
+    }
+}
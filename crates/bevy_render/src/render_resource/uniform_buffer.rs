@@ -351,6 +351,22 @@ impl<T: ShaderType + WriteInto> DynamicUniformBuffer<T> {
         self.scratch.as_mut().clear();
         self.scratch.set_offset(0);
     }
+
+    /// Shrinks the memory held by the buffer to `size` bytes, or to the size of
+    /// the data if it's larger.
+    ///
+    /// If the GPU-side buffer is larger than `size`, it's reallocated to fit the
+    /// data on the next [`write_buffer`](DynamicUniformBuffer::write_buffer).
+    pub fn shrink_to(&mut self, size: u64) {
+        self.scratch.as_mut().shrink_to(size as usize);
+        if self
+            .buffer
+            .as_deref()
+            .is_some_and(|buffer| buffer.size() > size)
+        {
+            self.changed = true;
+        }
+    }
 }
 
 /// A writer that can be used to directly write elements into the target buffer.
@@ -210,7 +210,26 @@ impl RenderGraphRunner {
                     #[cfg(feature = "trace")]
                     let _span = info_span!("node", name = node_state.type_name).entered();
 
+                    // Record the CPU and GPU time of every node, under `render/nodes/<graph>/<node>`.
+                    // Nodes that encode their commands in parallel tasks only have their CPU time
+                    // measured accurately this way, and should record pass spans themselves.
+                    let diagnostics_recorder = render_context.diagnostics_recorder.clone();
+                    if let Some(recorder) = &diagnostics_recorder {
+                        let graph_name = match &sub_graph {
+                            Some(label) => format!("{label:?}"),
+                            None => "main_graph".to_owned(),
+                        };
+                        recorder.begin_node_span(
+                            render_context.command_encoder(),
+                            format!("nodes/{graph_name}/{:?}", node_state.label).into(),
+                        );
+                    }
+
                     node_state.node.run(&mut context, render_context, world)?;
+
+                    if let Some(recorder) = &diagnostics_recorder {
+                        recorder.end_node_span(render_context.command_encoder());
+                    }
                 }
 
                 for run_sub_graph in context.finish() {
@@ -19,7 +19,7 @@ use bevy_reflect::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::hash::Hash;
 use thiserror::Error;
-use wgpu::{Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor};
+use wgpu::{Extent3d, TextureDimension, TextureFormat, TextureUsages, TextureViewDescriptor};
 
 pub const TEXTURE_ASSET_INDEX: u64 = 0;
 pub const SAMPLER_ASSET_INDEX: u64 = 1;
@@ -545,6 +545,30 @@ impl Image {
         value
     }
 
+    /// Creates a new zero-filled 2D image that a [`Camera`](crate::camera::Camera) can render to
+    /// through [`RenderTarget::Image`](crate::camera::RenderTarget::Image).
+    ///
+    /// The texture can be rendered to, sampled and copied from. Use an
+    /// [`ImageRenderTargetScale`](crate::camera::ImageRenderTargetScale) on the camera to keep
+    /// its size in sync with a window.
+    pub fn new_render_target(width: u32, height: u32, format: TextureFormat) -> Self {
+        let mut image = Image {
+            data: Vec::new(),
+            ..Default::default()
+        };
+        image.texture_descriptor.format = format;
+        image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+            | TextureUsages::COPY_DST
+            | TextureUsages::COPY_SRC
+            | TextureUsages::RENDER_ATTACHMENT;
+        image.resize(Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        });
+        image
+    }
+
     /// Returns the width of a 2D image.
     #[inline]
     pub fn width(&self) -> u32 {
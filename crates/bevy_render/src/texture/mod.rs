@@ -16,6 +16,7 @@ mod image_loader;
 mod ktx2;
 mod texture_attachment;
 mod texture_cache;
+mod virtual_texture;
 
 pub(crate) mod image_texture_conversion;
 
@@ -35,6 +36,7 @@ pub use fallback_image::*;
 pub use image_loader::*;
 pub use texture_attachment::*;
 pub use texture_cache::*;
+pub use virtual_texture::*;
 
 use crate::{
     render_asset::RenderAssetPlugin, renderer::RenderDevice, Render, RenderApp, RenderSet,
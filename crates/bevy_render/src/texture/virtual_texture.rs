@@ -0,0 +1,780 @@
+use crate::{
+    render_asset::{RenderAssetUsages, RenderAssets},
+    render_resource::{
+        Buffer, BufferDescriptor, BufferUsages, Extent3d, ImageCopyTexture, ImageDataLayout,
+        MapMode, Origin3d, Shader, ShaderType, TextureAspect, TextureDescriptor, TextureDimension,
+        TextureFormat, TextureUsages,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    texture::{GpuImage, Image, ImageLoaderSettings},
+    ExtractSchedule, MainWorld, Render, RenderApp, RenderSet,
+};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, AssetServer, Assets, Handle, LoadState};
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_math::{UVec2, UVec4};
+use bevy_tasks::AsyncComputeTaskPool;
+use bevy_utils::{
+    tracing::{error, warn},
+    HashMap,
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use wgpu::CommandEncoderDescriptor;
+
+pub const VIRTUAL_TEXTURE_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(224609487015434281290740457362306011378);
+
+/// Streams [`VirtualTexture`]s: loads the tiles requested by shaders and uploads them to
+/// the cache of each texture.
+pub struct VirtualTexturePlugin;
+
+impl Plugin for VirtualTexturePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            VIRTUAL_TEXTURE_SHADER_HANDLE,
+            "virtual_texture.wgsl",
+            Shader::from_wgsl
+        );
+
+        let (sender, receiver) = async_channel::unbounded();
+        app.insert_resource(VirtualTextureFeedbackReceiver(receiver))
+            .add_systems(PostUpdate, stream_virtual_textures);
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .insert_resource(VirtualTextureFeedbackSender(sender))
+            .init_resource::<RenderVirtualTextures>()
+            .add_systems(ExtractSchedule, extract_virtual_textures)
+            .add_systems(
+                Render,
+                (
+                    upload_virtual_texture_pages.in_set(RenderSet::PrepareResources),
+                    read_back_virtual_texture_feedback.in_set(RenderSet::Cleanup),
+                ),
+            );
+    }
+}
+
+/// Describes the tiles of a [`VirtualTexture`] and the size of its cache.
+#[derive(Clone, Debug)]
+pub struct VirtualTextureDescriptor {
+    /// The size of the texture in texels, at its most detailed mip level.
+    ///
+    /// Both dimensions must be powers of two, at least as large as
+    /// [`tile_size`](Self::tile_size).
+    pub size: UVec2,
+    /// The size of the side of a tile in texels, excluding its border. Must be a power of two.
+    pub tile_size: u32,
+    /// The number of texels duplicated from neighboring tiles on each side of a tile, so
+    /// that filtering doesn't sample other tiles of the cache.
+    pub tile_border: u32,
+    /// The path of the tile images, usually KTX2 files, with `{mip}`, `{x}` and `{y}`
+    /// replaced by the mip level and the position of the tile in tiles.
+    ///
+    /// Tiles are `tile_size + 2 * tile_border` texels wide and high, in
+    /// [`format`](Self::format), without mip levels.
+    pub tile_path: String,
+    /// The format of the tiles.
+    pub format: TextureFormat,
+    /// The number of tiles along each side of the cache texture.
+    ///
+    /// The cache holds `cache_size * cache_size` tiles, one of which is always the
+    /// least detailed tile of the texture.
+    pub cache_size: u32,
+    /// The maximum number of tiles loading at once.
+    pub max_loading_tiles: usize,
+}
+
+/// A page of a [`VirtualTexture`]: a tile at a mip level.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PageId {
+    pub mip: u32,
+    pub x: u32,
+    pub y: u32,
+}
+
+/// A texture too large for GPU memory, of which only the tiles used by shaders are
+/// loaded.
+///
+/// The texture is split into tiles at each mip level, stored as separate images loaded
+/// with the [`AssetServer`]. Shaders sample it with `virtual_texture_sample` from the
+/// `bevy_render::virtual_texture` import, which records the tiles it needs in a feedback
+/// buffer. The [`VirtualTexturePlugin`] reads the feedback back, loads the missing tiles
+/// and uploads them into a cache texture, evicting the least recently used ones. Until a
+/// tile is loaded, less detailed tiles are sampled instead.
+///
+/// Materials bind the resources of the texture in their bind group, with the bindings
+/// expected by the shader import:
+///
+/// ```ignore
+/// #[derive(Asset, TypePath, AsBindGroup, Clone)]
+/// struct TerrainMaterial {
+///     #[texture(100, sample_type = "u_int")]
+///     page_table: Handle<Image>,
+///     #[texture(101)]
+///     #[sampler(102)]
+///     cache: Handle<Image>,
+///     #[storage(103, buffer, visibility(fragment))]
+///     feedback: Buffer,
+///     #[uniform(104)]
+///     info: VirtualTextureInfo,
+/// }
+/// ```
+#[derive(Component)]
+pub struct VirtualTexture {
+    descriptor: VirtualTextureDescriptor,
+    page_table: Handle<Image>,
+    cache_texture: Handle<Image>,
+    feedback: Buffer,
+    readback: Buffer,
+    readback_in_flight: Arc<AtomicBool>,
+    cache: PageCache,
+    frame: u64,
+    requested: Vec<PageId>,
+    loading: HashMap<PageId, Handle<Image>>,
+    uploads: Vec<PageUpload>,
+}
+
+impl VirtualTexture {
+    /// Creates a virtual texture, with an empty cache.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size or tile size of `descriptor` are not powers of two, or if the
+    /// texture is smaller than a tile.
+    pub fn new(
+        descriptor: VirtualTextureDescriptor,
+        images: &mut Assets<Image>,
+        render_device: &RenderDevice,
+    ) -> Self {
+        assert!(
+            descriptor.tile_size.is_power_of_two()
+                && descriptor.size.x.is_power_of_two()
+                && descriptor.size.y.is_power_of_two(),
+            "the size and tile size of a virtual texture must be powers of two"
+        );
+        assert!(
+            descriptor.size.min_element() >= descriptor.tile_size,
+            "a virtual texture must be at least as large as a tile"
+        );
+
+        let tiles = descriptor.size / descriptor.tile_size;
+        let mip_count = tiles.max_element().trailing_zeros() + 1;
+        let page_count = page_count(tiles, mip_count);
+
+        let page_table = images.add(Image {
+            data: vec![0; page_count as usize * 4],
+            texture_descriptor: TextureDescriptor {
+                label: Some("virtual_texture_page_table"),
+                size: Extent3d {
+                    width: tiles.x,
+                    height: tiles.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: mip_count,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::R32Uint,
+                usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+            asset_usage: RenderAssetUsages::RENDER_WORLD,
+            ..Default::default()
+        });
+
+        let cache_texels = descriptor.cache_size * slot_size(&descriptor);
+        let (block_width, block_height) = descriptor.format.block_dimensions();
+        let block_size = descriptor.format.block_copy_size(None).unwrap_or(4);
+        let cache_texture = images.add(Image {
+            data: vec![
+                0;
+                ((cache_texels / block_width) * (cache_texels / block_height) * block_size)
+                    as usize
+            ],
+            texture_descriptor: TextureDescriptor {
+                label: Some("virtual_texture_cache"),
+                size: Extent3d {
+                    width: cache_texels,
+                    height: cache_texels,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: descriptor.format,
+                usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+            asset_usage: RenderAssetUsages::RENDER_WORLD,
+            ..Default::default()
+        });
+
+        let feedback = render_device.create_buffer(&BufferDescriptor {
+            label: Some("virtual_texture_feedback"),
+            size: page_count as u64 * 4,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let readback = render_device.create_buffer(&BufferDescriptor {
+            label: Some("virtual_texture_feedback_readback"),
+            size: page_count as u64 * 4,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let cache = PageCache::new(descriptor.cache_size * descriptor.cache_size);
+
+        Self {
+            descriptor,
+            page_table,
+            cache_texture,
+            feedback,
+            readback,
+            readback_in_flight: Arc::new(AtomicBool::new(false)),
+            cache,
+            frame: 0,
+            requested: Vec::new(),
+            loading: HashMap::default(),
+            uploads: Vec::new(),
+        }
+    }
+
+    /// Returns the descriptor of the texture.
+    pub fn descriptor(&self) -> &VirtualTextureDescriptor {
+        &self.descriptor
+    }
+
+    /// Returns the page table, which stores the slot in the cache of each page plus one,
+    /// or zero if it isn't loaded, with one mip level per mip level of the texture.
+    pub fn page_table(&self) -> &Handle<Image> {
+        &self.page_table
+    }
+
+    /// Returns the texture caching the loaded tiles.
+    pub fn cache_texture(&self) -> &Handle<Image> {
+        &self.cache_texture
+    }
+
+    /// Returns the buffer in which shaders record the pages they need.
+    pub fn feedback_buffer(&self) -> &Buffer {
+        &self.feedback
+    }
+
+    /// Returns the uniform describing the texture to shaders.
+    pub fn info(&self) -> VirtualTextureInfo {
+        VirtualTextureInfo {
+            size: self.descriptor.size,
+            tile_size: self.descriptor.tile_size,
+            tile_border: self.descriptor.tile_border,
+            mip_count: self.mip_count(),
+            cache_size: self.descriptor.cache_size,
+            _padding: UVec4::ZERO,
+        }
+    }
+
+    /// Returns the number of tiles along each side of the texture at mip level `mip`.
+    pub fn tiles(&self, mip: u32) -> UVec2 {
+        ((self.descriptor.size / self.descriptor.tile_size) >> mip).max(UVec2::ONE)
+    }
+
+    /// Returns the number of mip levels of the texture, down to a single tile.
+    pub fn mip_count(&self) -> u32 {
+        (self.descriptor.size / self.descriptor.tile_size)
+            .max_element()
+            .trailing_zeros()
+            + 1
+    }
+
+    /// Returns the index of `page` in the feedback buffer, or `None` if it is out of the
+    /// texture.
+    pub fn page_index(&self, page: PageId) -> Option<u32> {
+        if page.mip >= self.mip_count() {
+            return None;
+        }
+        let tiles = self.tiles(page.mip);
+        if page.x >= tiles.x || page.y >= tiles.y {
+            return None;
+        }
+        let offset = page_count(self.descriptor.size / self.descriptor.tile_size, page.mip);
+        Some(offset + page.y * tiles.x + page.x)
+    }
+
+    /// Returns the page at `index` in the feedback buffer.
+    pub fn page_at(&self, mut index: u32) -> Option<PageId> {
+        for mip in 0..self.mip_count() {
+            let tiles = self.tiles(mip);
+            let count = tiles.x * tiles.y;
+            if index < count {
+                return Some(PageId {
+                    mip,
+                    x: index % tiles.x,
+                    y: index / tiles.x,
+                });
+            }
+            index -= count;
+        }
+        None
+    }
+
+    /// Returns the path of the tile image of `page`.
+    pub fn tile_path(&self, page: PageId) -> String {
+        self.descriptor
+            .tile_path
+            .replace("{mip}", &page.mip.to_string())
+            .replace("{x}", &page.x.to_string())
+            .replace("{y}", &page.y.to_string())
+    }
+
+    /// Requests `page` to be loaded, in addition to the pages requested by shaders.
+    pub fn request(&mut self, page: PageId) {
+        self.requested.push(page);
+    }
+}
+
+/// The number of pages of the first `mip_count` mip levels of a texture `tiles` tiles wide.
+fn page_count(tiles: UVec2, mip_count: u32) -> u32 {
+    (0..mip_count)
+        .map(|mip| {
+            let tiles = (tiles >> mip).max(UVec2::ONE);
+            tiles.x * tiles.y
+        })
+        .sum()
+}
+
+/// The size of a slot of the cache texture in texels.
+fn slot_size(descriptor: &VirtualTextureDescriptor) -> u32 {
+    descriptor.tile_size + 2 * descriptor.tile_border
+}
+
+/// Describes a [`VirtualTexture`] to the `bevy_render::virtual_texture` shader import.
+#[derive(ShaderType, Clone, Copy, Debug, Default)]
+pub struct VirtualTextureInfo {
+    pub size: UVec2,
+    pub tile_size: u32,
+    pub tile_border: u32,
+    pub mip_count: u32,
+    pub cache_size: u32,
+    pub _padding: UVec4,
+}
+
+/// Assigns the slots of a cache of pages, evicting the least recently used pages when it
+/// is full.
+#[derive(Clone, Debug)]
+pub struct PageCache {
+    pages: HashMap<PageId, CachedPage>,
+    free_slots: Vec<u32>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct CachedPage {
+    slot: u32,
+    last_used: u64,
+}
+
+impl PageCache {
+    /// Creates an empty cache with `capacity` slots.
+    pub fn new(capacity: u32) -> Self {
+        Self {
+            pages: HashMap::default(),
+            free_slots: (0..capacity).rev().collect(),
+        }
+    }
+
+    /// Returns the slot of `page`, if it is in the cache.
+    pub fn get(&self, page: PageId) -> Option<u32> {
+        self.pages.get(&page).map(|cached| cached.slot)
+    }
+
+    /// Marks `page` as used in `frame`, if it is in the cache, and returns whether it is.
+    pub fn touch(&mut self, page: PageId, frame: u64) -> bool {
+        match self.pages.get_mut(&page) {
+            Some(cached) => {
+                cached.last_used = cached.last_used.max(frame);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds `page` to the cache, as used in `frame`, and returns its slot along with the
+    /// page it replaced, if any.
+    ///
+    /// Returns `None` if every slot holds a page used in `frame`.
+    pub fn insert(&mut self, page: PageId, frame: u64) -> Option<(u32, Option<PageId>)> {
+        if let Some(cached) = self.pages.get_mut(&page) {
+            cached.last_used = frame;
+            return Some((cached.slot, None));
+        }
+
+        let (slot, evicted) = match self.free_slots.pop() {
+            Some(slot) => (slot, None),
+            None => {
+                let (&evicted, cached) = self
+                    .pages
+                    .iter()
+                    .filter(|(_, cached)| cached.last_used < frame)
+                    .min_by_key(|(_, cached)| cached.last_used)?;
+                let slot = cached.slot;
+                self.pages.remove(&evicted);
+                (slot, Some(evicted))
+            }
+        };
+
+        self.pages.insert(
+            page,
+            CachedPage {
+                slot,
+                last_used: frame,
+            },
+        );
+        Some((slot, evicted))
+    }
+
+    /// Returns the number of pages in the cache.
+    pub fn len(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Returns `true` if the cache has no pages.
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+}
+
+/// A tile to copy to a slot of the cache, and the page table entries to write after it.
+struct PageUpload {
+    slot: u32,
+    data: Vec<u8>,
+    page_table_writes: Vec<(PageId, u32)>,
+}
+
+#[derive(Resource)]
+struct VirtualTextureFeedbackReceiver(async_channel::Receiver<(Entity, Vec<u32>)>);
+
+#[derive(Resource)]
+struct VirtualTextureFeedbackSender(async_channel::Sender<(Entity, Vec<u32>)>);
+
+/// Loads the pages requested for each [`VirtualTexture`] and queues the loaded ones for
+/// upload to its cache.
+fn stream_virtual_textures(
+    mut virtual_textures: Query<(Entity, &mut VirtualTexture)>,
+    receiver: Res<VirtualTextureFeedbackReceiver>,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    while let Ok((entity, page_indices)) = receiver.0.try_recv() {
+        let Ok((_, mut virtual_texture)) = virtual_textures.get_mut(entity) else {
+            continue;
+        };
+        let pages = page_indices
+            .into_iter()
+            .filter_map(|index| virtual_texture.page_at(index))
+            .collect::<Vec<_>>();
+        virtual_texture.requested.extend(pages);
+    }
+
+    for (_, mut virtual_texture) in &mut virtual_textures {
+        let virtual_texture = &mut *virtual_texture;
+        virtual_texture.frame += 1;
+        let frame = virtual_texture.frame;
+
+        // The least detailed page is the fallback of every other page, so it is always
+        // requested and never evicted.
+        let last_mip = virtual_texture.mip_count() - 1;
+        virtual_texture.requested.push(PageId {
+            mip: last_mip,
+            x: 0,
+            y: 0,
+        });
+
+        for page in std::mem::take(&mut virtual_texture.requested) {
+            if virtual_texture.cache.touch(page, frame)
+                || virtual_texture.loading.contains_key(&page)
+                || virtual_texture.loading.len() >= virtual_texture.descriptor.max_loading_tiles
+            {
+                continue;
+            }
+            let is_srgb = virtual_texture.descriptor.format.is_srgb();
+            let handle = asset_server.load_with_settings(
+                virtual_texture.tile_path(page),
+                move |settings: &mut ImageLoaderSettings| {
+                    settings.is_srgb = is_srgb;
+                    settings.asset_usage = RenderAssetUsages::MAIN_WORLD;
+                },
+            );
+            virtual_texture.loading.insert(page, handle);
+        }
+
+        let expected_size = UVec2::splat(slot_size(&virtual_texture.descriptor));
+        let loading = std::mem::take(&mut virtual_texture.loading);
+        for (page, handle) in loading {
+            match asset_server.load_state(&handle) {
+                LoadState::Loaded => {}
+                LoadState::Failed(_) => {
+                    warn!(
+                        "Failed to load virtual texture tile {}",
+                        virtual_texture.tile_path(page)
+                    );
+                    continue;
+                }
+                LoadState::NotLoaded | LoadState::Loading => {
+                    virtual_texture.loading.insert(page, handle);
+                    continue;
+                }
+            }
+
+            let Some(image) = images.remove(&handle) else {
+                continue;
+            };
+            if image.size() != expected_size
+                || image.texture_descriptor.format != virtual_texture.descriptor.format
+            {
+                error!(
+                    "Virtual texture tile {} must be {}x{} texels in {:?}",
+                    virtual_texture.tile_path(page),
+                    expected_size.x,
+                    expected_size.y,
+                    virtual_texture.descriptor.format,
+                );
+                continue;
+            }
+
+            let Some((slot, evicted)) = virtual_texture.cache.insert(page, frame) else {
+                // Every slot is used this frame; try again next frame.
+                images.insert(&handle, image);
+                virtual_texture.loading.insert(page, handle);
+                continue;
+            };
+
+            let mut page_table_writes = Vec::with_capacity(2);
+            if let Some(evicted) = evicted {
+                page_table_writes.push((evicted, 0));
+            }
+            page_table_writes.push((page, slot + 1));
+            virtual_texture.uploads.push(PageUpload {
+                slot,
+                data: image.data,
+                page_table_writes,
+            });
+        }
+    }
+}
+
+/// The render world data of a [`VirtualTexture`].
+struct RenderVirtualTexture {
+    page_table: Handle<Image>,
+    cache_texture: Handle<Image>,
+    slot_size: u32,
+    cache_size: u32,
+    format: TextureFormat,
+    feedback: Buffer,
+    readback: Buffer,
+    readback_in_flight: Arc<AtomicBool>,
+    uploads: Vec<PageUpload>,
+}
+
+#[derive(Resource, Default)]
+struct RenderVirtualTextures(EntityHashMap<RenderVirtualTexture>);
+
+fn extract_virtual_textures(
+    mut main_world: ResMut<MainWorld>,
+    mut render_virtual_textures: ResMut<RenderVirtualTextures>,
+) {
+    let mut virtual_textures = main_world.query::<(Entity, &mut VirtualTexture)>();
+    render_virtual_textures
+        .0
+        .retain(|entity, _| virtual_textures.get(&main_world, *entity).is_ok());
+
+    for (entity, mut virtual_texture) in virtual_textures.iter_mut(&mut main_world) {
+        let render_virtual_texture =
+            render_virtual_textures
+                .0
+                .entry(entity)
+                .or_insert_with(|| RenderVirtualTexture {
+                    page_table: virtual_texture.page_table.clone(),
+                    cache_texture: virtual_texture.cache_texture.clone(),
+                    slot_size: slot_size(&virtual_texture.descriptor),
+                    cache_size: virtual_texture.descriptor.cache_size,
+                    format: virtual_texture.descriptor.format,
+                    feedback: virtual_texture.feedback.clone(),
+                    readback: virtual_texture.readback.clone(),
+                    readback_in_flight: virtual_texture.readback_in_flight.clone(),
+                    uploads: Vec::new(),
+                });
+        render_virtual_texture
+            .uploads
+            .append(&mut virtual_texture.uploads);
+    }
+}
+
+/// Copies the loaded tiles to the caches, and updates the page tables.
+fn upload_virtual_texture_pages(
+    mut render_virtual_textures: ResMut<RenderVirtualTextures>,
+    images: Res<RenderAssets<GpuImage>>,
+    render_queue: Res<RenderQueue>,
+) {
+    for virtual_texture in render_virtual_textures.0.values_mut() {
+        // Uploads wait until both textures have been created.
+        let (Some(page_table), Some(cache_texture)) = (
+            images.get(&virtual_texture.page_table),
+            images.get(&virtual_texture.cache_texture),
+        ) else {
+            continue;
+        };
+
+        let (block_width, block_height) = virtual_texture.format.block_dimensions();
+        let block_size = virtual_texture.format.block_copy_size(None).unwrap_or(4);
+        let slot_size = virtual_texture.slot_size;
+
+        for upload in virtual_texture.uploads.drain(..) {
+            let slot = UVec2::new(
+                upload.slot % virtual_texture.cache_size,
+                upload.slot / virtual_texture.cache_size,
+            );
+            render_queue.write_texture(
+                ImageCopyTexture {
+                    texture: &cache_texture.texture,
+                    mip_level: 0,
+                    origin: Origin3d {
+                        x: slot.x * slot_size,
+                        y: slot.y * slot_size,
+                        z: 0,
+                    },
+                    aspect: TextureAspect::All,
+                },
+                &upload.data,
+                ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(slot_size / block_width * block_size),
+                    rows_per_image: Some(slot_size / block_height),
+                },
+                Extent3d {
+                    width: slot_size,
+                    height: slot_size,
+                    depth_or_array_layers: 1,
+                },
+            );
+
+            for (page, value) in upload.page_table_writes {
+                render_queue.write_texture(
+                    ImageCopyTexture {
+                        texture: &page_table.texture,
+                        mip_level: page.mip,
+                        origin: Origin3d {
+                            x: page.x,
+                            y: page.y,
+                            z: 0,
+                        },
+                        aspect: TextureAspect::All,
+                    },
+                    &value.to_le_bytes(),
+                    ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: Some(4),
+                        rows_per_image: Some(1),
+                    },
+                    Extent3d {
+                        width: 1,
+                        height: 1,
+                        depth_or_array_layers: 1,
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Reads back the pages requested by shaders this frame, and clears the feedback buffers.
+fn read_back_virtual_texture_feedback(
+    render_virtual_textures: Res<RenderVirtualTextures>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    sender: Res<VirtualTextureFeedbackSender>,
+) {
+    for (&entity, virtual_texture) in &render_virtual_textures.0 {
+        // Requests keep accumulating in the feedback buffer while the previous ones are
+        // being read.
+        if virtual_texture
+            .readback_in_flight
+            .swap(true, Ordering::AcqRel)
+        {
+            continue;
+        }
+
+        let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("virtual_texture_feedback_readback"),
+        });
+        encoder.copy_buffer_to_buffer(
+            &virtual_texture.feedback,
+            0,
+            &virtual_texture.readback,
+            0,
+            virtual_texture.feedback.size(),
+        );
+        encoder.clear_buffer(&virtual_texture.feedback, 0, None);
+        render_queue.submit([encoder.finish()]);
+
+        let readback = virtual_texture.readback.clone();
+        let readback_in_flight = virtual_texture.readback_in_flight.clone();
+        let sender = sender.0.clone();
+        let finish = async move {
+            let (tx, rx) = async_channel::bounded(1);
+            let buffer_slice = readback.slice(..);
+            // The polling for this map call is done every frame when the command queue is submitted.
+            buffer_slice.map_async(MapMode::Read, move |result| {
+                let _ = tx.try_send(result.is_ok());
+            });
+            if rx.recv().await.unwrap_or(false) {
+                let data = buffer_slice.get_mapped_range();
+                let page_indices = bytemuck::cast_slice::<u8, u32>(&data)
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &requested)| requested != 0)
+                    .map(|(index, _)| index as u32)
+                    .collect::<Vec<_>>();
+                drop(data);
+                readback.unmap();
+                if !page_indices.is_empty() {
+                    let _ = sender.try_send((entity, page_indices));
+                }
+            }
+            readback_in_flight.store(false, Ordering::Release);
+        };
+
+        AsyncComputeTaskPool::get().spawn(finish).detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PageCache, PageId};
+
+    fn page(x: u32) -> PageId {
+        PageId { mip: 0, x, y: 0 }
+    }
+
+    #[test]
+    fn page_cache_evicts_least_recently_used() {
+        let mut cache = PageCache::new(2);
+        assert_eq!(cache.insert(page(0), 0), Some((0, None)));
+        assert_eq!(cache.insert(page(1), 1), Some((1, None)));
+        assert!(cache.touch(page(0), 2));
+
+        // Page 1 was used least recently.
+        assert_eq!(cache.insert(page(2), 3), Some((1, Some(page(1)))));
+        assert_eq!(cache.get(page(1)), None);
+        assert_eq!(cache.get(page(2)), Some(1));
+
+        // Pages used this frame are never evicted.
+        assert!(cache.touch(page(0), 3));
+        assert_eq!(cache.insert(page(3), 3), None);
+        assert_eq!(cache.len(), 2);
+    }
+}
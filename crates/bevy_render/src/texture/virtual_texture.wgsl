@@ -0,0 +1,95 @@
+#define_import_path bevy_render::virtual_texture
+
+// Samples a virtual texture streamed by the `VirtualTexturePlugin`.
+//
+// Materials bind the page table, the cache texture and its sampler, the feedback buffer and
+// the `VirtualTextureInfo` of the texture at these bindings of their bind group.
+
+struct VirtualTextureInfo {
+    size: vec2<u32>,
+    tile_size: u32,
+    tile_border: u32,
+    mip_count: u32,
+    cache_size: u32,
+    _padding: vec4<u32>,
+}
+
+@group(2) @binding(100) var virtual_texture_page_table: texture_2d<u32>;
+@group(2) @binding(101) var virtual_texture_cache: texture_2d<f32>;
+@group(2) @binding(102) var virtual_texture_sampler: sampler;
+@group(2) @binding(103) var<storage, read_write> virtual_texture_feedback: array<u32>;
+@group(2) @binding(104) var<uniform> virtual_texture_info: VirtualTextureInfo;
+
+// The mip level the hardware would sample at `uv`, from the screen space derivatives.
+fn virtual_texture_mip_level(uv: vec2<f32>) -> u32 {
+    let texel_uv = uv * vec2<f32>(virtual_texture_info.size);
+    let dx = dpdx(texel_uv);
+    let dy = dpdy(texel_uv);
+    let max_length_squared = max(dot(dx, dx), dot(dy, dy));
+    let mip_level = max(0.5 * log2(max_length_squared), 0.0);
+    return min(u32(mip_level), virtual_texture_info.mip_count - 1u);
+}
+
+// The number of tiles along each side of the texture at mip level `mip`.
+fn virtual_texture_tiles(mip: u32) -> vec2<u32> {
+    return max((virtual_texture_info.size / virtual_texture_info.tile_size) >> vec2(mip), vec2(1u));
+}
+
+// The index of the page `tile` of mip level `mip` in the feedback buffer.
+fn virtual_texture_page_index(mip: u32, tile: vec2<u32>) -> u32 {
+    var offset = 0u;
+    for (var level = 0u; level < mip; level += 1u) {
+        let tiles = virtual_texture_tiles(level);
+        offset += tiles.x * tiles.y;
+    }
+    return offset + tile.y * virtual_texture_tiles(mip).x + tile.x;
+}
+
+// Samples the virtual texture at `uv`, requesting the tile it needs.
+//
+// Samples the most detailed loaded mip level covering `uv` until that tile is loaded.
+fn virtual_texture_sample(uv: vec2<f32>) -> vec4<f32> {
+    let wrapped_uv = fract(uv);
+    let desired_mip = virtual_texture_mip_level(uv);
+
+    let desired_tile = min(
+        vec2<u32>(wrapped_uv * vec2<f32>(virtual_texture_tiles(desired_mip))),
+        virtual_texture_tiles(desired_mip) - 1u,
+    );
+    virtual_texture_feedback[virtual_texture_page_index(desired_mip, desired_tile)] = 1u;
+
+    // Fall back to less detailed mip levels until a loaded page is found.
+    var mip = desired_mip;
+    var entry = 0u;
+    var tile_uv = vec2<f32>(0.0);
+    loop {
+        let tiles = virtual_texture_tiles(mip);
+        let tile_position = wrapped_uv * vec2<f32>(tiles);
+        let tile = min(vec2<u32>(tile_position), tiles - 1u);
+        entry = textureLoad(virtual_texture_page_table, tile, i32(mip)).r;
+        tile_uv = tile_position - vec2<f32>(tile);
+        if entry != 0u || mip + 1u >= virtual_texture_info.mip_count {
+            break;
+        }
+        mip += 1u;
+    }
+
+    if entry == 0u {
+        // Nothing is loaded yet.
+        return vec4<f32>(0.0);
+    }
+
+    let slot = entry - 1u;
+    let slot_size = f32(virtual_texture_info.tile_size + 2u * virtual_texture_info.tile_border);
+    let slot_origin = vec2<f32>(
+        f32(slot % virtual_texture_info.cache_size),
+        f32(slot / virtual_texture_info.cache_size),
+    ) * slot_size;
+    let texel = slot_origin
+        + f32(virtual_texture_info.tile_border)
+        + tile_uv * f32(virtual_texture_info.tile_size);
+    let cache_uv = texel / (f32(virtual_texture_info.cache_size) * slot_size);
+
+    // Derivatives aren't uniform across tiles, so the cache has a single mip level.
+    return textureSampleLevel(virtual_texture_cache, virtual_texture_sampler, cache_uv, 0.0);
+}
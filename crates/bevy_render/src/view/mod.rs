@@ -52,6 +52,8 @@ impl Plugin for ViewPlugin {
             .register_type::<RenderLayers>()
             .register_type::<Visibility>()
             .register_type::<VisibleEntities>()
+            .register_type::<MeshLod>()
+            .register_type::<VisibleMeshLods>()
             .register_type::<ColorGrading>()
             .init_resource::<Msaa>()
             // NOTE: windows.is_changed() handles cases where a window was resized
@@ -150,6 +152,18 @@ pub struct ColorGrading {
     /// with luminance defined by ITU-R BT.709
     /// Values above 1.0 increase saturation.
     pub post_saturation: f32,
+
+    /// Per-channel offset of the shadows, applied after tonemapping.
+    /// Positive values raise the black level, negative values crush it.
+    pub lift: Vec3,
+
+    /// Per-channel non-linear adjustment of the midtones, applied after tonemapping:
+    /// `y = pow(x, 1.0 / post_gamma)`.
+    /// Values above 1.0 brighten the midtones, values below 1.0 darken them.
+    pub post_gamma: Vec3,
+
+    /// Per-channel multiplier of the highlights, applied after tonemapping.
+    pub gain: Vec3,
 }
 
 impl Default for ColorGrading {
@@ -159,6 +173,9 @@ impl Default for ColorGrading {
             gamma: 1.0,
             pre_saturation: 1.0,
             post_saturation: 1.0,
+            lift: Vec3::ZERO,
+            post_gamma: Vec3::ONE,
+            gain: Vec3::ONE,
         }
     }
 }
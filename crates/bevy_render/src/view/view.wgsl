@@ -5,6 +5,9 @@ struct ColorGrading {
     gamma: f32,
     pre_saturation: f32,
     post_saturation: f32,
+    lift: vec3<f32>,
+    post_gamma: vec3<f32>,
+    gain: vec3<f32>,
 }
 
 struct View {
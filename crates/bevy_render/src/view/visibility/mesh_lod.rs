@@ -0,0 +1,270 @@
+use bevy_asset::{AssetId, Handle};
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::components::GlobalTransform;
+
+use crate::{camera::Camera, mesh::Mesh, primitives::Aabb};
+
+use super::{VisibleEntities, WithMesh};
+
+/// Replaces the mesh of an entity with simpler ones as it gets smaller on screen.
+///
+/// The level of detail is selected separately for each camera, by
+/// [`select_mesh_lods`], from the screen coverage of the entity: the height of its
+/// bounding sphere as a fraction of the height of the viewport. The selections are
+/// stored in the [`VisibleMeshLods`] of each camera, which renderers use instead of
+/// the [`Handle<Mesh>`] of the entity.
+///
+/// The entity still needs a [`Handle<Mesh>`], which is used to compute its [`Aabb`]
+/// and to render it in views without a selection, like shadow maps. It is usually
+/// the mesh of the first level.
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_render::{mesh::Mesh, view::{MeshLod, MeshLodLevel}};
+/// # let (high, medium, low) = (Handle::<Mesh>::default(), Handle::default(), Handle::default());
+/// let lod = MeshLod::new(vec![
+///     // Used while the entity covers at least half of the screen height.
+///     MeshLodLevel::new(high, 0.5),
+///     MeshLodLevel::new(medium, 0.1),
+///     // Used for everything smaller.
+///     MeshLodLevel::new(low, 0.0),
+/// ]);
+/// ```
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct MeshLod {
+    /// The levels of detail, from the most detailed to the least detailed.
+    ///
+    /// Their [`min_screen_coverage`](MeshLodLevel::min_screen_coverage) should decrease
+    /// from one level to the next.
+    pub levels: Vec<MeshLodLevel>,
+    /// How far past a threshold the screen coverage of the entity must go before its
+    /// level changes, as a fraction of the threshold.
+    ///
+    /// This prevents the level from switching back and forth when the coverage stays
+    /// close to a threshold. Defaults to `0.1`.
+    pub hysteresis: f32,
+}
+
+/// A level of detail of a [`MeshLod`].
+#[derive(Clone, Debug, Default, Reflect)]
+#[reflect(Default)]
+pub struct MeshLodLevel {
+    /// The mesh rendered at this level.
+    pub mesh: Handle<Mesh>,
+    /// The smallest fraction of the viewport height covered by the bounding sphere
+    /// of the entity for which this level is used.
+    ///
+    /// When the entity is smaller than the threshold of every level, the last one is used.
+    pub min_screen_coverage: f32,
+}
+
+impl MeshLodLevel {
+    /// Creates a level rendering `mesh` while the entity covers at least
+    /// `min_screen_coverage` of the viewport height.
+    pub fn new(mesh: Handle<Mesh>, min_screen_coverage: f32) -> Self {
+        Self {
+            mesh,
+            min_screen_coverage,
+        }
+    }
+}
+
+impl MeshLod {
+    /// Creates a [`MeshLod`] with the given levels, from the most detailed to the least
+    /// detailed, and the default hysteresis.
+    pub fn new(levels: Vec<MeshLodLevel>) -> Self {
+        Self {
+            levels,
+            hysteresis: 0.1,
+        }
+    }
+
+    /// Sets the [`hysteresis`](Self::hysteresis).
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Returns the index of the level to use for the given screen coverage, or `None`
+    /// if there are no levels.
+    ///
+    /// `previous` is the level selected for the same view in the previous frame, which
+    /// is kept as long as the coverage stays within the [`hysteresis`](Self::hysteresis)
+    /// of its thresholds.
+    pub fn select_level(&self, screen_coverage: f32, previous: Option<usize>) -> Option<usize> {
+        let last = self.levels.len().checked_sub(1)?;
+        let selected = self
+            .levels
+            .iter()
+            .position(|level| screen_coverage >= level.min_screen_coverage)
+            .unwrap_or(last);
+
+        let Some(previous) = previous.filter(|&previous| previous <= last) else {
+            return Some(selected);
+        };
+        let above_lower_bound = previous == last
+            || screen_coverage
+                >= self.levels[previous].min_screen_coverage * (1.0 - self.hysteresis);
+        let below_upper_bound = previous == 0
+            || screen_coverage
+                < self.levels[previous - 1].min_screen_coverage * (1.0 + self.hysteresis);
+        if above_lower_bound && below_upper_bound {
+            Some(previous)
+        } else {
+            Some(selected)
+        }
+    }
+}
+
+/// The level of detail selected for an entity with a [`MeshLod`] in a view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MeshLodSelection {
+    /// The index of the level in [`MeshLod::levels`].
+    pub level: usize,
+    /// The mesh of the level.
+    pub mesh: AssetId<Mesh>,
+}
+
+/// The levels of detail selected for the visible entities with a [`MeshLod`] of a camera.
+///
+/// This is added to cameras and updated by [`select_mesh_lods`], then extracted to
+/// the render world with the camera.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct VisibleMeshLods {
+    #[reflect(ignore)]
+    selections: EntityHashMap<MeshLodSelection>,
+}
+
+impl VisibleMeshLods {
+    /// Returns the level of detail selected for `entity`, if it has a [`MeshLod`] and
+    /// is visible in this view.
+    pub fn get(&self, entity: Entity) -> Option<&MeshLodSelection> {
+        self.selections.get(&entity)
+    }
+
+    /// Returns the mesh to render for `entity` in this view: the mesh of its selected
+    /// level if there is one, or `default` otherwise.
+    pub fn mesh(&self, entity: Entity, default: AssetId<Mesh>) -> AssetId<Mesh> {
+        self.get(entity).map_or(default, |selection| selection.mesh)
+    }
+
+    /// Returns an iterator over the visible entities with a [`MeshLod`] and their selection.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &MeshLodSelection)> {
+        self.selections
+            .iter()
+            .map(|(&entity, selection)| (entity, selection))
+    }
+
+    /// Returns the number of visible entities with a [`MeshLod`].
+    pub fn len(&self) -> usize {
+        self.selections.len()
+    }
+
+    /// Returns `true` if no visible entity has a [`MeshLod`].
+    pub fn is_empty(&self) -> bool {
+        self.selections.is_empty()
+    }
+}
+
+/// Selects the level of detail of each visible entity with a [`MeshLod`], for each
+/// active camera, and stores them in its [`VisibleMeshLods`].
+///
+/// This system is used in system set [`VisibilitySystems::CheckVisibility`](super::VisibilitySystems::CheckVisibility),
+/// after [`check_visibility`](super::check_visibility) has found the visible entities.
+pub fn select_mesh_lods(
+    mut commands: Commands,
+    mut views: Query<(
+        Entity,
+        &Camera,
+        &GlobalTransform,
+        &VisibleEntities,
+        Option<&mut VisibleMeshLods>,
+    )>,
+    lods: Query<(&MeshLod, &Aabb, &GlobalTransform)>,
+) {
+    for (view, camera, view_transform, visible_entities, visible_lods) in &mut views {
+        if !camera.is_active {
+            continue;
+        }
+
+        let clip_from_world =
+            camera.projection_matrix() * view_transform.compute_matrix().inverse();
+        let scale = camera.projection_matrix().y_axis.y;
+
+        let previous = visible_lods
+            .as_ref()
+            .map(|visible_lods| &visible_lods.selections);
+        let mut selections = EntityHashMap::default();
+        for &entity in visible_entities.get::<WithMesh>() {
+            let Ok((lod, aabb, transform)) = lods.get(entity) else {
+                continue;
+            };
+
+            let center = transform.affine().transform_point3a(aabb.center);
+            let radius = transform.radius_vec3a(aabb.half_extents);
+            let clip = clip_from_world * center.extend(1.0);
+            // The camera may be inside the bounding sphere, which is then as large as the screen.
+            let screen_coverage = radius * scale / clip.w.max(f32::EPSILON);
+
+            let previous_level = previous
+                .and_then(|previous| previous.get(&entity))
+                .map(|selection| selection.level);
+            if let Some(level) = lod.select_level(screen_coverage, previous_level) {
+                selections.insert(
+                    entity,
+                    MeshLodSelection {
+                        level,
+                        mesh: lod.levels[level].mesh.id(),
+                    },
+                );
+            }
+        }
+
+        match visible_lods {
+            Some(mut visible_lods) => visible_lods.selections = selections,
+            None => {
+                commands.entity(view).insert(VisibleMeshLods { selections });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lod() -> MeshLod {
+        MeshLod::new(vec![
+            MeshLodLevel::new(Handle::default(), 0.5),
+            MeshLodLevel::new(Handle::default(), 0.1),
+            MeshLodLevel::new(Handle::default(), 0.0),
+        ])
+    }
+
+    #[test]
+    fn select_level() {
+        let lod = lod();
+        assert_eq!(lod.select_level(2.0, None), Some(0));
+        assert_eq!(lod.select_level(0.5, None), Some(0));
+        assert_eq!(lod.select_level(0.3, None), Some(1));
+        assert_eq!(lod.select_level(0.01, None), Some(2));
+        assert_eq!(MeshLod::default().select_level(1.0, None), None);
+    }
+
+    #[test]
+    fn select_level_hysteresis() {
+        let lod = lod();
+        // Within 10% of the threshold between the first two levels, the level is kept.
+        assert_eq!(lod.select_level(0.47, Some(0)), Some(0));
+        assert_eq!(lod.select_level(0.53, Some(1)), Some(1));
+        // Further away, it changes.
+        assert_eq!(lod.select_level(0.4, Some(0)), Some(1));
+        assert_eq!(lod.select_level(0.6, Some(1)), Some(0));
+        assert_eq!(lod.select_level(0.01, Some(0)), Some(2));
+        // Levels that no longer exist are ignored.
+        assert_eq!(lod.select_level(0.3, Some(5)), Some(1));
+    }
+}
@@ -1,9 +1,11 @@
+mod mesh_lod;
 mod render_layers;
 
 use std::any::TypeId;
 
 use bevy_derive::Deref;
 use bevy_ecs::query::QueryFilter;
+pub use mesh_lod::*;
 pub use render_layers::*;
 
 use bevy_app::{Plugin, PostUpdate};
@@ -293,6 +295,9 @@ impl Plugin for VisibilityPlugin {
                     .after(UpdateProjectionFrusta)
                     .after(VisibilityPropagate)
                     .after(TransformSystem::TransformPropagate),
+                select_mesh_lods
+                    .in_set(CheckVisibility)
+                    .after(check_visibility::<WithMesh>),
             ),
         );
     }
@@ -10,9 +10,14 @@ use bevy_app::{App, Plugin};
 use bevy_ecs::{entity::EntityHashMap, prelude::*};
 #[cfg(target_os = "linux")]
 use bevy_utils::warn_once;
-use bevy_utils::{default, tracing::debug, HashSet};
+use bevy_utils::{
+    default,
+    tracing::{debug, warn},
+    HashSet,
+};
 use bevy_window::{
     CompositeAlphaMode, PresentMode, PrimaryWindow, RawHandleWrapper, Window, WindowClosed,
+    WindowHdrOutput,
 };
 use std::{
     ops::{Deref, DerefMut},
@@ -76,6 +81,10 @@ pub struct ExtractedWindow {
     pub size_changed: bool,
     pub present_mode_changed: bool,
     pub alpha_mode: CompositeAlphaMode,
+    /// The HDR output requested for this window.
+    ///
+    /// The surface falls back to SDR when HDR isn't supported, see [`Self::hdr_output_scale`].
+    pub hdr_output: WindowHdrOutput,
     pub screenshot_func: Option<screenshot::ScreenshotFn>,
 }
 
@@ -90,8 +99,23 @@ impl ExtractedWindow {
         ));
         self.swap_chain_texture = Some(SurfaceTexture::from(frame));
     }
+
+    /// The scale applied to the output of cameras when it is written to this window, so that
+    /// SDR white is displayed at the requested [`WindowHdrOutput`] brightness.
+    ///
+    /// This is `1.0` unless the window's surface was created for HDR output.
+    pub fn hdr_output_scale(&self) -> f32 {
+        if self.swap_chain_texture_format == Some(HDR_SURFACE_FORMAT) {
+            self.hdr_output.paper_white_scale()
+        } else {
+            1.0
+        }
+    }
 }
 
+/// The surface format used for [`WindowHdrOutput::ScRgb`].
+const HDR_SURFACE_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
 #[derive(Default, Resource)]
 pub struct ExtractedWindows {
     pub primary: Option<Entity>,
@@ -116,10 +140,34 @@ fn extract_windows(
     mut extracted_windows: ResMut<ExtractedWindows>,
     screenshot_manager: Extract<Res<ScreenshotManager>>,
     mut closed: Extract<EventReader<WindowClosed>>,
-    windows: Extract<Query<(Entity, &Window, &RawHandleWrapper, Option<&PrimaryWindow>)>>,
+    windows: Extract<
+        Query<(
+            Entity,
+            &Window,
+            Ref<RawHandleWrapper>,
+            Option<&PrimaryWindow>,
+        )>,
+    >,
     mut removed: Extract<RemovedComponents<RawHandleWrapper>>,
     mut window_surfaces: ResMut<WindowSurfaces>,
 ) {
+    // Handle removals first, so that a window whose handle was removed and re-added this frame
+    // (e.g. when an app is resumed on Android) gets a fresh surface instead of being dropped.
+    for closed_window in closed.read() {
+        extracted_windows.remove(&closed_window.window);
+        window_surfaces.remove(&closed_window.window);
+    }
+    for removed_window in removed.read() {
+        extracted_windows.remove(&removed_window);
+        window_surfaces.remove(&removed_window);
+    }
+    if extracted_windows
+        .primary
+        .is_some_and(|primary| !extracted_windows.contains_key(&primary))
+    {
+        extracted_windows.primary = None;
+    }
+
     for (entity, window, handle, primary) in windows.iter() {
         if primary.is_some() {
             extracted_windows.primary = Some(entity);
@@ -132,7 +180,7 @@ fn extract_windows(
 
         let extracted_window = extracted_windows.entry(entity).or_insert(ExtractedWindow {
             entity,
-            handle: handle.clone(),
+            handle: (*handle).clone(),
             physical_width: new_width,
             physical_height: new_height,
             present_mode: window.present_mode,
@@ -142,10 +190,17 @@ fn extract_windows(
             swap_chain_texture_format: None,
             present_mode_changed: false,
             alpha_mode: window.composite_alpha_mode,
+            hdr_output: window.hdr_output,
             screenshot_func: None,
             screenshot_memory: None,
         });
 
+        // The window was recreated with a new native handle, so its old surface is stale.
+        if handle.is_changed() && !handle.is_added() {
+            extracted_window.handle = (*handle).clone();
+            window_surfaces.remove(&entity);
+        }
+
         // NOTE: Drop the swap chain frame here
         extracted_window.swap_chain_texture_view = None;
         extracted_window.size_changed = new_width != extracted_window.physical_width
@@ -172,16 +227,18 @@ fn extract_windows(
             );
             extracted_window.present_mode = window.present_mode;
         }
-    }
 
-    for closed_window in closed.read() {
-        extracted_windows.remove(&closed_window.window);
-        window_surfaces.remove(&closed_window.window);
-    }
-    for removed_window in removed.read() {
-        extracted_windows.remove(&removed_window);
-        window_surfaces.remove(&removed_window);
+        // The surface format depends on the HDR output, so the surface is created again.
+        if window.hdr_output != extracted_window.hdr_output {
+            debug!(
+                "Window HDR output changed from {:?} to {:?}",
+                extracted_window.hdr_output, window.hdr_output
+            );
+            extracted_window.hdr_output = window.hdr_output;
+            window_surfaces.remove(&entity);
+        }
     }
+
     // This lock will never block because `callbacks` is `pub(crate)` and this is the singular callsite where it's locked.
     // Even if a user had multiple copies of this system, since the system has a mutable resource access the two systems would never run
     // at the same time
@@ -461,11 +518,9 @@ pub fn create_surfaces(
                 };
                 let caps = surface.get_capabilities(&render_adapter);
                 let formats = caps.formats;
-                // For future HDR output support, we'll need to request a format that supports HDR,
-                // but as of wgpu 0.15 that is not yet supported.
                 // Prefer sRGB formats for surfaces, but fall back to first available format if no sRGB formats are available.
                 let mut format = *formats.first().expect("No supported formats for surface");
-                for available_format in formats {
+                for &available_format in &formats {
                     // Rgba8UnormSrgb and Bgra8UnormSrgb and the only sRGB formats wgpu exposes that we can use for surfaces.
                     if available_format == TextureFormat::Rgba8UnormSrgb
                         || available_format == TextureFormat::Bgra8UnormSrgb
@@ -474,6 +529,18 @@ pub fn create_surfaces(
                         break;
                     }
                 }
+                // A floating point surface is composited as scRGB where HDR output is supported.
+                if window.hdr_output.is_hdr() {
+                    if formats.contains(&HDR_SURFACE_FORMAT) {
+                        format = HDR_SURFACE_FORMAT;
+                    } else {
+                        warn!(
+                            "HDR output was requested for window {:?}, but its surface doesn't \
+                            support {:?}. Falling back to SDR output.",
+                            window.entity, HDR_SURFACE_FORMAT
+                        );
+                    }
+                }
 
                 let configuration = wgpu::SurfaceConfiguration {
                     format,
@@ -505,7 +572,7 @@ pub fn create_surfaces(
                         }
                         CompositeAlphaMode::Inherit => wgpu::CompositeAlphaMode::Inherit,
                     },
-                    view_formats: if !format.is_srgb() {
+                    view_formats: if format.add_srgb_suffix() != format {
                         vec![format.add_srgb_suffix()]
                     } else {
                         vec![]
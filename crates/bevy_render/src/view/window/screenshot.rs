@@ -1,42 +1,56 @@
-use std::{borrow::Cow, path::Path, sync::PoisonError};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+    sync::PoisonError,
+};
 
 use bevy_app::Plugin;
-use bevy_asset::{load_internal_asset, Handle};
+use bevy_asset::{load_internal_asset, AssetId, Handle};
 use bevy_ecs::{entity::EntityHashMap, prelude::*};
 use bevy_tasks::AsyncComputeTaskPool;
-use bevy_utils::tracing::{error, info, info_span};
+use bevy_utils::{
+    tracing::{error, info, info_span},
+    HashMap,
+};
 use std::sync::Mutex;
 use thiserror::Error;
 use wgpu::{
-    CommandEncoder, Extent3d, ImageDataLayout, TextureFormat, COPY_BYTES_PER_ROW_ALIGNMENT,
+    BufferUsages, CommandEncoder, Extent3d, ImageDataLayout, TextureFormat, TextureUsages,
+    COPY_BYTES_PER_ROW_ALIGNMENT,
 };
 
 use crate::{
     prelude::{Image, Shader},
-    render_asset::RenderAssetUsages,
+    render_asset::{RenderAssetUsages, RenderAssets},
     render_resource::{
         binding_types::texture_2d, BindGroup, BindGroupLayout, BindGroupLayoutEntries, Buffer,
         CachedRenderPipelineId, FragmentState, PipelineCache, RenderPipelineDescriptor,
         SpecializedRenderPipeline, SpecializedRenderPipelines, Texture, VertexState,
     },
     renderer::RenderDevice,
-    texture::TextureFormatPixelInfo,
-    RenderApp,
+    texture::{GpuImage, TextureFormatPixelInfo},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
 
 use super::ExtractedWindows;
 
 pub type ScreenshotFn = Box<dyn FnOnce(Image) + Send + Sync>;
 
-/// A resource which allows for taking screenshots of the window.
+/// A resource which allows for taking screenshots of windows and of images that cameras render to.
+///
+/// Capturing an [`Image`] works without any window, which makes it possible to render and read
+/// back frames headlessly, e.g. for golden-image tests or generating thumbnails on a server.
+/// The image must have been created with [`TextureUsages::COPY_SRC`], which
+/// [`Image::new_render_target`] does.
 #[derive(Resource, Default)]
 pub struct ScreenshotManager {
     // this is in a mutex to enable extraction with only an immutable reference
     pub(crate) callbacks: Mutex<EntityHashMap<ScreenshotFn>>,
+    pub(crate) image_callbacks: Mutex<HashMap<AssetId<Image>, ScreenshotFn>>,
 }
 
 #[derive(Error, Debug)]
-#[error("A screenshot for this window has already been requested.")]
+#[error("A screenshot for this target has already been requested.")]
 pub struct ScreenshotAlreadyRequestedError;
 
 impl ScreenshotManager {
@@ -64,59 +78,89 @@ impl ScreenshotManager {
         window: Entity,
         path: impl AsRef<Path>,
     ) -> Result<(), ScreenshotAlreadyRequestedError> {
-        let path = path.as_ref().to_owned();
-        self.take_screenshot(window, move |img| match img.try_into_dynamic() {
-            Ok(dyn_img) => match image::ImageFormat::from_path(&path) {
-                Ok(format) => {
-                    // discard the alpha channel which stores brightness values when HDR is enabled to make sure
-                    // the screenshot looks right
-                    let img = dyn_img.to_rgb8();
-                    #[cfg(not(target_arch = "wasm32"))]
-                    match img.save_with_format(&path, format) {
+        self.take_screenshot(window, save_to_disk(path.as_ref().to_owned()))
+    }
+
+    /// Signals the renderer to read back the contents of `image` once this frame has been rendered.
+    ///
+    /// The given callback will eventually be called on one of the [`AsyncComputeTaskPool`]s threads.
+    /// If the image hasn't been uploaded to the GPU yet, the capture happens on the first frame where it is.
+    pub fn take_image_screenshot(
+        &mut self,
+        image: impl Into<AssetId<Image>>,
+        callback: impl FnOnce(Image) + Send + Sync + 'static,
+    ) -> Result<(), ScreenshotAlreadyRequestedError> {
+        self.image_callbacks
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .try_insert(image.into(), Box::new(callback))
+            .map(|_| ())
+            .map_err(|_| ScreenshotAlreadyRequestedError)
+    }
+
+    /// Signals the renderer to read back the contents of `image` once this frame has been rendered.
+    ///
+    /// The screenshot will eventually be saved to the given path, and the format will be derived from the extension.
+    pub fn save_image_screenshot_to_disk(
+        &mut self,
+        image: impl Into<AssetId<Image>>,
+        path: impl AsRef<Path>,
+    ) -> Result<(), ScreenshotAlreadyRequestedError> {
+        self.take_image_screenshot(image, save_to_disk(path.as_ref().to_owned()))
+    }
+}
+
+fn save_to_disk(path: PathBuf) -> impl FnOnce(Image) + Send + Sync + 'static {
+    move |img| match img.try_into_dynamic() {
+        Ok(dyn_img) => match image::ImageFormat::from_path(&path) {
+            Ok(format) => {
+                // discard the alpha channel which stores brightness values when HDR is enabled to make sure
+                // the screenshot looks right
+                let img = dyn_img.to_rgb8();
+                #[cfg(not(target_arch = "wasm32"))]
+                match img.save_with_format(&path, format) {
+                    Ok(_) => info!("Screenshot saved to {}", path.display()),
+                    Err(e) => error!("Cannot save screenshot, IO error: {e}"),
+                }
+
+                #[cfg(target_arch = "wasm32")]
+                {
+                    match (|| {
+                        use image::EncodableLayout;
+                        use wasm_bindgen::{JsCast, JsValue};
+
+                        let mut image_buffer = std::io::Cursor::new(Vec::new());
+                        img.write_to(&mut image_buffer, format)
+                            .map_err(|e| JsValue::from_str(&format!("{e}")))?;
+                        // SAFETY: `image_buffer` only exist in this closure, and is not used after this line
+                        let parts = js_sys::Array::of1(&unsafe {
+                            js_sys::Uint8Array::view(image_buffer.into_inner().as_bytes()).into()
+                        });
+                        let blob = web_sys::Blob::new_with_u8_array_sequence(&parts)?;
+                        let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+                        let window = web_sys::window().unwrap();
+                        let document = window.document().unwrap();
+                        let link = document.create_element("a")?;
+                        link.set_attribute("href", &url)?;
+                        link.set_attribute(
+                            "download",
+                            path.file_name()
+                                .and_then(|filename| filename.to_str())
+                                .ok_or_else(|| JsValue::from_str("Invalid filename"))?,
+                        )?;
+                        let html_element = link.dyn_into::<web_sys::HtmlElement>()?;
+                        html_element.click();
+                        web_sys::Url::revoke_object_url(&url)?;
+                        Ok::<(), JsValue>(())
+                    })() {
                         Ok(_) => info!("Screenshot saved to {}", path.display()),
-                        Err(e) => error!("Cannot save screenshot, IO error: {e}"),
-                    }
-
-                    #[cfg(target_arch = "wasm32")]
-                    {
-                        match (|| {
-                            use image::EncodableLayout;
-                            use wasm_bindgen::{JsCast, JsValue};
-
-                            let mut image_buffer = std::io::Cursor::new(Vec::new());
-                            img.write_to(&mut image_buffer, format)
-                                .map_err(|e| JsValue::from_str(&format!("{e}")))?;
-                            // SAFETY: `image_buffer` only exist in this closure, and is not used after this line
-                            let parts = js_sys::Array::of1(&unsafe {
-                                js_sys::Uint8Array::view(image_buffer.into_inner().as_bytes())
-                                    .into()
-                            });
-                            let blob = web_sys::Blob::new_with_u8_array_sequence(&parts)?;
-                            let url = web_sys::Url::create_object_url_with_blob(&blob)?;
-                            let window = web_sys::window().unwrap();
-                            let document = window.document().unwrap();
-                            let link = document.create_element("a")?;
-                            link.set_attribute("href", &url)?;
-                            link.set_attribute(
-                                "download",
-                                path.file_name()
-                                    .and_then(|filename| filename.to_str())
-                                    .ok_or_else(|| JsValue::from_str("Invalid filename"))?,
-                            )?;
-                            let html_element = link.dyn_into::<web_sys::HtmlElement>()?;
-                            html_element.click();
-                            web_sys::Url::revoke_object_url(&url)?;
-                            Ok::<(), JsValue>(())
-                        })() {
-                            Ok(_) => info!("Screenshot saved to {}", path.display()),
-                            Err(e) => error!("Cannot save screenshot, error: {e:?}"),
-                        };
-                    }
+                        Err(e) => error!("Cannot save screenshot, error: {e:?}"),
+                    };
                 }
-                Err(e) => error!("Cannot save screenshot, requested format not recognized: {e}"),
-            },
-            Err(e) => error!("Cannot save screenshot, screen format cannot be understood: {e}"),
-        })
+            }
+            Err(e) => error!("Cannot save screenshot, requested format not recognized: {e}"),
+        },
+        Err(e) => error!("Cannot save screenshot, screen format cannot be understood: {e}"),
     }
 }
 
@@ -138,7 +182,14 @@ impl Plugin for ScreenshotPlugin {
 
     fn finish(&self, app: &mut bevy_app::App) {
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
-            render_app.init_resource::<SpecializedRenderPipelines<ScreenshotToScreenPipeline>>();
+            render_app
+                .init_resource::<SpecializedRenderPipelines<ScreenshotToScreenPipeline>>()
+                .init_resource::<ImageCaptures>()
+                .add_systems(ExtractSchedule, extract_image_captures)
+                .add_systems(
+                    Render,
+                    prepare_image_captures.in_set(RenderSet::PrepareResources),
+                );
         }
     }
 }
@@ -226,6 +277,71 @@ pub struct ScreenshotPreparedState {
     pub pipeline_id: CachedRenderPipelineId,
 }
 
+/// Pending reads of images that were requested with [`ScreenshotManager::take_image_screenshot`].
+#[derive(Resource, Default)]
+pub(crate) struct ImageCaptures(Vec<ImageCapture>);
+
+struct ImageCapture {
+    image: AssetId<Image>,
+    func: ScreenshotFn,
+    /// The buffer the image is copied into, along with the size and format of the image.
+    /// This is `None` until the image is available on the GPU.
+    memory: Option<(Buffer, u32, u32, TextureFormat)>,
+}
+
+fn extract_image_captures(
+    mut image_captures: ResMut<ImageCaptures>,
+    screenshot_manager: Extract<Res<ScreenshotManager>>,
+) {
+    // Like the window callbacks, this lock never blocks as this is the only place it's taken.
+    image_captures.0.extend(
+        screenshot_manager
+            .image_callbacks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain()
+            .map(|(image, func)| ImageCapture {
+                image,
+                func,
+                memory: None,
+            }),
+    );
+}
+
+fn prepare_image_captures(
+    mut image_captures: ResMut<ImageCaptures>,
+    images: Res<RenderAssets<GpuImage>>,
+    render_device: Res<RenderDevice>,
+) {
+    image_captures.0.retain_mut(|capture| {
+        if capture.memory.is_some() {
+            return true;
+        }
+        let Some(gpu_image) = images.get(capture.image) else {
+            // Not uploaded yet, try again next frame.
+            return true;
+        };
+        if !gpu_image.texture.usage().contains(TextureUsages::COPY_SRC) {
+            error!(
+                "Cannot take a screenshot of image {:?}, it wasn't created with `TextureUsages::COPY_SRC`",
+                capture.image
+            );
+            return false;
+        }
+
+        let (width, height) = (gpu_image.size.x, gpu_image.size.y);
+        let buffer = render_device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("image-screenshot-transfer-buffer"),
+            size: get_aligned_size(width, height, gpu_image.texture_format.pixel_size() as u32)
+                as u64,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        capture.memory = Some((buffer, width, height, gpu_image.texture_format));
+        true
+    });
+}
+
 pub(crate) fn submit_screenshot_commands(world: &World, encoder: &mut CommandEncoder) {
     let windows = world.resource::<ExtractedWindows>();
     let pipelines = world.resource::<PipelineCache>();
@@ -275,6 +391,27 @@ pub(crate) fn submit_screenshot_commands(world: &World, encoder: &mut CommandEnc
             }
         }
     }
+
+    let images = world.resource::<RenderAssets<GpuImage>>();
+    for capture in &world.resource::<ImageCaptures>().0 {
+        let (Some((buffer, width, height, texture_format)), Some(gpu_image)) =
+            (&capture.memory, images.get(capture.image))
+        else {
+            continue;
+        };
+        encoder.copy_texture_to_buffer(
+            gpu_image.texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer,
+                layout: layout_data(*width, *height, *texture_format),
+            },
+            Extent3d {
+                width: *width,
+                height: *height,
+                ..Default::default()
+            },
+        );
+    }
 }
 
 pub(crate) fn collect_screenshots(world: &mut World) {
@@ -283,63 +420,86 @@ pub(crate) fn collect_screenshots(world: &mut World) {
     let mut windows = world.resource_mut::<ExtractedWindows>();
     for window in windows.values_mut() {
         if let Some(screenshot_func) = window.screenshot_func.take() {
-            let width = window.physical_width;
-            let height = window.physical_height;
-            let texture_format = window.swap_chain_texture_format.unwrap();
-            let pixel_size = texture_format.pixel_size();
             let ScreenshotPreparedState { buffer, .. } = window.screenshot_memory.take().unwrap();
+            read_back(
+                buffer,
+                window.physical_width,
+                window.physical_height,
+                window.swap_chain_texture_format.unwrap(),
+                screenshot_func,
+            );
+        }
+    }
 
-            let finish = async move {
-                let (tx, rx) = async_channel::bounded(1);
-                let buffer_slice = buffer.slice(..);
-                // The polling for this map call is done every frame when the command queue is submitted.
-                buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
-                    let err = result.err();
-                    if err.is_some() {
-                        panic!("{}", err.unwrap().to_string());
-                    }
-                    tx.try_send(()).unwrap();
-                });
-                rx.recv().await.unwrap();
-                let data = buffer_slice.get_mapped_range();
-                // we immediately move the data to CPU memory to avoid holding the mapped view for long
-                let mut result = Vec::from(&*data);
-                drop(data);
-                drop(buffer);
-
-                if result.len() != ((width * height) as usize * pixel_size) {
-                    // Our buffer has been padded because we needed to align to a multiple of 256.
-                    // We remove this padding here
-                    let initial_row_bytes = width as usize * pixel_size;
-                    let buffered_row_bytes = align_byte_size(width * pixel_size as u32) as usize;
-
-                    let mut take_offset = buffered_row_bytes;
-                    let mut place_offset = initial_row_bytes;
-                    for _ in 1..height {
-                        result.copy_within(
-                            take_offset..take_offset + buffered_row_bytes,
-                            place_offset,
-                        );
-                        take_offset += buffered_row_bytes;
-                        place_offset += initial_row_bytes;
-                    }
-                    result.truncate(initial_row_bytes * height as usize);
-                }
+    let mut image_captures = world.resource_mut::<ImageCaptures>();
+    // Captures without memory are still waiting for their image and stay queued.
+    let (ready, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut image_captures.0)
+        .into_iter()
+        .partition(|capture| capture.memory.is_some());
+    image_captures.0 = pending;
+    for capture in ready {
+        let (buffer, width, height, texture_format) = capture.memory.unwrap();
+        read_back(buffer, width, height, texture_format, capture.func);
+    }
+}
 
-                screenshot_func(Image::new(
-                    Extent3d {
-                        width,
-                        height,
-                        depth_or_array_layers: 1,
-                    },
-                    wgpu::TextureDimension::D2,
-                    result,
-                    texture_format,
-                    RenderAssetUsages::RENDER_WORLD,
-                ));
-            };
-
-            AsyncComputeTaskPool::get().spawn(finish).detach();
+/// Maps `buffer` once the GPU is done copying into it and hands its contents to `screenshot_func`
+/// as an [`Image`].
+fn read_back(
+    buffer: Buffer,
+    width: u32,
+    height: u32,
+    texture_format: TextureFormat,
+    screenshot_func: ScreenshotFn,
+) {
+    let pixel_size = texture_format.pixel_size();
+
+    let finish = async move {
+        let (tx, rx) = async_channel::bounded(1);
+        let buffer_slice = buffer.slice(..);
+        // The polling for this map call is done every frame when the command queue is submitted.
+        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+            let err = result.err();
+            if err.is_some() {
+                panic!("{}", err.unwrap().to_string());
+            }
+            tx.try_send(()).unwrap();
+        });
+        rx.recv().await.unwrap();
+        let data = buffer_slice.get_mapped_range();
+        // we immediately move the data to CPU memory to avoid holding the mapped view for long
+        let mut result = Vec::from(&*data);
+        drop(data);
+        drop(buffer);
+
+        if result.len() != ((width * height) as usize * pixel_size) {
+            // Our buffer has been padded because we needed to align to a multiple of 256.
+            // We remove this padding here
+            let initial_row_bytes = width as usize * pixel_size;
+            let buffered_row_bytes = align_byte_size(width * pixel_size as u32) as usize;
+
+            let mut take_offset = buffered_row_bytes;
+            let mut place_offset = initial_row_bytes;
+            for _ in 1..height {
+                result.copy_within(take_offset..take_offset + buffered_row_bytes, place_offset);
+                take_offset += buffered_row_bytes;
+                place_offset += initial_row_bytes;
+            }
+            result.truncate(initial_row_bytes * height as usize);
         }
-    }
+
+        screenshot_func(Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            wgpu::TextureDimension::D2,
+            result,
+            texture_format,
+            RenderAssetUsages::RENDER_WORLD,
+        ));
+    };
+
+    AsyncComputeTaskPool::get().spawn(finish).detach();
 }
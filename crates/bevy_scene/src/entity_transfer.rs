@@ -0,0 +1,192 @@
+use crate::{DynamicSceneBuilder, SceneSpawnError};
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
+    reflect::AppTypeRegistry,
+    world::World,
+};
+use bevy_hierarchy::{BuildWorldChildren, Children, DespawnRecursiveExt, Parent};
+
+/// Controls how [`TransferEntityExt`] treats the hierarchy below the transferred entity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransferHierarchy {
+    /// Only the given entity is transferred.
+    /// The copy in the destination world has neither a [`Parent`] nor [`Children`].
+    #[default]
+    EntityOnly,
+    /// The given entity and all of its descendants are transferred,
+    /// keeping their [`Parent`] and [`Children`] relationships intact.
+    Recursive,
+}
+
+/// Moves or copies entities from one [`World`] to another.
+///
+/// Components are copied through reflection, so every component that should be transferred
+/// must be registered in the source world's [`AppTypeRegistry`] and reflect `Component`.
+/// Transferring from a world without an [`AppTypeRegistry`] fails with
+/// [`SceneSpawnError::MissingTypeRegistry`] and leaves both worlds untouched.
+/// Components that hold [`Entity`] references and reflect `MapEntities` are remapped
+/// to the newly spawned entities in the destination world.
+///
+/// The transferred root never keeps its [`Parent`]: the parent is not part of the destination world.
+pub trait TransferEntityExt {
+    /// Copies `entity` into `destination`, returning the id of the copy.
+    fn copy_entity_to(
+        &self,
+        entity: Entity,
+        destination: &mut World,
+        hierarchy: TransferHierarchy,
+    ) -> Result<Entity, SceneSpawnError>;
+
+    /// Copies `entity` into `destination` and despawns it from this world, returning its new id.
+    ///
+    /// If the entity is moved with [`TransferHierarchy::EntityOnly`], its children stay
+    /// behind and are detached from it.
+    fn move_entity_to(
+        &mut self,
+        entity: Entity,
+        destination: &mut World,
+        hierarchy: TransferHierarchy,
+    ) -> Result<Entity, SceneSpawnError>;
+}
+
+impl TransferEntityExt for World {
+    fn copy_entity_to(
+        &self,
+        entity: Entity,
+        destination: &mut World,
+        hierarchy: TransferHierarchy,
+    ) -> Result<Entity, SceneSpawnError> {
+        let Some(type_registry) = self.get_resource::<AppTypeRegistry>().cloned() else {
+            return Err(SceneSpawnError::MissingTypeRegistry);
+        };
+
+        let mut entities = vec![entity];
+        if hierarchy == TransferHierarchy::Recursive {
+            collect_descendants(self, entity, &mut entities);
+        }
+
+        let scene = DynamicSceneBuilder::from_world(self)
+            .extract_entities(entities.into_iter())
+            .build();
+
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world_with(destination, &mut entity_map, &type_registry)?;
+
+        let root = entity_map[&entity];
+        let mut root_mut = destination.entity_mut(root);
+        // The original parent was not transferred, so this only points at a placeholder entity.
+        root_mut.remove::<Parent>();
+        if hierarchy == TransferHierarchy::EntityOnly {
+            root_mut.remove::<Children>();
+        }
+        Ok(root)
+    }
+
+    fn move_entity_to(
+        &mut self,
+        entity: Entity,
+        destination: &mut World,
+        hierarchy: TransferHierarchy,
+    ) -> Result<Entity, SceneSpawnError> {
+        let moved = self.copy_entity_to(entity, destination, hierarchy)?;
+
+        let mut entity_mut = self.entity_mut(entity);
+        entity_mut.remove_parent();
+        match hierarchy {
+            TransferHierarchy::EntityOnly => {
+                entity_mut.clear_children();
+                entity_mut.despawn();
+            }
+            TransferHierarchy::Recursive => entity_mut.despawn_recursive(),
+        }
+        Ok(moved)
+    }
+}
+
+fn collect_descendants(world: &World, entity: Entity, entities: &mut Vec<Entity>) {
+    if let Some(children) = world.get::<Children>(entity) {
+        for &child in children.iter() {
+            entities.push(child);
+            collect_descendants(world, child, entities);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{component::Component, reflect::ReflectComponent};
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    fn source_world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Parent>();
+            registry.register::<Children>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    #[test]
+    fn copy_single_entity() {
+        let mut source = source_world();
+        let mut destination = World::new();
+
+        let parent = source.spawn(Health(1)).id();
+        let entity = source.spawn(Health(2)).set_parent(parent).id();
+        source.spawn(Health(3)).set_parent(entity);
+
+        let copy = source
+            .copy_entity_to(entity, &mut destination, TransferHierarchy::EntityOnly)
+            .unwrap();
+
+        assert_eq!(destination.get::<Health>(copy), Some(&Health(2)));
+        assert!(destination.get::<Parent>(copy).is_none());
+        assert!(destination.get::<Children>(copy).is_none());
+        assert_eq!(destination.entities().len(), 1);
+        assert!(source.get_entity(entity).is_some());
+    }
+
+    #[test]
+    fn missing_type_registry() {
+        let mut source = World::new();
+        let mut destination = World::new();
+        let entity = source.spawn(Health(1)).id();
+
+        let result = source.move_entity_to(entity, &mut destination, TransferHierarchy::Recursive);
+
+        assert!(matches!(result, Err(SceneSpawnError::MissingTypeRegistry)));
+        assert!(source.get_entity(entity).is_some());
+        assert_eq!(destination.entities().len(), 0);
+    }
+
+    #[test]
+    fn move_hierarchy() {
+        let mut source = source_world();
+        let mut destination = World::new();
+
+        let root = source.spawn(Health(1)).id();
+        let child = source.spawn(Health(2)).set_parent(root).id();
+
+        let moved = source
+            .move_entity_to(root, &mut destination, TransferHierarchy::Recursive)
+            .unwrap();
+
+        assert!(source.get_entity(root).is_none());
+        assert!(source.get_entity(child).is_none());
+
+        let children = destination.get::<Children>(moved).unwrap();
+        assert_eq!(children.len(), 1);
+        let moved_child = children[0];
+        assert_eq!(destination.get::<Health>(moved_child), Some(&Health(2)));
+        assert_eq!(destination.get::<Parent>(moved_child).unwrap().get(), moved);
+    }
+}
@@ -14,6 +14,7 @@
 mod bundle;
 mod dynamic_scene;
 mod dynamic_scene_builder;
+mod entity_transfer;
 mod scene;
 mod scene_filter;
 mod scene_loader;
@@ -29,6 +30,7 @@ use bevy_ecs::schedule::IntoSystemConfigs;
 pub use bundle::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
+pub use entity_transfer::*;
 pub use scene::*;
 pub use scene_filter::*;
 pub use scene_loader::*;
@@ -123,6 +123,9 @@ pub enum SceneSpawnError {
         /// Id of the non-existent scene.
         id: AssetId<Scene>,
     },
+    /// The world entities are copied from has no [`AppTypeRegistry`](bevy_ecs::reflect::AppTypeRegistry).
+    #[error("the source world has no `AppTypeRegistry`. consider inserting one to copy entities through reflection")]
+    MissingTypeRegistry,
 }
 
 impl SceneSpawner {
@@ -67,6 +67,7 @@ use crate::{time::Time, virt::Virtual};
 pub struct Fixed {
     timestep: Duration,
     overstep: Duration,
+    steps_this_update: u32,
 }
 
 impl Time<Fixed> {
@@ -203,8 +204,21 @@ impl Time<Fixed> {
         self.context().overstep.as_secs_f64() / self.context().timestep.as_secs_f64()
     }
 
+    /// Returns how many times the [`FixedMain`] schedule has run during the
+    /// current update.
+    ///
+    /// This is reset to zero at the start of every
+    /// [`run_fixed_main_schedule`], so after the fixed schedules have run it is
+    /// the number of fixed steps taken this frame.
+    #[inline]
+    pub fn steps_this_update(&self) -> u32 {
+        self.context().steps_this_update
+    }
+
     fn accumulate(&mut self, delta: Duration) {
-        self.context_mut().overstep += delta;
+        let context = self.context_mut();
+        context.overstep += delta;
+        context.steps_this_update = 0;
     }
 
     fn expend(&mut self) -> bool {
@@ -212,6 +226,7 @@ impl Time<Fixed> {
         if let Some(new_value) = self.context_mut().overstep.checked_sub(timestep) {
             // reduce accumulated and increase elapsed by period
             self.context_mut().overstep = new_value;
+            self.context_mut().steps_this_update += 1;
             self.advance_by(timestep);
             true
         } else {
@@ -226,6 +241,7 @@ impl Default for Fixed {
         Self {
             timestep: Time::<Fixed>::DEFAULT_TIMESTEP,
             overstep: Duration::ZERO,
+            steps_this_update: 0,
         }
     }
 }
@@ -300,6 +316,7 @@ mod test {
 
         assert!(time.expend()); // true
 
+        assert_eq!(time.steps_this_update(), 1);
         assert_eq!(time.delta(), Duration::from_secs(2));
         assert_eq!(time.elapsed(), Duration::from_secs(2));
         assert_eq!(time.overstep(), Duration::ZERO);
@@ -152,6 +152,7 @@ impl Plugin for WindowPlugin {
 
         // Register window descriptor and related types
         app.register_type::<Window>()
+            .register_type::<WindowHdrOutput>()
             .register_type::<PrimaryWindow>();
     }
 }
@@ -155,6 +155,10 @@ pub struct Window {
     pub name: Option<String>,
     /// How the alpha channel of textures should be handled while compositing.
     pub composite_alpha_mode: CompositeAlphaMode,
+    /// Whether the window should output high dynamic range (HDR) images to the display.
+    ///
+    /// Falls back to standard dynamic range if the platform or the display doesn't support it.
+    pub hdr_output: WindowHdrOutput,
     /// The limits of the window's logical size
     /// (found in its [`resolution`](WindowResolution)) when resizing.
     pub resize_constraints: WindowResizeConstraints,
@@ -284,6 +288,7 @@ impl Default for Window {
             resolution: Default::default(),
             internal: Default::default(),
             composite_alpha_mode: Default::default(),
+            hdr_output: Default::default(),
             resize_constraints: Default::default(),
             ime_enabled: Default::default(),
             ime_position: Default::default(),
@@ -991,6 +996,57 @@ pub enum PresentMode {
     Mailbox = 5,
 }
 
+/// The dynamic range of the images a [`Window`] outputs to the display.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq, Default)]
+pub enum WindowHdrOutput {
+    /// Standard dynamic range output, encoded in sRGB.
+    #[default]
+    Sdr,
+    /// High dynamic range output in extended linear sRGB (scRGB), with a 16 bit float surface.
+    ///
+    /// The output of every camera drawn to the window, UI included, is scaled when it is written
+    /// to the window so that white is displayed at `paper_white` nits. Only cameras with
+    /// `Camera::hdr` enabled make use of the extended range, as values the tonemapper leaves
+    /// above `1.0` are displayed brighter.
+    ///
+    /// Supported where the compositor accepts floating point surfaces, such as on Windows with
+    /// HDR enabled and on macOS with an EDR capable display.
+    ///
+    /// HDR10 output and HDR metadata aren't supported, as the graphics backend doesn't expose
+    /// control over the color space and metadata of surfaces.
+    ScRgb {
+        /// The brightness, in nits, of SDR white.
+        ///
+        /// A value of `80.0` matches the scRGB reference white. Values around `200.0` match the
+        /// brightness of SDR content on most HDR displays.
+        paper_white: f32,
+    },
+}
+
+impl WindowHdrOutput {
+    /// The brightness of the scRGB reference white, in nits.
+    pub const SCRGB_REFERENCE_WHITE: f32 = 80.0;
+
+    /// Returns whether HDR output is requested.
+    pub fn is_hdr(&self) -> bool {
+        !matches!(self, WindowHdrOutput::Sdr)
+    }
+
+    /// The scale applied to SDR white so it is displayed at the requested brightness.
+    pub fn paper_white_scale(&self) -> f32 {
+        match self {
+            WindowHdrOutput::Sdr => 1.0,
+            WindowHdrOutput::ScRgb { paper_white } => paper_white / Self::SCRGB_REFERENCE_WHITE,
+        }
+    }
+}
+
 /// Specifies how the alpha channel of the textures should be handled during compositing, for a [`Window`].
 #[repr(C)]
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
@@ -131,6 +131,8 @@ fn update(
 
     let (camera_entity, ssao_settings, temporal_jitter) = camera.single();
 
+    let half_resolution = ssao_settings.is_some_and(|s| s.half_resolution);
+
     let mut commands = commands.entity(camera_entity);
     if keycode.just_pressed(KeyCode::Digit1) {
         commands.remove::<ScreenSpaceAmbientOcclusionSettings>();
@@ -138,23 +140,35 @@ fn update(
     if keycode.just_pressed(KeyCode::Digit2) {
         commands.insert(ScreenSpaceAmbientOcclusionSettings {
             quality_level: ScreenSpaceAmbientOcclusionQualityLevel::Low,
+            half_resolution,
         });
     }
     if keycode.just_pressed(KeyCode::Digit3) {
         commands.insert(ScreenSpaceAmbientOcclusionSettings {
             quality_level: ScreenSpaceAmbientOcclusionQualityLevel::Medium,
+            half_resolution,
         });
     }
     if keycode.just_pressed(KeyCode::Digit4) {
         commands.insert(ScreenSpaceAmbientOcclusionSettings {
             quality_level: ScreenSpaceAmbientOcclusionQualityLevel::High,
+            half_resolution,
         });
     }
     if keycode.just_pressed(KeyCode::Digit5) {
         commands.insert(ScreenSpaceAmbientOcclusionSettings {
             quality_level: ScreenSpaceAmbientOcclusionQualityLevel::Ultra,
+            half_resolution,
         });
     }
+    if keycode.just_pressed(KeyCode::KeyH) {
+        if let Some(ssao_settings) = ssao_settings {
+            commands.insert(ScreenSpaceAmbientOcclusionSettings {
+                half_resolution: !half_resolution,
+                ..ssao_settings.clone()
+            });
+        }
+    }
     if keycode.just_pressed(KeyCode::Space) {
         if temporal_jitter.is_some() {
             commands.remove::<TemporalJitter>();
@@ -183,6 +197,12 @@ fn update(
     text.push_str(&format!("(4) {h}High{h}\n"));
     text.push_str(&format!("(5) {u}Ultra{u}\n\n"));
 
+    text.push_str("Resolution:\n");
+    text.push_str(match half_resolution {
+        true => "(H) Half\n\n",
+        false => "(H) Full\n\n",
+    });
+
     text.push_str("Temporal Antialiasing:\n");
     text.push_str(match temporal_jitter {
         Some(_) => "(Space) Enabled",
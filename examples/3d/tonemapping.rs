@@ -626,6 +626,7 @@ impl PerMethodSettings {
                 gamma: 1.0,
                 pre_saturation: 1.1,
                 post_saturation: 1.1,
+                ..default()
             },
             _ => ColorGrading::default(),
         }
@@ -0,0 +1,118 @@
+//! Renders a scene to an image without opening a window, then saves it to disk and exits.
+//!
+//! This is the starting point for generating thumbnails on a server, or for golden-image
+//! tests that run in CI without a display.
+
+use bevy::{
+    app::{AppExit, ScheduleRunnerPlugin},
+    prelude::*,
+    render::{
+        camera::RenderTarget, render_resource::TextureFormat, view::screenshot::ScreenshotManager,
+    },
+    utils::Duration,
+    window::ExitCondition,
+    winit::WinitPlugin,
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Number of frames to render before capturing, so that assets are loaded
+/// and pipelines are compiled.
+const FRAMES_BEFORE_CAPTURE: u32 = 30;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    // Without windows, the default exit condition would quit right away.
+                    exit_condition: ExitCondition::DontExit,
+                    ..default()
+                })
+                // Winit needs a display, and isn't required to drive the app.
+                .disable::<WinitPlugin>(),
+            ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(1.0 / 60.0)),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, capture)
+        .run();
+}
+
+#[derive(Resource)]
+struct RenderTargetImage(Handle<Image>);
+
+fn setup(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let image = images.add(Image::new_render_target(
+        512,
+        512,
+        TextureFormat::Rgba8UnormSrgb,
+    ));
+    commands.insert_resource(RenderTargetImage(image.clone()));
+
+    commands.spawn(Camera3dBundle {
+        camera: Camera {
+            target: RenderTarget::Image(image),
+            ..default()
+        },
+        transform: Transform::from_xyz(-2.5, 4.5, 9.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Circle::new(4.0)),
+        material: materials.add(Color::WHITE),
+        transform: Transform::from_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+        ..default()
+    });
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        material: materials.add(Color::srgb_u8(124, 144, 255)),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..default()
+    });
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+}
+
+fn capture(
+    mut frame: Local<u32>,
+    saved: Local<Arc<AtomicBool>>,
+    image: Res<RenderTargetImage>,
+    mut screenshot_manager: ResMut<ScreenshotManager>,
+    mut exit: EventWriter<AppExit>,
+) {
+    *frame += 1;
+    if *frame == FRAMES_BEFORE_CAPTURE {
+        let saved = saved.clone();
+        screenshot_manager
+            .take_image_screenshot(&image.0, move |image| {
+                match image.try_into_dynamic() {
+                    Ok(image) => match image.to_rgba8().save("headless_renderer.png") {
+                        Ok(()) => info!("Saved headless_renderer.png"),
+                        Err(e) => error!("Failed to save image: {e}"),
+                    },
+                    Err(e) => error!("Failed to convert image: {e}"),
+                }
+                saved.store(true, Ordering::Release);
+            })
+            .unwrap();
+    }
+
+    // The callback runs on another thread a few frames later.
+    if saved.load(Ordering::Acquire) {
+        exit.send(AppExit);
+    }
+}
@@ -1,6 +1,12 @@
 //! Uses two windows to visualize a 3D model from different angles.
+//!
+//! Each window has its own surface, so they can use different present modes.
 
-use bevy::{prelude::*, render::camera::RenderTarget, window::WindowRef};
+use bevy::{
+    prelude::*,
+    render::camera::RenderTarget,
+    window::{PresentMode, WindowRef},
+};
 
 fn main() {
     App::new()
@@ -33,6 +39,8 @@ fn setup_scene(mut commands: Commands, asset_server: Res<AssetServer>) {
     let second_window = commands
         .spawn(Window {
             title: "Second window".to_owned(),
+            // Unlike the primary window, this one doesn't wait for vsync
+            present_mode: PresentMode::AutoNoVsync,
             ..default()
         })
         .id();